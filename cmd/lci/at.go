@@ -0,0 +1,84 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// parsePosition splits a "<file>:<offset>" argument at its last colon, so file paths
+// containing colons still parse
+func parsePosition(arg string) (string, int, error) {
+	i := strings.LastIndex(arg, ":")
+	if i <= 0 {
+		return "", 0, fmt.Errorf("expected <file>:<offset>, got %q", arg)
+	}
+	offset, err := strconv.Atoi(arg[i+1:])
+	if err != nil || offset < 0 {
+		return "", 0, fmt.Errorf("invalid byte offset %q in %q", arg[i+1:], arg)
+	}
+	return arg[:i], offset, nil
+}
+
+// atCommand prints the innermost symbol defined at a byte offset of a file
+func atCommand(c *cli.Context) error {
+	if c.NArg() < 1 {
+		return errors.New("usage: lci at <file>:<offset>")
+	}
+	file, offset, err := parsePosition(c.Args().First())
+	if err != nil {
+		return err
+	}
+	format := c.String("format")
+	if format != browseFormatText && format != browseFormatJSON {
+		return fmt.Errorf("unknown output format %q (expected %s or %s)", format, browseFormatText, browseFormatJSON)
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+
+	client, err := ensureServerRunning(c, cfg)
+	if err != nil {
+		return fmt.Errorf("failed to connect to index server: %w", err)
+	}
+
+	result, err := client.SymbolAt(server.SymbolAtRequest{File: file, Offset: offset})
+	if err != nil {
+		return fmt.Errorf("lookup failed: %w", err)
+	}
+	result.File = pathutil.ToRelative(result.File, reportRoot(projectRoot))
+	if result.Symbol != nil {
+		result.Symbol.File = result.File
+	}
+
+	if format == browseFormatJSON {
+		encoder := json.NewEncoder(os.Stdout)
+		encoder.SetIndent("", "  ")
+		return encoder.Encode(result)
+	}
+	if result.Symbol == nil {
+		return fmt.Errorf("no symbol at %s:%d", result.File, offset)
+	}
+	return writeSymbolAt(os.Stdout, result.Symbol)
+}
+
+// writeSymbolAt prints a symbol as "path:line:column kind Parent.name [start,end)"
+func writeSymbolAt(w io.Writer, sym *server.ListSymbolsEntry) error {
+	name := sym.Name
+	if sym.Parent != "" {
+		name = sym.Parent + "." + sym.Name
+	}
+	_, err := fmt.Fprintf(w, "%s:%d:%d %s %s [%d,%d)\n",
+		sym.File, sym.Line, sym.Column, sym.Type, name, sym.ByteSpan[0], sym.ByteSpan[1])
+	return err
+}
@@ -0,0 +1,36 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/server"
+)
+
+func TestParsePosition(t *testing.T) {
+	file, offset, err := parsePosition("src/lib.rs:1042")
+	require.NoError(t, err)
+	assert.Equal(t, "src/lib.rs", file)
+	assert.Equal(t, 1042, offset)
+
+	file, _, err = parsePosition(`C:\src\lib.rs:7`)
+	require.NoError(t, err)
+	assert.Equal(t, `C:\src\lib.rs`, file, "only the last colon separates the offset")
+
+	for _, bad := range []string{"src/lib.rs", ":12", "src/lib.rs:", "src/lib.rs:-1", "src/lib.rs:x"} {
+		_, _, err := parsePosition(bad)
+		assert.Error(t, err, bad)
+	}
+}
+
+func TestWriteSymbolAt(t *testing.T) {
+	var out bytes.Buffer
+	require.NoError(t, writeSymbolAt(&out, &server.ListSymbolsEntry{
+		Name: "bump", Type: "method", Parent: "Counter", File: "src/lib.rs",
+		Line: 6, Column: 5, ByteSpan: [2]int{52, 103},
+	}))
+	assert.Equal(t, "src/lib.rs:6:5 method Counter.bump [52,103)\n", out.String())
+}
@@ -0,0 +1,226 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"math"
+	"os"
+	"runtime"
+	"slices"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/urfave/cli/v2"
+)
+
+// defaultBenchIterations is how many times each query is timed unless --iterations is given
+const defaultBenchIterations = 20
+
+// benchMode is one search mode timed by `lci bench`: a fixed query set and how to run
+// a query in that mode, returning the number of hits
+type benchMode struct {
+	name    string
+	queries []string
+	run     func(engine *search.Engine, query string) (int, error)
+}
+
+// benchModes lists the timed modes in report order. The queries target identifiers
+// shared by the search-comparison fixtures so every mode has real work to do there.
+var benchModes = []benchMode{
+	{
+		name:    "substring",
+		queries: []string{"User", "authenticate", "password"},
+		run: func(engine *search.Engine, query string) (int, error) {
+			return len(engine.SearchWithOptions(query, nil, types.SearchOptions{})), nil
+		},
+	},
+	{
+		name:    "regex",
+		queries: []string{`[A-Z][a-z]+Service`, `user_?[Ii]d`, `(validate|authenticate)\w*`},
+		run: func(engine *search.Engine, query string) (int, error) {
+			results, err := engine.SearchRegex(query, types.SearchOptions{UseRegex: true})
+			return len(results), err
+		},
+	},
+	{
+		name:    "fuzzy",
+		queries: []string{"UserServce", "authentcate", "pasword"},
+		run: func(engine *search.Engine, query string) (int, error) {
+			return len(engine.SearchFuzzy(query, -1)), nil
+		},
+	},
+	{
+		name:    "fuzzy-subsequence",
+		queries: []string{"usv", "hur", "vt"},
+		run: func(engine *search.Engine, query string) (int, error) {
+			return len(engine.SearchWithOptions(query, nil, types.SearchOptions{Fuzzy: search.FuzzySubsequence, SmartCase: true})), nil
+		},
+	},
+	{
+		name:    "alias-expansion",
+		queries: []string{"usr", "cfg", "auth usr"},
+		run: func(engine *search.Engine, query string) (int, error) {
+			options := types.SearchOptions{Terms: true, SmartCase: true, Aliases: search.DefaultAliases()}
+			return len(engine.SearchWithOptions(query, nil, options)), nil
+		},
+	},
+	{
+		name:    "symbol",
+		queries: []string{"UserService", "authenticate", "ValidateToken"},
+		run: func(engine *search.Engine, query string) (int, error) {
+			return len(engine.SearchWithOptions(query, nil, types.SearchOptions{SymbolsOnly: true})), nil
+		},
+	},
+}
+
+// BenchResult summarizes the timed runs of one search mode
+type BenchResult struct {
+	Mode        string  `json:"mode"`
+	Queries     int     `json:"queries"`
+	Samples     int     `json:"samples"` // Timed query runs: queries × iterations
+	MinMs       float64 `json:"min_ms"`
+	MedianMs    float64 `json:"median_ms"`
+	P95Ms       float64 `json:"p95_ms"`
+	AllocsPerOp uint64  `json:"allocs_per_op"`
+	BytesPerOp  uint64  `json:"bytes_per_op"`
+	Hits        int     `json:"hits"` // Hits over one pass of the query set, to spot modes that find nothing
+}
+
+// BenchReport is the output of `lci bench`
+type BenchReport struct {
+	Root       string        `json:"root"`
+	Files      int           `json:"files"`
+	SampledOut int           `json:"sampled_out,omitempty"` // Files left out by --sample, so Files of Files+SampledOut were indexed
+	IndexMs    float64       `json:"index_ms"`
+	Iterations int           `json:"iterations"`
+	Modes      []BenchResult `json:"modes"`
+}
+
+// benchCommand indexes a directory once and times every search mode against it
+func benchCommand(c *cli.Context) error {
+	format := c.String("format")
+	if format != "" && format != searchFormatText && format != searchFormatJSON {
+		return fmt.Errorf("unknown output format %q (expected %s or %s)", format, searchFormatText, searchFormatJSON)
+	}
+	iterations := c.Int("iterations")
+	if iterations < 1 {
+		return fmt.Errorf("--iterations must be at least 1, got %d", iterations)
+	}
+	if dir := c.Args().First(); dir != "" {
+		if err := c.Set("root", dir); err != nil {
+			return fmt.Errorf("failed to set bench root: %w", err)
+		}
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return err
+	}
+	benchIndexer := indexing.NewMasterIndex(cfg)
+	defer benchIndexer.Close()
+
+	start := time.Now()
+	if err := benchIndexer.IndexDirectory(context.Background(), cfg.Project.Root); err != nil {
+		return fmt.Errorf("failed to index %s: %w", cfg.Project.Root, err)
+	}
+	report := BenchReport{
+		Root:       cfg.Project.Root,
+		Files:      benchIndexer.GetFileCount(),
+		SampledOut: benchIndexer.SampledOutFiles(),
+		IndexMs:    durationMs(time.Since(start)),
+		Iterations: iterations,
+	}
+
+	report.Modes, err = runBench(search.NewEngine(benchIndexer), benchModes, iterations)
+	if err != nil {
+		return err
+	}
+	if format == searchFormatJSON {
+		enc := json.NewEncoder(os.Stdout)
+		enc.SetIndent("", "  ")
+		return enc.Encode(report)
+	}
+	writeBenchTable(os.Stdout, report)
+	return nil
+}
+
+// runBench times iterations runs of every query of every mode. One untimed pass over
+// all queries comes first so lazily built index structures and caches are warm and
+// one-off setup cost does not land in the first mode's numbers.
+func runBench(engine *search.Engine, modes []benchMode, iterations int) ([]BenchResult, error) {
+	hits := make([]int, len(modes))
+	for i, m := range modes {
+		for _, q := range m.queries {
+			n, err := m.run(engine, q)
+			if err != nil {
+				return nil, fmt.Errorf("%s query %q: %w", m.name, q, err)
+			}
+			hits[i] += n
+		}
+	}
+
+	results := make([]BenchResult, len(modes))
+	for i, m := range modes {
+		var samples []time.Duration
+		var before, after runtime.MemStats
+		var mallocs, allocBytes uint64
+		for range iterations {
+			for _, q := range m.queries {
+				runtime.ReadMemStats(&before)
+				start := time.Now()
+				_, _ = m.run(engine, q) // Errors were reported by the warm-up pass
+				samples = append(samples, time.Since(start))
+				runtime.ReadMemStats(&after)
+				mallocs += after.Mallocs - before.Mallocs
+				allocBytes += after.TotalAlloc - before.TotalAlloc
+			}
+		}
+		results[i] = summarizeBench(m.name, len(m.queries), samples, mallocs, allocBytes)
+		results[i].Hits = hits[i]
+	}
+	return results, nil
+}
+
+// summarizeBench reduces timed samples to min, median and p95 latency and the mean
+// allocations per query run
+func summarizeBench(mode string, queries int, samples []time.Duration, mallocs, allocBytes uint64) BenchResult {
+	result := BenchResult{Mode: mode, Queries: queries, Samples: len(samples)}
+	if len(samples) == 0 {
+		return result
+	}
+	sorted := slices.Clone(samples)
+	slices.Sort(sorted)
+	result.MinMs = durationMs(sorted[0])
+	result.MedianMs = durationMs(percentileDuration(sorted, 50))
+	result.P95Ms = durationMs(percentileDuration(sorted, 95))
+	result.AllocsPerOp = mallocs / uint64(len(samples))
+	result.BytesPerOp = allocBytes / uint64(len(samples))
+	return result
+}
+
+// percentileDuration returns the nearest-rank p-th percentile of sorted samples
+func percentileDuration(sorted []time.Duration, p float64) time.Duration {
+	rank := int(math.Ceil(p / 100 * float64(len(sorted))))
+	return sorted[max(rank-1, 0)]
+}
+
+func durationMs(d time.Duration) float64 {
+	return float64(d.Microseconds()) / 1000.0
+}
+
+// writeBenchTable prints the report as an aligned table, one row per mode
+func writeBenchTable(w io.Writer, report BenchReport) {
+	if report.SampledOut > 0 {
+		fmt.Fprintf(w, "Sampled %d of %d files\n", report.Files, report.Files+report.SampledOut)
+	}
+	fmt.Fprintf(w, "Indexed %d files in %.1fms; %d timed runs per query\n\n", report.Files, report.IndexMs, report.Iterations)
+	fmt.Fprintf(w, "%-10s %7s %10s %10s %10s %10s %12s %6s\n", "MODE", "QUERIES", "MIN(ms)", "MEDIAN(ms)", "P95(ms)", "ALLOCS/OP", "BYTES/OP", "HITS")
+	for _, r := range report.Modes {
+		fmt.Fprintf(w, "%-10s %7d %10.3f %10.3f %10.3f %10d %12d %6d\n",
+			r.Mode, r.Queries, r.MinMs, r.MedianMs, r.P95Ms, r.AllocsPerOp, r.BytesPerOp, r.Hits)
+	}
+}
@@ -0,0 +1,57 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestSummarizeBench(t *testing.T) {
+	var samples []time.Duration
+	for i := 20; i >= 1; i-- {
+		samples = append(samples, time.Duration(i)*time.Millisecond)
+	}
+	r := summarizeBench("substring", 2, samples, 400, 8000)
+
+	assert.Equal(t, 20, r.Samples)
+	assert.Equal(t, 1.0, r.MinMs)
+	assert.Equal(t, 10.0, r.MedianMs)
+	assert.Equal(t, 19.0, r.P95Ms)
+	assert.Equal(t, uint64(20), r.AllocsPerOp)
+	assert.Equal(t, uint64(400), r.BytesPerOp)
+	assert.Equal(t, 20*time.Millisecond, samples[0], "samples are not reordered")
+}
+
+func TestRunBench(t *testing.T) {
+	calls := make(map[string]int)
+	modes := []benchMode{{
+		name:    "counting",
+		queries: []string{"a", "b"},
+		run: func(_ *search.Engine, query string) (int, error) {
+			calls[query]++
+			return 3, nil
+		},
+	}}
+
+	results, err := runBench(nil, modes, 5)
+	require.NoError(t, err)
+	require.Len(t, results, 1)
+	assert.Equal(t, map[string]int{"a": 6, "b": 6}, calls, "one warm-up run plus five timed runs per query")
+	assert.Equal(t, 10, results[0].Samples)
+	assert.Equal(t, 6, results[0].Hits, "hits come from a single pass")
+
+	var buf bytes.Buffer
+	writeBenchTable(&buf, BenchReport{Files: 1, Iterations: 5, Modes: results})
+	assert.Contains(t, buf.String(), "MEDIAN(ms)")
+	assert.Contains(t, buf.String(), "counting")
+
+	modes[0].run = func(*search.Engine, string) (int, error) { return 0, errors.New("bad pattern") }
+	_, err = runBench(nil, modes, 5)
+	assert.ErrorContains(t, err, `counting query "a"`)
+}
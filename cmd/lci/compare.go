@@ -0,0 +1,310 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// Rankers `lci compare` can run, by --ranker-a and --ranker-b name
+const (
+	compareRankerDefault   = "default" // The engine's built-in scoring
+	compareRankerSubstring = "substring"
+	compareRankerBM25      = "bm25"
+	compareRankerProximity = "proximity" // search.SymbolProximityRanker
+)
+
+// Defaults of `lci compare`
+const (
+	defaultCompareLimit      = 20
+	defaultCompareMaxResults = 1000
+)
+
+// compareSnippetWidth bounds the snippets of the text report
+const compareSnippetWidth = 60
+
+// CompareHit is a hit in the top of one ranking only
+type CompareHit struct {
+	Path      string  `json:"path"`
+	Line      int     `json:"line"`
+	Column    int     `json:"column"`
+	Rank      int     `json:"rank"`                 // 1-based rank in the ranking that has it in its top
+	OtherRank int     `json:"other_rank,omitempty"` // Rank in the other ranking, below its top; 0 when it did not find the hit
+	Score     float64 `json:"score"`
+	Snippet   string  `json:"snippet"`
+}
+
+// SharedHit is a hit in the top of both rankings
+type SharedHit struct {
+	Path   string `json:"path"`
+	Line   int    `json:"line"`
+	Column int    `json:"column"`
+	RankA  int    `json:"rank_a"`
+	RankB  int    `json:"rank_b"`
+	Delta  int    `json:"delta"` // RankB - RankA: positive when B ranks the hit lower
+}
+
+// CompareReport is the output of `lci compare`. KendallTau is the rank correlation of
+// the shared hits, from 1 when both rankings order them alike to -1 when one reverses
+// the other; it is null with fewer than two shared hits.
+type CompareReport struct {
+	Query      string       `json:"query"`
+	RankerA    string       `json:"ranker_a"`
+	RankerB    string       `json:"ranker_b"`
+	Limit      int          `json:"limit"`  // Top hits of each ranking compared, 0 = all
+	HitsA      int          `json:"hits_a"` // Hits ranker A's search found, before Limit
+	HitsB      int          `json:"hits_b"`
+	KendallTau *float64     `json:"kendall_tau"`
+	Shared     []SharedHit  `json:"shared"` // In A's order
+	OnlyA      []CompareHit `json:"only_a"`
+	OnlyB      []CompareHit `json:"only_b"`
+}
+
+// compareCommand indexes the project once, runs the query under two rankers and
+// reports how their rankings differ
+func compareCommand(c *cli.Context) error {
+	if c.NArg() != 1 {
+		return errors.New("usage: lci compare --ranker-a <ranker> --ranker-b <ranker> <query>")
+	}
+	query := c.Args().First()
+	format := c.String("format")
+	if format != "" && format != searchFormatText && format != searchFormatJSON {
+		return fmt.Errorf("unknown output format %q (expected %s or %s)", format, searchFormatText, searchFormatJSON)
+	}
+	limit := c.Int("limit")
+	if limit < 0 {
+		return fmt.Errorf("--limit must not be negative, got %d", limit)
+	}
+	minTau := c.Float64("min-tau")
+	if c.IsSet("min-tau") && (minTau < -1 || minTau > 1) {
+		return fmt.Errorf("--min-tau must be between -1 and 1, got %g", minTau)
+	}
+	nameA, nameB := c.String("ranker-a"), c.String("ranker-b")
+	for _, name := range []string{nameA, nameB} {
+		if err := validateCompareRanker(name); err != nil {
+			return err
+		}
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return err
+	}
+	idx := indexing.NewMasterIndex(cfg)
+	defer idx.Close()
+	if err := idx.IndexDirectory(context.Background(), cfg.Project.Root); err != nil {
+		return fmt.Errorf("failed to index %s: %w", cfg.Project.Root, err)
+	}
+
+	options := compareSearchOptions(c.Bool("ignore-case"), c.Int("max-results"))
+	run := func(name string) []search.GrepResult {
+		engine := search.NewEngine(idx)
+		engine.SetRanker(compareRanker(name, idx))
+		results := engine.SearchWithOptions(query, nil, options)
+		return pathutil.ToRelativeGrepResults(results, reportRoot(cfg.Project.Root))
+	}
+	report := compareRankings(run(nameA), run(nameB), limit)
+	report.Query, report.RankerA, report.RankerB = query, nameA, nameB
+
+	if format == searchFormatJSON {
+		enc := json.NewEncoder(os.Stdout)
+		enc.SetIndent("", "  ")
+		if err := enc.Encode(report); err != nil {
+			return err
+		}
+	} else {
+		writeCompareReport(os.Stdout, report)
+	}
+	if c.IsSet("min-tau") && report.KendallTau != nil && *report.KendallTau < minTau {
+		return cli.Exit(fmt.Sprintf("Kendall's tau %.3f is below --min-tau %g", *report.KendallTau, minTau), exitRankingDrift)
+	}
+	return nil
+}
+
+// compareSearchOptions returns the options both rankings are searched with: those of
+// `lci search` without flags, so the hits compared are the ones it finds
+func compareSearchOptions(caseInsensitive bool, maxResults int) types.SearchOptions {
+	return types.SearchOptions{
+		CaseInsensitive:    caseInsensitive,
+		SmartCase:          !caseInsensitive,
+		MergeFileResults:   true,
+		EnsureCompleteStmt: true,
+		Terms:              true, // Words match anywhere on the line; quote a phrase to match it as written
+		ResultCap:          maxResults,
+	}
+}
+
+// validateCompareRanker rejects an unknown --ranker-a or --ranker-b name
+func validateCompareRanker(name string) error {
+	switch name {
+	case compareRankerDefault, compareRankerSubstring, compareRankerBM25, compareRankerProximity:
+		return nil
+	}
+	return fmt.Errorf("unknown ranker %q (expected %s, %s, %s or %s)", name,
+		compareRankerDefault, compareRankerSubstring, compareRankerBM25, compareRankerProximity)
+}
+
+// compareRanker returns the ranker named name over idx, nil for the default scoring
+func compareRanker(name string, idx *indexing.MasterIndex) search.Ranker {
+	switch name {
+	case compareRankerSubstring:
+		return search.SubstringRanker{}
+	case compareRankerBM25:
+		return search.NewBm25Ranker(idx.GetTermStatsIndex())
+	case compareRankerProximity:
+		return search.NewSymbolProximityRanker(idx)
+	}
+	return nil
+}
+
+// compareHitKey identifies a hit across rankings
+type compareHitKey struct {
+	path             string
+	line, start, end int
+}
+
+func hitKeyOf(r search.GrepResult) compareHitKey {
+	return compareHitKey{r.Path, r.Line, r.StartByte, r.EndByte}
+}
+
+// compareRankings compares the top limit hits of rankings a and b, both best first
+func compareRankings(a, b []search.GrepResult, limit int) CompareReport {
+	report := CompareReport{
+		Limit:  limit,
+		HitsA:  len(a),
+		HitsB:  len(b),
+		Shared: []SharedHit{},
+		OnlyA:  []CompareHit{},
+		OnlyB:  []CompareHit{},
+	}
+	ranksA, ranksB := hitRanks(a), hitRanks(b)
+	topA, topB := topHits(a, limit), topHits(b, limit)
+
+	var sharedB []int // B's ranks of the shared hits, in A's order
+	for i, r := range topA {
+		rankB, ok := ranksB[hitKeyOf(r)]
+		if ok && rankB <= len(topB) {
+			report.Shared = append(report.Shared, SharedHit{
+				Path: r.Path, Line: r.Line, Column: r.Column,
+				RankA: i + 1, RankB: rankB, Delta: rankB - (i + 1),
+			})
+			sharedB = append(sharedB, rankB)
+			continue
+		}
+		report.OnlyA = append(report.OnlyA, newCompareHit(r, i+1, rankB))
+	}
+	for i, r := range topB {
+		rankA, ok := ranksA[hitKeyOf(r)]
+		if !ok || rankA > len(topA) {
+			report.OnlyB = append(report.OnlyB, newCompareHit(r, i+1, rankA))
+		}
+	}
+	if tau, ok := kendallTau(sharedB); ok {
+		report.KendallTau = &tau
+	}
+	return report
+}
+
+// hitRanks maps each hit of ranking to its 1-based rank, the first when it repeats
+func hitRanks(ranking []search.GrepResult) map[compareHitKey]int {
+	ranks := make(map[compareHitKey]int, len(ranking))
+	for i, r := range ranking {
+		if _, ok := ranks[hitKeyOf(r)]; !ok {
+			ranks[hitKeyOf(r)] = i + 1
+		}
+	}
+	return ranks
+}
+
+// topHits returns the first limit hits of ranking, all of them when limit is 0
+func topHits(ranking []search.GrepResult, limit int) []search.GrepResult {
+	if limit > 0 && len(ranking) > limit {
+		return ranking[:limit]
+	}
+	return ranking
+}
+
+func newCompareHit(r search.GrepResult, rank, otherRank int) CompareHit {
+	return CompareHit{
+		Path:      r.Path,
+		Line:      r.Line,
+		Column:    r.Column,
+		Rank:      rank,
+		OtherRank: otherRank,
+		Score:     r.Score,
+		Snippet:   matchedSnippet(r),
+	}
+}
+
+// kendallTau returns Kendall's tau between the order of ranks and their sorted order:
+// the concordant pairs minus the discordant ones over all pairs. Ranks are distinct,
+// so there are no ties. It returns false for fewer than two ranks.
+func kendallTau(ranks []int) (float64, bool) {
+	n := len(ranks)
+	if n < 2 {
+		return 0, false
+	}
+	balance := 0
+	for i := range n {
+		for j := i + 1; j < n; j++ {
+			if ranks[i] < ranks[j] {
+				balance++
+			} else {
+				balance--
+			}
+		}
+	}
+	return float64(balance) / float64(n*(n-1)/2), true
+}
+
+// writeCompareReport prints the report for a terminal: the correlation, the hits only
+// one ranking has in its top, then the rank changes of the shared hits
+func writeCompareReport(w io.Writer, report CompareReport) {
+	top := "all hits"
+	if report.Limit > 0 {
+		top = fmt.Sprintf("top %d", report.Limit)
+	}
+	fmt.Fprintf(w, "Query %q, %s compared: %s found %d hits, %s found %d\n",
+		report.Query, top, report.RankerA, report.HitsA, report.RankerB, report.HitsB)
+	if report.KendallTau != nil {
+		fmt.Fprintf(w, "Kendall's tau over %d shared hits: %.3f\n", len(report.Shared), *report.KendallTau)
+	} else {
+		fmt.Fprintf(w, "Kendall's tau: n/a (%d shared hits)\n", len(report.Shared))
+	}
+
+	writeOnly := func(ranker, other string, hits []CompareHit) {
+		if len(hits) == 0 {
+			return
+		}
+		fmt.Fprintf(w, "\nOnly %s (%d):\n", ranker, len(hits))
+		for _, h := range hits {
+			where := "not found by " + other
+			if h.OtherRank > 0 {
+				where = fmt.Sprintf("#%d in %s", h.OtherRank, other)
+			}
+			snippet := truncateLine(strings.TrimSpace(strings.SplitN(h.Snippet, "\n", 2)[0]), compareSnippetWidth)
+			fmt.Fprintf(w, "  #%-3d %s:%d  %s  (%s)\n", h.Rank, h.Path, h.Line, snippet, where)
+		}
+	}
+	writeOnly(report.RankerA, report.RankerB, report.OnlyA)
+	writeOnly(report.RankerB, report.RankerA, report.OnlyB)
+
+	if len(report.Shared) == 0 {
+		return
+	}
+	fmt.Fprintf(w, "\nShared (%d):\n", len(report.Shared))
+	fmt.Fprintf(w, "  %6s %6s %6s  %s\n", "RANK A", "RANK B", "DELTA", "HIT")
+	for _, h := range report.Shared {
+		fmt.Fprintf(w, "  %6d %6d %+6d  %s:%d\n", h.RankA, h.RankB, h.Delta, h.Path, h.Line)
+	}
+}
@@ -0,0 +1,72 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestKendallTau(t *testing.T) {
+	tau, ok := kendallTau([]int{1, 2, 3, 4})
+	require.True(t, ok)
+	assert.Equal(t, 1.0, tau, "same order")
+
+	tau, _ = kendallTau([]int{4, 3, 2, 1})
+	assert.Equal(t, -1.0, tau, "reversed")
+
+	tau, _ = kendallTau([]int{2, 1, 3})
+	assert.InDelta(t, 1.0/3, tau, 1e-9, "one discordant pair of three")
+
+	_, ok = kendallTau([]int{1})
+	assert.False(t, ok, "no pairs to compare")
+}
+
+func TestCompareRankings(t *testing.T) {
+	hit := func(path string, line int) search.GrepResult {
+		return search.GrepResult{Path: path, Line: line, Match: "user"}
+	}
+	a := []search.GrepResult{hit("a.go", 1), hit("b.go", 2), hit("c.go", 3), hit("d.go", 4)}
+	b := []search.GrepResult{hit("c.go", 3), hit("a.go", 1), hit("e.go", 5), hit("d.go", 4)}
+
+	report := compareRankings(a, b, 3)
+	assert.Equal(t, 4, report.HitsA)
+	assert.Equal(t, []SharedHit{
+		{Path: "a.go", Line: 1, RankA: 1, RankB: 2, Delta: 1},
+		{Path: "c.go", Line: 3, RankA: 3, RankB: 1, Delta: -2},
+	}, report.Shared)
+	require.Len(t, report.OnlyA, 1)
+	assert.Equal(t, "b.go", report.OnlyA[0].Path)
+	assert.Zero(t, report.OnlyA[0].OtherRank, "b did not find the hit at all")
+	require.Len(t, report.OnlyB, 1)
+	assert.Equal(t, "e.go", report.OnlyB[0].Path)
+	assert.Equal(t, 3, report.OnlyB[0].Rank)
+	require.NotNil(t, report.KendallTau)
+	assert.Equal(t, -1.0, *report.KendallTau)
+
+	report = compareRankings(a, b, 0)
+	require.Len(t, report.Shared, 3, "no limit compares every hit")
+	assert.Equal(t, SharedHit{Path: "d.go", Line: 4, RankA: 4, RankB: 4}, report.Shared[2])
+
+	report.Query, report.RankerA, report.RankerB = "user", "bm25", "proximity"
+	var buf bytes.Buffer
+	writeCompareReport(&buf, report)
+	assert.Contains(t, buf.String(), "Only bm25 (1):")
+	assert.Contains(t, buf.String(), "not found by proximity")
+	assert.Contains(t, buf.String(), "RANK A")
+}
+
+func TestCompareSearchOptions(t *testing.T) {
+	options := compareSearchOptions(false, 50)
+	assert.True(t, options.Terms, "words match anywhere on the line, as in lci search")
+	assert.True(t, options.SmartCase)
+	assert.True(t, options.MergeFileResults)
+	assert.Equal(t, 50, options.ResultCap)
+
+	options = compareSearchOptions(true, 0)
+	assert.True(t, options.CaseInsensitive)
+	assert.False(t, options.SmartCase)
+}
@@ -0,0 +1,23 @@
+package main
+
+import "github.com/urfave/cli/v2"
+
+// Exit statuses, following grep so scripts can tell "nothing matched" from a failed
+// search: lci search and lci grep exit 0 when something matched, exitNoMatches when
+// the search ran and found nothing, lci compare exits exitRankingDrift when its
+// rankings correlate less than --min-tau, and any command exits exitError when it fails
+const (
+	exitNoMatches    = 1
+	exitError        = 2 // Bad flags or pattern, a missing path, or a failed search
+	exitRankingDrift = 3
+)
+
+// matchStatus ends a search command given its number of matches and the error from
+// writing them: the error if there is one, otherwise a silent exitNoMatches when
+// nothing matched
+func matchStatus(matches int, err error) error {
+	if err != nil || matches > 0 {
+		return err
+	}
+	return cli.Exit("", exitNoMatches)
+}
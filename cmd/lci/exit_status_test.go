@@ -0,0 +1,52 @@
+package main
+
+import (
+	"errors"
+	"os/exec"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// exitCode is the status a CLI run exited with
+func exitCode(t *testing.T, err error) int {
+	t.Helper()
+	if err == nil {
+		return 0
+	}
+	var exitErr *exec.ExitError
+	require.True(t, errors.As(err, &exitErr), "the CLI ran: %v", err)
+	return exitErr.ExitCode()
+}
+
+// TestExitStatus checks the grep exit status contract against the fixtures: 0 when
+// something matched, 1 when nothing did and 2 when the search could not run
+func TestExitStatus(t *testing.T) {
+	fixtures, err := filepath.Abs("../../tests/search-comparison/fixtures")
+	require.NoError(t, err)
+	defer func() { _, _ = runCLICommand("--root", fixtures, "shutdown") }()
+
+	tests := []struct {
+		name string
+		args []string
+		want int
+	}{
+		{"match", []string{"search", "user"}, 0},
+		{"no match", []string{"search", "nonexistent_xyz"}, exitNoMatches},
+		{"no match as json", []string{"search", "--format", "json", "nonexistent_xyz"}, exitNoMatches},
+		{"no match counted", []string{"search", "--count", "nonexistent_xyz"}, exitNoMatches},
+		{"grep match", []string{"grep", "user"}, 0},
+		{"grep no match", []string{"grep", "nonexistent_xyz"}, exitNoMatches},
+		{"invalid regex", []string{"search", "--regex", "user(["}, exitError},
+		{"missing file", []string{"search", "--file", filepath.Join(fixtures, "missing.rs"), "user"}, exitError},
+		{"bad flag value", []string{"search", "--visibility", "secret", "user"}, exitError},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			output, err := runCLICommand(append([]string{"--root", fixtures}, tt.args...)...)
+			assert.Equal(t, tt.want, exitCode(t, err), output)
+		})
+	}
+}
@@ -0,0 +1,54 @@
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// exportFormatJSONL is the only `lci export --format` value: one symbol object per line
+const exportFormatJSONL = "jsonl"
+
+// exportCommand writes every symbol in the index to stdout, one JSON object per line,
+// as the server streams them
+func exportCommand(c *cli.Context) error {
+	if format := c.String("format"); format != exportFormatJSONL {
+		return fmt.Errorf("unknown export format %q (expected %s)", format, exportFormatJSONL)
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+
+	client, err := ensureServerRunning(c, cfg)
+	if err != nil {
+		return fmt.Errorf("failed to connect to index server: %w", err)
+	}
+
+	out := bufio.NewWriter(os.Stdout)
+	err = client.ExportSymbols(server.ExportRequest{Content: c.Bool("export-content")}, newSymbolExporter(out, reportRoot(projectRoot)))
+	if flushErr := out.Flush(); err == nil {
+		err = flushErr
+	}
+	if err != nil {
+		return fmt.Errorf("export failed: %w", err)
+	}
+	return nil
+}
+
+// newSymbolExporter returns an emit function writing each symbol to w as a JSON line,
+// with its path made relative to root (kept as is when root is empty)
+func newSymbolExporter(w io.Writer, root string) func(server.ExportSymbol) error {
+	enc := json.NewEncoder(w)
+	return func(sym server.ExportSymbol) error {
+		sym.Path = pathutil.ToRelative(sym.Path, root)
+		return enc.Encode(sym)
+	}
+}
@@ -0,0 +1,26 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/server"
+)
+
+func TestSymbolExporter(t *testing.T) {
+	var buf bytes.Buffer
+	emit := newSymbolExporter(&buf, "/src")
+	require.NoError(t, emit(server.ExportSymbol{Path: "/src/store.rs", Kind: "method", Name: "get_user", Parent: "Store", Line: 5, ByteSpan: [2]int{40, 66}}))
+	require.NoError(t, emit(server.ExportSymbol{Path: "/src/a.rs", Kind: "function", Name: "helper", Line: 1, ByteSpan: [2]int{0, 14}, Doc: "Helps."}))
+
+	want := `{"path":"store.rs","kind":"method","name":"get_user","parent":"Store","line":5,"byte_span":[40,66]}` + "\n" +
+		`{"path":"a.rs","kind":"function","name":"helper","line":1,"byte_span":[0,14],"doc":"Helps."}` + "\n"
+	assert.Equal(t, want, buf.String())
+
+	buf.Reset()
+	require.NoError(t, newSymbolExporter(&buf, "")(server.ExportSymbol{Path: "/src/a.rs", Name: "helper"}))
+	assert.Contains(t, buf.String(), `"path":"/src/a.rs"`, "an empty root keeps paths absolute")
+}
@@ -0,0 +1,62 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/server"
+
+	"github.com/urfave/cli/v2"
+)
+
+// progressInterval throttles the indexing progress line to a few redraws a second
+const progressInterval = 250 * time.Millisecond
+
+// indexProgress reports an index server's first indexing run on stderr: a progress line
+// redrawn in place while files are processed, then a summary line once the index is
+// ready. With draw off only the summary is written.
+type indexProgress struct {
+	w     io.Writer
+	draw  bool
+	start time.Time
+	last  time.Time // When the progress line was last drawn
+	drawn bool      // A progress line is on screen and must be cleared before the summary
+}
+
+func newIndexProgress(w io.Writer, draw bool, start time.Time) *indexProgress {
+	return &indexProgress{w: w, draw: draw, start: start}
+}
+
+// showIndexProgress reports whether c's output leaves room for a progress line: stdout
+// and stderr are both terminals and the command is not writing json
+func showIndexProgress(c *cli.Context) bool {
+	if c.Bool("json") || strings.HasPrefix(c.String("format"), "json") {
+		return false
+	}
+	return isTerminal(os.Stdout) && isTerminal(os.Stderr)
+}
+
+// update redraws the progress line for status, at most once per progressInterval
+func (p *indexProgress) update(status *server.IndexStatus, now time.Time) {
+	if !p.draw || !status.IndexingActive || (p.drawn && now.Sub(p.last) < progressInterval) {
+		return
+	}
+	if status.TotalFiles == 0 {
+		fmt.Fprint(p.w, "\rIndexing: discovering files...\033[K")
+	} else {
+		fmt.Fprintf(p.w, "\rIndexing: %d/%d files\033[K", status.FilesProcessed, status.TotalFiles)
+	}
+	p.last, p.drawn = now, true
+}
+
+// finish replaces the progress line with the files indexed, symbols extracted and time
+// taken
+func (p *indexProgress) finish(status *server.IndexStatus, now time.Time) {
+	if p.drawn {
+		fmt.Fprint(p.w, "\r\033[K")
+	}
+	fmt.Fprintf(p.w, "Indexed %d files, %d symbols in %s\n", status.FileCount, status.SymbolCount, now.Sub(p.start).Round(time.Millisecond))
+}
@@ -0,0 +1,38 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/server"
+)
+
+func TestIndexProgress(t *testing.T) {
+	start := time.Unix(0, 0)
+	var buf bytes.Buffer
+	p := newIndexProgress(&buf, true, start)
+
+	p.update(&server.IndexStatus{IndexingActive: true}, start)
+	p.update(&server.IndexStatus{IndexingActive: true, FilesProcessed: 10, TotalFiles: 40}, start.Add(100*time.Millisecond))
+	p.update(&server.IndexStatus{IndexingActive: true, FilesProcessed: 30, TotalFiles: 40}, start.Add(300*time.Millisecond))
+	p.finish(&server.IndexStatus{Ready: true, FileCount: 40, SymbolCount: 512}, start.Add(1500*time.Millisecond))
+
+	want := "\rIndexing: discovering files...\033[K" +
+		"\rIndexing: 30/40 files\033[K" + // The update at 100ms is throttled
+		"\r\033[K" +
+		"Indexed 40 files, 512 symbols in 1.5s\n"
+	assert.Equal(t, want, buf.String())
+}
+
+func TestIndexProgress_NoDraw(t *testing.T) {
+	start := time.Unix(0, 0)
+	var buf bytes.Buffer
+	p := newIndexProgress(&buf, false, start)
+
+	p.update(&server.IndexStatus{IndexingActive: true, FilesProcessed: 1, TotalFiles: 2}, start)
+	p.finish(&server.IndexStatus{Ready: true, FileCount: 2, SymbolCount: 3}, start.Add(20*time.Millisecond))
+	assert.Equal(t, "Indexed 2 files, 3 symbols in 20ms\n", buf.String(), "piped output gets only the summary")
+}
@@ -0,0 +1,187 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/urfave/cli/v2"
+)
+
+// JumpList is the state `lci search --numbered` leaves for `lci open N`: the hits it
+// printed, in order. One list is kept per working directory, so searches in
+// concurrent shells in other directories do not replace each other's.
+type JumpList struct {
+	Dir     string      `json:"dir"` // Working directory of the search
+	Pattern string      `json:"pattern"`
+	Hits    []JumpEntry `json:"hits"`
+}
+
+// JumpEntry is one numbered hit. Path is absolute so the hit can be opened from any
+// directory the list is read in.
+type JumpEntry struct {
+	Path   string `json:"path"`
+	Line   int    `json:"line"`
+	Column int    `json:"column"` // 1-based byte column
+}
+
+// jumpListFile returns where the jump list for dir is kept under cacheDir
+func jumpListFile(cacheDir, dir string) string {
+	sum := sha256.Sum256([]byte(dir))
+	return filepath.Join(cacheDir, "lci", "jump", hex.EncodeToString(sum[:8])+".json")
+}
+
+// saveJumpList writes list for its directory, replacing the previous one whole so a
+// concurrent `lci open` never reads half a list
+func saveJumpList(cacheDir string, list JumpList) error {
+	path := jumpListFile(cacheDir, list.Dir)
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return err
+	}
+	data, err := json.Marshal(list)
+	if err != nil {
+		return err
+	}
+	tmp, err := os.CreateTemp(filepath.Dir(path), ".jump-*")
+	if err != nil {
+		return err
+	}
+	if _, err := tmp.Write(data); err != nil {
+		tmp.Close()
+		os.Remove(tmp.Name())
+		return err
+	}
+	if err := tmp.Close(); err != nil {
+		os.Remove(tmp.Name())
+		return err
+	}
+	return os.Rename(tmp.Name(), path)
+}
+
+// loadJumpList reads the jump list the last numbered search in dir left
+func loadJumpList(cacheDir, dir string) (JumpList, error) {
+	var list JumpList
+	data, err := os.ReadFile(jumpListFile(cacheDir, dir))
+	if errors.Is(err, os.ErrNotExist) {
+		return list, fmt.Errorf("no numbered results for %s; run lci search --numbered first", dir)
+	} else if err != nil {
+		return list, err
+	}
+	if err := json.Unmarshal(data, &list); err != nil {
+		return list, fmt.Errorf("corrupt jump list %s: %w", jumpListFile(cacheDir, dir), err)
+	}
+	return list, nil
+}
+
+// jumpRecorder numbers the hits a search prints and collects them for the jump list.
+// A nil recorder numbers nothing, so callers need not check --numbered.
+type jumpRecorder struct {
+	root string // Directory relative hit paths are reported from
+	hits []JumpEntry
+}
+
+// newJumpRecorder returns a recorder for hits reported relative to root when
+// --numbered is set, else nil
+func newJumpRecorder(c *cli.Context, root string) *jumpRecorder {
+	if !c.Bool("numbered") {
+		return nil
+	}
+	return &jumpRecorder{root: root}
+}
+
+// prefix records r and returns its "[N] " label, or "" for a nil recorder
+func (j *jumpRecorder) prefix(r search.GrepResult) string {
+	if j == nil {
+		return ""
+	}
+	path := r.Path
+	if !filepath.IsAbs(path) {
+		path = filepath.Join(j.root, path)
+	}
+	if abs, err := filepath.Abs(path); err == nil {
+		path = abs
+	}
+	j.hits = append(j.hits, JumpEntry{Path: path, Line: r.Line, Column: r.Column + 1})
+	return fmt.Sprintf("[%d] ", len(j.hits))
+}
+
+// save writes the recorded hits as the jump list of the working directory. Failing to
+// save only costs `lci open`, so it warns rather than failing the search.
+func (j *jumpRecorder) save(pattern string) {
+	if j == nil {
+		return
+	}
+	cacheDir, err := os.UserCacheDir()
+	if err == nil {
+		var dir string
+		if dir, err = os.Getwd(); err == nil {
+			err = saveJumpList(cacheDir, JumpList{Dir: dir, Pattern: pattern, Hits: j.hits})
+		}
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "WARNING: could not save numbered results for lci open: %v\n", err)
+	}
+}
+
+// openCommand opens hit N of the last numbered search in this directory in $VISUAL or
+// $EDITOR, at its line
+func openCommand(c *cli.Context) error {
+	if c.NArg() != 1 {
+		return errors.New("usage: lci open N")
+	}
+	n, err := strconv.Atoi(c.Args().First())
+	if err != nil {
+		return fmt.Errorf("hit number %q is not a number", c.Args().First())
+	}
+	cacheDir, err := os.UserCacheDir()
+	if err != nil {
+		return err
+	}
+	dir, err := os.Getwd()
+	if err != nil {
+		return err
+	}
+	list, err := loadJumpList(cacheDir, dir)
+	if err != nil {
+		return err
+	}
+	if n < 1 || n > len(list.Hits) {
+		return fmt.Errorf("no hit %d: the last numbered search for %q has %d", n, list.Pattern, len(list.Hits))
+	}
+
+	editor := os.Getenv("VISUAL")
+	if editor == "" {
+		editor = os.Getenv("EDITOR")
+	}
+	args := editorCommand(editor, list.Hits[n-1])
+	cmd := exec.Command(args[0], args[1:]...)
+	cmd.Stdin, cmd.Stdout, cmd.Stderr = os.Stdin, os.Stdout, os.Stderr
+	return cmd.Run()
+}
+
+// editorCommand builds the command line opening hit in editor, which may carry its own
+// arguments (e.g. "code -w"). Editors are told the line in the syntax they accept:
+// file:line:column for VS Code, Sublime Text and Zed, +line for vi, Emacs, nano and
+// most others. An empty editor means vi.
+func editorCommand(editor string, hit JumpEntry) []string {
+	args := strings.Fields(editor)
+	if len(args) == 0 {
+		args = []string{"vi"}
+	}
+	location := fmt.Sprintf("%s:%d:%d", hit.Path, hit.Line, hit.Column)
+	switch name := strings.TrimSuffix(filepath.Base(args[0]), ".exe"); name {
+	case "code", "code-insiders", "codium", "cursor":
+		return append(args, "--goto", location)
+	case "subl", "zed":
+		return append(args, location)
+	}
+	return append(args, "+"+strconv.Itoa(hit.Line), hit.Path)
+}
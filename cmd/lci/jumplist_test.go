@@ -0,0 +1,57 @@
+package main
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestJumpList_SaveLoad(t *testing.T) {
+	cacheDir := t.TempDir()
+	_, err := loadJumpList(cacheDir, "/work/a")
+	assert.ErrorContains(t, err, "run lci search --numbered first")
+
+	a := JumpList{Dir: "/work/a", Pattern: "main", Hits: []JumpEntry{{Path: "/work/a/main.go", Line: 3, Column: 6}}}
+	b := JumpList{Dir: "/work/b", Pattern: "user", Hits: []JumpEntry{{Path: "/work/b/user.rs", Line: 9, Column: 1}}}
+	require.NoError(t, saveJumpList(cacheDir, a))
+	require.NoError(t, saveJumpList(cacheDir, b))
+	assert.NotEqual(t, jumpListFile(cacheDir, a.Dir), jumpListFile(cacheDir, b.Dir))
+
+	got, err := loadJumpList(cacheDir, "/work/a")
+	require.NoError(t, err)
+	assert.Equal(t, a, got, "a search in another directory keeps this one's list")
+
+	a.Hits = nil
+	require.NoError(t, saveJumpList(cacheDir, a))
+	got, err = loadJumpList(cacheDir, "/work/a")
+	require.NoError(t, err)
+	assert.Empty(t, got.Hits, "a new search replaces the list")
+}
+
+func TestJumpRecorder(t *testing.T) {
+	root := t.TempDir()
+	jumps := &jumpRecorder{root: root}
+	assert.Equal(t, "[1] ", jumps.prefix(search.GrepResult{Path: "src/main.go", Line: 3, Column: 5}))
+	assert.Equal(t, "[2] ", jumps.prefix(search.GrepResult{Path: "/elsewhere/lib.rs", Line: 1}))
+	assert.Equal(t, []JumpEntry{
+		{Path: filepath.Join(root, "src/main.go"), Line: 3, Column: 6},
+		{Path: "/elsewhere/lib.rs", Line: 1, Column: 1},
+	}, jumps.hits)
+
+	var off *jumpRecorder
+	assert.Empty(t, off.prefix(search.GrepResult{Path: "a.go"}), "a nil recorder numbers nothing")
+	off.save("pattern")
+}
+
+func TestEditorCommand(t *testing.T) {
+	hit := JumpEntry{Path: "/src/main.go", Line: 12, Column: 4}
+	assert.Equal(t, []string{"vi", "+12", "/src/main.go"}, editorCommand("", hit))
+	assert.Equal(t, []string{"nvim", "+12", "/src/main.go"}, editorCommand("nvim", hit))
+	assert.Equal(t, []string{"emacsclient", "-nw", "+12", "/src/main.go"}, editorCommand("emacsclient -nw", hit))
+	assert.Equal(t, []string{"code", "-w", "--goto", "/src/main.go:12:4"}, editorCommand("code -w", hit))
+	assert.Equal(t, []string{"/usr/local/bin/subl", "/src/main.go:12:4"}, editorCommand("/usr/local/bin/subl", hit))
+}
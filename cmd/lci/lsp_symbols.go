@@ -0,0 +1,219 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"sort"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// Output formats accepted by `lci browse --format`
+const (
+	browseFormatText       = "text"
+	browseFormatJSON       = "json"
+	browseFormatLSPSymbols = "lsp-symbols"
+)
+
+// LSP SymbolKind values from the Language Server Protocol specification
+const (
+	lspSymbolKindModule        = 2
+	lspSymbolKindNamespace     = 3
+	lspSymbolKindClass         = 5
+	lspSymbolKindMethod        = 6
+	lspSymbolKindProperty      = 7
+	lspSymbolKindField         = 8
+	lspSymbolKindConstructor   = 9
+	lspSymbolKindEnum          = 10
+	lspSymbolKindInterface     = 11
+	lspSymbolKindFunction      = 12
+	lspSymbolKindVariable      = 13
+	lspSymbolKindConstant      = 14
+	lspSymbolKindObject        = 19
+	lspSymbolKindEnumMember    = 22
+	lspSymbolKindStruct        = 23
+	lspSymbolKindEvent         = 24
+	lspSymbolKindOperator      = 25
+	lspSymbolKindTypeParameter = 26
+)
+
+// lspSymbolKinds maps SymbolType.String() names to LSP SymbolKind. Rust impl blocks
+// have no LSP counterpart and use Object, as rust-analyzer does.
+var lspSymbolKinds = map[string]int{
+	"function":    lspSymbolKindFunction,
+	"class":       lspSymbolKindClass,
+	"method":      lspSymbolKindMethod,
+	"variable":    lspSymbolKindVariable,
+	"constant":    lspSymbolKindConstant,
+	"interface":   lspSymbolKindInterface,
+	"type":        lspSymbolKindTypeParameter,
+	"struct":      lspSymbolKindStruct,
+	"module":      lspSymbolKindModule,
+	"namespace":   lspSymbolKindNamespace,
+	"property":    lspSymbolKindProperty,
+	"event":       lspSymbolKindEvent,
+	"delegate":    lspSymbolKindFunction,
+	"enum":        lspSymbolKindEnum,
+	"record":      lspSymbolKindStruct,
+	"operator":    lspSymbolKindOperator,
+	"indexer":     lspSymbolKindProperty,
+	"object":      lspSymbolKindObject,
+	"companion":   lspSymbolKindObject,
+	"extension":   lspSymbolKindFunction,
+	"annotation":  lspSymbolKindInterface,
+	"field":       lspSymbolKindField,
+	"enum_member": lspSymbolKindEnumMember,
+	"trait":       lspSymbolKindInterface,
+	"impl":        lspSymbolKindObject,
+	"constructor": lspSymbolKindConstructor,
+}
+
+// LSPPosition is a zero-based line and UTF-16 character offset
+type LSPPosition struct {
+	Line      int `json:"line"`
+	Character int `json:"character"`
+}
+
+func (p LSPPosition) before(o LSPPosition) bool {
+	return p.Line < o.Line || (p.Line == o.Line && p.Character < o.Character)
+}
+
+// LSPRange is a half-open [start, end) range
+type LSPRange struct {
+	Start LSPPosition `json:"start"`
+	End   LSPPosition `json:"end"`
+}
+
+func (r LSPRange) contains(o LSPRange) bool {
+	return !o.Start.before(r.Start) && !r.End.before(o.End)
+}
+
+// DocumentSymbol mirrors the LSP DocumentSymbol object returned by
+// textDocument/documentSymbol
+type DocumentSymbol struct {
+	Name           string            `json:"name"`
+	Detail         string            `json:"detail,omitempty"`
+	Kind           int               `json:"kind"`
+	Range          LSPRange          `json:"range"`
+	SelectionRange LSPRange          `json:"selectionRange"`
+	Children       []*DocumentSymbol `json:"children,omitempty"`
+}
+
+// validateBrowseFormat rejects unknown --format values before connecting to the server
+func validateBrowseFormat(format string) error {
+	switch format {
+	case "", browseFormatText, browseFormatJSON, browseFormatLSPSymbols:
+		return nil
+	}
+	return fmt.Errorf("unknown output format %q (expected %s, %s or %s)",
+		format, browseFormatText, browseFormatJSON, browseFormatLSPSymbols)
+}
+
+// writeLSPSymbols writes the symbols of one file as a JSON array of DocumentSymbol
+func writeLSPSymbols(w io.Writer, entries []server.ListSymbolsEntry, content []byte) error {
+	symbols := buildDocumentSymbols(entries, content)
+	if symbols == nil {
+		symbols = []*DocumentSymbol{}
+	}
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(symbols)
+}
+
+// buildDocumentSymbols nests symbols by range containment, so methods appear as
+// children of the impl, trait or class that encloses them. content is the file's
+// source and is used for UTF-16 positions and name ranges; without it byte columns
+// are used as-is.
+func buildDocumentSymbols(entries []server.ListSymbolsEntry, content []byte) []*DocumentSymbol {
+	var lines []string
+	var li *types.LineIndex
+	if content != nil {
+		lines = strings.Split(string(content), "\n")
+		li = types.NewLineIndex(content)
+	}
+	nodes := make([]*DocumentSymbol, 0, len(entries))
+	for _, e := range entries {
+		nodes = append(nodes, newDocumentSymbol(e, lines, li))
+	}
+
+	// Outer symbols sort before the symbols they contain
+	sort.SliceStable(nodes, func(i, j int) bool {
+		a, b := nodes[i].Range, nodes[j].Range
+		if a.Start != b.Start {
+			return a.Start.before(b.Start)
+		}
+		return b.End.before(a.End)
+	})
+
+	var roots, stack []*DocumentSymbol
+	for _, node := range nodes {
+		for len(stack) > 0 && !stack[len(stack)-1].Range.contains(node.Range) {
+			stack = stack[:len(stack)-1]
+		}
+		if len(stack) == 0 {
+			roots = append(roots, node)
+		} else {
+			parent := stack[len(stack)-1]
+			// The variable twin of a JS arrow function adds nothing to an outline
+			if parent.Name == node.Name && parent.Range == node.Range {
+				continue
+			}
+			parent.Children = append(parent.Children, node)
+		}
+		stack = append(stack, node)
+	}
+	return roots
+}
+
+func newDocumentSymbol(e server.ListSymbolsEntry, lines []string, li *types.LineIndex) *DocumentSymbol {
+	kind, ok := lspSymbolKinds[e.Type]
+	if !ok {
+		kind = lspSymbolKindVariable
+	}
+
+	startLine, startCol := max(e.Line-1, 0), max(e.Column-1, 0)
+	endLine, endCol := startLine, startCol
+	if e.EndLine > 0 {
+		endLine, endCol = e.EndLine-1, max(e.EndColumn-1, 0)
+	}
+	full := LSPRange{
+		Start: lspPosition(li, startLine, startCol),
+		End:   lspPosition(li, endLine, endCol),
+	}
+
+	// selectionRange covers the name on the declaration line
+	selection := LSPRange{Start: full.Start, End: full.Start}
+	if startLine < len(lines) {
+		line := lines[startLine]
+		from := min(startCol, len(line))
+		if idx := strings.Index(line[from:], e.Name); idx >= 0 {
+			selection.Start = lspPosition(li, startLine, from+idx)
+			selection.End = lspPosition(li, startLine, from+idx+len(e.Name))
+		}
+	}
+	if !full.contains(selection) {
+		selection = LSPRange{Start: full.Start, End: full.Start}
+	}
+
+	return &DocumentSymbol{
+		Name:           e.Name,
+		Detail:         e.Signature,
+		Kind:           kind,
+		Range:          full,
+		SelectionRange: selection,
+	}
+}
+
+// lspPosition converts a zero-based byte column to the UTF-16 character offset LSP
+// clients expect by default
+func lspPosition(li *types.LineIndex, line, byteCol int) LSPPosition {
+	if li == nil {
+		return LSPPosition{Line: line, Character: byteCol}
+	}
+	offset := li.Offset(types.Position{Line: line + 1, Column: byteCol + 1}, types.PositionEncodingUTF8)
+	pos := li.Position(offset, types.PositionEncodingUTF16)
+	return LSPPosition{Line: pos.Line - 1, Character: pos.Column - 1}
+}
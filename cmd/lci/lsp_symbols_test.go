@@ -0,0 +1,103 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/server"
+)
+
+const lspSampleRust = `trait Store {
+    fn get(&self) -> u32;
+}
+
+struct Cache {
+    hits: u32,
+}
+
+impl Cache {
+    fn new() -> Self {
+        Cache { hits: 0 }
+    }
+}
+
+fn größe() {}
+`
+
+func lspSampleEntries() []server.ListSymbolsEntry {
+	// Listed out of order: nesting must not depend on the server's sort
+	return []server.ListSymbolsEntry{
+		{Name: "größe", Type: "function", Line: 15, Column: 1, EndLine: 15, EndColumn: 16},
+		{Name: "new", Type: "method", Line: 10, Column: 5, EndLine: 12, EndColumn: 6, ReceiverType: "Cache"},
+		{Name: "Cache", Type: "impl", Line: 9, Column: 1, EndLine: 13, EndColumn: 2},
+		{Name: "Store", Type: "trait", Line: 1, Column: 1, EndLine: 3, EndColumn: 2},
+		{Name: "get", Type: "method", Line: 2, Column: 5, EndLine: 2, EndColumn: 26, ReceiverType: "Store"},
+		{Name: "Cache", Type: "struct", Line: 5, Column: 1, EndLine: 7, EndColumn: 2},
+	}
+}
+
+func lspRange(startLine, startChar, endLine, endChar int) LSPRange {
+	return LSPRange{
+		Start: LSPPosition{Line: startLine, Character: startChar},
+		End:   LSPPosition{Line: endLine, Character: endChar},
+	}
+}
+
+func TestBuildDocumentSymbols(t *testing.T) {
+	symbols := buildDocumentSymbols(lspSampleEntries(), []byte(lspSampleRust))
+	require.Len(t, symbols, 4)
+
+	store := symbols[0]
+	assert.Equal(t, "Store", store.Name)
+	assert.Equal(t, lspSymbolKindInterface, store.Kind)
+	assert.Equal(t, lspRange(0, 0, 2, 1), store.Range)
+	assert.Equal(t, lspRange(0, 6, 0, 11), store.SelectionRange)
+	require.Len(t, store.Children, 1)
+	assert.Equal(t, "get", store.Children[0].Name)
+	assert.Equal(t, lspSymbolKindMethod, store.Children[0].Kind)
+	assert.Equal(t, lspRange(1, 7, 1, 10), store.Children[0].SelectionRange)
+
+	assert.Equal(t, "Cache", symbols[1].Name)
+	assert.Equal(t, lspSymbolKindStruct, symbols[1].Kind)
+	assert.Empty(t, symbols[1].Children)
+
+	impl := symbols[2]
+	assert.Equal(t, lspSymbolKindObject, impl.Kind)
+	assert.Equal(t, lspRange(8, 5, 8, 10), impl.SelectionRange)
+	require.Len(t, impl.Children, 1)
+	assert.Equal(t, "new", impl.Children[0].Name)
+	assert.Equal(t, lspRange(9, 4, 11, 5), impl.Children[0].Range)
+
+	// Positions are UTF-16 offsets: ö and ß are two bytes each but one character
+	fn := symbols[3]
+	assert.Equal(t, lspSymbolKindFunction, fn.Kind)
+	assert.Equal(t, lspRange(14, 0, 14, 13), fn.Range)
+	assert.Equal(t, lspRange(14, 3, 14, 8), fn.SelectionRange)
+}
+
+func TestWriteLSPSymbolsJSON(t *testing.T) {
+	var buf bytes.Buffer
+	require.NoError(t, writeLSPSymbols(&buf, lspSampleEntries()[3:5], nil))
+
+	var out []map[string]interface{}
+	require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+	require.Len(t, out, 1)
+	assert.Equal(t, "Store", out[0]["name"])
+	assert.Equal(t, float64(lspSymbolKindInterface), out[0]["kind"])
+	assert.Contains(t, out[0], "selectionRange")
+	assert.Len(t, out[0]["children"], 1)
+
+	buf.Reset()
+	require.NoError(t, writeLSPSymbols(&buf, nil, nil))
+	assert.JSONEq(t, "[]", buf.String(), "a file without symbols is an empty array, not null")
+}
+
+func TestValidateBrowseFormat(t *testing.T) {
+	assert.NoError(t, validateBrowseFormat(browseFormatLSPSymbols))
+	assert.NoError(t, validateBrowseFormat(""))
+	assert.Error(t, validateBrowseFormat("xml"))
+}
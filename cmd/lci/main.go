@@ -5,6 +5,7 @@ import (
 	"encoding/json"
 	"errors"
 	"fmt"
+	"math"
 	"os"
 	"os/signal"
 	"path/filepath"
@@ -16,14 +17,18 @@ import (
 	"time"
 
 	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/core"
 	"github.com/standardbeagle/lci/internal/debug"
 	"github.com/standardbeagle/lci/internal/display"
+	"github.com/standardbeagle/lci/internal/encoding"
 	"github.com/standardbeagle/lci/internal/git"
 	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/lang"
 	"github.com/standardbeagle/lci/internal/mcp"
 	"github.com/standardbeagle/lci/internal/server"
 	"github.com/standardbeagle/lci/internal/types"
 	"github.com/standardbeagle/lci/internal/version"
+	"github.com/standardbeagle/lci/pkg/pathutil"
 
 	"github.com/urfave/cli/v2"
 )
@@ -33,30 +38,69 @@ var (
 	indexer      *indexing.MasterIndex
 	cleanupFuncs []func()
 	projectRoot  string // Stores the absolute path to the project root for path conversion
+	absoluteOut  bool   // --absolute: report paths as indexed rather than relative to the root
 )
 
+// reportRoot returns the directory reported paths are made relative to: root, or "" with
+// --absolute, which pathutil.ToRelative takes as keep the path as is
+func reportRoot(root string) string {
+	if absoluteOut {
+		return ""
+	}
+	return root
+}
+
 // loadConfigWithOverrides loads configuration and applies CLI flag overrides
 func loadConfigWithOverrides(c *cli.Context) (*config.Config, error) {
-	configPath := c.String("config")
+	return loadConfigForRoot(c, rootFlag(c))
+}
 
-	// If root is specified and config path is default, look for config in root directory
-	if rootFlag := c.String("root"); rootFlag != "" && configPath == ".lci.kdl" {
-		configPath = filepath.Join(rootFlag, ".lci.kdl")
+// rootFlag returns the --root override. `lci search` declares its own repeatable
+// --root, which takes precedence over the global flag.
+func rootFlag(c *cli.Context) string {
+	if roots := c.StringSlice("root"); len(roots) > 0 {
+		return roots[0]
 	}
+	return appContext(c).String("root")
+}
 
-	cfg, err := config.Load(configPath)
+// appContext returns the context of the global flags. The last context in c's lineage
+// is the placeholder App.Run starts from, which has no flags; the one below it, the
+// first with a Command, is the app's.
+func appContext(c *cli.Context) *cli.Context {
+	lineage := c.Lineage()
+	for i := len(lineage) - 1; i >= 0; i-- {
+		if lineage[i].Command != nil {
+			return lineage[i]
+		}
+	}
+	return lineage[len(lineage)-1]
+}
+
+// loadConfigForRoot loads configuration for rootFlag (empty for the configured root)
+// and applies CLI flag overrides
+func loadConfigForRoot(c *cli.Context, rootFlag string) (*config.Config, error) {
+	// The project config is the .lci.kdl nearest the search root, in it or a parent
+	searchRoot := rootFlag
+	if searchRoot == "" {
+		searchRoot = "."
+	}
+	cfg, err := config.LoadNearest(searchRoot)
 	if err != nil {
-		return nil, fmt.Errorf("failed to load config from %s: %w", configPath, err)
+		return nil, fmt.Errorf("failed to load config: %w", err)
 	}
 
-	// Apply CLI flag overrides
-	if includeFlags := c.StringSlice("include"); len(includeFlags) > 0 {
+	// Apply CLI flag overrides. Index walk globs and --lang are global flags; `lci search`
+	// declares its own --include and --exclude for query-time filtering, and --lang for
+	// the language of --stdin.
+	global := appContext(c)
+	if includeFlags := global.StringSlice("include"); len(includeFlags) > 0 {
 		cfg.Include = includeFlags
 	}
-	if excludeFlags := c.StringSlice("exclude"); len(excludeFlags) > 0 {
+	if excludeFlags := global.StringSlice("exclude"); len(excludeFlags) > 0 {
 		cfg.Exclude = append(cfg.Exclude, excludeFlags...)
 	}
-	if rootFlag := c.String("root"); rootFlag != "" {
+	if rootFlag != "" {
 		// Convert to absolute path to ensure consistent path handling
 		absRoot, err := filepath.Abs(rootFlag)
 		if err != nil {
@@ -64,6 +108,84 @@ func loadConfigWithOverrides(c *cli.Context) (*config.Config, error) {
 		}
 		cfg.Project.Root = absRoot
 	}
+	// Resolve a symlinked root once, so the walk and every reported path share one base
+	if realRoot, err := filepath.EvalSymlinks(cfg.Project.Root); err == nil {
+		cfg.Project.Root = realRoot
+	}
+	if c.Bool("no-ignore") {
+		cfg.Index.RespectGitignore = false
+	}
+	if c.Bool("text") {
+		cfg.Index.TreatAsText = true
+	}
+	if c.IsSet("tokenize") {
+		tokenizer, err := core.ParseTermTokenizer(c.String("tokenize"))
+		if err != nil {
+			return nil, fmt.Errorf("invalid --tokenize: %w", err)
+		}
+		cfg.Index.Tokenize = string(tokenizer)
+	}
+	if global.IsSet("max-depth") { // Not `lci tree --max-depth`, the depth of the call tree
+		depth := global.Int("max-depth")
+		if depth < 0 {
+			return nil, fmt.Errorf("--max-depth must be 0 or more, got %d", depth)
+		}
+		cfg.Index.MaxDepth = &depth
+	}
+	if global.IsSet("sample") {
+		p := global.Float64("sample")
+		if p <= 0 || p > 1 {
+			return nil, fmt.Errorf("--sample must be a probability above 0 and at most 1, got %g", p)
+		}
+		cfg.Index.Sample = p
+	}
+	if global.IsSet("seed") {
+		if cfg.Index.Sample == 0 {
+			return nil, errors.New("--seed requires --sample")
+		}
+		cfg.Index.SampleSeed = global.Int64("seed")
+	}
+	if global.IsSet("encoding") {
+		enc, err := encoding.ParseTextEncoding(global.String("encoding"))
+		if err != nil {
+			return nil, fmt.Errorf("invalid --encoding: %w", err)
+		}
+		cfg.Index.Encoding = enc
+	}
+	if c.IsSet("threads") {
+		threads := c.Int("threads")
+		if threads < 0 {
+			return nil, fmt.Errorf("--threads must be 0 or more, got %d", threads)
+		}
+		cfg.Performance.ParallelFileWorkers = threads
+	}
+	if size := c.String("max-file-size"); size != "" {
+		maxFileSize, err := config.ParseSize(size)
+		if err != nil || maxFileSize <= 0 {
+			return nil, fmt.Errorf("--max-file-size must be a positive size such as 512KB or 20MB, got %q", size)
+		}
+		cfg.Index.MaxFileSize = maxFileSize
+	}
+	for _, spec := range global.StringSlice("lang") {
+		ext, language, err := lang.ParseOverride(spec)
+		if err == nil {
+			err = lang.ValidateLanguage(language)
+		}
+		if err != nil {
+			return nil, fmt.Errorf("--lang: %w", err)
+		}
+		if cfg.Index.Languages == nil {
+			cfg.Index.Languages = make(map[string]string)
+		}
+		cfg.Index.Languages[ext] = language
+	}
+	if indexFile := c.String("index-file"); indexFile != "" {
+		absIndexFile, err := filepath.Abs(indexFile)
+		if err != nil {
+			return nil, fmt.Errorf("failed to resolve index file path %q: %w", indexFile, err)
+		}
+		cfg.Index.IndexFile = absIndexFile
+	}
 
 	return cfg, nil
 }
@@ -99,6 +221,54 @@ func main() {
 				Aliases: []string{"r"},
 				Usage:   "Project root directory to index (overrides config)",
 			},
+			&cli.BoolFlag{
+				Name:  "no-ignore",
+				Usage: "Index files excluded by .gitignore, .ignore and .lciignore",
+			},
+			&cli.BoolFlag{
+				Name:  "absolute",
+				Usage: "Report absolute file paths instead of paths relative to the project root",
+			},
+			&cli.BoolFlag{
+				Name:  "text",
+				Usage: "Index every file as text, including files that look binary (NUL bytes, binary extensions)",
+			},
+			&cli.StringFlag{
+				Name:  "tokenize",
+				Usage: "BM25 term splitting: identifier (also index snake_case/CamelCase parts) or raw",
+			},
+			&cli.IntFlag{
+				Name:  "max-depth",
+				Usage: "Only descend this many directories below the root: 0 indexes only the files in the root, 1 also those of its subdirectories",
+			},
+			&cli.Float64Flag{
+				Name:  "sample",
+				Usage: "Index a random sample of the files, each kept with this probability (e.g. 0.1), for quick approximate numbers on huge trees",
+			},
+			&cli.Int64Flag{
+				Name:  "seed",
+				Usage: "Seed of the --sample draw; the same seed samples the same files",
+			},
+			&cli.StringFlag{
+				Name:  "encoding",
+				Usage: "Decode files to UTF-8 before indexing: auto (detect UTF-16 and Windows-1252 per file) or utf-16le, utf-16be, windows-1252, latin-1 to force one; a byte order mark always wins",
+			},
+			&cli.IntFlag{
+				Name:  "threads",
+				Usage: "Number of files to index in parallel (0 = available CPUs)",
+			},
+			&cli.StringFlag{
+				Name:  "max-file-size",
+				Usage: "Skip files larger than this (e.g. 512KB, 20MB; overrides config)",
+			},
+			&cli.StringSliceFlag{
+				Name:  "lang",
+				Usage: "Parse files with extension EXT as language LANG, e.g. --lang .tpl=go (files with no known language are indexed as plain text)",
+			},
+			&cli.StringFlag{
+				Name:  "index-file",
+				Usage: "Save the index to this file and reuse it on the next run, re-indexing files changed since",
+			},
 			&cli.BoolFlag{
 				Name:   "test-run",
 				Usage:  "Show files that would be indexed without processing (hidden flag)",
@@ -128,30 +298,76 @@ func main() {
 						Value:   0,
 					},
 					&cli.BoolFlag{
-						Name:    "case-insensitive",
-						Aliases: []string{"i"},
-						Usage:   "Case-insensitive search",
+						Name:    "ignore-case",
+						Aliases: []string{"i", "case-insensitive"},
+						Usage:   "Case-insensitive search (default: smart case, case-insensitive unless the pattern has an uppercase letter)",
+					},
+					&cli.BoolFlag{
+						Name:    "case-sensitive",
+						Aliases: []string{"s"},
+						Usage:   "Case-sensitive search, even for all-lowercase patterns",
 					},
 					&cli.BoolFlag{
 						Name:    "json",
 						Aliases: []string{"j"},
 						Usage:   "Output as JSON",
 					},
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: text, json (stable hit schema), jsonl (one hit per line), lsp-symbols (outline of the --file, no pattern needed)",
+						Value: "text",
+					},
+					&cli.StringFlag{
+						Name:  "position-encoding",
+						Usage: "Column unit of start/end positions in json/jsonl output: utf-8 (bytes) or utf-16 (code units, as VS Code counts)",
+						Value: "utf-8",
+					},
+					&cli.IntFlag{
+						Name:    "context",
+						Aliases: []string{"C"},
+						Usage:   "Show N lines before and after each match (like grep -C)",
+						Value:   0,
+					},
+					&cli.StringFlag{
+						Name:  "color",
+						Usage: "Highlight matched text: auto (when stdout is a terminal), always, never",
+						Value: "auto",
+					},
+					&cli.IntFlag{
+						Name:  "max-snippet-width",
+						Usage: "Cut printed lines longer than N characters, keeping the match centered and marking cut sides with …, 0 = no limit (text output only)",
+						Value: defaultMaxSnippetWidth,
+					},
+					&cli.StringFlag{
+						Name:  "snippet-format",
+						Usage: "Render snippets as plain (numbered lines), markdown (fenced code blocks) or html (<pre> with <mark>ed matches)",
+						Value: snippetFormatPlain,
+					},
+					&cli.StringFlag{
+						Name:  "template",
+						Usage: "Print each hit as this template instead, e.g. '{path}:{line}:{col}: {text}'. Fields: {path} {root} {line} {col} {end_line} {end_col} {byte_start} {byte_end} {symbol} {score} {match} {text} {matched_by}; {{ and }} are literal braces",
+					},
+					&cli.BoolFlag{
+						Name:  "locations",
+						Usage: "Print only path:line:col per hit (path, line and column objects with --format json or jsonl), skipping snippets. Every hit is printed, so hits the default output merges into one snippet count separately; with --limit, the fast way to fill an editor's quickfix list",
+					},
+					&cli.BoolFlag{
+						Name:  "numbered",
+						Usage: "Prefix each hit with [N] and remember the hits, so `lci open N` opens one (text output only)",
+					},
 					&cli.BoolFlag{
 						Name:  "light",
 						Usage: "Use light search without relational data and breadcrumbs",
 					},
-					&cli.StringFlag{
+					&cli.StringSliceFlag{
 						Name:    "exclude",
 						Aliases: []string{"e"},
-						Usage:   "Exclude files matching regex pattern (e.g., '.*test.*\\.go$')",
-						Value:   "",
+						Usage:   "Skip files matching this glob, repeatable; wins over --include (e.g., --exclude '**/*_test.go' --exclude vendor). A glob, not the regex this flag once took",
 					},
-					&cli.StringFlag{
+					&cli.StringSliceFlag{
 						Name:    "include",
 						Aliases: []string{"inc"},
-						Usage:   "Include only files matching regex pattern (e.g., '.*\\.go$')",
-						Value:   "",
+						Usage:   "Search only files matching this glob, repeatable (e.g., --include '*.rs' --include 'src/**'). A glob, not the regex this flag once took",
 					},
 					&cli.BoolFlag{
 						Name:  "comments-only",
@@ -199,7 +415,11 @@ func main() {
 					&cli.BoolFlag{
 						Name:    "count",
 						Aliases: []string{"c"},
-						Usage:   "Return match count per file (grep -c)",
+						Usage:   "Print only the total number of matches instead of the hits",
+					},
+					&cli.StringFlag{
+						Name:  "count-by",
+						Usage: "Print match counts grouped by: file (a per-file tally, highest count first)",
 					},
 					&cli.BoolFlag{
 						Name:    "files-with-matches",
@@ -208,14 +428,151 @@ func main() {
 					},
 					&cli.BoolFlag{
 						Name:    "word-regexp",
-						Aliases: []string{"w"},
-						Usage:   "Match whole words only (grep -w)",
+						Aliases: []string{"w", "word"},
+						Usage:   "Match whole words only: the match must be bounded by non-word characters ([A-Za-z0-9_] are word characters) or the start/end of the file (grep -w)",
+					},
+					&cli.BoolFlag{
+						Name:  "ignore-whitespace",
+						Usage: "Let each run of whitespace in the pattern match any run of spaces and tabs, e.g. 'invalid credentials' finds 'invalid\t  credentials' (literal patterns only)",
 					},
 					&cli.BoolFlag{
 						Name:    "regex",
 						Aliases: []string{"E"},
 						Usage:   "Interpret pattern as extended regex (grep -E). Supports ^, $, *, +, ?, [], (), |",
 					},
+					&cli.BoolFlag{
+						Name:  "multiline",
+						Usage: "Let a --regex match span lines: . matches newlines, e.g. -E --multiline 'func \\w+\\(.*?\\) \\{'; each match is one hit (and one --count) at its first line",
+					},
+					&cli.BoolFlag{
+						Name:  "symbols-only",
+						Usage: "Match the pattern against symbol names instead of file content, e.g. -E --symbols-only '^(get|set)_\\w+'",
+					},
+					&cli.StringFlag{
+						Name:  "in",
+						Usage: "What to search: all, code (skip comments) or docs (symbols' doc comments, returning the documented symbol)",
+						Value: searchInAll,
+					},
+					&cli.BoolFlag{
+						Name:    "query",
+						Aliases: []string{"q"},
+						Usage:   "Interpret pattern as a query over symbols: field:value terms (kind, name, parent, path, lang, text, sig) combined with AND, OR, NOT and parentheses, e.g. 'kind:fn AND name:user AND NOT path:auth'; 'token NEAR/5 expires' finds symbols with both words within 5 tokens, closest first (NEARW/5 keeps their order)",
+					},
+					&cli.BoolFlag{
+						Name:  "sig-strip-generics",
+						Usage: "Match sig: query terms with lifetimes and generic arguments removed, e.g. -q 'sig:\"(&str) -> Vec\"' finds (&'a str) -> Vec<T>",
+					},
+					&cli.StringFlag{
+						Name:    "kind",
+						Aliases: []string{"k"},
+						Usage:   "Only return matches in symbols of these kinds, comma-separated: struct, trait, fn, method, field, ...",
+					},
+					&cli.StringFlag{
+						Name:  "fuzzy",
+						Usage: "Match the pattern fuzzily against symbol names: subsequence (fzf-style, rewards word starts and runs, so gu finds get_user) or edit (typos within 1-2 edits); --explain shows the score components",
+					},
+					&cli.BoolFlag{
+						Name:  "prefix",
+						Usage: "List the symbols whose name starts with the pattern, so get finds get_user but not save_user; looked up in a trie of symbol names, smart-case like a search",
+					},
+					&cli.BoolFlag{
+						Name:  "expand",
+						Usage: "Let abbreviations also match the words they stand for, e.g. cfg finds config and usr finds user; add your own under search { aliases { ... } } in .lci.kdl",
+					},
+					&cli.StringFlag{
+						Name:  "visibility",
+						Usage: "Which symbols' hits to keep: pub (pub and pub(crate) items in Rust, exported names in Go and Python; other languages by their naming conventions) or all",
+						Value: visibilityAll,
+					},
+					&cli.StringFlag{
+						Name:  "term-scope",
+						Usage: "Where the words of a plain query, and none of its -excluded words, must appear: line or symbol (the enclosing symbol)",
+						Value: termScopeLine,
+					},
+					&cli.StringFlag{
+						Name:  "within",
+						Usage: "Only match inside the named symbol: authenticate, AuthService::authenticate or auth.rs:authenticate",
+					},
+					&cli.StringFlag{
+						Name:  "diff",
+						Usage: "Only match on lines added or modified since this git ref, e.g. --diff main (uncommitted changes included)",
+					},
+					&cli.BoolFlag{
+						Name:  "blame",
+						Usage: "Annotate each displayed hit with the commit, author and date that last touched its line, from git blame (omitted outside git repositories)",
+					},
+					&cli.StringFlag{
+						Name:  "file",
+						Usage: "Parse and search only this file in-process, without the index server, walking the tree or ignore files",
+					},
+					&cli.BoolFlag{
+						Name:  "stdin",
+						Usage: "Index standard input in-process as one virtual file and search it, e.g. staged content in a pre-commit hook",
+					},
+					&cli.StringFlag{
+						Name:  "stdin-name",
+						Value: stdinName,
+						Usage: "Path hits in --stdin content are reported in; its extension picks the language unless --lang is set",
+					},
+					&cli.StringFlag{
+						Name:  "lang",
+						Usage: "Language to parse --stdin content as, e.g. rust",
+					},
+					&cli.StringSliceFlag{
+						Name:  "root",
+						Usage: "Search this root; repeat to index and search several roots in-process with merged, globally ranked results",
+					},
+					&cli.StringFlag{
+						Name:  "group-by",
+						Usage: "Group results by: root (keeps each --root's hits together, in flag order) or symbol (nests hits under their enclosing symbol)",
+					},
+					&cli.IntFlag{
+						Name:  "limit",
+						Usage: "Show the best N results after duplicates are merged and results ranked; ties break by path then offset, 0 = unlimited",
+						Value: defaultSearchLimit,
+					},
+					&cli.IntFlag{
+						Name:  "offset",
+						Usage: "Skip the best N ranked results, for paging with --limit; an offset past the last result returns none",
+					},
+					&cli.IntFlag{
+						Name:  "timeout-ms",
+						Usage: "Stop searching further files after N milliseconds and show the hits found so far, flagged timed_out in json output; 0 = no limit",
+					},
+					&cli.IntFlag{
+						Name:  "max-results",
+						Usage: "Stop searching once N hits are found, before ranking and --limit, flagged cap_reached in json output; 0 = the index server's cap of 100000",
+					},
+					&cli.BoolFlag{
+						Name:  "no-suggest",
+						Usage: "Don't suggest symbol names close to the pattern when a search finds nothing",
+					},
+					&cli.IntFlag{
+						Name:  "warn-lines",
+						Usage: "Warn on stderr before printing more than N lines of results to a terminal; 0 = never warn",
+						Value: defaultWarnLines,
+					},
+					&cli.BoolFlag{
+						Name:  "streaming",
+						Usage: "Print hits as they are found, in file discovery order with per-hit scores and no global ranking; --limit N then stops after the first N hits found, not the best N (text or jsonl only)",
+					},
+					&cli.BoolFlag{
+						Name:  "explain",
+						Usage: "Print each result's score breakdown from the active ranker (e.g. BM25 term frequency, idf and length normalization)",
+					},
+					&cli.BoolFlag{
+						Name:  "no-dedup",
+						Usage: "Keep identical hits found by several matchers (e.g. overlapping -e patterns) as separate results",
+					},
+					&cli.IntFlag{
+						Name:  "merge-gap",
+						Usage: "Merge hits in the same file whose lines are at most N apart into one hit spanning them, 0 = off",
+					},
+					&cli.StringFlag{
+						Name:  "merge-score",
+						Usage: "Score of a hit merged by --merge-gap: max (its best hit) or sum (all its hits, so runs of hits rank higher)",
+						Value: "max",
+					},
 					&cli.IntFlag{
 						Name:  "max-count",
 						Usage: "Max matches per file (grep -m NUM), 0 = unlimited",
@@ -235,6 +592,10 @@ func main() {
 						Aliases: []string{"cs"},
 						Usage: "Show compact output (patterns only, no full context)",
 					},
+					&cli.StringSliceFlag{
+						Name:  "boost",
+						Usage: "Rank by symbol proximity, multiplying each hit's score by the weight of its enclosing symbol kind, or doc for comments; repeat for several kinds, e.g. --boost fn=2.0 --boost struct=1.5 --boost doc=0.5",
+					},
 					&cli.StringFlag{
 						Name:  "rank-by",
 						Usage: "Rank results by: relevance, proximity, similarity (default: none)",
@@ -264,9 +625,14 @@ func main() {
 						Value:   3,
 					},
 					&cli.BoolFlag{
-						Name:    "case-insensitive",
-						Aliases: []string{"i"},
-						Usage:   "Case-insensitive search",
+						Name:    "ignore-case",
+						Aliases: []string{"i", "case-insensitive"},
+						Usage:   "Case-insensitive search (default: smart case, case-insensitive unless the pattern has an uppercase letter)",
+					},
+					&cli.BoolFlag{
+						Name:    "case-sensitive",
+						Aliases: []string{"s"},
+						Usage:   "Case-sensitive search, even for all-lowercase patterns",
 					},
 					&cli.BoolFlag{
 						Name:    "json",
@@ -303,6 +669,11 @@ func main() {
 						Aliases: []string{"R"},
 						Usage:   "Interpret pattern as extended regex (supports ^, $, *, +, ?, [], (), |)",
 					},
+					&cli.BoolFlag{
+						Name:    "word-regexp",
+						Aliases: []string{"w", "word"},
+						Usage:   "Match whole words only (grep -w)",
+					},
 				},
 				Action: grepCommand,
 			},
@@ -315,8 +686,20 @@ func main() {
 			{
 				Name:    "refs",
 				Aliases: []string{"r"},
-				Usage:   "Find symbol references",
-				Action:  referencesCommand,
+				Usage:   "Find symbol references: whole-word usages of the name, excluding its definitions",
+				Flags: []cli.Flag{
+					&cli.BoolFlag{
+						Name:  "include-def",
+						Usage: "Also list the symbol's definitions, marked [def]",
+					},
+					&cli.IntFlag{
+						Name:    "max-results",
+						Aliases: []string{"n"},
+						Usage:   "Max number of references",
+						Value:   100,
+					},
+				},
+				Action: referencesCommand,
 			},
 			{
 				Name:    "tree",
@@ -757,7 +1140,8 @@ Examples:
   lci browse internal/mcp/server.go           # Browse file symbols
   lci browse server.go                        # Suffix match
   lci browse server.go --kind func            # Functions only
-  lci browse server.go --exported --stats     # Exported with stats`,
+  lci browse server.go --exported --stats     # Exported with stats
+  lci browse src/main.rs --format lsp-symbols # LSP DocumentSymbol outline`,
 				Flags: []cli.Flag{
 					&cli.StringFlag{
 						Name:    "kind",
@@ -787,9 +1171,95 @@ Examples:
 						Aliases: []string{"j"},
 						Usage:   "Output as JSON",
 					},
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: text, json, lsp-symbols (LSP DocumentSymbol array for editor outlines)",
+						Value: "text",
+					},
 				},
 				Action: browseCommand,
 			},
+			{
+				Name:      "at",
+				Usage:     "Show the symbol defined at a byte offset of a file",
+				ArgsUsage: "<file>:<offset>",
+				Description: `Print the innermost symbol whose span contains the byte offset, for editor
+hover and outline integrations. A method is preferred over the impl or class it
+belongs to; at the boundary between two symbols the one starting there wins.
+
+Examples:
+  lci at src/parser.rs:1042                # Symbol at byte 1042
+  lci at parser.rs:1042 --format json      # Suffix match, JSON output`,
+				Flags: []cli.Flag{
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: text, json",
+						Value: browseFormatText,
+					},
+				},
+				Action: atCommand,
+			},
+			{
+				Name:  "export",
+				Usage: "Write every symbol in the index as JSON Lines, for external tools",
+				Description: `Stream the whole symbol index to stdout without running a query: one object per
+line with path, kind, name, parent, span and doc comment. Files are written in path
+order and each file's symbols in declaration order.
+
+Examples:
+  lci export --format jsonl > symbols.jsonl
+  lci export --format jsonl --export-content   # Also inline each symbol's source`,
+				Flags: []cli.Flag{
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: jsonl",
+						Value: exportFormatJSONL,
+					},
+					&cli.BoolFlag{
+						Name:  "export-content",
+						Usage: "Inline the source bytes of each symbol's span as \"content\"",
+					},
+				},
+				Action: exportCommand,
+			},
+			{
+				Name:  "stats",
+				Usage: "Show the composition of the index: symbols per kind, files per language and the largest symbols",
+				Description: `Summarize the indexed corpus from the symbol index: the count of each symbol
+kind, files per detected language, average symbols per file and the symbols with
+the longest spans.
+
+Examples:
+  lci stats
+  lci stats --format json > corpus.json   # Track composition over time
+  lci stats --top 25`,
+				Flags: []cli.Flag{
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: text or json",
+						Value: searchFormatText,
+					},
+					&cli.IntFlag{
+						Name:  "top",
+						Usage: "Number of largest symbols to list",
+						Value: 10,
+					},
+				},
+				Action: statsCommand,
+			},
+			{
+				Name:      "open",
+				Usage:     "Open hit N of the last `lci search --numbered` in this directory in $VISUAL or $EDITOR",
+				ArgsUsage: "N",
+				Description: `Open a hit of the last numbered search run in the current directory at its line.
+The editor comes from $VISUAL, then $EDITOR, then vi; VS Code, Sublime Text and
+Zed are given file:line:column, other editors +line.
+
+Examples:
+  lci search --numbered "func main"
+  lci open 2`,
+				Action: openCommand,
+			},
 			{
 				Name:    "server",
 				Usage:   "Start persistent index server (shared between CLI and MCP)",
@@ -812,6 +1282,172 @@ The server runs until explicitly shut down with 'lci shutdown'.`,
 				},
 				Action: serverCommand,
 			},
+			{
+				Name:      "watch",
+				Usage:     "Index a directory and re-index changed files as they are saved",
+				ArgsUsage: "[dir]",
+				Description: `Index the directory (default: project root), then watch it for file
+creates, modifications and deletions. Each debounced batch of changes is re-indexed
+incrementally and summarized on a single line.
+
+The watcher also serves the project socket, so 'lci search' and other commands
+issued while it runs always query the freshest index.`,
+				Flags: []cli.Flag{
+					&cli.IntFlag{
+						Name:  "debounce",
+						Usage: "Milliseconds to wait for a burst of file events to settle",
+						Value: defaultWatchDebounceMs,
+					},
+				},
+				Action: watchCommand,
+			},
+			{
+				Name:  "serve",
+				Usage: "Hold the index in memory and answer length-prefixed JSON queries on a Unix socket",
+				Description: `Index the project root, then accept connections on the --socket path. Each
+request and response is a JSON document prefixed with its length as a 4-byte
+big-endian integer; connections are served concurrently and may send any number
+of requests. Send {"type":"reindex"} to rebuild the index, {"type":"stats"} for
+index and search cache statistics, and query it with 'lci query --socket'.
+
+A reindex builds the new index beside the old one, which keeps answering queries,
+and swaps it in once complete; the reply comes after the swap, with the old and
+new symbol counts. Set performance { reindex_in_place true } in .lci.kdl to hold
+only one index in memory instead, failing queries until the rebuild finishes.
+
+Search responses are cached until the index next changes.
+
+The socket file is removed when the server stops.`,
+				Flags: []cli.Flag{
+					&cli.StringFlag{
+						Name:  "socket",
+						Usage: "Unix socket path to listen on (e.g. /tmp/lci.sock)",
+					},
+				},
+				Action: serveCommand,
+			},
+			{
+				Name:      "query",
+				Usage:     "Search the index held by 'lci serve'",
+				ArgsUsage: "<pattern>",
+				Description: `Send one search to a 'lci serve' socket and print "path:line:text" for each
+hit, with --reindex ask the server to rebuild its index, or with --stats print
+its search cache counters.`,
+				Flags: []cli.Flag{
+					&cli.StringFlag{
+						Name:  "socket",
+						Usage: "Unix socket path of the server",
+					},
+					&cli.BoolFlag{
+						Name:  "reindex",
+						Usage: "Rebuild the server's index instead of searching",
+					},
+					&cli.BoolFlag{
+						Name:  "stats",
+						Usage: "Print the server's search cache statistics instead of searching",
+					},
+					&cli.BoolFlag{
+						Name:    "regex",
+						Aliases: []string{"E"},
+						Usage:   "Interpret pattern as a regex",
+					},
+					&cli.BoolFlag{
+						Name:    "ignore-case",
+						Aliases: []string{"i"},
+						Usage:   "Case-insensitive search",
+					},
+					&cli.IntFlag{
+						Name:    "max-results",
+						Aliases: []string{"n"},
+						Usage:   "Max number of results (0 = all)",
+					},
+					&cli.IntFlag{
+						Name:  "timeout-ms",
+						Usage: "Stop searching further files after N milliseconds and return the hits found so far (0 = no limit)",
+					},
+					&cli.BoolFlag{
+						Name:    "json",
+						Aliases: []string{"j"},
+						Usage:   "Print the raw JSON response",
+					},
+				},
+				Action: queryCommand,
+			},
+			{
+				Name:      "bench",
+				Usage:     "Time each search mode (substring, regex, fuzzy, fuzzy-subsequence, symbol) over a fixed query set",
+				ArgsUsage: "[dir]",
+				Description: `Index the directory (default: project root) once, warm it up with an untimed
+pass over every query, then time each query of each search mode and report min,
+median and p95 latency with allocations per query.
+
+Point it at tests/search-comparison/fixtures to compare modes on the shared fixtures.`,
+				Flags: []cli.Flag{
+					&cli.IntFlag{
+						Name:  "iterations",
+						Usage: "Timed runs per query",
+						Value: defaultBenchIterations,
+					},
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: text (table) or json (for CI tracking)",
+						Value: searchFormatText,
+					},
+				},
+				Action: benchCommand,
+			},
+			{
+				Name:      "compare",
+				Usage:     "Run a query under two rankers and diff their rankings",
+				ArgsUsage: "<query>",
+				Description: `Index the project once, run the query under --ranker-a and --ranker-b,
+and report the hits only one of them ranks in its top --limit, the rank change of
+every hit both rank there, and Kendall's tau between the two orderings of the shared
+hits (1 = same order, -1 = reversed).
+
+Rankers: default (built-in scoring), substring, bm25, proximity (symbol proximity).
+With --min-tau, exit with status 3 when tau falls below it, to catch ranking
+regressions in CI:
+
+    lci compare --ranker-a bm25 --ranker-b proximity --format json --min-tau 0.5 "user"`,
+				Flags: []cli.Flag{
+					&cli.StringFlag{
+						Name:     "ranker-a",
+						Usage:    "First ranker: default, substring, bm25 or proximity",
+						Required: true,
+					},
+					&cli.StringFlag{
+						Name:     "ranker-b",
+						Usage:    "Second ranker, compared against the first",
+						Required: true,
+					},
+					&cli.IntFlag{
+						Name:  "limit",
+						Usage: "Compare the top this many hits of each ranking (0 = all)",
+						Value: defaultCompareLimit,
+					},
+					&cli.IntFlag{
+						Name:  "max-results",
+						Usage: "Hits each search keeps before ranking is compared",
+						Value: defaultCompareMaxResults,
+					},
+					&cli.BoolFlag{
+						Name:    "ignore-case",
+						Aliases: []string{"i"},
+						Usage:   "Case insensitive search",
+					},
+					&cli.Float64Flag{
+						Name:  "min-tau",
+						Usage: "Exit with status 3 when Kendall's tau between the rankings is below this",
+					},
+					&cli.StringFlag{
+						Name:  "format",
+						Usage: "Output format: text or json (for CI tracking)",
+						Value: searchFormatText,
+					},
+				},
+				Action: compareCommand,
+			},
 			{
 				Name:    "shutdown",
 				Usage:   "Shutdown the persistent index server",
@@ -872,11 +1508,18 @@ This will free all memory and close the socket.`,
 				return nil
 			}
 
+			absoluteOut = c.Bool("absolute")
+
 			// Initialize config with CLI overrides
 			cfg, err := loadConfigWithOverrides(c)
 			if err != nil {
 				return err
 			}
+			// Subcommand flags are parsed after this hook, so config defaults can still
+			// take the place of built-in flag values
+			if search := c.App.Command("search"); search != nil {
+				applySearchDefaults(search, cfg.Defaults)
+			}
 
 			// Only create indexer if needed
 			command := c.Args().Get(0)
@@ -893,23 +1536,7 @@ This will free all memory and close the socket.`,
 					}
 				})
 
-				// For serve command, start indexing in background
-				if command == "serve" {
-					go func() {
-						start := time.Now()
-						err := indexer.IndexDirectory(context.Background(), cfg.Project.Root)
-						if err != nil {
-							debug.LogIndexing("Indexing error: %v\n", err)
-						} else {
-							debug.LogIndexing("Indexed in %v\n", time.Since(start))
-						}
-					}()
-
-					// File watching is now built into the MasterIndex and started automatically
-					if c.Bool("daemon") {
-						debug.LogIndexing("Running in daemon mode with file watching enabled\n")
-					}
-				} else if command == "mcp" {
+				if command == "mcp" {
 					// Enable MCP mode to suppress all debug output
 					debug.SetMCPMode(true)
 					// For MCP command, do NOT auto-index - let AI assistants control indexing
@@ -966,7 +1593,7 @@ This will free all memory and close the socket.`,
 
 	if err := app.Run(os.Args); err != nil {
 		fmt.Fprintf(os.Stderr, "Fatal error: %v\n", err)
-		os.Exit(1)
+		os.Exit(exitError)
 	}
 }
 
@@ -1025,20 +1652,24 @@ func grepCommand(c *cli.Context) error {
 	pattern := c.Args().First()
 	maxResults := c.Int("max-results")
 	contextLines := c.Int("context")
-	caseInsensitive := c.Bool("case-insensitive")
+	caseInsensitive := c.Bool("ignore-case")
+	smartCase := !caseInsensitive && !c.Bool("case-sensitive")
 	excludePattern := c.String("exclude")
 	includePattern := c.String("include")
 	excludeTests := c.Bool("exclude-tests")
 	excludeComments := c.Bool("exclude-comments")
 	verbose := c.Bool("verbose")
 	useRegex := c.Bool("regex")
+	wordBoundary := c.Bool("word-regexp")
 
 	start := time.Now()
 
 	// Use basic search with grep-optimized options (no semantic analysis)
 	searchOptions := types.SearchOptions{
 		CaseInsensitive:    caseInsensitive,
+		SmartCase:          smartCase,
 		UseRegex:           useRegex,
+		WordBoundary:       wordBoundary,
 		MaxResults:         maxResults, // Pass through max results limit
 		MaxContextLines:    contextLines,
 		ExcludePattern:     excludePattern,
@@ -1057,7 +1688,7 @@ func grepCommand(c *cli.Context) error {
 	}
 
 	// Ensure server is running (auto-start if needed)
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -1071,7 +1702,7 @@ func grepCommand(c *cli.Context) error {
 	elapsed := time.Since(start)
 
 	// Display results in grep-like format
-	return displayGrepResults(c, pattern, results, elapsed)
+	return matchStatus(len(results), displayGrepResults(c, pattern, results, elapsed))
 }
 
 func definitionCommand(c *cli.Context) error {
@@ -1092,7 +1723,7 @@ func definitionCommand(c *cli.Context) error {
 	}
 
 	// Ensure server is running (auto-start if needed)
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -1105,10 +1736,11 @@ func definitionCommand(c *cli.Context) error {
 
 	// Output format: file:line: signature or name
 	for _, r := range results {
+		path := pathutil.ToRelative(r.FilePath, reportRoot(projectRoot))
 		if r.Signature != "" {
-			fmt.Printf("%s:%d: %s\n", r.FilePath, r.Line, r.Signature)
+			fmt.Printf("%s:%d: %s\n", path, r.Line, r.Signature)
 		} else {
-			fmt.Printf("%s:%d: %s %s\n", r.FilePath, r.Line, r.Type, r.Name)
+			fmt.Printf("%s:%d: %s %s\n", path, r.Line, r.Type, r.Name)
 		}
 	}
 
@@ -1133,24 +1765,28 @@ func referencesCommand(c *cli.Context) error {
 	}
 
 	// Ensure server is running (auto-start if needed)
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
 
 	// Use server's references endpoint
-	results, err := client.GetReferences(symbol, maxResults)
+	results, err := client.GetReferences(symbol, maxResults, c.Bool("include-def"))
 	if err != nil {
 		return fmt.Errorf("references search failed: %w", err)
 	}
 
-	// Output format: file:line: context or match (maintaining same format as before)
+	// Output format: file:line: context or match (maintaining same format as before),
+	// with definitions marked when --include-def lists them
 	for _, r := range results {
-		if r.Context != "" {
-			fmt.Printf("%s:%d: %s\n", r.FilePath, r.Line, r.Context)
-		} else {
-			fmt.Printf("%s:%d: %s\n", r.FilePath, r.Line, r.Match)
+		text := r.Context
+		if text == "" {
+			text = r.Match
+		}
+		if r.Definition {
+			text = "[def] " + text
 		}
+		fmt.Printf("%s:%d: %s\n", pathutil.ToRelative(r.FilePath, reportRoot(projectRoot)), r.Line, text)
 	}
 
 	return nil
@@ -1200,7 +1836,7 @@ func treeCommand(c *cli.Context) error {
 	}
 
 	// Ensure server is running (auto-start if needed)
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -1893,7 +2529,7 @@ func gitAnalyzeCommand(c *cli.Context) error {
 	}
 
 	// Ensure server is running (auto-start if needed)
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -2011,7 +2647,7 @@ func symbolsCommand(c *cli.Context) error {
 		return fmt.Errorf("failed to load config: %w", err)
 	}
 
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -2086,7 +2722,7 @@ func inspectCommand(c *cli.Context) error {
 		return fmt.Errorf("failed to load config: %w", err)
 	}
 
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -2159,14 +2795,18 @@ func browseCommand(c *cli.Context) error {
 	}
 
 	filePath := c.Args().First()
-	jsonOutput := c.Bool("json")
+	format := c.String("format")
+	if err := validateBrowseFormat(format); err != nil {
+		return err
+	}
+	jsonOutput := c.Bool("json") || format == browseFormatJSON
 
 	cfg, err := loadConfigWithOverrides(c)
 	if err != nil {
 		return fmt.Errorf("failed to load config: %w", err)
 	}
 
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to index server: %w", err)
 	}
@@ -2183,12 +2823,20 @@ func browseCommand(c *cli.Context) error {
 		exported := true
 		req.Exported = &exported
 	}
+	if format == browseFormatLSPSymbols {
+		req.Max = math.MaxInt32 // An outline needs every symbol, not the first page
+	}
 
 	result, err := client.BrowseFile(req)
 	if err != nil {
 		return fmt.Errorf("browse failed: %w", err)
 	}
 
+	if format == browseFormatLSPSymbols {
+		content, _ := os.ReadFile(result.File.Path) // Unreadable files fall back to byte columns
+		return writeLSPSymbols(os.Stdout, result.Symbols, content)
+	}
+
 	if jsonOutput {
 		encoder := json.NewEncoder(os.Stdout)
 		encoder.SetIndent("", "  ")
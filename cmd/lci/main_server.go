@@ -3,9 +3,12 @@ package main
 import (
 	"context"
 	"fmt"
+	"maps"
 	"os"
 	"os/exec"
 	"os/signal"
+	"slices"
+	"strconv"
 	"syscall"
 	"time"
 
@@ -111,8 +114,9 @@ func shutdownCommand(c *cli.Context) error {
 }
 
 // ensureServerRunning checks if the index server is running, and starts it if not
-// It uses a project-specific socket path based on the configured root directory
-func ensureServerRunning(cfg *config.Config) (*server.Client, error) {
+// It uses a project-specific socket path based on the configured root directory. While a
+// newly started server indexes, progress is shown on stderr (see showIndexProgress).
+func ensureServerRunning(c *cli.Context, cfg *config.Config) (*server.Client, error) {
 	// Get project-specific socket path
 	socketPath := server.GetSocketPathForRoot(cfg.Project.Root)
 	client := server.NewClientWithSocket(socketPath)
@@ -147,6 +151,36 @@ func ensureServerRunning(cfg *config.Config) (*server.Client, error) {
 	if cfg.Project.Root != "" && cfg.Project.Root != "." {
 		args = append([]string{"--root", cfg.Project.Root}, args...)
 	}
+	if cfg.Index.IndexFile != "" {
+		args = append([]string{"--index-file", cfg.Index.IndexFile}, args...)
+	}
+	if !cfg.Index.RespectGitignore {
+		args = append([]string{"--no-ignore"}, args...)
+	}
+	if cfg.Index.TreatAsText {
+		args = append([]string{"--text"}, args...)
+	}
+	if cfg.Index.Tokenize != "" {
+		args = append([]string{"--tokenize", cfg.Index.Tokenize}, args...)
+	}
+	if cfg.Index.MaxDepth != nil {
+		args = append([]string{"--max-depth", strconv.Itoa(*cfg.Index.MaxDepth)}, args...)
+	}
+	if cfg.Index.Sample > 0 {
+		args = append([]string{"--sample", strconv.FormatFloat(cfg.Index.Sample, 'g', -1, 64), "--seed", strconv.FormatInt(cfg.Index.SampleSeed, 10)}, args...)
+	}
+	if c.IsSet("threads") {
+		args = append([]string{"--threads", strconv.Itoa(cfg.Performance.ParallelFileWorkers)}, args...)
+	}
+	if size := c.String("max-file-size"); size != "" {
+		args = append([]string{"--max-file-size", size}, args...)
+	}
+	if cfg.Index.Encoding != "" {
+		args = append([]string{"--encoding", cfg.Index.Encoding}, args...)
+	}
+	for _, ext := range slices.Sorted(maps.Keys(cfg.Index.Languages)) {
+		args = append([]string{"--lang", ext + "=" + cfg.Index.Languages[ext]}, args...)
+	}
 	cmd := exec.Command(executable, args...)
 	cmd.Stdout = nil
 	cmd.Stderr = nil
@@ -164,11 +198,15 @@ func ensureServerRunning(cfg *config.Config) (*server.Client, error) {
 
 	// Wait for server to be ready (with timeout)
 	fmt.Fprintln(os.Stderr, "Waiting for index server to be ready...")
-	if err := client.WaitForReady(30 * time.Second); err != nil {
+	progress := newIndexProgress(os.Stderr, showIndexProgress(c), time.Now())
+	status, err := client.WaitForIndex(30*time.Second, func(status *server.IndexStatus) {
+		progress.update(status, time.Now())
+	})
+	if err != nil {
 		return nil, fmt.Errorf("server did not become ready: %w", err)
 	}
 
-	fmt.Fprintln(os.Stderr, "Index server ready")
+	progress.finish(status, time.Now())
 	return client, nil
 }
 
@@ -227,6 +227,26 @@ func TestServerBasedSearchWorkflow(t *testing.T) {
 	assert.NoError(t, err, "Search should complete cleanly")
 }
 
+// TestSymlinkedRootPaths checks that hits under a symlinked root are reported relative
+// to it, or as absolute paths under its real location with --absolute
+func TestSymlinkedRootPaths(t *testing.T) {
+	projectDir := setupTestProject(t)
+	realDir, err := filepath.EvalSymlinks(projectDir)
+	require.NoError(t, err)
+	link := filepath.Join(t.TempDir(), "link")
+	require.NoError(t, os.Symlink(projectDir, link))
+	defer func() { _, _ = runCLICommand("--root", link, "shutdown") }()
+
+	output, err := runCLICommand("--root", link, "grep", "processData")
+	require.NoError(t, err, output)
+	assert.Contains(t, output, "main.go:")
+	assert.NotContains(t, output, filepath.Join(realDir, "main.go"))
+
+	output, err = runCLICommand("--root", link, "--absolute", "grep", "processData")
+	require.NoError(t, err, output)
+	assert.Contains(t, output, filepath.Join(realDir, "main.go")+":")
+}
+
 // TestCLIDiagnosticCapabilities tests the CLI's diagnostic features for MCP debugging
 func TestCLIDiagnosticCapabilities(t *testing.T) {
 	projectDir := setupTestProject(t)
@@ -289,11 +309,10 @@ func TestCLIErrorHandling(t *testing.T) {
 		{
 			name:      "search for non-existent pattern",
 			args:      []string{"search", "ThisDoesNotExist12345"},
-			expectErr: false,
+			expectErr: true,
 			validate: func(t *testing.T, output string, err error) {
-				assert.NoError(t, err)
-				// Server-based search completes without error
-				// Search with no results shows "Found 0" message
+				// Like grep, a search with no results exits 1 after showing "Found 0"
+				assert.Equal(t, exitNoMatches, exitCode(t, err))
 				assert.Contains(t, output, "Found 0")
 			},
 		},
@@ -0,0 +1,156 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+
+	"github.com/urfave/cli/v2"
+)
+
+// --group-by values: root keeps hits together per --root, symbol nests them under
+// their enclosing symbol
+const (
+	groupByRoot   = "root"
+	groupBySymbol = server.GroupBySymbol
+)
+
+// defaultSearchLimit is the --limit used when none is given
+const defaultSearchLimit = 50
+
+// searchRootsCommand runs `lci search` over several --root values in this process,
+// without the index server, and writes the merged results
+func searchRootsCommand(c *cli.Context, roots []string, pattern string, options types.SearchOptions, offset, limit int, format string, encoding types.PositionEncoding, groupBy string) error {
+	start := time.Now()
+	results, total, err := searchRoots(roots, pattern, options, offset, limit, func(root string) (*config.Config, error) {
+		return loadConfigForRoot(c, root)
+	})
+	if err != nil {
+		return err
+	}
+	if groupBy == groupByRoot {
+		groupResultsByRoot(results, roots)
+	}
+	if c.Bool("blame") {
+		annotateBlame(results, "")
+	}
+	elapsed := time.Since(start)
+
+	if tmpl := hitTemplateFlag(c); tmpl != nil {
+		writeTemplateResults(os.Stdout, tmpl, results, newHitPositions(encoding, ""))
+		return matchStatus(total, nil)
+	}
+	if c.Bool("json") && !isStructuredFormat(format) {
+		format = searchFormatJSON
+	}
+	if isStructuredFormat(format) {
+		return matchStatus(total, writeFormattedResults(os.Stdout, format, pattern, results, searchPage{total: total, offset: offset}, elapsed, newHitPositions(encoding, "")))
+	}
+	displayRootResults(c, results, total, len(roots), groupBy == groupByRoot, elapsed)
+	return matchStatus(total, nil)
+}
+
+// searchRoots indexes each root, runs the search on it and merges the results ranked
+// by score across all roots, keeping limit of them (0 = all) from rank offset on.
+// Each result is tagged with the root it came from and its path is relative to that
+// root. Equal scores break by path then offset, then keep root order. The returned
+// total counts the hits found in every root before the offset and limit.
+func searchRoots(roots []string, pattern string, options types.SearchOptions, offset, limit int, loadConfig func(root string) (*config.Config, error)) ([]search.GrepResult, int, error) {
+	// Any of the page's hits may come from a single root
+	perRoot := limit
+	if limit > 0 {
+		perRoot = offset + limit
+	}
+
+	var merged []search.GrepResult
+	total := 0
+	for _, root := range roots {
+		cfg, err := loadConfig(root)
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to load config for %s: %w", root, err)
+		}
+		results, found, err := searchRoot(cfg, pattern, options, perRoot)
+		if err != nil {
+			return nil, 0, fmt.Errorf("%s: %w", root, err)
+		}
+		for i := range results {
+			results[i].Root = root
+		}
+		merged = append(merged, results...)
+		total += found
+	}
+	return search.PageResults(merged, offset, limit), total, nil
+}
+
+// searchRoot indexes cfg.Project.Root and searches it the way the index server does,
+// returning its best limit hits and the number found
+func searchRoot(cfg *config.Config, pattern string, options types.SearchOptions, limit int) ([]search.GrepResult, int, error) {
+	rootIndexer := indexing.NewMasterIndex(cfg)
+	defer rootIndexer.Close()
+	if err := rootIndexer.IndexDirectory(context.Background(), cfg.Project.Root); err != nil {
+		return nil, 0, fmt.Errorf("failed to index: %w", err)
+	}
+
+	results, err := searchIndex(rootIndexer, pattern, options)
+	if err != nil {
+		return nil, 0, err
+	}
+	total := len(results)
+	results = pathutil.ToRelativeGrepResults(results, reportRoot(cfg.Project.Root))
+	return search.LimitResults(results, limit), total, nil
+}
+
+// searchIndex runs a search on an index built in this process, picking the query,
+// regex or plain search the way the index server does
+func searchIndex(indexer *indexing.MasterIndex, pattern string, options types.SearchOptions) ([]search.GrepResult, error) {
+	engine := search.NewEngine(indexer)
+	switch {
+	case options.Query:
+		return engine.SearchQuery(pattern, options)
+	case options.UseRegex:
+		return engine.SearchRegex(pattern, options)
+	}
+	return engine.SearchWithOptions(pattern, nil, options), nil
+}
+
+// groupResultsByRoot reorders ranked results so each root's hits are contiguous, in
+// the order the roots were given, keeping the ranking within each root
+func groupResultsByRoot(results []search.GrepResult, roots []string) {
+	order := make(map[string]int, len(roots))
+	for i, root := range roots {
+		if _, ok := order[root]; !ok {
+			order[root] = i
+		}
+	}
+	sort.SliceStable(results, func(i, j int) bool {
+		return order[results[i].Root] < order[results[j].Root]
+	})
+}
+
+// displayRootResults prints merged results with paths prefixed by their root, under a
+// header per root when grouped
+func displayRootResults(c *cli.Context, results []search.GrepResult, total, rootCount int, grouped bool, elapsed time.Duration) {
+	fmt.Printf("Found %d results across %d roots in %.1fms\n\n", total, rootCount, float64(elapsed.Microseconds())/1000.0)
+	printLimitNote(c.Int("offset"), len(results), total)
+
+	formatter := snippetFormatter(c)
+	jumps := newJumpRecorder(c, "") // Paths below are joined with their root already
+	for i, r := range results {
+		if grouped && (i == 0 || results[i-1].Root != r.Root) {
+			fmt.Printf("== %s ==\n\n", r.Root)
+		}
+		r.Path = filepath.Join(r.Root, r.Path)
+		printRegularResult(c, r, formatter, jumps)
+	}
+	jumps.save(c.Args().First())
+}
@@ -0,0 +1,81 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchRoots(t *testing.T) {
+	base := t.TempDir()
+	files := map[string]string{
+		"rust-sample/src/main.rs": "fn shared_token() {}\n",
+		"go-sample/main.go":       "package main\n\nfunc sharedToken() {}\n\n// sharedToken again\n",
+	}
+	for path, content := range files {
+		full := filepath.Join(base, path)
+		require.NoError(t, os.MkdirAll(filepath.Dir(full), 0755))
+		require.NoError(t, os.WriteFile(full, []byte(content), 0644))
+	}
+
+	roots := []string{filepath.Join(base, "rust-sample"), filepath.Join(base, "go-sample")}
+	loadConfig := func(root string) (*config.Config, error) {
+		return &config.Config{
+			Project: config.Project{Root: root},
+			Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+			Include: []string{"*.go", "*.rs"},
+		}, nil
+	}
+
+	results, total, err := searchRoots(roots, "shared", types.SearchOptions{CaseInsensitive: true}, 0, 0, loadConfig)
+	require.NoError(t, err)
+	require.NotEmpty(t, results)
+	assert.Equal(t, len(results), total)
+
+	limited, limitedTotal, err := searchRoots(roots, "shared", types.SearchOptions{CaseInsensitive: true}, 0, 1, loadConfig)
+	require.NoError(t, err)
+	require.Len(t, limited, 1)
+	assert.Equal(t, total, limitedTotal, "the total counts hits dropped by the limit")
+	assert.Equal(t, [3]interface{}{results[0].Root, results[0].Path, results[0].StartByte},
+		[3]interface{}{limited[0].Root, limited[0].Path, limited[0].StartByte}, "the limit keeps the best hit")
+
+	seen := make(map[string]bool)
+	for i, r := range results {
+		seen[r.Root] = true
+		assert.False(t, filepath.IsAbs(r.Path), "paths are relative to their root: %s", r.Path)
+		if i > 0 {
+			assert.GreaterOrEqual(t, results[i-1].Score, r.Score, "results are ranked across roots")
+		}
+	}
+	assert.True(t, seen[roots[0]] && seen[roots[1]], "both roots contribute hits")
+
+	groupResultsByRoot(results, roots)
+	for i := 1; i < len(results); i++ {
+		if results[i-1].Root == roots[1] {
+			assert.Equal(t, roots[1], results[i].Root, "grouped hits stay contiguous in flag order")
+		}
+	}
+}
+
+func TestGroupResultsByRoot(t *testing.T) {
+	results := []search.GrepResult{
+		{Root: "b", Path: "b1", Score: 9},
+		{Root: "a", Path: "a1", Score: 8},
+		{Root: "b", Path: "b2", Score: 7},
+		{Root: "a", Path: "a2", Score: 6},
+	}
+	groupResultsByRoot(results, []string{"a", "b"})
+
+	var paths []string
+	for _, r := range results {
+		paths = append(paths, r.Path)
+	}
+	assert.Equal(t, []string{"a1", "a2", "b1", "b2"}, paths)
+}
@@ -0,0 +1,77 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"unicode"
+
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// validatePrefix rejects the flags a symbol-name prefix search cannot honor
+func validatePrefix(useRegex, useQuery bool, fuzzy, within string, diff bool, format, singleFile string, roots int) error {
+	switch {
+	case useRegex || useQuery || fuzzy != "":
+		return errors.New("--prefix cannot be combined with --regex, --query or --fuzzy")
+	case within != "" || diff:
+		return errors.New("--prefix matches symbol names; it cannot be combined with --within or --diff")
+	case singleFile != "" || roots > 1:
+		return errors.New("--prefix searches the project index; it cannot be combined with --file or several --root")
+	case format != "" && format != searchFormatText && format != searchFormatJSON:
+		return fmt.Errorf("--prefix writes %s or %s, not %q", searchFormatText, searchFormatJSON, format)
+	}
+	return nil
+}
+
+// prefixIgnoresCase reports whether a prefix search for prefix ignores case: always
+// with --ignore-case, never with --case-sensitive, and otherwise, as for a smart-case
+// search, unless prefix holds an uppercase letter
+func prefixIgnoresCase(prefix string, caseInsensitive, smartCase bool) bool {
+	if caseInsensitive || !smartCase {
+		return caseInsensitive
+	}
+	for _, r := range prefix {
+		if unicode.IsUpper(r) {
+			return false
+		}
+	}
+	return true
+}
+
+// prefixSearchCommand prints the symbols whose name starts with prefix, one
+// "path:line:column kind Parent.name [start,end)" line each, in name order
+func prefixSearchCommand(c *cli.Context, prefix string, ignoreCase bool, limit int, format string) error {
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+
+	client, err := ensureServerRunning(c, cfg)
+	if err != nil {
+		return fmt.Errorf("failed to connect to index server: %w", err)
+	}
+
+	result, err := client.SymbolPrefix(server.SymbolPrefixRequest{Prefix: prefix, IgnoreCase: ignoreCase, Max: limit})
+	if err != nil {
+		return fmt.Errorf("search failed: %w", err)
+	}
+	for i := range result.Symbols {
+		result.Symbols[i].File = pathutil.ToRelative(result.Symbols[i].File, reportRoot(projectRoot))
+	}
+
+	if format == searchFormatJSON {
+		encoder := json.NewEncoder(os.Stdout)
+		encoder.SetIndent("", "  ")
+		return matchStatus(len(result.Symbols), encoder.Encode(result.Symbols))
+	}
+	for i := range result.Symbols {
+		if err := writeSymbolAt(os.Stdout, &result.Symbols[i]); err != nil {
+			return err
+		}
+	}
+	return matchStatus(len(result.Symbols), nil)
+}
@@ -0,0 +1,22 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestPrefixIgnoresCase(t *testing.T) {
+	assert.True(t, prefixIgnoresCase("get", false, true), "smart case ignores case for a lowercase prefix")
+	assert.False(t, prefixIgnoresCase("Get", false, true), "an uppercase letter makes it case-sensitive")
+	assert.True(t, prefixIgnoresCase("Get", true, false), "--ignore-case wins")
+	assert.False(t, prefixIgnoresCase("get", false, false), "--case-sensitive wins")
+}
+
+func TestValidatePrefix(t *testing.T) {
+	assert.NoError(t, validatePrefix(false, false, "", "", false, searchFormatJSON, "", 1))
+	assert.Error(t, validatePrefix(true, false, "", "", false, "", "", 0))
+	assert.Error(t, validatePrefix(false, false, "edit", "", false, "", "", 0))
+	assert.Error(t, validatePrefix(false, false, "", "", false, "", "src/main.rs", 0))
+	assert.Error(t, validatePrefix(false, false, "", "", false, searchFormatJSONL, "", 0))
+}
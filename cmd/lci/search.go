@@ -10,6 +10,7 @@ import (
 	"strings"
 	"time"
 
+	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/core"
 	"github.com/standardbeagle/lci/internal/indexing"
 	"github.com/standardbeagle/lci/internal/search"
@@ -17,20 +18,32 @@ import (
 	"github.com/standardbeagle/lci/internal/types"
 	"github.com/standardbeagle/lci/pkg/pathutil"
 
+	"github.com/bmatcuk/doublestar/v4"
 	"github.com/urfave/cli/v2"
 )
 
 func searchCommand(c *cli.Context) error {
+	singleFile := c.String("file")
+	if c.String("format") == browseFormatLSPSymbols {
+		if singleFile == "" {
+			return fmt.Errorf("--format %s outlines one file; use it with --file", browseFormatLSPSymbols)
+		}
+		return outlineFileCommand(c, singleFile)
+	}
 	if c.NArg() < 1 {
 		return errors.New("usage: lci search <pattern>")
 	}
 
 	pattern := c.Args().First()
 	maxLines := c.Int("max-lines")
-	caseInsensitive := c.Bool("case-insensitive")
+	caseInsensitive := c.Bool("ignore-case")
+	if c.Bool("case-sensitive") && !c.IsSet("ignore-case") {
+		caseInsensitive = false // Beats an ignore_case default from .lci.kdl
+	}
+	smartCase := !caseInsensitive && !c.Bool("case-sensitive")
 	light := c.Bool("light")
-	excludePattern := c.String("exclude")
-	includePattern := c.String("include")
+	excludeGlobs := c.StringSlice("exclude")
+	includeGlobs := c.StringSlice("include")
 	commentsOnly := c.Bool("comments-only")
 	codeOnly := c.Bool("code-only")
 	stringsOnly := c.Bool("strings-only")
@@ -45,20 +58,209 @@ func searchCommand(c *cli.Context) error {
 	rankBy := c.String("rank-by")
 	contextFilter := c.String("context-filter")
 
-	// Debug: test if flags are being read
-	fmt.Printf("DEBUG: verbose=%v, compact=%v, json=%v, light=%v\n", verbose, compact, c.Bool("json"), light)
+	format := c.String("format")
+	if err := validateSearchFormat(format); err != nil {
+		return err
+	}
+	if err := validateColorMode(c.String("color")); err != nil {
+		return err
+	}
+	if err := validateSnippetFormat(c.String("snippet-format")); err != nil {
+		return err
+	}
+	if err := validatePathGlobs("include", includeGlobs); err != nil {
+		return err
+	}
+	if err := validatePathGlobs("exclude", excludeGlobs); err != nil {
+		return err
+	}
+	positionEncoding, err := types.ParsePositionEncoding(c.String("position-encoding"))
+	if err != nil {
+		return fmt.Errorf("--position-encoding: %w", err)
+	}
+	snippetContext := c.Int("context")
+	if snippetContext < 0 {
+		return fmt.Errorf("--context must not be negative, got %d", snippetContext)
+	}
+	limit := c.Int("limit")
+	if limit < 0 {
+		return fmt.Errorf("--limit must not be negative, got %d", limit)
+	}
+	offset := c.Int("offset")
+	if offset < 0 {
+		return fmt.Errorf("--offset must not be negative, got %d", offset)
+	}
+	timeoutMs := c.Int("timeout-ms")
+	if timeoutMs < 0 {
+		return fmt.Errorf("--timeout-ms must not be negative, got %d", timeoutMs)
+	}
+	maxResults := c.Int("max-results")
+	if maxResults < 0 {
+		return fmt.Errorf("--max-results must not be negative, got %d", maxResults)
+	}
+	if warnLines := c.Int("warn-lines"); warnLines < 0 {
+		return fmt.Errorf("--warn-lines must not be negative, got %d", warnLines)
+	}
+	if width := c.Int("max-snippet-width"); width < 0 {
+		return fmt.Errorf("--max-snippet-width must not be negative, got %d", width)
+	}
+	mergeGap := c.Int("merge-gap")
+	if mergeGap < 0 {
+		return fmt.Errorf("--merge-gap must not be negative, got %d", mergeGap)
+	}
+	mergeScore := c.String("merge-score")
+	if mergeScore != search.MergeScoreMax && mergeScore != search.MergeScoreSum {
+		return fmt.Errorf("unknown --merge-score %q (expected %s or %s)", mergeScore, search.MergeScoreMax, search.MergeScoreSum)
+	}
+	if c.IsSet("merge-score") && mergeGap == 0 {
+		return errors.New("--merge-score requires --merge-gap")
+	}
 
 	// Grep-like feature flags
 	invertMatch := c.Bool("invert-match")
 	patterns := c.StringSlice("patterns")
 	countPerFile := c.Bool("count")
+	countBy := c.String("count-by")
 	filesOnly := c.Bool("files-with-matches")
 	wordBoundary := c.Bool("word-regexp")
+	ignoreWhitespace := c.Bool("ignore-whitespace")
 	useRegex := c.Bool("regex")
+	multiline := c.Bool("multiline")
+	if multiline && !useRegex {
+		return errors.New("--multiline requires --regex")
+	}
+	symbolsOnly := c.Bool("symbols-only")
+	useQuery := c.Bool("query")
+	within := c.String("within")
+	if useQuery && (useRegex || within != "") {
+		return errors.New("--query cannot be combined with --regex or --within")
+	}
+	stripGenerics := c.Bool("sig-strip-generics")
+	if stripGenerics && !useQuery {
+		return errors.New("--sig-strip-generics applies to sig: terms and requires --query")
+	}
+	docsOnly, inCode, err := parseSearchScope(c.String("in"), symbolsOnly, useQuery)
+	if err != nil {
+		return err
+	}
+	codeOnly = codeOnly || inCode
+	termsInSymbol, err := parseTermScope(c.String("term-scope"))
+	if err != nil {
+		return err
+	}
+	fuzzy := c.String("fuzzy")
+	if fuzzy != "" {
+		if err := validateFuzzy(fuzzy, useRegex, useQuery, within, c.String("diff") != ""); err != nil {
+			return err
+		}
+	}
+	stdin := c.Bool("stdin")
+	if err := validateStdin(c, stdin, singleFile); err != nil {
+		return err
+	}
+	if c.Bool("prefix") {
+		if err := validatePrefix(useRegex, useQuery, fuzzy, within, c.String("diff") != "", format, singleFile, len(c.StringSlice("root"))); err != nil {
+			return err
+		}
+		return prefixSearchCommand(c, pattern, prefixIgnoresCase(pattern, caseInsensitive, smartCase), limit, format)
+	}
+	publicOnly, err := parseVisibility(c.String("visibility"))
+	if err != nil {
+		return err
+	}
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+	var aliases map[string][]string
+	if c.Bool("expand") {
+		if useRegex || useQuery || fuzzy != "" {
+			return errors.New("--expand expands the words of a plain search; it cannot be combined with --regex, --query or --fuzzy")
+		}
+		aliases = searchAliases(cfg)
+	}
+	noDedup := c.Bool("no-dedup")
+	explain := c.Bool("explain")
+	symbolTypes, err := parseKindFlag(c.String("kind"))
+	if err != nil {
+		return err
+	}
+	kindBoosts, err := search.ParseKindBoosts(c.StringSlice("boost"))
+	if err != nil {
+		return fmt.Errorf("--boost: %w", err)
+	}
 	maxCountPerFile := c.Int("max-count")
 	includeIDs := c.Bool("ids")
 	noIDs := c.Bool("no-ids")
 
+	roots := c.StringSlice("root")
+	groupBy := c.String("group-by")
+	streaming := c.Bool("streaming")
+	counting := countPerFile || countBy != ""
+	if err := validateGroupBy(groupBy, len(roots), streaming, counting, filesOnly); err != nil {
+		return err
+	}
+	multiRoot := len(roots) > 1 || groupBy == groupByRoot
+	if multiRoot && compareSearch {
+		return errors.New("--compare-search searches a single root")
+	}
+	if counting {
+		if err := validateCounting(countBy, streaming, filesOnly); err != nil {
+			return err
+		}
+	}
+	blame := c.Bool("blame")
+	if blame {
+		if err := validateBlame(streaming, counting, filesOnly, groupBy); err != nil {
+			return err
+		}
+	}
+	if mergeGap > 0 && (counting || filesOnly) {
+		return errors.New("--merge-gap merges hits; it cannot be combined with --count, --count-by or --files-with-matches")
+	}
+	if publicOnly && (counting || filesOnly) {
+		return errors.New("--visibility judges each hit by its enclosing symbol; it cannot be combined with --count, --count-by or --files-with-matches")
+	}
+	if err := validateTemplate(c, format, counting, groupBy); err != nil {
+		return err
+	}
+	locations := c.Bool("locations")
+	if locations {
+		if err := validateLocations(c, counting, filesOnly, multiRoot, streaming); err != nil {
+			return err
+		}
+		snippetContext = 0
+	}
+	if streaming {
+		if err := validateStreaming(format, multiRoot, compareSearch, offset); err != nil {
+			return err
+		}
+	}
+	if timeoutMs > 0 {
+		if err := validateTimeout(multiRoot, streaming, counting); err != nil {
+			return err
+		}
+	}
+	if singleFile != "" {
+		if err := validateSingleFile("--file", multiRoot, streaming, counting, compareSearch, groupBy, timeoutMs); err != nil {
+			return err
+		}
+	}
+	if stdin {
+		if err := validateSingleFile("--stdin", multiRoot, streaming, counting, compareSearch, groupBy, timeoutMs); err != nil {
+			return err
+		}
+	}
+	var changedLines map[string][]types.LineRange
+	if diffRef := c.String("diff"); diffRef != "" {
+		if err := validateDiff(multiRoot, useQuery); err != nil {
+			return err
+		}
+		if changedLines, err = diffChangedLines(diffRef); err != nil {
+			return err
+		}
+	}
+
 	// Determine final object ID setting
 	// --ids forces inclusion, --no-ids forces exclusion
 	// Default: include object IDs (MCP-friendly)
@@ -77,22 +279,29 @@ func searchCommand(c *cli.Context) error {
 		defer pprof.StopCPUProfile()
 	}
 
-	// Load configuration and ensure server is running
-	cfg, err := loadConfigWithOverrides(c)
-	if err != nil {
-		return fmt.Errorf("failed to load config: %w", err)
-	}
+	// Load configuration and ensure server is running; several roots, a single --file
+	// or --stdin are indexed in this process instead
+	var client *server.Client
+	if !multiRoot && singleFile == "" && !stdin {
+		if verbose {
+			if cfg.File != "" {
+				fmt.Fprintf(os.Stderr, "Config: %s\n", cfg.File)
+			} else {
+				fmt.Fprintf(os.Stderr, "Config: none found, using built-in defaults\n")
+			}
+		}
 
-	client, err := ensureServerRunning(cfg)
-	if err != nil {
-		return fmt.Errorf("failed to connect to index server: %w", err)
+		client, err = ensureServerRunning(c, cfg)
+		if err != nil {
+			return fmt.Errorf("failed to connect to index server: %w", err)
+		}
 	}
 
 	start := time.Now()
 
 	// Handle A/B testing comparison
 	if compareSearch {
-		return compareSearchImplementationsWithClient(c, client, pattern, maxLines, caseInsensitive, light, excludePattern, includePattern, verbose)
+		return compareSearchImplementationsWithClient(c, client, pattern, maxLines, caseInsensitive, smartCase, light, excludeGlobs, includeGlobs, verbose)
 	}
 
 	if light {
@@ -102,64 +311,193 @@ func searchCommand(c *cli.Context) error {
 
 		searchOptions := types.SearchOptions{
 			CaseInsensitive:    caseInsensitive,
+			SmartCase:          smartCase,
 			MaxContextLines:    maxLines,
-			ExcludePattern:     excludePattern,
-			IncludePattern:     includePattern,
+			ExcludeGlobs:       excludeGlobs,
+			IncludeGlobs:       includeGlobs,
 			CommentsOnly:       commentsOnly,
 			CodeOnly:           codeOnly,
+			ExcludeComments:    inCode,
 			StringsOnly:        stringsOnly,
 			TemplateStrings:    templateStrings,
 			Verbose:            verbose,
 			MergeFileResults:   true,
 			EnsureCompleteStmt: false,
+			LocationsOnly:      locations,
 			UseRegex:           useRegex,
+			Multiline:          multiline,
+			Terms:              true, // Words match anywhere on the line; quote a phrase to match it as written
+			TermsInSymbol:      termsInSymbol,
+			Aliases:            aliases,
+			SymbolsOnly:        symbolsOnly,
+			DocsOnly:           docsOnly,
+			Query:              useQuery,
+			SymbolTypes:        symbolTypes,
+			Within:             within,
+			Fuzzy:              fuzzy,
+			ExportedOnly:       publicOnly,
+			ChangedLines:       changedLines,
+			NoDedup:            noDedup,
+			Explain:            explain,
+			ResultCap:          maxResults,
+			MergeGap:           mergeGap,
+			MergeScore:         mergeScore,
+			KindBoosts:         kindBoosts,
 			// Grep-like features
-			InvertMatch:     invertMatch,
-			Patterns:        patterns,
-			CountPerFile:    countPerFile,
-			FilesOnly:       filesOnly,
-			WordBoundary:    wordBoundary,
-			MaxCountPerFile: maxCountPerFile,
+			InvertMatch:      invertMatch,
+			Patterns:         patterns,
+			CountPerFile:     countPerFile,
+			FilesOnly:        filesOnly,
+			WordBoundary:     wordBoundary,
+			IgnoreWhitespace: ignoreWhitespace,
+			MaxCountPerFile:  maxCountPerFile,
+			SnippetContext:   snippetContext,
+			// Query language
+			SignatureStripGenerics: stripGenerics,
 		}
 
-		results, err := client.Search(pattern, searchOptions, 500)
+		if singleFile != "" {
+			return searchFileCommand(c, singleFile, pattern, searchOptions, offset, limit, format, positionEncoding)
+		}
+		if stdin {
+			return searchStdinCommand(c, pattern, searchOptions, offset, limit, format, positionEncoding)
+		}
+		if counting {
+			return countSearchCommand(c, client, roots, pattern, searchOptions, format)
+		}
+		if multiRoot {
+			return searchRootsCommand(c, roots, pattern, searchOptions, offset, limit, format, positionEncoding, groupBy)
+		}
+		if streaming {
+			return streamSearchCommand(c, client, pattern, searchOptions, limit, format, positionEncoding)
+		}
+		req := server.SearchRequest{Pattern: pattern, Options: searchOptions, Offset: offset, MaxResults: limit, TimeoutMs: timeoutMs, Suggest: !c.Bool("no-suggest")}
+		if groupBy == groupBySymbol {
+			return groupedSearchCommand(c, client, req, format, positionEncoding)
+		}
+		resp, err := client.SearchWithRequest(req)
 		if err != nil {
 			fmt.Fprintf(os.Stderr, "ERROR: %v\n", err)
-			return cli.Exit(err.Error(), 2)
+			return cli.Exit(err.Error(), exitError)
+		}
+		if blame {
+			annotateBlame(resp.Results, projectRoot)
 		}
 		elapsed := time.Since(start)
-		return displayRegularResults(c, pattern, results, elapsed, compact)
+		page := responsePage(resp, offset)
+		if locations {
+			return matchStatus(resp.Total, writeLocations(os.Stdout, format, pattern, pathutil.ToRelativeGrepResults(resp.Results, reportRoot(projectRoot)), page, elapsed, newHitPositions(positionEncoding, projectRoot)))
+		}
+		if isStructuredFormat(format) {
+			return matchStatus(resp.Total, writeFormattedResults(os.Stdout, format, pattern, pathutil.ToRelativeGrepResults(resp.Results, reportRoot(projectRoot)), page, elapsed, newHitPositions(positionEncoding, projectRoot)))
+		}
+		printTimeoutNote(resp.TimedOut, timeoutMs)
+		printCapNote(resp.CapReached, resp.Total)
+		printSuggestions(resp.Suggestions)
+		tmpl := hitTemplateFlag(c)
+		warnLargeOutput(c, resp.Results, tmpl != nil || compact)
+		if tmpl != nil {
+			writeTemplateResults(os.Stdout, tmpl, pathutil.ToRelativeGrepResults(resp.Results, reportRoot(projectRoot)), newHitPositions(positionEncoding, projectRoot))
+			return matchStatus(resp.Total, nil)
+		}
+		return matchStatus(resp.Total, displayRegularResults(c, pattern, resp.Results, resp.Total, elapsed, compact))
 	} else {
 		// DEFAULT: Use StandardResult with full semantic analysis
 		searchOptions := types.SearchOptions{
 			CaseInsensitive:    caseInsensitive,
+			SmartCase:          smartCase,
 			MaxContextLines:    maxLines,
-			ExcludePattern:     excludePattern,
-			IncludePattern:     includePattern,
+			ExcludeGlobs:       excludeGlobs,
+			IncludeGlobs:       includeGlobs,
 			CommentsOnly:       commentsOnly,
 			CodeOnly:           codeOnly,
+			ExcludeComments:    inCode,
 			StringsOnly:        stringsOnly,
 			TemplateStrings:    templateStrings,
 			Verbose:            verbose,
 			MergeFileResults:   true,
-			EnsureCompleteStmt: true, // Enable complete statements for better context
+			EnsureCompleteStmt: !locations, // Enable complete statements for better context
+			LocationsOnly:      locations,
 			UseRegex:           useRegex,
+			Multiline:          multiline,
+			Terms:              true, // Words match anywhere on the line; quote a phrase to match it as written
+			TermsInSymbol:      termsInSymbol,
+			Aliases:            aliases,
+			SymbolsOnly:        symbolsOnly,
+			DocsOnly:           docsOnly,
+			Query:              useQuery,
+			SymbolTypes:        symbolTypes,
+			Within:             within,
+			Fuzzy:              fuzzy,
+			ExportedOnly:       publicOnly,
+			ChangedLines:       changedLines,
+			NoDedup:            noDedup,
+			Explain:            explain,
+			ResultCap:          maxResults,
+			MergeGap:           mergeGap,
+			MergeScore:         mergeScore,
+			KindBoosts:         kindBoosts,
 			// Grep-like features
 			InvertMatch:      invertMatch,
 			Patterns:         patterns,
 			CountPerFile:     countPerFile,
 			FilesOnly:        filesOnly,
 			WordBoundary:     wordBoundary,
+			IgnoreWhitespace: ignoreWhitespace,
 			MaxCountPerFile:  maxCountPerFile,
 			IncludeObjectIDs: includeObjectIDs,
 			ContextFilter:    contextFilter,
+			SnippetContext:   snippetContext,
+			// Query language
+			SignatureStripGenerics: stripGenerics,
+		}
+
+		if singleFile != "" {
+			return searchFileCommand(c, singleFile, pattern, searchOptions, offset, limit, format, positionEncoding)
+		}
+		if stdin {
+			return searchStdinCommand(c, pattern, searchOptions, offset, limit, format, positionEncoding)
+		}
+		if counting {
+			return countSearchCommand(c, client, roots, pattern, searchOptions, format)
+		}
+		if multiRoot {
+			return searchRootsCommand(c, roots, pattern, searchOptions, offset, limit, format, positionEncoding, groupBy)
+		}
+		if streaming {
+			return streamSearchCommand(c, client, pattern, searchOptions, limit, format, positionEncoding)
+		}
+		req := server.SearchRequest{Pattern: pattern, Options: searchOptions, Offset: offset, MaxResults: limit, TimeoutMs: timeoutMs, Suggest: !c.Bool("no-suggest")}
+		if groupBy == groupBySymbol {
+			return groupedSearchCommand(c, client, req, format, positionEncoding)
 		}
 
 		// Use server search and convert to StandardResult format
-		grepResults, err := client.Search(pattern, searchOptions, 500)
+		resp, err := client.SearchWithRequest(req)
 		if err != nil {
 			return fmt.Errorf("search failed: %w", err)
 		}
+		grepResults, total := resp.Results, resp.Total
+		if blame {
+			annotateBlame(grepResults, projectRoot)
+		}
+
+		if locations {
+			return matchStatus(total, writeLocations(os.Stdout, format, pattern, pathutil.ToRelativeGrepResults(grepResults, reportRoot(projectRoot)), responsePage(resp, offset), time.Since(start), newHitPositions(positionEncoding, projectRoot)))
+		}
+		if isStructuredFormat(format) {
+			page := responsePage(resp, offset)
+			return matchStatus(total, writeFormattedResults(os.Stdout, format, pattern, pathutil.ToRelativeGrepResults(grepResults, reportRoot(projectRoot)), page, time.Since(start), newHitPositions(positionEncoding, projectRoot)))
+		}
+		printTimeoutNote(resp.TimedOut, timeoutMs)
+		printCapNote(resp.CapReached, total)
+		printSuggestions(resp.Suggestions)
+		tmpl := hitTemplateFlag(c)
+		warnLargeOutput(c, grepResults, tmpl != nil || compact)
+		if tmpl != nil {
+			writeTemplateResults(os.Stdout, tmpl, pathutil.ToRelativeGrepResults(grepResults, reportRoot(projectRoot)), newHitPositions(positionEncoding, projectRoot))
+			return matchStatus(total, nil)
+		}
 
 		// Convert GrepResult to StandardResult for display
 		standardResults := make([]search.StandardResult, len(grepResults))
@@ -225,17 +563,122 @@ func searchCommand(c *cli.Context) error {
 			}
 		}
 
-		return displayStandardResultsWithAssembly(c, pattern, standardResults, assemblyResults, assemblyTriggered, elapsed, compact, rankBy)
+		return matchStatus(total+len(assemblyResults), displayStandardResultsWithAssembly(c, pattern, standardResults, total, assemblyResults, assemblyTriggered, elapsed, compact, rankBy))
 	}
 }
 
+// parseKindFlag converts --kind into the SymbolTypes search option, e.g. "trait,fn" to
+// ["trait", "function"]
+func parseKindFlag(kinds string) ([]string, error) {
+	parsed, err := types.ParseSymbolTypeList(kinds)
+	if err != nil {
+		return nil, fmt.Errorf("--kind: %w", err)
+	}
+	var names []string
+	for _, st := range parsed {
+		names = append(names, st.String())
+	}
+	return names, nil
+}
+
+// Scopes accepted by --in
+const (
+	searchInAll  = "all"
+	searchInCode = "code"
+	searchInDocs = "docs"
+)
+
+// parseSearchScope converts --in into the DocsOnly and code-only search options. Doc
+// search matches symbols rather than content, so it excludes the other symbol modes.
+func parseSearchScope(scope string, symbolsOnly, query bool) (docsOnly, codeOnly bool, err error) {
+	switch scope {
+	case "", searchInAll:
+		return false, false, nil
+	case searchInCode:
+		return false, true, nil
+	case searchInDocs:
+		if symbolsOnly || query {
+			return false, false, errors.New("--in docs cannot be combined with --symbols-only or --query")
+		}
+		return true, false, nil
+	}
+	return false, false, fmt.Errorf("unknown --in %q (expected %s, %s or %s)", scope, searchInAll, searchInCode, searchInDocs)
+}
+
+// Units accepted by --term-scope
+const (
+	termScopeLine   = "line"
+	termScopeSymbol = "symbol"
+)
+
+// parseTermScope reports whether --term-scope asks for the terms of a plain query to
+// share the enclosing symbol rather than the matched line
+func parseTermScope(scope string) (inSymbol bool, err error) {
+	switch scope {
+	case "", termScopeLine:
+		return false, nil
+	case termScopeSymbol:
+		return true, nil
+	}
+	return false, fmt.Errorf("unknown --term-scope %q (expected %s or %s)", scope, termScopeLine, termScopeSymbol)
+}
+
+// Values accepted by --visibility
+const (
+	visibilityPub = "pub"
+	visibilityAll = "all"
+)
+
+// parseVisibility reports whether --visibility keeps only hits inside public symbols
+func parseVisibility(visibility string) (publicOnly bool, err error) {
+	switch visibility {
+	case "", visibilityAll:
+		return false, nil
+	case visibilityPub:
+		return true, nil
+	}
+	return false, fmt.Errorf("unknown --visibility %q (expected %s or %s)", visibility, visibilityPub, visibilityAll)
+}
+
+// searchAliases returns the aliases --expand applies: search.DefaultAliases plus the
+// project's `search { aliases { ... } }` from cfg
+func searchAliases(cfg *config.Config) map[string][]string {
+	return search.MergeAliases(search.DefaultAliases(), cfg.Search.Aliases)
+}
+
+// validateFuzzy rejects unknown --fuzzy scorers and the flags a fuzzy symbol-name
+// search cannot honor
+func validateFuzzy(scorer string, useRegex, useQuery bool, within string, diff bool) error {
+	switch {
+	case scorer != search.FuzzyEdit && scorer != search.FuzzySubsequence:
+		return fmt.Errorf("unknown --fuzzy %q (expected %s or %s)", scorer, search.FuzzySubsequence, search.FuzzyEdit)
+	case useRegex || useQuery:
+		return errors.New("--fuzzy cannot be combined with --regex or --query")
+	case within != "" || diff:
+		return errors.New("--fuzzy matches symbol names; it cannot be combined with --within or --diff")
+	}
+	return nil
+}
+
+// validatePathGlobs rejects malformed --include and --exclude globs, which would
+// otherwise silently match nothing
+func validatePathGlobs(flag string, globs []string) error {
+	for _, glob := range globs {
+		if !doublestar.ValidatePattern(glob) {
+			return fmt.Errorf("invalid --%s glob %q", flag, glob)
+		}
+	}
+	return nil
+}
+
 // compareSearchImplementationsWithClient is a version of compareSearchImplementations that uses the server client
-func compareSearchImplementationsWithClient(c *cli.Context, client *server.Client, pattern string, maxLines int, caseInsensitive, light bool, excludePattern, includePattern string, verbose bool) error {
+func compareSearchImplementationsWithClient(c *cli.Context, client *server.Client, pattern string, maxLines int, caseInsensitive, smartCase, light bool, excludeGlobs, includeGlobs []string, verbose bool) error {
 	options := types.SearchOptions{
 		CaseInsensitive: caseInsensitive,
+		SmartCase:       smartCase,
 		MaxContextLines: maxLines,
-		ExcludePattern:  excludePattern,
-		IncludePattern:  includePattern,
+		ExcludeGlobs:    excludeGlobs,
+		IncludeGlobs:    includeGlobs,
 		Verbose:         verbose,
 	}
 
@@ -244,7 +687,7 @@ func compareSearchImplementationsWithClient(c *cli.Context, client *server.Clien
 	// Test search via server
 	fmt.Println("Server Search Mode")
 	start := time.Now()
-	results, err := client.Search(pattern, options, 500)
+	results, err := client.Search(pattern, options, c.Int("limit"))
 	if err != nil {
 		return fmt.Errorf("server search failed: %w", err)
 	}
@@ -255,17 +698,21 @@ func compareSearchImplementationsWithClient(c *cli.Context, client *server.Clien
 	return nil
 }
 
-func displayRegularResults(c *cli.Context, pattern string, results []search.GrepResult, elapsed time.Duration, compact bool) error {
+func displayRegularResults(c *cli.Context, pattern string, results []search.GrepResult, total int, elapsed time.Duration, compact bool) error {
 	if c.Bool("json") {
 		output := map[string]interface{}{
 			"query":   pattern,
 			"time_ms": float64(elapsed.Microseconds()) / 1000.0,
 			"count":   len(results),
+			"total":   total,
 			"results": results,
 		}
 		return json.NewEncoder(os.Stdout).Encode(output)
 	}
 
+	jumps := newJumpRecorder(c, reportRoot(projectRoot))
+	defer jumps.save(pattern)
+
 	// Compact mode: show just the matching lines with minimal context
 	if compact {
 		fmt.Printf("Found %d matches in %.1fms (compact mode)\n\n", len(results), float64(elapsed.Microseconds())/1000.0)
@@ -274,7 +721,7 @@ func displayRegularResults(c *cli.Context, pattern string, results []search.Grep
 			for i, line := range r.Context.Lines {
 				lineNum := r.Context.StartLine + i
 				if lineNum == r.Line {
-					fmt.Printf("%s:%d: %s\n", r.Path, lineNum, line)
+					fmt.Printf("%s%s:%d: %s\n", jumps.prefix(r), r.Path, lineNum, line)
 					break
 				}
 			}
@@ -282,32 +729,78 @@ func displayRegularResults(c *cli.Context, pattern string, results []search.Grep
 		return nil
 	}
 
-	fmt.Printf("Found %d results in %.1fms\n\n", len(results), float64(elapsed.Microseconds())/1000.0)
+	fmt.Printf("Found %d results in %.1fms\n\n", total, float64(elapsed.Microseconds())/1000.0)
+	printLimitNote(c.Int("offset"), len(results), total)
 
+	formatter := snippetFormatter(c)
 	for _, r := range results {
-		fmt.Printf("%s:%d", r.Path, r.Line)
-		if r.Context.BlockName != "" {
-			fmt.Printf(" (in %s %s)", r.Context.BlockType, r.Context.BlockName)
-		}
-		fmt.Println()
+		printRegularResult(c, r, formatter, jumps)
+	}
 
-		for i, line := range r.Context.Lines {
-			lineNum := r.Context.StartLine + i
-			if lineNum == r.Line {
-				fmt.Printf("  > %4d | %s\n", lineNum, line)
-			} else {
-				fmt.Printf("    %4d | %s\n", lineNum, line)
-			}
-		}
-		fmt.Println()
+	return nil
+}
+
+// printLimitNote points out that --limit or --offset hid some of the total hits found
+func printLimitNote(offset, shown, total int) {
+	switch {
+	case offset > 0 && shown == 0:
+		fmt.Printf("No results past --offset %d\n\n", offset)
+	case offset > 0:
+		fmt.Printf("Showing results %d-%d\n\n", offset+1, offset+shown)
+	case total > shown:
+		fmt.Printf("Showing the top %d (--limit 0 shows all)\n\n", shown)
 	}
+}
 
+// validateTimeout rejects --timeout-ms in the modes that cannot report a partial result
+func validateTimeout(multiRoot, streaming, counting bool) error {
+	switch {
+	case multiRoot:
+		return errors.New("--timeout-ms bounds the index server search; it cannot be combined with several --root values")
+	case streaming:
+		return errors.New("--timeout-ms cannot be combined with --streaming; use --limit to stop early")
+	case counting:
+		return errors.New("--timeout-ms cannot be combined with --count or --count-by")
+	}
 	return nil
 }
 
+// printTimeoutNote warns that --timeout-ms cut the search short
+func printTimeoutNote(timedOut bool, timeoutMs int) {
+	if timedOut {
+		fmt.Fprintf(os.Stderr, "WARNING: search stopped after --timeout-ms %d; showing the hits found so far\n\n", timeoutMs)
+	}
+}
+
+// printRegularResult prints one result as its location followed by its context block,
+// numbered when jumps records a jump list
+func printRegularResult(c *cli.Context, r search.GrepResult, f SnippetFormatter, jumps *jumpRecorder) {
+	fmt.Printf("%s%s:%d", jumps.prefix(r), r.Path, r.Line)
+	if r.EndLine > r.Line {
+		fmt.Printf("-%d", r.EndLine)
+	}
+	if r.Context.BlockName != "" {
+		fmt.Printf(" (in %s %s)", r.Context.BlockType, r.Context.BlockName)
+	}
+	if r.Encoding != "" {
+		fmt.Printf(" (decoded from %s)", r.Encoding)
+	}
+	printBlame(os.Stdout, r.Blame)
+	fmt.Println()
+
+	width := c.Int("max-snippet-width")
+	if c.Int("context") > 0 {
+		printContextSnippet(os.Stdout, r, f, width)
+	} else {
+		f.FormatSnippet(os.Stdout, r.Path, blockSnippetLines(r, width))
+	}
+	printExplanation(os.Stdout, r.Explanation)
+	fmt.Println()
+}
+
 func displayGrepResults(c *cli.Context, pattern string, results []search.GrepResult, elapsed time.Duration) error {
 	// Convert paths to relative for user-facing output
-	results = pathutil.ToRelativeGrepResults(results, projectRoot)
+	results = pathutil.ToRelativeGrepResults(results, reportRoot(projectRoot))
 
 	if c.Bool("json") {
 		output := map[string]interface{}{
@@ -350,15 +843,16 @@ func displayGrepResults(c *cli.Context, pattern string, results []search.GrepRes
 	return nil
 }
 
-func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results []search.StandardResult, assemblyResults []core.AssemblyResult, assemblyTriggered bool, elapsed time.Duration, compact bool, rankBy string) error {
+func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results []search.StandardResult, total int, assemblyResults []core.AssemblyResult, assemblyTriggered bool, elapsed time.Duration, compact bool, rankBy string) error {
 	// Convert paths to relative for user-facing output
-	results = pathutil.ToRelativeStandardResults(results, projectRoot)
+	results = pathutil.ToRelativeStandardResults(results, reportRoot(projectRoot))
 
 	if c.Bool("json") {
 		output := map[string]interface{}{
 			"query":   pattern,
 			"time_ms": float64(elapsed.Microseconds()) / 1000.0,
 			"count":   len(results),
+			"total":   total,
 			"results": results,
 			"mode":    "standard",
 		}
@@ -384,6 +878,9 @@ func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results
 		}
 	}
 
+	jumps := newJumpRecorder(c, reportRoot(projectRoot))
+	defer jumps.save(pattern)
+
 	// Compact mode: show just the patterns with file location
 	if compact {
 		fmt.Printf("Found %d matches in %.1fms (compact mode)\n\n", len(results), float64(elapsed.Microseconds())/1000.0)
@@ -393,7 +890,7 @@ func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results
 			for i, line := range result.Context.Lines {
 				lineNum := result.Context.StartLine + i
 				if lineNum == result.Line {
-					fmt.Printf("%s:%d: %s\n", result.Path, lineNum, strings.TrimSpace(line))
+					fmt.Printf("%s%s:%d: %s\n", jumps.prefix(result), result.Path, lineNum, strings.TrimSpace(line))
 					break
 				}
 			}
@@ -404,15 +901,16 @@ func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results
 	// Display search summary
 	if assemblyTriggered {
 		if len(assemblyResults) > 0 {
-			fmt.Printf("Found %d results in %.1fms (integrated mode)\n", len(results), float64(elapsed.Microseconds())/1000.0)
+			fmt.Printf("Found %d results in %.1fms (integrated mode)\n", total, float64(elapsed.Microseconds())/1000.0)
 			fmt.Printf("  Direct matches: %d\n", len(results))
 			fmt.Printf("  Assembly patterns: %d\n\n", len(assemblyResults))
 		} else {
-			fmt.Printf("Found %d results in %.1fms (integrated mode - no assembly matches)\n\n", len(results), float64(elapsed.Microseconds())/1000.0)
+			fmt.Printf("Found %d results in %.1fms (integrated mode - no assembly matches)\n\n", total, float64(elapsed.Microseconds())/1000.0)
 		}
 	} else {
-		fmt.Printf("Found %d results in %.1fms (standard mode)\n\n", len(results), float64(elapsed.Microseconds())/1000.0)
+		fmt.Printf("Found %d results in %.1fms (standard mode)\n\n", total, float64(elapsed.Microseconds())/1000.0)
 	}
+	printLimitNote(c.Int("offset"), len(results), total)
 
 	if totalMatches > len(results) {
 		fmt.Printf("Total matches: %d (merged into %d results)\n\n", totalMatches, len(results))
@@ -420,10 +918,12 @@ func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results
 
 	// Display regular results
 	if len(results) > 0 {
+		formatter, blocks := snippetFormatter(c), blockFormatter(c)
+		width := c.Int("max-snippet-width")
 		fmt.Println("=== Direct Matches ===")
 		for _, r := range results {
 			result := r.Result
-			fmt.Printf("%s:%d", result.Path, result.Line)
+			fmt.Printf("%s%s:%d", jumps.prefix(result), result.Path, result.Line)
 
 			// Add block context if available
 			if result.Context.BlockName != "" {
@@ -434,6 +934,7 @@ func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results
 			if r.ObjectID != "" {
 				fmt.Printf(" [id: %s]", r.ObjectID)
 			}
+			printBlame(os.Stdout, result.Blame)
 
 			if result.Context.BlockName != "" {
 				if rd := r.RelationalData; rd != nil {
@@ -446,12 +947,12 @@ func displayStandardResultsWithAssembly(c *cli.Context, pattern string, results
 			fmt.Println()
 
 			// Display context lines
-			if result.Context.Lines != nil {
-				for i, line := range result.Context.Lines {
-					lineNum := result.Context.StartLine + i
-					fmt.Printf("  %4d | %s\n", lineNum, line)
-				}
+			if c.Int("context") > 0 {
+				printContextSnippet(os.Stdout, result, formatter, width)
+			} else if result.Context.Lines != nil {
+				blocks.FormatSnippet(os.Stdout, result.Path, blockSnippetLines(result, width))
 			}
+			printExplanation(os.Stdout, result.Explanation)
 			fmt.Println()
 		}
 	}
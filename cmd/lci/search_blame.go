@@ -0,0 +1,107 @@
+package main
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"path/filepath"
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/git"
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+// validateBlame rejects --blame in the modes that print no individual hits or print
+// them before the page to annotate is known
+func validateBlame(streaming, counting, filesOnly bool, groupBy string) error {
+	switch {
+	case streaming:
+		return errors.New("--blame cannot be combined with --streaming")
+	case counting || filesOnly:
+		return errors.New("--blame annotates hits; it cannot be combined with --count, --count-by or --files-with-matches")
+	case groupBy != "":
+		return errors.New("--blame cannot be combined with --group-by")
+	}
+	return nil
+}
+
+// annotateBlame sets the Blame of each hit from git blame of its line. It runs after
+// --limit and --offset, once per file, so only the hits that are displayed pay for it.
+// Relative paths are resolved against the hit's Root, or root when it has none. Hits
+// outside a git repository or in files git does not track are left without blame.
+func annotateBlame(results []search.GrepResult, root string) {
+	type fileHits struct {
+		repoRoot string
+		hits     []int // Indexes into results
+	}
+	files := make(map[string]*fileHits)
+	var order []string
+	for i, r := range results {
+		base := root
+		if r.Root != "" {
+			base = r.Root
+		}
+		path := r.Path
+		if !filepath.IsAbs(path) {
+			path = filepath.Join(base, path)
+		}
+		f, ok := files[path]
+		if !ok {
+			f = &fileHits{repoRoot: base}
+			files[path] = f
+			order = append(order, path)
+		}
+		f.hits = append(f.hits, i)
+	}
+
+	providers := make(map[string]*git.Provider) // nil entries: not a git repository
+	for _, path := range order {
+		f := files[path]
+		provider, ok := providers[f.repoRoot]
+		if !ok {
+			provider, _ = git.NewProvider(f.repoRoot)
+			providers[f.repoRoot] = provider
+		}
+		if provider == nil {
+			continue
+		}
+
+		lines := blameLines(results, f.hits)
+		blamed, err := provider.BlameLines(context.Background(), path, lines)
+		if err != nil {
+			continue // Untracked or unreadable: no blame rather than a failed search
+		}
+		for _, i := range f.hits {
+			if b, ok := blamed[results[i].Line]; ok {
+				results[i].Blame = &search.Blame{Commit: b.Commit, Author: b.Author, Date: b.AuthorTime}
+			}
+		}
+	}
+}
+
+// blameLines returns the distinct lines of the given hits in ascending order
+func blameLines(results []search.GrepResult, hits []int) []int {
+	seen := make(map[int]bool, len(hits))
+	var lines []int
+	for _, i := range hits {
+		if line := results[i].Line; line > 0 && !seen[line] {
+			seen[line] = true
+			lines = append(lines, line)
+		}
+	}
+	sort.Ints(lines)
+	return lines
+}
+
+// printBlame appends a hit's blame to its header line as [commit author date]
+func printBlame(w io.Writer, b *search.Blame) {
+	if b == nil {
+		return
+	}
+	commit := b.Commit
+	if len(commit) > 8 {
+		commit = commit[:8]
+	}
+	fmt.Fprintf(w, " [%s %s %s]", commit, b.Author, b.Date.Format("2006-01-02"))
+}
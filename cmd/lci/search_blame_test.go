@@ -0,0 +1,64 @@
+package main
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestAnnotateBlame(t *testing.T) {
+	repo := t.TempDir()
+	git := func(args ...string) string {
+		t.Helper()
+		cmd := exec.Command("git", args...)
+		cmd.Dir = repo
+		cmd.Env = append(os.Environ(),
+			"GIT_AUTHOR_NAME=Ada Lovelace", "GIT_AUTHOR_EMAIL=ada@example.com", "GIT_AUTHOR_DATE=2024-03-01T12:00:00Z",
+			"GIT_COMMITTER_NAME=Ada Lovelace", "GIT_COMMITTER_EMAIL=ada@example.com")
+		out, err := cmd.CombinedOutput()
+		require.NoError(t, err, string(out))
+		return strings.TrimSpace(string(out))
+	}
+	git("init", "-q")
+	require.NoError(t, os.WriteFile(filepath.Join(repo, "main.go"), []byte("package main\n\nfunc main() {}\n"), 0644))
+	git("add", "main.go")
+	git("commit", "-q", "-m", "Add main")
+	head := git("rev-parse", "HEAD")
+	require.NoError(t, os.WriteFile(filepath.Join(repo, "scratch.go"), []byte("package main\n"), 0644))
+
+	results := []search.GrepResult{
+		{Path: "main.go", Line: 3},
+		{Path: filepath.Join(repo, "main.go"), Line: 1},
+		{Path: "scratch.go", Line: 1},
+	}
+	annotateBlame(results, repo)
+
+	require.NotNil(t, results[0].Blame)
+	assert.Equal(t, head, results[0].Blame.Commit)
+	assert.Equal(t, "Ada Lovelace", results[0].Blame.Author)
+	assert.Equal(t, "2024-03-01", results[0].Blame.Date.Format("2006-01-02"))
+	require.NotNil(t, results[1].Blame, "absolute paths are blamed too")
+	assert.Equal(t, head, results[1].Blame.Commit)
+	assert.Nil(t, results[2].Blame, "untracked files have no blame")
+
+	plain := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(plain, "main.go"), []byte("package main\n"), 0644))
+	outside := []search.GrepResult{{Path: "main.go", Line: 1}}
+	annotateBlame(outside, plain)
+	assert.Nil(t, outside[0].Blame, "hits outside a git repository are left as they are")
+}
+
+func TestValidateBlame(t *testing.T) {
+	assert.NoError(t, validateBlame(false, false, false, ""))
+	assert.ErrorContains(t, validateBlame(true, false, false, ""), "--streaming")
+	assert.ErrorContains(t, validateBlame(false, true, false, ""), "--count")
+	assert.ErrorContains(t, validateBlame(false, false, true, ""), "--files-with-matches")
+	assert.ErrorContains(t, validateBlame(false, false, false, groupBySymbol), "--group-by")
+}
@@ -0,0 +1,61 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+
+	"github.com/standardbeagle/lci/internal/search"
+
+	"github.com/urfave/cli/v2"
+)
+
+// defaultWarnLines is how many lines of results a search prints to a terminal before
+// --warn-lines asks for a narrower query
+const defaultWarnLines = 2000
+
+// printCapNote warns that the search stopped at its result cap, so total is a floor
+// rather than the number of hits in the project
+func printCapNote(capReached bool, total int) {
+	if capReached {
+		fmt.Fprintf(os.Stderr, "WARNING: result cap reached at %d hits, refine your query (or raise --max-results)\n\n", total)
+	}
+}
+
+// warnLargeOutput warns on stderr when results would print more than --warn-lines
+// lines to a terminal. Piped output is never warned about, since it is meant to be read
+// by a program or a pager.
+func warnLargeOutput(c *cli.Context, results []search.GrepResult, oneLine bool) {
+	limit := c.Int("warn-lines")
+	if limit == 0 || c.Bool("json") || !isTerminal(os.Stdout) {
+		return
+	}
+	writeOutputWarning(os.Stderr, estimateOutputLines(results, oneLine), limit)
+}
+
+// writeOutputWarning writes the --warn-lines warning when lines exceeds limit
+func writeOutputWarning(w io.Writer, lines, limit int) {
+	if lines > limit {
+		fmt.Fprintf(w, "WARNING: printing about %d lines of results; narrow the query or use --limit (--warn-lines 0 turns this off)\n\n", lines)
+	}
+}
+
+// estimateOutputLines counts the lines results print as: one per hit with oneLine, for
+// templated and compact output, otherwise a location line, the context block and a
+// blank separator per hit
+func estimateOutputLines(results []search.GrepResult, oneLine bool) int {
+	if oneLine {
+		return len(results)
+	}
+	lines := 0
+	for _, r := range results {
+		lines += len(r.Context.Lines) + 2
+	}
+	return lines
+}
+
+// isTerminal reports whether out is a terminal rather than a pipe or file
+func isTerminal(out *os.File) bool {
+	stat, err := out.Stat()
+	return err == nil && stat.Mode()&os.ModeCharDevice != 0
+}
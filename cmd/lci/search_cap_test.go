@@ -0,0 +1,26 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+)
+
+func TestEstimateOutputLines(t *testing.T) {
+	results := []search.GrepResult{
+		{Context: searchtypes.ExtractedContext{Lines: []string{"a", "b", "c"}}},
+		{Context: searchtypes.ExtractedContext{Lines: []string{"d"}}},
+	}
+	assert.Equal(t, 8, estimateOutputLines(results, false), "a location line, the block and a separator per hit")
+	assert.Equal(t, 2, estimateOutputLines(results, true))
+
+	var buf bytes.Buffer
+	writeOutputWarning(&buf, 8, 8)
+	assert.Empty(t, buf.String())
+	writeOutputWarning(&buf, 9, 8)
+	assert.Contains(t, buf.String(), "about 9 lines")
+}
@@ -0,0 +1,85 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+// Values accepted by `lci search --color`
+const (
+	colorAuto   = "auto"
+	colorAlways = "always"
+	colorNever  = "never"
+)
+
+// ANSI escapes used to highlight the matched span
+const (
+	ansiMatchStart = "\x1b[1;31m"
+	ansiReset      = "\x1b[0m"
+)
+
+// validateColorMode rejects unknown --color values before any search work is done
+func validateColorMode(mode string) error {
+	switch mode {
+	case "", colorAuto, colorAlways, colorNever:
+		return nil
+	}
+	return fmt.Errorf("unknown color mode %q (expected %s, %s or %s)", mode, colorAuto, colorAlways, colorNever)
+}
+
+// colorEnabled resolves a --color mode for the given output. auto colors only when the
+// output is a terminal and NO_COLOR is unset.
+func colorEnabled(mode string, out *os.File) bool {
+	switch mode {
+	case colorAlways:
+		return true
+	case colorNever:
+		return false
+	}
+	if os.Getenv("NO_COLOR") != "" {
+		return false
+	}
+	return isTerminal(out)
+}
+
+// highlightMatch wraps the bytes of line covered by the hit's byte span in ANSI color.
+// line must be the unmodified source line the hit was found on (see matchSpan).
+func highlightMatch(line string, r search.GrepResult, enabled bool) string {
+	if !enabled {
+		return line
+	}
+	start, end, ok := matchSpan(line, r)
+	if !ok {
+		return line
+	}
+	return line[:start] + ansiMatchStart + line[start:end] + ansiReset + line[end:]
+}
+
+// matchSpan returns the [start, end) bytes of line covered by the hit's byte span. The
+// span is located via the hit's column and widened to whole code points so multibyte
+// UTF-8 is never split; ok is false when the hit has no span on line.
+func matchSpan(line string, r search.GrepResult) (start, end int, ok bool) {
+	if r.EndByte <= r.StartByte {
+		return 0, 0, false
+	}
+
+	start = r.Column
+	end = start + (r.EndByte - r.StartByte)
+	if start < 0 || start >= len(line) {
+		return 0, 0, false
+	}
+	if end > len(line) {
+		end = len(line)
+	}
+
+	for start > 0 && !utf8.RuneStart(line[start]) {
+		start--
+	}
+	for end < len(line) && !utf8.RuneStart(line[end]) {
+		end++
+	}
+	return start, end, true
+}
@@ -0,0 +1,59 @@
+package main
+
+import (
+	"os"
+	"testing"
+	"unicode/utf8"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestHighlightMatch(t *testing.T) {
+	line := "    pub fn get_user(&self) -> User {"
+	hit := search.GrepResult{Column: 11, StartByte: 111, EndByte: 119, Match: "get_user"}
+
+	assert.Equal(t, "    pub fn \x1b[1;31mget_user\x1b[0m(&self) -> User {", highlightMatch(line, hit, true))
+	assert.Equal(t, line, highlightMatch(line, hit, false), "disabled color leaves the line untouched")
+
+	// A span past the end of the line is clamped rather than panicking
+	hit.Column = 30
+	hit.EndByte = hit.StartByte + 50
+	assert.Equal(t, line[:30]+ansiMatchStart+line[30:]+ansiReset, highlightMatch(line, hit, true))
+
+	hit.Column = len(line) + 5
+	assert.Equal(t, line, highlightMatch(line, hit, true))
+}
+
+func TestHighlightMatch_MultibyteUTF8(t *testing.T) {
+	line := `let greeting = "héllo wörld";`
+
+	// Exact span over a word containing a two-byte code point
+	start := len(`let greeting = "`)
+	exact := search.GrepResult{Column: start, StartByte: 200, EndByte: 200 + len("héllo")}
+	assert.Equal(t, `let greeting = "`+ansiMatchStart+"héllo"+ansiReset+` wörld";`, highlightMatch(line, exact, true))
+
+	// A span that starts and ends inside code points is widened to whole characters
+	inside := len(`let greeting = "h`) + 1 // second byte of é
+	misaligned := search.GrepResult{Column: inside, StartByte: 300, EndByte: 300 + len("éllo wö") - 2}
+	out := highlightMatch(line, misaligned, true)
+	require.True(t, utf8.ValidString(out), "highlighting must not split a code point: %q", out)
+	assert.Contains(t, out, ansiMatchStart+"éllo wö"+ansiReset)
+}
+
+func TestColorMode(t *testing.T) {
+	for _, mode := range []string{"", "auto", "always", "never"} {
+		assert.NoError(t, validateColorMode(mode), mode)
+	}
+	assert.Error(t, validateColorMode("sometimes"))
+
+	f, err := os.CreateTemp(t.TempDir(), "out")
+	require.NoError(t, err)
+	defer f.Close()
+
+	assert.True(t, colorEnabled(colorAlways, f))
+	assert.False(t, colorEnabled(colorNever, f))
+	assert.False(t, colorEnabled(colorAuto, f), "a regular file is not a terminal")
+}
@@ -0,0 +1,120 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+
+	"github.com/urfave/cli/v2"
+)
+
+// countByFile is the only --count-by value: one tally per file
+const countByFile = "file"
+
+// SearchCounts is the JSON output of `lci search --count` and `--count-by file`.
+// Counts maps each file with matches to its number of matches.
+type SearchCounts struct {
+	Counts map[string]int `json:"counts"`
+	Total  int            `json:"total"`
+}
+
+// validateCounting rejects --count-by values and flag combinations count mode cannot
+// honor
+func validateCounting(countBy string, streaming, filesOnly bool) error {
+	if countBy != "" && countBy != countByFile {
+		return fmt.Errorf("unknown --count-by %q (expected %s)", countBy, countByFile)
+	}
+	switch {
+	case streaming:
+		return errors.New("--count and --count-by need every hit before printing; drop --streaming")
+	case filesOnly:
+		return errors.New("--count and --count-by cannot be combined with --files-with-matches")
+	}
+	return nil
+}
+
+// countSearchCommand runs a search in count mode and prints the total, or with
+// --count-by file the per-file tally. The engine only counts matches, so no snippets
+// are built or highlighted.
+func countSearchCommand(c *cli.Context, client *server.Client, roots []string, pattern string, options types.SearchOptions, format string) error {
+	options.CountPerFile = true
+
+	var results []search.GrepResult
+	if client == nil {
+		var err error
+		results, _, err = searchRoots(roots, pattern, options, 0, 0, func(root string) (*config.Config, error) {
+			return loadConfigForRoot(c, root)
+		})
+		if err != nil {
+			return err
+		}
+		for i := range results {
+			results[i].Path = filepath.Join(results[i].Root, results[i].Path)
+		}
+	} else {
+		var err error
+		if results, err = client.Search(pattern, options, 0); err != nil {
+			return fmt.Errorf("search failed: %w", err)
+		}
+		results = pathutil.ToRelativeGrepResults(results, reportRoot(projectRoot))
+	}
+
+	asJSON := c.Bool("json") || isStructuredFormat(format)
+	counts := countResults(results)
+	return matchStatus(counts.Total, writeCounts(os.Stdout, counts, c.String("count-by") == countByFile, asJSON))
+}
+
+// countResults tallies count-mode results, which carry one file each
+func countResults(results []search.GrepResult) SearchCounts {
+	counts := SearchCounts{Counts: make(map[string]int, len(results))}
+	for _, r := range results {
+		counts.Counts[r.Path] += r.FileMatchCount
+		counts.Total += r.FileMatchCount
+	}
+	return counts
+}
+
+// writeCounts prints the total, preceded with byFile by one "count path" line per file,
+// highest count first and ties by path, in the style of wc. JSON always includes the
+// per-file counts.
+func writeCounts(w io.Writer, counts SearchCounts, byFile, asJSON bool) error {
+	if asJSON {
+		return json.NewEncoder(w).Encode(counts)
+	}
+	if !byFile {
+		_, err := fmt.Fprintln(w, counts.Total)
+		return err
+	}
+
+	paths := make([]string, 0, len(counts.Counts))
+	for path := range counts.Counts {
+		paths = append(paths, path)
+	}
+	sort.Slice(paths, func(i, j int) bool {
+		a, b := counts.Counts[paths[i]], counts.Counts[paths[j]]
+		if a != b {
+			return a > b
+		}
+		return paths[i] < paths[j]
+	})
+
+	width := len(strconv.Itoa(counts.Total))
+	for _, path := range paths {
+		if _, err := fmt.Fprintf(w, "%*d %s\n", width, counts.Counts[path], path); err != nil {
+			return err
+		}
+	}
+	_, err := fmt.Fprintf(w, "%*d total\n", width, counts.Total)
+	return err
+}
@@ -0,0 +1,46 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestWriteCounts(t *testing.T) {
+	counts := countResults([]search.GrepResult{
+		{Path: "src/b.rs", FileMatchCount: 3},
+		{Path: "src/a.rs", FileMatchCount: 3},
+		{Path: "main.go", FileMatchCount: 12},
+	})
+	assert.Equal(t, 18, counts.Total)
+
+	var total bytes.Buffer
+	require.NoError(t, writeCounts(&total, counts, false, false))
+	assert.Equal(t, "18\n", total.String())
+
+	var byFile bytes.Buffer
+	require.NoError(t, writeCounts(&byFile, counts, true, false))
+	assert.Equal(t, "12 main.go\n 3 src/a.rs\n 3 src/b.rs\n18 total\n", byFile.String(), "highest count first, ties by path")
+
+	var out bytes.Buffer
+	require.NoError(t, writeCounts(&out, counts, false, true))
+	var decoded map[string]any
+	require.NoError(t, json.Unmarshal(out.Bytes(), &decoded))
+	assert.Equal(t, map[string]any{
+		"counts": map[string]any{"main.go": 12.0, "src/a.rs": 3.0, "src/b.rs": 3.0},
+		"total":  18.0,
+	}, decoded)
+}
+
+func TestValidateCounting(t *testing.T) {
+	assert.NoError(t, validateCounting("", false, false))
+	assert.NoError(t, validateCounting(countByFile, false, false))
+	assert.Error(t, validateCounting("dir", false, false))
+	assert.Error(t, validateCounting("", true, false), "counts need every hit")
+	assert.Error(t, validateCounting(countByFile, false, true))
+}
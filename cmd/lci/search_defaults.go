@@ -0,0 +1,35 @@
+package main
+
+import (
+	"github.com/standardbeagle/lci/internal/config"
+
+	"github.com/urfave/cli/v2"
+)
+
+// applySearchDefaults replaces the built-in values of the search command's flags with the
+// `defaults` block of .lci.kdl. It must run before the command's flags are parsed, so a
+// flag given on the command line still wins.
+func applySearchDefaults(cmd *cli.Command, d config.Defaults) {
+	for _, flag := range cmd.Flags {
+		switch f := flag.(type) {
+		case *cli.BoolFlag:
+			if f.Name == "ignore-case" && d.IgnoreCase {
+				f.Value = true
+			}
+		case *cli.IntFlag:
+			if f.Name == "context" && d.Context > 0 {
+				f.Value = d.Context
+			}
+		case *cli.StringFlag:
+			if f.Name == "format" && d.Format != "" {
+				f.Value = d.Format
+			}
+		case *cli.StringSliceFlag:
+			if f.Name == "include" && len(d.Include) > 0 {
+				f.Value = cli.NewStringSlice(d.Include...)
+			} else if f.Name == "exclude" && len(d.Exclude) > 0 {
+				f.Value = cli.NewStringSlice(d.Exclude...)
+			}
+		}
+	}
+}
@@ -0,0 +1,103 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/urfave/cli/v2"
+
+	"github.com/standardbeagle/lci/internal/config"
+)
+
+func TestApplySearchDefaults(t *testing.T) {
+	run := func(args ...string) *cli.Context {
+		var got *cli.Context
+		search := &cli.Command{
+			Name: "search",
+			Flags: []cli.Flag{
+				&cli.BoolFlag{Name: "ignore-case", Aliases: []string{"i"}},
+				&cli.IntFlag{Name: "context", Aliases: []string{"C"}},
+				&cli.StringFlag{Name: "format", Value: "text"},
+				&cli.StringSliceFlag{Name: "include"},
+				&cli.StringSliceFlag{Name: "exclude"},
+			},
+			Action: func(c *cli.Context) error {
+				got = c
+				return nil
+			},
+		}
+		applySearchDefaults(search, config.Defaults{
+			IgnoreCase: true,
+			Context:    2,
+			Format:     "json",
+			Include:    []string{"src/**"},
+			Exclude:    []string{"**/*_test.go"},
+		})
+		app := &cli.App{Commands: []*cli.Command{search}}
+		require.NoError(t, app.Run(append([]string{"lci", "search"}, args...)))
+		return got
+	}
+
+	c := run("pattern")
+	assert.True(t, c.Bool("ignore-case"))
+	assert.Equal(t, 2, c.Int("context"))
+	assert.Equal(t, "json", c.String("format"))
+	assert.Equal(t, []string{"src/**"}, c.StringSlice("include"))
+	assert.Equal(t, []string{"**/*_test.go"}, c.StringSlice("exclude"))
+
+	c = run("--context", "0", "--format", "text", "--include", "*.go", "--ignore-case=false", "pattern")
+	assert.False(t, c.Bool("ignore-case"), "flags on the command line win over config defaults")
+	assert.Equal(t, 0, c.Int("context"))
+	assert.Equal(t, "text", c.String("format"))
+	assert.Equal(t, []string{"*.go"}, c.StringSlice("include"), "a flag replaces the configured globs")
+	assert.Equal(t, []string{"**/*_test.go"}, c.StringSlice("exclude"))
+}
+
+func TestGlobalFlagsReachSearch(t *testing.T) {
+	dir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(dir, ".lci.kdl"), []byte("defaults {\n    context 3\n}\n"), 0644))
+	realDir, err := filepath.EvalSymlinks(dir)
+	require.NoError(t, err)
+
+	var before, inSearch *config.Config
+	var searchContext int
+	search := &cli.Command{
+		Name: "search",
+		Flags: []cli.Flag{
+			&cli.StringSliceFlag{Name: "root"},
+			&cli.StringSliceFlag{Name: "include"},
+			&cli.IntFlag{Name: "context", Aliases: []string{"C"}},
+		},
+		Action: func(c *cli.Context) error {
+			searchContext = c.Int("context")
+			var err error
+			inSearch, err = loadConfigWithOverrides(c)
+			return err
+		},
+	}
+	app := &cli.App{
+		Flags: []cli.Flag{
+			&cli.StringFlag{Name: "root", Aliases: []string{"r"}},
+			&cli.StringSliceFlag{Name: "include"},
+			&cli.StringSliceFlag{Name: "exclude"},
+		},
+		Before: func(c *cli.Context) error {
+			var err error
+			if before, err = loadConfigWithOverrides(c); err == nil {
+				applySearchDefaults(c.App.Command("search"), before.Defaults)
+			}
+			return err
+		},
+		Commands: []*cli.Command{search},
+	}
+	require.NoError(t, app.Run([]string{"lci", "--root", dir, "--include", "*.rs", "search", "pattern"}))
+
+	for _, cfg := range []*config.Config{before, inSearch} {
+		assert.Equal(t, realDir, cfg.Project.Root, "the global --root is the project root")
+		assert.Equal(t, []string{"*.rs"}, cfg.Include, "the global --include filters the index walk")
+	}
+	assert.Equal(t, 3, searchContext, "search defaults come from the .lci.kdl of the global --root")
+}
@@ -0,0 +1,51 @@
+package main
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"path/filepath"
+
+	"github.com/standardbeagle/lci/internal/git"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// validateDiff rejects --diff in the modes that do not search the single project root
+// line by line
+func validateDiff(multiRoot, query bool) error {
+	switch {
+	case multiRoot:
+		return errors.New("--diff searches the project's git work tree; it cannot be combined with several --root values")
+	case query:
+		return errors.New("--diff cannot be combined with --query")
+	}
+	return nil
+}
+
+// diffChangedLines runs git diff against baseRef in the project root and returns the
+// added or modified lines by absolute path, for SearchOptions.ChangedLines
+func diffChangedLines(baseRef string) (map[string][]types.LineRange, error) {
+	provider, err := git.NewProvider(projectRoot)
+	if err != nil {
+		return nil, fmt.Errorf("--diff: %w", err)
+	}
+	changed, err := provider.GetChangedLines(context.Background(), baseRef)
+	if err != nil {
+		return nil, fmt.Errorf("--diff: %w", err)
+	}
+	return changedLinesByPath(changed, provider.GetRepoRoot()), nil
+}
+
+// changedLinesByPath keys lines changed by git, whose paths are relative to the work
+// tree's top level, by the absolute path each file is indexed at. The top level is
+// resolved through symlinks like the project root, so the paths compare equal.
+func changedLinesByPath(changed map[string][]types.LineRange, topLevel string) map[string][]types.LineRange {
+	if realTop, err := filepath.EvalSymlinks(topLevel); err == nil {
+		topLevel = realTop
+	}
+	byPath := make(map[string][]types.LineRange, len(changed))
+	for rel, lines := range changed {
+		byPath[filepath.Join(topLevel, filepath.FromSlash(rel))] = lines
+	}
+	return byPath
+}
@@ -0,0 +1,36 @@
+package main
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestValidateDiff(t *testing.T) {
+	assert.NoError(t, validateDiff(false, false))
+	assert.Error(t, validateDiff(true, false), "several roots have no single work tree")
+	assert.Error(t, validateDiff(false, true), "queries match symbols, not lines")
+}
+
+func TestDiffChangedLinesOutsideGitRepo(t *testing.T) {
+	saved := projectRoot
+	defer func() { projectRoot = saved }()
+	projectRoot = t.TempDir()
+
+	_, err := diffChangedLines("main")
+	assert.ErrorContains(t, err, "not a git repository")
+}
+
+func TestChangedLinesByPath(t *testing.T) {
+	top := t.TempDir()
+	realTop, err := filepath.EvalSymlinks(top)
+	assert.NoError(t, err)
+	lines := []types.LineRange{{Start: 3, End: 4}}
+
+	byPath := changedLinesByPath(map[string][]types.LineRange{"src/auth.go": lines}, top)
+	assert.Equal(t, map[string][]types.LineRange{filepath.Join(realTop, "src", "auth.go"): lines}, byPath,
+		"repo-relative paths are joined to the resolved top level")
+}
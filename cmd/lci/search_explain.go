@@ -0,0 +1,29 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+// printExplanation prints a result's score breakdown under its snippet: the total and
+// ranker, then one line per component with the factors it was computed from
+func printExplanation(w io.Writer, x *search.ScoreExplanation) {
+	if x == nil {
+		return
+	}
+	fmt.Fprintf(w, "    score %.4f (%s)\n", x.Score, x.Ranker)
+	for _, c := range x.Components {
+		line := fmt.Sprintf("      %+.4f  %s", c.Value, c.Name)
+		if len(c.Factors) > 0 {
+			factors := make([]string, len(c.Factors))
+			for i, f := range c.Factors {
+				factors[i] = fmt.Sprintf("%s=%.4g", f.Name, f.Value)
+			}
+			line += "  " + strings.Join(factors, " ")
+		}
+		fmt.Fprintln(w, line)
+	}
+}
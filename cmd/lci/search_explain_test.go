@@ -0,0 +1,26 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+func TestPrintExplanation(t *testing.T) {
+	x := &search.ScoreExplanation{Ranker: "bm25", Score: 1.5}
+	x.Add(`term "user"`, 1, search.ScoreFactor{Name: "tf", Value: 3}, search.ScoreFactor{Name: "idf", Value: 0.6931})
+	x.Add("bonus", 0.5)
+
+	var out bytes.Buffer
+	printExplanation(&out, x)
+	assert.Equal(t, "    score 1.5000 (bm25)\n"+
+		"      +1.0000  term \"user\"  tf=3 idf=0.6931\n"+
+		"      +0.5000  bonus\n", out.String())
+
+	out.Reset()
+	printExplanation(&out, nil)
+	assert.Empty(t, out.String())
+}
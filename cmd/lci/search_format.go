@@ -0,0 +1,358 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+	"unicode/utf16"
+
+	"github.com/standardbeagle/lci/internal/encoding"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// Output formats accepted by `lci search --format`
+const (
+	searchFormatText  = "text"
+	searchFormatJSON  = "json"
+	searchFormatJSONL = "jsonl"
+)
+
+// SearchHit is the stable, machine-readable representation of a single search hit.
+// Field names are part of the CLI contract; add new fields rather than renaming.
+//
+// ByteSpan is the [start, end) byte offset range of the match in the file and is the
+// canonical location. Start and End are the same span as 1-based line/column positions,
+// with columns counted in the summary's position_encoding; they are omitted when the
+// file could not be read or the hit has no span. Column is the 0-based byte column of
+// the match start, kept for existing consumers. Spans and positions in a file decoded
+// with --encoding are those of its UTF-8 decoding, named by Encoding.
+type SearchHit struct {
+	Path          string                   `json:"path"`
+	Root          string                   `json:"root,omitempty"` // The --root the hit came from when several roots are searched
+	Line          int                      `json:"line"`
+	Column        int                      `json:"column"`
+	ByteSpan      [2]int                   `json:"byte_span"`
+	Start         *types.Position          `json:"start,omitempty"`
+	End           *types.Position          `json:"end,omitempty"`
+	MatchedSymbol string                   `json:"matched_symbol"`
+	Score         float64                  `json:"score"`
+	MatchedBy     []search.Matcher         `json:"matched_by"`            // Matchers that found this span
+	Snippet       string                   `json:"snippet"`               // The matched line, or all of them joined by newlines for a match spanning lines
+	Highlights    [][2]int                 `json:"highlights"`            // [start, end) ranges of snippet that matched (see snippetHighlights)
+	ContextBefore []string                 `json:"context_before"`        // Up to --context lines preceding the snippet
+	ContextAfter  []string                 `json:"context_after"`         // Up to --context lines following the snippet
+	Explanation   *search.ScoreExplanation `json:"explanation,omitempty"` // Score breakdown with --explain
+	Blame         *search.Blame            `json:"blame,omitempty"`       // Commit, author and date of the matched line with --blame
+	Encoding      string                   `json:"encoding,omitempty"`    // Encoding the file was decoded from with --encoding, when not UTF-8
+}
+
+// SearchSummary carries the query metadata for a formatted search run
+type SearchSummary struct {
+	Query            string   `json:"query"`
+	Total            int      `json:"total"`                 // Hits found, before --offset and --limit
+	Offset           int      `json:"offset"`                // Rank of the first returned hit, from --offset
+	Returned         int      `json:"returned"`              // Hits in this output
+	Truncated        bool     `json:"truncated,omitempty"`   // More hits follow the returned ones
+	TimedOut         bool     `json:"timed_out,omitempty"`   // --timeout-ms stopped the search; unsearched files may hold more hits
+	CapReached       bool     `json:"cap_reached,omitempty"` // The search found more than --max-results hits and kept that many
+	Suggestions      []string `json:"suggestions,omitempty"` // Symbol names close to a pattern that found nothing, unless --no-suggest
+	ElapsedMs        float64  `json:"elapsed_ms"`
+	PositionEncoding string   `json:"position_encoding,omitempty"` // Column unit of start/end: utf-8 or utf-16
+}
+
+// SearchOutput is the top-level object written by --format json
+type SearchOutput struct {
+	SearchSummary
+	Results []SearchHit `json:"results"`
+}
+
+// validateSearchFormat rejects unknown --format values before any search work is done
+func validateSearchFormat(format string) error {
+	switch format {
+	case "", searchFormatText, searchFormatJSON, searchFormatJSONL:
+		return nil
+	}
+	return fmt.Errorf("unknown output format %q (expected %s, %s or %s)",
+		format, searchFormatText, searchFormatJSON, searchFormatJSONL)
+}
+
+// isStructuredFormat reports whether the format bypasses the human-readable display
+func isStructuredFormat(format string) bool {
+	return format == searchFormatJSON || format == searchFormatJSONL
+}
+
+// hitPositions resolves hit byte spans to line/column positions. Each file is read and
+// indexed once, on its first hit.
+type hitPositions struct {
+	encoding types.PositionEncoding
+	load     func(path string) ([]byte, error)
+	indexes  map[string]*types.LineIndex // nil entries: the file could not be read
+}
+
+// newHitPositions reads hit files from disk, resolving relative paths against root
+func newHitPositions(encoding types.PositionEncoding, root string) *hitPositions {
+	return &hitPositions{
+		encoding: encoding,
+		load: func(path string) ([]byte, error) {
+			if !filepath.IsAbs(path) {
+				path = filepath.Join(root, path)
+			}
+			return os.ReadFile(path)
+		},
+		indexes: make(map[string]*types.LineIndex),
+	}
+}
+
+// span returns the start and end positions of r, or nils when they are unknown
+func (p *hitPositions) span(r search.GrepResult) (*types.Position, *types.Position) {
+	if p == nil || r.EndByte <= r.StartByte {
+		return nil, nil
+	}
+	path := r.Path
+	if r.Root != "" && !filepath.IsAbs(path) {
+		path = filepath.Join(r.Root, path)
+	}
+	li, ok := p.indexes[path]
+	if !ok {
+		if content, err := p.load(path); err == nil {
+			if r.Encoding != "" {
+				// Spans are offsets into the content as it was indexed
+				if decoded, err := encoding.DecodeText(content, r.Encoding); err == nil {
+					content = decoded
+				}
+			}
+			li = types.NewLineIndex(content)
+		}
+		p.indexes[path] = li
+	}
+	if li == nil {
+		return nil, nil
+	}
+	start := li.Position(r.StartByte, p.encoding)
+	end := li.Position(r.EndByte, p.encoding)
+	return &start, &end
+}
+
+// unit returns the encoding columns are counted in, UTF-8 bytes when p is nil
+func (p *hitPositions) unit() types.PositionEncoding {
+	if p == nil {
+		return types.PositionEncodingUTF8
+	}
+	return p.encoding
+}
+
+// newSearchHit converts an engine result into the stable hit schema
+func newSearchHit(r search.GrepResult, positions *hitPositions) SearchHit {
+	start, end := positions.span(r)
+	snippet := matchedSnippet(r)
+	return SearchHit{
+		Path:          r.Path,
+		Root:          r.Root,
+		Line:          r.Line,
+		Column:        r.Column,
+		ByteSpan:      [2]int{r.StartByte, r.EndByte},
+		Start:         start,
+		End:           end,
+		MatchedSymbol: r.Context.BlockName,
+		Score:         r.Score,
+		MatchedBy:     nonNilMatchers(r.MatchedBy),
+		Snippet:       snippet,
+		Highlights:    snippetHighlights(r, snippet, positions.unit()),
+		ContextBefore: nonNilLines(r.ContextBefore),
+		ContextAfter:  nonNilLines(r.ContextAfter),
+		Explanation:   r.Explanation,
+		Blame:         r.Blame,
+		Encoding:      r.Encoding,
+	}
+}
+
+// searchPage places the returned hits among all the hits a search found
+type searchPage struct {
+	total       int      // Hits found, before --offset and --limit
+	offset      int      // Rank of the first returned hit
+	timedOut    bool     // --timeout-ms stopped the search early
+	capReached  bool     // The search stopped at its result cap
+	suggestions []string // Symbol names close to a pattern that found nothing
+}
+
+// responsePage places the hits of resp, returned from offset on
+func responsePage(resp server.SearchResponse, offset int) searchPage {
+	return searchPage{
+		total:       resp.Total,
+		offset:      offset,
+		timedOut:    resp.TimedOut,
+		capReached:  resp.CapReached,
+		suggestions: resp.Suggestions,
+	}
+}
+
+// newSearchSummary describes returned hits on page. positions may be nil.
+func newSearchSummary(pattern string, page searchPage, returned int, elapsed time.Duration, positions *hitPositions) SearchSummary {
+	total := page.total
+	if returned > 0 {
+		total = max(total, page.offset+returned)
+	}
+	summary := SearchSummary{
+		Query:       pattern,
+		Total:       total,
+		Offset:      page.offset,
+		Returned:    returned,
+		Truncated:   total > page.offset+returned,
+		TimedOut:    page.timedOut,
+		CapReached:  page.capReached,
+		Suggestions: page.suggestions,
+		ElapsedMs:   float64(elapsed.Microseconds()) / 1000.0,
+	}
+	if positions != nil {
+		summary.PositionEncoding = positions.encoding.String()
+	}
+	return summary
+}
+
+// nonNilLines keeps context arrays as [] rather than null in the JSON schema
+func nonNilLines(lines []string) []string {
+	if lines == nil {
+		return []string{}
+	}
+	return lines
+}
+
+// nonNilMatchers keeps matched_by as [] rather than null in the JSON schema
+func nonNilMatchers(matchers []search.Matcher) []search.Matcher {
+	if matchers == nil {
+		return []search.Matcher{}
+	}
+	return matchers
+}
+
+// printContextSnippet prints a grep -C style window: context lines around the matched
+// line, rendered by f. Lines longer than width characters are cut (see
+// newSnippetLine); width <= 0 keeps them whole.
+func printContextSnippet(w io.Writer, r search.GrepResult, f SnippetFormatter, width int) {
+	lines := make([]SnippetLine, 0, len(r.ContextBefore)+1+len(r.ContextAfter))
+	lineNum := r.Line - len(r.ContextBefore)
+	for _, line := range r.ContextBefore {
+		lines = append(lines, newSnippetLine(lineNum, line, r, width))
+		lineNum++
+	}
+	last := lastMatchLine(r)
+	for l := r.Line; l <= last; l++ {
+		lines = append(lines, newSnippetLine(l, matchedLineSnippet(r, l), r, width))
+	}
+	for i, line := range r.ContextAfter {
+		lines = append(lines, newSnippetLine(last+1+i, line, r, width))
+	}
+	f.FormatSnippet(w, r.Path, lines)
+}
+
+// matchedSnippet returns the full source lines the hit covers, joined by newlines
+func matchedSnippet(r search.GrepResult) string {
+	last := lastMatchLine(r)
+	if last == r.Line {
+		return matchedLineSnippet(r, r.Line)
+	}
+	lines := make([]string, 0, last-r.Line+1)
+	for l := r.Line; l <= last; l++ {
+		lines = append(lines, matchedLineSnippet(r, l))
+	}
+	return strings.Join(lines, "\n")
+}
+
+// snippetHighlights returns the [start, end) ranges of snippet a hit matched, so a
+// client can highlight them without searching the snippet again: every term occurrence
+// on the hit's line, else the hit's own span. The snippet always starts at the
+// start of the hit's first line, however many context lines surround it, so offsets
+// are relative to that line. They count bytes, or UTF-16 code units with
+// --position-encoding utf-16.
+func snippetHighlights(r search.GrepResult, snippet string, enc types.PositionEncoding) [][2]int {
+	spans := r.TermSpans
+	if len(spans) == 0 && r.EndByte > r.StartByte {
+		spans = [][2]int{{r.StartByte, r.EndByte}}
+	}
+	lineStart := r.StartByte - r.Column
+	if idx := r.Line - r.Context.StartLine; idx < 0 || idx >= len(r.Context.Lines) {
+		lineStart = r.StartByte // The snippet fell back to the matched text
+	}
+	highlights := make([][2]int, 0, len(spans))
+	for _, span := range spans {
+		start := span[0] - lineStart
+		end := start + span[1] - span[0]
+		if match := strings.TrimRight(r.Match, "\r\n"); span[0] == r.StartByte && strings.Contains(match, "\n") {
+			// The snippet joins the lines of a multi-line match with bare newlines, so
+			// its end is found from the match's last line rather than the file bytes
+			end = strings.LastIndexByte(snippet, '\n') + len(match) - strings.LastIndexByte(match, '\n')
+		}
+		start, end = max(start, 0), min(end, len(snippet))
+		if start >= end {
+			continue
+		}
+		if enc == types.PositionEncodingUTF16 {
+			start, end = utf16Len(snippet[:start]), utf16Len(snippet[:end])
+		}
+		highlights = append(highlights, [2]int{start, end})
+	}
+	return highlights
+}
+
+// utf16Len counts the UTF-16 code units needed to encode s
+func utf16Len(s string) int {
+	n := 0
+	for _, r := range s {
+		n += utf16.RuneLen(r)
+	}
+	return n
+}
+
+// matchedLineSnippet returns the full source line of the hit at line, falling back to
+// the matched text on the hit's first line when the context does not include it
+func matchedLineSnippet(r search.GrepResult, line int) string {
+	idx := line - r.Context.StartLine
+	if idx >= 0 && idx < len(r.Context.Lines) {
+		return strings.TrimRight(r.Context.Lines[idx], "\r")
+	}
+	if line != r.Line {
+		return ""
+	}
+	return r.Match
+}
+
+// writeFormattedResults writes results in a structured format. positions may be nil,
+// in which case hits carry no start/end positions.
+//
+// page.total is the number of hits found and page.offset the rank of the first result.
+// --offset and --limit are applied after duplicates are merged and results are ranked,
+// so total can exceed offset+len(results); truncated says so, telling a pager more
+// pages exist.
+//
+// json writes a single object: {"query", "total", "offset", "returned", "elapsed_ms",
+// "results": [...]}. jsonl writes one hit object per line followed by a final summary
+// line ({"query", "total", "offset", "returned", "elapsed_ms"}) so large result sets
+// can be streamed.
+func writeFormattedResults(w io.Writer, format, pattern string, results []search.GrepResult, page searchPage, elapsed time.Duration, positions *hitPositions) error {
+	summary := newSearchSummary(pattern, page, len(results), elapsed, positions)
+
+	enc := json.NewEncoder(w)
+	switch format {
+	case searchFormatJSON:
+		hits := make([]SearchHit, len(results))
+		for i, r := range results {
+			hits[i] = newSearchHit(r, positions)
+		}
+		return enc.Encode(SearchOutput{SearchSummary: summary, Results: hits})
+	case searchFormatJSONL:
+		for _, r := range results {
+			if err := enc.Encode(newSearchHit(r, positions)); err != nil {
+				return err
+			}
+		}
+		return enc.Encode(summary)
+	default:
+		return validateSearchFormat(format)
+	}
+}
@@ -0,0 +1,273 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"os"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func sampleFormatResults() []search.GrepResult {
+	return []search.GrepResult{
+		{
+			Path:      "src/main.rs",
+			Line:      29,
+			Column:    11,
+			StartByte: 612,
+			EndByte:   620,
+			Match:     "get_user",
+			Score:     12.5,
+			Context: search.ExtractedContext{
+				StartLine: 28,
+				EndLine:   30,
+				Lines:     []string{"    /// GetUser retrieves a user by ID", "    pub fn get_user(&self, id: &str) -> Result<User, Box<dyn Error>> {\r", "        self.db.find_user(id)"},
+				BlockName: "get_user",
+			},
+		},
+		{
+			Path:      "main.go",
+			Line:      33,
+			Column:    22,
+			StartByte: 700,
+			EndByte:   707,
+			Match:     "GetUser",
+			Score:     10,
+		},
+	}
+}
+
+func TestWriteFormattedResultsJSON(t *testing.T) {
+	var buf bytes.Buffer
+	err := writeFormattedResults(&buf, searchFormatJSON, "get_user", sampleFormatResults(), searchPage{total: 2}, 1500*time.Microsecond, nil)
+	require.NoError(t, err)
+
+	var out map[string]interface{}
+	require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+	assert.Equal(t, "get_user", out["query"])
+	assert.Equal(t, float64(2), out["total"])
+	assert.NotContains(t, out, "truncated")
+	assert.Equal(t, 1.5, out["elapsed_ms"])
+
+	results := out["results"].([]interface{})
+	require.Len(t, results, 2)
+
+	first := results[0].(map[string]interface{})
+	assert.Equal(t, "src/main.rs", first["path"])
+	assert.Equal(t, float64(29), first["line"])
+	assert.Equal(t, float64(11), first["column"])
+	assert.Equal(t, []interface{}{float64(612), float64(620)}, first["byte_span"])
+	assert.Equal(t, "get_user", first["matched_symbol"])
+	assert.Equal(t, 12.5, first["score"])
+	assert.Equal(t, "    pub fn get_user(&self, id: &str) -> Result<User, Box<dyn Error>> {", first["snippet"])
+	assert.Equal(t, []interface{}{[]interface{}{float64(11), float64(19)}}, first["highlights"])
+
+	// Without context the snippet falls back to the matched text
+	second := results[1].(map[string]interface{})
+	assert.Equal(t, "GetUser", second["snippet"])
+	assert.Equal(t, []interface{}{[]interface{}{float64(0), float64(7)}}, second["highlights"])
+	assert.Equal(t, "", second["matched_symbol"])
+}
+
+func TestWriteFormattedResultsJSONL(t *testing.T) {
+	var buf bytes.Buffer
+	err := writeFormattedResults(&buf, searchFormatJSONL, "get_user", sampleFormatResults(), searchPage{total: 7}, 2*time.Millisecond, nil)
+	require.NoError(t, err)
+
+	var lines []map[string]interface{}
+	scanner := bufio.NewScanner(&buf)
+	for scanner.Scan() {
+		var obj map[string]interface{}
+		require.NoError(t, json.Unmarshal(scanner.Bytes(), &obj), "line %q", scanner.Text())
+		lines = append(lines, obj)
+	}
+	require.Len(t, lines, 3, "two hits plus a summary line")
+
+	assert.Equal(t, "src/main.rs", lines[0]["path"])
+	assert.Equal(t, "main.go", lines[1]["path"])
+
+	summary := lines[2]
+	assert.Equal(t, "get_user", summary["query"])
+	assert.Equal(t, float64(7), summary["total"], "total counts hits dropped by --limit")
+	assert.Equal(t, true, summary["truncated"])
+	assert.Equal(t, 2.0, summary["elapsed_ms"])
+	assert.NotContains(t, summary, "results")
+}
+
+func TestWriteFormattedResultsPage(t *testing.T) {
+	var buf bytes.Buffer
+	require.NoError(t, writeFormattedResults(&buf, searchFormatJSON, "get_user", sampleFormatResults(), searchPage{total: 5, offset: 2}, time.Millisecond, nil))
+	var out SearchOutput
+	require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+	assert.Equal(t, 5, out.Total)
+	assert.Equal(t, 2, out.Offset)
+	assert.Equal(t, 2, out.Returned)
+	assert.True(t, out.Truncated, "a fifth hit follows ranks 2 and 3")
+
+	buf.Reset()
+	require.NoError(t, writeFormattedResults(&buf, searchFormatJSON, "get_user", nil, searchPage{total: 5, offset: 10}, time.Millisecond, nil))
+	out = SearchOutput{}
+	require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+	assert.Equal(t, 5, out.Total, "an offset past the end still reports the total")
+	assert.Equal(t, 0, out.Returned)
+	assert.False(t, out.Truncated)
+	assert.Contains(t, buf.String(), `"results":[]`)
+}
+
+func TestWriteFormattedResultsContextLines(t *testing.T) {
+	results := sampleFormatResults()
+	results[0].ContextBefore = []string{"    /// GetUser retrieves a user by ID"}
+	results[0].ContextAfter = []string{"        self.db.find_user(id)", "    }"}
+
+	var buf bytes.Buffer
+	require.NoError(t, writeFormattedResults(&buf, searchFormatJSON, "get_user", results, searchPage{total: len(results)}, time.Millisecond, nil))
+
+	var out SearchOutput
+	require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+	require.Len(t, out.Results, 2)
+	assert.Equal(t, results[0].ContextBefore, out.Results[0].ContextBefore)
+	assert.Equal(t, results[0].ContextAfter, out.Results[0].ContextAfter)
+
+	// Hits without context still carry empty arrays, never null
+	assert.Contains(t, buf.String(), `"context_before":[]`)
+	assert.NotNil(t, out.Results[1].ContextBefore)
+	assert.Empty(t, out.Results[1].ContextBefore)
+
+	var text bytes.Buffer
+	printContextSnippet(&text, results[0], PlainFormatter{}, 0)
+	want := "      28 |     /// GetUser retrieves a user by ID\n" +
+		"  >   29 |     pub fn get_user(&self, id: &str) -> Result<User, Box<dyn Error>> {\n" +
+		"      30 |         self.db.find_user(id)\n" +
+		"      31 |     }\n"
+	assert.Equal(t, want, text.String())
+}
+
+func TestWriteFormattedResultsMultiline(t *testing.T) {
+	r := search.GrepResult{
+		Path:      "open.go",
+		Line:      3,
+		EndLine:   5,
+		StartByte: 14,
+		EndByte:   46,
+		Match:     "func open(\n\tpath string,\n) error",
+		Context: search.ExtractedContext{
+			StartLine: 2,
+			EndLine:   6,
+			Lines:     []string{"", "func open(", "\tpath string,", ") error {", "\treturn nil"},
+		},
+		ContextBefore: []string{""},
+		ContextAfter:  []string{"\treturn nil"},
+	}
+
+	var buf bytes.Buffer
+	require.NoError(t, writeFormattedResults(&buf, searchFormatJSON, "open", []search.GrepResult{r}, searchPage{total: 1}, time.Millisecond, nil))
+	var out SearchOutput
+	require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+	require.Len(t, out.Results, 1)
+	assert.Equal(t, "func open(\n\tpath string,\n) error {", out.Results[0].Snippet, "the snippet holds every matched line")
+	assert.Equal(t, [][2]int{{0, 32}}, out.Results[0].Highlights, "the highlight ends on the match's last line")
+
+	var text bytes.Buffer
+	printContextSnippet(&text, r, PlainFormatter{}, 0)
+	want := "       2 | \n" +
+		"  >    3 | func open(\n" +
+		"  >    4 | \tpath string,\n" +
+		"  >    5 | ) error {\n" +
+		"       6 | \treturn nil\n"
+	assert.Equal(t, want, text.String())
+}
+
+func TestWriteFormattedResultsPositions(t *testing.T) {
+	files := map[string]string{"src/main.rs": "// ünïcode\nfn get_user() {}\n"}
+	results := []search.GrepResult{
+		{Path: "src/main.rs", Line: 2, Column: 3, StartByte: 16, EndByte: 24, Match: "get_user"},
+		{Path: "src/main.rs", Line: 1, Column: 3, StartByte: 3, EndByte: 12, Match: "ünïcode"},
+		{Path: "gone.rs", Line: 1, StartByte: 0, EndByte: 4, Match: "gone"},
+	}
+	positions := func(enc types.PositionEncoding) SearchOutput {
+		p := newHitPositions(enc, "")
+		p.load = func(path string) ([]byte, error) {
+			if content, ok := files[path]; ok {
+				return []byte(content), nil
+			}
+			return nil, os.ErrNotExist
+		}
+
+		var buf bytes.Buffer
+		require.NoError(t, writeFormattedResults(&buf, searchFormatJSON, "x", results, searchPage{total: len(results)}, time.Millisecond, p))
+		var out SearchOutput
+		require.NoError(t, json.Unmarshal(buf.Bytes(), &out))
+		return out
+	}
+
+	utf8 := positions(types.PositionEncodingUTF8)
+	assert.Equal(t, "utf-8", utf8.PositionEncoding)
+	assert.Equal(t, &types.Position{Line: 2, Column: 4}, utf8.Results[0].Start)
+	assert.Equal(t, &types.Position{Line: 2, Column: 12}, utf8.Results[0].End)
+	assert.Equal(t, &types.Position{Line: 1, Column: 13}, utf8.Results[1].End)
+	assert.Nil(t, utf8.Results[2].Start, "unreadable files have no positions")
+	assert.Equal(t, [2]int{0, 4}, utf8.Results[2].ByteSpan, "byte spans are always present")
+
+	utf16 := positions(types.PositionEncodingUTF16)
+	assert.Equal(t, "utf-16", utf16.PositionEncoding)
+	assert.Equal(t, &types.Position{Line: 1, Column: 4}, utf16.Results[1].Start)
+	assert.Equal(t, &types.Position{Line: 1, Column: 11}, utf16.Results[1].End, "ü and ï are one UTF-16 unit each")
+	assert.Equal(t, [][2]int{{0, 9}}, utf8.Results[1].Highlights)
+	assert.Equal(t, [][2]int{{0, 7}}, utf16.Results[1].Highlights, "highlights count in the position encoding too")
+	assert.Equal(t, utf8.Results[0].Start, utf16.Results[0].Start, "ASCII lines agree in both encodings")
+}
+
+func TestSnippetHighlights(t *testing.T) {
+	// `invalid credentials` as terms: the hit spans both words and lists each occurrence
+	r := search.GrepResult{
+		Line:      12,
+		Column:    8,
+		StartByte: 208,
+		EndByte:   243,
+		Match:     "invalid user or invalid credentials",
+		TermSpans: [][2]int{{208, 215}, {224, 231}, {232, 243}},
+		Context: search.ExtractedContext{
+			StartLine: 10,
+			Lines:     []string{"func login() {", "\t// check", "\treturn invalid user or invalid credentials", "}"},
+		},
+		ContextBefore: []string{"func login() {", "\t// check"},
+	}
+	snippet := matchedSnippet(r)
+	highlights := snippetHighlights(r, snippet, types.PositionEncodingUTF8)
+	assert.Equal(t, [][2]int{{8, 15}, {24, 31}, {32, 43}}, highlights, "context lines before the hit do not shift the snippet")
+	for _, h := range highlights {
+		assert.Contains(t, []string{"invalid", "credentials"}, snippet[h[0]:h[1]])
+	}
+
+	// A single term found twice on the line is one hit spanning the first occurrence
+	r = search.GrepResult{
+		Line:      3,
+		Column:    3,
+		StartByte: 14,
+		EndByte:   20,
+		Match:     "needle",
+		TermSpans: [][2]int{{14, 20}, {27, 33}},
+		Context:   search.ExtractedContext{StartLine: 3, Lines: []string{"// needle, then needle again"}},
+	}
+	snippet = matchedSnippet(r)
+	assert.Equal(t, [][2]int{{3, 9}, {16, 22}}, snippetHighlights(r, snippet, types.PositionEncodingUTF8), "every occurrence is highlighted")
+
+	assert.Empty(t, snippetHighlights(search.GrepResult{Line: 1}, "", types.PositionEncodingUTF8), "hits without a span highlight nothing")
+	assert.NotNil(t, snippetHighlights(search.GrepResult{Line: 1}, "", types.PositionEncodingUTF8))
+}
+
+func TestValidateSearchFormat(t *testing.T) {
+	for _, format := range []string{"", "text", "json", "jsonl"} {
+		assert.NoError(t, validateSearchFormat(format), format)
+	}
+	assert.Error(t, validateSearchFormat("xml"))
+	assert.False(t, isStructuredFormat("text"))
+	assert.True(t, isStructuredFormat("jsonl"))
+}
@@ -0,0 +1,142 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+
+	"github.com/urfave/cli/v2"
+)
+
+// SearchGroup is the stable schema of one --group-by symbol group: the hits inside
+// one symbol, or outside every symbol of the file when Symbol is search.TopLevelGroup
+type SearchGroup struct {
+	Path   string      `json:"path"`
+	Symbol string      `json:"symbol"`
+	Kind   string      `json:"kind,omitempty"`
+	Line   int         `json:"line,omitempty"` // Declaration line of the symbol
+	Hits   []SearchHit `json:"hits"`
+}
+
+// GroupedSearchOutput is the top-level object written by --format json with
+// --group-by symbol
+type GroupedSearchOutput struct {
+	SearchSummary
+	Groups []SearchGroup `json:"groups"`
+}
+
+// validateGroupBy rejects unknown --group-by values and symbol grouping combined with
+// modes that do not produce located hits
+func validateGroupBy(groupBy string, roots int, streaming, counting, filesOnly bool) error {
+	switch groupBy {
+	case "", groupByRoot:
+		return nil
+	case groupBySymbol:
+	default:
+		return fmt.Errorf("unknown --group-by %q (expected %s or %s)", groupBy, groupByRoot, groupBySymbol)
+	}
+	switch {
+	case roots > 1:
+		return errors.New("--group-by symbol searches a single root")
+	case streaming:
+		return errors.New("--group-by symbol needs every hit; it cannot be combined with --streaming")
+	case counting, filesOnly:
+		return errors.New("--group-by symbol cannot be combined with --count, --count-by or --files-with-matches")
+	}
+	return nil
+}
+
+// groupedSearchCommand runs req as a ranked search and prints the kept hits nested
+// under their enclosing symbol
+func groupedSearchCommand(c *cli.Context, client *server.Client, req server.SearchRequest, format string, encoding types.PositionEncoding) error {
+	start := time.Now()
+	req.GroupBy = server.GroupBySymbol
+	resp, err := client.SearchWithRequest(req)
+	if err != nil {
+		return fmt.Errorf("search failed: %w", err)
+	}
+	groups := resp.Groups
+	for i := range groups {
+		groups[i].Path = pathutil.ToRelative(groups[i].Path, reportRoot(projectRoot))
+		groups[i].Hits = pathutil.ToRelativeGrepResults(groups[i].Hits, reportRoot(projectRoot))
+	}
+	elapsed := time.Since(start)
+
+	if c.Bool("json") && !isStructuredFormat(format) {
+		format = searchFormatJSON
+	}
+	if isStructuredFormat(format) {
+		page := responsePage(resp, req.Offset)
+		return matchStatus(resp.Total, writeFormattedGroups(os.Stdout, format, req.Pattern, groups, page, elapsed, newHitPositions(encoding, projectRoot)))
+	}
+
+	shown := 0
+	for _, g := range groups {
+		shown += len(g.Hits)
+	}
+	printTimeoutNote(resp.TimedOut, req.TimeoutMs)
+	printCapNote(resp.CapReached, resp.Total)
+	printSuggestions(resp.Suggestions)
+	fmt.Printf("Found %d results in %d symbols in %.1fms\n\n", resp.Total, len(groups), float64(elapsed.Microseconds())/1000.0)
+	printLimitNote(req.Offset, shown, resp.Total)
+	writeGroups(os.Stdout, groups, snippetFormatter(c), c.Int("max-snippet-width"))
+	return matchStatus(resp.Total, nil)
+}
+
+// writeGroups prints each group as a "path: Symbol (kind, line N)" header followed by
+// its hits, indented, in line order. Hits carrying --context lines print them around
+// the matched line.
+func writeGroups(w io.Writer, groups []search.SymbolGroup, f SnippetFormatter, width int) {
+	for _, g := range groups {
+		if g.Kind != "" {
+			fmt.Fprintf(w, "%s: %s (%s, line %d)\n", g.Path, g.Symbol, g.Kind, g.Line)
+		} else {
+			fmt.Fprintf(w, "%s: %s\n", g.Path, g.Symbol)
+		}
+		for _, r := range g.Hits {
+			printContextSnippet(w, r, f, width)
+		}
+		fmt.Fprintln(w)
+	}
+}
+
+// writeFormattedGroups is writeFormattedResults for grouped hits. json writes a single
+// object with a "groups" array; jsonl writes one group per line followed by the
+// summary line.
+func writeFormattedGroups(w io.Writer, format, pattern string, groups []search.SymbolGroup, page searchPage, elapsed time.Duration, positions *hitPositions) error {
+	out := make([]SearchGroup, len(groups))
+	shown := 0
+	for i, g := range groups {
+		hits := make([]SearchHit, len(g.Hits))
+		for j, r := range g.Hits {
+			hits[j] = newSearchHit(r, positions)
+		}
+		out[i] = SearchGroup{Path: g.Path, Symbol: g.Symbol, Kind: g.Kind, Line: g.Line, Hits: hits}
+		shown += len(hits)
+	}
+
+	summary := newSearchSummary(pattern, page, shown, elapsed, positions)
+
+	enc := json.NewEncoder(w)
+	switch format {
+	case searchFormatJSON:
+		return enc.Encode(GroupedSearchOutput{SearchSummary: summary, Groups: out})
+	case searchFormatJSONL:
+		for _, g := range out {
+			if err := enc.Encode(g); err != nil {
+				return err
+			}
+		}
+		return enc.Encode(summary)
+	default:
+		return validateSearchFormat(format)
+	}
+}
@@ -0,0 +1,65 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+)
+
+func TestWriteGroups(t *testing.T) {
+	hit := func(line int, text string) search.GrepResult {
+		return search.GrepResult{
+			Path:    "src/auth.rs",
+			Line:    line,
+			Match:   "token",
+			Context: searchtypes.ExtractedContext{StartLine: line, Lines: []string{text}},
+		}
+	}
+	groups := []search.SymbolGroup{
+		{Path: "src/auth.rs", Symbol: "AuthService::authenticate", Kind: "method", Line: 6, Hits: []search.GrepResult{
+			hit(7, "        let ok = token == self.token;"),
+			hit(8, "        log(token);"),
+			hit(9, "        ok && !token.is_empty()"),
+		}},
+		{Path: "src/auth.rs", Symbol: search.TopLevelGroup, Hits: []search.GrepResult{hit(1, "// token checks")}},
+	}
+
+	var out bytes.Buffer
+	writeGroups(&out, groups, PlainFormatter{}, 0)
+	assert.Equal(t, "src/auth.rs: AuthService::authenticate (method, line 6)\n"+
+		"  >    7 |         let ok = token == self.token;\n"+
+		"  >    8 |         log(token);\n"+
+		"  >    9 |         ok && !token.is_empty()\n"+
+		"\n"+
+		"src/auth.rs: <file top-level>\n"+
+		"  >    1 | // token checks\n"+
+		"\n", out.String())
+
+	var structured bytes.Buffer
+	require.NoError(t, writeFormattedGroups(&structured, searchFormatJSON, "token", groups, searchPage{total: 6}, time.Millisecond, nil))
+	var decoded GroupedSearchOutput
+	require.NoError(t, json.Unmarshal(structured.Bytes(), &decoded))
+	require.Len(t, decoded.Groups, 2)
+	assert.Equal(t, "AuthService::authenticate", decoded.Groups[0].Symbol)
+	assert.Len(t, decoded.Groups[0].Hits, 3)
+	assert.Equal(t, search.TopLevelGroup, decoded.Groups[1].Symbol)
+	assert.True(t, decoded.Truncated, "total counts hits dropped by --limit")
+}
+
+func TestValidateGroupBy(t *testing.T) {
+	assert.NoError(t, validateGroupBy("", 2, false, false, false))
+	assert.NoError(t, validateGroupBy(groupByRoot, 2, false, false, false))
+	assert.NoError(t, validateGroupBy(groupBySymbol, 1, false, false, false))
+	assert.Error(t, validateGroupBy("file", 1, false, false, false))
+	assert.Error(t, validateGroupBy(groupBySymbol, 2, false, false, false))
+	assert.Error(t, validateGroupBy(groupBySymbol, 1, true, false, false))
+	assert.Error(t, validateGroupBy(groupBySymbol, 1, false, true, false))
+	assert.Error(t, validateGroupBy(groupBySymbol, 1, false, false, true))
+}
@@ -0,0 +1,89 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"time"
+
+	"github.com/urfave/cli/v2"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// SearchLocation is a hit as written by --locations --format json or jsonl: where it
+// is and nothing else. Line and Column are 1-based, with the column counted in the
+// summary's position_encoding.
+type SearchLocation struct {
+	Path   string `json:"path"`
+	Line   int    `json:"line"`
+	Column int    `json:"column"`
+}
+
+// SearchLocationsOutput is the top-level object written by --locations --format json
+type SearchLocationsOutput struct {
+	SearchSummary
+	Results []SearchLocation `json:"results"`
+}
+
+// validateLocations rejects the flags --locations cannot honor: those that print
+// snippets or replace the hit list. A context from the config's defaults is not
+// rejected; --locations just ignores it.
+func validateLocations(c *cli.Context, counting, filesOnly, multiRoot, streaming bool) error {
+	switch {
+	case c.IsSet("context") && c.Int("context") > 0 || c.Bool("blame") || c.String("template") != "" || c.Bool("numbered"):
+		return errors.New("--locations prints no snippets; it cannot be combined with --context, --blame, --template or --numbered")
+	case counting || filesOnly:
+		return errors.New("--locations prints hits; it cannot be combined with --count, --count-by or --files-with-matches")
+	case c.String("group-by") != "" || c.Int("merge-gap") > 0:
+		return errors.New("--locations cannot be combined with --group-by or --merge-gap")
+	case multiRoot || streaming || c.String("file") != "" || c.Bool("compare-search"):
+		return errors.New("--locations searches the project index; it cannot be combined with several --root, --streaming, --file or --compare-search")
+	}
+	return nil
+}
+
+// newSearchLocation returns where r starts. Its file is only read to count the column
+// in UTF-16 units; UTF-8 columns come straight from the hit.
+func newSearchLocation(r search.GrepResult, positions *hitPositions) SearchLocation {
+	loc := SearchLocation{Path: r.Path, Line: r.Line, Column: r.Column + 1}
+	if positions.unit() == types.PositionEncodingUTF16 {
+		if start, _ := positions.span(r); start != nil {
+			loc.Line, loc.Column = start.Line, start.Column
+		}
+	}
+	return loc
+}
+
+// writeLocations prints results as --locations: one "path:line:col" line per hit in
+// text, the quickfix format editors read, or SearchLocation objects laid out as by
+// writeFormattedResults in json and jsonl
+func writeLocations(w io.Writer, format, pattern string, results []search.GrepResult, page searchPage, elapsed time.Duration, positions *hitPositions) error {
+	if !isStructuredFormat(format) {
+		for _, r := range results {
+			loc := newSearchLocation(r, positions)
+			if _, err := fmt.Fprintf(w, "%s:%d:%d\n", loc.Path, loc.Line, loc.Column); err != nil {
+				return err
+			}
+		}
+		return nil
+	}
+
+	summary := newSearchSummary(pattern, page, len(results), elapsed, positions)
+	enc := json.NewEncoder(w)
+	if format == searchFormatJSON {
+		locs := make([]SearchLocation, len(results))
+		for i, r := range results {
+			locs[i] = newSearchLocation(r, positions)
+		}
+		return enc.Encode(SearchLocationsOutput{SearchSummary: summary, Results: locs})
+	}
+	for _, r := range results {
+		if err := enc.Encode(newSearchLocation(r, positions)); err != nil {
+			return err
+		}
+	}
+	return enc.Encode(summary)
+}
@@ -0,0 +1,79 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/urfave/cli/v2"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestWriteLocations(t *testing.T) {
+	root := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(root, "a.go"), []byte("// é\né := héllo()\n"), 0644))
+	results := []search.GrepResult{
+		{Path: "a.go", Line: 2, Column: 6, StartByte: 12, EndByte: 18, Match: "héllo"},
+		{Path: "b.go", Line: 7, Column: 0, StartByte: 40, EndByte: 43, Match: "foo"},
+	}
+	page := searchPage{total: 2}
+
+	var out bytes.Buffer
+	require.NoError(t, writeLocations(&out, searchFormatText, "héllo", results, page, 0, newHitPositions(types.PositionEncodingUTF8, root)))
+	assert.Equal(t, "a.go:2:7\nb.go:7:1\n", out.String(), "1-based byte columns, quickfix style")
+
+	out.Reset()
+	require.NoError(t, writeLocations(&out, searchFormatJSON, "héllo", results, page, 0, newHitPositions(types.PositionEncodingUTF16, root)))
+	var decoded SearchLocationsOutput
+	require.NoError(t, json.Unmarshal(out.Bytes(), &decoded))
+	assert.Equal(t, 2, decoded.Returned)
+	assert.Equal(t, "utf-16", decoded.PositionEncoding)
+	assert.Equal(t, []SearchLocation{{Path: "a.go", Line: 2, Column: 6}, {Path: "b.go", Line: 7, Column: 1}}, decoded.Results,
+		"utf-16 columns come from the file; an unreadable one keeps the byte column")
+
+	out.Reset()
+	require.NoError(t, writeLocations(&out, searchFormatJSONL, "héllo", results, page, 0, nil))
+	lines := strings.Split(strings.TrimSpace(out.String()), "\n")
+	require.Len(t, lines, 3, "a line per hit, then the summary")
+	assert.JSONEq(t, `{"path":"a.go","line":2,"column":7}`, lines[0])
+	assert.Contains(t, lines[2], `"returned":2`)
+}
+
+func TestValidateLocations_ConfiguredContext(t *testing.T) {
+	validate := func(args ...string) error {
+		var err error
+		search := &cli.Command{
+			Name: "search",
+			Flags: []cli.Flag{
+				&cli.IntFlag{Name: "context", Aliases: []string{"C"}},
+				&cli.BoolFlag{Name: "blame"},
+				&cli.StringFlag{Name: "template"},
+				&cli.BoolFlag{Name: "numbered"},
+				&cli.StringFlag{Name: "group-by"},
+				&cli.IntFlag{Name: "merge-gap"},
+				&cli.StringFlag{Name: "file"},
+				&cli.BoolFlag{Name: "compare-search"},
+			},
+			Action: func(c *cli.Context) error {
+				err = validateLocations(c, false, false, false, false)
+				return nil
+			},
+		}
+		applySearchDefaults(search, config.Defaults{Context: 2})
+		app := &cli.App{Commands: []*cli.Command{search}}
+		require.NoError(t, app.Run(append([]string{"lci", "search"}, args...)))
+		return err
+	}
+
+	assert.NoError(t, validate("pattern"), "a context from the config's defaults is ignored")
+	assert.ErrorContains(t, validate("--context", "3", "pattern"), "--context")
+	assert.NoError(t, validate("-C", "0", "pattern"))
+}
@@ -0,0 +1,131 @@
+package main
+
+import (
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+// defaultMaxSnippetWidth is the default of `lci search --max-snippet-width`
+const defaultMaxSnippetWidth = 200
+
+// ellipsis marks the side(s) of a snippet line that were cut
+const ellipsis = "…"
+
+// newSnippetLine prepares one line of a hit's snippet for a SnippetFormatter: the
+// matched line is cut to at most width characters around the matched span, keeping the
+// hit located within it; other lines keep their start. width <= 0 keeps whole lines.
+func newSnippetLine(number int, line string, r search.GrepResult, width int) SnippetLine {
+	if number < r.Line || number > lastMatchLine(r) {
+		return SnippetLine{Number: number, Text: truncateLine(line, width)}
+	}
+	if number != r.Line {
+		// A later line of a match spanning lines: marked, but there is no column to center on
+		return SnippetLine{Number: number, Text: truncateLine(line, width), Matched: true}
+	}
+	text, hit := truncateAroundMatch(line, r, width)
+	return SnippetLine{Number: number, Text: text, Matched: true, Hit: hit}
+}
+
+// lastMatchLine returns the last line r's match covers
+func lastMatchLine(r search.GrepResult) int {
+	return max(r.Line, r.EndLine)
+}
+
+// blockSnippetLines prepares a hit's context block (Context.Lines) for formatting
+func blockSnippetLines(r search.GrepResult, width int) []SnippetLine {
+	lines := make([]SnippetLine, 0, len(r.Context.Lines))
+	for i, line := range r.Context.Lines {
+		lines = append(lines, newSnippetLine(r.Context.StartLine+i, line, r, width))
+	}
+	return lines
+}
+
+// truncateLine cuts a context line to at most width characters, keeping its start
+func truncateLine(line string, width int) string {
+	line, _ = truncateAroundMatch(line, search.GrepResult{}, width)
+	return line
+}
+
+// truncateAroundMatch cuts line to at most width characters (code points, ellipses
+// included), keeping the hit's span visible and centered, and returns the hit with its
+// column and byte span moved to where the span now sits in the cut line. A span longer
+// than the window keeps its start. A hit whose column is not on the line keeps the
+// start of the line. Cuts always fall on code point boundaries.
+func truncateAroundMatch(line string, r search.GrepResult, width int) (string, search.GrepResult) {
+	n := utf8.RuneCountInString(line)
+	if width <= 0 || n <= width {
+		return line, r
+	}
+
+	// offsets[i] is the byte offset of the i-th code point; offsets[n] is len(line)
+	offsets := make([]int, 0, n+1)
+	for i := range line {
+		offsets = append(offsets, i)
+	}
+	offsets = append(offsets, len(line))
+	runeAt := func(b int) int {
+		// Index of the code point containing byte b
+		lo, hi := 0, n
+		for lo < hi {
+			mid := (lo + hi + 1) / 2
+			if offsets[mid] <= b {
+				lo = mid
+			} else {
+				hi = mid - 1
+			}
+		}
+		return lo
+	}
+
+	ms, me := 0, 0
+	if r.Column >= 0 && r.Column < len(line) {
+		ms = runeAt(r.Column)
+		me = ms
+		if end := r.Column + (r.EndByte - r.StartByte); end > r.Column {
+			me = runeAt(min(end, len(line))-1) + 1
+		}
+	}
+
+	// Lay the window out assuming both sides are cut, then give the room of an
+	// ellipsis that turned out to be unnecessary back to the text
+	inner := max(width-2, 1)
+	ws, we := windowAround(ms, me, inner, n)
+	if ws == 0 && we < n {
+		we++
+	}
+	if we == n && ws > 0 {
+		ws--
+	}
+
+	out := line[offsets[ws]:offsets[we]]
+	shift := offsets[ws]
+	if ws > 0 {
+		out = ellipsis + out
+		shift -= len(ellipsis)
+	}
+	if we < n {
+		out += ellipsis
+	}
+
+	if r.Column >= 0 && r.Column < len(line) {
+		start := max(r.Column, offsets[ws])
+		end := min(r.Column+(r.EndByte-r.StartByte), offsets[we])
+		r.Column = start - shift
+		r.EndByte = r.StartByte + max(end-start, 0)
+	} else {
+		r.EndByte = r.StartByte
+	}
+	return out, r
+}
+
+// windowAround returns the [start, end) code point window of size w within a line of
+// n code points that centers the span [ms, me), or starts at ms when the span is wider
+func windowAround(ms, me, w, n int) (int, int) {
+	if me-ms >= w {
+		return ms, ms + w
+	}
+	start := ms - (w-(me-ms))/2
+	start = max(min(start, n-w), 0)
+	return start, start + w
+}
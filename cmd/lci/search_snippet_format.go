@@ -0,0 +1,136 @@
+package main
+
+import (
+	"fmt"
+	"html"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/urfave/cli/v2"
+)
+
+// Values accepted by `lci search --snippet-format`
+const (
+	snippetFormatPlain    = "plain"
+	snippetFormatMarkdown = "markdown"
+	snippetFormatHTML     = "html"
+)
+
+// SnippetLine is one source line of a hit's snippet. Text is already cut to
+// --max-snippet-width; on the matched line, Hit's column and byte span locate the match
+// within Text.
+type SnippetLine struct {
+	Number  int
+	Text    string
+	Matched bool
+	Hit     search.GrepResult
+}
+
+// SnippetFormatter renders the lines of one hit's snippet. The lines are selected and
+// truncated before formatting, so a formatter only decides the markup.
+type SnippetFormatter interface {
+	FormatSnippet(w io.Writer, path string, lines []SnippetLine)
+}
+
+// PlainFormatter writes numbered lines, marking the matched line with '>' and
+// optionally coloring the match with ANSI escapes
+type PlainFormatter struct {
+	Color    bool
+	Unmarked bool // Indent every line alike, with no '>', as the default search output does
+}
+
+func (f PlainFormatter) FormatSnippet(w io.Writer, _ string, lines []SnippetLine) {
+	for _, l := range lines {
+		text, marker := l.Text, "  "
+		if l.Matched {
+			text, marker = highlightMatch(l.Text, l.Hit, f.Color), "> "
+		}
+		if f.Unmarked {
+			marker = ""
+		}
+		fmt.Fprintf(w, "  %s%4d | %s\n", marker, l.Number, text)
+	}
+}
+
+// MarkdownFormatter writes the lines as a fenced code block tagged with the file's
+// extension, so Markdown viewers highlight them. The fence is longer than any run of
+// backticks in the code.
+type MarkdownFormatter struct{}
+
+func (MarkdownFormatter) FormatSnippet(w io.Writer, path string, lines []SnippetLine) {
+	longest := 0
+	for _, l := range lines {
+		run := 0
+		for _, c := range l.Text {
+			if c == '`' {
+				run++
+				longest = max(longest, run)
+			} else {
+				run = 0
+			}
+		}
+	}
+	fence := strings.Repeat("`", max(3, longest+1))
+
+	fmt.Fprintf(w, "%s%s\n", fence, strings.TrimPrefix(filepath.Ext(path), "."))
+	for _, l := range lines {
+		fmt.Fprintln(w, l.Text)
+	}
+	fmt.Fprintln(w, fence)
+}
+
+// HTMLFormatter writes the lines as a <pre> block with one <span> per line carrying its
+// number, and wraps the matched span in <mark>. All source text is escaped.
+type HTMLFormatter struct{}
+
+func (HTMLFormatter) FormatSnippet(w io.Writer, path string, lines []SnippetLine) {
+	fmt.Fprintf(w, "<pre class=\"lci-snippet\" data-path=\"%s\"><code>", html.EscapeString(path))
+	for _, l := range lines {
+		class, text := "line", html.EscapeString(l.Text)
+		if l.Matched {
+			class = "line match"
+			if start, end, ok := matchSpan(l.Text, l.Hit); ok {
+				text = html.EscapeString(l.Text[:start]) + "<mark>" + html.EscapeString(l.Text[start:end]) + "</mark>" +
+					html.EscapeString(l.Text[end:])
+			}
+		}
+		fmt.Fprintf(w, "<span class=\"%s\" data-line=\"%d\">%s</span>\n", class, l.Number, text)
+	}
+	fmt.Fprintln(w, "</code></pre>")
+}
+
+// validateSnippetFormat rejects unknown --snippet-format values before any search work
+// is done
+func validateSnippetFormat(format string) error {
+	switch format {
+	case "", snippetFormatPlain, snippetFormatMarkdown, snippetFormatHTML:
+		return nil
+	}
+	return fmt.Errorf("unknown snippet format %q (expected %s, %s or %s)", format, snippetFormatPlain, snippetFormatMarkdown, snippetFormatHTML)
+}
+
+// snippetFormatter returns the formatter --snippet-format selects. Only plain output
+// is colored, following --color.
+func snippetFormatter(c *cli.Context) SnippetFormatter {
+	switch c.String("snippet-format") {
+	case snippetFormatMarkdown:
+		return MarkdownFormatter{}
+	case snippetFormatHTML:
+		return HTMLFormatter{}
+	}
+	return PlainFormatter{Color: colorEnabled(c.String("color"), os.Stdout)}
+}
+
+// blockFormatter is snippetFormatter for the context blocks of the default search
+// output, whose plain lines carry no '>' marker
+func blockFormatter(c *cli.Context) SnippetFormatter {
+	f := snippetFormatter(c)
+	if plain, ok := f.(PlainFormatter); ok {
+		plain.Unmarked = true
+		return plain
+	}
+	return f
+}
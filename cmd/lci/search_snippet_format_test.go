@@ -0,0 +1,70 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+)
+
+func sampleSnippetHit() search.GrepResult {
+	return search.GrepResult{
+		Path:      "src/cmp.rs",
+		Line:      2,
+		Column:    7,
+		StartByte: 20,
+		EndByte:   23,
+		Match:     "a<b",
+		Context: searchtypes.ExtractedContext{StartLine: 1, Lines: []string{
+			"// `compare`",
+			"    if a<b && c {",
+			"    }",
+		}},
+	}
+}
+
+func TestSnippetFormatters(t *testing.T) {
+	hit := sampleSnippetHit()
+	lines := blockSnippetLines(hit, 0)
+	render := func(f SnippetFormatter) string {
+		var out bytes.Buffer
+		f.FormatSnippet(&out, hit.Path, lines)
+		return out.String()
+	}
+
+	assert.Equal(t, "       1 | // `compare`\n"+
+		"  >    2 |     if a<b && c {\n"+
+		"       3 |     }\n", render(PlainFormatter{}))
+	assert.Contains(t, render(PlainFormatter{Color: true}), "if "+ansiMatchStart+"a<b"+ansiReset+" && c")
+	assert.Equal(t, "     1 | // `compare`\n"+
+		"     2 |     if a<b && c {\n"+
+		"     3 |     }\n", render(PlainFormatter{Unmarked: true}), "the default output marks no line")
+
+	assert.Equal(t, "```rs\n"+
+		"// `compare`\n"+
+		"    if a<b && c {\n"+
+		"    }\n"+
+		"```\n", render(MarkdownFormatter{}))
+
+	assert.Equal(t, `<pre class="lci-snippet" data-path="src/cmp.rs"><code>`+
+		`<span class="line" data-line="1">// `+"`compare`"+`</span>`+"\n"+
+		`<span class="line match" data-line="2">    if <mark>a&lt;b</mark> &amp;&amp; c {</span>`+"\n"+
+		`<span class="line" data-line="3">    }</span>`+"\n"+
+		"</code></pre>\n", render(HTMLFormatter{}), "the match is marked and everything else escaped")
+}
+
+func TestMarkdownFormatterFence(t *testing.T) {
+	var out bytes.Buffer
+	MarkdownFormatter{}.FormatSnippet(&out, "README", []SnippetLine{{Number: 1, Text: "````go"}})
+	assert.Equal(t, "`````\n````go\n`````\n", out.String(), "the fence outgrows backtick runs in the code")
+}
+
+func TestValidateSnippetFormat(t *testing.T) {
+	for _, format := range []string{"", snippetFormatPlain, snippetFormatMarkdown, snippetFormatHTML} {
+		assert.NoError(t, validateSnippetFormat(format))
+	}
+	assert.Error(t, validateSnippetFormat("rtf"))
+}
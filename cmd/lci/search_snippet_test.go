@@ -0,0 +1,64 @@
+package main
+
+import (
+	"strings"
+	"testing"
+	"unicode/utf8"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+// matchedSnippet renders the matched line the way PlainFormatter prints it
+func matchedSnippet(line string, r search.GrepResult, color bool, width int) string {
+	l := newSnippetLine(r.Line, line, r, width)
+	return highlightMatch(l.Text, l.Hit, color)
+}
+
+func TestMatchedSnippet_CentersMatch(t *testing.T) {
+	line := strings.Repeat("a", 300) + "needle" + strings.Repeat("b", 300)
+	hit := search.GrepResult{Column: 300, StartByte: 1300, EndByte: 1306, Match: "needle"}
+
+	out := matchedSnippet(line, hit, false, 20)
+	assert.Equal(t, "…aaaaaaneedlebbbbbb…", out)
+	assert.Equal(t, 20, utf8.RuneCountInString(out))
+
+	colored := matchedSnippet(line, hit, true, 20)
+	assert.Equal(t, "…aaaaaa"+ansiMatchStart+"needle"+ansiReset+"bbbbbb…", colored)
+
+	assert.Equal(t, line, matchedSnippet(line, hit, false, 0), "0 keeps the whole line")
+	assert.Equal(t, "short needle", matchedSnippet("short needle", search.GrepResult{Column: 6, EndByte: 6}, false, 20))
+}
+
+func TestMatchedSnippet_AtEdges(t *testing.T) {
+	atStart := "needle" + strings.Repeat("b", 300)
+	out := matchedSnippet(atStart, search.GrepResult{Column: 0, EndByte: 6}, true, 20)
+	assert.Equal(t, ansiMatchStart+"needle"+ansiReset+strings.Repeat("b", 13)+"…", out, "only the cut side gets an ellipsis")
+
+	atEnd := strings.Repeat("a", 300) + "needle"
+	out = matchedSnippet(atEnd, search.GrepResult{Column: 300, EndByte: 6}, true, 20)
+	assert.Equal(t, "…"+strings.Repeat("a", 13)+ansiMatchStart+"needle"+ansiReset, out)
+
+	// A match wider than the window keeps its start and is highlighted up to the cut
+	wide := strings.Repeat("a", 50) + strings.Repeat("x", 100) + strings.Repeat("b", 50)
+	out = matchedSnippet(wide, search.GrepResult{Column: 50, EndByte: 100}, true, 20)
+	assert.Equal(t, "…"+ansiMatchStart+strings.Repeat("x", 18)+ansiReset+"…", out)
+}
+
+func TestMatchedSnippet_MultibyteUTF8(t *testing.T) {
+	line := strings.Repeat("é", 300) + "naïve" + strings.Repeat("ö", 300)
+	column := len(strings.Repeat("é", 300))
+	hit := search.GrepResult{Column: column, StartByte: 5000, EndByte: 5000 + len("naïve")}
+
+	out := matchedSnippet(line, hit, true, 15)
+	require.True(t, utf8.ValidString(out), "cuts must fall on code point boundaries: %q", out)
+	assert.Equal(t, "…"+strings.Repeat("é", 4)+ansiMatchStart+"naïve"+ansiReset+strings.Repeat("ö", 4)+"…", out)
+}
+
+func TestTruncateLine(t *testing.T) {
+	assert.Equal(t, "0123456789", truncateLine("0123456789", 10))
+	assert.Equal(t, "012345678…", truncateLine("0123456789abc", 10))
+	assert.Equal(t, "ééé…", truncateLine("éééééé", 4))
+}
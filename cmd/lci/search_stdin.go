@@ -0,0 +1,94 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/urfave/cli/v2"
+)
+
+// stdinName is the path hits in standard input are reported in without --stdin-name
+const stdinName = "<stdin>"
+
+// validateStdin rejects the flags a search of standard input cannot honor on top of
+// those validateSingleFile rejects, and --lang or --stdin-name without --stdin
+func validateStdin(c *cli.Context, stdin bool, singleFile string) error {
+	switch {
+	case !stdin && (c.IsSet("stdin-name") || stdinLanguage(c) != ""):
+		return errors.New("--lang and --stdin-name describe the content of --stdin; use the global --lang EXT=LANG for files")
+	case !stdin:
+		return nil
+	case singleFile != "":
+		return errors.New("--stdin searches standard input; it cannot be combined with --file")
+	case c.Bool("blame") || c.String("diff") != "":
+		return errors.New("--stdin content is not in git; it cannot be combined with --blame or --diff")
+	case c.Bool("prefix") || c.Bool("numbered") || c.Bool("locations"):
+		return errors.New("--stdin cannot be combined with --prefix, --numbered or --locations")
+	case c.String("stdin-name") == "":
+		return errors.New("--stdin-name must not be empty")
+	}
+	return nil
+}
+
+// stdinLanguage returns the --lang of `lci search`, the language of --stdin content.
+// The global --lang, which maps extensions to languages, is not it.
+func stdinLanguage(c *cli.Context) string {
+	if c.Command == nil || c.Command.Name != "search" {
+		return ""
+	}
+	return c.String("lang")
+}
+
+// searchContent indexes content alone as the virtual file name, parsed as language
+// when it is set or else by the extension of name, and searches it. Hits are reported
+// in name as given.
+func searchContent(cfg *config.Config, name, language string, content []byte, pattern string, options types.SearchOptions) ([]search.GrepResult, error) {
+	indexer := indexing.NewMasterIndex(cfg)
+	defer indexer.Close()
+	if language != "" {
+		if err := indexer.SetFileLanguage(name, language); err != nil {
+			return nil, fmt.Errorf("--lang: %w", err)
+		}
+	}
+	if _, err := indexer.IndexContent(name, content); err != nil {
+		return nil, fmt.Errorf("failed to index %s: %w", name, err)
+	}
+	return searchIndex(indexer, pattern, options)
+}
+
+// contentPositions resolves hit positions against content, the one file searched,
+// instead of reading files from disk
+func contentPositions(encoding types.PositionEncoding, content []byte) *hitPositions {
+	return &hitPositions{
+		encoding: encoding,
+		load:     func(string) ([]byte, error) { return content, nil },
+		indexes:  make(map[string]*types.LineIndex),
+	}
+}
+
+// searchStdinCommand runs `lci search --stdin` in this process, without the index
+// server: standard input is indexed as one virtual file and searched like --file
+func searchStdinCommand(c *cli.Context, pattern string, options types.SearchOptions, offset, limit int, format string, encoding types.PositionEncoding) error {
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+	content, err := io.ReadAll(os.Stdin)
+	if err != nil {
+		return fmt.Errorf("failed to read stdin: %w", err)
+	}
+
+	start := time.Now()
+	results, err := searchContent(cfg, c.String("stdin-name"), stdinLanguage(c), content, pattern, options)
+	if err != nil {
+		return err
+	}
+	return printFileResults(c, cfg, pattern, results, offset, limit, format, start, contentPositions(encoding, content))
+}
@@ -0,0 +1,79 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/urfave/cli/v2"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchContent(t *testing.T) {
+	cfg := &config.Config{
+		Project: config.Project{Root: t.TempDir()},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	content := []byte("// generated\npub fn load_user() -> Option<String> {\n    None.unwrap()\n}\n")
+	symbols := types.SearchOptions{UseRegex: true, SymbolsOnly: true}
+
+	results, err := searchContent(cfg, stdinName, "rust", content, `^load_`, symbols)
+	require.NoError(t, err)
+	require.Len(t, results, 1, "the content is parsed as --lang")
+	assert.Equal(t, stdinName, results[0].Path)
+	assert.Equal(t, 2, results[0].Line)
+	assert.Equal(t, 7, results[0].Column)
+
+	start, _ := contentPositions(types.PositionEncodingUTF8, content).span(results[0])
+	require.NotNil(t, start, "positions come from the content, not the disk")
+	assert.Equal(t, types.Position{Line: 2, Column: 8}, *start)
+
+	results, err = searchContent(cfg, stdinName, "", content, `^load_`, symbols)
+	require.NoError(t, err)
+	assert.Empty(t, results, "--lang of an earlier search does not carry over")
+
+	results, err = searchContent(cfg, "staged/user.txt", "", content, `^load_`, symbols)
+	require.NoError(t, err)
+	assert.Empty(t, results, "without --lang the name's extension picks no extractor")
+	results, err = searchContent(cfg, "staged/user.txt", "", content, "unwrap", types.SearchOptions{Terms: true})
+	require.NoError(t, err)
+	require.Len(t, results, 1)
+	assert.Equal(t, "staged/user.txt", results[0].Path)
+	assert.Equal(t, 3, results[0].Line)
+
+	_, err = searchContent(cfg, stdinName, "cobol", content, "unwrap", types.SearchOptions{})
+	assert.ErrorContains(t, err, "--lang")
+}
+
+func TestGlobalLangWithSearchLang(t *testing.T) {
+	run := func(args ...string) (*config.Config, string) {
+		var cfg *config.Config
+		var language string
+		search := &cli.Command{
+			Name:  "search",
+			Flags: []cli.Flag{&cli.StringFlag{Name: "lang"}, &cli.BoolFlag{Name: "stdin"}},
+			Action: func(c *cli.Context) error {
+				language = stdinLanguage(c)
+				var err error
+				cfg, err = loadConfigWithOverrides(c)
+				return err
+			},
+		}
+		app := &cli.App{
+			Flags:    []cli.Flag{&cli.StringFlag{Name: "root"}, &cli.StringSliceFlag{Name: "lang"}},
+			Commands: []*cli.Command{search},
+		}
+		require.NoError(t, app.Run(append([]string{"lci", "--root", t.TempDir()}, args...)))
+		return cfg, language
+	}
+
+	cfg, language := run("--lang", ".tpl=go", "search", "pattern")
+	assert.Equal(t, map[string]string{".tpl": "go"}, cfg.Index.Languages, "the global --lang reaches the index")
+	assert.Empty(t, language)
+
+	cfg, language = run("--lang", ".tpl=go", "search", "--stdin", "--lang", "rust", "pattern")
+	assert.Equal(t, map[string]string{".tpl": "go"}, cfg.Index.Languages, "search --lang does not replace the global one")
+	assert.Equal(t, "rust", language)
+}
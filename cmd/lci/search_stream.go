@@ -0,0 +1,104 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+
+	"github.com/urfave/cli/v2"
+)
+
+// validateStreaming rejects --streaming combinations that need every hit before
+// output can start
+func validateStreaming(format string, multiRoot, compareSearch bool, offset int) error {
+	switch {
+	case format == searchFormatJSON:
+		return fmt.Errorf("--streaming writes hits as they are found; use --format %s or %s", searchFormatJSONL, searchFormatText)
+	case multiRoot:
+		return errors.New("--streaming searches a single root")
+	case compareSearch:
+		return errors.New("--streaming cannot be combined with --compare-search")
+	case offset > 0:
+		return errors.New("--offset pages ranked results; it cannot be combined with --streaming")
+	}
+	return nil
+}
+
+// streamSearchCommand prints hits as the server finds them, in file discovery order
+// with no ranking across files. A non-zero limit stops the search once that many hits
+// have been printed, so it keeps the first N found rather than the best N.
+func streamSearchCommand(c *cli.Context, client *server.Client, pattern string, options types.SearchOptions, limit int, format string, encoding types.PositionEncoding) error {
+	start := time.Now()
+	formatter := snippetFormatter(c)
+	tmpl := hitTemplateFlag(c)
+	positions := newHitPositions(encoding, projectRoot)
+	jumps := newJumpRecorder(c, reportRoot(projectRoot))
+	defer jumps.save(pattern)
+	var capReached bool
+	emitted, err := streamHits(os.Stdout, format, positions, func(r search.GrepResult) {
+		if tmpl != nil {
+			tmpl.render(os.Stdout, templateHit{newSearchHit(r, positions), r.Match})
+		} else {
+			printRegularResult(c, r, formatter, jumps)
+		}
+	}, func(emit func(search.GrepResult) error) (err error) {
+		capReached, err = client.SearchStream(pattern, options, limit, emit)
+		return err
+	})
+	if err != nil {
+		return fmt.Errorf("search failed: %w", err)
+	}
+	printCapNote(capReached, emitted)
+	if tmpl != nil {
+		return matchStatus(emitted, nil) // Templated output is one line per hit
+	}
+	return matchStatus(emitted, writeStreamSummary(os.Stdout, format, pattern, emitted, limit, time.Since(start), encoding))
+}
+
+// streamHits writes each hit produced by run as soon as it arrives, with its path made
+// relative to the project root: a SearchHit line for jsonl, printText otherwise. It
+// returns the number of hits written.
+func streamHits(w io.Writer, format string, positions *hitPositions, printText func(search.GrepResult), run func(emit func(search.GrepResult) error) error) (int, error) {
+	enc := json.NewEncoder(w)
+	emitted := 0
+	err := run(func(r search.GrepResult) error {
+		emitted++
+		r.Path = pathutil.ToRelative(r.Path, reportRoot(projectRoot))
+		if format == searchFormatJSONL {
+			return enc.Encode(newSearchHit(r, positions))
+		}
+		printText(r)
+		return nil
+	})
+	return emitted, err
+}
+
+// writeStreamSummary ends a streamed search. The count is of hits written, since the
+// search stops early under --limit; truncated means the limit was reached and more hits
+// may exist.
+func writeStreamSummary(w io.Writer, format, pattern string, emitted, limit int, elapsed time.Duration, encoding types.PositionEncoding) error {
+	truncated := limit > 0 && emitted >= limit
+	if format == searchFormatJSONL {
+		return json.NewEncoder(w).Encode(SearchSummary{
+			Query:            pattern,
+			Total:            emitted,
+			Returned:         emitted,
+			Truncated:        truncated,
+			ElapsedMs:        float64(elapsed.Microseconds()) / 1000.0,
+			PositionEncoding: encoding.String(),
+		})
+	}
+	fmt.Fprintf(w, "Streamed %d results in %.1fms\n", emitted, float64(elapsed.Microseconds())/1000.0)
+	if truncated {
+		fmt.Fprintf(w, "Stopped at --limit %d (--limit 0 streams all)\n", limit)
+	}
+	return nil
+}
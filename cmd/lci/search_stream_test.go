@@ -0,0 +1,58 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestStreamHitsJSONL(t *testing.T) {
+	results := sampleFormatResults()
+	var buf bytes.Buffer
+	emitted, err := streamHits(&buf, searchFormatJSONL, nil, func(search.GrepResult) {
+		t.Fatal("jsonl does not print text")
+	}, func(emit func(search.GrepResult) error) error {
+		for _, r := range results {
+			if err := emit(r); err != nil {
+				return err
+			}
+		}
+		return nil
+	})
+	require.NoError(t, err)
+	assert.Equal(t, len(results), emitted)
+	require.NoError(t, writeStreamSummary(&buf, searchFormatJSONL, "get_user", emitted, emitted, time.Millisecond, types.PositionEncodingUTF8))
+
+	scanner := bufio.NewScanner(&buf)
+	var lines [][]byte
+	for scanner.Scan() {
+		lines = append(lines, append([]byte(nil), scanner.Bytes()...))
+	}
+	require.Len(t, lines, len(results)+1, "one line per hit plus a summary")
+
+	var hit SearchHit
+	require.NoError(t, json.Unmarshal(lines[0], &hit))
+	assert.Equal(t, results[0].Path, hit.Path, "hits keep arrival order")
+
+	var summary SearchSummary
+	require.NoError(t, json.Unmarshal(lines[len(lines)-1], &summary))
+	assert.Equal(t, len(results), summary.Total)
+	assert.True(t, summary.Truncated, "reaching --limit means more hits may exist")
+}
+
+func TestValidateStreaming(t *testing.T) {
+	assert.NoError(t, validateStreaming(searchFormatJSONL, false, false, 0))
+	assert.NoError(t, validateStreaming("", false, false, 0))
+	assert.Error(t, validateStreaming(searchFormatJSON, false, false, 0), "json needs every hit before writing")
+	assert.Error(t, validateStreaming("", true, false, 0))
+	assert.Error(t, validateStreaming("", false, true, 0))
+	assert.Error(t, validateStreaming("", false, false, 10), "offsets page ranked results")
+}
@@ -0,0 +1,22 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"strings"
+)
+
+// printSuggestions points a search that found nothing at the symbol names closest to
+// its pattern, which the index server only fills in without --no-suggest
+func printSuggestions(suggestions []string) {
+	writeSuggestions(os.Stderr, suggestions)
+}
+
+// writeSuggestions writes the "did you mean" line for suggestions, or nothing when
+// there are none
+func writeSuggestions(w io.Writer, suggestions []string) {
+	if len(suggestions) > 0 {
+		fmt.Fprintf(w, "no results; did you mean: %s?\n\n", strings.Join(suggestions, ", "))
+	}
+}
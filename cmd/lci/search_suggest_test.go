@@ -0,0 +1,18 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestWriteSuggestions(t *testing.T) {
+	var buf bytes.Buffer
+	writeSuggestions(&buf, []string{"get_user", "save_user"})
+	assert.Equal(t, "no results; did you mean: get_user, save_user?\n\n", buf.String())
+
+	buf.Reset()
+	writeSuggestions(&buf, nil)
+	assert.Empty(t, buf.String())
+}
@@ -0,0 +1,176 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+
+	"github.com/urfave/cli/v2"
+)
+
+// templateHit is what a --template is rendered from: the hit's SearchHit fields and
+// the matched text
+type templateHit struct {
+	SearchHit
+	Match string
+}
+
+// templateFields renders the fields `lci search --template` can interpolate. Columns
+// are 1-based in --position-encoding units, like SearchHit.Start, falling back to the
+// byte column when the file cannot be read.
+var templateFields = map[string]func(templateHit) string{
+	"path":       func(h templateHit) string { return h.Path },
+	"root":       func(h templateHit) string { return h.Root },
+	"line":       func(h templateHit) string { return strconv.Itoa(h.Line) },
+	"col":        func(h templateHit) string { return strconv.Itoa(h.start().Column) },
+	"end_line":   func(h templateHit) string { return strconv.Itoa(h.end().Line) },
+	"end_col":    func(h templateHit) string { return strconv.Itoa(h.end().Column) },
+	"byte_start": func(h templateHit) string { return strconv.Itoa(h.ByteSpan[0]) },
+	"byte_end":   func(h templateHit) string { return strconv.Itoa(h.ByteSpan[1]) },
+	"symbol":     func(h templateHit) string { return h.MatchedSymbol },
+	"score":      func(h templateHit) string { return strconv.FormatFloat(h.Score, 'f', 2, 64) },
+	"match":      func(h templateHit) string { return h.Match },
+	"text":       func(h templateHit) string { return h.Snippet },
+	"matched_by": func(h templateHit) string { return joinMatchers(h.MatchedBy) },
+}
+
+// start is the hit's start position, by byte column when the file could not be read
+func (h templateHit) start() types.Position {
+	if h.Start != nil {
+		return *h.Start
+	}
+	return types.Position{Line: h.Line, Column: h.Column + 1}
+}
+
+// end is the hit's end position, by byte column when the file could not be read
+func (h templateHit) end() types.Position {
+	if h.End != nil {
+		return *h.End
+	}
+	start := h.start()
+	return types.Position{Line: start.Line, Column: start.Column + len(h.Match)}
+}
+
+// hitTemplate is a parsed --template: literal text and field references in order
+type hitTemplate struct {
+	parts []templatePart
+}
+
+// templatePart is literal text, or a field when render is set
+type templatePart struct {
+	text   string
+	render func(templateHit) string
+}
+
+// parseHitTemplate parses a template such as `{path}:{line}:{col}: {text}`. `{{` and
+// `}}` stand for literal braces. An unknown field or an unbalanced brace is an error,
+// so a typo fails before the search runs. An empty template returns nil.
+func parseHitTemplate(tmpl string) (*hitTemplate, error) {
+	if tmpl == "" {
+		return nil, nil
+	}
+	t := &hitTemplate{}
+	var literal strings.Builder
+	for i := 0; i < len(tmpl); i++ {
+		switch c := tmpl[i]; {
+		case c == '{' && strings.HasPrefix(tmpl[i:], "{{"), c == '}' && strings.HasPrefix(tmpl[i:], "}}"):
+			literal.WriteByte(c)
+			i++
+		case c == '{':
+			end := strings.IndexByte(tmpl[i:], '}')
+			if end < 0 {
+				return nil, fmt.Errorf("--template has an unclosed { at byte %d (write {{ for a literal brace)", i)
+			}
+			name := tmpl[i+1 : i+end]
+			render, ok := templateFields[name]
+			if !ok {
+				return nil, fmt.Errorf("unknown --template field {%s} (known fields: %s)", name, knownTemplateFields())
+			}
+			if literal.Len() > 0 {
+				t.parts = append(t.parts, templatePart{text: literal.String()})
+				literal.Reset()
+			}
+			t.parts = append(t.parts, templatePart{render: render})
+			i += end
+		case c == '}':
+			return nil, fmt.Errorf("--template has an unmatched } at byte %d (write }} for a literal brace)", i)
+		default:
+			literal.WriteByte(c)
+		}
+	}
+	if literal.Len() > 0 {
+		t.parts = append(t.parts, templatePart{text: literal.String()})
+	}
+	return t, nil
+}
+
+// knownTemplateFields lists the field names for error messages
+func knownTemplateFields() string {
+	names := make([]string, 0, len(templateFields))
+	for name := range templateFields {
+		names = append(names, "{"+name+"}")
+	}
+	sort.Strings(names)
+	return strings.Join(names, ", ")
+}
+
+// render writes hit as one line
+func (t *hitTemplate) render(w io.Writer, hit templateHit) {
+	var b strings.Builder
+	for _, p := range t.parts {
+		if p.render != nil {
+			b.WriteString(p.render(hit))
+		} else {
+			b.WriteString(p.text)
+		}
+	}
+	fmt.Fprintln(w, b.String())
+}
+
+// writeTemplateResults writes one templated line per hit and nothing else, so the
+// output can be read by tools such as Emacs compilation-mode
+func writeTemplateResults(w io.Writer, t *hitTemplate, results []search.GrepResult, positions *hitPositions) {
+	for _, r := range results {
+		t.render(w, templateHit{newSearchHit(r, positions), r.Match})
+	}
+}
+
+// hitTemplateFlag returns the parsed --template, or nil when it is not set. searchCommand
+// has already rejected a malformed template.
+func hitTemplateFlag(c *cli.Context) *hitTemplate {
+	t, _ := parseHitTemplate(c.String("template"))
+	return t
+}
+
+// validateTemplate rejects a malformed --template and output modes that do not print
+// one line per hit
+func validateTemplate(c *cli.Context, format string, counting bool, groupBy string) error {
+	t, err := parseHitTemplate(c.String("template"))
+	if err != nil || t == nil {
+		return err
+	}
+	switch {
+	case isStructuredFormat(format):
+		return fmt.Errorf("--template cannot be combined with --format %s", format)
+	case counting:
+		return errors.New("--template cannot be combined with --count or --count-by")
+	case groupBy == groupBySymbol:
+		return fmt.Errorf("--template cannot be combined with --group-by %s", groupBySymbol)
+	}
+	return nil
+}
+
+// joinMatchers lists matchers comma-separated
+func joinMatchers(matchers []search.Matcher) string {
+	names := make([]string, len(matchers))
+	for i, m := range matchers {
+		names[i] = string(m)
+	}
+	return strings.Join(names, ",")
+}
@@ -0,0 +1,48 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+)
+
+func TestHitTemplate(t *testing.T) {
+	hit := sampleSnippetHit()
+	hit.Score = 3.14159
+	hit.Context.BlockName = "compare"
+	hit.MatchedBy = []search.Matcher{searchtypes.MatcherSubstring, searchtypes.MatcherSymbol}
+
+	render := func(tmpl string) string {
+		parsed, err := parseHitTemplate(tmpl)
+		require.NoError(t, err)
+		var out bytes.Buffer
+		writeTemplateResults(&out, parsed, []search.GrepResult{hit}, nil)
+		return out.String()
+	}
+
+	assert.Equal(t, "src/cmp.rs:2:8:     if a<b && c {\n", render("{path}:{line}:{col}: {text}"), "grep-compatible, 1-based columns")
+	assert.Equal(t, "compare [3.14] a<b 2:11\n", render("{symbol} [{score}] {match} {end_line}:{end_col}"))
+	assert.Equal(t, "20-23 substring,symbol\n", render("{byte_start}-{byte_end} {matched_by}"))
+	assert.Equal(t, "{path} }{ src/cmp.rs\n", render("{{path}} }}{{ {path}"), "doubled braces are literal")
+
+	for tmpl, msg := range map[string]string{
+		"{path}:{lien}": "unknown --template field {lien}",
+		"{}":            "unknown --template field {}",
+		"{path":         "unclosed {",
+		"path}":         "unmatched }",
+	} {
+		_, err := parseHitTemplate(tmpl)
+		if assert.Error(t, err, tmpl) {
+			assert.Contains(t, err.Error(), msg)
+		}
+	}
+
+	parsed, err := parseHitTemplate("")
+	assert.NoError(t, err)
+	assert.Nil(t, parsed)
+}
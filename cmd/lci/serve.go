@@ -0,0 +1,149 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"os/signal"
+	"syscall"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// serveCommand indexes the project root and answers length-prefixed JSON requests on
+// the --socket path until interrupted, removing the socket file on the way out
+func serveCommand(c *cli.Context) error {
+	socketPath := c.String("socket")
+	if socketPath == "" {
+		return errors.New("--socket is required")
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return err
+	}
+
+	srv, err := server.NewIndexServer(cfg)
+	if err != nil {
+		return fmt.Errorf("failed to create index server: %w", err)
+	}
+
+	start := time.Now()
+	if _, err := srv.Reindex(cfg.Project.Root); err != nil {
+		return fmt.Errorf("failed to index %s: %w", cfg.Project.Root, err)
+	}
+	fmt.Printf("Indexed %d files in %s\n", srv.FileCount(), time.Since(start).Round(time.Millisecond))
+
+	framed := server.NewFramedServer(srv, socketPath)
+	if err := framed.Listen(); err != nil {
+		return err
+	}
+	fmt.Printf("Serving %s on %s\n", cfg.Project.Root, socketPath)
+	fmt.Printf("Press Ctrl+C to stop\n")
+
+	serveErr := make(chan error, 1)
+	go func() {
+		serveErr <- framed.Serve()
+	}()
+
+	sigChan := make(chan os.Signal, 1)
+	signal.Notify(sigChan, os.Interrupt, syscall.SIGTERM)
+
+	select {
+	case sig := <-sigChan:
+		fmt.Printf("\nReceived signal %v, shutting down...\n", sig)
+	case err := <-serveErr:
+		framed.Close()
+		return fmt.Errorf("server error: %w", err)
+	}
+	return framed.Close()
+}
+
+// queryCommand sends one search, or with --reindex or --stats a control request, to a
+// `lci serve` socket and prints the reply
+func queryCommand(c *cli.Context) error {
+	socketPath := c.String("socket")
+	if socketPath == "" {
+		return errors.New("--socket is required")
+	}
+
+	var req server.FramedRequest
+	switch {
+	case c.Bool("reindex"):
+		req = server.FramedRequest{Type: server.FramedReindex, Reindex: &server.ReindexRequest{}}
+	case c.Bool("stats"):
+		req = server.FramedRequest{Type: server.FramedStats}
+	default:
+		if c.NArg() == 0 {
+			return errors.New("query requires a pattern (or --reindex or --stats)")
+		}
+		req = server.FramedRequest{
+			Type: server.FramedSearch,
+			Search: &server.SearchRequest{
+				Pattern: c.Args().First(),
+				Options: types.SearchOptions{
+					UseRegex:        c.Bool("regex"),
+					CaseInsensitive: c.Bool("ignore-case"),
+					MaxContextLines: 1,
+				},
+				MaxResults: c.Int("max-results"),
+				TimeoutMs:  c.Int("timeout-ms"),
+			},
+		}
+	}
+
+	client, err := server.DialFramed(socketPath)
+	if err != nil {
+		return err
+	}
+	defer client.Close()
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return err
+	}
+
+	if c.Bool("json") {
+		return json.NewEncoder(os.Stdout).Encode(resp)
+	}
+	if resp.Error != "" {
+		return errors.New(resp.Error)
+	}
+	if resp.Reindex != nil {
+		fmt.Println(resp.Reindex.Message)
+		return nil
+	}
+	if resp.Stats != nil {
+		if cache := resp.Stats.Cache; cache != nil {
+			fmt.Printf("search cache: %d hits, %d misses, %d evictions, %d entries holding %d results\n",
+				cache.Hits, cache.Misses, cache.Evictions, cache.Entries, cache.Results)
+		}
+		return nil
+	}
+	if resp.Search.Error != "" {
+		return errors.New(resp.Search.Error)
+	}
+	for _, r := range resp.Search.Results {
+		fmt.Printf("%s:%d:%s\n", pathutil.ToRelative(r.Path, reportRoot(projectRoot)), r.Line, matchedLine(r))
+	}
+	if resp.Search.TimedOut {
+		fmt.Fprintf(os.Stderr, "WARNING: search stopped after --timeout-ms %d; showing the hits found so far\n", c.Int("timeout-ms"))
+	}
+	printCapNote(resp.Search.CapReached, resp.Search.Total)
+	return nil
+}
+
+// matchedLine returns the text of the line a result matched on, falling back to the
+// match itself when the context does not cover it
+func matchedLine(r searchtypes.Result) string {
+	if i := r.Line - r.Context.StartLine; i >= 0 && i < len(r.Context.Lines) {
+		return r.Context.Lines[i]
+	}
+	return r.Match
+}
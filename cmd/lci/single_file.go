@@ -0,0 +1,133 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// validateSingleFile rejects flags that need the index server or the whole tree
+// alongside flag, --file or --stdin
+func validateSingleFile(flag string, multiRoot, streaming, counting, compareSearch bool, groupBy string, timeoutMs int) error {
+	switch {
+	case multiRoot:
+		return fmt.Errorf("%s searches one file; it cannot be combined with several --root values", flag)
+	case streaming:
+		return fmt.Errorf("%s cannot be combined with --streaming", flag)
+	case counting:
+		return fmt.Errorf("%s cannot be combined with --count or --count-by", flag)
+	case compareSearch:
+		return fmt.Errorf("%s cannot be combined with --compare-search", flag)
+	case groupBy != "":
+		return fmt.Errorf("%s cannot be combined with --group-by", flag)
+	case timeoutMs > 0:
+		return fmt.Errorf("--timeout-ms bounds the index server search; it cannot be combined with %s", flag)
+	}
+	return nil
+}
+
+// indexSingleFile parses just file into a fresh in-process index, without walking the
+// tree or consulting ignore files. The caller closes the index.
+func indexSingleFile(cfg *config.Config, file string) (*indexing.MasterIndex, types.FileID, error) {
+	path, err := filepath.Abs(file)
+	if err != nil {
+		return nil, 0, err
+	}
+	indexer := indexing.NewMasterIndex(cfg)
+	if err := indexer.IndexFile(path); err != nil {
+		indexer.Close()
+		return nil, 0, fmt.Errorf("failed to index %s: %w", file, err)
+	}
+	fileIDs := indexer.GetAllFileIDs()
+	if len(fileIDs) == 0 {
+		indexer.Close()
+		return nil, 0, fmt.Errorf("%s was not indexed: unsupported file type or larger than the max file size", file)
+	}
+	return indexer, fileIDs[0], nil
+}
+
+// searchFile searches only file the way searchRoot searches a tree, so paths and
+// spans match those a full-tree search reports for that file
+func searchFile(cfg *config.Config, file, pattern string, options types.SearchOptions) ([]search.GrepResult, error) {
+	indexer, _, err := indexSingleFile(cfg, file)
+	if err != nil {
+		return nil, err
+	}
+	defer indexer.Close()
+
+	results, err := searchIndex(indexer, pattern, options)
+	if err != nil {
+		return nil, err
+	}
+	return pathutil.ToRelativeGrepResults(results, reportRoot(cfg.Project.Root)), nil
+}
+
+// searchFileCommand runs `lci search --file` in this process, without the index server
+func searchFileCommand(c *cli.Context, file, pattern string, options types.SearchOptions, offset, limit int, format string, encoding types.PositionEncoding) error {
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+
+	start := time.Now()
+	results, err := searchFile(cfg, file, pattern, options)
+	if err != nil {
+		return err
+	}
+	return printFileResults(c, cfg, pattern, results, offset, limit, format, start, newHitPositions(encoding, cfg.Project.Root))
+}
+
+// printFileResults pages and prints the results of an in-process search begun at
+// start, as searchCommand prints those of the index server
+func printFileResults(c *cli.Context, cfg *config.Config, pattern string, results []search.GrepResult, offset, limit int, format string, start time.Time, positions *hitPositions) error {
+	total := len(results)
+	results = search.PageResults(results, offset, limit)
+	if c.Bool("blame") {
+		annotateBlame(results, reportRoot(cfg.Project.Root))
+	}
+	elapsed := time.Since(start)
+
+	if tmpl := hitTemplateFlag(c); tmpl != nil {
+		writeTemplateResults(os.Stdout, tmpl, results, positions)
+		return matchStatus(total, nil)
+	}
+	if c.Bool("json") && !isStructuredFormat(format) {
+		format = searchFormatJSON
+	}
+	if isStructuredFormat(format) {
+		return matchStatus(total, writeFormattedResults(os.Stdout, format, pattern, results, searchPage{total: total, offset: offset}, elapsed, positions))
+	}
+	return matchStatus(total, displayRegularResults(c, pattern, results, total, elapsed, c.Bool("compact-search")))
+}
+
+// outlineFileCommand runs `lci search --file F --format lsp-symbols`: it parses F alone
+// and prints its symbols as an LSP DocumentSymbol outline, with no query needed
+func outlineFileCommand(c *cli.Context, file string) error {
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+
+	indexer, fileID, err := indexSingleFile(cfg, file)
+	if err != nil {
+		return err
+	}
+	defer indexer.Close()
+
+	path := indexer.GetFilePath(fileID)
+	var entries []server.ListSymbolsEntry
+	for _, sym := range indexer.GetFileEnhancedSymbols(fileID) {
+		entries = append(entries, server.NewListSymbolsEntry(sym, path))
+	}
+	content, _ := indexer.GetFileContent(fileID) // As indexed, so decoded with --encoding
+	return writeLSPSymbols(os.Stdout, entries, content)
+}
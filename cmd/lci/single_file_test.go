@@ -0,0 +1,80 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchFile_MatchesFullTree(t *testing.T) {
+	root := t.TempDir()
+	files := map[string]string{
+		"src/main.rs": "fn main() {\n    let user = load_user().unwrap();\n    println!(\"{}\", user);\n}\n",
+		"src/lib.rs":  "pub fn load_user() -> Option<String> {\n    None.unwrap()\n}\n",
+		"blob.xyz":    "unwrap\n",
+	}
+	for path, content := range files {
+		full := filepath.Join(root, path)
+		require.NoError(t, os.MkdirAll(filepath.Dir(full), 0755))
+		require.NoError(t, os.WriteFile(full, []byte(content), 0644))
+	}
+	cfg := &config.Config{
+		Project: config.Project{Root: root},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+		Include: []string{"*.rs"},
+	}
+	options := types.SearchOptions{Terms: true}
+
+	type span struct {
+		Path             string
+		Line, Start, End int
+	}
+	spans := func(results []search.GrepResult, path string) []span {
+		var out []span
+		for _, r := range results {
+			if r.Path == path {
+				out = append(out, span{r.Path, r.Line, r.StartByte, r.EndByte})
+			}
+		}
+		return out
+	}
+
+	tree, _, err := searchRoot(cfg, "unwrap", options, 0)
+	require.NoError(t, err)
+	one, err := searchFile(cfg, filepath.Join(root, "src/main.rs"), "unwrap", options)
+	require.NoError(t, err)
+	require.NotEmpty(t, one)
+	assert.Equal(t, spans(tree, "src/main.rs"), spans(one, "src/main.rs"),
+		"the file's hits have the paths and spans of a full-tree search")
+	assert.Len(t, one, len(spans(one, "src/main.rs")), "no other file is searched")
+
+	indexer, fileID, err := indexSingleFile(cfg, filepath.Join(root, "src/lib.rs"))
+	require.NoError(t, err)
+	defer indexer.Close()
+	var names []string
+	for _, sym := range indexer.GetFileEnhancedSymbols(fileID) {
+		names = append(names, sym.Symbol.Name)
+	}
+	assert.Contains(t, names, "load_user", "the outline comes from the one parsed file")
+
+	_, err = searchFile(cfg, filepath.Join(root, "blob.xyz"), "unwrap", options)
+	assert.ErrorContains(t, err, "was not indexed")
+	_, err = searchFile(cfg, filepath.Join(root, "missing.rs"), "unwrap", options)
+	assert.Error(t, err)
+}
+
+func TestValidateSingleFile(t *testing.T) {
+	assert.NoError(t, validateSingleFile("--file", false, false, false, false, "", 0))
+	assert.ErrorContains(t, validateSingleFile("--file", true, false, false, false, "", 0), "several --root")
+	assert.ErrorContains(t, validateSingleFile("--file", false, true, false, false, "", 0), "--streaming")
+	assert.ErrorContains(t, validateSingleFile("--file", false, false, true, false, "", 0), "--count")
+	assert.ErrorContains(t, validateSingleFile("--file", false, false, false, false, groupBySymbol, 0), "--group-by")
+	assert.ErrorContains(t, validateSingleFile("--file", false, false, false, false, "", 100), "--timeout-ms")
+}
@@ -0,0 +1,169 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// CorpusStats describes what the index holds, for `lci stats`. Field names are part of
+// the --format json contract.
+type CorpusStats struct {
+	Files          int             `json:"files"`
+	Symbols        int             `json:"symbols"`
+	SymbolsPerFile float64         `json:"symbols_per_file"`
+	Kinds          map[string]int  `json:"kinds"`     // Symbols per kind, such as "struct" or "method"
+	Languages      map[string]int  `json:"languages"` // Indexed files per detected language, "text" for the rest
+	Largest        []LargestSymbol `json:"largest"`   // Symbols with the longest byte spans, longest first
+}
+
+// LargestSymbol is one entry of CorpusStats.Largest
+type LargestSymbol struct {
+	Path    string `json:"path"`
+	Kind    string `json:"kind"`
+	Name    string `json:"name"`
+	Parent  string `json:"parent,omitempty"`
+	Line    int    `json:"line"`
+	EndLine int    `json:"end_line,omitempty"`
+	Bytes   int    `json:"bytes"` // Length of the symbol's byte span
+}
+
+// corpusStatsBuilder accumulates CorpusStats from the exported symbol stream, keeping
+// only the top largest symbols so memory does not grow with the index
+type corpusStatsBuilder struct {
+	stats CorpusStats
+	top   int
+}
+
+func newCorpusStatsBuilder(stats server.StatsResponse, top int) *corpusStatsBuilder {
+	languages := stats.Languages
+	if languages == nil {
+		languages = map[string]int{}
+	}
+	return &corpusStatsBuilder{
+		stats: CorpusStats{
+			Files:     stats.FileCount,
+			Kinds:     map[string]int{},
+			Languages: languages,
+			Largest:   []LargestSymbol{},
+		},
+		top: top,
+	}
+}
+
+// add counts sym and keeps it if it is among the largest seen so far
+func (b *corpusStatsBuilder) add(sym server.ExportSymbol) {
+	b.stats.Symbols++
+	b.stats.Kinds[sym.Kind]++
+
+	size := sym.ByteSpan[1] - sym.ByteSpan[0]
+	largest := b.stats.Largest
+	if b.top <= 0 || size <= 0 || (len(largest) == b.top && size <= largest[len(largest)-1].Bytes) {
+		return
+	}
+	i := sort.Search(len(largest), func(i int) bool { return largest[i].Bytes < size })
+	if len(largest) < b.top {
+		largest = append(largest, LargestSymbol{})
+	}
+	copy(largest[i+1:], largest[i:])
+	largest[i] = LargestSymbol{Path: sym.Path, Kind: sym.Kind, Name: sym.Name, Parent: sym.Parent, Line: sym.Line, EndLine: sym.EndLine, Bytes: size}
+	b.stats.Largest = largest
+}
+
+func (b *corpusStatsBuilder) finish() CorpusStats {
+	if b.stats.Files > 0 {
+		b.stats.SymbolsPerFile = float64(b.stats.Symbols) / float64(b.stats.Files)
+	}
+	return b.stats
+}
+
+// statsCommand prints the composition of the index: symbols per kind, files per
+// language, symbols per file and the largest symbols
+func statsCommand(c *cli.Context) error {
+	format := c.String("format")
+	if format != searchFormatText && format != searchFormatJSON {
+		return fmt.Errorf("unknown stats format %q (expected %s or %s)", format, searchFormatText, searchFormatJSON)
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return fmt.Errorf("failed to load config: %w", err)
+	}
+
+	client, err := ensureServerRunning(c, cfg)
+	if err != nil {
+		return fmt.Errorf("failed to connect to index server: %w", err)
+	}
+
+	indexStats, err := client.GetStats()
+	if err != nil {
+		return fmt.Errorf("failed to get index stats: %w", err)
+	}
+	builder := newCorpusStatsBuilder(*indexStats, c.Int("top"))
+	root := reportRoot(projectRoot)
+	err = client.ExportSymbols(server.ExportRequest{}, func(sym server.ExportSymbol) error {
+		sym.Path = pathutil.ToRelative(sym.Path, root)
+		builder.add(sym)
+		return nil
+	})
+	if err != nil {
+		return fmt.Errorf("failed to read symbols: %w", err)
+	}
+
+	stats := builder.finish()
+	if format == searchFormatJSON {
+		encoder := json.NewEncoder(os.Stdout)
+		encoder.SetIndent("", "  ")
+		return encoder.Encode(stats)
+	}
+	writeCorpusStats(os.Stdout, stats)
+	return nil
+}
+
+// writeCorpusStats prints stats for people, counts sorted from the largest
+func writeCorpusStats(w io.Writer, stats CorpusStats) {
+	fmt.Fprintf(w, "Files:              %d\n", stats.Files)
+	fmt.Fprintf(w, "Symbols:            %d\n", stats.Symbols)
+	fmt.Fprintf(w, "Symbols per file:   %.1f\n", stats.SymbolsPerFile)
+
+	writeCounts(w, "Symbol kinds", stats.Kinds)
+	writeCounts(w, "Files per language", stats.Languages)
+
+	if len(stats.Largest) > 0 {
+		fmt.Fprintf(w, "\nLargest symbols:\n")
+		for _, sym := range stats.Largest {
+			name := sym.Name
+			if sym.Parent != "" {
+				name = sym.Parent + "." + sym.Name
+			}
+			fmt.Fprintf(w, "  %8d bytes  %s %s  %s:%d\n", sym.Bytes, sym.Kind, name, sym.Path, sym.Line)
+		}
+	}
+}
+
+// writeCounts prints a titled list of counts, largest first and ties by name
+func writeCounts(w io.Writer, title string, counts map[string]int) {
+	if len(counts) == 0 {
+		return
+	}
+	names := make([]string, 0, len(counts))
+	for name := range counts {
+		names = append(names, name)
+	}
+	sort.Slice(names, func(i, j int) bool {
+		if ci, cj := counts[names[i]], counts[names[j]]; ci != cj {
+			return ci > cj
+		}
+		return names[i] < names[j]
+	})
+	fmt.Fprintf(w, "\n%s:\n", title)
+	for _, name := range names {
+		fmt.Fprintf(w, "  %-18s%d\n", name+":", counts[name])
+	}
+}
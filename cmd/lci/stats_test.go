@@ -0,0 +1,43 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/server"
+)
+
+func TestCorpusStatsBuilder(t *testing.T) {
+	b := newCorpusStatsBuilder(server.StatsResponse{FileCount: 2, Languages: map[string]int{"rust": 2}}, 2)
+	for _, sym := range []server.ExportSymbol{
+		{Path: "store.rs", Kind: "struct", Name: "Store", Line: 1, ByteSpan: [2]int{0, 13}},
+		{Path: "store.rs", Kind: "method", Name: "get_user", Parent: "Store", Line: 4, ByteSpan: [2]int{30, 90}},
+		{Path: "store.rs", Kind: "method", Name: "put_user", Parent: "Store", Line: 8, ByteSpan: [2]int{95, 120}},
+		{Path: "user.rs", Kind: "trait", Name: "Named", Line: 1, ByteSpan: [2]int{0, 40}},
+	} {
+		b.add(sym)
+	}
+	stats := b.finish()
+
+	assert.Equal(t, 4, stats.Symbols)
+	assert.Equal(t, 2.0, stats.SymbolsPerFile)
+	assert.Equal(t, map[string]int{"struct": 1, "method": 2, "trait": 1}, stats.Kinds)
+	assert.Equal(t, map[string]int{"rust": 2}, stats.Languages)
+	if assert.Len(t, stats.Largest, 2, "only the top symbols are kept") {
+		assert.Equal(t, LargestSymbol{Path: "store.rs", Kind: "method", Name: "get_user", Parent: "Store", Line: 4, Bytes: 60}, stats.Largest[0])
+		assert.Equal(t, "Named", stats.Largest[1].Name)
+	}
+
+	var text bytes.Buffer
+	writeCorpusStats(&text, stats)
+	assert.Contains(t, text.String(), "Symbols per file:   2.0\n")
+	assert.Contains(t, text.String(), "\nSymbol kinds:\n  method:           2\n  struct:           1\n  trait:            1\n",
+		"kinds are listed largest first, ties by name")
+	assert.Contains(t, text.String(), "        60 bytes  method Store.get_user  store.rs:4\n")
+
+	empty := newCorpusStatsBuilder(server.StatsResponse{}, 10).finish()
+	assert.Zero(t, empty.SymbolsPerFile, "an empty index has no average")
+	assert.NotNil(t, empty.Largest)
+}
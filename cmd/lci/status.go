@@ -4,8 +4,10 @@ import (
 	"encoding/json"
 	"fmt"
 	"os"
+	"sort"
 	"time"
 
+	"github.com/standardbeagle/lci/internal/indexing"
 	"github.com/standardbeagle/lci/internal/server"
 
 	"github.com/urfave/cli/v2"
@@ -13,19 +15,23 @@ import (
 
 // ServerStatsReport represents the server stats for JSON output
 type ServerStatsReport struct {
-	Timestamp       time.Time `json:"timestamp"`
-	Ready           bool      `json:"ready"`
-	FileCount       int       `json:"file_count"`
-	SymbolCount     int       `json:"symbol_count"`
-	IndexSizeBytes  int64     `json:"index_size_bytes"`
-	BuildDurationMs int64     `json:"build_duration_ms"`
-	MemoryAllocMB   float64   `json:"memory_alloc_mb"`
-	MemoryTotalMB   float64   `json:"memory_total_mb"`
-	MemoryHeapMB    float64   `json:"memory_heap_mb"`
-	NumGoroutines   int       `json:"num_goroutines"`
-	UptimeSeconds   float64   `json:"uptime_seconds"`
-	SearchCount     int64     `json:"search_count"`
-	AvgSearchTimeMs float64   `json:"avg_search_time_ms"`
+	Timestamp       time.Time               `json:"timestamp"`
+	Ready           bool                    `json:"ready"`
+	FileCount       int                     `json:"file_count"`
+	SymbolCount     int                     `json:"symbol_count"`
+	IndexSizeBytes  int64                   `json:"index_size_bytes"`
+	BuildDurationMs int64                   `json:"build_duration_ms"`
+	MemoryAllocMB   float64                 `json:"memory_alloc_mb"`
+	MemoryTotalMB   float64                 `json:"memory_total_mb"`
+	MemoryHeapMB    float64                 `json:"memory_heap_mb"`
+	NumGoroutines   int                     `json:"num_goroutines"`
+	UptimeSeconds   float64                 `json:"uptime_seconds"`
+	SearchCount     int64                   `json:"search_count"`
+	AvgSearchTimeMs float64                 `json:"avg_search_time_ms"`
+	Languages       map[string]int          `json:"languages,omitempty"`
+	ParseWarnings   []indexing.ParseWarning `json:"parse_warnings,omitempty"`
+	SkippedBinary   int                     `json:"skipped_binary,omitempty"`
+	SampledOut      int                     `json:"sampled_out,omitempty"`
 }
 
 // statusCommand shows index server status and statistics
@@ -39,7 +45,7 @@ func statusCommand(c *cli.Context) error {
 		return fmt.Errorf("failed to load config: %w", err)
 	}
 
-	client, err := ensureServerRunning(cfg)
+	client, err := ensureServerRunning(c, cfg)
 	if err != nil {
 		return fmt.Errorf("failed to connect to server: %w", err)
 	}
@@ -81,6 +87,10 @@ func outputServerStatsJSON(stats *server.StatsResponse, status *server.IndexStat
 		UptimeSeconds:   stats.UptimeSeconds,
 		SearchCount:     stats.SearchCount,
 		AvgSearchTimeMs: stats.AvgSearchTimeMs,
+		Languages:       stats.Languages,
+		ParseWarnings:   stats.ParseWarnings,
+		SkippedBinary:   stats.SkippedBinary,
+		SampledOut:      stats.SampledOut,
 	}
 
 	encoder := json.NewEncoder(os.Stdout)
@@ -88,6 +98,9 @@ func outputServerStatsJSON(stats *server.StatsResponse, status *server.IndexStat
 	return encoder.Encode(report)
 }
 
+// maxParseWarningsShown caps the parse warnings listed by lci status --verbose
+const maxParseWarningsShown = 20
+
 // outputServerStatsHuman outputs server stats in human-readable format
 func outputServerStatsHuman(stats *server.StatsResponse, status *server.IndexStatus, verbose bool) error {
 	fmt.Printf("Lightning Code Index Server Status\n")
@@ -105,6 +118,9 @@ func outputServerStatsHuman(stats *server.StatsResponse, status *server.IndexSta
 	// Index statistics
 	fmt.Printf("\nIndex Statistics:\n")
 	fmt.Printf("  Files indexed:    %d\n", stats.FileCount)
+	if stats.SampledOut > 0 {
+		fmt.Printf("  Files sampled:    %d of %d (--sample)\n", stats.FileCount, stats.FileCount+stats.SampledOut)
+	}
 	fmt.Printf("  Symbols indexed:  %d\n", stats.SymbolCount)
 	fmt.Printf("  Index size:       %s\n", formatBytes(stats.IndexSizeBytes))
 	fmt.Printf("  Build time:       %s\n", formatMilliseconds(stats.BuildDurationMs))
@@ -140,6 +156,37 @@ func outputServerStatsHuman(stats *server.StatsResponse, status *server.IndexSta
 		fmt.Printf("  Memory total (bytes):   %d\n", int64(stats.MemoryTotalMB*1024*1024))
 	}
 
+	// Verbose mode also breaks the indexed files down by detected language
+	if verbose && len(stats.Languages) > 0 {
+		fmt.Printf("\nFiles per language:\n")
+		languages := make([]string, 0, len(stats.Languages))
+		for language := range stats.Languages {
+			languages = append(languages, language)
+		}
+		sort.Slice(languages, func(i, j int) bool {
+			if ci, cj := stats.Languages[languages[i]], stats.Languages[languages[j]]; ci != cj {
+				return ci > cj
+			}
+			return languages[i] < languages[j]
+		})
+		for _, language := range languages {
+			fmt.Printf("  %-18s%d\n", language+":", stats.Languages[language])
+		}
+	}
+
+	// Verbose mode ends with the files that were skipped or parsed with syntax errors
+	if verbose {
+		fmt.Printf("\nSkipped binary:   %d files (index them as text with --text)\n", stats.SkippedBinary)
+		fmt.Printf("Parse warnings:   %d files\n", len(stats.ParseWarnings))
+		for i, warning := range stats.ParseWarnings {
+			if i == maxParseWarningsShown {
+				fmt.Printf("  ... and %d more (see lci status --json)\n", len(stats.ParseWarnings)-i)
+				break
+			}
+			fmt.Printf("  %s: %s\n", warning.Path, warning.Message)
+		}
+	}
+
 	return nil
 }
 
@@ -0,0 +1,96 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/signal"
+	"syscall"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/server"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+	"github.com/urfave/cli/v2"
+)
+
+// defaultWatchDebounceMs batches editor save storms into a single re-index pass
+const defaultWatchDebounceMs = 200
+
+// watchCommand indexes a directory, keeps the index fresh as files change, and serves
+// queries over the project socket so CLI commands always see the latest index
+func watchCommand(c *cli.Context) error {
+	if dir := c.Args().First(); dir != "" {
+		if err := c.Set("root", dir); err != nil {
+			return fmt.Errorf("failed to set watch root: %w", err)
+		}
+	}
+
+	cfg, err := loadConfigWithOverrides(c)
+	if err != nil {
+		return err
+	}
+	cfg.Index.WatchMode = true
+	cfg.Index.WatchDebounceMs = c.Int("debounce")
+
+	socketPath := server.GetSocketPathForRoot(cfg.Project.Root)
+	if server.NewClientWithSocket(socketPath).IsServerRunning() {
+		return fmt.Errorf("an index server is already running for %s (use 'lci shutdown' first)", cfg.Project.Root)
+	}
+
+	watchIndexer := indexing.NewMasterIndex(cfg)
+	watchIndexer.SetWatchBatchHandler(func(summary indexing.WatchBatchSummary) {
+		if summary.Changed() == 0 && summary.Failed == 0 {
+			return
+		}
+		for i, p := range summary.Paths {
+			summary.Paths[i] = pathutil.ToRelative(p, reportRoot(cfg.Project.Root))
+		}
+		fmt.Printf("[%s] %s\n", time.Now().Format("15:04:05"), summary)
+	})
+
+	start := time.Now()
+	if err := watchIndexer.IndexDirectory(context.Background(), cfg.Project.Root); err != nil {
+		return fmt.Errorf("failed to index %s: %w", cfg.Project.Root, err)
+	}
+	watchIndexer.SetSearchEngine(search.NewEngine(watchIndexer))
+	fmt.Printf("Indexed %d files in %s\n", watchIndexer.GetFileCount(), time.Since(start).Round(time.Millisecond))
+
+	srv, err := server.NewIndexServerWithIndex(cfg, watchIndexer, watchIndexer.GetSearchEngine())
+	if err != nil {
+		return fmt.Errorf("failed to create index server: %w", err)
+	}
+	srv.SetSocketPath(socketPath)
+	if err := srv.Start(); err != nil {
+		return fmt.Errorf("failed to start index server: %w", err)
+	}
+
+	fmt.Printf("Watching %s (debounce %dms)\n", cfg.Project.Root, cfg.Index.WatchDebounceMs)
+	fmt.Printf("Socket: %s\n", socketPath)
+	fmt.Printf("Press Ctrl+C or run 'lci shutdown' to stop\n")
+
+	sigChan := make(chan os.Signal, 1)
+	signal.Notify(sigChan, os.Interrupt, syscall.SIGTERM)
+
+	serverDone := make(chan struct{})
+	go func() {
+		srv.Wait()
+		close(serverDone)
+	}()
+
+	select {
+	case sig := <-sigChan:
+		fmt.Printf("\nReceived signal %v, shutting down...\n", sig)
+	case <-serverDone:
+		fmt.Println("Server shutdown requested")
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+	defer cancel()
+
+	if err := srv.Shutdown(ctx); err != nil {
+		return fmt.Errorf("shutdown error: %w", err)
+	}
+	return watchIndexer.Close()
+}
@@ -3,6 +3,7 @@ package config
 import (
 	"fmt"
 	"os"
+	"path/filepath"
 	"runtime"
 	"github.com/standardbeagle/lci/internal/types"
 )
@@ -25,10 +26,12 @@ type Config struct {
 	Semantic             Semantic
 	SemanticScoring      SemanticScoring
 	Search               Search
+	Defaults             Defaults
 	FeatureFlags         FeatureFlags
 	Include              []string
 	Exclude              []string
 	PropagationConfigDir string // Directory for propagation configuration files
+	File                 string // The .lci.kdl this config was read from (empty = built-in defaults)
 }
 
 type Project struct {
@@ -42,10 +45,19 @@ type Index struct {
 	MaxFileCount     int
 	FollowSymlinks   bool
 	SmartSizeControl bool
-	PriorityMode     string // "recent", "small", "important"
-	RespectGitignore bool   // Process .gitignore files for additional exclusions
-	WatchMode        bool   // Enable file system watching for automatic reindexing
-	WatchDebounceMs  int    // Debounce time for file change events
+	PriorityMode     string            // "recent", "small", "important"
+	RespectGitignore bool              // Process .gitignore files for additional exclusions
+	WatchMode        bool              // Enable file system watching for automatic reindexing
+	WatchDebounceMs  int               // Debounce time for file change events
+	IndexFile        string            // Saved index to reuse and update across runs (empty = disabled)
+	MmapThreshold    int64             // Files at least this many bytes are memory-mapped while loaded instead of read (0 = never)
+	Languages        map[string]string // Extension -> language overrides, e.g. ".tpl" -> "go" (see internal/lang)
+	TreatAsText      bool              // Index binary-looking files as text instead of skipping them
+	Encoding         string            // Text encoding files are decoded from, "auto" to detect it per file ("" = UTF-8 only)
+	Tokenize         string            // BM25 term splitting: "raw" (default) or "identifier" (see core.TermTokenizer)
+	MaxDepth         *int              // Deepest directory level indexed below the root, 0 = only files in the root (nil = no limit)
+	Sample           float64           // Fraction of files indexed, each kept with this probability (0 = every file)
+	SampleSeed       int64             // Seed of the Sample draw; the same seed keeps the same files
 }
 
 type Performance struct {
@@ -62,6 +74,11 @@ type Performance struct {
 	StartupDelayMs int // Delay before auto-indexing starts (default: 1500ms)
 	// This delay allows the UI (e.g., Claude Code) to become responsive before
 	// CPU-intensive indexing begins. Set to 0 to disable the delay.
+
+	ReindexInPlace bool // Rebuild the server's index in place on reindex (default: build aside and swap)
+	// Building aside keeps the old index serving queries until the new one is
+	// complete, but briefly holds both in memory. In place holds one and fails
+	// queries until the rebuild finishes.
 }
 
 type Semantic struct {
@@ -143,6 +160,18 @@ type Search struct {
 	EnsureCompleteStmt     bool          // Ensure complete statements in context
 	IncludeLeadingComments bool          // Include leading comments in context
 	Ranking                SearchRanking // File type and symbol ranking preferences
+
+	Aliases map[string][]string // Spellings --expand adds to the built-in aliases, by lower-case word, e.g. "cfg" -> ["settings"]
+}
+
+// Defaults holds the `defaults` block of .lci.kdl: values for `lci search` flags that
+// apply when the flag is not given on the command line
+type Defaults struct {
+	IgnoreCase bool     // --ignore-case
+	Context    int      // --context
+	Format     string   // --format (empty = text)
+	Include    []string // --include, query-time globs
+	Exclude    []string // --exclude, query-time globs
 }
 
 // FeatureFlags controls experimental features and rollback capabilities
@@ -168,7 +197,42 @@ func LoadWithRoot(path string, rootDir string) (*Config, error) {
 	if rootDir != "" {
 		searchDir = rootDir
 	}
+	return load(searchDir, searchDir)
+}
+
+// LoadNearest loads the .lci.kdl nearest to dir: the one in dir or else in the closest
+// parent directory below the home directory, whose .lci.kdl is the global base config
+func LoadNearest(dir string) (*Config, error) {
+	configDir := findConfigDir(dir)
+	if configDir == "" {
+		configDir = dir
+	}
+	return load(dir, configDir)
+}
 
+// findConfigDir walks up from dir to the first directory holding a .lci.kdl, returning ""
+// when it reaches the home directory or the filesystem root without finding one
+func findConfigDir(dir string) string {
+	absDir, err := filepath.Abs(dir)
+	if err != nil {
+		return ""
+	}
+	homeDir, _ := os.UserHomeDir()
+	for {
+		if _, err := os.Stat(filepath.Join(absDir, ".lci.kdl")); err == nil {
+			return absDir
+		}
+		parent := filepath.Dir(absDir)
+		if parent == absDir || parent == homeDir {
+			return ""
+		}
+		absDir = parent
+	}
+}
+
+// load merges the global ~/.lci.kdl with the project config in configDir, falling back to
+// built-in defaults rooted at searchDir
+func load(searchDir, configDir string) (*Config, error) {
 	// Step 1: Load global base config from ~/.lci.kdl (if exists)
 	homeDir, err := os.UserHomeDir()
 	var baseConfig *Config
@@ -180,7 +244,7 @@ func LoadWithRoot(path string, rootDir string) (*Config, error) {
 
 	// Step 2: Load project-specific config from project directory
 	var projectConfig *Config
-	if kdlCfg, err := LoadKDL(searchDir); err == nil && kdlCfg != nil {
+	if kdlCfg, err := loadKDL(configDir, searchDir); err == nil && kdlCfg != nil {
 		projectConfig = kdlCfg
 	} else if err != nil {
 		return nil, err
@@ -219,6 +283,7 @@ func LoadWithRoot(path string, rootDir string) (*Config, error) {
 			RespectGitignore: true,     // Process .gitignore files by default
 			WatchMode:        true,     // Enable file watching by default
 			WatchDebounceMs:  300,      // 300ms debounce for file changes
+			MmapThreshold:    types.DefaultMmapThreshold,
 		},
 		Performance: Performance{
 			MaxMemoryMB:         500,
@@ -486,6 +551,28 @@ func mergeConfigs(base, project *Config) *Config {
 		merged.Include = base.Include
 	}
 
+	// Merge language overrides: project entries win for the same extension
+	if len(base.Index.Languages) > 0 {
+		merged.Index.Languages = make(map[string]string, len(base.Index.Languages)+len(project.Index.Languages))
+		for ext, language := range base.Index.Languages {
+			merged.Index.Languages[ext] = language
+		}
+		for ext, language := range project.Index.Languages {
+			merged.Index.Languages[ext] = language
+		}
+	}
+
+	// Merge aliases: project alternatives are added to the base ones for the same word
+	if len(base.Search.Aliases) > 0 {
+		merged.Search.Aliases = make(map[string][]string, len(base.Search.Aliases)+len(project.Search.Aliases))
+		for word, spellings := range base.Search.Aliases {
+			merged.Search.Aliases[word] = spellings
+		}
+		for word, spellings := range project.Search.Aliases {
+			merged.Search.Aliases[word] = append(append([]string(nil), merged.Search.Aliases[word]...), spellings...)
+		}
+	}
+
 	// Use project settings for everything else (already copied above)
 	// This allows project to override performance settings, search settings, etc.
 
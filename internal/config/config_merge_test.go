@@ -313,3 +313,55 @@ func TestMergeConfigs_PreservesBaseExclusionsInTests(t *testing.T) {
 	assert.Contains(t, merged.Exclude, "**/testdata/**",
 		"Base exclusion for testdata must be preserved")
 }
+
+func TestLoadNearest_WalksUpToProjectConfig(t *testing.T) {
+	t.Setenv("HOME", "/nonexistent")
+	tmpProject := t.TempDir()
+	subDir := filepath.Join(tmpProject, "src", "pkg")
+	require.NoError(t, os.MkdirAll(subDir, 0755))
+	require.NoError(t, os.WriteFile(filepath.Join(tmpProject, ".lci.kdl"), []byte("defaults {\n    context 3\n}\n"), 0644))
+
+	cfg, err := LoadNearest(subDir)
+	require.NoError(t, err)
+	assert.Equal(t, 3, cfg.Defaults.Context)
+	assert.Equal(t, filepath.Join(tmpProject, ".lci.kdl"), cfg.File)
+
+	// A config error names the file instead of being ignored
+	require.NoError(t, os.WriteFile(filepath.Join(tmpProject, ".lci.kdl"), []byte("defaults {\n    contxt 3\n}\n"), 0644))
+	_, err = LoadNearest(subDir)
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), filepath.Join(tmpProject, ".lci.kdl"))
+}
+
+func TestLoadNearest_KeepsSearchRoot(t *testing.T) {
+	t.Setenv("HOME", "/nonexistent")
+	tmpProject := t.TempDir()
+	subDir := filepath.Join(tmpProject, "src")
+	require.NoError(t, os.MkdirAll(subDir, 0755))
+
+	// A parent's config applies to the search without moving its root to the parent
+	require.NoError(t, os.WriteFile(filepath.Join(tmpProject, ".lci.kdl"), []byte("project {\n    name \"p\"\n}\n"), 0644))
+	cfg, err := LoadNearest(subDir)
+	require.NoError(t, err)
+	assert.Equal(t, "p", cfg.Project.Name)
+	assert.Equal(t, subDir, cfg.Project.Root)
+
+	// An explicit root is resolved against the directory holding the config
+	require.NoError(t, os.WriteFile(filepath.Join(tmpProject, ".lci.kdl"), []byte("project {\n    root \".\"\n}\n"), 0644))
+	cfg, err = LoadNearest(subDir)
+	require.NoError(t, err)
+	assert.Equal(t, tmpProject, cfg.Project.Root)
+}
+
+func TestLoadNearest_StopsAtHome(t *testing.T) {
+	tmpHome := t.TempDir()
+	t.Setenv("HOME", tmpHome)
+	require.NoError(t, os.WriteFile(filepath.Join(tmpHome, ".lci.kdl"), []byte("defaults {\n    context 5\n}\n"), 0644))
+	projectDir := filepath.Join(tmpHome, "project")
+	require.NoError(t, os.MkdirAll(projectDir, 0755))
+
+	cfg, err := LoadNearest(projectDir)
+	require.NoError(t, err)
+	assert.Equal(t, 5, cfg.Defaults.Context, "the global config still applies")
+	assert.Equal(t, projectDir, cfg.Project.Root, "the global config is not taken as the project config")
+}
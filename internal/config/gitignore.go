@@ -23,6 +23,10 @@ type GitignorePattern struct {
 	Directory bool
 	Absolute  bool
 
+	// base is the slash-separated directory (relative to the project root) holding the
+	// ignore file this pattern came from; empty for root-level patterns
+	base string
+
 	// Performance optimization fields
 	patternType PatternType
 	compiled    *regexp.Regexp
@@ -42,6 +46,10 @@ const (
 	PatternComplex
 )
 
+// IgnoreFileNames lists the ignore files honoured in every directory, lowest precedence
+// first. .ignore and .lciignore use the same syntax as .gitignore.
+var IgnoreFileNames = []string{".gitignore", ".ignore", ".lciignore"}
+
 // NewGitignoreParser creates a new gitignore parser
 func NewGitignoreParser() *GitignoreParser {
 	return &GitignoreParser{
@@ -60,11 +68,65 @@ func (gp *GitignoreParser) LoadGitignore(rootPath string) error {
 	}
 	defer file.Close()
 
-	return gp.scanAndParsePatterns(file)
+	return gp.scanAndParsePatterns(file, "")
+}
+
+// LoadIgnoreFiles walks rootPath and loads every .gitignore, .ignore and .lciignore it
+// finds. As in git, patterns from a nested ignore file only apply inside the directory
+// that contains it, and deeper files take precedence over shallower ones. Directories
+// already ignored by an outer file are not descended into.
+func (gp *GitignoreParser) LoadIgnoreFiles(rootPath string) error {
+	return filepath.WalkDir(rootPath, func(path string, d os.DirEntry, walkErr error) error {
+		if walkErr != nil {
+			// Unreadable directories are skipped, same as during indexing
+			if d != nil && d.IsDir() && path != rootPath {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if !d.IsDir() {
+			return nil
+		}
+
+		base := ""
+		if path != rootPath {
+			if d.Name() == ".git" {
+				return filepath.SkipDir
+			}
+			rel, err := filepath.Rel(rootPath, path)
+			if err != nil {
+				return filepath.SkipDir
+			}
+			base = filepath.ToSlash(rel)
+			if gp.ShouldIgnore(base, true) {
+				return filepath.SkipDir
+			}
+		}
+
+		return gp.loadIgnoreFilesInDir(path, base)
+	})
 }
 
-// scanAndParsePatterns scans a file and parses each line as a pattern
-func (gp *GitignoreParser) scanAndParsePatterns(file *os.File) error {
+// loadIgnoreFilesInDir loads the ignore files of a single directory, scoping their
+// patterns to base
+func (gp *GitignoreParser) loadIgnoreFilesInDir(dir, base string) error {
+	for _, name := range IgnoreFileNames {
+		file, err := os.Open(filepath.Join(dir, name))
+		if err != nil {
+			// Missing ignore files are the common case
+			continue
+		}
+		err = gp.scanAndParsePatterns(file, base)
+		file.Close()
+		if err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// scanAndParsePatterns scans a file and parses each line as a pattern scoped to base
+func (gp *GitignoreParser) scanAndParsePatterns(file *os.File, base string) error {
 	scanner := bufio.NewScanner(file)
 	for scanner.Scan() {
 		line := strings.TrimSpace(scanner.Text())
@@ -74,6 +136,7 @@ func (gp *GitignoreParser) scanAndParsePatterns(file *os.File) error {
 		}
 
 		pattern := gp.parsePattern(line)
+		pattern.base = base
 		gp.patterns = append(gp.patterns, pattern)
 	}
 
@@ -236,7 +299,16 @@ func (gp *GitignoreParser) ShouldIgnore(path string, isDir bool) bool {
 	ignored := false
 
 	for _, pattern := range gp.patterns {
-		if gp.matchesPattern(pattern, path, isDir) {
+		relPath := path
+		if pattern.base != "" {
+			// Nested ignore files only apply to their own subtree
+			if !strings.HasPrefix(path, pattern.base+"/") {
+				continue
+			}
+			relPath = path[len(pattern.base)+1:]
+		}
+
+		if gp.matchesPattern(pattern, relPath, isDir) {
 			if pattern.Negate {
 				ignored = false
 			} else {
@@ -366,6 +438,15 @@ func (gp *GitignoreParser) GetExclusionPatterns() []string {
 // convertToLCIPattern converts a gitignore pattern to an LCI exclusion pattern
 func (gp *GitignoreParser) convertToLCIPattern(pattern GitignorePattern) string {
 	p := pattern.Pattern
+	if pattern.base != "" {
+		// Nested patterns only match below their own directory
+		if pattern.Absolute {
+			p = pattern.base + "/" + p
+		} else {
+			p = pattern.base + "/**/" + p
+		}
+		pattern.Absolute = true
+	}
 
 	// Handle directory patterns
 	if pattern.Directory {
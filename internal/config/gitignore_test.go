@@ -2,11 +2,14 @@ package config
 
 import (
 	"fmt"
+	"os"
+	"path/filepath"
 	"strings"
 	"testing"
 	"time"
 
 	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
 )
 
 // TestGitignoreParser_BasicPatterns tests fundamental gitignore pattern matching
@@ -444,6 +447,54 @@ func TestGitignoreParser_NegationPriority(t *testing.T) {
 	}
 }
 
+// TestGitignoreParser_LoadIgnoreFiles tests nested .gitignore, .ignore and .lciignore files
+func TestGitignoreParser_LoadIgnoreFiles(t *testing.T) {
+	root := t.TempDir()
+	writeIgnore := func(rel, content string) {
+		path := filepath.Join(root, filepath.FromSlash(rel))
+		require.NoError(t, os.MkdirAll(filepath.Dir(path), 0755))
+		require.NoError(t, os.WriteFile(path, []byte(content), 0644))
+	}
+
+	writeIgnore(".gitignore", "*.log\nbuild/\n")
+	writeIgnore(".ignore", "*.tmp\n")
+	writeIgnore(".lciignore", "generated/\n!keep.tmp\n")
+	writeIgnore("pkg/.gitignore", "fixtures/\n*.golden\n!important.log\n")
+	writeIgnore("pkg/sub/.lciignore", "/local.go\n")
+	// An ignore file inside an ignored directory must not be read
+	writeIgnore("build/.gitignore", "!*.log\n")
+
+	parser := NewGitignoreParser()
+	require.NoError(t, parser.LoadIgnoreFiles(root))
+
+	tests := []struct {
+		path     string
+		isDir    bool
+		expected bool
+	}{
+		{"app.log", false, true},                  // root .gitignore
+		{"scratch.tmp", false, true},              // root .ignore
+		{"keep.tmp", false, false},                // .lciignore overrides .ignore
+		{"generated/api.go", false, true},         // root .lciignore
+		{"build/out.log", false, true},            // nested file under ignored dir is not loaded
+		{"pkg/fixtures/data.json", false, true},   // nested directory pattern
+		{"pkg/data.golden", false, true},          // nested file pattern
+		{"pkg/important.log", false, false},       // nested negation overrides root
+		{"other/important.log", false, true},      // nested negation only applies to its subtree
+		{"other/data.golden", false, false},       // nested pattern only applies to its subtree
+		{"fixtures/data.json", false, false},      // nested directory pattern doesn't leak upwards
+		{"pkg/sub/local.go", false, true},         // anchored to the nested directory
+		{"pkg/sub/deeper/local.go", false, false}, // anchored patterns don't float
+		{"pkg/sub/main.go", false, false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.path, func(t *testing.T) {
+			assert.Equal(t, tt.expected, parser.ShouldIgnore(tt.path, tt.isDir), "Path: %s", tt.path)
+		})
+	}
+}
+
 // BenchmarkGitignoreParsing benchmarks gitignore parsing performance
 func BenchmarkGitignoreParsing(b *testing.B) {
 	content := `
@@ -10,11 +10,21 @@ import (
 
 	kdl "github.com/sblinch/kdl-go"
 	"github.com/sblinch/kdl-go/document"
+
+	"github.com/standardbeagle/lci/internal/encoding"
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 // LoadKDL attempts to load configuration from .lci.kdl file
 func LoadKDL(projectRoot string) (*Config, error) {
-	kdlPath := filepath.Join(projectRoot, ".lci.kdl")
+	return loadKDL(projectRoot, projectRoot)
+}
+
+// loadKDL loads the .lci.kdl in configDir for a search of searchDir. An explicit
+// project root is resolved against configDir; without one the root is searchDir, so a
+// config found in a parent directory does not widen the search to that parent.
+func loadKDL(configDir, searchDir string) (*Config, error) {
+	kdlPath := filepath.Join(configDir, ".lci.kdl")
 
 	// Check if .lci.kdl exists
 	if _, err := os.Stat(kdlPath); os.IsNotExist(err) {
@@ -28,28 +38,32 @@ func LoadKDL(projectRoot string) (*Config, error) {
 
 	cfg, err := parseKDL(string(content))
 	if err != nil {
-		return nil, err
+		return nil, fmt.Errorf("%s: %w", kdlPath, err)
 	}
+	if absPath, err := filepath.Abs(kdlPath); err == nil {
+		kdlPath = absPath
+	}
+	cfg.File = kdlPath
 
 	// Ensure root path is absolute for consistent path handling
 	// Resolve relative paths relative to the directory containing the .lci.kdl file
-	if cfg != nil && cfg.Project.Root != "" {
+	if cfg.Project.Root != "" {
 		var absRoot string
 		if filepath.IsAbs(cfg.Project.Root) {
 			absRoot = cfg.Project.Root
 		} else {
-			// Resolve relative to the projectRoot directory (where .lci.kdl is)
-			absRoot = filepath.Join(projectRoot, cfg.Project.Root)
+			// Resolve relative to the configDir directory (where .lci.kdl is)
+			absRoot = filepath.Join(configDir, cfg.Project.Root)
 		}
 		// Clean the path to resolve . and ..
 		cfg.Project.Root = filepath.Clean(absRoot)
-	} else if cfg != nil {
-		// If no root specified in KDL, use the projectRoot parameter
-		absRoot, err := filepath.Abs(projectRoot)
+	} else {
+		// If no root specified in KDL, use the directory being searched
+		absRoot, err := filepath.Abs(searchDir)
 		if err == nil {
 			cfg.Project.Root = absRoot
 		} else {
-			cfg.Project.Root = projectRoot
+			cfg.Project.Root = searchDir
 		}
 	}
 
@@ -58,15 +72,9 @@ func LoadKDL(projectRoot string) (*Config, error) {
 
 // Simple KDL parser for LCI configuration
 func parseKDL(content string) (*Config, error) {
-	// Default to absolute current working directory
-	defaultRoot, _ := os.Getwd()
-	if defaultRoot == "" {
-		defaultRoot = "."
-	}
-
+	// Project.Root stays empty unless the config sets it; loadKDL fills it in
 	cfg := &Config{
 		Version: 1,
-		Project: Project{Root: defaultRoot},
 		Index: Index{
 			MaxFileSize:      10 * 1024 * 1024,
 			MaxTotalSizeMB:   500,
@@ -74,6 +82,7 @@ func parseKDL(content string) (*Config, error) {
 			FollowSymlinks:   false,
 			SmartSizeControl: true,
 			PriorityMode:     "recent",
+			MmapThreshold:    types.DefaultMmapThreshold,
 		},
 		Performance: Performance{
 			MaxMemoryMB:   500,
@@ -110,8 +119,14 @@ func parseKDL(content string) (*Config, error) {
 		switch nodeName(n) {
 		case "project":
 			for _, cn := range n.Children { // project { root "." name "foo" }
-				assignSimpleString(cn, "root", func(v string) { cfg.Project.Root = v })
-				assignSimpleString(cn, "name", func(v string) { cfg.Project.Name = v })
+				switch nodeName(cn) {
+				case "root":
+					assignSimpleString(cn, "root", func(v string) { cfg.Project.Root = v })
+				case "name":
+					assignSimpleString(cn, "name", func(v string) { cfg.Project.Name = v })
+				default:
+					return nil, unknownKeyError(nodeName(cn), "project")
+				}
 			}
 		case "index":
 			for _, cn := range n.Children {
@@ -122,10 +137,19 @@ func parseKDL(content string) (*Config, error) {
 						cfg.Index.MaxFileSize = int64(v)
 					}
 					if s, ok := firstStringArg(cn); ok {
-						if sz, err := parseSize(s); err == nil {
+						if sz, err := ParseSize(s); err == nil {
 							cfg.Index.MaxFileSize = sz
 						}
 					}
+				case "mmap_threshold":
+					if v, ok := firstIntArg(cn); ok {
+						cfg.Index.MmapThreshold = int64(v)
+					}
+					if s, ok := firstStringArg(cn); ok {
+						if sz, err := ParseSize(s); err == nil {
+							cfg.Index.MmapThreshold = sz
+						}
+					}
 				case "max_total_size_mb":
 					if v, ok := firstIntArg(cn); ok {
 						cfg.Index.MaxTotalSizeMB = int64(v)
@@ -134,6 +158,18 @@ func parseKDL(content string) (*Config, error) {
 					if v, ok := firstIntArg(cn); ok {
 						cfg.Index.MaxFileCount = v
 					}
+				case "max_depth":
+					if v, ok := firstIntArg(cn); ok && v >= 0 {
+						cfg.Index.MaxDepth = &v
+					}
+				case "sample":
+					if v, ok := firstFloatArg(cn); ok && v > 0 && v <= 1 {
+						cfg.Index.Sample = v
+					}
+				case "sample_seed":
+					if v, ok := firstIntArg(cn); ok {
+						cfg.Index.SampleSeed = int64(v)
+					}
 				case "follow_symlinks":
 					if b, ok := firstBoolArg(cn); ok {
 						cfg.Index.FollowSymlinks = b
@@ -150,6 +186,20 @@ func parseKDL(content string) (*Config, error) {
 					if b, ok := firstBoolArg(cn); ok {
 						cfg.Index.RespectGitignore = b
 					}
+				case "treat_as_text":
+					if b, ok := firstBoolArg(cn); ok {
+						cfg.Index.TreatAsText = b
+					}
+				case "encoding":
+					if s, ok := firstStringArg(cn); ok {
+						if enc, err := encoding.ParseTextEncoding(s); err == nil {
+							cfg.Index.Encoding = enc
+						}
+					}
+				case "tokenize":
+					if s, ok := firstStringArg(cn); ok {
+						cfg.Index.Tokenize = s
+					}
 				case "watch_mode":
 					if b, ok := firstBoolArg(cn); ok {
 						cfg.Index.WatchMode = b
@@ -158,8 +208,24 @@ func parseKDL(content string) (*Config, error) {
 					if v, ok := firstIntArg(cn); ok {
 						cfg.Index.WatchDebounceMs = v
 					}
+				case "languages":
+					// languages { ".tpl" "go"; inc "cpp" } parses each extension as that language
+					for _, ln := range cn.Children {
+						language, ok := firstStringArg(ln)
+						if !ok || ln.Name == nil {
+							continue
+						}
+						if ext, ok := ln.Name.Value.(string); ok {
+							if cfg.Index.Languages == nil {
+								cfg.Index.Languages = make(map[string]string)
+							}
+							cfg.Index.Languages[ext] = language
+						}
+					}
 				case "cache_dir":
 					// cache_dir removed - persistence no longer supported
+				default:
+					return nil, unknownKeyError(name, "index")
 				}
 			}
 		case "performance":
@@ -181,6 +247,12 @@ func parseKDL(content string) (*Config, error) {
 					if v, ok := firstIntArg(cn); ok {
 						cfg.Performance.StartupDelayMs = v
 					}
+				case "reindex_in_place":
+					if b, ok := firstBoolArg(cn); ok {
+						cfg.Performance.ReindexInPlace = b
+					}
+				default:
+					return nil, unknownKeyError(nodeName(cn), "performance")
 				}
 			}
 		case "search":
@@ -210,6 +282,20 @@ func parseKDL(content string) (*Config, error) {
 					if b, ok := firstBoolArg(cn); ok {
 						cfg.Search.IncludeLeadingComments = b
 					}
+				case "aliases":
+					// aliases { cfg "config" "settings"; usr "user" } adds --expand spellings per word
+					for _, an := range cn.Children {
+						word := nodeName(an)
+						spellings := collectStringArgs(an)
+						if word == "" || len(spellings) == 0 {
+							continue
+						}
+						if cfg.Search.Aliases == nil {
+							cfg.Search.Aliases = make(map[string][]string)
+						}
+						word = strings.ToLower(word)
+						cfg.Search.Aliases[word] = append(cfg.Search.Aliases[word], spellings...)
+					}
 				case "ranking":
 					// Parse ranking block for file type and symbol preferences
 					for _, rn := range cn.Children {
@@ -238,10 +324,18 @@ func parseKDL(content string) (*Config, error) {
 							if v, ok := firstFloatArg(rn); ok {
 								cfg.Search.Ranking.NonSymbolPenalty = v
 							}
+						default:
+							return nil, unknownKeyError(nodeName(rn), "search ranking")
 						}
 					}
+				default:
+					return nil, unknownKeyError(nodeName(cn), "search")
 				}
 			}
+		case "defaults":
+			if err := parseDefaultsSection(cfg, n); err != nil {
+				return nil, err
+			}
 		case "include":
 			cfg.Include = append(cfg.Include, collectStringArgs(n)...)
 		case "exclude":
@@ -252,6 +346,8 @@ func parseKDL(content string) (*Config, error) {
 			if s, ok := firstStringArg(n); ok {
 				cfg.PropagationConfigDir = s
 			}
+		default:
+			return nil, fmt.Errorf("unknown section %q (expected project, index, performance, search, defaults, include, exclude or propagation_config_dir)", nodeName(n))
 		}
 	}
 
@@ -345,6 +441,11 @@ func collectStringArgs(n *document.Node) []string {
 
 	return out
 }
+// unknownKeyError reports a key the parser does not know in block, so a typo or a key
+// placed in the wrong block fails the load instead of being silently dropped
+func unknownKeyError(key, block string) error {
+	return fmt.Errorf("unknown key %q in %s block", key, block)
+}
 func assignSimpleString(n *document.Node, target string, set func(string)) {
 	if nodeName(n) == target {
 		if s, ok := firstStringArg(n); ok {
@@ -353,12 +454,50 @@ func assignSimpleString(n *document.Node, target string, set func(string)) {
 	}
 }
 
+// parseDefaultsSection reads the `defaults` block of `lci search` flag defaults, plus
+// threads (--threads). Like every section it rejects unknown keys; unlike the others it
+// also rejects mistyped values, so a typo does not silently leave a default unapplied.
+func parseDefaultsSection(cfg *Config, n *document.Node) error {
+	for _, cn := range n.Children {
+		key := nodeName(cn)
+		ok := true
+		switch key {
+		case "ignore_case":
+			cfg.Defaults.IgnoreCase, ok = firstBoolArg(cn)
+		case "context":
+			cfg.Defaults.Context, ok = firstIntArg(cn)
+			ok = ok && cfg.Defaults.Context >= 0
+		case "format":
+			cfg.Defaults.Format, ok = firstStringArg(cn)
+		case "threads":
+			cfg.Performance.ParallelFileWorkers, ok = firstIntArg(cn)
+			ok = ok && cfg.Performance.ParallelFileWorkers >= 0
+		case "include":
+			cfg.Defaults.Include = collectStringArgs(cn)
+			ok = len(cfg.Defaults.Include) > 0
+		case "exclude":
+			cfg.Defaults.Exclude = collectStringArgs(cn)
+			ok = len(cfg.Defaults.Exclude) > 0
+		default:
+			return fmt.Errorf("unknown key %q in defaults block (expected ignore_case, context, format, threads, include or exclude)", key)
+		}
+		if !ok {
+			return fmt.Errorf("invalid value for %q in defaults block", key)
+		}
+	}
+	return nil
+}
+
 func parseIndexSection(cfg *Config, key, value string) error {
 	switch key {
 	case "max_file_size":
-		if size, err := parseSize(value); err == nil {
+		if size, err := ParseSize(value); err == nil {
 			cfg.Index.MaxFileSize = size
 		}
+	case "mmap_threshold":
+		if size, err := ParseSize(value); err == nil {
+			cfg.Index.MmapThreshold = size
+		}
 	case "max_total_size_mb":
 		if mb, err := strconv.ParseInt(value, 10, 64); err == nil {
 			cfg.Index.MaxTotalSizeMB = mb
@@ -367,6 +506,18 @@ func parseIndexSection(cfg *Config, key, value string) error {
 		if count, err := strconv.Atoi(value); err == nil {
 			cfg.Index.MaxFileCount = count
 		}
+	case "max_depth":
+		if depth, err := strconv.Atoi(value); err == nil && depth >= 0 {
+			cfg.Index.MaxDepth = &depth
+		}
+	case "sample":
+		if p, err := strconv.ParseFloat(value, 64); err == nil && p > 0 && p <= 1 {
+			cfg.Index.Sample = p
+		}
+	case "sample_seed":
+		if seed, err := strconv.ParseInt(value, 10, 64); err == nil {
+			cfg.Index.SampleSeed = seed
+		}
 	case "smart_size_control":
 		cfg.Index.SmartSizeControl = parseBool(value)
 	case "priority_mode":
@@ -375,14 +526,22 @@ func parseIndexSection(cfg *Config, key, value string) error {
 		cfg.Index.FollowSymlinks = parseBool(value)
 	case "respect_gitignore":
 		cfg.Index.RespectGitignore = parseBool(value)
+	case "treat_as_text":
+		cfg.Index.TreatAsText = parseBool(value)
+	case "encoding":
+		if enc, err := encoding.ParseTextEncoding(value); err == nil {
+			cfg.Index.Encoding = enc
+		}
+	case "tokenize":
+		cfg.Index.Tokenize = value
 	}
 	return nil
 }
 
 // Legacy helper removals: index/performance/search/project parsing now performed via KDL AST traversal
 
-// parseSize handles size strings like "10MB", "500KB", "1GB"
-func parseSize(s string) (int64, error) {
+// ParseSize handles size strings like "10MB", "500KB", "1GB"; a bare number is bytes
+func ParseSize(s string) (int64, error) {
 	s = strings.ToUpper(strings.TrimSpace(s))
 
 	var multiplier int64 = 1
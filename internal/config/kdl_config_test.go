@@ -116,6 +116,7 @@ index {
 performance {
     max_memory_mb 256
     max_goroutines 8
+    reindex_in_place true
 }
 
 search {
@@ -139,6 +140,7 @@ exclude "**/.git/**" "**/node_modules/**"
 	assert.Equal(t, 5000, cfg.Index.MaxFileCount)
 	assert.Equal(t, 256, cfg.Performance.MaxMemoryMB)
 	assert.Equal(t, 8, cfg.Performance.MaxGoroutines)
+	assert.True(t, cfg.Performance.ReindexInPlace)
 	assert.Equal(t, 50, cfg.Search.MaxResults)
 	assert.True(t, cfg.Search.EnableFuzzy)
 	assert.Equal(t, 60.0, cfg.Search.Ranking.CodeFileBoost)
@@ -146,3 +148,125 @@ exclude "**/.git/**" "**/node_modules/**"
 	assert.Contains(t, cfg.Exclude, "**/.git/**")
 	assert.Contains(t, cfg.Exclude, "**/node_modules/**")
 }
+
+func TestParseKDL_LanguageOverrides(t *testing.T) {
+	kdlContent := `
+index {
+    languages {
+        ".tpl" "go"
+        inc "cpp"
+    }
+}
+`
+	cfg, err := parseKDL(kdlContent)
+	require.NoError(t, err)
+	require.NotNil(t, cfg)
+
+	assert.Equal(t, map[string]string{".tpl": "go", "inc": "cpp"}, cfg.Index.Languages)
+}
+
+func TestParseKDL_MaxDepth(t *testing.T) {
+	cfg, err := parseKDL("index {\n    max_depth 0\n}\n")
+	require.NoError(t, err)
+	require.NotNil(t, cfg.Index.MaxDepth, "depth 0 is a limit, not unset")
+	assert.Equal(t, 0, *cfg.Index.MaxDepth)
+
+	cfg, err = parseKDL("")
+	require.NoError(t, err)
+	assert.Nil(t, cfg.Index.MaxDepth, "no limit by default")
+}
+
+func TestParseKDL_Sample(t *testing.T) {
+	cfg, err := parseKDL("index {\n    sample 0.25\n    sample_seed 42\n}\n")
+	require.NoError(t, err)
+	assert.Equal(t, 0.25, cfg.Index.Sample)
+	assert.Equal(t, int64(42), cfg.Index.SampleSeed)
+
+	cfg, err = parseKDL("index {\n    sample 1.5\n}\n")
+	require.NoError(t, err)
+	assert.Zero(t, cfg.Index.Sample, "a probability above 1 is ignored")
+}
+
+func TestParseKDL_Encoding(t *testing.T) {
+	cfg, err := parseKDL("index {\n    encoding \"CP1252\"\n}\n")
+	require.NoError(t, err)
+	assert.Equal(t, "windows-1252", cfg.Index.Encoding)
+
+	cfg, err = parseKDL("index {\n    encoding \"ebcdic\"\n}\n")
+	require.NoError(t, err)
+	assert.Empty(t, cfg.Index.Encoding, "an unknown encoding is ignored")
+}
+
+func TestParseKDL_SearchAliases(t *testing.T) {
+	kdlContent := `
+search {
+    aliases {
+        cfg "settings"
+        Tx "transaction" "txn"
+    }
+}
+`
+	cfg, err := parseKDL(kdlContent)
+	require.NoError(t, err)
+	require.NotNil(t, cfg)
+
+	assert.Equal(t, map[string][]string{"cfg": {"settings"}, "tx": {"transaction", "txn"}}, cfg.Search.Aliases)
+}
+
+func TestParseKDL_SearchDefaults(t *testing.T) {
+	kdlContent := `
+defaults {
+    ignore_case true
+    context 2
+    format "json"
+    threads 4
+    include "src/**" "lib/**"
+    exclude "**/*_test.go"
+}
+`
+	cfg, err := parseKDL(kdlContent)
+	require.NoError(t, err)
+	require.NotNil(t, cfg)
+
+	assert.Equal(t, Defaults{
+		IgnoreCase: true,
+		Context:    2,
+		Format:     "json",
+		Include:    []string{"src/**", "lib/**"},
+		Exclude:    []string{"**/*_test.go"},
+	}, cfg.Defaults)
+	assert.Equal(t, 4, cfg.Performance.ParallelFileWorkers)
+}
+
+func TestParseKDL_SearchDefaultsErrors(t *testing.T) {
+	_, err := parseKDL("defaults {\n    ignore_cas true\n}\n")
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), `unknown key "ignore_cas" in defaults block`)
+
+	_, err = parseKDL("defaults {\n    ranker \"proximity\"\n}\n")
+	require.Error(t, err, "no search flag picks a ranker, so a ranker default would do nothing")
+	assert.Contains(t, err.Error(), `unknown key "ranker" in defaults block`)
+
+	_, err = parseKDL("defaults {\n    context \"two\"\n}\n")
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), `invalid value for "context"`)
+}
+
+func TestParseKDL_UnknownKeys(t *testing.T) {
+	tests := []struct {
+		content string
+		want    string
+	}{
+		{"project {\n    nme \"x\"\n}\n", `unknown key "nme" in project block`},
+		{"index {\n    max_files 10\n}\n", `unknown key "max_files" in index block`},
+		{"performance {\n    max_memory 10\n}\n", `unknown key "max_memory" in performance block`},
+		{"search {\n    max-results 10\n}\n", `unknown key "max-results" in search block`},
+		{"search {\n    ranking {\n        boost 1.0\n    }\n}\n", `unknown key "boost" in search ranking block`},
+		{"serch {\n    max_results 10\n}\n", `unknown section "serch"`},
+	}
+	for _, tt := range tests {
+		_, err := parseKDL(tt.content)
+		require.Error(t, err, tt.content)
+		assert.Contains(t, err.Error(), tt.want)
+	}
+}
@@ -70,6 +70,12 @@ func (v *Validator) validateIndexConfig(index *Index) error {
 		return fmt.Errorf("MaxFileSize should not exceed 100MB, got %d", index.MaxFileSize)
 	}
 
+	switch index.Tokenize {
+	case "", "raw", "identifier":
+	default:
+		return fmt.Errorf("Tokenize must be raw or identifier, got %q", index.Tokenize)
+	}
+
 	return nil
 }
 
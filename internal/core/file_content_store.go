@@ -46,6 +46,7 @@ const (
 	UpdateTypeInvalidate
 	UpdateTypeInvalidateByID
 	UpdateTypeClear
+	UpdateTypeReserve
 )
 
 // ContentUpdate represents a file content update request
@@ -202,7 +203,21 @@ func (fcs *FileContentStore) handleUpdate(update *ContentUpdate) {
 		fcs.currentMemory.Store(0)
 		fcs.nextID.Store(0)
 		update.Response <- UpdateResult{Success: true}
+
+	case UpdateTypeReserve:
+		update.Response <- UpdateResult{FileID: fcs.applyReserveUpdate(snapshot, update.Path), Success: true}
+	}
+}
+
+// applyReserveUpdate assigns a FileID to path without loading content. A path that
+// already has an ID keeps it.
+func (fcs *FileContentStore) applyReserveUpdate(snapshot *FileContentSnapshot, path string) types.FileID {
+	if idVal, exists := snapshot.pathToID.Load(path); exists {
+		return idVal.(types.FileID)
 	}
+	fileID := types.FileID(fcs.nextID.Add(1))
+	snapshot.pathToID.Store(path, fileID)
+	return fileID
 }
 
 // applyLoadUpdate adds/updates a file using sync.Map (O(1), no copying)
@@ -233,6 +248,9 @@ func (fcs *FileContentStore) applyLoadUpdate(snapshot *FileContentSnapshot, path
 			oldSize := int64(len(fc.Content) + len(fc.LineOffsets)*4 + 64)
 			newSize := int64(len(content) + len(lineOffsets)*4 + 64)
 			fcs.currentMemory.Add(newSize - oldSize)
+		} else {
+			// Reserved ID, first content for this path
+			fcs.currentMemory.Add(int64(len(content) + len(lineOffsets)*4 + 64))
 		}
 	} else {
 		// New file
@@ -287,6 +305,9 @@ func (fcs *FileContentStore) applyBatchUpdate(snapshot *FileContentSnapshot, fil
 				oldSize := int64(len(fc.Content) + len(fc.LineOffsets)*4 + 64)
 				newSize := int64(len(file.Content) + len(lineOffsets)*4 + 64)
 				totalMemoryDelta += newSize - oldSize
+			} else {
+				// Reserved ID, first content for this path
+				totalMemoryDelta += int64(len(file.Content) + len(lineOffsets)*4 + 64)
 			}
 			fileIDs[i] = id
 		} else {
@@ -497,17 +518,12 @@ func (fcs *FileContentStore) GetLine(fileID types.FileID, lineNum int) (types.St
 	}
 
 	start := fc.LineOffsets[lineNum]
-	var end uint32
-
+	end := uint32(len(fc.Content))
 	if lineNum+1 < len(fc.LineOffsets) {
 		end = fc.LineOffsets[lineNum+1]
-		// Remove newline character
-		if end > start && fc.Content[end-1] == '\n' {
-			end--
-		}
-	} else {
-		end = uint32(len(fc.Content))
 	}
+	// Remove the "\n" or "\r\n", which the last line has too when the file ends with one
+	end = uint32(types.TrimLineEnding(fc.Content, int(start), int(end)))
 
 	length := end - start
 	var hash uint64
@@ -674,6 +690,23 @@ func (fcs *FileContentStore) LoadFile(path string, content []byte) types.FileID
 	return result.FileID
 }
 
+// ReserveFileID assigns path its FileID ahead of LoadFile. Reserving IDs in a fixed
+// order keeps FileIDs deterministic when files are then loaded concurrently.
+func (fcs *FileContentStore) ReserveFileID(path string) types.FileID {
+	if fcs.closed.Load() {
+		return 0
+	}
+
+	update := &ContentUpdate{
+		Type:     UpdateTypeReserve,
+		Path:     path,
+		Response: make(chan UpdateResult, 1),
+	}
+	fcs.updateChan <- update
+	result := <-update.Response
+	return result.FileID
+}
+
 // BatchLoadFiles loads multiple files with a single update
 func (fcs *FileContentStore) BatchLoadFiles(files []struct {
 	Path    string
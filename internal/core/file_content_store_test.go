@@ -613,4 +613,30 @@ func TestFileContentStoreCloseEdgeCases(t *testing.T) {
 			t.Fatal("Operations didn't complete after Close()")
 		}
 	})
-}
\ No newline at end of file
+}
+
+// TestReserveFileID tests that LoadFile reuses an ID reserved ahead of time
+func TestReserveFileID(t *testing.T) {
+	store := NewFileContentStore()
+	defer store.Close()
+
+	idA := store.ReserveFileID("a.go")
+	idB := store.ReserveFileID("b.go")
+	if idA == 0 || idB != idA+1 {
+		t.Fatalf("Expected consecutive IDs, got %d and %d", idA, idB)
+	}
+	if again := store.ReserveFileID("a.go"); again != idA {
+		t.Errorf("Reserving twice changed the ID: %d != %d", again, idA)
+	}
+
+	// Load in the opposite order; the reserved IDs still apply
+	if got := store.LoadFile("b.go", []byte("package b")); got != idB {
+		t.Errorf("LoadFile(b.go) = %d, want %d", got, idB)
+	}
+	if got := store.LoadFile("a.go", []byte("package a")); got != idA {
+		t.Errorf("LoadFile(a.go) = %d, want %d", got, idA)
+	}
+	if store.GetMemoryUsage() <= 0 {
+		t.Error("Expected memory usage to include reserved files once loaded")
+	}
+}
@@ -66,17 +66,12 @@ func (fcs *FileContentStore) GetZeroAllocLines(fileID types.FileID, startLine, c
 		refs := make([]types.ZeroAllocStringRef, 0, endLine-startLine)
 		for i := startLine; i < endLine; i++ {
 			start := lineOffsets[i]
-			var end uint32
-
+			end := uint32(len(content))
 			if i+1 < len(lineOffsets) {
 				end = lineOffsets[i+1]
-				// Remove newline character
-				if end > start && content[end-1] == '\n' {
-					end--
-				}
-			} else {
-				end = uint32(len(content))
 			}
+			// Remove the "\n" or "\r\n", which the last line has too when the file ends with one
+			end = uint32(types.TrimLineEnding(content, int(start), int(end)))
 
 			length := end - start
 			// Include empty lines as well - they're valid context
@@ -1,6 +1,7 @@
 package core
 
 import (
+	"bytes"
 	"errors"
 	"fmt"
 	"io/fs"
@@ -10,6 +11,7 @@ import (
 	"sync"
 	"time"
 
+	"github.com/standardbeagle/lci/internal/encoding"
 	"github.com/standardbeagle/lci/internal/types"
 )
 
@@ -27,11 +29,14 @@ type FileService struct {
 	fileInfo       map[string]FileMetadata
 	fileIDToPath   map[types.FileID]string // Reverse mapping for O(1) FileID -> path lookup
 	directoryCache map[string][]string     // directory -> list of files
+	encodings      map[string]string       // path -> encoding of files decoded to UTF-8
 
 	// Configuration
-	maxFileSizeBytes int64
-	ignoreDotFiles   bool
-	ignorePatterns   []string
+	maxFileSizeBytes   int64
+	mmapThresholdBytes int64
+	ignoreDotFiles     bool
+	ignorePatterns     []string
+	textEncoding       string
 
 	// Optional external file path lookup function (for integration with MasterIndex)
 	externalPathLookup func(types.FileID) string
@@ -94,11 +99,13 @@ func NewFileService() *FileService {
 
 // FileServiceOptions configures the file service
 type FileServiceOptions struct {
-	ContentStore     *FileContentStore
-	FileSystem       FileSystemInterface
-	MaxFileSizeBytes int64
-	IgnoreDotFiles   bool
-	IgnorePatterns   []string
+	ContentStore       *FileContentStore
+	FileSystem         FileSystemInterface
+	MaxFileSizeBytes   int64
+	MmapThresholdBytes int64 // Map files at least this large into memory instead of reading them (0 = never)
+	IgnoreDotFiles     bool
+	IgnorePatterns     []string
+	TextEncoding       string // Decode files from this encoding, or detect it with "auto" ("" = UTF-8 only)
 }
 
 // NewFileServiceWithOptions creates a file service with custom configuration
@@ -119,14 +126,17 @@ func NewFileServiceWithOptions(opts FileServiceOptions) *FileService {
 	}
 
 	return &FileService{
-		contentStore:     contentStore,
-		fileSystem:       fileSystem,
-		fileInfo:         make(map[string]FileMetadata),
-		fileIDToPath:     make(map[types.FileID]string),
-		directoryCache:   make(map[string][]string),
-		maxFileSizeBytes: maxSize,
-		ignoreDotFiles:   opts.IgnoreDotFiles,
-		ignorePatterns:   opts.IgnorePatterns,
+		contentStore:       contentStore,
+		fileSystem:         fileSystem,
+		fileInfo:           make(map[string]FileMetadata),
+		fileIDToPath:       make(map[types.FileID]string),
+		directoryCache:     make(map[string][]string),
+		encodings:          make(map[string]string),
+		maxFileSizeBytes:   maxSize,
+		mmapThresholdBytes: opts.MmapThresholdBytes,
+		ignoreDotFiles:     opts.IgnoreDotFiles,
+		ignorePatterns:     opts.IgnorePatterns,
+		textEncoding:       opts.TextEncoding,
 	}
 }
 
@@ -134,6 +144,13 @@ func NewFileServiceWithOptions(opts FileServiceOptions) *FileService {
 
 // LoadFile loads a file's content and returns its FileID
 func (fs *FileService) LoadFile(path string) (types.FileID, error) {
+	return fs.LoadFileChecked(path, nil)
+}
+
+// LoadFileChecked is LoadFile that first passes the content, decoded as it would be
+// stored, to check. Content check rejects is not stored, and its error is returned
+// unwrapped so callers can tell a skipped file from one that failed to load.
+func (fs *FileService) LoadFileChecked(path string, check func(content []byte) error) (types.FileID, error) {
 	// Check file metadata first
 	metadata, err := fs.getFileMetadata(path)
 	if err != nil {
@@ -155,11 +172,41 @@ func (fs *FileService) LoadFile(path string) (types.FileID, error) {
 			metadata.Size, fs.maxFileSizeBytes, path)
 	}
 
-	// Read file content
-	content, err := fs.fileSystem.ReadFile(path)
+	// Read file content; large files are mapped so check reads them in place
+	mapped := fs.shouldMap(metadata.Size)
+	var content []byte
+	if mapped {
+		content, err = mapFile(path)
+	} else {
+		content, err = fs.fileSystem.ReadFile(path)
+	}
 	if err != nil {
 		return 0, fmt.Errorf("failed to read file %s: %w", path, err)
 	}
+	if decoded, ok := fs.decode(path, content); ok {
+		if mapped {
+			unmapFile(content) // The store keeps the decoded copy
+			mapped = false
+		}
+		content = decoded
+	}
+
+	if check != nil {
+		if err := check(content); err != nil {
+			if mapped {
+				unmapFile(content)
+			}
+			return 0, err
+		}
+	}
+	if mapped {
+		// The store shares content with parsers and results without tracking them, so it
+		// keeps a heap copy: a mapping would fault if the file shrank and change under the
+		// index if the file were rewritten in place
+		heapContent := bytes.Clone(content)
+		unmapFile(content)
+		content = heapContent
+	}
 
 	// Load into content store
 	fileID := fs.contentStore.LoadFile(path, content)
@@ -176,6 +223,22 @@ func (fs *FileService) LoadFile(path string) (types.FileID, error) {
 	return fileID, nil
 }
 
+// shouldMap reports whether a file of size bytes is memory-mapped by LoadFile. Only
+// the real filesystem can be mapped.
+func (fs *FileService) shouldMap(size int64) bool {
+	if fs.mmapThresholdBytes <= 0 || size < fs.mmapThresholdBytes {
+		return false
+	}
+	_, ok := fs.fileSystem.(*RealFileSystem)
+	return ok
+}
+
+// ReserveFileID assigns path its FileID before the file is loaded, so a later
+// LoadFile returns the reserved ID
+func (fs *FileService) ReserveFileID(path string) types.FileID {
+	return fs.contentStore.ReserveFileID(path)
+}
+
 // LoadFileFromMemory loads file content from memory (for testing or streaming)
 func (fs *FileService) LoadFileFromMemory(path string, content []byte) types.FileID {
 	fileID := fs.contentStore.LoadFile(path, content)
@@ -207,7 +270,7 @@ func (fs *FileService) UpdateFileContent(path string, content []byte) (types.Fil
 		}
 
 		// Use disk content to ensure consistency
-		content = diskContent
+		content = fs.DecodeContent(path, diskContent)
 	}
 
 	fileID := fs.contentStore.LoadFile(path, content)
@@ -232,6 +295,45 @@ func (fs *FileService) GetFileContent(fileID types.FileID) ([]byte, bool) {
 	return fs.contentStore.GetContent(fileID)
 }
 
+// DecodeContent converts the content of path to UTF-8 when the configured text
+// encoding resolves it to another encoding, recording that encoding for
+// FileEncoding. Content that is UTF-8, or whose encoding cannot be detected, is
+// returned as is, so binary detection still sees the original bytes.
+func (fs *FileService) DecodeContent(path string, content []byte) []byte {
+	if decoded, ok := fs.decode(path, content); ok {
+		return decoded
+	}
+	return content
+}
+
+// decode is DecodeContent, reporting whether content was decoded into a new slice
+func (fs *FileService) decode(path string, content []byte) ([]byte, bool) {
+	if fs.textEncoding == "" {
+		return nil, false
+	}
+	enc, ok := encoding.ResolveTextEncoding(fs.textEncoding, content)
+	if ok && enc != encoding.UTF8 && len(content) > 0 {
+		if decoded, err := encoding.DecodeText(content, enc); err == nil {
+			fs.mu.Lock()
+			fs.encodings[path] = enc
+			fs.mu.Unlock()
+			return decoded, true
+		}
+	}
+	fs.mu.Lock()
+	delete(fs.encodings, path)
+	fs.mu.Unlock()
+	return nil, false
+}
+
+// FileEncoding returns the encoding path was decoded from, or "" when it was read
+// as UTF-8
+func (fs *FileService) FileEncoding(path string) string {
+	fs.mu.RLock()
+	defer fs.mu.RUnlock()
+	return fs.encodings[path]
+}
+
 // ReadFile reads the content of a file directly from disk
 func (fs *FileService) ReadFile(path string) ([]byte, error) {
 	return fs.fileSystem.ReadFile(path)
@@ -438,6 +540,7 @@ func (fs *FileService) InvalidateFile(path string) {
 
 	fs.mu.Lock()
 	delete(fs.fileInfo, path)
+	delete(fs.encodings, path)
 
 	// Invalidate directory cache for parent directory
 	dir := filepath.Dir(path)
@@ -445,6 +548,31 @@ func (fs *FileService) InvalidateFile(path string) {
 	fs.mu.Unlock()
 }
 
+// RefreshMetadata drops cached existence/size/mtime for a file without touching its
+// loaded content, so the next metadata query re-stats the filesystem.
+// The FileID association is preserved.
+func (fs *FileService) RefreshMetadata(path string) {
+	fs.mu.Lock()
+	defer fs.mu.Unlock()
+
+	if metadata, exists := fs.fileInfo[path]; exists && metadata.FileID != 0 {
+		if info, err := fs.fileSystem.Stat(path); err == nil {
+			fs.fileInfo[path] = FileMetadata{
+				Path:    path,
+				Size:    info.Size(),
+				ModTime: info.ModTime(),
+				IsDir:   info.IsDir(),
+				Exists:  true,
+				FileID:  metadata.FileID,
+			}
+			delete(fs.directoryCache, filepath.Dir(path))
+			return
+		}
+	}
+	delete(fs.fileInfo, path)
+	delete(fs.directoryCache, filepath.Dir(path))
+}
+
 // InvalidateDirectory removes directory from caches
 func (fs *FileService) InvalidateDirectory(dirPath string) {
 	fs.mu.Lock()
@@ -468,6 +596,7 @@ func (fs *FileService) ClearCache() {
 	fs.mu.Lock()
 	fs.fileInfo = make(map[string]FileMetadata)
 	fs.directoryCache = make(map[string][]string)
+	fs.encodings = make(map[string]string)
 	fs.mu.Unlock()
 }
 
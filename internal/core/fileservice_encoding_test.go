@@ -0,0 +1,53 @@
+package core
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+	"unicode/utf16"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// TestFileServiceDecodesText verifies files in other encodings are stored as UTF-8
+// with their original encoding recorded, and that undetectable files are left alone
+func TestFileServiceDecodesText(t *testing.T) {
+	dir := t.TempDir()
+	source := "fn résumé() {}\n"
+	wide := []byte{0xFF, 0xFE}
+	for _, u := range utf16.Encode([]rune(source)) {
+		wide = append(wide, byte(u), byte(u>>8))
+	}
+	binary := []byte{0x7f, 'E', 'L', 'F', 0x00, 0x01, 0x00, 0x00, 0x02}
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "wide.rs"), wide, 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "legacy.rs"), []byte("fn r\xe9sum\xe9() {}\n"), 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "plain.rs"), []byte(source), 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "blob.bin"), binary, 0644))
+
+	load := func(fileService *FileService, name string) ([]byte, string) {
+		path := filepath.Join(dir, name)
+		fileID, err := fileService.LoadFile(path)
+		require.NoError(t, err, name)
+		content, ok := fileService.GetFileContent(fileID)
+		require.True(t, ok, name)
+		return content, fileService.FileEncoding(path)
+	}
+
+	auto := NewFileServiceWithOptions(FileServiceOptions{TextEncoding: "auto"})
+	defer auto.Close()
+	for name, want := range map[string]string{"wide.rs": "utf-16le", "legacy.rs": "windows-1252", "plain.rs": ""} {
+		content, enc := load(auto, name)
+		assert.Equal(t, source, string(content), name)
+		assert.Equal(t, want, enc, name)
+	}
+	content, enc := load(auto, "blob.bin")
+	assert.Equal(t, binary, content, "undetectable content stays as is, to be skipped as binary")
+	assert.Empty(t, enc)
+
+	off := NewFileServiceWithOptions(FileServiceOptions{})
+	defer off.Close()
+	content, enc = load(off, "wide.rs")
+	assert.Equal(t, wide, content, "no decoding without an encoding setting")
+	assert.Empty(t, enc)
+}
@@ -0,0 +1,89 @@
+package core
+
+import (
+	"bytes"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// TestFileServiceMapsLargeFiles verifies files above the mmap threshold load with the
+// same content as small files read from the heap
+func TestFileServiceMapsLargeFiles(t *testing.T) {
+	dir := t.TempDir()
+	small := []byte("fn small() {}\n")
+	large := bytes.Repeat([]byte("fn large() { let x = \"é\"; }\n"), 200)
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "small.rs"), small, 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "large.rs"), large, 0644))
+
+	contentStore := NewFileContentStore()
+	defer contentStore.Close()
+	fileService := NewFileServiceWithOptions(FileServiceOptions{
+		ContentStore:       contentStore,
+		FileSystem:         &RealFileSystem{},
+		MaxFileSizeBytes:   1024 * 1024,
+		MmapThresholdBytes: 1024,
+	})
+	assert.False(t, fileService.shouldMap(int64(len(small))))
+	assert.True(t, fileService.shouldMap(int64(len(large))))
+
+	for name, want := range map[string][]byte{"small.rs": small, "large.rs": large} {
+		path := filepath.Join(dir, name)
+		fileID, err := fileService.LoadFile(path)
+		require.NoError(t, err, name)
+		got, ok := fileService.GetFileContent(fileID)
+		require.True(t, ok, name)
+		assert.Equal(t, want, got, name)
+
+		// Reloading unchanged content keeps the stored copy
+		reloadedID, err := fileService.LoadFile(path)
+		require.NoError(t, err, name)
+		assert.Equal(t, fileID, reloadedID, name)
+		got, _ = fileService.GetFileContent(fileID)
+		assert.Equal(t, want, got, name)
+	}
+}
+
+// TestFileServiceCopiesMappedContent verifies mapped content is checked in place and
+// stored as a heap copy, so rewriting the file afterwards leaves the index unchanged,
+// and that content the check rejects is not stored
+func TestFileServiceCopiesMappedContent(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "large.rs")
+	large := bytes.Repeat([]byte("fn large() {}\n"), 200)
+	require.NoError(t, os.WriteFile(path, large, 0644))
+
+	contentStore := NewFileContentStore()
+	defer contentStore.Close()
+	fileService := NewFileServiceWithOptions(FileServiceOptions{
+		ContentStore:       contentStore,
+		FileSystem:         &RealFileSystem{},
+		MaxFileSizeBytes:   1024 * 1024,
+		MmapThresholdBytes: 1024,
+	})
+
+	// The checked slice is the mapping, released once the load returns
+	checkedLarge := false
+	fileID, err := fileService.LoadFileChecked(path, func(content []byte) error {
+		checkedLarge = bytes.Equal(content, large)
+		return nil
+	})
+	require.NoError(t, err)
+	assert.True(t, checkedLarge)
+
+	// Truncating the file would fault a mapping that was stored
+	require.NoError(t, os.WriteFile(path, []byte("fn small() {}\n"), 0644))
+	got, ok := fileService.GetFileContent(fileID)
+	require.True(t, ok)
+	assert.Equal(t, large, got)
+
+	rejected := errors.New("rejected")
+	other := filepath.Join(filepath.Dir(path), "other.rs")
+	require.NoError(t, os.WriteFile(other, large, 0644))
+	_, err = fileService.LoadFileChecked(other, func([]byte) error { return rejected })
+	assert.ErrorIs(t, err, rejected)
+	assert.Equal(t, 1, contentStore.GetFileCount(), "rejected content is not stored")
+}
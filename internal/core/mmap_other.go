@@ -0,0 +1,13 @@
+//go:build !unix
+
+package core
+
+import "os"
+
+// mapFile reads the file at path; memory mapping is only used on unix systems
+func mapFile(path string) ([]byte, error) {
+	return os.ReadFile(path)
+}
+
+// unmapFile is a no-op where mapFile reads into the heap
+func unmapFile([]byte) {}
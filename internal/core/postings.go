@@ -1,13 +1,19 @@
 package core
 
-// Context-free unit: Minimal postings index mapping lowercased ASCII tokens (len>=3)
-// to files and the first byte offset per file. Purpose: accelerate common literal
-// word searches (e.g., "function") by avoiding per-file scans at query time.
-// External deps: none; used by GoroutineIndex and search.Engine fast path.
-// Prompt log: Initial implementation to meet <50ms search for 500/1000 files.
+// Context-free unit: Inverted index mapping lowercased ASCII tokens (len>=3) to
+// postings, the files holding each token and every byte offset and token position of
+// it in them.
+// Purpose: answer whole-word searches (grep -w) with a postings lookup and intersection
+// instead of scanning every file.
+// External deps: none; filled by MasterIndex and the indexing pipeline, read by the
+// search.Engine whole-word path.
+// Prompt log: Initial implementation to meet <50ms search for 500/1000 files; offsets
+// of every occurrence added so single-token searches skip the content scan; token
+// positions added for the query language's NEAR operator.
 
 import (
-	"bytes"
+	"encoding/binary"
+	"sort"
 	"strings"
 	"sync"
 	"sync/atomic"
@@ -15,14 +21,35 @@ import (
 	"github.com/standardbeagle/lci/internal/types"
 )
 
-// PostingsIndex stores token -> (fileID -> firstOffset).
+// MinPostingsTokenLength is the shortest token the postings index records
+const MinPostingsTokenLength = 3
+
+// FilePostings maps each token of one file to its encoded occurrences. It is computed
+// in the indexing map phase by TokenizePostings and recorded with PostingsIndex.Add.
+type FilePostings map[string][]byte
+
+// Occurrence is one appearance of a token in a file. Position counts the tokens before
+// it, of any length, so adjacent words are one position apart even when the postings
+// index skips the shorter one.
+type Occurrence struct {
+	Offset   int // Byte offset in the file content
+	Position int // Token position in the file, from 0
+}
+
+// posting is one file's occurrences of a token
+type posting struct {
+	fileID  types.FileID
+	offsets []byte // Ascending byte offsets and token positions as pairs of uvarint deltas, mostly one byte each
+}
+
+// PostingsIndex stores token -> postings sorted by FileID.
 // Offsets are byte positions in original file content.
-// For memory efficiency we only store the first offset per (token,file).
-// Note: This index is ASCII-focused and best-effort; non-ASCII tokens are ignored.
+// Note: This index is ASCII-focused; a token is a run of a-z, A-Z, 0-9 and _, so
+// non-ASCII bytes end tokens the way they end words for grep -w.
 type PostingsIndex struct {
 	mu          sync.RWMutex
-	tokens      map[string]map[types.FileID]int // token -> fileID -> firstOffset
-	reverseKeys map[types.FileID][]string       // fileID -> tokens present (for fast removal)
+	tokens      map[string][]posting      // token -> postings sorted by fileID
+	reverseKeys map[types.FileID][]string // fileID -> tokens present (for fast removal); every indexed file has an entry
 
 	// Flag to indicate bulk indexing mode (lock-free when true)
 	BulkIndexing int32
@@ -30,42 +57,68 @@ type PostingsIndex struct {
 
 func NewPostingsIndex() *PostingsIndex {
 	return &PostingsIndex{
-		tokens:      make(map[string]map[types.FileID]int),
+		tokens:      make(map[string][]posting),
 		reverseKeys: make(map[types.FileID][]string),
 	}
 }
 
-// IndexFile tokenizes ASCII word tokens (a-z,A-Z,0-9,_) and records the first
-// occurrence offset per token for this file. Skips tokens shorter than 3.
-func (pi *PostingsIndex) IndexFile(fileID types.FileID, content []byte) {
-	if len(content) == 0 {
-		return
-	}
+// TokenizePostings splits content into lowercased ASCII word tokens (a-z,A-Z,0-9,_) of
+// at least MinPostingsTokenLength bytes and encodes the offsets and positions of each
+func TokenizePostings(content []byte) FilePostings {
+	postings := make(FilePostings)
+	last := make(map[string]Occurrence) // token -> previous occurrence, for the deltas
+	forEachToken(content, func(start, end, position int) {
+		if end-start < MinPostingsTokenLength {
+			return
+		}
+		tok := strings.ToLower(string(content[start:end]))
+		prev := last[tok]
+		buf := binary.AppendUvarint(postings[tok], uint64(start-prev.Offset))
+		postings[tok] = binary.AppendUvarint(buf, uint64(position-prev.Position))
+		last[tok] = Occurrence{Offset: start, Position: position}
+	})
+	return postings
+}
 
-	// Build a local set to avoid duplicate inserts and track first offset.
-	tokensForFile := make(map[string]int)
-	// Fast ASCII scan; treat letters and underscores/digits as part of a token.
-	start := -1
-	for i, b := range content {
-		if isTokenChar(b) {
+// FindOccurrences scans content for token, matched case-insensitively as a whole
+// token, numbering positions the way TokenizePostings does. It answers for tokens
+// shorter than MinPostingsTokenLength and files the index does not cover.
+func FindOccurrences(content []byte, token string) []Occurrence {
+	var occurrences []Occurrence
+	forEachToken(content, func(start, end, position int) {
+		if end-start == len(token) && strings.EqualFold(string(content[start:end]), token) {
+			occurrences = append(occurrences, Occurrence{Offset: start, Position: position})
+		}
+	})
+	return occurrences
+}
+
+// forEachToken calls fn with the span and position of every token of content
+func forEachToken(content []byte, fn func(start, end, position int)) {
+	start, position := -1, 0
+	for i := 0; i <= len(content); i++ {
+		if i < len(content) && isTokenChar(content[i]) {
 			if start < 0 {
 				start = i
 			}
 			continue
 		}
 		if start >= 0 {
-			pi.maybeAddTokenAt(tokensForFile, content[start:i], start)
-			start = -1
+			fn(start, i, position)
+			position++
 		}
+		start = -1
 	}
-	if start >= 0 {
-		pi.maybeAddTokenAt(tokensForFile, content[start:], start)
-	}
+}
 
-	if len(tokensForFile) == 0 {
-		return
-	}
+// IndexFile tokenizes content and records every token offset for this file
+func (pi *PostingsIndex) IndexFile(fileID types.FileID, content []byte) {
+	pi.Add(fileID, TokenizePostings(content))
+}
 
+// Add records precomputed postings for fileID, replacing any previous ones for it. A
+// file without tokens is still recorded, so Covers reports it as indexed.
+func (pi *PostingsIndex) Add(fileID types.FileID, postings FilePostings) {
 	// Only acquire lock if not in bulk indexing mode (multiple callers)
 	// During indexing, FileIntegrator is the only writer (lock-free)
 	if atomic.LoadInt32(&pi.BulkIndexing) == 0 {
@@ -73,91 +126,78 @@ func (pi *PostingsIndex) IndexFile(fileID types.FileID, content []byte) {
 		defer pi.mu.Unlock()
 	}
 
+	pi.removeLocked(fileID)
+
 	// Record reverse mapping first for efficient removal
-	keys := make([]string, 0, len(tokensForFile))
-	for tok := range tokensForFile {
+	keys := make([]string, 0, len(postings))
+	for tok, offsets := range postings {
 		keys = append(keys, tok)
+		list := pi.tokens[tok]
+		i := sort.Search(len(list), func(i int) bool { return list[i].fileID >= fileID })
+		// Files are mostly added in FileID order, making this an append
+		list = append(list, posting{})
+		copy(list[i+1:], list[i:])
+		list[i] = posting{fileID: fileID, offsets: offsets}
+		pi.tokens[tok] = list
 	}
 	pi.reverseKeys[fileID] = keys
-
-	for tok, off := range tokensForFile {
-		m, ok := pi.tokens[tok]
-		if !ok {
-			m = make(map[types.FileID]int)
-			pi.tokens[tok] = m
-		}
-		// Only store the first offset for this token/file
-		if _, exists := m[fileID]; !exists {
-			m[fileID] = off
-		}
-	}
-}
-func (pi *PostingsIndex) maybeAddTokenAt(dst map[string]int, raw []byte, absStart int) {
-	if len(raw) < 3 {
-		return
-	}
-	lower := bytes.ToLower(raw)
-	if !isAllASCII(lower) {
-		return
-	}
-	lower = bytes.TrimFunc(lower, func(r rune) bool {
-		return !(r >= 'a' && r <= 'z' || r >= '0' && r <= '9' || r == '_')
-	})
-	if len(lower) < 3 {
-		return
-	}
-	tok := string(lower)
-	if _, exists := dst[tok]; !exists {
-		dst[tok] = absStart
-	}
-}
-
-func (pi *PostingsIndex) maybeAddToken(dst map[string]int, raw []byte) {
-	if len(raw) < 3 {
-		return
-	}
-	// Lowercase for case-insensitive matching; skip non-ASCII quickly
-	lower := bytes.ToLower(raw)
-	if !isAllASCII(lower) {
-		return
-	}
-	// Trim leading/trailing non-letter/digit/underscore just in case
-	lower = bytes.TrimFunc(lower, func(r rune) bool {
-		return !(r >= 'a' && r <= 'z' || r >= '0' && r <= '9' || r == '_')
-	})
-	if len(lower) < 3 {
-		return
-	}
-	tok := string(lower)
-	// Record only the first offset for this token in this file
-	if _, exists := dst[tok]; !exists {
-		// Find first occurrence offset of tok in raw content segment's vicinity
-		// To avoid costly global search, use the start of the provided slice, which
-		// corresponds to the first occurrence position.
-		dst[tok] = 0 // placeholder; will be corrected by caller via absolute index
-	}
 }
 
 // RemoveFile removes all postings for a given fileID.
 func (pi *PostingsIndex) RemoveFile(fileID types.FileID) {
 	pi.mu.Lock()
 	defer pi.mu.Unlock()
-	keys := pi.reverseKeys[fileID]
+	pi.removeLocked(fileID)
+}
+
+func (pi *PostingsIndex) removeLocked(fileID types.FileID) {
+	keys, ok := pi.reverseKeys[fileID]
+	if !ok {
+		return
+	}
 	for _, tok := range keys {
-		if m, ok := pi.tokens[tok]; ok {
-			delete(m, fileID)
-			if len(m) == 0 {
-				delete(pi.tokens, tok)
-			}
+		list := pi.tokens[tok]
+		i := sort.Search(len(list), func(i int) bool { return list[i].fileID >= fileID })
+		if i == len(list) || list[i].fileID != fileID {
+			continue
 		}
+		if len(list) == 1 {
+			delete(pi.tokens, tok)
+			continue
+		}
+		pi.tokens[tok] = append(list[:i], list[i+1:]...)
 	}
 	delete(pi.reverseKeys, fileID)
 }
 
+// Clear removes all postings
+func (pi *PostingsIndex) Clear() {
+	pi.mu.Lock()
+	defer pi.mu.Unlock()
+	pi.tokens = make(map[string][]posting)
+	pi.reverseKeys = make(map[types.FileID][]string)
+}
+
+// FileCount returns the number of files recorded, with or without tokens
+func (pi *PostingsIndex) FileCount() int {
+	pi.mu.RLock()
+	defer pi.mu.RUnlock()
+	return len(pi.reverseKeys)
+}
+
+// Covers reports whether fileID was indexed, so an empty Offsets result means the
+// token is absent rather than unknown
+func (pi *PostingsIndex) Covers(fileID types.FileID) bool {
+	pi.mu.RLock()
+	defer pi.mu.RUnlock()
+	_, ok := pi.reverseKeys[fileID]
+	return ok
+}
+
 // Find returns candidate files and first offsets for a token. If caseInsensitive
 // is true, the token is lowercased before lookup.
 func (pi *PostingsIndex) Find(token string, caseInsensitive bool) (files []types.FileID, firstOffsets map[types.FileID]int) {
-	if len(token) < 3 {
+	if len(token) < MinPostingsTokenLength {
 		return nil, nil
 	}
 	tok := token
@@ -166,29 +206,99 @@ func (pi *PostingsIndex) Find(token string, caseInsensitive bool) (files []types
 	}
 	pi.mu.RLock()
 	defer pi.mu.RUnlock()
-	m, ok := pi.tokens[tok]
-	if !ok || len(m) == 0 {
+	list := pi.tokens[tok]
+	if len(list) == 0 {
 		return nil, nil
 	}
-	firstOffsets = make(map[types.FileID]int, len(m))
-	files = make([]types.FileID, 0, len(m))
-	for fid, off := range m {
-		files = append(files, fid)
-		firstOffsets[fid] = off
+	firstOffsets = make(map[types.FileID]int, len(list))
+	files = make([]types.FileID, 0, len(list))
+	for _, p := range list {
+		files = append(files, p.fileID)
+		first, _ := binary.Uvarint(p.offsets)
+		firstOffsets[p.fileID] = int(first)
 	}
 	return files, firstOffsets
 }
 
-// Helper: ASCII token char
-func isTokenChar(b byte) bool {
-	return (b >= 'A' && b <= 'Z') || (b >= 'a' && b <= 'z') || (b >= '0' && b <= '9') || b == '_'
+// FilesWithAll returns the files holding every one of tokens, in FileID order.
+// Tokens are matched case-insensitively; ones shorter than MinPostingsTokenLength
+// are not indexed and must not be passed.
+func (pi *PostingsIndex) FilesWithAll(tokens []string) []types.FileID {
+	if len(tokens) == 0 {
+		return nil
+	}
+	pi.mu.RLock()
+	defer pi.mu.RUnlock()
+
+	lists := make([][]posting, len(tokens))
+	for i, tok := range tokens {
+		lists[i] = pi.tokens[strings.ToLower(tok)]
+		if len(lists[i]) == 0 {
+			return nil
+		}
+	}
+	// Walk the rarest token's postings and look each file up in the others
+	sort.Slice(lists, func(i, j int) bool { return len(lists[i]) < len(lists[j]) })
+	files := make([]types.FileID, 0, len(lists[0]))
+	next := make([]int, len(lists)) // Search start per list; files only grow
+	for _, p := range lists[0] {
+		inAll := true
+		for i := 1; i < len(lists) && inAll; i++ {
+			list := lists[i][next[i]:]
+			j := sort.Search(len(list), func(j int) bool { return list[j].fileID >= p.fileID })
+			next[i] += j
+			inAll = j < len(list) && list[j].fileID == p.fileID
+		}
+		if inAll {
+			files = append(files, p.fileID)
+		}
+	}
+	return files
+}
+
+// Offsets returns every byte offset of token in fileID, ascending. The token is
+// matched case-insensitively.
+func (pi *PostingsIndex) Offsets(token string, fileID types.FileID) []int {
+	occurrences := pi.Occurrences(token, fileID)
+	if len(occurrences) == 0 {
+		return nil
+	}
+	offsets := make([]int, len(occurrences))
+	for i, o := range occurrences {
+		offsets[i] = o.Offset
+	}
+	return offsets
 }
 
-func isAllASCII(b []byte) bool {
-	for _, c := range b {
-		if c > 0x7F {
-			return false
+// Occurrences returns every occurrence of token in fileID, ascending. The token is
+// matched case-insensitively.
+func (pi *PostingsIndex) Occurrences(token string, fileID types.FileID) []Occurrence {
+	pi.mu.RLock()
+	defer pi.mu.RUnlock()
+	list := pi.tokens[strings.ToLower(token)]
+	i := sort.Search(len(list), func(i int) bool { return list[i].fileID >= fileID })
+	if i == len(list) || list[i].fileID != fileID {
+		return nil
+	}
+	var occurrences []Occurrence
+	var prev Occurrence
+	for buf := list[i].offsets; len(buf) > 0; {
+		offsetDelta, n := binary.Uvarint(buf)
+		if n <= 0 {
+			break
+		}
+		positionDelta, m := binary.Uvarint(buf[n:])
+		if m <= 0 {
+			break
 		}
+		prev = Occurrence{Offset: prev.Offset + int(offsetDelta), Position: prev.Position + int(positionDelta)}
+		occurrences = append(occurrences, prev)
+		buf = buf[n+m:]
 	}
-	return true
+	return occurrences
+}
+
+// Helper: ASCII token char
+func isTokenChar(b byte) bool {
+	return (b >= 'A' && b <= 'Z') || (b >= 'a' && b <= 'z') || (b >= '0' && b <= '9') || b == '_'
 }
@@ -0,0 +1,63 @@
+package core
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestPostingsIndex(t *testing.T) {
+	pi := NewPostingsIndex()
+	pi.IndexFile(2, []byte("func GetUser() *User {\n\treturn user // id\n}"))
+	pi.IndexFile(1, []byte("user := load_user()"))
+	pi.IndexFile(3, []byte("x y"))
+
+	assert.Equal(t, []int{16, 31}, pi.Offsets("user", 2), "offsets are case-folded and ascending")
+	assert.Equal(t, []int{0}, pi.Offsets("USER", 1))
+	assert.Empty(t, pi.Offsets("id", 2), "tokens shorter than three bytes are not indexed")
+	assert.Empty(t, pi.Offsets("user", 3))
+
+	assert.Equal(t, []types.FileID{1, 2}, pi.FilesWithAll([]string{"user"}), "files come back in FileID order")
+	assert.Equal(t, []types.FileID{2}, pi.FilesWithAll([]string{"user", "return"}))
+	assert.Empty(t, pi.FilesWithAll([]string{"user", "missing"}))
+
+	assert.Equal(t, 3, pi.FileCount(), "a file without tokens still counts as indexed")
+	assert.True(t, pi.Covers(3))
+
+	pi.RemoveFile(2)
+	assert.Equal(t, []types.FileID{1}, pi.FilesWithAll([]string{"user"}))
+	assert.Empty(t, pi.FilesWithAll([]string{"return"}))
+	assert.False(t, pi.Covers(2))
+
+	pi.Add(1, TokenizePostings([]byte("return")))
+	assert.Empty(t, pi.Offsets("user", 1), "adding a file again replaces its postings")
+	assert.Equal(t, []types.FileID{1}, pi.FilesWithAll([]string{"return"}))
+}
+
+func TestPostingsIndex_Occurrences(t *testing.T) {
+	content := []byte("if token.expires_at < now { return Err(ExpiredToken) } // a token")
+	pi := NewPostingsIndex()
+	pi.IndexFile(1, content)
+
+	want := []Occurrence{{Offset: 3, Position: 1}, {Offset: 60, Position: 8}}
+	assert.Equal(t, want, pi.Occurrences("TOKEN", 1), "positions count every token, short ones included")
+	assert.Equal(t, want, FindOccurrences(content, "token"), "scanning numbers positions like the index")
+	assert.Equal(t, []Occurrence{{Offset: 0, Position: 0}}, FindOccurrences(content, "if"), "short tokens are found by scanning")
+	assert.Empty(t, FindOccurrences(content, "expired"), "only whole tokens match")
+	assert.Equal(t, []int{3, 60}, pi.Offsets("token", 1))
+}
+
+func TestTokenizePostings_Compact(t *testing.T) {
+	content := []byte(strings.Repeat("token ", 1000))
+	postings := TokenizePostings(content)
+	assert.Len(t, postings["token"], 2000, "each delta of 6 bytes and of one position takes one byte")
+
+	pi := NewPostingsIndex()
+	pi.Add(1, postings)
+	offsets := pi.Offsets("token", 1)
+	assert.Len(t, offsets, 1000)
+	assert.Equal(t, 5994, offsets[999])
+}
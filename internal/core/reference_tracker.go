@@ -27,6 +27,7 @@ type ReferenceTracker struct {
 	references    map[uint64]*types.Reference       // All references by ID
 	symbolsByName map[string][]types.SymbolID       // Symbol lookup by name
 	symbolsByFile map[types.FileID][]types.SymbolID // Symbols by file
+	symbolNames   *nameTrie                         // Names of symbolsByName by prefix (see FindSymbolsByPrefix)
 
 	// Reference maps for fast lookup
 	incomingRefs map[types.SymbolID][]uint64 // Symbol -> References pointing to it
@@ -100,6 +101,7 @@ func NewReferenceTracker(symbolLocationIndex *SymbolLocationIndex) *ReferenceTra
 		symbols:             NewSymbolStore(expectedSymbols),
 		references:          make(map[uint64]*types.Reference, expectedSymbols*2),
 		symbolsByName:       make(map[string][]types.SymbolID, expectedSymbols),
+		symbolNames:         &nameTrie{},
 		symbolsByFile:       make(map[types.FileID][]types.SymbolID, 32), // Typical: 10-100 files
 		incomingRefs:        make(map[types.SymbolID][]uint64, expectedSymbols),
 		outgoingRefs:        make(map[types.SymbolID][]uint64, expectedSymbols),
@@ -145,6 +147,7 @@ func (rt *ReferenceTracker) Clear() {
 	// Clear all maps
 	rt.references = make(map[uint64]*types.Reference)
 	rt.symbolsByName = make(map[string][]types.SymbolID)
+	rt.symbolNames = &nameTrie{}
 	rt.symbolsByFile = make(map[types.FileID][]types.SymbolID)
 	rt.incomingRefs = make(map[types.SymbolID][]uint64)
 	rt.outgoingRefs = make(map[types.SymbolID][]uint64)
@@ -252,6 +255,7 @@ func (rt *ReferenceTracker) RemoveFile(fileID types.FileID) {
 				rt.symbolsByName[symbol.Name] = filtered
 			} else {
 				delete(rt.symbolsByName, symbol.Name)
+				rt.symbolNames.remove(symbol.Name)
 			}
 		}
 
@@ -415,13 +419,17 @@ func (rt *ReferenceTracker) ProcessFileWithScopeChains(fileID types.FileID, path
 	// Store scope hierarchy for this file
 	rt.scopesByFile[fileID] = scopes
 
-	// Build complexity lookup map from parser's enhanced symbols (keyed by line:column)
+	// Build complexity and doc comment lookup maps from parser's enhanced symbols (keyed by line)
 	// This allows O(1) lookup when creating our enhanced symbols
 	complexityMap := make(map[int]int) // line -> complexity
+	docMap := make(map[int]string)     // line -> leading doc comment
 	for _, pe := range parserEnhanced {
 		if pe.Complexity > 0 {
 			complexityMap[pe.Line] = pe.Complexity
 		}
+		if pe.DocComment != "" {
+			docMap[pe.Line] = pe.DocComment
+		}
 	}
 
 	// Convert basic symbols to enhanced symbols
@@ -456,6 +464,8 @@ func (rt *ReferenceTracker) ProcessFileWithScopeChains(fileID types.FileID, path
 		// If Visibility.IsExported is true, use it directly
 		// If Visibility.IsExported is false but Access is non-zero, trust the explicit setting
 		// If Visibility is completely unset (default zero values), compute from naming conventions
+		// The extractor records visibility for Rust, Go and Python, so a private Rust item is
+		// not exported even though computeIsExported treats every Rust name as exported
 		isExported := symbol.Visibility.IsExported
 		if !symbol.Visibility.IsExported && symbol.Visibility.Access == 0 {
 			// No explicit visibility set by parser, compute from language-specific naming conventions
@@ -476,11 +486,16 @@ func (rt *ReferenceTracker) ProcessFileWithScopeChains(fileID types.FileID, path
 			RefStats:     types.RefStats{}, // Will be calculated later
 			IsExported:   isExported,
 			Complexity:   complexity, // From parser's enhanced symbols
+			DocComment:   docMap[symbol.Line],
 		}
 
 		// Use SymbolStore.Set for O(1) array-based storage (30-50% faster)
 		rt.symbols.Set(symbolID, &enhancedSymbol)
-		rt.symbolsByName[symbol.Name] = append(rt.symbolsByName[symbol.Name], symbolID)
+		ids, known := rt.symbolsByName[symbol.Name]
+		if !known {
+			rt.symbolNames.insert(symbol.Name)
+		}
+		rt.symbolsByName[symbol.Name] = append(ids, symbolID)
 		rt.symbolScopes[symbolID] = scopeChain
 
 		enhancedSymbols = append(enhancedSymbols, enhancedSymbol)
@@ -1328,6 +1343,40 @@ func (rt *ReferenceTracker) FindSymbolsByName(name string) []*types.EnhancedSymb
 	return symbols
 }
 
+// FindSymbolsByPrefix finds the symbols whose name starts with prefix, ignoring case
+// when ignoreCase is set, through a trie of the symbol names rather than a scan of
+// them. Symbols come in the order of their lowercase names, at most limit of them unless
+// limit is 0, and symbols from deleted files are left out.
+func (rt *ReferenceTracker) FindSymbolsByPrefix(prefix string, ignoreCase bool, limit int) []*types.EnhancedSymbol {
+	rt.mu.RLock()
+	defer rt.mu.RUnlock()
+
+	var deletedSet interface{ Contains(types.FileID) bool }
+	if rt.deletedFileTracker != nil {
+		if set := rt.deletedFileTracker.GetDeletedSet(); set.Len() > 0 {
+			deletedSet = set
+		}
+	}
+	var symbols []*types.EnhancedSymbol
+	rt.symbolNames.walkPrefix(prefix, func(name string) bool {
+		if !ignoreCase && !strings.HasPrefix(name, prefix) {
+			return true
+		}
+		for _, symbolID := range rt.symbolsByName[name] {
+			symbol := rt.symbols.Get(symbolID)
+			if symbol == nil || deletedSet != nil && deletedSet.Contains(symbol.FileID) {
+				continue
+			}
+			symbols = append(symbols, symbol)
+			if limit > 0 && len(symbols) == limit {
+				return false
+			}
+		}
+		return true
+	})
+	return symbols
+}
+
 // GetEnhancedSymbol returns an enhanced symbol by ID
 // Returns nil if the symbol is from a deleted file
 // OPTIMIZED: Uses SymbolStore.Get for O(1) array access instead of map lookup
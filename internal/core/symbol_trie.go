@@ -0,0 +1,144 @@
+package core
+
+import (
+	"cmp"
+	"slices"
+	"strings"
+)
+
+// nameTrie is a radix trie of symbol names for prefix lookups. Names are keyed by
+// their lowercase form, so one walk serves both case-sensitive and case-insensitive
+// lookups, and each key keeps the names that fold to it. Children are sorted by the
+// first byte of their edge, so a walk yields names in the order of their keys.
+type nameTrie struct {
+	root trieNode
+	size int // Distinct names
+}
+
+type trieNode struct {
+	label    string      // Key bytes on the edge into this node
+	children []*trieNode // Sorted by label[0]
+	names    []string    // Sorted names whose key ends at this node
+}
+
+// child returns where the child whose edge starts with b is, or would be inserted
+func (n *trieNode) child(b byte) (int, bool) {
+	return slices.BinarySearchFunc(n.children, b, func(c *trieNode, b byte) int {
+		return cmp.Compare(c.label[0], b)
+	})
+}
+
+// insert adds name; adding a name twice keeps one copy
+func (t *nameTrie) insert(name string) {
+	n, key := &t.root, strings.ToLower(name)
+	for key != "" {
+		i, found := n.child(key[0])
+		if !found {
+			leaf := &trieNode{label: key}
+			n.children = slices.Insert(n.children, i, leaf)
+			n = leaf
+			break
+		}
+		c := n.children[i]
+		common := commonPrefixLen(c.label, key)
+		if common < len(c.label) {
+			// Split the edge where key leaves it
+			split := &trieNode{label: c.label[:common], children: []*trieNode{c}}
+			c.label = c.label[common:]
+			n.children[i] = split
+			c = split
+		}
+		n, key = c, key[common:]
+	}
+	if j, found := slices.BinarySearch(n.names, name); !found {
+		n.names = slices.Insert(n.names, j, name)
+		t.size++
+	}
+}
+
+// remove deletes name, pruning nodes left without names and merging a node left with
+// a single child into it, so the trie stays as small as after inserting the remaining
+// names alone
+func (t *nameTrie) remove(name string) {
+	n, key := &t.root, strings.ToLower(name)
+	path := []*trieNode{n}
+	for key != "" {
+		i, found := n.child(key[0])
+		if !found || !strings.HasPrefix(key, n.children[i].label) {
+			return
+		}
+		n = n.children[i]
+		key = key[len(n.label):]
+		path = append(path, n)
+	}
+	j, found := slices.BinarySearch(n.names, name)
+	if !found {
+		return
+	}
+	n.names = slices.Delete(n.names, j, j+1)
+	t.size--
+
+	for k := len(path) - 1; k > 0; k-- {
+		node, parent := path[k], path[k-1]
+		if len(node.names) > 0 || len(node.children) > 1 {
+			return
+		}
+		i, _ := parent.child(node.label[0])
+		if len(node.children) == 1 {
+			child := node.children[0]
+			child.label = node.label + child.label
+			parent.children[i] = child
+			return
+		}
+		parent.children = slices.Delete(parent.children, i, i+1)
+	}
+}
+
+// walkPrefix calls visit with every name whose key starts with the lowercase form of
+// prefix, in key order, until visit returns false. An empty prefix visits every name.
+func (t *nameTrie) walkPrefix(prefix string, visit func(name string) bool) {
+	n, key := &t.root, strings.ToLower(prefix)
+	for key != "" {
+		i, found := n.child(key[0])
+		if !found {
+			return
+		}
+		c := n.children[i]
+		common := commonPrefixLen(c.label, key)
+		switch {
+		case common == len(key):
+			key = "" // The prefix ends on this edge: every name below c matches
+		case common < len(c.label):
+			return
+		default:
+			key = key[common:]
+		}
+		n = c
+	}
+	n.walk(visit)
+}
+
+// walk visits the names at and below n in key order, reporting whether visit wants more
+func (n *trieNode) walk(visit func(name string) bool) bool {
+	for _, name := range n.names {
+		if !visit(name) {
+			return false
+		}
+	}
+	for _, c := range n.children {
+		if !c.walk(visit) {
+			return false
+		}
+	}
+	return true
+}
+
+func commonPrefixLen(a, b string) int {
+	n := min(len(a), len(b))
+	for i := range n {
+		if a[i] != b[i] {
+			return i
+		}
+	}
+	return n
+}
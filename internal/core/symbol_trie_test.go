@@ -0,0 +1,116 @@
+package core
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func trieNames(trie *nameTrie, prefix string) []string {
+	var names []string
+	trie.walkPrefix(prefix, func(name string) bool {
+		names = append(names, name)
+		return true
+	})
+	return names
+}
+
+func TestNameTrie(t *testing.T) {
+	trie := &nameTrie{}
+	for _, name := range []string{"get_user", "save_user", "getUser", "get", "GetAll", "get_user"} {
+		trie.insert(name)
+	}
+	if trie.size != 5 {
+		t.Errorf("size = %d, want 5 (a repeated name is kept once)", trie.size)
+	}
+
+	tests := []struct {
+		prefix string
+		want   []string
+	}{
+		{"get", []string{"get", "get_user", "GetAll", "getUser"}},
+		{"GET_", []string{"get_user"}},
+		{"getu", []string{"getUser"}},
+		{"ge", []string{"get", "get_user", "GetAll", "getUser"}},
+		{"user", nil},
+		{"get_users", nil},
+		{"", []string{"get", "get_user", "GetAll", "getUser", "save_user"}},
+	}
+	for _, tt := range tests {
+		if got := trieNames(trie, tt.prefix); !slices.Equal(got, tt.want) {
+			t.Errorf("walkPrefix(%q) = %v, want %v", tt.prefix, got, tt.want)
+		}
+	}
+
+	var first []string
+	trie.walkPrefix("get", func(name string) bool {
+		first = append(first, name)
+		return len(first) < 2
+	})
+	if len(first) != 2 {
+		t.Errorf("walk went on after visit returned false: %v", first)
+	}
+}
+
+func TestNameTrieRemove(t *testing.T) {
+	trie := &nameTrie{}
+	for _, name := range []string{"get_user", "get_account", "getUser", "GetUser"} {
+		trie.insert(name)
+	}
+	trie.remove("get_account")
+	trie.remove("missing")
+	trie.remove("get_acc")
+	if got, want := trieNames(trie, "get"), []string{"get_user", "GetUser", "getUser"}; !slices.Equal(got, want) {
+		t.Errorf("after remove: %v, want %v", got, want)
+	}
+
+	trie.remove("getUser")
+	if got, want := trieNames(trie, "getuser"), []string{"GetUser"}; !slices.Equal(got, want) {
+		t.Errorf("names folding to one key: %v, want %v", got, want)
+	}
+
+	// get_user alone is left: its node is merged back into a single edge
+	trie.remove("GetUser")
+	if len(trie.root.children) != 1 || trie.root.children[0].label != "get_user" {
+		t.Errorf("trie was not pruned: %+v", trie.root.children)
+	}
+	trie.remove("get_user")
+	if len(trie.root.children) != 0 || trie.size != 0 {
+		t.Errorf("trie is not empty: size %d, %+v", trie.size, trie.root.children)
+	}
+}
+
+func TestFindSymbolsByPrefix(t *testing.T) {
+	rt := NewReferenceTrackerForTest()
+	rt.ProcessFile(1, "a.go", []types.Symbol{
+		{Name: "get_user", Type: types.SymbolTypeFunction, Line: 1, EndLine: 2},
+		{Name: "save_user", Type: types.SymbolTypeFunction, Line: 3, EndLine: 4},
+		{Name: "GetAll", Type: types.SymbolTypeFunction, Line: 5, EndLine: 6},
+	}, nil, nil)
+	rt.ProcessFile(2, "b.go", []types.Symbol{
+		{Name: "get_user", Type: types.SymbolTypeMethod, Line: 1, EndLine: 2},
+	}, nil, nil)
+
+	names := func(symbols []*types.EnhancedSymbol) []string {
+		var names []string
+		for _, sym := range symbols {
+			names = append(names, sym.Name)
+		}
+		return names
+	}
+	if got, want := names(rt.FindSymbolsByPrefix("get", false, 0)), []string{"get_user", "get_user"}; !slices.Equal(got, want) {
+		t.Errorf("case-sensitive: %v, want %v", got, want)
+	}
+	if got, want := names(rt.FindSymbolsByPrefix("get", true, 0)), []string{"get_user", "get_user", "GetAll"}; !slices.Equal(got, want) {
+		t.Errorf("ignoring case: %v, want %v", got, want)
+	}
+	if got := rt.FindSymbolsByPrefix("get", true, 2); len(got) != 2 {
+		t.Errorf("max 2 returned %d symbols", len(got))
+	}
+
+	rt.RemoveFile(1)
+	if got, want := names(rt.FindSymbolsByPrefix("", true, 0)), []string{"get_user"}; !slices.Equal(got, want) {
+		t.Errorf("after removing a.go: %v, want %v", got, want)
+	}
+}
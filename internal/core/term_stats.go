@@ -0,0 +1,278 @@
+package core
+
+import (
+	"fmt"
+	"strings"
+	"sync"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TermTokenizer selects how text is split into BM25 terms
+type TermTokenizer string
+
+const (
+	// TermTokenizerRaw makes each identifier token (a-z, 0-9, _) of at least two
+	// characters one lowercased term
+	TermTokenizerRaw TermTokenizer = "raw"
+	// TermTokenizerIdentifier keeps each identifier as a term in its original casing
+	// and adds its lowercased snake_case and CamelCase parts, so "get_user" and
+	// "UserService" are found by a query for "user"
+	TermTokenizerIdentifier TermTokenizer = "identifier"
+)
+
+// ParseTermTokenizer validates a tokenizer name; empty selects TermTokenizerRaw
+func ParseTermTokenizer(name string) (TermTokenizer, error) {
+	switch TermTokenizer(name) {
+	case "", TermTokenizerRaw:
+		return TermTokenizerRaw, nil
+	case TermTokenizerIdentifier:
+		return TermTokenizerIdentifier, nil
+	}
+	return "", fmt.Errorf("unknown tokenizer %q (expected %s or %s)", name, TermTokenizerIdentifier, TermTokenizerRaw)
+}
+
+// CountTerms tokenizes content into terms and counts them
+func (t TermTokenizer) CountTerms(content []byte) FileTermStats {
+	stats := FileTermStats{Frequencies: make(map[string]int)}
+	t.forEachTerm(content, func(term string) {
+		stats.Frequencies[term]++
+		stats.Length++
+	})
+	return stats
+}
+
+// TokenizeTerms splits text into terms using the same rules as CountTerms
+func (t TermTokenizer) TokenizeTerms(text string) []string {
+	var terms []string
+	t.forEachTerm([]byte(text), func(term string) {
+		terms = append(terms, term)
+	})
+	return terms
+}
+
+func (t TermTokenizer) forEachTerm(content []byte, fn func(term string)) {
+	if t != TermTokenizerIdentifier {
+		forEachTerm(content, fn)
+		return
+	}
+	forEachToken(content, func(token string) {
+		fn(token)
+		parts := splitIdentifier(token)
+		if len(parts) == 1 && parts[0] == strings.ToLower(token) {
+			return // A plain lowercase word is its own only part
+		}
+		for _, part := range parts {
+			if len(part) >= 2 {
+				fn(strings.ToLower(part))
+			}
+		}
+	})
+}
+
+// splitIdentifier splits an identifier at underscores and case changes: "get_user" is
+// "get", "user"; "HTTPServer" is "HTTP", "Server"; digits stay with the part before
+// them, so "utf8Decode" is "utf8", "Decode"
+func splitIdentifier(s string) []string {
+	var parts []string
+	start := 0
+	for i := 0; i < len(s); i++ {
+		c := s[i]
+		if c == '_' {
+			if i > start {
+				parts = append(parts, s[start:i])
+			}
+			start = i + 1
+			continue
+		}
+		if i == start || !isUpperASCII(c) {
+			continue
+		}
+		prev := s[i-1]
+		acronymEnd := isUpperASCII(prev) && i+1 < len(s) && isLowerASCII(s[i+1])
+		if isLowerASCII(prev) || isDigitASCII(prev) || acronymEnd {
+			parts = append(parts, s[start:i])
+			start = i
+		}
+	}
+	if start < len(s) {
+		parts = append(parts, s[start:])
+	}
+	return parts
+}
+
+func isUpperASCII(b byte) bool { return b >= 'A' && b <= 'Z' }
+func isLowerASCII(b byte) bool { return b >= 'a' && b <= 'z' }
+func isDigitASCII(b byte) bool { return b >= '0' && b <= '9' }
+
+// FileTermStats holds the term frequencies of a single file, split into terms by a
+// TermTokenizer.
+type FileTermStats struct {
+	Frequencies map[string]int // term -> occurrences in the file
+	Length      int            // total number of term occurrences (document length)
+}
+
+// CountTerms tokenizes content into raw terms and counts them. It has no shared state
+// so indexing workers can call it in parallel and hand the result to TermStatsIndex.Add.
+func CountTerms(content []byte) FileTermStats {
+	return TermTokenizerRaw.CountTerms(content)
+}
+
+// TokenizeTerms splits text into terms using the same rules as CountTerms, so query
+// terms can be looked up in the index.
+func TokenizeTerms(text string) []string {
+	return TermTokenizerRaw.TokenizeTerms(text)
+}
+
+func forEachTerm(content []byte, fn func(term string)) {
+	forEachToken(content, func(token string) {
+		fn(strings.ToLower(token))
+	})
+}
+
+// forEachToken calls fn with every identifier token of at least two characters
+func forEachToken(content []byte, fn func(token string)) {
+	start := -1
+	for i := 0; i <= len(content); i++ {
+		if i < len(content) && isTokenChar(content[i]) {
+			if start < 0 {
+				start = i
+			}
+			continue
+		}
+		if start >= 0 && i-start >= 2 {
+			fn(string(content[start:i]))
+		}
+		start = -1
+	}
+}
+
+// TermStatsIndex records per-file term frequencies and corpus-wide document frequencies
+// at index time, which is what BM25-style ranking needs at query time.
+type TermStatsIndex struct {
+	mu          sync.RWMutex
+	tokenizer   TermTokenizer
+	files       map[types.FileID]FileTermStats
+	docFreq     map[string]int // term -> number of files containing it
+	totalLength int64
+}
+
+// NewTermStatsIndex creates an empty term statistics index with raw tokenization
+func NewTermStatsIndex() *TermStatsIndex {
+	return NewTermStatsIndexWithTokenizer(TermTokenizerRaw)
+}
+
+// NewTermStatsIndexWithTokenizer creates an empty term statistics index whose files
+// and queries are split into terms by tokenizer
+func NewTermStatsIndexWithTokenizer(tokenizer TermTokenizer) *TermStatsIndex {
+	return &TermStatsIndex{
+		tokenizer: tokenizer,
+		files:     make(map[types.FileID]FileTermStats),
+		docFreq:   make(map[string]int),
+	}
+}
+
+// Tokenizer returns the tokenizer that splits files and queries into terms
+func (ts *TermStatsIndex) Tokenizer() TermTokenizer {
+	return ts.tokenizer
+}
+
+// TokenizeTerms splits query text into terms the way indexed files were split
+func (ts *TermStatsIndex) TokenizeTerms(text string) []string {
+	return ts.tokenizer.TokenizeTerms(text)
+}
+
+// IndexFile counts the terms of content and records them for fileID
+func (ts *TermStatsIndex) IndexFile(fileID types.FileID, content []byte) {
+	ts.Add(fileID, ts.tokenizer.CountTerms(content))
+}
+
+// Add records precomputed stats for fileID, replacing any previous stats for it
+func (ts *TermStatsIndex) Add(fileID types.FileID, stats FileTermStats) {
+	ts.mu.Lock()
+	defer ts.mu.Unlock()
+
+	ts.removeLocked(fileID)
+	if stats.Frequencies == nil {
+		return
+	}
+	ts.files[fileID] = stats
+	ts.totalLength += int64(stats.Length)
+	for term := range stats.Frequencies {
+		ts.docFreq[term]++
+	}
+}
+
+// RemoveFile drops the stats recorded for fileID
+func (ts *TermStatsIndex) RemoveFile(fileID types.FileID) {
+	ts.mu.Lock()
+	defer ts.mu.Unlock()
+	ts.removeLocked(fileID)
+}
+
+func (ts *TermStatsIndex) removeLocked(fileID types.FileID) {
+	old, ok := ts.files[fileID]
+	if !ok {
+		return
+	}
+	for term := range old.Frequencies {
+		if ts.docFreq[term] <= 1 {
+			delete(ts.docFreq, term)
+		} else {
+			ts.docFreq[term]--
+		}
+	}
+	ts.totalLength -= int64(old.Length)
+	delete(ts.files, fileID)
+}
+
+// TermFrequencies returns a copy of the term frequencies recorded for fileID
+func (ts *TermStatsIndex) TermFrequencies(fileID types.FileID) map[string]int {
+	ts.mu.RLock()
+	defer ts.mu.RUnlock()
+
+	stats, ok := ts.files[fileID]
+	if !ok {
+		return nil
+	}
+	freqs := make(map[string]int, len(stats.Frequencies))
+	for term, n := range stats.Frequencies {
+		freqs[term] = n
+	}
+	return freqs
+}
+
+// TermFrequency returns how often term occurs in fileID and the file's length in terms
+func (ts *TermStatsIndex) TermFrequency(fileID types.FileID, term string) (freq, length int) {
+	ts.mu.RLock()
+	defer ts.mu.RUnlock()
+	stats := ts.files[fileID]
+	return stats.Frequencies[term], stats.Length
+}
+
+// DocumentFrequency returns the number of files that contain term
+func (ts *TermStatsIndex) DocumentFrequency(term string) int {
+	ts.mu.RLock()
+	defer ts.mu.RUnlock()
+	return ts.docFreq[term]
+}
+
+// CorpusStats returns the number of indexed files and their average length in terms
+func (ts *TermStatsIndex) CorpusStats() (docCount int, avgLength float64) {
+	ts.mu.RLock()
+	defer ts.mu.RUnlock()
+	docCount = len(ts.files)
+	if docCount > 0 {
+		avgLength = float64(ts.totalLength) / float64(docCount)
+	}
+	return docCount, avgLength
+}
+
+// Clear removes all recorded stats
+func (ts *TermStatsIndex) Clear() {
+	ts.mu.Lock()
+	defer ts.mu.Unlock()
+	ts.files = make(map[types.FileID]FileTermStats)
+	ts.docFreq = make(map[string]int)
+	ts.totalLength = 0
+}
@@ -0,0 +1,86 @@
+package core
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestCountTerms(t *testing.T) {
+	stats := CountTerms([]byte("func GetUser(id string) *User {\n\treturn db.get_user(id) // x\n}"))
+
+	assert.Equal(t, 2, stats.Frequencies["id"])
+	assert.Equal(t, 1, stats.Frequencies["getuser"])
+	assert.Equal(t, 1, stats.Frequencies["get_user"])
+	assert.Equal(t, 1, stats.Frequencies["user"], "terms are lowercased")
+	assert.NotContains(t, stats.Frequencies, "x", "single characters are not terms")
+
+	total := 0
+	for _, n := range stats.Frequencies {
+		total += n
+	}
+	assert.Equal(t, total, stats.Length)
+
+	assert.Equal(t, []string{"find_user", "by", "id"}, TokenizeTerms("find_user by-ID"))
+}
+
+func TestIdentifierTokenizer(t *testing.T) {
+	assert.Equal(t, []string{"get_user", "get", "user"}, TermTokenizerIdentifier.TokenizeTerms("get_user"))
+	assert.Equal(t, []string{"UserService", "user", "service"}, TermTokenizerIdentifier.TokenizeTerms("UserService"),
+		"the whole identifier keeps its casing for exact matches")
+	assert.Equal(t, []string{"HTTPServer", "http", "server"}, TermTokenizerIdentifier.TokenizeTerms("HTTPServer"))
+	assert.Equal(t, []string{"utf8Decode", "utf8", "decode"}, TermTokenizerIdentifier.TokenizeTerms("utf8Decode"))
+	assert.Equal(t, []string{"user", "id"}, TermTokenizerIdentifier.TokenizeTerms("user id"), "plain words are not repeated")
+	assert.Equal(t, []string{"get_x", "get"}, TermTokenizerIdentifier.TokenizeTerms("get_x"), "single-character parts are dropped")
+
+	stats := TermTokenizerIdentifier.CountTerms([]byte("func GetUser() *User { return get_user() }"))
+	assert.Equal(t, 3, stats.Frequencies["user"], "GetUser, User and get_user each contribute a part")
+	assert.Equal(t, 1, stats.Frequencies["User"])
+	assert.Equal(t, 1, stats.Frequencies["GetUser"])
+	assert.NotContains(t, stats.Frequencies, "getuser")
+
+	ts := NewTermStatsIndexWithTokenizer(TermTokenizerIdentifier)
+	ts.IndexFile(1, []byte("fn get_user() {}"))
+	assert.Equal(t, 1, ts.DocumentFrequency("user"))
+	assert.Equal(t, []string{"FindUser", "find", "user"}, ts.TokenizeTerms("FindUser"))
+
+	tok, err := ParseTermTokenizer("")
+	assert.NoError(t, err)
+	assert.Equal(t, TermTokenizerRaw, tok)
+	_, err = ParseTermTokenizer("words")
+	assert.Error(t, err)
+}
+
+func TestTermStatsIndex_DocumentFrequencies(t *testing.T) {
+	ts := NewTermStatsIndex()
+	ts.IndexFile(1, []byte("token token token parse"))
+	ts.IndexFile(2, []byte("token render"))
+
+	assert.Equal(t, 2, ts.DocumentFrequency("token"))
+	assert.Equal(t, 1, ts.DocumentFrequency("parse"))
+
+	freq, length := ts.TermFrequency(1, "token")
+	assert.Equal(t, 3, freq)
+	assert.Equal(t, 4, length)
+
+	docs, avg := ts.CorpusStats()
+	assert.Equal(t, 2, docs)
+	assert.Equal(t, 3.0, avg)
+
+	// Re-adding a file replaces its old stats
+	ts.IndexFile(1, []byte("parse"))
+	assert.Equal(t, 1, ts.DocumentFrequency("token"))
+	assert.Equal(t, map[string]int{"parse": 1}, ts.TermFrequencies(1))
+
+	ts.RemoveFile(2)
+	assert.Equal(t, 0, ts.DocumentFrequency("token"))
+	assert.Nil(t, ts.TermFrequencies(2))
+	docs, avg = ts.CorpusStats()
+	assert.Equal(t, 1, docs)
+	assert.Equal(t, 1.0, avg)
+
+	// Callers get a copy they can't use to corrupt the index
+	ts.TermFrequencies(1)["parse"] = 99
+	freq, _ = ts.TermFrequency(1, "parse")
+	assert.Equal(t, 1, freq)
+}
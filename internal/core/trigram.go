@@ -2,6 +2,7 @@ package core
 
 import (
 	"bytes"
+	"slices"
 	"strings"
 	"sync"
 	"sync/atomic"
@@ -480,15 +481,17 @@ func (ti *TrigramIndex) FindMatchLocations(pattern string, caseInsensitive bool,
 
 		// Find all exact matches in this file
 		content := fileInfo.Content
-		if caseInsensitive {
-			// Use bytes.ToLower to avoid double conversion ([]byte → string → []byte)
-			content = bytes.ToLower(fileInfo.Content)
-		}
 
-		// Find exact pattern matches (use bytes.Index to avoid allocations)
+		// Find exact pattern matches (use bytes.Index to avoid allocations); case-insensitive
+		// matching folds case on the fly rather than lowercasing the whole file
 		offset := 0
 		for {
-			idx := bytes.Index(content[offset:], patternBytes)
+			var idx int
+			if caseInsensitive {
+				idx, _ = types.IndexFold(content[offset:], patternBytes)
+			} else {
+				idx = bytes.Index(content[offset:], patternBytes)
+			}
 			if idx < 0 {
 				break
 			}
@@ -646,6 +649,7 @@ func (ti *TrigramIndex) filterAndReturnCandidates(fileTrigramCounts map[types.Fi
 			candidates = append(candidates, fileID)
 		}
 	}
+	slices.Sort(candidates) // Map order is random; keep results stable across runs
 
 	// OPTIMIZED: Cache the results for future lookups
 	// Only cache if there are results and no active indexing operations
@@ -0,0 +1,165 @@
+package encoding
+
+import (
+	"bytes"
+	"fmt"
+	"strings"
+	"unicode/utf16"
+	"unicode/utf8"
+)
+
+// Text encodings files can be decoded from before indexing, by their canonical names
+const (
+	UTF8        = "utf-8"
+	UTF16LE     = "utf-16le"
+	UTF16BE     = "utf-16be"
+	Windows1252 = "windows-1252"
+	Latin1      = "latin-1" // ISO-8859-1
+)
+
+// AutoDetect is the encoding setting that detects each file's encoding (see DetectText)
+const AutoDetect = "auto"
+
+// Byte order marks
+var (
+	bomUTF8    = []byte{0xEF, 0xBB, 0xBF}
+	bomUTF16LE = []byte{0xFF, 0xFE}
+	bomUTF16BE = []byte{0xFE, 0xFF}
+)
+
+// ParseTextEncoding returns the canonical name of an encoding setting: AutoDetect or
+// one of the text encodings, accepting common aliases such as utf16, cp1252 and
+// iso-8859-1. UTF-16 without an endianness is little-endian, as Windows writes it.
+func ParseTextEncoding(name string) (string, error) {
+	switch strings.ToLower(strings.TrimSpace(name)) {
+	case AutoDetect:
+		return AutoDetect, nil
+	case "utf-8", "utf8":
+		return UTF8, nil
+	case "utf-16", "utf16", "utf-16le", "utf16le", "ucs-2":
+		return UTF16LE, nil
+	case "utf-16be", "utf16be":
+		return UTF16BE, nil
+	case "windows-1252", "windows1252", "cp1252":
+		return Windows1252, nil
+	case "latin-1", "latin1", "iso-8859-1", "iso8859-1":
+		return Latin1, nil
+	}
+	return "", fmt.Errorf("unknown encoding %q (expected auto, utf-8, utf-16le, utf-16be, windows-1252 or latin-1)", name)
+}
+
+// ResolveTextEncoding returns the encoding a file starting with head is decoded from
+// under setting, a value of ParseTextEncoding or "" to read every file as UTF-8. A byte
+// order mark outranks a forced encoding. It returns false when AutoDetect cannot tell,
+// leaving the file to be judged, typically skipped, as binary.
+func ResolveTextEncoding(setting string, head []byte) (string, bool) {
+	switch {
+	case setting == "":
+		return UTF8, true
+	case bytes.HasPrefix(head, bomUTF8):
+		return UTF8, true
+	case bytes.HasPrefix(head, bomUTF16LE):
+		return UTF16LE, true
+	case bytes.HasPrefix(head, bomUTF16BE):
+		return UTF16BE, true
+	case setting == AutoDetect:
+		return DetectText(head)
+	}
+	return setting, true
+}
+
+// utf16SampleSize bounds how much of a file DetectText inspects for UTF-16 without a
+// byte order mark
+const utf16SampleSize = 4096
+
+// DetectText guesses the encoding of content without a byte order mark: UTF-8 when it
+// is valid UTF-8 without NUL bytes, UTF-16 when NUL bytes fill the high byte of most
+// characters, as in mostly-ASCII UTF-16 text, and otherwise Windows-1252 unless it holds
+// NUL bytes at all, in which case the encoding is unknown and it returns false.
+func DetectText(content []byte) (string, bool) {
+	sample := content[:min(len(content), utf16SampleSize)]
+	if bytes.IndexByte(sample, 0) < 0 {
+		if utf8.Valid(content) {
+			return UTF8, true
+		}
+		return Windows1252, true
+	}
+
+	var evenNULs, oddNULs int
+	for i, b := range sample {
+		if b == 0 {
+			if i%2 == 0 {
+				evenNULs++
+			} else {
+				oddNULs++
+			}
+		}
+	}
+	chars := len(sample) / 2
+	switch {
+	case chars == 0:
+		return "", false
+	case oddNULs*2 > chars && evenNULs*10 < oddNULs:
+		return UTF16LE, true
+	case evenNULs*2 > chars && oddNULs*10 < evenNULs:
+		return UTF16BE, true
+	}
+	return "", false
+}
+
+// DecodeText converts content from encoding to UTF-8, dropping a byte order mark of
+// that encoding. UTF-8 content is returned as is. Unpaired UTF-16 surrogates and a
+// trailing odd byte become U+FFFD.
+func DecodeText(content []byte, encoding string) ([]byte, error) {
+	switch encoding {
+	case UTF8:
+		return content, nil
+	case UTF16LE, UTF16BE:
+		return decodeUTF16(content, encoding == UTF16BE), nil
+	case Windows1252, Latin1:
+		out := make([]byte, 0, len(content)+len(content)/8)
+		for _, b := range content {
+			r := rune(b)
+			if encoding == Windows1252 && b >= 0x80 && b < 0xA0 && windows1252[b-0x80] != 0 {
+				r = windows1252[b-0x80]
+			}
+			out = utf8.AppendRune(out, r)
+		}
+		return out, nil
+	}
+	return nil, fmt.Errorf("unknown encoding %q", encoding)
+}
+
+func decodeUTF16(content []byte, bigEndian bool) []byte {
+	bom := bomUTF16LE
+	if bigEndian {
+		bom = bomUTF16BE
+	}
+	content = bytes.TrimPrefix(content, bom)
+
+	units := make([]uint16, len(content)/2)
+	for i := range units {
+		lo, hi := content[2*i], content[2*i+1]
+		if bigEndian {
+			lo, hi = hi, lo
+		}
+		units[i] = uint16(lo) | uint16(hi)<<8
+	}
+	out := make([]byte, 0, len(units))
+	for _, r := range utf16.Decode(units) {
+		out = utf8.AppendRune(out, r)
+	}
+	if len(content)%2 == 1 {
+		out = utf8.AppendRune(out, utf8.RuneError)
+	}
+	return out
+}
+
+// windows1252 maps the bytes 0x80-0x9F where Windows-1252 differs from Latin-1; zero
+// entries are unassigned and decode like Latin-1
+var windows1252 = [32]rune{
+	0x20AC, 0, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021,
+	0x02C6, 0x2030, 0x0160, 0x2039, 0x0152, 0, 0x017D, 0,
+	0, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014,
+	0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0, 0x017E, 0x0178,
+}
@@ -0,0 +1,108 @@
+package encoding
+
+import (
+	"testing"
+	"unicode/utf16"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// utf16Bytes encodes s as UTF-16 in the given byte order, without a byte order mark
+func utf16Bytes(s string, bigEndian bool) []byte {
+	var out []byte
+	for _, u := range utf16.Encode([]rune(s)) {
+		if bigEndian {
+			out = append(out, byte(u>>8), byte(u))
+		} else {
+			out = append(out, byte(u), byte(u>>8))
+		}
+	}
+	return out
+}
+
+func TestParseTextEncoding(t *testing.T) {
+	for name, want := range map[string]string{
+		"auto":       AutoDetect,
+		"UTF8":       UTF8,
+		"utf-16":     UTF16LE,
+		"UTF-16BE":   UTF16BE,
+		"cp1252":     Windows1252,
+		"ISO-8859-1": Latin1,
+	} {
+		got, err := ParseTextEncoding(name)
+		require.NoError(t, err, name)
+		assert.Equal(t, want, got, name)
+	}
+
+	_, err := ParseTextEncoding("ebcdic")
+	assert.Error(t, err)
+}
+
+func TestDetectText(t *testing.T) {
+	source := "func main() {\n\tprintln(\"héllo\")\n}\n"
+	tests := []struct {
+		name    string
+		content []byte
+		want    string
+		ok      bool
+	}{
+		{"utf-8", []byte(source), UTF8, true},
+		{"utf-16le", utf16Bytes(source, false), UTF16LE, true},
+		{"utf-16be", utf16Bytes(source, true), UTF16BE, true},
+		{"windows-1252", []byte("caf\xe9 \x93quoted\x94\n"), Windows1252, true},
+		{"binary", []byte{0x00, 0x01, 0x00, 0x00, 0x7f, 0x45, 0x4c, 0x46, 0x02, 0x01, 0x01, 0x00}, "", false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, ok := DetectText(tt.content)
+			assert.Equal(t, tt.ok, ok)
+			assert.Equal(t, tt.want, got)
+		})
+	}
+}
+
+func TestResolveTextEncoding(t *testing.T) {
+	latin := []byte("caf\xe9\n")
+	bomLE := append([]byte{0xFF, 0xFE}, utf16Bytes("x", false)...)
+
+	enc, ok := ResolveTextEncoding("", bomLE)
+	assert.True(t, ok)
+	assert.Equal(t, UTF8, enc, "no decoding without a setting")
+
+	enc, _ = ResolveTextEncoding(Latin1, bomLE)
+	assert.Equal(t, UTF16LE, enc, "a byte order mark outranks a forced encoding")
+
+	enc, _ = ResolveTextEncoding(Latin1, latin)
+	assert.Equal(t, Latin1, enc)
+
+	enc, _ = ResolveTextEncoding(AutoDetect, latin)
+	assert.Equal(t, Windows1252, enc)
+}
+
+func TestDecodeText(t *testing.T) {
+	source := "fn naïve() -> 😀 {}\n"
+
+	got, err := DecodeText(append([]byte{0xFF, 0xFE}, utf16Bytes(source, false)...), UTF16LE)
+	require.NoError(t, err)
+	assert.Equal(t, source, string(got), "the byte order mark is dropped")
+
+	got, err = DecodeText(utf16Bytes(source, true), UTF16BE)
+	require.NoError(t, err)
+	assert.Equal(t, source, string(got))
+
+	got, err = DecodeText([]byte("\x80 caf\xe9 \x93q\x94 \x81"), Windows1252)
+	require.NoError(t, err)
+	assert.Equal(t, "€ café “q” \u0081", string(got))
+
+	got, err = DecodeText([]byte("\x80 caf\xe9"), Latin1)
+	require.NoError(t, err)
+	assert.Equal(t, "\u0080 café", string(got))
+
+	got, err = DecodeText([]byte{'a', 0x00, 'b'}, UTF16LE)
+	require.NoError(t, err)
+	assert.Equal(t, "a�", string(got), "a trailing odd byte is replaced")
+
+	_, err = DecodeText([]byte("x"), "ebcdic")
+	assert.Error(t, err)
+}
@@ -11,6 +11,9 @@ import (
 	"path/filepath"
 	"strconv"
 	"strings"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 // Provider wraps git commands to extract file states at different refs
@@ -315,6 +318,188 @@ func (p *Provider) parseNumstat(output []byte) (DiffStats, error) {
 	return stats, scanner.Err()
 }
 
+// GetChangedLines returns the lines added or modified in the working tree since
+// baseRef, keyed by repo-relative path. Deleted lines have no counterpart in the tree
+// and are not reported; untracked files are not part of the diff.
+func (p *Provider) GetChangedLines(ctx context.Context, baseRef string) (map[string][]types.LineRange, error) {
+	if baseRef == "" || strings.HasPrefix(baseRef, "-") {
+		return nil, fmt.Errorf("invalid base ref %q", baseRef)
+	}
+
+	cmd := exec.CommandContext(ctx, "git", "diff", "--unified=0", "--no-color", "--no-ext-diff",
+		"--src-prefix=a/", "--dst-prefix=b/", baseRef, "--")
+	cmd.Dir = p.repoRoot
+
+	output, err := cmd.Output()
+	if err != nil {
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) && len(exitErr.Stderr) > 0 {
+			return nil, fmt.Errorf("git diff %s failed: %s", baseRef, strings.TrimSpace(string(exitErr.Stderr)))
+		}
+		return nil, fmt.Errorf("git diff %s failed: %w", baseRef, err)
+	}
+
+	return p.parseDiffHunks(output)
+}
+
+// parseDiffHunks collects the new-side line range of every `git diff --unified=0` hunk
+// by path. Pure deletions (+N,0) and deleted files add no ranges.
+func (p *Provider) parseDiffHunks(output []byte) (map[string][]types.LineRange, error) {
+	changed := make(map[string][]types.LineRange)
+	var path string
+	inHeader := false
+
+	scanner := bufio.NewScanner(bytes.NewReader(output))
+	scanner.Buffer(make([]byte, 0, 64*1024), 64*1024*1024) // Changed lines of minified files can be long
+	for scanner.Scan() {
+		line := scanner.Text()
+		switch {
+		case strings.HasPrefix(line, "diff --git "):
+			path, inHeader = "", true
+		case inHeader && strings.HasPrefix(line, "+++ "):
+			path = diffPath(strings.TrimPrefix(line, "+++ "))
+		case strings.HasPrefix(line, "@@ "):
+			inHeader = false
+			start, count, ok := parseHunkNewRange(line)
+			if ok && count > 0 && path != "" {
+				changed[path] = append(changed[path], types.LineRange{Start: start, End: start + count - 1})
+			}
+		}
+	}
+
+	return changed, scanner.Err()
+}
+
+// diffPath extracts the path from the target of a "+++ " header such as "b/src/x.go",
+// unquoting names git writes C-quoted. It returns "" for /dev/null.
+func diffPath(target string) string {
+	if target == "/dev/null" {
+		return ""
+	}
+	if strings.HasPrefix(target, "\"") {
+		if unquoted, err := strconv.Unquote(target); err == nil {
+			target = unquoted
+		}
+	}
+	return strings.TrimPrefix(target, "b/")
+}
+
+// parseHunkNewRange reads the "+start,count" part of a hunk header such as
+// "@@ -10,2 +12,3 @@ func main() {". A missing count means one line.
+func parseHunkNewRange(header string) (start, count int, ok bool) {
+	fields := strings.Fields(header)
+	if len(fields) < 3 || !strings.HasPrefix(fields[2], "+") {
+		return 0, 0, false
+	}
+	startText, countText, hasCount := strings.Cut(fields[2][1:], ",")
+	start, err := strconv.Atoi(startText)
+	if err != nil {
+		return 0, 0, false
+	}
+	count = 1
+	if hasCount {
+		if count, err = strconv.Atoi(countText); err != nil {
+			return 0, 0, false
+		}
+	}
+	return start, count, true
+}
+
+// BlameLine is the commit that last touched a line, as git blame reports it
+type BlameLine struct {
+	Commit     string    // Full hash; all zeros for a line not committed yet
+	Author     string
+	AuthorTime time.Time // When the change was authored, in UTC
+}
+
+// BlameLines runs git blame on just the given lines of the working tree file at path,
+// absolute or relative to the repo root, and returns who last touched each by line
+// number. Files git does not track are an error.
+func (p *Provider) BlameLines(ctx context.Context, path string, lines []int) (map[int]BlameLine, error) {
+	if len(lines) == 0 {
+		return map[int]BlameLine{}, nil
+	}
+	if filepath.IsAbs(path) {
+		// The repo root git reports has symlinks resolved, so resolve path to match
+		if resolved, err := filepath.EvalSymlinks(path); err == nil {
+			path = resolved
+		}
+		rel, err := filepath.Rel(p.repoRoot, path)
+		if err != nil {
+			return nil, fmt.Errorf("%s is not in the repository: %w", path, err)
+		}
+		path = rel
+	}
+
+	args := []string{"blame", "--porcelain"}
+	for _, line := range lines {
+		args = append(args, "-L", fmt.Sprintf("%d,%d", line, line))
+	}
+	cmd := exec.CommandContext(ctx, "git", append(args, "--", path)...)
+	cmd.Dir = p.repoRoot
+
+	output, err := cmd.Output()
+	if err != nil {
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) && len(exitErr.Stderr) > 0 {
+			return nil, fmt.Errorf("git blame %s failed: %s", path, strings.TrimSpace(string(exitErr.Stderr)))
+		}
+		return nil, fmt.Errorf("git blame %s failed: %w", path, err)
+	}
+
+	return parseBlamePorcelain(output)
+}
+
+// parseBlamePorcelain reads `git blame --porcelain` output. Each blamed line starts with
+// a "<hash> <original line> <final line>" header; the commit's author fields follow
+// only the first time the commit appears, and the line's content, prefixed by a tab,
+// ends the entry.
+func parseBlamePorcelain(output []byte) (map[int]BlameLine, error) {
+	blamed := make(map[int]BlameLine)
+	commits := make(map[string]*BlameLine)
+	var current *BlameLine
+	finalLine := 0
+
+	scanner := bufio.NewScanner(bytes.NewReader(output))
+	scanner.Buffer(make([]byte, 0, 64*1024), 64*1024*1024) // Blamed lines of minified files can be long
+	for scanner.Scan() {
+		line := scanner.Text()
+		switch {
+		case strings.HasPrefix(line, "\t"):
+			if current != nil {
+				blamed[finalLine] = *current
+			}
+			current = nil
+		case current == nil:
+			fields := strings.Fields(line)
+			if len(fields) < 3 {
+				continue
+			}
+			n, err := strconv.Atoi(fields[2])
+			if err != nil {
+				continue
+			}
+			finalLine = n
+			if current = commits[fields[0]]; current == nil {
+				current = &BlameLine{Commit: fields[0]}
+				commits[fields[0]] = current
+			}
+		default:
+			key, value, _ := strings.Cut(line, " ")
+			switch key {
+			case "author":
+				current.Author = value
+			case "author-time":
+				if secs, err := strconv.ParseInt(value, 10, 64); err == nil {
+					current.AuthorTime = time.Unix(secs, 0).UTC()
+				}
+			}
+		}
+	}
+
+	return blamed, scanner.Err()
+}
+
 // GetBaseRef determines the appropriate base reference for a scope
 func (p *Provider) GetBaseRef(ctx context.Context, params AnalysisParams) (string, error) {
 	switch params.Scope {
@@ -3,7 +3,11 @@ package git
 import (
 	"os"
 	"path/filepath"
+	"reflect"
 	"testing"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 func TestNewProvider_NotGitRepo(t *testing.T) {
@@ -455,3 +459,77 @@ func TestProvider_GetTargetRef(t *testing.T) {
 		})
 	}
 }
+
+func TestProvider_parseDiffHunks(t *testing.T) {
+	input := "diff --git a/src/auth.go b/src/auth.go\n" +
+		"index 1111111..2222222 100644\n" +
+		"--- a/src/auth.go\n" +
+		"+++ b/src/auth.go\n" +
+		"@@ -3 +3 @@ package auth\n" +
+		"-old\n" +
+		"+new\n" +
+		"@@ -10,0 +11,3 @@ func Login() {\n" +
+		"+++ counter\n" +
+		"+b\n" +
+		"+c\n" +
+		"@@ -20,2 +23,0 @@\n" +
+		"-gone\n" +
+		"-gone\n" +
+		"diff --git a/old.go b/old.go\n" +
+		"deleted file mode 100644\n" +
+		"--- a/old.go\n" +
+		"+++ /dev/null\n" +
+		"@@ -1,2 +0,0 @@\n" +
+		"-x\n" +
+		"-y\n"
+
+	p := &Provider{}
+	changed, err := p.parseDiffHunks([]byte(input))
+	if err != nil {
+		t.Fatalf("parseDiffHunks() error = %v", err)
+	}
+
+	want := []types.LineRange{{Start: 3, End: 3}, {Start: 11, End: 13}}
+	if got := changed["src/auth.go"]; !reflect.DeepEqual(got, want) {
+		t.Errorf("src/auth.go ranges = %v, want %v", got, want)
+	}
+	if len(changed) != 1 {
+		t.Errorf("changed files = %v, want only src/auth.go (added lines starting with ++ are not headers, deleted files have no lines)", changed)
+	}
+}
+
+func TestProvider_parseBlamePorcelain(t *testing.T) {
+	input := "1111111111111111111111111111111111111111 2 3 1\n" +
+		"author Ada Lovelace\n" +
+		"author-mail <ada@example.com>\n" +
+		"author-time 1700000000\n" +
+		"author-tz +0100\n" +
+		"committer Ada Lovelace\n" +
+		"summary Add the engine\n" +
+		"filename engine.go\n" +
+		"\tfunc Run() {\n" +
+		"2222222222222222222222222222222222222222 9 10 1\n" +
+		"author Charles Babbage\n" +
+		"author-time 1600000000\n" +
+		"summary Fix the difference\n" +
+		"previous 3333333333333333333333333333333333333333 engine.go\n" +
+		"filename engine.go\n" +
+		"\tauthor x\n" +
+		"1111111111111111111111111111111111111111 5 12\n" +
+		"\treturn nil\n"
+
+	blamed, err := parseBlamePorcelain([]byte(input))
+	if err != nil {
+		t.Fatalf("parseBlamePorcelain() error = %v", err)
+	}
+
+	ada := BlameLine{Commit: "1111111111111111111111111111111111111111", Author: "Ada Lovelace", AuthorTime: time.Unix(1700000000, 0).UTC()}
+	want := map[int]BlameLine{
+		3:  ada,
+		10: {Commit: "2222222222222222222222222222222222222222", Author: "Charles Babbage", AuthorTime: time.Unix(1600000000, 0).UTC()},
+		12: ada, // A commit's author fields are only given the first time it appears
+	}
+	if !reflect.DeepEqual(blamed, want) {
+		t.Errorf("blamed lines = %v, want %v (content lines that look like fields are not fields)", blamed, want)
+	}
+}
@@ -0,0 +1,168 @@
+package indexing
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/debug"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// isArchive reports whether path names a gzipped tarball, which is indexed as if it
+// were a directory when passed as a root
+func isArchive(path string) bool {
+	lower := strings.ToLower(path)
+	return strings.HasSuffix(lower, ".tar.gz") || strings.HasSuffix(lower, ".tgz")
+}
+
+// archiveEntryPath returns the virtual path of an entry inside an archive
+func archiveEntryPath(archive, entry string) string {
+	return archive + types.ArchivePathSeparator + entry
+}
+
+// isArchiveRoot reports whether root is an archive file rather than a directory
+func isArchiveRoot(root string) bool {
+	if !isArchive(root) {
+		return false
+	}
+	info, err := os.Stat(root)
+	return err == nil && info.Mode().IsRegular()
+}
+
+// walkArchive streams the regular files of a gzipped tarball to fn in archive order,
+// with each entry's cleaned name and header. Entries are decompressed as they are read,
+// so the archive is never extracted to disk; fn reads the entry from tr or leaves it to
+// be skipped.
+func walkArchive(ctx context.Context, archive string, fn func(name string, hdr *tar.Header, tr *tar.Reader) error) error {
+	f, err := os.Open(archive)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+
+	gz, err := gzip.NewReader(f)
+	if err != nil {
+		return fmt.Errorf("failed to decompress %s: %w", archive, err)
+	}
+	defer gz.Close()
+
+	tr := tar.NewReader(gz)
+	for {
+		if err := ctx.Err(); err != nil {
+			return err
+		}
+		hdr, err := tr.Next()
+		if errors.Is(err, io.EOF) {
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("failed to read %s: %w", archive, err)
+		}
+		if hdr.Typeflag != tar.TypeReg {
+			continue
+		}
+
+		// Entry names are slash-separated; anything escaping the archive is dropped
+		name := path.Clean(strings.TrimPrefix(hdr.Name, "./"))
+		if name == "." || path.IsAbs(name) || name == ".." || strings.HasPrefix(name, "../") {
+			continue
+		}
+		if err := fn(name, hdr, tr); err != nil {
+			return err
+		}
+	}
+}
+
+// checkArchiveEntry applies the scanner's filename, depth and size filters to an
+// entry, and reports whether a rejected entry was rejected for a binary extension
+func (fs *FileScanner) checkArchiveEntry(name string, size int64) (accept, binary bool) {
+	if beyondMaxDepth(fs.config, name, false) || fs.shouldExcludeFast(name) || !fs.shouldIncludeFast(name) {
+		return false, false
+	}
+	if fs.binaryDetector != nil && fs.binaryDetector.IsBinaryByExtension(name) {
+		return false, true
+	}
+	return size <= int64(fs.config.Index.MaxFileSize), false
+}
+
+// scanArchive sends a task for every text entry of an archive root. Entries carry
+// their content since they have no file on disk of their own; binary and non-UTF-8
+// entries are skipped like binary files in a directory.
+func (fs *FileScanner) scanArchive(ctx context.Context, archive string, taskChan chan<- FileTask, progress *ProgressTracker) error {
+	debug.LogIndexing("Starting archive scan of %s", archive)
+
+	var processedFiles int64
+	err := walkArchive(ctx, archive, func(name string, hdr *tar.Header, tr *tar.Reader) error {
+		accept, binary := fs.checkArchiveEntry(name, hdr.Size)
+		if binary {
+			countSkippedBinary(fs.skippedBinary)
+		}
+		if !accept {
+			return nil
+		}
+		if sampledOut(fs.config, name) {
+			fs.countSampledOut()
+			return nil
+		}
+
+		content, err := io.ReadAll(tr)
+		if err != nil {
+			return fmt.Errorf("failed to read %s from %s: %w", name, archive, err)
+		}
+		text := decodedForDetection(fs.config, content)
+		if fs.binaryDetector != nil && fs.binaryDetector.IsBinaryByMagicNumber(text) {
+			countSkippedBinary(fs.skippedBinary)
+			debug.LogIndexing("Skipping binary archive entry: %s", name)
+			return nil
+		}
+		if !fs.config.Index.TreatAsText && !utf8.Valid(text) {
+			debug.LogIndexing("Skipping non-UTF-8 archive entry: %s", name)
+			return nil
+		}
+
+		virtual := archiveEntryPath(archive, name)
+		task := FileTask{
+			Path:     virtual,
+			Info:     hdr.FileInfo(),
+			Language: fs.languages.Detect(name),
+			Priority: fs.getFilePriority(virtual),
+			Content:  content,
+		}
+		select {
+		case taskChan <- task:
+			processedFiles++
+			progress.IncrementScanned()
+			return nil
+		case <-ctx.Done():
+			return ctx.Err()
+		}
+	})
+	if err != nil {
+		return fmt.Errorf("error scanning archive %s (processed %d entries): %w", archive, processedFiles, err)
+	}
+
+	debug.LogIndexing("Archive scanner: found %d files to process (root: %s)\n", processedFiles, archive)
+	progress.SetTotal(int(processedFiles))
+	return nil
+}
+
+// countArchive is CountFiles for an archive root. Binary content is only detected
+// when entries are scanned, so the count includes binary entries with text names.
+func (fs *FileScanner) countArchive(ctx context.Context, archive string) (fileCount int, totalBytes int64, err error) {
+	err = walkArchive(ctx, archive, func(name string, hdr *tar.Header, _ *tar.Reader) error {
+		if accept, _ := fs.checkArchiveEntry(name, hdr.Size); accept && !sampledOut(fs.config, name) {
+			fileCount++
+			totalBytes += hdr.Size
+		}
+		return nil
+	})
+	return fileCount, totalBytes, err
+}
@@ -0,0 +1,84 @@
+package indexing
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"context"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// writeTarGz writes entries, in name order, to a gzipped tarball at path
+func writeTarGz(t *testing.T, path string, entries map[string][]byte) {
+	t.Helper()
+	f, err := os.Create(path)
+	require.NoError(t, err)
+	defer f.Close()
+	gz := gzip.NewWriter(f)
+	tw := tar.NewWriter(gz)
+
+	names := make([]string, 0, len(entries))
+	for name := range entries {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	require.NoError(t, tw.WriteHeader(&tar.Header{Name: "./src/", Typeflag: tar.TypeDir, Mode: 0755}))
+	for _, name := range names {
+		require.NoError(t, tw.WriteHeader(&tar.Header{Name: name, Typeflag: tar.TypeReg, Mode: 0644, Size: int64(len(entries[name]))}))
+		_, err := tw.Write(entries[name])
+		require.NoError(t, err)
+	}
+	require.NoError(t, tw.Close())
+	require.NoError(t, gz.Close())
+}
+
+func TestIndexDirectory_ArchiveRoot(t *testing.T) {
+	archive := filepath.Join(t.TempDir(), "corpus.tar.gz")
+	writeTarGz(t, archive, map[string][]byte{
+		"./src/main.rs":     []byte("fn main() {\n    let needle = 1;\n}\n"),
+		"docs/notes.md":     []byte("a needle in the docs\n"),
+		"data/blob.txt":     append([]byte("needle"), make([]byte, 64)...),
+		"data/latin1.txt":   []byte("needle caf\xe9\n"),
+		"assets/needle.png": []byte("needle"),
+		"../escape.rs":      []byte("needle"),
+	})
+
+	cfg := &config.Config{
+		Project: config.Project{Root: archive},
+		Index:   config.Index{MaxFileSize: types.DefaultMaxFileSize, MaxFileCount: types.DefaultMaxFileCount},
+		Search:  config.Search{MaxResults: 100},
+	}
+	count, _, err := NewFileScanner(cfg, 0).CountFiles(context.Background(), archive)
+	require.NoError(t, err)
+	assert.Equal(t, 4, count, "names are filtered before entries are read")
+
+	gi := NewMasterIndex(cfg)
+	defer gi.Close()
+	require.NoError(t, gi.IndexDirectory(context.Background(), archive))
+
+	results, err := gi.SearchWithOptions("needle", types.SearchOptions{MaxResults: 100, MaxContextLines: 1})
+	require.NoError(t, err)
+	byPath := map[string]int{}
+	for _, r := range results {
+		byPath[r.Path] = r.Line
+	}
+	assert.Equal(t, map[string]int{
+		archive + "!/src/main.rs":   2,
+		archive + "!/docs/notes.md": 1,
+	}, byPath, "binary, non-UTF-8 and escaping entries are skipped")
+
+	for _, r := range results {
+		if strings.HasSuffix(r.Path, "main.rs") {
+			assert.Contains(t, strings.Join(r.Context.Lines, "\n"), "let needle = 1;", "snippets come from the indexed content")
+		}
+	}
+}
@@ -4,8 +4,15 @@ package indexing
 
 import (
 	"bytes"
+	"errors"
 	"path/filepath"
 	"strings"
+	"sync/atomic"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/encoding"
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 // BinaryDetector handles detection of binary files that should not be indexed
@@ -103,6 +110,36 @@ func NewBinaryDetector() *BinaryDetector {
 	}
 }
 
+// newBinaryDetectorFor returns the binary detector to use under cfg, or nil when
+// cfg.Index.TreatAsText has every file indexed as text
+func newBinaryDetectorFor(cfg *config.Config) *BinaryDetector {
+	if cfg.Index.TreatAsText {
+		return nil
+	}
+	return NewBinaryDetector()
+}
+
+// decodedForDetection returns content as it is indexed under cfg.Index.Encoding, for
+// checking content for binary data before the FileService loads and decodes it: UTF-16
+// text is full of NUL bytes until it is decoded.
+func decodedForDetection(cfg *config.Config, content []byte) []byte {
+	enc, ok := encoding.ResolveTextEncoding(cfg.Index.Encoding, content)
+	if !ok || enc == encoding.UTF8 {
+		return content
+	}
+	if decoded, err := encoding.DecodeText(content, enc); err == nil {
+		return decoded
+	}
+	return content
+}
+
+// countSkippedBinary adds a file skipped as binary to counter, when there is one
+func countSkippedBinary(counter *atomic.Int64) {
+	if counter != nil {
+		counter.Add(1)
+	}
+}
+
 // IsBinaryByExtension checks if a file is binary based on its extension
 func (bd *BinaryDetector) IsBinaryByExtension(path string) bool {
 	ext := strings.ToLower(filepath.Ext(path))
@@ -121,19 +158,16 @@ func (bd *BinaryDetector) IsBinaryByExtension(path string) bool {
 	return exists && isBinary
 }
 
-// IsBinaryByMagicNumber checks if content is binary using magic number detection
-// This is a fast heuristic check for the first 512 bytes
+// IsBinaryByMagicNumber checks if content is binary from its first
+// types.BinaryPreCheckBytes: a known binary signature, or like git any NUL byte.
+// Control characters alone do not make a file binary, so text with escape sequences
+// or form feeds is still indexed.
 func (bd *BinaryDetector) IsBinaryByMagicNumber(content []byte) bool {
 	if len(content) == 0 {
 		return false
 	}
 
-	// Check first 512 bytes (standard for file type detection)
-	checkLen := 512
-	if len(content) < checkLen {
-		checkLen = len(content)
-	}
-	sample := content[:checkLen]
+	sample := content[:min(len(content), types.BinaryPreCheckBytes)]
 
 	// Common binary file signatures (magic numbers)
 	if bytes.HasPrefix(sample, []byte{0x1F, 0x8B}) {
@@ -169,34 +203,8 @@ func (bd *BinaryDetector) IsBinaryByMagicNumber(content []byte) bool {
 		return true // WOFF/WOFF2 fonts
 	}
 
-	// Heuristic: Check for null bytes and high proportion of non-printable characters
-	// Binary files typically have null bytes in first 512 bytes
-	nullBytes := 0
-	nonPrintable := 0
-
-	for _, b := range sample {
-		if b == 0 {
-			nullBytes++
-		}
-		// Count bytes that are not printable ASCII and not common whitespace
-		if b < 0x20 && b != 0x09 && b != 0x0A && b != 0x0D {
-			nonPrintable++
-		}
-		// High bytes (>= 0x80) might be UTF-8 or binary
-		// Don't count them as non-printable to avoid false positives on UTF-8 text
-	}
-
-	// If more than 1% null bytes, very likely binary
-	if nullBytes > len(sample)/100 {
-		return true
-	}
-
-	// If more than 30% non-printable characters (excluding UTF-8), likely binary
-	if nonPrintable > len(sample)*30/100 {
-		return true
-	}
-
-	return false
+	// Text never contains NUL; like git, this also skips UTF-16 text
+	return bytes.IndexByte(sample, 0) >= 0
 }
 
 // IsBinary combines extension and magic number checks for robust detection
@@ -213,3 +221,22 @@ func (bd *BinaryDetector) IsBinary(path string, content []byte) bool {
 
 	return false
 }
+
+// Errors checkText rejects content with, so a file is skipped before it is stored
+var (
+	errBinaryContent = errors.New("binary file detected by magic number")
+	errInvalidUTF8   = errors.New("invalid UTF-8")
+)
+
+// checkText rejects content that is not indexed as text: content with a binary magic
+// number when detector is set and, unless treatAsText, content that is not valid UTF-8.
+// The whole file is validated, so a bad byte deep in a large file still skips it.
+func checkText(detector *BinaryDetector, treatAsText bool, content []byte) error {
+	if detector != nil && detector.IsBinaryByMagicNumber(content) {
+		return errBinaryContent
+	}
+	if !treatAsText && !utf8.Valid(content) {
+		return errInvalidUTF8
+	}
+	return nil
+}
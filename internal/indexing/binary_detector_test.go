@@ -1,7 +1,10 @@
 package indexing
 
 import (
+	"bytes"
 	"testing"
+
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 func TestBinaryDetector_IsBinaryByExtension(t *testing.T) {
@@ -130,6 +133,21 @@ func TestBinaryDetector_IsBinaryByMagicNumber(t *testing.T) {
 			content: []byte{},
 			binary:  false,
 		},
+		{
+			name:    "UTF-8 text with control characters",
+			content: []byte("\x1b[31mred\x1b[0m\f\a\x01\x02\x03\x04\x05\x06\x0e\x0f\x10 naïve\n"),
+			binary:  false,
+		},
+		{
+			name:    "NUL after the first 512 bytes",
+			content: append(bytes.Repeat([]byte("a"), 4096), 0x00),
+			binary:  true,
+		},
+		{
+			name:    "NUL past the sniffed prefix",
+			content: append(bytes.Repeat([]byte("a"), types.BinaryPreCheckBytes), 0x00),
+			binary:  false,
+		},
 		{
 			name: "Binary with null bytes",
 			content: []byte{
@@ -188,3 +206,31 @@ func TestBinaryDetector_IsBinary(t *testing.T) {
 		})
 	}
 }
+
+func TestCheckText(t *testing.T) {
+	detector := NewBinaryDetector()
+	invalidLate := append(bytes.Repeat([]byte("a"), 64*1024+10), 0xff)
+
+	tests := []struct {
+		name        string
+		content     []byte
+		treatAsText bool
+		want        error
+	}{
+		{"Empty", nil, false, nil},
+		{"ASCII", []byte("package main\n"), false, nil},
+		{"Multibyte", []byte("let größe = \"😀\";\n"), false, nil},
+		{"Latin-1", []byte("caf\xe9\n"), false, errInvalidUTF8},
+		{"Invalid deep in a large file", invalidLate, false, errInvalidUTF8},
+		{"Latin-1 treated as text", []byte("caf\xe9\n"), true, nil},
+		{"ELF binary", []byte("\x7fELF\x02\x01\x01\x00"), true, errBinaryContent},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := checkText(detector, tt.treatAsText, tt.content); got != tt.want {
+				t.Errorf("checkText() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
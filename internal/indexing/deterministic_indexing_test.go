@@ -0,0 +1,57 @@
+package indexing
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// TestIndexDirectory_DeterministicAcrossWorkerCounts indexes the same tree with one and
+// with many workers and expects identical FileIDs and search result order.
+func TestIndexDirectory_DeterministicAcrossWorkerCounts(t *testing.T) {
+	testDir := t.TempDir()
+	for i := 0; i < 40; i++ {
+		dir := filepath.Join(testDir, fmt.Sprintf("pkg%d", i%5))
+		require.NoError(t, os.MkdirAll(dir, 0755))
+		content := fmt.Sprintf("package pkg\n\n// SharedHelper%d uses sharedToken\nfunc SharedHelper%d() string {\n\treturn \"sharedToken\"\n}\n", i, i)
+		require.NoError(t, os.WriteFile(filepath.Join(dir, fmt.Sprintf("file%02d.go", i)), []byte(content), 0644))
+	}
+
+	type snapshot struct {
+		files   map[string]string
+		results []string
+	}
+	index := func(workers int) snapshot {
+		cfg := createProductionFlowTestConfig(testDir)
+		cfg.Performance.ParallelFileWorkers = workers
+
+		indexer := NewMasterIndex(cfg)
+		defer indexer.Close()
+		require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+
+		files := make(map[string]string)
+		for _, fileID := range indexer.GetAllFileIDs() {
+			files[fmt.Sprint(fileID)] = indexer.GetFilePath(fileID)
+		}
+		var results []string
+		for _, r := range searchAndWait(t, indexer, "sharedToken") {
+			results = append(results, fmt.Sprintf("%s:%d", r.Path, r.Line))
+		}
+		return snapshot{files: files, results: results}
+	}
+
+	serial := index(1)
+	require.Len(t, serial.files, 40)
+	require.NotEmpty(t, serial.results)
+
+	for run := 0; run < 3; run++ {
+		parallel := index(8)
+		assert.Equal(t, serial.files, parallel.files, "FileIDs must follow scan order")
+		assert.Equal(t, serial.results, parallel.results, "result order must not depend on worker count")
+	}
+}
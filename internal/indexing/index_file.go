@@ -0,0 +1,296 @@
+package indexing
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/binary"
+	"encoding/gob"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sync"
+
+	"github.com/standardbeagle/lci/internal/debug"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/internal/version"
+
+	"github.com/cespare/xxhash/v2"
+)
+
+// Index files persist the parser output of every indexed file together with a hash
+// of the file's content, so repeated invocations on the same tree skip tree-sitter.
+//
+// Layout: the 8-byte magic, a big-endian uint32 format version, then a gob stream
+// holding an indexFileHeader followed by the entries keyed by path.
+//
+// An entry is reused while the file at its path has the same content and language,
+// whatever its mtime or the FileID it is given this run; anything else is flagged stale
+// and parsed again. Trigrams, term statistics and the other content-derived indexes are always
+// rebuilt from the file content.
+
+const (
+	indexFileMagic   = "LCIINDEX"
+	indexFileVersion = uint32(4)
+)
+
+// ErrIncompatibleIndexFile is returned when an index file is not an lci index, is
+// corrupt, or was written by a different lci version or format
+var ErrIncompatibleIndexFile = errors.New("incompatible index file")
+
+type indexFileHeader struct {
+	LCIVersion string
+}
+
+// indexFileEntry is one file's saved parse. Parse holds the gob-encoded parsedFile,
+// captured before integration so later index mutations never leak into it.
+type indexFileEntry struct {
+	ContentHash uint64 // xxhash of the content the parse was made from
+	Language    string // Language the content was parsed as, which --lang can change
+	Parse       []byte
+}
+
+// parsedFile is the parser output that an index file lets indexing skip
+type parsedFile struct {
+	Symbols         []types.Symbol
+	EnhancedSymbols []types.EnhancedSymbol
+	References      []types.Reference
+	Scopes          []types.ScopeInfo
+	PerfData        []types.FunctionPerfData
+	SideEffects     map[string]*types.SideEffectInfo
+	SyntaxError     string // Parse warning, see parser.SyntaxError
+}
+
+// setFileID stamps fileID on everything in p that names its file, for a parse saved
+// under the FileID of an earlier run
+func (p *parsedFile) setFileID(fileID types.FileID) {
+	for i := range p.Symbols {
+		p.Symbols[i].FileID = fileID
+	}
+	for i := range p.EnhancedSymbols {
+		sym := &p.EnhancedSymbols[i]
+		sym.FileID = fileID
+		setReferencesFileID(sym.IncomingRefs, fileID)
+		setReferencesFileID(sym.OutgoingRefs, fileID)
+	}
+	setReferencesFileID(p.References, fileID)
+}
+
+func setReferencesFileID(refs []types.Reference, fileID types.FileID) {
+	for i := range refs {
+		refs[i].FileID = fileID
+		for j := range refs[i].ContextLines {
+			refs[i].ContextLines[j].FileID = fileID
+		}
+	}
+}
+
+// indexFileCache holds the entries loaded from an index file and records the
+// entries of the current run. It is shared by all processor workers.
+type indexFileCache struct {
+	mu      sync.Mutex
+	loaded  map[string]indexFileEntry
+	current map[string]indexFileEntry
+	reused  int
+	stale   int
+}
+
+func newIndexFileCache(loaded map[string]indexFileEntry) *indexFileCache {
+	if loaded == nil {
+		loaded = make(map[string]indexFileEntry)
+	}
+	return &indexFileCache{
+		loaded:  loaded,
+		current: make(map[string]indexFileEntry, len(loaded)),
+	}
+}
+
+// lookup returns the saved parse for task if content is what it was parsed from, as
+// task's language, stamped with fileID
+func (c *indexFileCache) lookup(task FileTask, fileID types.FileID, content []byte) (parsedFile, bool) {
+	c.mu.Lock()
+	entry, ok := c.loaded[task.Path]
+	if ok && (entry.ContentHash != xxhash.Sum64(content) || entry.Language != task.Language) {
+		c.stale++
+		ok = false
+	}
+	c.mu.Unlock()
+	if !ok {
+		return parsedFile{}, false
+	}
+
+	var parsed parsedFile
+	if err := gob.NewDecoder(bytes.NewReader(entry.Parse)).Decode(&parsed); err != nil {
+		debug.LogIndexing("Warning: discarding saved parse of %s: %v\n", task.Path, err)
+		return parsedFile{}, false
+	}
+	parsed.setFileID(fileID)
+
+	c.mu.Lock()
+	c.reused++
+	c.current[task.Path] = entry
+	c.mu.Unlock()
+	return parsed, true
+}
+
+// store records a fresh parse of task's content for the next save
+func (c *indexFileCache) store(task FileTask, content []byte, parsed parsedFile) {
+	var buf bytes.Buffer
+	if err := gob.NewEncoder(&buf).Encode(&parsed); err != nil {
+		debug.LogIndexing("Warning: not saving parse of %s: %v\n", task.Path, err)
+		return
+	}
+
+	entry := indexFileEntry{
+		ContentHash: xxhash.Sum64(content),
+		Language:    task.Language,
+		Parse:       buf.Bytes(),
+	}
+	c.mu.Lock()
+	c.current[task.Path] = entry
+	c.mu.Unlock()
+}
+
+// LoadIndex reads an index file saved by SaveIndex. The next IndexDirectory reuses
+// its entries for files that have not changed and re-indexes the rest.
+func (mi *MasterIndex) LoadIndex(path string) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+
+	entries, err := readIndexFile(bufio.NewReader(f))
+	if err != nil {
+		return fmt.Errorf("%s: %w", path, err)
+	}
+	mi.savedIndex = newIndexFileCache(entries)
+	return nil
+}
+
+// SaveIndex writes the parse results of the last IndexDirectory to path. The file is
+// replaced atomically, so a concurrent LoadIndex never sees a partial write.
+func (mi *MasterIndex) SaveIndex(path string) error {
+	if mi.savedIndex == nil {
+		return errors.New("no parse results to save: set an index file or call LoadIndex before IndexDirectory")
+	}
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return err
+	}
+	tmp, err := os.CreateTemp(filepath.Dir(path), filepath.Base(path)+".tmp*")
+	if err != nil {
+		return err
+	}
+	defer os.Remove(tmp.Name())
+
+	mi.savedIndex.mu.Lock()
+	w := bufio.NewWriter(tmp)
+	err = writeIndexFile(w, mi.savedIndex.current)
+	mi.savedIndex.mu.Unlock()
+	if err == nil {
+		err = w.Flush()
+	}
+	if closeErr := tmp.Close(); err == nil {
+		err = closeErr
+	}
+	if err != nil {
+		return fmt.Errorf("failed to write index file %s: %w", path, err)
+	}
+	return os.Rename(tmp.Name(), path)
+}
+
+func writeIndexFile(w io.Writer, entries map[string]indexFileEntry) error {
+	if _, err := io.WriteString(w, indexFileMagic); err != nil {
+		return err
+	}
+	if err := binary.Write(w, binary.BigEndian, indexFileVersion); err != nil {
+		return err
+	}
+	enc := gob.NewEncoder(w)
+	if err := enc.Encode(indexFileHeader{LCIVersion: version.Version}); err != nil {
+		return err
+	}
+	return enc.Encode(entries)
+}
+
+func readIndexFile(r io.Reader) (map[string]indexFileEntry, error) {
+	dec, err := readIndexFileHeader(r)
+	if err != nil {
+		return nil, err
+	}
+	var entries map[string]indexFileEntry
+	if err := dec.Decode(&entries); err != nil {
+		return nil, fmt.Errorf("%w: corrupt entries: %v", ErrIncompatibleIndexFile, err)
+	}
+	return entries, nil
+}
+
+// readIndexFileHeader checks the magic, format and lci version at the start of an index
+// file, returning the decoder positioned at its entries
+func readIndexFileHeader(r io.Reader) (*gob.Decoder, error) {
+	magic := make([]byte, len(indexFileMagic))
+	if _, err := io.ReadFull(r, magic); err != nil || string(magic) != indexFileMagic {
+		return nil, fmt.Errorf("%w: not an lci index file", ErrIncompatibleIndexFile)
+	}
+	var format uint32
+	if err := binary.Read(r, binary.BigEndian, &format); err != nil {
+		return nil, fmt.Errorf("%w: truncated header", ErrIncompatibleIndexFile)
+	}
+	if format != indexFileVersion {
+		return nil, fmt.Errorf("%w: format %d, this lci reads format %d", ErrIncompatibleIndexFile, format, indexFileVersion)
+	}
+
+	dec := gob.NewDecoder(r)
+	var header indexFileHeader
+	if err := dec.Decode(&header); err != nil {
+		return nil, fmt.Errorf("%w: corrupt header: %v", ErrIncompatibleIndexFile, err)
+	}
+	if header.LCIVersion != version.Version {
+		return nil, fmt.Errorf("%w: written by lci %s, this is lci %s", ErrIncompatibleIndexFile, header.LCIVersion, version.Version)
+	}
+	return dec, nil
+}
+
+// CheckIndexFile reports whether the index file at path can be reused by this lci,
+// reading only its header. The error wraps ErrIncompatibleIndexFile when it was written
+// by another lci version or format, and os.ErrNotExist when there is no file.
+func CheckIndexFile(path string) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	if _, err := readIndexFileHeader(bufio.NewReader(f)); err != nil {
+		return fmt.Errorf("%s: %w", path, err)
+	}
+	return nil
+}
+
+// prepareIndexFile loads the configured index file before indexing. A missing or
+// incompatible file only costs a full re-index.
+func (mi *MasterIndex) prepareIndexFile() {
+	path := mi.config.Index.IndexFile
+	if path == "" {
+		return
+	}
+	if err := mi.LoadIndex(path); err != nil {
+		if !errors.Is(err, os.ErrNotExist) {
+			debug.LogIndexing("Warning: ignoring index file, re-indexing everything: %v\n", err)
+		}
+		mi.savedIndex = newIndexFileCache(nil)
+	}
+}
+
+// finishIndexFile saves the configured index file after a completed IndexDirectory
+func (mi *MasterIndex) finishIndexFile() {
+	path := mi.config.Index.IndexFile
+	if path == "" || mi.savedIndex == nil {
+		return
+	}
+	debug.LogIndexing("Index file: reused %d parsed files, re-indexed %d stale files\n",
+		mi.savedIndex.reused, mi.savedIndex.stale)
+	if err := mi.SaveIndex(path); err != nil {
+		debug.LogIndexing("Warning: failed to save index file: %v\n", err)
+	}
+}
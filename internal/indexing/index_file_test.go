@@ -0,0 +1,124 @@
+package indexing
+
+import (
+	"bytes"
+	"context"
+	"encoding/binary"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestIndexFile_ReusesUnchangedAndReindexesStale(t *testing.T) {
+	testDir := t.TempDir()
+	indexPath := filepath.Join(t.TempDir(), "lci.index")
+	alpha := filepath.Join(testDir, "alpha.go")
+	require.NoError(t, os.WriteFile(alpha, []byte("package test\n\nfunc AlphaBefore() {}\n"), 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "beta.go"), []byte("package test\n\nfunc BetaStable() {}\n"), 0644))
+
+	index := func() *MasterIndex {
+		cfg := createProductionFlowTestConfig(testDir)
+		cfg.Index.IndexFile = indexPath
+		indexer := NewMasterIndex(cfg)
+		t.Cleanup(func() { indexer.Close() })
+		require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+		return indexer
+	}
+
+	first := index()
+	assert.Equal(t, 0, first.savedIndex.reused)
+	require.FileExists(t, indexPath)
+
+	second := index()
+	assert.Equal(t, 2, second.savedIndex.reused, "unchanged files come from the index file")
+	assert.Equal(t, 0, second.savedIndex.stale)
+	assertResultContainsPath(t, searchAndWait(t, second, "BetaStable"), "beta.go")
+	assert.NotEmpty(t, second.GetFileSymbols(second.fileSnapshot.Load().fileMap[alpha]), "reused parse restores symbols")
+
+	// A new mtime alone does not invalidate the saved parse
+	future := time.Now().Add(time.Hour)
+	require.NoError(t, os.Chtimes(alpha, future, future))
+	touched := index()
+	assert.Equal(t, 2, touched.savedIndex.reused, "unchanged content is reused whatever its mtime")
+	alphaID := touched.fileSnapshot.Load().fileMap[alpha]
+	for _, sym := range touched.GetFileSymbols(alphaID) {
+		assert.Equal(t, alphaID, sym.FileID, "reused symbols carry this run's FileID")
+	}
+
+	// Same size, new content: the saved parse must not be trusted
+	require.NoError(t, os.WriteFile(alpha, []byte("package test\n\nfunc AlphaUpdate() {}\n"), 0644))
+	require.NoError(t, os.Chtimes(alpha, future, future))
+
+	third := index()
+	assert.Equal(t, 1, third.savedIndex.reused)
+	assert.Equal(t, 1, third.savedIndex.stale)
+	assertResultContainsPath(t, searchAndWait(t, third, "AlphaUpdate"), "alpha.go")
+	assertResultNotContainsPath(t, searchAndWait(t, third, "AlphaBefore"), "alpha.go", "stale parse must be replaced")
+}
+
+func TestIndexFile_ReindexesOnLanguageChange(t *testing.T) {
+	testDir := t.TempDir()
+	indexPath := filepath.Join(t.TempDir(), "lci.index")
+	widget := filepath.Join(testDir, "widget.tpl")
+	require.NoError(t, os.WriteFile(widget, []byte("package test\n\nfunc WidgetTemplate() {}\n"), 0644))
+
+	index := func(languages map[string]string) *MasterIndex {
+		cfg := createProductionFlowTestConfig(testDir)
+		cfg.Include = []string{"*.tpl"}
+		cfg.Index.IndexFile = indexPath
+		cfg.Index.Languages = languages
+		indexer := NewMasterIndex(cfg)
+		t.Cleanup(func() { indexer.Close() })
+		require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+		return indexer
+	}
+	symbols := func(indexer *MasterIndex) int {
+		return len(indexer.GetFileSymbols(indexer.fileSnapshot.Load().fileMap[widget]))
+	}
+
+	text := index(nil)
+	assert.Zero(t, symbols(text), "without an override the file is plain text")
+
+	asGo := index(map[string]string{".tpl": "go"})
+	assert.Equal(t, 0, asGo.savedIndex.reused, "a parse as plain text is not reused for go")
+	assert.Equal(t, 1, asGo.savedIndex.stale)
+	assert.NotZero(t, symbols(asGo), "the file is parsed again as go")
+
+	asGoAgain := index(map[string]string{".tpl": "go"})
+	assert.Equal(t, 1, asGoAgain.savedIndex.reused)
+	assert.NotZero(t, symbols(asGoAgain))
+
+	textAgain := index(nil)
+	assert.Equal(t, 0, textAgain.savedIndex.reused, "a parse as go is not reused for plain text")
+	assert.Equal(t, 1, textAgain.savedIndex.stale)
+	assert.Zero(t, symbols(textAgain))
+}
+
+func TestLoadIndex_Incompatible(t *testing.T) {
+	dir := t.TempDir()
+	mi := &MasterIndex{}
+
+	garbage := filepath.Join(dir, "garbage.index")
+	require.NoError(t, os.WriteFile(garbage, []byte("definitely not an index"), 0644))
+	assert.ErrorIs(t, mi.LoadIndex(garbage), ErrIncompatibleIndexFile)
+
+	var future bytes.Buffer
+	future.WriteString(indexFileMagic)
+	require.NoError(t, binary.Write(&future, binary.BigEndian, indexFileVersion+1))
+	newer := filepath.Join(dir, "newer.index")
+	require.NoError(t, os.WriteFile(newer, future.Bytes(), 0644))
+	err := mi.LoadIndex(newer)
+	assert.ErrorIs(t, err, ErrIncompatibleIndexFile)
+	assert.Contains(t, err.Error(), "format 4")
+
+	assert.ErrorIs(t, mi.LoadIndex(filepath.Join(dir, "missing.index")), os.ErrNotExist)
+	assert.Nil(t, mi.savedIndex, "a failed load leaves no saved parses behind")
+
+	assert.ErrorIs(t, CheckIndexFile(garbage), ErrIncompatibleIndexFile)
+	assert.ErrorIs(t, CheckIndexFile(newer), ErrIncompatibleIndexFile)
+	assert.ErrorIs(t, CheckIndexFile(filepath.Join(dir, "missing.index")), os.ErrNotExist)
+}
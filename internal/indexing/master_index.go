@@ -9,16 +9,19 @@ import (
 	"os"
 	"path/filepath"
 	"runtime"
+	"slices"
 	"strings"
 	"sync"
 	"sync/atomic"
 	"time"
+	"unicode/utf8"
 
 	"github.com/standardbeagle/lci/internal/analysis"
 	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/core"
 	"github.com/standardbeagle/lci/internal/debug"
 	"github.com/standardbeagle/lci/internal/interfaces"
+	"github.com/standardbeagle/lci/internal/lang"
 	"github.com/standardbeagle/lci/internal/parser"
 	"github.com/standardbeagle/lci/internal/search"
 	"github.com/standardbeagle/lci/internal/types"
@@ -61,7 +64,8 @@ type MasterIndex struct {
 	fileContentStore     *core.FileContentStore     // Centralized content management
 	fileService          *core.FileService          // Centralized file operations
 	fileSearchEngine     *core.FileSearchEngine     // File path search with glob patterns
-	postingsIndex        *core.PostingsIndex        // Minimal content postings for first-hit-per-file
+	postingsIndex        *core.PostingsIndex        // Token -> file and offset postings for whole-word search
+	termStats            *core.TermStatsIndex       // Per-file term frequencies for BM25 ranking
 	componentDetector    *core.ComponentDetector    // Semantic component detection
 	patternVerifier      *core.PatternVerifier      // Pattern verification and compliance checking
 	intentAnalyzer       *core.IntentAnalyzer       // Code intent analysis and semantic understanding
@@ -81,6 +85,12 @@ type MasterIndex struct {
 	fileWatcher        *FileWatcher
 	rebuilder          *DebouncedRebuilder
 	deletedFileTracker *DeletedFileTracker // Tracks deleted files for filtering stale index entries
+	savedIndex         *indexFileCache     // Parses loaded from and saved to the index file
+	parseWarnings      *parseWarnings      // Files whose parse recovered from syntax errors
+	languages          *lang.Overrides     // Extension overrides from config.Index.Languages
+	skippedBinary      atomic.Int64        // Files the last IndexDirectory skipped as binary
+	sampledOut         atomic.Int64        // Files the last IndexDirectory left out by config.Index.Sample
+	generation         atomic.Uint64       // Bumped before and after every index mutation
 
 	// Index coordinator integration
 	coordinator core.IndexCoordinator
@@ -107,6 +117,15 @@ type MasterIndex struct {
 	snapshotMu sync.Mutex // Lightweight lock for atomic snapshot updates (IndexFile, UpdateFile, RemoveFile)
 	bulkMu     sync.Mutex // Heavy lock for bulk operations (IndexDirectory, Clear)
 
+	// Change detection for SyncFile - mtime+size recorded per path at last sync
+	fileStamps map[string]fileStamp
+	stampMu    sync.Mutex
+
+	// Watch mode batch reporting
+	watchBatch        WatchBatchSummary
+	watchBatchHandler func(WatchBatchSummary)
+	watchBatchMu      sync.Mutex
+
 	// Performance metrics
 	searchCount     int64 // atomic
 	totalSearchTime int64 // atomic nanoseconds
@@ -146,6 +165,7 @@ func NewMasterIndex(cfg *config.Config) *MasterIndex {
 		fileContentStore:    core.NewFileContentStoreWithLimit(int64(cfg.Performance.MaxMemoryMB * 1024 * 1024)),
 		fileSearchEngine:    core.NewFileSearchEngine(),
 		postingsIndex:       core.NewPostingsIndex(),
+		termStats:           core.NewTermStatsIndexWithTokenizer(core.TermTokenizer(cfg.Index.Tokenize)),
 		componentDetector:   core.NewComponentDetector(),
 		patternVerifier:     core.NewPatternVerifier(),
 		intentAnalyzer:      core.NewIntentAnalyzer(),
@@ -167,8 +187,10 @@ func NewMasterIndex(cfg *config.Config) *MasterIndex {
 	// Note: Enforcing MaxFileSize at the FileService layer provides a second safety net
 	// for all code paths (including watch events) that may attempt to load file content.
 	mi.fileService = core.NewFileServiceWithOptions(core.FileServiceOptions{
-		ContentStore:     mi.fileContentStore,
-		MaxFileSizeBytes: mi.config.Index.MaxFileSize,
+		ContentStore:       mi.fileContentStore,
+		MaxFileSizeBytes:   mi.config.Index.MaxFileSize,
+		MmapThresholdBytes: mi.config.Index.MmapThreshold,
+		TextEncoding:       mi.config.Index.Encoding,
 	})
 
 	// Initialize metrics calculator with duplicate detector for complexity calculation
@@ -195,6 +217,14 @@ func NewMasterIndex(cfg *config.Config) *MasterIndex {
 	// Search engine should be created externally to avoid circular dependency
 	_ = contextLines // Mark as used even though search engine creation is disabled
 
+	// Configured language overrides apply to this index only
+	mi.languages = lang.NewOverrides()
+	for ext, language := range cfg.Index.Languages {
+		if err := mi.languages.Set(ext, language); err != nil {
+			debug.LogIndexing("Warning: ignoring language override %s: %v\n", ext, err)
+		}
+	}
+
 	// Initialize pipeline components with validated configuration
 	numWorkers := cfg.Performance.ParallelFileWorkers
 	if numWorkers <= 0 {
@@ -204,12 +234,21 @@ func NewMasterIndex(cfg *config.Config) *MasterIndex {
 	mi.fileScanner = NewFileScanner(cfg, numWorkers*2)
 	mi.fileProcessor = NewFileProcessorWithService(cfg, mi.fileService)
 	mi.fileProcessor.SetTrigramIndex(mi.trigramIndex) // Enable bucketed trigram extraction
+	mi.parseWarnings = newParseWarnings()
+	mi.fileProcessor.SetParseWarnings(mi.parseWarnings)
+	mi.fileScanner.SetSkippedBinary(&mi.skippedBinary)
+	mi.fileScanner.SetSampledOut(&mi.sampledOut)
+	mi.fileProcessor.SetSkippedBinary(&mi.skippedBinary)
+	mi.fileProcessor.SetLanguages(mi.languages)
+	mi.fileScanner.SetLanguages(mi.languages)
 	// Initialize with nil maps - will be properly set during IndexDirectory
 	mi.fileIntegrator = NewFileIntegratorWithMap(mi.trigramIndex, mi.symbolIndex, mi.refTracker, mi.symbolLocationIndex, nil, nil, nil)
 	mi.fileIntegrator.SetScopeStore(mi)                              // Set MasterIndex as the scope store
 	mi.fileIntegrator.SetFileContentStore(mi.fileContentStore)       // Set FileContentStore for FileID generation
 	mi.fileIntegrator.SetFileSearchEngine(mi.fileSearchEngine)       // Set FileSearchEngine for file path indexing
 	mi.fileIntegrator.SetSemanticSearchIndex(mi.semanticSearchIndex) // Set SemanticSearchIndex for pre-computed semantic optimizations
+	mi.fileIntegrator.SetTermStatsIndex(mi.termStats)                // Set TermStatsIndex for BM25 ranking statistics
+	mi.fileIntegrator.SetPostingsIndex(mi.postingsIndex)             // Set PostingsIndex for whole-word search
 	// Note: Universal Symbol Graph removed (no longer supported)
 	// Set config for feature flags
 	mi.fileIntegrator.SetConfig(mi.config)
@@ -324,6 +363,8 @@ func (mi *MasterIndex) ListFilesTo(ctx context.Context, root string, verbose boo
 // @lci:labels[indexing,directory-scan,pipeline,entry-point]
 // @lci:category[indexing]
 func (mi *MasterIndex) IndexDirectory(ctx context.Context, root string) error {
+	defer mi.bumpGeneration()()
+
 	start := time.Now()
 	defer func() {
 		debug.LogIndexing("IndexDirectory completed in %v\n", time.Since(start))
@@ -372,11 +413,18 @@ func (mi *MasterIndex) IndexDirectory(ctx context.Context, root string) error {
 		atomic.StoreInt64(&mi.indexingTime, duration.Nanoseconds())
 	}()
 
+	mi.prepareIndexFile()
+
 	// Clear existing data
 	mi.trigramIndex.Clear()
+	mi.termStats.Clear()
+	mi.postingsIndex.Clear()
 	mi.symbolIndex = core.NewSymbolIndex()
 	mi.refTracker.Clear()
 	mi.fileSearchEngine.Clear() // Clear file search engine path index
+	mi.parseWarnings.reset()
+	mi.skippedBinary.Store(0)
+	mi.sampledOut.Store(0)
 	atomic.StoreInt64(&mi.processedFiles, 0)
 	atomic.StoreInt64(&mi.totalFiles, 0) // Reset totalFiles counter for consistency
 
@@ -413,6 +461,8 @@ func (mi *MasterIndex) IndexDirectory(ctx context.Context, root string) error {
 	mi.fileIntegrator.SetFileSearchEngine(mi.fileSearchEngine)         // Set FileSearchEngine for file path indexing
 	mi.fileIntegrator.SetSemanticSearchIndex(mi.semanticSearchIndex)   // Set SemanticSearchIndex for pre-computed semantic optimizations
 	mi.fileIntegrator.SetSideEffectPropagator(mi.sideEffectPropagator) // Set SideEffectPropagator for function purity analysis
+	mi.fileIntegrator.SetTermStatsIndex(mi.termStats)                  // Set TermStatsIndex for BM25 ranking statistics
+	mi.fileIntegrator.SetPostingsIndex(mi.postingsIndex)               // Set PostingsIndex for whole-word search
 	// Note: Universal Symbol Graph removed (no longer supported)
 	// Set config for feature flags
 	mi.fileIntegrator.SetConfig(mi.config)
@@ -492,12 +542,17 @@ func (mi *MasterIndex) IndexDirectory(ctx context.Context, root string) error {
 	mi.workingSnapshot = nil // Clear working snapshot reference
 	debug.LogIndexing("File snapshot updated with %d files\n", len(workingSnapshot.reverseFileMap))
 
+	if ctx.Err() == nil {
+		mi.finishIndexFile()
+	}
+
 	// Note: Universal Symbol Graph is populated during symbol extraction
 	// This happens incrementally as files are processed in pipeline_integrator
 	// This is more efficient than running a separate analysis pass
 
-	// Start file watching after initial indexing
-	if mi.fileWatcher != nil && mi.config.Index.WatchMode {
+	// Start file watching after initial indexing. An archive root is a snapshot with
+	// nothing to watch.
+	if mi.fileWatcher != nil && mi.config.Index.WatchMode && !isArchiveRoot(root) {
 		if err := mi.startWatching(root); err != nil {
 			debug.LogIndexing("Warning: failed to start file watching: %v\n", err)
 		}
@@ -519,9 +574,27 @@ func (mi *MasterIndex) runFileScanner(ctx context.Context, root string, taskChan
 	defer mi.wg.Done()
 	defer close(taskChan) // Scanner closes the task channel
 
-	err := mi.fileScanner.ScanDirectory(ctx, root, taskChan, mi.progressTracker)
-	if err != nil {
-		debug.LogIndexing("File scanner error: %v\n", err)
+	scanned := make(chan FileTask, cap(taskChan))
+	go func() {
+		defer close(scanned)
+		err := mi.fileScanner.ScanDirectory(ctx, root, scanned, mi.progressTracker)
+		if err != nil {
+			debug.LogIndexing("File scanner error: %v\n", err)
+		}
+	}()
+
+	// The walk is single-threaded and lexical, so numbering tasks and reserving
+	// FileIDs here makes both independent of how many workers process them
+	var seq uint64
+	for task := range scanned {
+		seq++
+		task.Seq = seq
+		mi.fileService.ReserveFileID(task.Path)
+		select {
+		case taskChan <- task:
+		case <-ctx.Done():
+			// Keep draining so the scanner can observe cancellation and exit
+		}
 	}
 }
 
@@ -548,6 +621,10 @@ func (mi *MasterIndex) runFileProcessor(ctx context.Context, taskChan <-chan Fil
 			// This ensures all workers use the same FileContentStore and consistent FileIDs
 			workerProcessor := NewFileProcessorWithService(mi.config, mi.fileService)
 			workerProcessor.SetTrigramIndex(mi.trigramIndex) // Enable bucketed trigram extraction
+			workerProcessor.SetIndexFileCache(mi.savedIndex) // Reuse unchanged parses from the index file
+			workerProcessor.SetParseWarnings(mi.parseWarnings)
+			workerProcessor.SetSkippedBinary(&mi.skippedBinary)
+			workerProcessor.SetLanguages(mi.languages)
 			defer func() {
 				// Ensure parser is returned to pool even if worker panics
 				if r := recover(); r != nil {
@@ -573,7 +650,7 @@ func (mi *MasterIndex) runFileProcessor(ctx context.Context, taskChan <-chan Fil
 func (mi *MasterIndex) runFileIntegrator(ctx context.Context, resultChan <-chan ProcessedFile) {
 	defer mi.wg.Done()
 
-	mi.fileIntegrator.IntegrateFiles(ctx, resultChan, mi.progressTracker)
+	mi.fileIntegrator.IntegrateFiles(ctx, orderResults(ctx, resultChan), mi.progressTracker)
 }
 
 // File watching methods
@@ -614,37 +691,7 @@ func (mi *MasterIndex) stopWatching() error {
 // @lci:category[indexing]
 func (mi *MasterIndex) handleFileChanged(path string, eventType FileEventType) {
 	debug.LogIndexing("File changed: %s (type: %d)\n", path, eventType)
-
-	// Check file size first through FileService
-	if !mi.fileService.Exists(path) {
-		debug.LogIndexing("Warning: file does not exist %s\n", path)
-		return
-	}
-
-	fileSize, err := mi.fileService.GetFileSize(path)
-	if err != nil {
-		debug.LogIndexing("Warning: failed to get file size %s: %v\n", path, err)
-		return
-	}
-
-	// Skip files that exceed size limit
-	if fileSize > int64(mi.config.Index.MaxFileSize) {
-		debug.LogIndexing("Skipping oversized file %s (%d bytes > %d limit)\n",
-			path, fileSize, mi.config.Index.MaxFileSize)
-		return
-	}
-
-	// Read the file content directly (don't use LoadFile - UpdateFile will handle FileID creation)
-	content, err := mi.fileService.ReadFile(path)
-	if err != nil {
-		debug.LogIndexing("Warning: failed to read changed file %s: %v\n", path, err)
-		return
-	}
-
-	// Update the file in the index
-	if err := mi.UpdateFile(path, content); err != nil {
-		debug.LogIndexing("Warning: failed to update file %s in index: %v\n", path, err)
-	}
+	mi.syncWatchedFile(path)
 }
 
 // handleFileCreated handles file creation events
@@ -652,25 +699,7 @@ func (mi *MasterIndex) handleFileChanged(path string, eventType FileEventType) {
 // @lci:category[indexing]
 func (mi *MasterIndex) handleFileCreated(path string) {
 	debug.LogIndexing("File created: %s\n", path)
-
-	// Enforce size limit before attempting to index
-	if !mi.fileService.Exists(path) {
-		debug.LogIndexing("Warning: file does not exist %s\n", path)
-		return
-	}
-	fileSize, err := mi.fileService.GetFileSize(path)
-	if err != nil {
-		debug.LogIndexing("Warning: failed to get file size %s: %v\n", path, err)
-		return
-	}
-	if fileSize > int64(mi.config.Index.MaxFileSize) {
-		debug.LogIndexing("Skipping oversized file %s (%d bytes > %d limit)\n", path, fileSize, mi.config.Index.MaxFileSize)
-		return
-	}
-
-	if err := mi.IndexFile(path); err != nil {
-		debug.LogIndexing("Warning: failed to index new file %s: %v\n", path, err)
-	}
+	mi.syncWatchedFile(path)
 }
 
 // handleFileRemoved handles file deletion events
@@ -678,25 +707,45 @@ func (mi *MasterIndex) handleFileCreated(path string) {
 // @lci:category[indexing]
 func (mi *MasterIndex) handleFileRemoved(path string) {
 	debug.LogIndexing("File removed: %s\n", path)
+	mi.syncWatchedFile(path)
+}
 
-	if err := mi.RemoveFile(path); err != nil {
-		debug.LogIndexing("Warning: failed to remove file %s from index: %v\n", path, err)
+// syncWatchedFile re-indexes a single path reported by the watcher and records the
+// outcome in the current batch summary
+func (mi *MasterIndex) syncWatchedFile(path string) {
+	action, err := mi.SyncFile(path)
+	if err != nil {
+		debug.LogIndexing("Warning: failed to sync file %s: %v\n", path, err)
 	}
+	mi.recordWatchResult(path, action, err)
 }
 
 // handleWatchBatchStart handles the start of a batch of file events
 func (mi *MasterIndex) handleWatchBatchStart(count int) {
 	debug.LogIndexing("Starting batch processing of %d file events\n", count)
-	// Could update progress tracker here if needed
+	mi.watchBatchMu.Lock()
+	mi.watchBatch = WatchBatchSummary{}
+	mi.watchBatchMu.Unlock()
 }
 
 // handleWatchBatchEnd handles the end of a batch of file events
 func (mi *MasterIndex) handleWatchBatchEnd(count int, duration time.Duration) {
 	debug.LogIndexing("Completed batch processing of %d file events in %v\n", count, duration)
+
+	mi.watchBatchMu.Lock()
+	summary := mi.watchBatch
+	summary.Duration = duration
+	handler := mi.watchBatchHandler
+	mi.watchBatchMu.Unlock()
+
 	// Trigger debounced rebuild if we have one
 	if mi.rebuilder != nil {
 		mi.rebuilder.ForceRebuild()
 	}
+
+	if handler != nil {
+		handler(summary)
+	}
 }
 
 // Core interface methods with concurrent implementation
@@ -757,9 +806,12 @@ func (mi *MasterIndex) validateFileForIndexing(path string) (skip bool, err erro
 	return false, nil
 }
 
-// loadFileForIndexing loads a file and returns its ID and content.
+// loadFileForIndexing loads a file and returns its ID and content. Text that is not
+// UTF-8, unless every file is treated as text, is an errInvalidUTF8 error and not stored.
 func (mi *MasterIndex) loadFileForIndexing(path string) (types.FileID, []byte, error) {
-	fileID, err := mi.fileService.LoadFile(path)
+	fileID, err := mi.fileService.LoadFileChecked(path, func(content []byte) error {
+		return checkText(nil, mi.config.Index.TreatAsText, content)
+	})
 	if err != nil {
 		return 0, nil, fmt.Errorf("failed to load file %s: %w", path, err)
 	}
@@ -792,7 +844,9 @@ func (mi *MasterIndex) indexFileContent(path string, fileID types.FileID, conten
 	// Parse file for symbols using FileContentStore
 	parser := parser.NewTreeSitterParser()
 	parser.SetFileContentStore(mi.fileContentStore)
-	_, symbols, _, _, references, scopes := parser.ParseFileEnhancedFromStore(path, fileID)
+	parser.SetLanguages(mi.languages)
+	_, symbols, _, enhanced, references, scopes := parser.ParseFileEnhancedFromStore(path, fileID)
+	mi.parseWarnings.set(path, parser.LastSyntaxError())
 
 	// Index trigrams
 	mi.trigramIndex.IndexFile(fileID, content)
@@ -801,6 +855,7 @@ func (mi *MasterIndex) indexFileContent(path string, fileID types.FileID, conten
 	if mi.postingsIndex != nil {
 		mi.postingsIndex.IndexFile(fileID, content)
 	}
+	mi.termStats.IndexFile(fileID, content)
 
 	// Heuristic include resolution for C/C++ files
 	if mi.isCppFile(path) {
@@ -809,7 +864,7 @@ func (mi *MasterIndex) indexFileContent(path string, fileID types.FileID, conten
 
 	// Index symbols with reference tracker processing
 	if len(symbols) > 0 {
-		_ = mi.refTracker.ProcessFile(fileID, path, symbols, references, scopes)
+		_ = mi.refTracker.ProcessFileWithEnhanced(fileID, path, symbols, enhanced, references, scopes)
 		mi.symbolIndex.IndexSymbols(fileID, symbols)
 		mi.rebuilder.ScheduleRebuild(fileID)
 	}
@@ -831,8 +886,11 @@ func (mi *MasterIndex) isCppFile(path string) bool {
 	return false
 }
 
-// IndexFile indexes a single file by path.
+// IndexFile indexes a single file by path. A file that is not UTF-8 text, unless every
+// file is treated as text, is an error and its content is not stored.
 func (mi *MasterIndex) IndexFile(path string) error {
+	defer mi.bumpGeneration()()
+
 	// Validate file before acquiring locks
 	skip, err := mi.validateFileForIndexing(path)
 	if err != nil {
@@ -871,6 +929,42 @@ func (mi *MasterIndex) IndexFile(path string) error {
 	return nil
 }
 
+// IndexContent indexes content as the file at path, which need not exist on disk, such
+// as a virtual file read from stdin. The content is decoded like a file read from disk.
+func (mi *MasterIndex) IndexContent(path string, content []byte) (types.FileID, error) {
+	defer mi.bumpGeneration()()
+
+	if path == "" {
+		return 0, errors.New("file path cannot be empty")
+	}
+	if int64(len(content)) > mi.config.Index.MaxFileSize {
+		return 0, fmt.Errorf("content of %s too large: %d bytes > %d limit", path, len(content), mi.config.Index.MaxFileSize)
+	}
+	content = mi.fileService.DecodeContent(path, content)
+	if !utf8.Valid(content) {
+		return 0, fmt.Errorf("content of %s is not text", path)
+	}
+
+	indexTypes := []core.IndexType{
+		core.TrigramIndexType,
+		core.SymbolIndexType,
+		core.ReferenceIndexType,
+		core.PostingsIndexType,
+		core.LocationIndexType,
+		core.ContentIndexType,
+	}
+
+	releases, err := mi.lockManager.AcquireMultipleWriteLocksWithTimeout(indexTypes, 10*time.Second)
+	if err != nil {
+		return 0, fmt.Errorf("failed to acquire index locks: %w", err)
+	}
+	defer releases()
+
+	fileID := mi.fileService.LoadFileFromMemory(path, content)
+	mi.indexFileContent(path, fileID, content)
+	return fileID, nil
+}
+
 // validateForUpdate validates inputs for file update (no lock acquisition)
 func (mi *MasterIndex) validateForUpdate(path string, content []byte) error {
 	// Check memory pressure before proceeding
@@ -912,6 +1006,8 @@ func (mi *MasterIndex) validateForUpdate(path string, content []byte) error {
 }
 
 func (mi *MasterIndex) UpdateFile(path string, content []byte) error {
+	defer mi.bumpGeneration()()
+
 	// Validate inputs first (before acquiring any locks)
 	if err := mi.validateForUpdate(path, content); err != nil {
 		return err
@@ -940,6 +1036,7 @@ func (mi *MasterIndex) UpdateFile(path string, content []byte) error {
 	defer mi.snapshotMu.Unlock()
 
 	debug.LogIndexing("Updating file: %s (%d bytes)\n", path, len(content))
+	mi.forgetFileStamp(path)
 
 	// Get old file information and remove if exists
 	currentSnapshot := mi.fileSnapshot.Load()
@@ -988,6 +1085,9 @@ func (mi *MasterIndex) removeOldFileFromIndexes(oldFileID types.FileID, path str
 	if mi.postingsIndex != nil {
 		mi.postingsIndex.RemoveFile(oldFileID)
 	}
+	if mi.termStats != nil {
+		mi.termStats.RemoveFile(oldFileID)
+	}
 	if mi.symbolIndex != nil {
 		mi.symbolIndex.RemoveFileSymbols(oldFileID)
 	}
@@ -1053,7 +1153,9 @@ func (mi *MasterIndex) indexNewFileContent(path string, fileID types.FileID, con
 	// Parse file for symbols using FileContentStore
 	parser := parser.NewTreeSitterParser()
 	parser.SetFileContentStore(mi.fileContentStore)
-	_, symbols, _, _, references, scopes := parser.ParseFileEnhancedFromStore(path, fileID)
+	parser.SetLanguages(mi.languages)
+	_, symbols, _, enhanced, references, scopes := parser.ParseFileEnhancedFromStore(path, fileID)
+	mi.parseWarnings.set(path, parser.LastSyntaxError())
 
 	// Index trigrams
 	mi.trigramIndex.IndexFile(fileID, content)
@@ -1062,6 +1164,7 @@ func (mi *MasterIndex) indexNewFileContent(path string, fileID types.FileID, con
 	if mi.postingsIndex != nil {
 		mi.postingsIndex.IndexFile(fileID, content)
 	}
+	mi.termStats.IndexFile(fileID, content)
 
 	// Heuristic include resolution for C/C++ files
 	if mi.isCppFile(path) {
@@ -1072,7 +1175,7 @@ func (mi *MasterIndex) indexNewFileContent(path string, fileID types.FileID, con
 	if len(symbols) > 0 {
 		// First, let reference tracker process the symbols
 		// This is essential for proper symbol ID assignment
-		_ = mi.refTracker.ProcessFile(fileID, path, symbols, references, scopes)
+		_ = mi.refTracker.ProcessFileWithEnhanced(fileID, path, symbols, enhanced, references, scopes)
 
 		// Then index the original symbols (reference tracker updates are internal)
 		mi.symbolIndex.IndexSymbols(fileID, symbols)
@@ -1091,6 +1194,8 @@ func (mi *MasterIndex) indexNewFileContent(path string, fileID types.FileID, con
 }
 
 func (mi *MasterIndex) RemoveFile(path string) error {
+	defer mi.bumpGeneration()()
+
 	// Acquire write locks for all index types using coordinator
 	indexTypes := []core.IndexType{
 		core.TrigramIndexType,
@@ -1109,6 +1214,8 @@ func (mi *MasterIndex) RemoveFile(path string) error {
 	}
 	defer releases()
 
+	mi.forgetFileStamp(path)
+
 	// Get file ID for removal
 	currentSnapshot := mi.fileSnapshot.Load()
 	fileID, exists := currentSnapshot.fileMap[path]
@@ -1123,6 +1230,7 @@ func (mi *MasterIndex) RemoveFile(path string) error {
 	if mi.deletedFileTracker != nil {
 		mi.deletedFileTracker.MarkDeleted(fileID)
 	}
+	mi.parseWarnings.set(path, "")
 
 	// Remove with atomic copy-on-write
 	mi.updateSnapshotAtomic(func(oldSnapshot *FileSnapshot) *FileSnapshot {
@@ -1163,6 +1271,7 @@ func (mi *MasterIndex) RemoveFile(path string) error {
 	if mi.postingsIndex != nil {
 		mi.postingsIndex.RemoveFile(fileID)
 	}
+	mi.termStats.RemoveFile(fileID)
 	mi.symbolIndex.RemoveFileSymbols(fileID)
 	mi.symbolLocationIndex.RemoveFile(fileID)
 	mi.refTracker.RemoveFile(fileID)
@@ -1391,6 +1500,12 @@ func (mi *MasterIndex) FindSymbolsByName(name string) []*types.EnhancedSymbol {
 	return mi.refTracker.FindSymbolsByName(name)
 }
 
+// FindSymbolsByPrefix finds the symbols whose name starts with prefix, in name order
+// (see core.ReferenceTracker.FindSymbolsByPrefix)
+func (mi *MasterIndex) FindSymbolsByPrefix(prefix string, ignoreCase bool, limit int) []*types.EnhancedSymbol {
+	return mi.refTracker.FindSymbolsByPrefix(prefix, ignoreCase, limit)
+}
+
 // Analysis operations
 
 // convertTreeNode converts a core.FunctionTreeNode to types.TreeNode
@@ -1490,6 +1605,8 @@ func (mi *MasterIndex) GenerateFunctionTree(functionName string, options types.T
 // Management operations
 
 func (mi *MasterIndex) Clear() error {
+	defer mi.bumpGeneration()()
+
 	debug.LogIndexing("Clearing index - releasing all indexed data\n")
 
 	// Prevent clear during active indexing
@@ -1538,6 +1655,13 @@ func (mi *MasterIndex) Clear() error {
 	mi.fileSnapshot.Store(newFileSnapshot())
 	mi.snapshotMu.Unlock()
 
+	mi.stampMu.Lock()
+	mi.fileStamps = nil
+	mi.stampMu.Unlock()
+	mi.parseWarnings.reset()
+	mi.skippedBinary.Store(0)
+	mi.sampledOut.Store(0)
+
 	// Reset counters
 	atomic.StoreInt64(&mi.processedFiles, 0)
 	atomic.StoreInt64(&mi.searchCount, 0)
@@ -1972,11 +2096,21 @@ func (mi *MasterIndex) GetTrigramIndex() *core.TrigramIndex {
 	return mi.trigramIndex
 }
 
-// GetPostingsIndex returns the minimal content postings index
+// GetPostingsIndex returns the token postings index used for whole-word search
 func (mi *MasterIndex) GetPostingsIndex() *core.PostingsIndex {
 	return mi.postingsIndex
 }
 
+// GetTermStatsIndex returns the per-file term statistics gathered at index time
+func (mi *MasterIndex) GetTermStatsIndex() *core.TermStatsIndex {
+	return mi.termStats
+}
+
+// GetFileTermFrequencies returns the term frequencies recorded for a file
+func (mi *MasterIndex) GetFileTermFrequencies(fileID types.FileID) map[string]int {
+	return mi.termStats.TermFrequencies(fileID)
+}
+
 // GetFileInfo implements the interfaces.Indexer interface
 func (mi *MasterIndex) GetFileInfo(fileID types.FileID) *types.FileInfo {
 	return mi.GetFile(fileID)
@@ -1987,6 +2121,72 @@ func (mi *MasterIndex) GetFileCount() int {
 	return len(mi.GetAllFileIDs())
 }
 
+// SetFileLanguage makes IndexContent parse the file at path as language whatever its
+// extension. language must be known.
+func (mi *MasterIndex) SetFileLanguage(path, language string) error {
+	return mi.languages.SetFile(path, language)
+}
+
+// DetectLanguage returns the language of the file at path with this index's overrides
+// applied, or "" for plain text
+func (mi *MasterIndex) DetectLanguage(path string) string {
+	return mi.languages.Detect(path)
+}
+
+// LanguageCounts returns the number of indexed files per detected language, with files
+// no extractor handles counted as lang.PlainText
+func (mi *MasterIndex) LanguageCounts() map[string]int {
+	fileIDs := mi.GetAllFileIDsFiltered()
+	paths := make([]string, 0, len(fileIDs))
+	for _, fileID := range fileIDs {
+		paths = append(paths, mi.GetFilePath(fileID))
+	}
+	return mi.languages.Count(paths)
+}
+
+// ParseWarnings returns the indexed files whose parse hit syntax errors, ordered by path.
+// Those files keep the symbols that parsed cleanly and remain searchable as text.
+func (mi *MasterIndex) ParseWarnings() []ParseWarning {
+	return mi.parseWarnings.list()
+}
+
+// SkippedBinaryFiles returns how many files the last IndexDirectory skipped as binary,
+// by extension or by content. It is 0 when config.Index.TreatAsText is set.
+func (mi *MasterIndex) SkippedBinaryFiles() int {
+	return int(mi.skippedBinary.Load())
+}
+
+// SampledOutFiles returns how many files the last IndexDirectory left out by
+// config.Index.Sample, so GetFileCount files were sampled out of GetFileCount plus
+// SampledOutFiles.
+func (mi *MasterIndex) SampledOutFiles() int {
+	return int(mi.sampledOut.Load())
+}
+
+// FileEncoding returns the encoding a file was decoded from before it was indexed
+// under config.Index.Encoding, or "" for a file read as UTF-8
+func (mi *MasterIndex) FileEncoding(fileID types.FileID) string {
+	if mi.config == nil || mi.config.Index.Encoding == "" {
+		return ""
+	}
+	return mi.fileService.FileEncoding(mi.GetFilePath(fileID))
+}
+
+// Generation identifies the current content of the index. It changes whenever a file is
+// indexed, updated or removed and whenever the index is rebuilt or cleared, so results
+// computed at one generation are valid until it changes.
+func (mi *MasterIndex) Generation() uint64 {
+	return mi.generation.Load()
+}
+
+// bumpGeneration advances the generation on entry and returns a func that advances it
+// again, to be deferred: a search that overlaps the mutation reads a generation that is
+// already stale when the mutation finishes.
+func (mi *MasterIndex) bumpGeneration() func() {
+	mi.generation.Add(1)
+	return func() { mi.generation.Add(1) }
+}
+
 // GetSymbolCount returns the total number of indexed symbols
 func (mi *MasterIndex) GetSymbolCount() int {
 	if mi.symbolIndex == nil {
@@ -2149,11 +2349,14 @@ func (mi *MasterIndex) GetAllFileIDs() []types.FileID {
 		for fileID := range snapshot.reverseFileMap {
 			fileIDs = append(fileIDs, fileID)
 		}
+		slices.Sort(fileIDs)
 		return fileIDs
 	}
 
 	// Fallback: get from symbol index
-	return mi.symbolIndex.GetAllFileIDs()
+	fileIDs := mi.symbolIndex.GetAllFileIDs()
+	slices.Sort(fileIDs)
+	return fileIDs
 }
 
 // GetAllFileIDsFiltered returns all indexed file IDs excluding deleted files.
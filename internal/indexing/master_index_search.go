@@ -89,15 +89,22 @@ func (mi *MasterIndex) SearchWithOptions(pattern string, options types.SearchOpt
 	debug.LogIndexing("Search: pattern='%s' (%d candidates, max_results=%d)\n",
 		pattern, len(allFiles), options.MaxResults)
 
-	// Parse query syntax and filter candidates
-	contentPattern, candidates := mi.parseQuerySyntax(pattern, allFiles)
-
 	// Use injected search engine with semantic scoring, or create default engine
 	engine := mi.searchEngine
 	if engine == nil {
 		engine = search.NewEngine(mi)
 	}
 
+	// A query expression has its own path: field, so it skips the path:/dir:/ext: syntax
+	if options.Query {
+		results, err := engine.SearchQuery(pattern, options)
+		atomic.AddInt64(&mi.searchCount, 1)
+		return results, err
+	}
+
+	// Parse query syntax and filter candidates
+	contentPattern, candidates := mi.parseQuerySyntax(pattern, allFiles)
+
 	// Delegate to search engine
 	results := engine.SearchWithOptions(contentPattern, candidates, options)
 
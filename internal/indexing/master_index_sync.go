@@ -0,0 +1,226 @@
+package indexing
+
+import (
+	"bytes"
+	"fmt"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/standardbeagle/lci/internal/debug"
+)
+
+// SyncAction describes what SyncFile did to the index
+type SyncAction int
+
+const (
+	SyncUnchanged SyncAction = iota // File matched the indexed version, nothing was done
+	SyncUpdated                     // File content changed and was re-indexed
+	SyncAdded                       // File was not indexed before and has been added
+	SyncRemoved                     // File no longer exists and its entries were evicted
+)
+
+// String returns a human-readable name for the action
+func (a SyncAction) String() string {
+	switch a {
+	case SyncUnchanged:
+		return "unchanged"
+	case SyncUpdated:
+		return "updated"
+	case SyncAdded:
+		return "added"
+	case SyncRemoved:
+		return "removed"
+	default:
+		return "unknown"
+	}
+}
+
+// fileStamp is the cheap change-detection key recorded for each synced file
+type fileStamp struct {
+	modTime time.Time
+	size    int64
+}
+
+// SyncFile brings the index up to date with the file at path, re-extracting symbols for
+// just that file. Entries are keyed by path: the old symbols, trigrams and references are
+// evicted before the new content is indexed, and a path that no longer exists on disk is
+// removed from the index.
+//
+// A file whose modification time and size match the last sync is left alone, so calling
+// SyncFile on an unchanged file is a no-op. Files indexed by IndexDirectory have no stamp
+// yet; the first sync compares their content with the stored copy instead.
+func (mi *MasterIndex) SyncFile(path string) (SyncAction, error) {
+	info, err := os.Stat(path)
+	if os.IsNotExist(err) {
+		return mi.syncRemovedFile(path)
+	}
+	if err != nil {
+		return SyncUnchanged, fmt.Errorf("failed to stat %s: %w", path, err)
+	}
+	if info.IsDir() {
+		return SyncUnchanged, fmt.Errorf("cannot sync directory %s", path)
+	}
+
+	stamp := fileStamp{modTime: info.ModTime(), size: info.Size()}
+	fileID, indexed := mi.fileSnapshot.Load().fileMap[path]
+
+	if indexed {
+		if prev, ok := mi.getFileStamp(path); ok && prev == stamp {
+			return SyncUnchanged, nil
+		}
+	}
+
+	// validation goes through FileService, whose stat cache may predate this change
+	mi.fileService.RefreshMetadata(path)
+
+	skip, err := mi.validateFileForIndexing(path)
+	if err != nil {
+		return SyncUnchanged, err
+	}
+	if skip {
+		// Oversized or unsupported now - make sure stale entries don't linger
+		if indexed {
+			return mi.syncRemovedFile(path)
+		}
+		return SyncUnchanged, nil
+	}
+
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return SyncUnchanged, fmt.Errorf("failed to read %s: %w", path, err)
+	}
+	content = mi.fileService.DecodeContent(path, content)
+
+	if len(content) == 0 {
+		// Nothing to index in an empty file; evict whatever was there before
+		return mi.syncRemovedFile(path)
+	}
+
+	if indexed {
+		if stored, ok := mi.fileContentStore.GetContent(fileID); ok && bytes.Equal(stored, content) {
+			mi.setFileStamp(path, stamp)
+			return SyncUnchanged, nil
+		}
+	}
+
+	if err := mi.UpdateFile(path, content); err != nil {
+		return SyncUnchanged, err
+	}
+	mi.setFileStamp(path, stamp)
+
+	action := SyncAdded
+	if indexed {
+		action = SyncUpdated
+	}
+	debug.LogIndexing("Synced file: %s (%s)\n", path, action)
+	return action, nil
+}
+
+// syncRemovedFile evicts a file that disappeared from disk
+func (mi *MasterIndex) syncRemovedFile(path string) (SyncAction, error) {
+	if _, indexed := mi.fileSnapshot.Load().fileMap[path]; !indexed {
+		mi.forgetFileStamp(path)
+		return SyncUnchanged, nil
+	}
+	if err := mi.RemoveFile(path); err != nil {
+		return SyncUnchanged, err
+	}
+	debug.LogIndexing("Synced file: %s (%s)\n", path, SyncRemoved)
+	return SyncRemoved, nil
+}
+
+func (mi *MasterIndex) getFileStamp(path string) (fileStamp, bool) {
+	mi.stampMu.Lock()
+	defer mi.stampMu.Unlock()
+	stamp, ok := mi.fileStamps[path]
+	return stamp, ok
+}
+
+func (mi *MasterIndex) setFileStamp(path string, stamp fileStamp) {
+	mi.stampMu.Lock()
+	defer mi.stampMu.Unlock()
+	if mi.fileStamps == nil {
+		mi.fileStamps = make(map[string]fileStamp)
+	}
+	mi.fileStamps[path] = stamp
+}
+
+// forgetFileStamp drops the recorded stamp so the next SyncFile re-checks the file
+func (mi *MasterIndex) forgetFileStamp(path string) {
+	mi.stampMu.Lock()
+	defer mi.stampMu.Unlock()
+	delete(mi.fileStamps, path)
+}
+
+// WatchBatchSummary describes the outcome of one debounced batch of watcher events
+type WatchBatchSummary struct {
+	Added     int
+	Updated   int
+	Removed   int
+	Unchanged int
+	Failed    int
+	Paths     []string // Paths whose index entries changed, in processing order
+	Duration  time.Duration
+}
+
+// Changed returns the number of files whose index entries were modified
+func (s WatchBatchSummary) Changed() int {
+	return s.Added + s.Updated + s.Removed
+}
+
+// String renders the summary as a single line suitable for watch mode output
+func (s WatchBatchSummary) String() string {
+	line := fmt.Sprintf("re-indexed %d file(s) in %s: %d added, %d updated, %d removed",
+		s.Changed(), s.Duration.Round(time.Millisecond), s.Added, s.Updated, s.Removed)
+	if s.Unchanged > 0 {
+		line += fmt.Sprintf(", %d unchanged", s.Unchanged)
+	}
+	if s.Failed > 0 {
+		line += fmt.Sprintf(", %d failed", s.Failed)
+	}
+	if len(s.Paths) > 0 {
+		const maxListed = 3
+		listed := s.Paths
+		if len(listed) > maxListed {
+			listed = listed[:maxListed]
+		}
+		line += " [" + strings.Join(listed, ", ")
+		if extra := len(s.Paths) - len(listed); extra > 0 {
+			line += fmt.Sprintf(", +%d more", extra)
+		}
+		line += "]"
+	}
+	return line
+}
+
+// SetWatchBatchHandler registers a callback invoked after each debounced batch of
+// watcher events has been applied to the index. Pass nil to remove it.
+func (mi *MasterIndex) SetWatchBatchHandler(handler func(WatchBatchSummary)) {
+	mi.watchBatchMu.Lock()
+	defer mi.watchBatchMu.Unlock()
+	mi.watchBatchHandler = handler
+}
+
+// recordWatchResult adds one synced path to the current batch summary
+func (mi *MasterIndex) recordWatchResult(path string, action SyncAction, err error) {
+	mi.watchBatchMu.Lock()
+	defer mi.watchBatchMu.Unlock()
+
+	if err != nil {
+		mi.watchBatch.Failed++
+		return
+	}
+	switch action {
+	case SyncAdded:
+		mi.watchBatch.Added++
+	case SyncUpdated:
+		mi.watchBatch.Updated++
+	case SyncRemoved:
+		mi.watchBatch.Removed++
+	default:
+		mi.watchBatch.Unchanged++
+		return
+	}
+	mi.watchBatch.Paths = append(mi.watchBatch.Paths, path)
+}
@@ -0,0 +1,55 @@
+package indexing
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestFileScanner_MaxDepth(t *testing.T) {
+	root := t.TempDir()
+	for _, name := range []string{"main.go", "src/lib.go", "src/cmd/tool.go", "vendor/dep.go", "a/b/c/deep.go"} {
+		path := filepath.Join(root, filepath.FromSlash(name))
+		require.NoError(t, os.MkdirAll(filepath.Dir(path), 0755))
+		require.NoError(t, os.WriteFile(path, []byte("package x\n"), 0644))
+	}
+
+	scanned := func(maxDepth *int, exclude ...string) []string {
+		t.Helper()
+		cfg := &config.Config{
+			Project: config.Project{Root: root},
+			Index:   config.Index{MaxFileSize: types.DefaultMaxFileSize, MaxDepth: maxDepth},
+			Exclude: exclude,
+		}
+		scanner := NewFileScanner(cfg, 10)
+		tasks := make(chan FileTask, 10)
+		go func() {
+			_ = scanner.ScanDirectory(context.Background(), root, tasks, NewProgressTracker())
+			close(tasks)
+		}()
+		var names []string
+		for task := range tasks {
+			rel, err := filepath.Rel(root, task.Path)
+			require.NoError(t, err)
+			names = append(names, filepath.ToSlash(rel))
+		}
+
+		count, _, err := scanner.CountFiles(context.Background(), root)
+		require.NoError(t, err)
+		assert.Len(t, names, count, "counting walks like scanning")
+		return names
+	}
+	depth := func(n int) *int { return &n }
+
+	assert.ElementsMatch(t, []string{"main.go"}, scanned(depth(0)), "depth 0 is only the files in the root")
+	assert.ElementsMatch(t, []string{"main.go", "src/lib.go", "vendor/dep.go"}, scanned(depth(1)))
+	assert.ElementsMatch(t, []string{"main.go", "src/lib.go"}, scanned(depth(1), "vendor/**"), "exclusions still apply")
+	assert.Len(t, scanned(nil), 5, "no limit by default")
+}
@@ -0,0 +1,55 @@
+package indexing
+
+import (
+	"sort"
+	"sync"
+)
+
+// ParseWarning records a file whose parse recovered from a syntax error or crashed. The
+// file stays indexed: symbols outside the broken region are kept, and its content is
+// searchable as plain text either way.
+type ParseWarning struct {
+	Path    string `json:"path"`
+	Message string `json:"message"`
+}
+
+// parseWarnings holds the current parse warning of each file. Re-indexing a file
+// replaces its warning and removing it drops the warning.
+type parseWarnings struct {
+	mu     sync.RWMutex
+	byPath map[string]string
+}
+
+func newParseWarnings() *parseWarnings {
+	return &parseWarnings{byPath: make(map[string]string)}
+}
+
+// set records message for path, or clears path's warning when message is ""
+func (pw *parseWarnings) set(path, message string) {
+	pw.mu.Lock()
+	defer pw.mu.Unlock()
+	if message == "" {
+		delete(pw.byPath, path)
+		return
+	}
+	pw.byPath[path] = message
+}
+
+// reset drops every warning
+func (pw *parseWarnings) reset() {
+	pw.mu.Lock()
+	defer pw.mu.Unlock()
+	clear(pw.byPath)
+}
+
+// list returns the warnings ordered by path
+func (pw *parseWarnings) list() []ParseWarning {
+	pw.mu.RLock()
+	defer pw.mu.RUnlock()
+	warnings := make([]ParseWarning, 0, len(pw.byPath))
+	for path, message := range pw.byPath {
+		warnings = append(warnings, ParseWarning{Path: path, Message: message})
+	}
+	sort.Slice(warnings, func(i, j int) bool { return warnings[i].Path < warnings[j].Path })
+	return warnings
+}
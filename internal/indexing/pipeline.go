@@ -12,7 +12,7 @@ import (
 
 	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/debug"
-	"github.com/standardbeagle/lci/internal/parser"
+	"github.com/standardbeagle/lci/internal/lang"
 )
 
 // NewFileScanner creates a new file scanner
@@ -20,27 +20,53 @@ func NewFileScanner(cfg *config.Config, bufferSize int) *FileScanner {
 	scanner := &FileScanner{
 		config:         cfg,
 		bufferSize:     bufferSize,
-		binaryDetector: NewBinaryDetector(),
+		binaryDetector: newBinaryDetectorFor(cfg),
 	}
 
 	// Pre-compile glob patterns for fast matching
 	scanner.compilePatterns()
 
-	// Initialize gitignore parser if enabled
+	// Initialize ignore-file parser (.gitignore, .ignore, .lciignore) if enabled
 	if cfg.Index.RespectGitignore {
 		scanner.gitignoreParser = config.NewGitignoreParser()
-		if err := scanner.gitignoreParser.LoadGitignore(cfg.Project.Root); err != nil {
-			log.Printf("Warning: failed to load .gitignore: %v", err)
+		if err := scanner.gitignoreParser.LoadIgnoreFiles(cfg.Project.Root); err != nil {
+			log.Printf("Warning: failed to load ignore files: %v", err)
 		}
 	}
 
 	return scanner
 }
 
+// SetSkippedBinary sets the counter of files skipped as binary
+func (fs *FileScanner) SetSkippedBinary(counter *atomic.Int64) {
+	fs.skippedBinary = counter
+}
+
+// SetLanguages sets the language overrides applied when detecting each file's language
+func (fs *FileScanner) SetLanguages(languages *lang.Overrides) {
+	fs.languages = languages
+}
+
+// SetSampledOut sets the counter of files left out by config.Index.Sample
+func (fs *FileScanner) SetSampledOut(counter *atomic.Int64) {
+	fs.sampledOut = counter
+}
+
+// countSampledOut adds a file left out by config.Index.Sample to the counter, when
+// there is one
+func (fs *FileScanner) countSampledOut() {
+	if fs.sampledOut != nil {
+		fs.sampledOut.Add(1)
+	}
+}
+
 // CountFiles counts files that would be indexed without actually processing them.
 // This uses the same exclusion/inclusion logic as ScanDirectory but only counts.
 // Returns file count and total size in bytes.
 func (fs *FileScanner) CountFiles(ctx context.Context, root string) (fileCount int, totalBytes int64, err error) {
+	if isArchiveRoot(root) {
+		return fs.countArchive(ctx, root)
+	}
 	visitedDirs := make(map[string]bool)
 
 	err = filepath.Walk(root, func(path string, info os.FileInfo, walkErr error) error {
@@ -76,7 +102,7 @@ func (fs *FileScanner) CountFiles(ctx context.Context, root string) (fileCount i
 			}
 			normalizedPath := filepath.ToSlash(relPath)
 			// Check with trailing slash for directory patterns
-			if fs.shouldExcludeFast(normalizedPath) || fs.shouldExcludeFast(normalizedPath+"/") {
+			if beyondMaxDepth(fs.config, normalizedPath, true) || fs.shouldExcludeFast(normalizedPath) || fs.shouldExcludeFast(normalizedPath+"/") {
 				return filepath.SkipDir
 			}
 			return nil
@@ -102,7 +128,7 @@ func (fs *FileScanner) CountFiles(ctx context.Context, root string) (fileCount i
 			}
 
 			// File matches - count it
-			if fs.shouldProcessFile(path, info) {
+			if fs.shouldProcessFile(path, info) && !sampledOut(fs.config, normalizedPath) {
 				fileCount++
 				totalBytes += info.Size()
 			}
@@ -114,7 +140,8 @@ func (fs *FileScanner) CountFiles(ctx context.Context, root string) (fileCount i
 	return fileCount, totalBytes, err
 }
 
-// ScanDirectory scans a directory and sends file tasks to the channel
+// ScanDirectory scans a directory and sends file tasks to the channel. A .tar.gz root
+// is scanned as a directory of its entries (see scanArchive).
 func (fs *FileScanner) ScanDirectory(ctx context.Context, root string, taskChan chan<- FileTask, progress *ProgressTracker) error {
 	var scannedFiles int64
 	var processedFiles int64
@@ -128,6 +155,10 @@ func (fs *FileScanner) ScanDirectory(ctx context.Context, root string, taskChan
 	runtime.ReadMemStats(&memStats)
 	baselineMemMB := memStats.HeapAlloc / 1024 / 1024
 
+	if isArchiveRoot(root) {
+		return fs.scanArchive(ctx, root, taskChan, progress)
+	}
+
 	debug.LogIndexing("Starting directory scan of %s", root)
 
 	// Single pass: find and process files with early directory pruning
@@ -205,21 +236,22 @@ func (fs *FileScanner) ScanDirectory(ctx context.Context, root string, taskChan
 				relPath = path // Fallback to absolute path
 			}
 			normalizedPath := filepath.ToSlash(relPath)
-			// Check if this directory itself should be excluded
-			if fs.shouldExcludeFast(normalizedPath) || fs.shouldExcludeFast(normalizedPath+"/") {
+			// Check if this directory itself should be excluded, or is deeper than --max-depth
+			if beyondMaxDepth(fs.config, normalizedPath, true) || fs.shouldExcludeFast(normalizedPath) || fs.shouldExcludeFast(normalizedPath+"/") {
 				return filepath.SkipDir
 			}
 			return nil // Continue into this directory
 		}
 
 		// For files, do quick filename filtering BEFORE expensive operations
+		var normalizedPath string
 		if !info.IsDir() {
 			// Use relative path from root for pattern matching
 			relPath, err := filepath.Rel(root, path)
 			if err != nil {
 				relPath = path // Fallback to absolute path
 			}
-			normalizedPath := filepath.ToSlash(relPath)
+			normalizedPath = filepath.ToSlash(relPath)
 
 			// Quick filename-based exclusion first (fast check)
 			if fs.shouldExcludeFast(normalizedPath) {
@@ -234,17 +266,22 @@ func (fs *FileScanner) ScanDirectory(ctx context.Context, root string, taskChan
 
 		atomic.AddInt64(&scannedFiles, 1)
 
-		if fs.shouldProcessFile(path, info) {
+		process, binary := fs.checkFile(path, info)
+		if binary {
+			countSkippedBinary(fs.skippedBinary)
+		}
+		if process && sampledOut(fs.config, normalizedPath) {
+			fs.countSampledOut()
+			process = false
+		}
+		if process {
 			atomic.AddInt64(&processedFiles, 1)
 
-			// Detect language from file extension for parser selection
-			ext := filepath.Ext(path)
-			language := parser.GetLanguageFromExtension(ext)
-
+			// Detect language from file extension (or a --lang override) for parser selection
 			task := FileTask{
 				Path:     path,
 				Info:     info,
-				Language: language,
+				Language: fs.languages.Detect(path),
 				Priority: fs.getFilePriority(path),
 			}
 
@@ -7,6 +7,8 @@ import (
 	"bytes"
 	"context"
 	"log"
+	"maps"
+	"slices"
 	"strings"
 	"sync"
 	"sync/atomic"
@@ -148,6 +150,12 @@ type FileIntegrator struct {
 
 	// Side effect propagator for function purity analysis
 	sideEffectPropagator *core.SideEffectPropagator
+
+	// Term statistics for BM25 ranking
+	termStats *core.TermStatsIndex
+
+	// Token postings for whole-word search
+	postings *core.PostingsIndex
 }
 
 // SpecializedIntegrator handles a specific subset of indexing operations in parallel
@@ -265,6 +273,16 @@ func (fi *FileIntegrator) SetSideEffectPropagator(propagator *core.SideEffectPro
 	fi.sideEffectPropagator = propagator
 }
 
+// SetTermStatsIndex sets the index that receives per-file term frequencies
+func (fi *FileIntegrator) SetTermStatsIndex(index *core.TermStatsIndex) {
+	fi.termStats = index
+}
+
+// SetPostingsIndex sets the index that receives per-file token postings
+func (fi *FileIntegrator) SetPostingsIndex(index *core.PostingsIndex) {
+	fi.postings = index
+}
+
 // EnableMergerPipeline enables the channel-based merger pipeline for lock-free trigram indexing
 // mergerCount specifies how many parallel merger goroutines to use (default: 16)
 func (fi *FileIntegrator) EnableMergerPipeline(mergerCount int) {
@@ -297,6 +315,56 @@ func (fi *FileIntegrator) GetMergerPipelineStats() *ChannelStats {
 	return &stats
 }
 
+// orderResults re-emits processed files in scan order (by Seq) so the order files are
+// integrated in, and with it the index ordering, does not depend on which worker
+// finished first. Input is always drained, so a worker never blocks behind a slower
+// one. Results without a Seq pass straight through.
+func orderResults(ctx context.Context, in <-chan ProcessedFile) <-chan ProcessedFile {
+	out := make(chan ProcessedFile, cap(in))
+	go func() {
+		defer close(out)
+		send := func(result ProcessedFile) bool {
+			select {
+			case out <- result:
+				return true
+			case <-ctx.Done():
+				return false
+			}
+		}
+
+		pending := make(map[uint64]ProcessedFile)
+		next := uint64(1)
+		for result := range in {
+			if result.Seq == 0 {
+				if !send(result) {
+					return
+				}
+				continue
+			}
+			pending[result.Seq] = result
+			for {
+				ready, ok := pending[next]
+				if !ok {
+					break
+				}
+				delete(pending, next)
+				next++
+				if !send(ready) {
+					return
+				}
+			}
+		}
+
+		// A result dropped by a processor leaves a gap; flush whatever is left in order
+		for _, seq := range slices.Sorted(maps.Keys(pending)) {
+			if !send(pending[seq]) {
+				return
+			}
+		}
+	}()
+	return out
+}
+
 // IntegrateFiles integrates processed files into the indexes
 func (fi *FileIntegrator) IntegrateFiles(ctx context.Context, resultChan <-chan ProcessedFile, progress *ProgressTracker) {
 	// Ensure merger pipeline is always shut down, even if context is cancelled
@@ -379,6 +447,13 @@ func (fi *FileIntegrator) IntegrateFiles(ctx context.Context, resultChan <-chan
 			}
 			result.FileID = fileID
 
+			if fi.termStats != nil {
+				fi.termStats.Add(fileID, result.TermStats)
+			}
+			if fi.postings != nil {
+				fi.postings.Add(fileID, result.Postings)
+			}
+
 			// Index file path for file search functionality
 			if fi.fileSearchEngine != nil {
 				fi.fileSearchEngine.IndexFile(fileID, result.Path)
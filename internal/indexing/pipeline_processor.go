@@ -5,15 +5,18 @@ package indexing
 
 import (
 	"context"
+	"errors"
 	"fmt"
 	"log"
 	"path/filepath"
 	"runtime"
+	"sync/atomic"
 	"time"
 
 	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/core"
 	"github.com/standardbeagle/lci/internal/debug"
+	"github.com/standardbeagle/lci/internal/lang"
 	"github.com/standardbeagle/lci/internal/parser"
 	"github.com/standardbeagle/lci/internal/types"
 
@@ -25,9 +28,13 @@ type FileProcessor struct {
 	config         *config.Config
 	parser         *parser.TreeSitterParser
 	fileService    *core.FileService
-	binaryDetector *BinaryDetector
+	binaryDetector *BinaryDetector    // nil when every file is treated as text
+	skippedBinary  *atomic.Int64      // Counts files skipped as binary, nil to not count
 	ownsParser     bool               // Track if we need to release the parser
 	trigramIndex   *core.TrigramIndex // NEW: For bucketing strategy
+	indexFile      *indexFileCache    // Saved parses to reuse, nil when no index file is used
+	parseWarnings  *parseWarnings     // Records files that parsed with errors, nil to not record
+	languages      *lang.Overrides    // Language overrides handed to each parser
 	// String interning moved to FileIntegrator (global only, no per-file)
 }
 
@@ -47,7 +54,7 @@ func NewFileProcessorWithService(cfg *config.Config, fileService *core.FileServi
 	// Performance optimization: Create a basic processor
 	// Workers will get language-specific parsers from pools on-demand
 	return &FileProcessor{
-		binaryDetector: NewBinaryDetector(),
+		binaryDetector: newBinaryDetectorFor(cfg),
 		config:         cfg,
 		fileService:    fileService,
 		ownsParser:     false, // We don't own a parser - workers get them from pools
@@ -66,6 +73,26 @@ func (fp *FileProcessor) SetTrigramIndex(idx *core.TrigramIndex) {
 	fp.trigramIndex = idx
 }
 
+// SetIndexFileCache sets the saved parses consulted before parsing a file
+func (fp *FileProcessor) SetIndexFileCache(cache *indexFileCache) {
+	fp.indexFile = cache
+}
+
+// SetParseWarnings sets where files that parse with syntax errors are recorded
+func (fp *FileProcessor) SetParseWarnings(warnings *parseWarnings) {
+	fp.parseWarnings = warnings
+}
+
+// SetSkippedBinary sets the counter of files skipped as binary
+func (fp *FileProcessor) SetSkippedBinary(counter *atomic.Int64) {
+	fp.skippedBinary = counter
+}
+
+// SetLanguages sets the language overrides the parsers use to pick each file's extractor
+func (fp *FileProcessor) SetLanguages(languages *lang.Overrides) {
+	fp.languages = languages
+}
+
 // ProcessFiles processes files from the task channel
 func (fp *FileProcessor) ProcessFiles(ctx context.Context, workerID int, taskChan <-chan FileTask, resultChan chan<- ProcessedFile) {
 	for {
@@ -120,7 +147,7 @@ func (fp *FileProcessor) ProcessFiles(ctx context.Context, workerID int, taskCha
 func (fp *FileProcessor) processFile(ctx context.Context, workerID int, task FileTask) ProcessedFile {
 	start := time.Now()
 
-	result := ProcessedFile{Path: task.Path, FileID: 0, Stage: "parsing", Duration: 0, Language: task.Language}
+	result := ProcessedFile{Path: task.Path, FileID: 0, Seq: task.Seq, Stage: "parsing", Duration: 0, Language: task.Language}
 
 	// Check for cancellation
 	select {
@@ -146,6 +173,7 @@ func (fp *FileProcessor) processFile(ctx context.Context, workerID int, task Fil
 			parser.ReleaseParser(parserInstance)
 		}
 	}
+	p.SetLanguages(fp.languages)
 	// Ensure parser is returned to pool after processing
 	// Add panic recovery to ensure parser is always released
 	defer func() {
@@ -168,7 +196,37 @@ func (fp *FileProcessor) processFile(ctx context.Context, workerID int, task Fil
 
 	// TODO: This should be removed when the integrator handles file loading
 	// For now, we use LoadFile() to ensure content goes through ContentStore
-	fileID, err := fp.fileService.LoadFile(task.Path)
+	var fileID types.FileID
+	var err error
+	// Defense-in-depth: checkText rejects binary content by magic number, and text that is
+	// not UTF-8 unless every file is treated as text, before the content is stored.
+	// Primary binary detection happens during file enumeration (shouldProcessFile) for
+	// files > 100KB; this catches small binary files and files modified between scan and load.
+	check := func(content []byte) error {
+		return checkText(fp.binaryDetector, fp.config.Index.TreatAsText, content)
+	}
+	if task.Content != nil {
+		content := fp.fileService.DecodeContent(task.Path, task.Content)
+		if err = check(content); err == nil {
+			fileID = fp.fileService.LoadFileFromMemory(task.Path, content)
+		}
+	} else {
+		fileID, err = fp.fileService.LoadFileChecked(task.Path, check)
+	}
+	if errors.Is(err, errBinaryContent) {
+		countSkippedBinary(fp.skippedBinary)
+		result.Error = fmt.Errorf("binary file detected by magic number: %s", task.Path)
+		result.Stage = "binary_detection"
+		result.Duration = time.Since(start)
+		return result
+	}
+	// Text that is not UTF-8 is skipped with a warning rather than failing the index
+	if errors.Is(err, errInvalidUTF8) {
+		result.Error = fmt.Errorf("invalid UTF-8, file skipped: %s", task.Path)
+		result.Stage = "utf8_validation"
+		result.Duration = time.Since(start)
+		return result
+	}
 	if err != nil {
 		result.Error = fmt.Errorf("failed to load file: %w", err)
 		result.Stage = "loading"
@@ -193,16 +251,6 @@ func (fp *FileProcessor) processFile(ctx context.Context, workerID int, task Fil
 		return result
 	}
 
-	// Defense-in-depth: Check for binary content by magic number
-	// Primary check happens during file enumeration (shouldProcessFile) for files > 100KB
-	// This is a fallback to catch edge cases (small binary files, files modified between scan and load)
-	if fp.binaryDetector != nil && fp.binaryDetector.IsBinaryByMagicNumber(content) {
-		result.Error = fmt.Errorf("binary file detected by magic number: %s", task.Path)
-		result.Stage = "binary_detection"
-		result.Duration = time.Since(start)
-		return result
-	}
-
 	// Parse file for symbols, references, AST, performance data, and side effects with panic recovery
 	var symbols []types.Symbol
 	var enhancedSymbols []types.EnhancedSymbol
@@ -211,15 +259,45 @@ func (fp *FileProcessor) processFile(ctx context.Context, workerID int, task Fil
 	var ast *tree_sitter.Tree
 	var perfData []types.FunctionPerfData
 	var sideEffects map[string]*types.SideEffectInfo
-	func() {
-		defer func() {
-			if r := recover(); r != nil {
-				log.Printf("Parser panic for file %s: %v", task.Path, r)
-				result.Error = fmt.Errorf("parser panic: %v", r)
-			}
+	var syntaxError string
+	var saved parsedFile
+	var reused bool
+	if fp.indexFile != nil {
+		saved, reused = fp.indexFile.lookup(task, fileID, content)
+	}
+	if reused {
+		symbols, enhancedSymbols, references, scopes = saved.Symbols, saved.EnhancedSymbols, saved.References, saved.Scopes
+		perfData, sideEffects = saved.PerfData, saved.SideEffects
+		syntaxError = saved.SyntaxError
+	} else {
+		// A parser panic leaves every result empty: the file is still indexed, as plain text
+		panicked := false
+		func() {
+			defer func() {
+				if r := recover(); r != nil {
+					log.Printf("Parser panic for file %s: %v", task.Path, r)
+					syntaxError = fmt.Sprintf("parser panic: %v", r)
+					panicked = true
+				}
+			}()
+			ast, _, symbols, _, enhancedSymbols, references, scopes, perfData, sideEffects = p.ParseFileWithSideEffects(context.Background(), task.Path, content, fileID)
+			syntaxError = p.LastSyntaxError()
 		}()
-		ast, _, symbols, _, enhancedSymbols, references, scopes, perfData, sideEffects = p.ParseFileWithSideEffects(context.Background(), task.Path, content, fileID)
-	}()
+		if fp.indexFile != nil && !panicked {
+			fp.indexFile.store(task, content, parsedFile{
+				Symbols:         symbols,
+				EnhancedSymbols: enhancedSymbols,
+				References:      references,
+				Scopes:          scopes,
+				PerfData:        perfData,
+				SideEffects:     sideEffects,
+				SyntaxError:     syntaxError,
+			})
+		}
+	}
+	if fp.parseWarnings != nil {
+		fp.parseWarnings.set(task.Path, syntaxError)
+	}
 
 	// String interning is done globally in FileIntegrator, not per-file
 	// This avoids double allocation (per-file + global)
@@ -323,9 +401,16 @@ func (fp *FileProcessor) processFile(ctx context.Context, workerID int, task Fil
 	result.AST = ast
 	result.Language = filepath.Ext(task.Path)
 	result.BucketedTrigrams = bucketedTrigrams // Pre-sharded format for lock-free merging
+	result.TermStats = fp.countTerms(content)  // Term frequencies counted in the map phase for BM25
 	result.PerfData = perfData                 // Performance analysis data for anti-pattern detection
 	result.SideEffectResults = sideEffects     // Side effect analysis for purity detection
+	result.Postings = core.TokenizePostings(content)
 	result.Stage = "completed"
 	result.Duration = time.Since(start)
 	return result
 }
+
+// countTerms splits content into BM25 terms with the configured tokenizer
+func (fp *FileProcessor) countTerms(content []byte) core.FileTermStats {
+	return core.TermTokenizer(fp.config.Index.Tokenize).CountTerms(content)
+}
@@ -4,24 +4,63 @@
 package indexing
 
 import (
+	"hash/fnv"
 	"io"
+	"math/rand/v2"
 	"os"
 	"path/filepath"
 	"strings"
 
+	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/types"
 )
 
 // shouldProcessFile determines if a file should be indexed (simplified - main filtering done earlier)
 func (fs *FileScanner) shouldProcessFile(path string, info os.FileInfo) bool {
+	process, _ := fs.checkFile(path, info)
+	return process
+}
+
+// beyondMaxDepth reports whether Index.MaxDepth keeps the walk out of rel, a
+// slash-separated path relative to the root. Depth counts the directories between the
+// root and a file, so files directly in the root are at depth 0 and a directory is at
+// the depth of the files directly inside it: with MaxDepth 1, src/main.go is indexed
+// but src/cmd is not descended into.
+func beyondMaxDepth(cfg *config.Config, rel string, dir bool) bool {
+	if cfg.Index.MaxDepth == nil {
+		return false
+	}
+	depth := strings.Count(rel, "/")
+	if dir {
+		depth++
+	}
+	return depth > *cfg.Index.MaxDepth
+}
+
+// sampledOut reports whether Index.Sample leaves rel, a slash-separated path relative
+// to the root, out of the index. Each file is kept with probability Sample by a draw
+// from an RNG seeded with SampleSeed and the path, so the same seed keeps the same
+// files whatever order the walk finds them in.
+func sampledOut(cfg *config.Config, rel string) bool {
+	if cfg.Index.Sample <= 0 || cfg.Index.Sample >= 1 {
+		return false
+	}
+	h := fnv.New64a()
+	h.Write([]byte(rel))
+	return rand.New(rand.NewPCG(uint64(cfg.Index.SampleSeed), h.Sum64())).Float64() >= cfg.Index.Sample
+}
+
+// checkFile is shouldProcessFile that also reports whether a skipped file was skipped
+// for being binary
+func (fs *FileScanner) checkFile(path string, info os.FileInfo) (process, binary bool) {
 	// Skip directories (should already be filtered out)
 	if info.IsDir() {
-		return false
+		return false, false
 	}
 
 	// Fast binary detection by extension (no I/O needed)
 	if fs.binaryDetector != nil && fs.binaryDetector.IsBinaryByExtension(path) {
-		return false
+		return false, true
 	}
 
 	// Check gitignore if enabled (more expensive check done after filename filtering)
@@ -38,25 +77,25 @@ func (fs *FileScanner) shouldProcessFile(path string, info os.FileInfo) bool {
 		}
 
 		if fs.gitignoreParser.ShouldIgnore(relativePath, info.IsDir()) {
-			return false
+			return false, false
 		}
 	}
 
 	// Check file size limits (done after filename filtering to avoid stat calls)
 	if info.Size() > int64(fs.config.Index.MaxFileSize) {
-		return false
+		return false, false
 	}
 
 	// Binary pre-check: For files above the threshold, read first bytes to detect binary content
 	// This prevents loading large binary files into memory
 	if fs.binaryDetector != nil && info.Size() > types.BinaryPreCheckSizeThreshold {
 		if isBinary := fs.preCheckBinaryFile(path); isBinary {
-			return false
+			return false, true
 		}
 	}
 
 	// If we get here, the file passed all filename filters and should be processed
-	return true
+	return true, false
 }
 
 // preCheckBinaryFile reads the first bytes of a file to detect binary content
@@ -78,7 +117,7 @@ func (fs *FileScanner) preCheckBinaryFile(path string) bool {
 	}
 
 	// Check the bytes we read for binary signatures
-	return fs.binaryDetector.IsBinaryByMagicNumber(buffer[:n])
+	return fs.binaryDetector.IsBinaryByMagicNumber(decodedForDetection(fs.config, buffer[:n]))
 }
 
 // matchesGlobPattern performs glob-style pattern matching with ** support
@@ -6,10 +6,12 @@ package indexing
 import (
 	"os"
 	"runtime"
+	"sync/atomic"
 	"time"
 
 	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/lang"
 	"github.com/standardbeagle/lci/internal/types"
 
 	"github.com/bmatcuk/doublestar/v4"
@@ -60,12 +62,15 @@ type FileTask struct {
 	Info     os.FileInfo
 	Language string // File language (go, python, typescript, etc.) for parser selection
 	Priority int    // Higher priority files processed first
+	Seq      uint64 // Scan order, starting at 1; 0 means unordered
+	Content  []byte // Content of an archive entry, which has no file to load; nil otherwise
 }
 
 // ProcessedFile represents the result of processing a file
 type ProcessedFile struct {
 	Path             string
 	FileID           types.FileID
+	Seq              uint64 // Copied from FileTask.Seq
 	Symbols          []types.Symbol
 	EnhancedSymbols  []types.EnhancedSymbol      // Enhanced symbols with complexity data
 	References       []types.Reference           // extracted references
@@ -73,9 +78,11 @@ type ProcessedFile struct {
 	ScopeChains      [][]types.ScopeInfo         // Pre-computed scope chains per symbol (indexed same as Symbols)
 	LineToSymbols    map[int][]int               // Pre-computed line->symbol indices for O(1) semantic filtering
 	BucketedTrigrams *core.BucketedTrigramResult // Pre-sharded trigrams for lock-free merging
+	TermStats        core.FileTermStats          // Per-file term frequencies for BM25 ranking
+	Postings         core.FilePostings           // Per-file token offsets for the postings index
 	Content          []byte                      // file content for metrics calculation
 	LineOffsets      []int                       // precomputed line boundaries for O(1) line access
-	AST              *tree_sitter.Tree           // parsed AST for Tree-sitter queries
+	AST              *tree_sitter.Tree           // parsed AST for Tree-sitter queries; nil when the parse came from an index file, see TreeSitterParser.ParseTree
 	Language         string                      // file language extension (.go, .js, etc.)
 	Error            error
 	Stage            string // "scanning", "parsing", "indexing"
@@ -93,7 +100,10 @@ type FileScanner struct {
 	config          *config.Config
 	bufferSize      int
 	gitignoreParser *config.GitignoreParser
-	binaryDetector  *BinaryDetector
+	binaryDetector  *BinaryDetector // nil when every file is treated as text
+	skippedBinary   *atomic.Int64   // Counts files skipped as binary, nil to not count
+	sampledOut      *atomic.Int64   // Counts files left out by Index.Sample, nil to not count
+	languages       *lang.Overrides // Language overrides applied when detecting each file's language
 	// Pre-compiled glob patterns for fast matching
 	compiledExclusions []string // Pattern strings (doublestar compiles internally)
 	compiledInclusions []string // Pattern strings (doublestar compiles internally)
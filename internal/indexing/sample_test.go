@@ -0,0 +1,61 @@
+package indexing
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sync/atomic"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSampledOut(t *testing.T) {
+	kept := func(sample float64, seed int64) map[string]bool {
+		cfg := &config.Config{Index: config.Index{Sample: sample, SampleSeed: seed}}
+		files := make(map[string]bool)
+		for i := range 1000 {
+			if rel := fmt.Sprintf("src/pkg%d/file%d.go", i%10, i); !sampledOut(cfg, rel) {
+				files[rel] = true
+			}
+		}
+		return files
+	}
+
+	sample := kept(0.2, 42)
+	assert.InDelta(t, 200, len(sample), 50, "about the sampled fraction is kept")
+	assert.Equal(t, sample, kept(0.2, 42), "the same seed keeps the same files")
+	assert.NotEqual(t, sample, kept(0.2, 43), "another seed keeps other files")
+	assert.Len(t, kept(0, 42), 1000, "no sampling by default")
+	assert.Len(t, kept(1, 42), 1000)
+}
+
+func TestFileScanner_SampleCounts(t *testing.T) {
+	root := t.TempDir()
+	for i := range 50 {
+		require.NoError(t, os.WriteFile(filepath.Join(root, fmt.Sprintf("file%d.go", i)), []byte("package x\n"), 0644))
+	}
+	cfg := &config.Config{
+		Project: config.Project{Root: root},
+		Index:   config.Index{MaxFileSize: types.DefaultMaxFileSize, Sample: 0.5, SampleSeed: 7},
+	}
+	scanner := NewFileScanner(cfg, 50)
+	var sampledOut atomic.Int64
+	scanner.SetSampledOut(&sampledOut)
+
+	tasks := make(chan FileTask, 50)
+	require.NoError(t, scanner.ScanDirectory(context.Background(), root, tasks, NewProgressTracker()))
+	close(tasks)
+	scanned := len(tasks)
+
+	count, _, err := scanner.CountFiles(context.Background(), root)
+	require.NoError(t, err)
+	assert.Equal(t, scanned, count, "counting samples like scanning")
+	assert.Equal(t, 50, scanned+int(sampledOut.Load()), "every file is either scanned or sampled out")
+	assert.Positive(t, sampledOut.Load())
+}
@@ -1,6 +1,7 @@
 package indexing
 
 import (
+	"bytes"
 	"context"
 	"os"
 	"path/filepath"
@@ -156,3 +157,50 @@ func main() {}`
 
 	assert.True(t, foundGo, "Should have found the Go file")
 }
+
+// TestLanguageOverridesArePerIndex checks that config.Index.Languages parses files as
+// the given language in one index without changing how any other index parses them
+func TestLanguageOverridesArePerIndex(t *testing.T) {
+	testDir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "page.tpl"), []byte("package views\n\nfunc RenderPage() {}\n"), 0644))
+
+	index := func(languages map[string]string) *MasterIndex {
+		cfg := createProductionFlowTestConfig(testDir)
+		cfg.Include = []string{"*.tpl"}
+		cfg.Index.Languages = languages
+		indexer := NewMasterIndex(cfg)
+		t.Cleanup(func() { indexer.Close() })
+		require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+		return indexer
+	}
+
+	overridden := index(map[string]string{".tpl": "go"})
+	plain := index(nil)
+	assert.NotEmpty(t, overridden.FindSymbolsByName("RenderPage"), "the override parses .tpl as Go")
+	assert.Equal(t, map[string]int{"go": 1}, overridden.LanguageCounts())
+	assert.Empty(t, plain.FindSymbolsByName("RenderPage"), "another index keeps .tpl as plain text")
+	assert.Equal(t, "", plain.DetectLanguage(filepath.Join(testDir, "page.tpl")))
+}
+
+// TestNonUTF8FilesAreNotStored verifies a file with invalid UTF-8 anywhere in it, not
+// only near its start, is skipped without its content reaching the content store
+func TestNonUTF8FilesAreNotStored(t *testing.T) {
+	testDir := t.TempDir()
+	valid := filepath.Join(testDir, "valid.go")
+	invalid := filepath.Join(testDir, "latin1.go")
+	require.NoError(t, os.WriteFile(valid, []byte("package main\n\nfunc Valid() {}\n"), 0644))
+	require.NoError(t, os.WriteFile(invalid, append(bytes.Repeat([]byte("// padding\n"), 8*1024), "var caf\xe9 = 1\n"...), 0644))
+
+	cfg := createProductionFlowTestConfig(testDir)
+	cfg.Include = []string{"*.go"}
+	indexer := NewMasterIndex(cfg)
+	defer indexer.Close()
+	require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+	assert.Equal(t, 1, indexer.GetFileCount())
+	assert.Equal(t, 1, indexer.GetFileContentStore().GetFileCount(), "the skipped file's content is not stored")
+
+	err := indexer.IndexFile(invalid)
+	require.Error(t, err)
+	assert.ErrorIs(t, err, errInvalidUTF8)
+	assert.Equal(t, 1, indexer.GetFileContentStore().GetFileCount())
+}
@@ -0,0 +1,72 @@
+package indexing
+
+import (
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// GetSymbolAtOffset returns the innermost symbol of a file whose span contains the
+// byte offset, or nil when the offset is outside every symbol
+func (mi *MasterIndex) GetSymbolAtOffset(fileID types.FileID, offset int) *types.Symbol {
+	enhancedSymbol := mi.GetEnhancedSymbolAtOffset(fileID, offset)
+	if enhancedSymbol == nil {
+		return nil
+	}
+	return &enhancedSymbol.Symbol
+}
+
+// GetEnhancedSymbolAtOffset is GetSymbolAtOffset with reference counts and
+// relational data
+func (mi *MasterIndex) GetEnhancedSymbolAtOffset(fileID types.FileID, offset int) *types.EnhancedSymbol {
+	return innermostSymbol(mi.GetFileEnhancedSymbols(fileID), offset)
+}
+
+// innermostSymbol picks the most specific of the symbols containing offset. Spans are
+// half-open, so at the boundary between two adjacent symbols the one starting there
+// wins; a zero-width symbol contains only its own start.
+func innermostSymbol(symbols []*types.EnhancedSymbol, offset int) *types.EnhancedSymbol {
+	var best *types.EnhancedSymbol
+	for _, sym := range symbols {
+		if !spanContains(&sym.Symbol, offset) {
+			continue
+		}
+		if best == nil || moreSpecific(&sym.Symbol, &best.Symbol) {
+			best = sym
+		}
+	}
+	return best
+}
+
+func spanContains(sym *types.Symbol, offset int) bool {
+	if sym.StartByte == sym.EndByte {
+		return offset == sym.StartByte
+	}
+	return offset >= sym.StartByte && offset < sym.EndByte
+}
+
+// moreSpecific reports whether a should be preferred over b when both contain the
+// offset. A symbol nested in the other through its parent always wins, so a method is
+// chosen over the impl or class it belongs to even when their spans coincide; otherwise
+// the narrower span wins, then the later start.
+func moreSpecific(a, b *types.Symbol) bool {
+	switch {
+	case isParentOf(b, a):
+		return true
+	case isParentOf(a, b):
+		return false
+	}
+	if wa, wb := a.EndByte-a.StartByte, b.EndByte-b.StartByte; wa != wb {
+		return wa < wb
+	}
+	return a.StartByte > b.StartByte
+}
+
+// isParentOf reports whether child's Parent names parent. Rust impl blocks are indexed
+// as "impl Type" while their methods record just "Type".
+func isParentOf(parent, child *types.Symbol) bool {
+	if child.Parent == "" {
+		return false
+	}
+	return parent.Name == child.Parent || strings.TrimPrefix(parent.Name, "impl ") == child.Parent
+}
@@ -0,0 +1,50 @@
+package indexing
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestInnermostSymbol(t *testing.T) {
+	sym := func(name, parent string, start, end int) *types.EnhancedSymbol {
+		return &types.EnhancedSymbol{Symbol: types.Symbol{Name: name, Parent: parent, StartByte: start, EndByte: end}}
+	}
+	symbols := []*types.EnhancedSymbol{
+		sym("impl Parser", "", 0, 100),
+		sym("new", "Parser", 10, 40),
+		sym("parse", "Parser", 40, 90),
+		sym("Token", "", 100, 120),
+		sym("Marker", "", 120, 120),
+		// A method whose span coincides with its impl's, as for a one-method impl
+		sym("impl Lexer", "", 200, 250),
+		sym("next", "Lexer", 200, 250),
+	}
+	at := func(offset int) string {
+		if s := innermostSymbol(symbols, offset); s != nil {
+			return s.Name
+		}
+		return ""
+	}
+
+	assert.Equal(t, "impl Parser", at(5))
+	assert.Equal(t, "new", at(10))
+	assert.Equal(t, "parse", at(40), "adjacent spans resolve to the one starting at the offset")
+	assert.Equal(t, "impl Parser", at(95))
+	assert.Equal(t, "Token", at(100))
+	assert.Equal(t, "Marker", at(120), "a zero-width symbol is found at its own offset")
+	assert.Equal(t, "", at(150))
+	assert.Equal(t, "next", at(220), "the parent chain prefers the method over its impl")
+	assert.Equal(t, "", at(250), "spans are half-open")
+}
+
+func TestInnermostSymbol_ZeroWidthAtStart(t *testing.T) {
+	symbols := []*types.EnhancedSymbol{
+		{Symbol: types.Symbol{Name: "Marker", StartByte: 0, EndByte: 0}},
+	}
+
+	assert.Equal(t, "Marker", innermostSymbol(symbols, 0).Name, "a zero-width symbol at offset 0 contains it")
+	assert.Nil(t, innermostSymbol(symbols, 1))
+}
@@ -0,0 +1,96 @@
+package indexing
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSyncFile_UnchangedIsNoOp(t *testing.T) {
+	testDir := t.TempDir()
+	filePath := filepath.Join(testDir, "stable.go")
+	require.NoError(t, os.WriteFile(filePath, []byte("package test\n\nfunc StableSyncFunction() {}\n"), 0644))
+
+	indexer := NewMasterIndex(createProductionFlowTestConfig(testDir))
+	require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+
+	fileID := indexer.fileSnapshot.Load().fileMap[filePath]
+	require.NotZero(t, fileID)
+
+	// First sync has no stamp yet and falls back to a content comparison
+	action, err := indexer.SyncFile(filePath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncUnchanged, action)
+
+	// Second sync is answered by mtime+size alone
+	action, err = indexer.SyncFile(filePath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncUnchanged, action)
+
+	assert.Equal(t, fileID, indexer.fileSnapshot.Load().fileMap[filePath], "no-op sync must not re-index the file")
+}
+
+func TestSyncFile_ModifiedEvictsOldSymbols(t *testing.T) {
+	testDir := t.TempDir()
+	filePath := filepath.Join(testDir, "editable.go")
+	require.NoError(t, os.WriteFile(filePath, []byte("package test\n\nfunc OldSyncFunction() {}\n"), 0644))
+
+	indexer := NewMasterIndex(createProductionFlowTestConfig(testDir))
+	require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+	require.NotEmpty(t, searchAndWait(t, indexer, "OldSyncFunction"))
+
+	require.NoError(t, os.WriteFile(filePath, []byte("package test\n\nfunc NewSyncFunctionName() {}\n"), 0644))
+	// Make sure the mtime moves even on filesystems with coarse timestamps
+	later := time.Now().Add(2 * time.Second)
+	require.NoError(t, os.Chtimes(filePath, later, later))
+
+	action, err := indexer.SyncFile(filePath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncUpdated, action)
+
+	assertResultContainsPath(t, searchAndWait(t, indexer, "NewSyncFunctionName"), "editable.go")
+	assert.Empty(t, indexer.FindSymbolsByName("OldSyncFunction"), "old symbols must be evicted")
+	assertResultNotContainsPath(t, searchAndWait(t, indexer, "OldSyncFunction"), "editable.go", "old content must be evicted")
+
+	// Syncing again without touching the file does nothing
+	action, err = indexer.SyncFile(filePath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncUnchanged, action)
+}
+
+func TestSyncFile_AddAndDelete(t *testing.T) {
+	testDir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "keep.go"), []byte("package test\n\nfunc KeepSyncFunction() {}\n"), 0644))
+
+	indexer := NewMasterIndex(createProductionFlowTestConfig(testDir))
+	require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+
+	newPath := filepath.Join(testDir, "added.go")
+	require.NoError(t, os.WriteFile(newPath, []byte("package test\n\nfunc AddedSyncFunction() {}\n"), 0644))
+
+	action, err := indexer.SyncFile(newPath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncAdded, action)
+	assertResultContainsPath(t, searchAndWait(t, indexer, "AddedSyncFunction"), "added.go")
+
+	require.NoError(t, os.Remove(newPath))
+
+	action, err = indexer.SyncFile(newPath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncRemoved, action)
+	assertResultNotContainsPath(t, searchAndWait(t, indexer, "AddedSyncFunction"), "added.go", "deleted file must be cleared")
+	_, stillMapped := indexer.fileSnapshot.Load().fileMap[newPath]
+	assert.False(t, stillMapped)
+
+	// Deleting an already-removed file is a no-op
+	action, err = indexer.SyncFile(newPath)
+	require.NoError(t, err)
+	assert.Equal(t, SyncUnchanged, action)
+
+	assertResultContainsPath(t, searchAndWait(t, indexer, "KeepSyncFunction"), "keep.go")
+}
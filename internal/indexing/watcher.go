@@ -41,6 +41,11 @@ type FileWatcher struct {
 	// Progress tracking callback
 	onBatchStart func(count int)
 	onBatchEnd   func(count int, duration time.Duration)
+
+	// Real (symlink-resolved) paths of watched directories, shared between the initial
+	// walk and directories created later so symlink cycles can't cause endless re-watching
+	watchedDirs   map[string]bool
+	watchedDirsMu sync.Mutex
 }
 
 // FileEventType represents the type of file system event
@@ -63,12 +68,13 @@ func NewFileWatcher(cfg *config.Config, scanner *FileScanner) (*FileWatcher, err
 	ctx, cancel := context.WithCancel(context.Background())
 
 	fw := &FileWatcher{
-		watcher:   watcher,
-		config:    cfg,
-		debouncer: newEventDebouncer(time.Duration(cfg.Index.WatchDebounceMs) * time.Millisecond),
-		scanner:   scanner,
-		ctx:       ctx,
-		cancel:    cancel,
+		watcher:     watcher,
+		config:      cfg,
+		debouncer:   newEventDebouncer(time.Duration(cfg.Index.WatchDebounceMs) * time.Millisecond),
+		scanner:     scanner,
+		ctx:         ctx,
+		cancel:      cancel,
+		watchedDirs: make(map[string]bool),
 	}
 
 	// Set up the debouncer callbacks
@@ -143,9 +149,6 @@ func (fw *FileWatcher) Stop() error {
 
 // addWatches recursively adds watches to all relevant directories
 func (fw *FileWatcher) addWatches(root string) error {
-	// Track visited directories to prevent infinite loops from symlink cycles
-	visitedDirs := make(map[string]bool)
-
 	return filepath.Walk(root, func(path string, info os.FileInfo, err error) error {
 		if err != nil {
 			return nil // Skip errors, continue walking
@@ -160,10 +163,9 @@ func (fw *FileWatcher) addWatches(root string) error {
 			}
 
 			// Check if we've already visited this real directory
-			if visitedDirs[realPath] {
+			if !fw.markDirWatched(realPath) {
 				return filepath.SkipDir // Skip to prevent cycle
 			}
-			visitedDirs[realPath] = true
 		}
 
 		// Only watch directories
@@ -186,6 +188,17 @@ func (fw *FileWatcher) addWatches(root string) error {
 	})
 }
 
+// markDirWatched records a real directory path, returning false if it is already watched
+func (fw *FileWatcher) markDirWatched(realPath string) bool {
+	fw.watchedDirsMu.Lock()
+	defer fw.watchedDirsMu.Unlock()
+	if fw.watchedDirs[realPath] {
+		return false
+	}
+	fw.watchedDirs[realPath] = true
+	return true
+}
+
 // shouldIgnoreDirectory checks if a directory should be ignored based on configuration
 func (fw *FileWatcher) shouldIgnoreDirectory(path string, info os.FileInfo) bool {
 	// Check exclude patterns
@@ -206,6 +219,11 @@ func (fw *FileWatcher) shouldIgnoreDirectory(path string, info os.FileInfo) bool
 		}
 	}
 
+	// Directories the scan did not descend into stay unwatched
+	if rel, err := filepath.Rel(fw.config.Project.Root, path); err == nil && rel != "." && beyondMaxDepth(fw.config, filepath.ToSlash(rel), true) {
+		return true
+	}
+
 	// Check gitignore if enabled
 	if fw.scanner != nil && fw.scanner.gitignoreParser != nil {
 		// Convert absolute path to relative path from project root for gitignore matching
@@ -306,13 +324,14 @@ func (fw *FileWatcher) handleEvent(event fsnotify.Event) {
 
 // handleDirectoryEvent handles events for directories
 func (fw *FileWatcher) handleDirectoryEvent(event fsnotify.Event, path string, info os.FileInfo) {
-	// If a new directory was created, add a watch for it
+	// If a new directory was created, watch it and any subdirectories it arrived with.
+	// addWatches resolves symlinks, so a new link back into the tree is not watched twice.
 	if event.Op&fsnotify.Create != 0 {
 		if !fw.shouldIgnoreDirectory(path, info) {
-			if err := fw.watcher.Add(path); err != nil {
+			if err := fw.addWatches(path); err != nil {
 				log.Printf("Warning: failed to add watch for new directory %s: %v", path, err)
 			} else {
-				log.Printf("Added watch for new directory: %s", path)
+				debug.LogIndexing("Added watch for new directory: %s\n", path)
 			}
 		}
 	}
@@ -325,6 +344,9 @@ func (fw *FileWatcher) shouldProcessPath(path string) bool {
 
 	// Use the file scanner's logic to determine if we should process this file
 	if fw.scanner != nil {
+		if rel, err := filepath.Rel(fw.config.Project.Root, path); err == nil && sampledOut(fw.config, filepath.ToSlash(rel)) {
+			return false // Left out of the index by --sample, so its changes are too
+		}
 		return fw.scanner.shouldProcessFile(path, info)
 	}
 
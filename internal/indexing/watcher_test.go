@@ -0,0 +1,99 @@
+package indexing
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestFileWatcher_SymlinkCycleWatchedOnce(t *testing.T) {
+	root := t.TempDir()
+	sub := filepath.Join(root, "src")
+	require.NoError(t, os.MkdirAll(filepath.Join(sub, "nested"), 0755))
+	// nested/loop -> root creates a cycle through the tree
+	if err := os.Symlink(root, filepath.Join(sub, "nested", "loop")); err != nil {
+		t.Skipf("symlinks not supported: %v", err)
+	}
+
+	cfg := createProductionFlowTestConfig(root)
+	cfg.Index.WatchMode = true
+	cfg.Index.WatchDebounceMs = 50
+
+	fw, err := NewFileWatcher(cfg, nil)
+	require.NoError(t, err)
+	defer fw.Stop()
+
+	done := make(chan error, 1)
+	go func() { done <- fw.addWatches(root) }()
+
+	select {
+	case err := <-done:
+		require.NoError(t, err)
+	case <-time.After(5 * time.Second):
+		t.Fatal("addWatches did not terminate on a symlink cycle")
+	}
+
+	// Re-adding the same tree (e.g. a directory create event) must not add anything new
+	before := len(fw.watchedDirs)
+	require.NoError(t, fw.addWatches(sub))
+	assert.Equal(t, before, len(fw.watchedDirs))
+	assert.Equal(t, 3, before, "root, src and src/nested")
+}
+
+func TestWatchBatchSummary_String(t *testing.T) {
+	summary := WatchBatchSummary{
+		Added:    1,
+		Updated:  3,
+		Removed:  1,
+		Paths:    []string{"a.go", "b.go", "c.go", "d.go", "e.go"},
+		Duration: 12 * time.Millisecond,
+	}
+	assert.Equal(t, 5, summary.Changed())
+	assert.Equal(t, "re-indexed 5 file(s) in 12ms: 1 added, 3 updated, 1 removed [a.go, b.go, c.go, +2 more]", summary.String())
+
+	quiet := WatchBatchSummary{Unchanged: 2, Duration: time.Millisecond}
+	assert.Equal(t, "re-indexed 0 file(s) in 1ms: 0 added, 0 updated, 0 removed, 2 unchanged", quiet.String())
+}
+
+func TestWatchMode_DebouncedBatchReindexes(t *testing.T) {
+	testDir := t.TempDir()
+	filePath := filepath.Join(testDir, "watched.go")
+	require.NoError(t, os.WriteFile(filePath, []byte("package test\n\nfunc BeforeWatchEdit() {}\n"), 0644))
+
+	cfg := createProductionFlowTestConfig(testDir)
+	cfg.Index.WatchMode = true
+	cfg.Index.WatchDebounceMs = 100
+
+	indexer := NewMasterIndex(cfg)
+	defer indexer.Close()
+
+	batches := make(chan WatchBatchSummary, 10)
+	indexer.SetWatchBatchHandler(func(s WatchBatchSummary) { batches <- s })
+
+	require.NoError(t, indexer.IndexDirectory(context.Background(), testDir))
+	if indexer.fileWatcher == nil {
+		t.Skip("file watcher unavailable on this platform")
+	}
+
+	// A burst of saves should collapse into one batch
+	for i := 0; i < 5; i++ {
+		require.NoError(t, os.WriteFile(filePath, []byte("package test\n\nfunc AfterWatchEdit() {}\n"), 0644))
+		time.Sleep(10 * time.Millisecond)
+	}
+
+	select {
+	case summary := <-batches:
+		assert.Equal(t, 1, summary.Updated)
+		assert.Equal(t, []string{filePath}, summary.Paths)
+	case <-time.After(5 * time.Second):
+		t.Fatal("no watch batch reported")
+	}
+
+	assertResultContainsPath(t, searchAndWait(t, indexer, "AfterWatchEdit"), "watched.go")
+	assertResultNotContainsPath(t, searchAndWait(t, indexer, "BeforeWatchEdit"), "watched.go", "stale content must be gone")
+}
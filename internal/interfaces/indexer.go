@@ -23,6 +23,7 @@ type Indexer interface {
 	GetFileEnhancedSymbols(fileID types.FileID) []*types.EnhancedSymbol
 	GetSymbolAtLine(fileID types.FileID, line int) *types.Symbol
 	GetEnhancedSymbolAtLine(fileID types.FileID, line int) *types.EnhancedSymbol
+	GetSymbolAtOffset(fileID types.FileID, offset int) *types.Symbol // Innermost symbol whose span contains the byte offset
 	FindSymbolsByName(name string) []*types.EnhancedSymbol
 	GetEnhancedSymbol(symbolID types.SymbolID) *types.EnhancedSymbol
 	GetFileLineToSymbols(fileID types.FileID) map[int][]int // Pre-computed line->symbol indices for O(1) semantic filtering
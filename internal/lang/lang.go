@@ -0,0 +1,213 @@
+// Package lang decides which language extractor handles a file. Languages are detected
+// from the file extension; an index's Overrides map further extensions to a known
+// language, so files with unusual extensions can still be parsed, or set the language of
+// one file such as a virtual file read from stdin. Files whose extension maps to no
+// language are indexed as plain text: their content is searchable by substring and
+// regex, but no symbols are extracted.
+package lang
+
+import (
+	"fmt"
+	"path/filepath"
+	"slices"
+	"strings"
+	"sync"
+)
+
+// PlainText is the language reported for files no extractor handles
+const PlainText = "text"
+
+var (
+	mu sync.RWMutex
+
+	// languages maps each language to its extensions; the first one is the extension
+	// the language's extractor is registered under in the parser
+	languages = map[string][]string{
+		"go":         {".go"},
+		"rust":       {".rs"},
+		"python":     {".py"},
+		"javascript": {".js", ".jsx"},
+		"typescript": {".ts", ".tsx"},
+		"java":       {".java"},
+		"cpp":        {".cpp", ".cc", ".cxx", ".c", ".h", ".hpp"},
+		"csharp":     {".cs"},
+		"kotlin":     {".kt", ".kts"},
+		"zig":        {".zig"},
+	}
+
+	// extensions is the reverse of languages
+	extensions = reverse(languages)
+)
+
+func reverse(languages map[string][]string) map[string]string {
+	m := make(map[string]string)
+	for language, exts := range languages {
+		for _, ext := range exts {
+			m[ext] = language
+		}
+	}
+	return m
+}
+
+// Register adds a language and its extensions, or more extensions for a known language.
+// A new language extractor calls it with the extension its parser is registered under
+// first.
+func Register(language string, exts ...string) {
+	mu.Lock()
+	defer mu.Unlock()
+	for _, ext := range exts {
+		ext = normalizeExt(ext)
+		if !slices.Contains(languages[language], ext) {
+			languages[language] = append(languages[language], ext)
+		}
+		extensions[ext] = language
+	}
+}
+
+// Overrides maps extensions, and single files, to the language they parse as, taking
+// precedence over detection. Each index builds its own from its configuration, so overrides never
+// leak between indexes. They are set before indexing starts and only read after. A nil
+// *Overrides overrides nothing.
+type Overrides struct {
+	exts  map[string]string
+	files map[string]string
+}
+
+// NewOverrides returns an empty set of overrides
+func NewOverrides() *Overrides {
+	return &Overrides{exts: make(map[string]string), files: make(map[string]string)}
+}
+
+// Set makes files with extension ext parse as language. language must be known.
+func (o *Overrides) Set(ext, language string) error {
+	if err := ValidateLanguage(language); err != nil {
+		return err
+	}
+	o.exts[normalizeExt(ext)] = language
+	return nil
+}
+
+// SetFile makes the file at path parse as language whatever its extension, taking
+// precedence over Set. language must be known.
+func (o *Overrides) SetFile(path, language string) error {
+	language = strings.ToLower(strings.TrimSpace(language))
+	if err := ValidateLanguage(language); err != nil {
+		return err
+	}
+	o.files[path] = language
+	return nil
+}
+
+// ValidateLanguage returns an error listing the known languages when language is not one
+func ValidateLanguage(language string) error {
+	mu.RLock()
+	defer mu.RUnlock()
+	if _, ok := languages[language]; !ok {
+		return fmt.Errorf("unknown language %q (known: %s)", language, strings.Join(namesLocked(), ", "))
+	}
+	return nil
+}
+
+// ParseOverride splits an EXT=LANG override such as ".tpl=go" or "inc=cpp"
+func ParseOverride(spec string) (ext, language string, err error) {
+	ext, language, ok := strings.Cut(spec, "=")
+	ext, language = strings.TrimSpace(ext), strings.ToLower(strings.TrimSpace(language))
+	if !ok || strings.Trim(ext, ".") == "" || language == "" {
+		return "", "", fmt.Errorf("language override %q must look like EXT=LANG, e.g. .tpl=go", spec)
+	}
+	return normalizeExt(ext), language, nil
+}
+
+// ForExtension returns the language for a file extension such as ".rs", or "" when no
+// extractor handles it
+func ForExtension(ext string) string {
+	return (*Overrides)(nil).ForExtension(ext)
+}
+
+// ForExtension is the package ForExtension with o applied
+func (o *Overrides) ForExtension(ext string) string {
+	if o != nil {
+		if language, ok := o.exts[ext]; ok {
+			return language
+		}
+	}
+	mu.RLock()
+	defer mu.RUnlock()
+	return extensions[ext]
+}
+
+// Detect returns the language of the file at path, or "" for plain text
+func Detect(path string) string {
+	return (*Overrides)(nil).Detect(path)
+}
+
+// Detect is the package Detect with o applied
+func (o *Overrides) Detect(path string) string {
+	if o != nil {
+		if language, ok := o.files[path]; ok {
+			return language
+		}
+	}
+	return o.ForExtension(filepath.Ext(path))
+}
+
+// ParserExtension returns the extension the parser should treat path as having: its own,
+// unless an override maps it to a language whose extractor is registered under another
+func ParserExtension(path string) string {
+	return (*Overrides)(nil).ParserExtension(path)
+}
+
+// ParserExtension is the package ParserExtension with o applied
+func (o *Overrides) ParserExtension(path string) string {
+	ext := filepath.Ext(path)
+	if o == nil {
+		return ext
+	}
+	language, ok := o.files[path]
+	if !ok {
+		language, ok = o.exts[ext]
+	}
+	if !ok {
+		return ext
+	}
+	mu.RLock()
+	defer mu.RUnlock()
+	return languages[language][0]
+}
+
+// Names returns the known languages, sorted
+func Names() []string {
+	mu.RLock()
+	defer mu.RUnlock()
+	return namesLocked()
+}
+
+func namesLocked() []string {
+	names := make([]string, 0, len(languages))
+	for name := range languages {
+		names = append(names, name)
+	}
+	slices.Sort(names)
+	return names
+}
+
+// Count tallies paths per language detected with o, with PlainText for the rest
+func (o *Overrides) Count(paths []string) map[string]int {
+	counts := make(map[string]int)
+	for _, path := range paths {
+		language := o.Detect(path)
+		if language == "" {
+			language = PlainText
+		}
+		counts[language]++
+	}
+	return counts
+}
+
+func normalizeExt(ext string) string {
+	ext = strings.TrimSpace(ext)
+	if !strings.HasPrefix(ext, ".") {
+		ext = "." + ext
+	}
+	return ext
+}
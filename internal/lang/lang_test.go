@@ -0,0 +1,87 @@
+package lang
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestDetect(t *testing.T) {
+	tests := map[string]string{
+		"src/main.rs":     "rust",
+		"cmd/lci/main.go": "go",
+		"web/app.tsx":     "typescript",
+		"include/util.h":  "cpp",
+		"README.md":       "",
+		"Makefile":        "",
+	}
+	for path, want := range tests {
+		assert.Equal(t, want, Detect(path), path)
+	}
+}
+
+func TestOverrides(t *testing.T) {
+	o := NewOverrides()
+	require.NoError(t, o.Set(".lcitpl", "go"))
+	assert.Equal(t, "go", o.Detect("views/page.lcitpl"))
+	assert.Equal(t, ".go", o.ParserExtension("views/page.lcitpl"), "an override parses with the language's extractor")
+	assert.Equal(t, ".tsx", o.ParserExtension("web/app.tsx"), "detected files keep their own extension")
+	assert.Empty(t, Detect("views/page.lcitpl"), "overrides apply only where they are passed")
+
+	require.NoError(t, o.Set("rs", "cpp"))
+	assert.Equal(t, "cpp", o.Detect("src/main.rs"), "overrides take precedence over detection")
+	assert.Equal(t, "rust", NewOverrides().Detect("src/main.rs"), "another index keeps detection")
+
+	err := o.Set(".lcix", "cobol")
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), "rust", "the error lists the known languages")
+	assert.Empty(t, o.Detect("a.lcix"))
+
+	var none *Overrides
+	assert.Equal(t, "go", none.Detect("main.go"))
+}
+
+func TestOverridesSetFile(t *testing.T) {
+	o := NewOverrides()
+	require.NoError(t, o.SetFile("<stdin>", "Rust"))
+	assert.Equal(t, "rust", o.Detect("<stdin>"))
+	assert.Equal(t, ".rs", o.ParserExtension("<stdin>"))
+	assert.Empty(t, Detect("<stdin>"), "a file override applies only where it is passed")
+
+	require.NoError(t, o.Set(".go", "cpp"))
+	require.NoError(t, o.SetFile("staged/page.go", "rust"))
+	assert.Equal(t, ".rs", o.ParserExtension("staged/page.go"), "a file override beats the extension")
+	assert.Equal(t, ".cpp", o.ParserExtension("other/page.go"), "only that file is overridden")
+
+	assert.Error(t, o.SetFile("<stdin>", "cobol"))
+}
+
+func TestParseOverride(t *testing.T) {
+	ext, language, err := ParseOverride("tpl=Go")
+	require.NoError(t, err)
+	assert.Equal(t, ".tpl", ext)
+	assert.Equal(t, "go", language)
+
+	for _, spec := range []string{"tpl", "=go", ".=go", ".tpl="} {
+		_, _, err := ParseOverride(spec)
+		assert.Error(t, err, spec)
+	}
+}
+
+func TestRegister(t *testing.T) {
+	Register("lcilang", ".lcil", "lcim")
+	assert.Equal(t, "lcilang", Detect("a.lcim"))
+	assert.Contains(t, Names(), "lcilang")
+
+	o := NewOverrides()
+	require.NoError(t, o.Set(".lcio", "lcilang"))
+	assert.Equal(t, ".lcil", o.ParserExtension("b.lcio"))
+}
+
+func TestCount(t *testing.T) {
+	o := NewOverrides()
+	require.NoError(t, o.Set(".tpl", "go"))
+	counts := o.Count([]string{"a.go", "b.tpl", "c.rs", "README.md", "LICENSE"})
+	assert.Equal(t, map[string]int{"go": 2, "rust": 1, PlainText: 2}, counts)
+}
@@ -0,0 +1,111 @@
+package parser
+
+import (
+	"bytes"
+	"slices"
+	"strings"
+	"unicode"
+)
+
+// leadingDocComment returns the comment block written directly above the declaration
+// starting at byte start, with comment markers stripped and lines joined by "\n".
+// A blank line ends the block; attribute and decorator lines between the comment and
+// the declaration (#[derive(...)], @Override) are skipped.
+func leadingDocComment(content []byte, start int) string {
+	if start <= 0 || start > len(content) {
+		return ""
+	}
+	lineStart := bytes.LastIndexByte(content[:start], '\n') + 1
+	if !isKeywordPrefix(content[lineStart:start]) {
+		return "" // Not the first declaration on its line, e.g. a parameter of `fn f(a: u32)`
+	}
+
+	var lines []string
+	inBlock := false
+scan:
+	for end := lineStart; end > 0; {
+		prevStart := bytes.LastIndexByte(content[:end-1], '\n') + 1
+		line := strings.TrimSpace(string(content[prevStart : end-1]))
+		end = prevStart
+
+		switch {
+		case inBlock:
+			inBlock = !strings.HasPrefix(line, "/*")
+			lines = append(lines, blockCommentText(line))
+		case strings.HasSuffix(line, "*/"):
+			inBlock = !strings.HasPrefix(line, "/*")
+			if inBlock && strings.Contains(line, "/*") {
+				break scan // Trailing comment on a line of code
+			}
+			lines = append(lines, blockCommentText(line))
+		case isLineComment(line):
+			lines = append(lines, lineCommentText(line))
+		case len(lines) == 0 && isAttributeLine(line):
+			// Attributes sit between the doc comment and the declaration
+		default:
+			break scan
+		}
+	}
+
+	slices.Reverse(lines)
+	for len(lines) > 0 && lines[0] == "" {
+		lines = lines[1:]
+	}
+	for len(lines) > 0 && lines[len(lines)-1] == "" {
+		lines = lines[:len(lines)-1]
+	}
+	return strings.Join(lines, "\n")
+}
+
+// isKeywordPrefix reports whether the text before a declaration on its line is only
+// words such as `pub fn` or `type`, so symbols that start at their name still qualify
+func isKeywordPrefix(prefix []byte) bool {
+	for _, c := range prefix {
+		if c != ' ' && c != '\t' && c != '_' && !unicode.IsLetter(rune(c)) && !unicode.IsDigit(rune(c)) {
+			return false
+		}
+	}
+	return true
+}
+
+// isLineComment reports whether a trimmed line is a //, ///, //! or # comment. A #
+// must be followed by a space, another # or nothing, so #[attr], #!shebang and
+// #include lines are not comments.
+func isLineComment(line string) bool {
+	if strings.HasPrefix(line, "//") {
+		return true
+	}
+	if !strings.HasPrefix(line, "#") {
+		return false
+	}
+	return len(line) == 1 || line[1] == ' ' || line[1] == '\t' || line[1] == '#'
+}
+
+// lineCommentText strips the comment marker from a trimmed line comment
+func lineCommentText(line string) string {
+	for _, marker := range []string{"///", "//!", "//"} {
+		if strings.HasPrefix(line, marker) {
+			return strings.TrimSpace(line[len(marker):])
+		}
+	}
+	return strings.TrimSpace(strings.TrimLeft(line, "#"))
+}
+
+// blockCommentText strips /**, /*, */ and a leading * from one line of a block comment
+func blockCommentText(line string) string {
+	line = strings.TrimSuffix(line, "*/")
+	if rest, ok := strings.CutPrefix(line, "/**"); ok {
+		line = rest
+	} else if rest, ok := strings.CutPrefix(line, "/*"); ok {
+		line = rest
+	} else {
+		line = strings.TrimPrefix(line, "*")
+	}
+	return strings.TrimSpace(line)
+}
+
+// isAttributeLine reports whether a trimmed line is a Rust attribute or a decorator /
+// annotation that may sit between a doc comment and its declaration
+func isAttributeLine(line string) bool {
+	return strings.HasPrefix(line, "#[") || strings.HasPrefix(line, "#![") || strings.HasPrefix(line, "@")
+}
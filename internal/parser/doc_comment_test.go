@@ -0,0 +1,52 @@
+package parser
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestLeadingDocComment(t *testing.T) {
+	tests := []struct {
+		name string
+		code string // The declaration is the last line
+		want string
+	}{
+		{"rust outer doc", "/// Retrieves a user\n/// by id.\npub fn get_user() {}", "Retrieves a user\nby id."},
+		{"rust inner doc and attribute", "//! Module docs\n#[inline]\nfn f() {}", "Module docs"},
+		{"go line comments", "// GetUser retrieves a user.\nfunc GetUser() {}", "GetUser retrieves a user."},
+		{"javadoc block", "/**\n * Retrieves a user.\n *\n * @param id the id\n */\n@Override\npublic User get() {}", "Retrieves a user.\n\n@param id the id"},
+		{"one-line block", "/* Retrieves a user. */\nint get(void);", "Retrieves a user."},
+		{"hash comments", "# Retrieves a user.\nget_user <- function() {}", "Retrieves a user."},
+		{"blank line ends the block", "// unrelated\n\nfn f() {}", ""},
+		{"code above", "let x = 1;\nfn f() {}", ""},
+		{"trailing comment on code", "/* a */\nlet x = 1; /* b */\nfn f() {}", ""},
+		{"only nearest block", "// first\n\n// second\nfn f() {}", "second"},
+		{"indented method", "impl A {\n    /// Does it.\n    fn f() {}", "Does it."},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			start := strings.LastIndexByte(tt.code, '\n') + 1
+			start += len(tt.code[start:]) - len(strings.TrimLeft(tt.code[start:], " "))
+			assert.Equal(t, tt.want, leadingDocComment([]byte(tt.code), start))
+		})
+	}
+
+	t.Run("not first on its line", func(t *testing.T) {
+		code := "/// Docs\nfn f(a: u32) {}"
+		assert.Equal(t, "", leadingDocComment([]byte(code), len("/// Docs\nfn f(")))
+	})
+}
+
+func TestEnhancedSymbolDocComment(t *testing.T) {
+	code := "struct Store;\n\nimpl Store {\n    /// Retrieves a user by id.\n    #[inline]\n    pub fn get_user(&self) {}\n\n    pub fn undocumented(&self) {}\n}\n"
+	_, _, _, enhanced, _, _ := NewTreeSitterParser().ParseFileEnhanced("store.rs", []byte(code))
+
+	docs := make(map[string]string)
+	for _, sym := range enhanced {
+		docs[sym.Name] = sym.DocComment
+	}
+	assert.Equal(t, "Retrieves a user by id.", docs["get_user"])
+	assert.Empty(t, docs["undocumented"])
+}
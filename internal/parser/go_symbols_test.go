@@ -0,0 +1,75 @@
+package parser
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestGoFixtureSymbols verifies the go-sample fixture produces the same symbol shape
+// as the rust-sample fixture: receiver methods and interface methods carry a parent.
+func TestGoFixtureSymbols(t *testing.T) {
+	mainPath, _ := filepath.Abs("../../tests/search-comparison/fixtures/go-sample/main.go")
+	if _, err := os.Stat(mainPath); os.IsNotExist(err) {
+		t.Skip("Test fixture not found")
+	}
+	content, err := os.ReadFile(mainPath)
+	require.NoError(t, err)
+
+	parser := NewTreeSitterParser()
+	_, symbols, _ := parser.ParseFile(mainPath, content)
+
+	expected := []struct {
+		name   string
+		kind   types.SymbolType
+		parent string
+	}{
+		{"UserService", types.SymbolTypeStruct, ""},
+		{"Database", types.SymbolTypeInterface, ""},
+		{"FindUser", types.SymbolTypeMethod, "Database"},
+		{"SaveUser", types.SymbolTypeMethod, "Database"},
+		{"User", types.SymbolTypeStruct, ""},
+		{"NewUserService", types.SymbolTypeFunction, ""},
+		{"GetUser", types.SymbolTypeMethod, "UserService"},
+		{"CreateUser", types.SymbolTypeMethod, "UserService"},
+		{"HandleUserRequest", types.SymbolTypeFunction, ""},
+	}
+
+	for _, exp := range expected {
+		t.Run(exp.name, func(t *testing.T) {
+			var found *types.Symbol
+			for i := range symbols {
+				if symbols[i].Name == exp.name && symbols[i].Type == exp.kind {
+					found = &symbols[i]
+					break
+				}
+			}
+			require.NotNil(t, found, "symbol %s (%s) not extracted", exp.name, exp.kind)
+			assert.Equal(t, exp.parent, found.Parent)
+			assert.Less(t, found.StartByte, found.EndByte)
+		})
+	}
+}
+
+func TestBaseReceiverType(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{"UserService", "UserService"},
+		{"*UserService", "UserService"},
+		{"* UserService", "UserService"},
+		{"*Cache[K, V]", "Cache"},
+		{"List[T]", "List"},
+		{"", ""},
+	}
+
+	for _, tt := range tests {
+		assert.Equal(t, tt.expected, baseReceiverType(tt.input), "input %q", tt.input)
+	}
+}
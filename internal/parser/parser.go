@@ -12,6 +12,7 @@ import (
 
 	"github.com/standardbeagle/lci/internal/core"
 	"github.com/standardbeagle/lci/internal/debug"
+	"github.com/standardbeagle/lci/internal/lang"
 	"github.com/standardbeagle/lci/internal/types"
 )
 
@@ -32,6 +33,10 @@ type TreeSitterParser struct {
 	childNodeCache   map[uintptr][]*tree_sitter.Node // node ptr -> cached children
 	// Performance optimization: Track if this is a shared instance
 	isShared bool
+	// Syntax error of the most recent parse, see LastSyntaxError
+	lastSyntaxError string
+	// Language overrides of the index parsing with this instance, see SetLanguages
+	languages *lang.Overrides
 }
 
 type BlockBoundary struct {
@@ -325,37 +330,15 @@ func GetSharedParserWithStore(store *core.FileContentStore) *TreeSitterParser {
 // This must be called when done with the parser
 func ReleaseParser(p *TreeSitterParser) {
 	if p != nil {
+		p.languages = nil
 		parserPool.Put(p)
 	}
 }
 
-// GetLanguageFromExtension returns the language name for a given file extension
+// GetLanguageFromExtension returns the language name for a given file extension, or ""
+// when no extractor handles it. The mapping lives in the lang package.
 func GetLanguageFromExtension(ext string) string {
-	// Map extensions to language names for parser cache optimization
-	switch ext {
-	case ".js", ".jsx":
-		return "javascript"
-	case ".ts", ".tsx":
-		return "typescript"
-	case ".go":
-		return "go"
-	case ".py":
-		return "python"
-	case ".rs":
-		return "rust"
-	case ".cpp", ".cc", ".cxx", ".c", ".h", ".hpp":
-		return "cpp"
-	case ".java":
-		return "java"
-	case ".cs":
-		return "csharp"
-	case ".kt", ".kts":
-		return "kotlin"
-	case ".zig":
-		return "zig"
-	default:
-		return ""
-	}
+	return lang.ForExtension(ext)
 }
 
 // NewProjectSpecificParser creates a parser optimized for a specific project
@@ -385,7 +368,7 @@ func scanProjectForExtensions(projectRoot string) map[string]bool {
 			return nil
 		}
 
-		ext := filepath.Ext(path)
+		ext := lang.ParserExtension(path)
 		if ext != "" && len(ext) <= 5 { // Reasonable extension length
 			extensions[ext] = true
 		}
@@ -445,6 +428,7 @@ func ReleaseParserToPool(p *TreeSitterParser, language Language) {
 	if p == nil {
 		return
 	}
+	p.languages = nil
 
 	data, exists := parserPools[language]
 	if !exists {
@@ -495,6 +479,12 @@ func (p *TreeSitterParser) SetFileContentStore(store *core.FileContentStore) {
 	p.fileContentStore = store
 }
 
+// SetLanguages sets the language overrides that pick the extractor for each path.
+// Parsers are returned to their pool without them.
+func (p *TreeSitterParser) SetLanguages(languages *lang.Overrides) {
+	p.languages = languages
+}
+
 // setupQueryForLanguage provides consistent error handling for tree-sitter query setup
 func (p *TreeSitterParser) setupQueryForLanguage(language string, extensions []string, queryStr string, tsLanguage *tree_sitter.Language) error {
 	query, _ := tree_sitter.NewQuery(tsLanguage, queryStr)
@@ -569,31 +559,10 @@ func (p *TreeSitterParser) ensureParserInitialized(ext string) bool {
 
 // Phase 5: GetLanguageFromExtension returns language name for cache optimization
 func (p *TreeSitterParser) GetLanguageFromExtension(ext string) string {
-	// Phase 5: Map extensions to language names for parser cache optimization
-	switch ext {
-	case ".js", ".jsx":
-		return "javascript"
-	case ".ts", ".tsx":
-		return "typescript"
-	case ".go":
-		return "go"
-	case ".py":
-		return "python"
-	case ".rs":
-		return "rust"
-	case ".cpp", ".cc", ".cxx", ".c", ".h", ".hpp":
-		return "cpp"
-	case ".java":
-		return "java"
-	case ".cs": // Phase 5A: C# language support
-		return "csharp"
-	case ".kt", ".kts": // Phase 5B: Kotlin language support
-		return "kotlin"
-	case ".zig": // Phase 5C: Zig language support
-		return "zig"
-	default:
-		return "unknown"
+	if language := lang.ForExtension(ext); language != "" {
+		return language
 	}
+	return "unknown"
 }
 
 // GetSupportedLanguages returns a list of all supported languages
@@ -624,6 +593,24 @@ func (p *TreeSitterParser) ParseFileEnhanced(path string, content []byte) ([]typ
 	return p.ParseFileEnhancedWithContext(context.Background(), path, content)
 }
 
+// ParseTree parses content into its tree-sitter tree without extracting anything,
+// for parses whose symbols were reused but whose tree is needed again. It returns nil
+// for languages without a parser.
+func (p *TreeSitterParser) ParseTree(path string, content []byte) *tree_sitter.Tree {
+	ext := p.languages.ParserExtension(path)
+	if !p.ensureParserInitialized(ext) {
+		return nil
+	}
+	parser, ok := p.parsers[ext]
+	if !ok {
+		return nil
+	}
+	// Tree-sitter mutates its input buffer, as in ParseFileWithSideEffects
+	parserBuffer := make([]byte, len(content))
+	copy(parserBuffer, content)
+	return parser.Parse(parserBuffer, nil)
+}
+
 // ParseFileEnhancedWithAST extracts enhanced symbols and returns the AST for storage
 func (p *TreeSitterParser) ParseFileEnhancedWithAST(path string, content []byte) (*tree_sitter.Tree, []types.BlockBoundary, []types.Symbol, []types.Import, []types.EnhancedSymbol, []types.Reference, []types.ScopeInfo) {
 	return p.ParseFileEnhancedWithASTAndContext(context.Background(), path, content)
@@ -674,7 +661,7 @@ func isDeclarationNode(nodeType string) bool {
 
 // ParseFileEnhancedWithASTAndContextStringRef extracts enhanced symbols using StringRef for zero-copy operations
 func (p *TreeSitterParser) ParseFileEnhancedWithASTAndContextStringRef(ctx context.Context, path string, content []byte, fileID types.FileID) (*tree_sitter.Tree, []types.BlockBoundary, []types.Symbol, []types.Import, []types.EnhancedSymbol, []types.Reference, []types.ScopeInfo) {
-	ext := filepath.Ext(path)
+	ext := p.languages.ParserExtension(path)
 
 	// Phase 5: Ensure parser is initialized on first use (30% memory reduction)
 	if !p.ensureParserInitialized(ext) {
@@ -730,7 +717,7 @@ func (p *TreeSitterParser) ParseFileEnhancedWithASTAndContextStringRef(ctx conte
 // ParseFileWithPerfData parses a file and returns performance analysis data alongside standard results.
 // This method extracts loops, awaits, and calls during AST traversal for performance anti-pattern detection.
 func (p *TreeSitterParser) ParseFileWithPerfData(ctx context.Context, path string, content []byte, fileID types.FileID) (*tree_sitter.Tree, []types.BlockBoundary, []types.Symbol, []types.Import, []types.EnhancedSymbol, []types.Reference, []types.ScopeInfo, []types.FunctionPerfData) {
-	ext := filepath.Ext(path)
+	ext := p.languages.ParserExtension(path)
 
 	// Phase 5: Ensure parser is initialized on first use (30% memory reduction)
 	if !p.ensureParserInitialized(ext) {
@@ -790,7 +777,8 @@ func (p *TreeSitterParser) ParseFileWithSideEffects(ctx context.Context, path st
 	perfData []types.FunctionPerfData,
 	sideEffects map[string]*types.SideEffectInfo,
 ) {
-	ext := filepath.Ext(path)
+	ext := p.languages.ParserExtension(path)
+	p.lastSyntaxError = ""
 
 	// Ensure parser is initialized
 	if !p.ensureParserInitialized(ext) {
@@ -806,6 +794,7 @@ func (p *TreeSitterParser) ParseFileWithSideEffects(ctx context.Context, path st
 	defer func() {
 		if r := recover(); r != nil {
 			debug.LogIndexing("TREE-SITTER PANIC in file %s: %v", path, r)
+			p.lastSyntaxError = fmt.Sprintf("parser panic: %v", r)
 		}
 	}()
 
@@ -817,6 +806,7 @@ func (p *TreeSitterParser) ParseFileWithSideEffects(ctx context.Context, path st
 	if tree == nil {
 		return nil, nil, nil, nil, nil, nil, nil, nil, nil
 	}
+	p.lastSyntaxError = SyntaxError(tree)
 
 	// UNIFIED SINGLE-PASS EXTRACTION with side effect tracking enabled
 	extractor := NewUnifiedExtractor(p, parserBuffer, fileID, ext, path)
@@ -893,7 +883,8 @@ func convertPerfResultsToTypes(results []PerfAnalysisResult) []types.FunctionPer
 
 // ParseFileEnhancedWithContext extracts enhanced symbols with context cancellation support
 func (p *TreeSitterParser) ParseFileEnhancedWithContext(ctx context.Context, path string, content []byte) ([]types.BlockBoundary, []types.Symbol, []types.Import, []types.EnhancedSymbol, []types.Reference, []types.ScopeInfo) {
-	ext := filepath.Ext(path)
+	ext := p.languages.ParserExtension(path)
+	p.lastSyntaxError = ""
 
 	// Phase 5: Ensure parser is initialized on first use (30% memory reduction)
 	if !p.ensureParserInitialized(ext) {
@@ -911,6 +902,7 @@ func (p *TreeSitterParser) ParseFileEnhancedWithContext(ctx context.Context, pat
 			// CRITICAL: Log parser panics instead of silently swallowing them
 			// This helps identify problematic files and parsing issues
 			debug.LogIndexing("TREE-SITTER PANIC in file %s: %v", path, r)
+			p.lastSyntaxError = fmt.Sprintf("parser panic: %v", r)
 			// Don't return nil - let the calling code handle the parsing failure
 		}
 	}()
@@ -926,6 +918,7 @@ func (p *TreeSitterParser) ParseFileEnhancedWithContext(ctx context.Context, pat
 		return nil, nil, nil, nil, nil, nil
 	}
 	defer tree.Close()
+	p.lastSyntaxError = SyntaxError(tree)
 
 	// UNIFIED SINGLE-PASS EXTRACTION
 	// Uses UnifiedExtractor to extract ALL data in one tree walk:
@@ -1617,6 +1610,11 @@ func (p *TreeSitterParser) buildEnhancedSymbols(content []byte, symbols []types.
 			complexityMap,
 			scopeChainCache,
 		)
+		// The AST lookup only covers some declaration kinds and keeps a single raw
+		// comment node; scanning the source covers every language's leading doc block
+		if doc := leadingDocComment(content, symbol.StartByte); doc != "" {
+			enhancedSymbol.DocComment = doc
+		}
 		enhancedSymbols = append(enhancedSymbols, enhancedSymbol)
 	}
 
@@ -1699,6 +1697,8 @@ func (p *TreeSitterParser) buildSingleEnhancedSymbol(
 		DocComment: docComment, // From single-pass extraction
 		IsExported: p.isSymbolExported(symbol.Name),
 		Complexity: complexity, // Cyclomatic complexity for functions/methods
+
+		ReceiverType: symbol.Parent, // Go receiver or enclosing Rust impl/trait type
 	}
 }
 
@@ -0,0 +1,131 @@
+package parser
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestPythonFixtureSymbols verifies the python-sample fixture yields the same symbols as
+// its Go and Rust counterparts: classes, methods with their class as parent and free
+// functions.
+func TestPythonFixtureSymbols(t *testing.T) {
+	mainPath, _ := filepath.Abs("../../tests/search-comparison/fixtures/python-sample/main.py")
+	if _, err := os.Stat(mainPath); os.IsNotExist(err) {
+		t.Skip("Test fixture not found")
+	}
+	content, err := os.ReadFile(mainPath)
+	require.NoError(t, err)
+
+	parser := NewTreeSitterParser()
+	_, symbols, _ := parser.ParseFile(mainPath, content)
+
+	expected := []struct {
+		name   string
+		kind   types.SymbolType
+		parent string
+	}{
+		{"User", types.SymbolTypeClass, ""},
+		{"Database", types.SymbolTypeClass, ""},
+		{"find_user", types.SymbolTypeMethod, "Database"},
+		{"save_user", types.SymbolTypeMethod, "Database"},
+		{"UserService", types.SymbolTypeClass, ""},
+		{"get_user", types.SymbolTypeMethod, "UserService"},
+		{"create_user", types.SymbolTypeMethod, "UserService"},
+		{"handle_user_request", types.SymbolTypeFunction, ""},
+	}
+
+	for _, exp := range expected {
+		t.Run(exp.name, func(t *testing.T) {
+			found := findSymbol(symbols, exp.name)
+			require.NotNil(t, found, "symbol %s not extracted", exp.name)
+			assert.Equal(t, exp.kind, found.Type)
+			assert.Equal(t, exp.parent, found.Parent)
+		})
+	}
+}
+
+// TestPythonDecoratedNestedAndModuleSymbols covers decorators in the symbol span,
+// nested functions, nested classes and module-level assignments.
+func TestPythonDecoratedNestedAndModuleSymbols(t *testing.T) {
+	code := `MAX_RETRIES = 3
+first, second = 1, 2
+user_cache: dict = {}
+
+
+@cached
+@route("/users")
+def list_users():
+    limit = 10
+
+    def render(user):
+        return user
+
+    return [render(u) for u in user_cache]
+
+
+@dataclass
+class Account:
+    balance = 0
+
+    @property
+    def owner(self):
+        return None
+
+    class Meta:
+        table = "accounts"
+`
+
+	parser := NewTreeSitterParser()
+	_, symbols, _ := parser.ParseFile("service.py", []byte(code))
+
+	for _, name := range []string{"MAX_RETRIES", "first", "second", "user_cache"} {
+		found := findSymbol(symbols, name)
+		require.NotNil(t, found, "module-level %s not extracted", name)
+		assert.Equal(t, types.SymbolTypeVariable, found.Type, name)
+	}
+	for _, name := range []string{"limit", "balance", "table"} {
+		assert.Nil(t, findSymbol(symbols, name), "%s is a local or class attribute, not a module symbol", name)
+	}
+
+	listUsers := findSymbol(symbols, "list_users")
+	require.NotNil(t, listUsers)
+	assert.Equal(t, types.SymbolTypeFunction, listUsers.Type)
+	assert.Equal(t, 8, listUsers.Line, "the symbol line stays on the def")
+	span := code[listUsers.StartByte:listUsers.EndByte]
+	assert.True(t, strings.HasPrefix(span, "@cached\n@route(\"/users\")\ndef list_users():"), "span %q should start at the first decorator", span)
+
+	render := findSymbol(symbols, "render")
+	require.NotNil(t, render)
+	assert.Equal(t, types.SymbolTypeFunction, render.Type, "a function nested in a function is not a method")
+	assert.Equal(t, "list_users", render.Parent)
+
+	account := findSymbol(symbols, "Account")
+	require.NotNil(t, account)
+	assert.True(t, strings.HasPrefix(code[account.StartByte:account.EndByte], "@dataclass\nclass Account:"))
+
+	owner := findSymbol(symbols, "owner")
+	require.NotNil(t, owner)
+	assert.Equal(t, types.SymbolTypeMethod, owner.Type)
+	assert.Equal(t, "Account", owner.Parent)
+	assert.True(t, strings.HasPrefix(code[owner.StartByte:owner.EndByte], "@property\n    def owner(self):"))
+
+	meta := findSymbol(symbols, "Meta")
+	require.NotNil(t, meta)
+	assert.Equal(t, "Account", meta.Parent)
+}
+
+func findSymbol(symbols []types.Symbol, name string) *types.Symbol {
+	for i := range symbols {
+		if symbols[i].Name == name {
+			return &symbols[i]
+		}
+	}
+	return nil
+}
@@ -0,0 +1,170 @@
+package parser
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestRustFixtureSymbols verifies the rust-sample fixture yields symbol-aware results:
+// structs, traits, trait method signatures, impl methods and free functions, each with
+// a byte span that covers the declaration and the enclosing trait/impl as parent.
+func TestRustFixtureSymbols(t *testing.T) {
+	mainPath, _ := filepath.Abs("../../tests/search-comparison/fixtures/rust-sample/src/main.rs")
+	if _, err := os.Stat(mainPath); os.IsNotExist(err) {
+		t.Skip("Test fixture not found")
+	}
+	content, err := os.ReadFile(mainPath)
+	require.NoError(t, err)
+
+	parser := NewTreeSitterParser()
+	_, symbols, _ := parser.ParseFile(mainPath, content)
+
+	expected := []struct {
+		name   string
+		kind   types.SymbolType
+		parent string
+		prefix string
+	}{
+		{"User", types.SymbolTypeStruct, "", "pub struct User {"},
+		{"Database", types.SymbolTypeTrait, "", "pub trait Database {"},
+		{"find_user", types.SymbolTypeMethod, "Database", "fn find_user(&self, id: &str)"},
+		{"save_user", types.SymbolTypeMethod, "Database", "fn save_user(&self, user: &User)"},
+		{"UserService", types.SymbolTypeStruct, "", "pub struct UserService<D: Database> {"},
+		{"new", types.SymbolTypeMethod, "UserService", "pub fn new(db: D) -> Self {"},
+		{"get_user", types.SymbolTypeMethod, "UserService", "pub fn get_user(&self, id: &str)"},
+		{"create_user", types.SymbolTypeMethod, "UserService", "pub fn create_user(&self, username: String"},
+		{"handle_user_request", types.SymbolTypeFunction, "", "pub fn handle_user_request() -> String {"},
+	}
+
+	for _, exp := range expected {
+		t.Run(exp.name, func(t *testing.T) {
+			var found *types.Symbol
+			for i := range symbols {
+				if symbols[i].Name == exp.name && symbols[i].Type == exp.kind {
+					found = &symbols[i]
+					break
+				}
+			}
+			require.NotNil(t, found, "symbol %s (%s) not extracted", exp.name, exp.kind)
+
+			assert.Equal(t, exp.parent, found.Parent)
+			require.Less(t, found.StartByte, found.EndByte)
+			require.LessOrEqual(t, found.EndByte, len(content))
+
+			span := string(content[found.StartByte:found.EndByte])
+			assert.True(t, strings.HasPrefix(span, exp.prefix), "span for %s starts with %q", exp.name, span)
+			if exp.kind == types.SymbolTypeMethod && exp.parent == "Database" {
+				assert.True(t, strings.HasSuffix(span, ";"), "trait signature span should include the terminating semicolon")
+			} else {
+				assert.True(t, strings.HasSuffix(span, "}"), "span for %s should end at the closing brace", exp.name)
+			}
+		})
+	}
+}
+
+// TestRustMultiLineTraitSignatureSpan verifies a trait method signature spread over
+// several lines is captured with its full extent rather than just the first line.
+func TestRustMultiLineTraitSignatureSpan(t *testing.T) {
+	rustCode := "pub trait Repository {\n" +
+		"    fn find_many(\n" +
+		"        &self,\n" +
+		"        ids: &[u64],\n" +
+		"    ) -> Vec<String>;\n" +
+		"}\n"
+
+	parser := NewTreeSitterParser()
+	_, symbols, _ := parser.ParseFile("repo.rs", []byte(rustCode))
+
+	var found *types.Symbol
+	for i := range symbols {
+		if symbols[i].Name == "find_many" {
+			found = &symbols[i]
+			break
+		}
+	}
+	require.NotNil(t, found, "multi-line trait signature not extracted")
+
+	assert.Equal(t, types.SymbolTypeMethod, found.Type)
+	assert.Equal(t, "Repository", found.Parent)
+	assert.Equal(t, 2, found.Line)
+	assert.Equal(t, 5, found.EndLine)
+	assert.Equal(t, "fn find_many(\n        &self,\n        ids: &[u64],\n    ) -> Vec<String>;",
+		rustCode[found.StartByte:found.EndByte])
+}
+
+// TestSymbolVisibility verifies the extractor records visibility from Rust modifiers
+// and from Go and Python naming, with trait items as visible as their trait.
+func TestSymbolVisibility(t *testing.T) {
+	parser := NewTreeSitterParser()
+	access := func(path, code string) map[string]types.AccessLevel {
+		_, symbols, _ := parser.ParseFile(path, []byte(code))
+		levels := make(map[string]types.AccessLevel)
+		for _, sym := range symbols {
+			levels[sym.Name] = sym.Visibility.Access
+		}
+		return levels
+	}
+
+	rust := access("lib.rs", "pub struct Account {\n    pub id: u64,\n    secret: String,\n}\n"+
+		"pub(crate) fn audit() {}\n"+
+		"fn helper() {}\n"+
+		"pub trait Store {\n    fn load(&self);\n}\n"+
+		"trait Hidden {\n    fn peek(&self);\n}\n"+
+		"impl Account {\n    pub fn open() -> Self { todo!() }\n    fn check(&self) {}\n}\n"+
+		"impl Store for Account {\n    fn load(&self) {}\n}\n")
+	assert.Equal(t, types.AccessPublic, rust["Account"])
+	assert.Equal(t, types.AccessPublic, rust["id"])
+	assert.Equal(t, types.AccessPrivate, rust["secret"])
+	assert.Equal(t, types.AccessInternal, rust["audit"], "pub(crate) is visible within the crate")
+	assert.Equal(t, types.AccessPrivate, rust["helper"])
+	assert.Equal(t, types.AccessPublic, rust["Store"])
+	assert.Equal(t, types.AccessPublic, rust["load"], "trait and trait impl methods are as visible as the trait")
+	assert.Equal(t, types.AccessPrivate, rust["peek"], "a private trait's methods are private")
+	assert.Equal(t, types.AccessPublic, rust["open"])
+	assert.Equal(t, types.AccessPrivate, rust["check"])
+
+	goLevels := access("main.go", "package main\n\nfunc Serve() {}\n\nfunc handle() {}\n")
+	assert.Equal(t, types.AccessPublic, goLevels["Serve"])
+	assert.Equal(t, types.AccessPackage, goLevels["handle"])
+
+	py := access("app.py", "def serve():\n    pass\n\ndef _handle():\n    pass\n\nclass App:\n    def __init__(self):\n        pass\n")
+	assert.Equal(t, types.AccessPublic, py["serve"])
+	assert.Equal(t, types.AccessPrivate, py["_handle"])
+	assert.Equal(t, types.AccessPrivate, py["__init__"], "dunders follow the leading-underscore rule")
+}
+
+// TestSymbolSignature verifies functions and methods record their parameters and
+// return type with whitespace normalized, and that other symbols record none.
+func TestSymbolSignature(t *testing.T) {
+	parser := NewTreeSitterParser()
+	signatures := func(path, code string) map[string]string {
+		_, symbols, _ := parser.ParseFile(path, []byte(code))
+		sigs := make(map[string]string)
+		for _, sym := range symbols {
+			sigs[sym.Name] = sym.Signature
+		}
+		return sigs
+	}
+
+	rust := signatures("lib.rs", "pub struct Store;\n"+
+		"pub trait Users {\n    fn find_user(&self, id: &str) -> Result<User, Error>;\n}\n"+
+		"impl Store {\n    pub fn get_user(\n        &self,\n        id: &str,\n    ) -> Result<User, Error> {\n        todo!()\n    }\n}\n"+
+		"fn flush() {}\n")
+	assert.Equal(t, "(&self, id: &str) -> Result<User, Error>", rust["find_user"], "trait method declarations have signatures")
+	assert.Equal(t, "(&self, id: &str) -> Result<User, Error>", rust["get_user"], "a signature split over lines reads as one line")
+	assert.Equal(t, "()", rust["flush"])
+	assert.Empty(t, rust["Store"])
+
+	goSigs := signatures("main.go", "package main\n\nfunc (s *Store) Find(id string) (*User, error) { return nil, nil }\n")
+	assert.Equal(t, "(id string) (*User, error)", goSigs["Find"], "the receiver is not part of the signature")
+
+	py := signatures("app.py", "def find(id: str) -> User:\n    pass\n")
+	assert.Equal(t, "(id: str) -> User", py["find"])
+}
@@ -0,0 +1,54 @@
+package parser
+
+import (
+	"fmt"
+
+	tree_sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// SyntaxError describes the first syntax error tree-sitter recovered from in tree, or
+// returns "" when the file parsed cleanly. Tree-sitter is error tolerant: the rest of
+// the tree is still usable, so symbols outside the broken region are still extracted.
+func SyntaxError(tree *tree_sitter.Tree) string {
+	if tree == nil {
+		return ""
+	}
+	root := tree.RootNode()
+	if root == nil || !root.HasError() {
+		return ""
+	}
+	node := firstErrorNode(root)
+	if node == nil {
+		return "syntax error"
+	}
+	pos := node.StartPosition()
+	if node.IsMissing() {
+		return fmt.Sprintf("missing %s at line %d, column %d", node.Kind(), pos.Row+1, pos.Column+1)
+	}
+	return fmt.Sprintf("syntax error at line %d, column %d", pos.Row+1, pos.Column+1)
+}
+
+// firstErrorNode returns the first ERROR or MISSING node under node in source order,
+// descending only into subtrees that contain an error
+func firstErrorNode(node *tree_sitter.Node) *tree_sitter.Node {
+	if node.IsError() || node.IsMissing() {
+		return node
+	}
+	for i := uint(0); i < node.ChildCount(); i++ {
+		child := node.Child(i)
+		if child == nil || !child.HasError() {
+			continue
+		}
+		if found := firstErrorNode(child); found != nil {
+			return found
+		}
+	}
+	return nil
+}
+
+// LastSyntaxError returns the syntax error of the most recent ParseFileWithSideEffects
+// or ParseFileEnhanced call on p (see SyntaxError), or a description of the crash when
+// tree-sitter panicked. It is "" when that file parsed cleanly.
+func (p *TreeSitterParser) LastSyntaxError() string {
+	return p.lastSyntaxError
+}
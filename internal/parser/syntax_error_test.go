@@ -0,0 +1,35 @@
+package parser
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLastSyntaxError(t *testing.T) {
+	parser := NewTreeSitterParser()
+
+	_, symbols, _ := parser.ParseFile("clean.go", []byte("package main\n\nfunc clean() {}\n"))
+	require.NotEmpty(t, symbols)
+	assert.Empty(t, parser.LastSyntaxError())
+
+	broken := "package main\n\nfunc before() {}\n\nfunc broken( {\n\treturn 1\n}\n\nfunc after() {}\n"
+	_, symbols, _ = parser.ParseFile("broken.go", []byte(broken))
+	assert.Contains(t, parser.LastSyntaxError(), "line 5")
+	assert.NotNil(t, findSymbol(symbols, "before"), "symbols before the error still parse")
+
+	_, _, _ = parser.ParseFile("clean.go", []byte("package main\n"))
+	assert.Empty(t, parser.LastSyntaxError(), "each parse replaces the previous error")
+}
+
+func TestParseTree(t *testing.T) {
+	parser := NewTreeSitterParser()
+
+	tree := parser.ParseTree("broken.go", []byte("package main\n\nfunc broken( {\n}\n"))
+	require.NotNil(t, tree)
+	defer tree.Close()
+	assert.Contains(t, SyntaxError(tree), "line 3")
+
+	assert.Nil(t, parser.ParseTree("notes.txt", []byte("no parser for text")))
+}
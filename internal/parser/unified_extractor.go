@@ -5,6 +5,7 @@ import (
 	"path/filepath"
 	"strings"
 	"sync"
+	"unicode"
 
 	tree_sitter "github.com/tree-sitter/go-tree-sitter"
 	"github.com/standardbeagle/lci/internal/types"
@@ -72,7 +73,10 @@ type UnifiedExtractor struct {
 	inImportContext   bool              // Track if we're in an import statement
 	currentLevel      int               // Current scope nesting level
 	inTraitOrImplBody bool              // Track if we're inside a trait or impl body (Rust)
+	traitOrImplName   string            // Name of the enclosing trait or impl type (Rust)
 	inClassBody       bool              // Track if we're inside a class body (Python, JS, etc.)
+	pyParent          string            // Name of the enclosing class or function (Python)
+	pyParentIsClass   bool              // Whether pyParent is a class, making functions methods (Python)
 
 	// Complexity tracking during traversal (replaces separate calculateCyclomaticComplexity walk)
 	complexityStack []int // Stack of complexity counts for nested functions
@@ -277,7 +281,10 @@ func (ue *UnifiedExtractor) Reset() {
 	ue.inImportContext = false
 	ue.currentLevel = 0
 	ue.inTraitOrImplBody = false
+	ue.traitOrImplName = ""
 	ue.inClassBody = false
+	ue.pyParent = ""
+	ue.pyParentIsClass = false
 	ue.currentFuncKey = nil
 	ue.currentFuncAnalysis = nil
 	ue.sideEffectTracker = nil
@@ -378,6 +385,35 @@ func (ue *UnifiedExtractor) Extract(tree *tree_sitter.Tree) {
 	// Single-pass traversal
 	ue.currentLevel = 1
 	ue.visitNode(root)
+
+	ue.assignSymbolByteSpans()
+}
+
+// assignSymbolByteSpans converts each symbol's line/column range into byte offsets.
+// Tree-sitter columns are byte offsets within the line, so a line offset table is enough.
+func (ue *UnifiedExtractor) assignSymbolByteSpans() {
+	if len(ue.symbols) == 0 {
+		return
+	}
+	offsets := types.ComputeLineOffsets(ue.content)
+	toByte := func(line, column int) int {
+		if line < 1 || line > len(offsets) {
+			return 0
+		}
+		pos := offsets[line-1] + column - 1
+		if pos > len(ue.content) {
+			pos = len(ue.content)
+		}
+		return pos
+	}
+	for i := range ue.symbols {
+		sym := &ue.symbols[i]
+		if sym.EndByte > 0 {
+			continue // Span set by the extractor, e.g. a decorated Python definition
+		}
+		sym.StartByte = toByte(sym.Line, sym.Column)
+		sym.EndByte = toByte(sym.EndLine, sym.EndColumn)
+	}
 }
 
 // GetResults returns all extracted data (legacy signature for backward compatibility)
@@ -427,7 +463,12 @@ func (ue *UnifiedExtractor) visitNode(node *tree_sitter.Node) {
 
 	// === SYMBOL/BLOCK/IMPORT EXTRACTION (from extractBasicSymbolsStringRef) ===
 	// This replaces the separate query-based pass with visitor-based extraction
+	symbolsBefore := len(ue.symbols)
 	ue.processSymbolNode(node, nodeType)
+	if len(ue.symbols) > symbolsBefore {
+		ue.recordVisibility(node, nodeType, ue.symbols[symbolsBefore:])
+		ue.recordSignature(node, ue.symbols[symbolsBefore:])
+	}
 
 	// === SCOPE EXTRACTION (from extractNestedScopes) ===
 	scopeEntry := ue.processScopeNode(node, nodeType)
@@ -461,8 +502,20 @@ func (ue *UnifiedExtractor) visitNode(node *tree_sitter.Node) {
 
 	// Track if we're entering a trait or impl body (Rust)
 	wasInTraitOrImpl := ue.inTraitOrImplBody
+	prevTraitOrImplName := ue.traitOrImplName
 	if nodeType == "trait_item" || nodeType == "impl_item" {
 		ue.inTraitOrImplBody = true
+		ue.traitOrImplName = ue.rustContainerName(node, nodeType)
+	}
+
+	// Track the enclosing class or function (Python) so nested definitions record their parent
+	prevPyParent, prevPyParentIsClass := ue.pyParent, ue.pyParentIsClass
+	isPyDefinition := ue.ext == ".py" && (nodeType == "class_definition" || nodeType == "function_definition")
+	if isPyDefinition {
+		if nameNode := node.ChildByFieldName("name"); nameNode != nil {
+			ue.pyParent = string(ue.content[nameNode.StartByte():nameNode.EndByte()])
+			ue.pyParentIsClass = nodeType == "class_definition"
+		}
 	}
 
 	// Track if we're entering a class body (Python, JS, etc.)
@@ -501,6 +554,7 @@ func (ue *UnifiedExtractor) visitNode(node *tree_sitter.Node) {
 	// Reset trait/impl context
 	if nodeType == "trait_item" || nodeType == "impl_item" {
 		ue.inTraitOrImplBody = wasInTraitOrImpl
+		ue.traitOrImplName = prevTraitOrImplName
 	}
 
 	// Reset class body context
@@ -508,6 +562,11 @@ func (ue *UnifiedExtractor) visitNode(node *tree_sitter.Node) {
 		ue.inClassBody = wasInClassBody
 	}
 
+	// Reset Python enclosing definition
+	if isPyDefinition {
+		ue.pyParent, ue.pyParentIsClass = prevPyParent, prevPyParentIsClass
+	}
+
 	// Pop loop stack if we pushed one
 	if loopEntry != nil {
 		ue.loopStack = ue.loopStack[:len(ue.loopStack)-1]
@@ -739,9 +798,10 @@ func (ue *UnifiedExtractor) processSymbolNode(node *tree_sitter.Node, nodeType s
 		ue.extractFunction(node, nodeType)
 
 	case "function_definition":
-		// Python function_definition: if inside class, it's a method; otherwise function
-		if ue.inClassBody {
-			ue.extractPythonMethod(node)
+		// Python function_definition: a method directly inside a class, otherwise a function;
+		// C/C++ and PHP function_definition is always a function
+		if ue.ext == ".py" {
+			ue.extractPythonFunction(node)
 		} else {
 			ue.extractFunction(node, nodeType)
 		}
@@ -754,9 +814,19 @@ func (ue *UnifiedExtractor) processSymbolNode(node *tree_sitter.Node, nodeType s
 			ue.extractFunction(node, nodeType)
 		}
 
+	case "function_signature_item":
+		// Rust trait method declaration without a body (fn find_user(&self) -> User;)
+		if ue.inTraitOrImplBody {
+			ue.extractRustMethod(node)
+		}
+
 	case "method_definition", "method_declaration":
 		ue.extractMethod(node, nodeType)
 
+	case "method_elem":
+		// Go interface method (FindUser(id string) (*User, error)) inside an interface_type
+		ue.extractGoInterfaceMethod(node)
+
 	case "arrow_function", "function_expression", "generator_function":
 		// Only extract if parent is variable_declarator (named arrow function)
 		// Anonymous functions are captured by their container
@@ -769,7 +839,11 @@ func (ue *UnifiedExtractor) processSymbolNode(node *tree_sitter.Node, nodeType s
 
 	// === CLASSES ===
 	case "class_declaration", "class_definition", "class_specifier":
-		ue.extractClass(node, nodeType)
+		if ue.ext == ".py" {
+			ue.extractPythonClass(node)
+		} else {
+			ue.extractClass(node, nodeType)
+		}
 
 	// === INTERFACES ===
 	case "interface_declaration":
@@ -814,6 +888,12 @@ func (ue *UnifiedExtractor) processSymbolNode(node *tree_sitter.Node, nodeType s
 	case "short_var_declaration", "var_declaration", "const_declaration":
 		ue.extractGoVariable(node, nodeType)
 
+	case "assignment":
+		// Python module-level assignment (MAX_RETRIES = 3, user_cache: dict = {})
+		if ue.ext == ".py" {
+			ue.extractPythonAssignment(node)
+		}
+
 	// === IMPORTS ===
 	case "import_statement":
 		// Could be JavaScript/TypeScript or Python import
@@ -873,9 +953,101 @@ func (ue *UnifiedExtractor) processSymbolNode(node *tree_sitter.Node, nodeType s
 		if ue.ext == ".zig" {
 			ue.extractZigVariableDeclaration(node)
 		}
+	}
+}
+
+// recordVisibility sets the Visibility of the symbols extracted from node for the
+// languages whose visibility the extractor reads: Rust from its visibility modifier, Go
+// from the case of the name and Python from a leading underscore, the naming rules the
+// reference tracker's computeIsExported applies. A Rust item is exported when it is pub
+// or pub(crate). Other languages keep it unset, and the reference tracker falls back to
+// naming conventions.
+func (ue *UnifiedExtractor) recordVisibility(node *tree_sitter.Node, nodeType string, symbols []types.Symbol) {
+	switch ue.ext {
+	case ".rs":
+		if nodeType == "impl_item" {
+			return // Impl blocks have no visibility of their own
+		}
+		visibility := ue.rustVisibility(node)
+		for i := range symbols {
+			symbols[i].Visibility.Access = visibility
+			symbols[i].Visibility.IsExported = visibility != types.AccessPrivate
+		}
+	case ".go":
+		for i := range symbols {
+			exported := symbols[i].Name != "" && unicode.IsUpper([]rune(symbols[i].Name)[0])
+			symbols[i].Visibility.Access = types.AccessPackage
+			if exported {
+				symbols[i].Visibility.Access = types.AccessPublic
+			}
+			symbols[i].Visibility.IsExported = exported
+		}
+	case ".py":
+		for i := range symbols {
+			exported := !strings.HasPrefix(symbols[i].Name, "_")
+			symbols[i].Visibility.Access = types.AccessPrivate
+			if exported {
+				symbols[i].Visibility.Access = types.AccessPublic
+			}
+			symbols[i].Visibility.IsExported = exported
+		}
+	}
+}
 
-		// Note: Python class_definition is handled by extractClass() above
+// recordSignature sets the Signature of the functions and methods extracted from node
+// to the source from its parameter list through its return type, such as
+// (&self, id: &str) -> Result<User, Error> in Rust or (id string) (*User, error) in Go.
+// Grammars without a parameters field, and so their symbols, get none.
+func (ue *UnifiedExtractor) recordSignature(node *tree_sitter.Node, symbols []types.Symbol) {
+	params := node.ChildByFieldName("parameters")
+	if params == nil {
+		return
+	}
+	end := params.EndByte()
+	for _, field := range []string{"return_type", "result"} { // result: Go
+		if ret := node.ChildByFieldName(field); ret != nil {
+			end = ret.EndByte()
+			break
+		}
+	}
+	if end > uint(len(ue.content)) {
+		return
 	}
+	sig := types.NormalizeSignature(string(ue.content[params.StartByte():end]), false)
+	for i := range symbols {
+		if symbols[i].Type == types.SymbolTypeFunction || symbols[i].Type == types.SymbolTypeMethod {
+			symbols[i].Signature = sig
+		}
+	}
+}
+
+// rustVisibility reads a Rust item's visibility modifier: pub is public; pub(crate),
+// pub(super) and pub(in path) are internal; no modifier is private, except for items
+// of a trait, which are as visible as the trait, and of a trait impl, which are public
+func (ue *UnifiedExtractor) rustVisibility(node *tree_sitter.Node) types.AccessLevel {
+	for i := uint(0); i < node.ChildCount(); i++ {
+		child := node.Child(i)
+		if ue.getNodeType(child) != "visibility_modifier" {
+			continue
+		}
+		if string(ue.content[child.StartByte():child.EndByte()]) == "pub" {
+			return types.AccessPublic
+		}
+		return types.AccessInternal
+	}
+	if body := node.Parent(); body != nil && ue.getNodeType(body) == "declaration_list" {
+		if container := body.Parent(); container != nil {
+			switch ue.getNodeType(container) {
+			case "trait_item":
+				return ue.rustVisibility(container)
+			case "impl_item":
+				if container.ChildByFieldName("trait") != nil {
+					return types.AccessPublic
+				}
+			}
+		}
+	}
+	return types.AccessPrivate
 }
 
 // isArrowFunctionDeclarator checks if a variable_declarator contains an arrow function
@@ -956,6 +1128,13 @@ func (ue *UnifiedExtractor) extractMethod(node *tree_sitter.Node, nodeType strin
 	// Detect attributes
 	attributes := ue.parser.detectContextAttributes(node, ue.content)
 
+	// Go methods record their receiver type as the parent: func (s *UserService) GetUser -> UserService
+	var parent string
+	if receiverNode := node.ChildByFieldName("receiver"); receiverNode != nil {
+		_, receiverType := ue.extractGoReceiver(receiverNode)
+		parent = baseReceiverType(receiverType)
+	}
+
 	block := types.BlockBoundary{
 		Start: int(startPoint.Row),
 		End:   int(endPoint.Row),
@@ -971,13 +1150,15 @@ func (ue *UnifiedExtractor) extractMethod(node *tree_sitter.Node, nodeType strin
 		Column:     int(startPoint.Column) + 1,
 		EndLine:    int(endPoint.Row) + 1,
 		EndColumn:  int(endPoint.Column) + 1,
+		Parent:     parent,
 		Attributes: attributes,
 	}
 	ue.symbols = append(ue.symbols, symbol)
 }
 
-// extractPythonMethod extracts a Python function_definition that's inside a class as a method
-func (ue *UnifiedExtractor) extractPythonMethod(node *tree_sitter.Node) {
+// extractGoInterfaceMethod extracts a method declared in a Go interface type,
+// recording the interface name as the parent so it lines up with Rust trait methods
+func (ue *UnifiedExtractor) extractGoInterfaceMethod(node *tree_sitter.Node) {
 	startPoint := node.StartPosition()
 	endPoint := node.EndPosition()
 
@@ -990,15 +1171,15 @@ func (ue *UnifiedExtractor) extractPythonMethod(node *tree_sitter.Node) {
 		return
 	}
 
-	// Note: Complexity is computed during visitNode traversal, not here
-
-	block := types.BlockBoundary{
-		Start: int(startPoint.Row),
-		End:   int(endPoint.Row),
-		Type:  types.BlockTypeMethod,
-		Name:  name,
+	// method_elem -> interface_type -> type_spec(name)
+	var parent string
+	if ifaceNode := node.Parent(); ifaceNode != nil {
+		if specNode := ifaceNode.Parent(); specNode != nil && ue.getNodeType(specNode) == "type_spec" {
+			if nameNode := specNode.ChildByFieldName("name"); nameNode != nil {
+				parent = string(ue.content[nameNode.StartByte():nameNode.EndByte()])
+			}
+		}
 	}
-	ue.blocks = append(ue.blocks, block)
 
 	symbol := types.Symbol{
 		Name:      name,
@@ -1007,11 +1188,91 @@ func (ue *UnifiedExtractor) extractPythonMethod(node *tree_sitter.Node) {
 		Column:    int(startPoint.Column) + 1,
 		EndLine:   int(endPoint.Row) + 1,
 		EndColumn: int(endPoint.Column) + 1,
+		Parent:    parent,
+	}
+	ue.symbols = append(ue.symbols, symbol)
+}
+
+// extractPythonFunction extracts a Python function_definition: a method with its class
+// as parent when defined directly in a class body, otherwise a function whose parent is
+// the enclosing function, if any. Decorators are included in the symbol's byte span and
+// block so decorated definitions are found by their decorator lines too.
+func (ue *UnifiedExtractor) extractPythonFunction(node *tree_sitter.Node) {
+	startPoint := node.StartPosition()
+	endPoint := node.EndPosition()
+
+	var name string
+	if nameNode := node.ChildByFieldName("name"); nameNode != nil {
+		name = string(ue.content[nameNode.StartByte():nameNode.EndByte()])
+	}
+
+	if name == "" {
+		return
+	}
+
+	// Note: Complexity is computed during visitNode traversal, not here
+
+	symbolType, blockType := types.SymbolTypeFunction, types.BlockTypeFunction
+	if ue.pyParentIsClass {
+		symbolType, blockType = types.SymbolTypeMethod, types.BlockTypeMethod
+	}
+	spanNode := ue.pythonDecoratedNode(node)
+
+	block := types.BlockBoundary{
+		Start: int(spanNode.StartPosition().Row),
+		End:   int(endPoint.Row),
+		Type:  blockType,
+		Name:  name,
+	}
+	ue.blocks = append(ue.blocks, block)
+
+	symbol := types.Symbol{
+		Name:       name,
+		Type:       symbolType,
+		Line:       int(startPoint.Row) + 1,
+		Column:     int(startPoint.Column) + 1,
+		EndLine:    int(endPoint.Row) + 1,
+		EndColumn:  int(endPoint.Column) + 1,
+		StartByte:  int(spanNode.StartByte()),
+		EndByte:    int(node.EndByte()),
+		Parent:     ue.pyParent,
+		Attributes: ue.parser.detectContextAttributes(node, ue.content),
 	}
 	ue.symbols = append(ue.symbols, symbol)
 }
 
-// extractRustMethod extracts a Rust function_item that's inside a trait/impl as a method
+// pythonDecoratedNode returns the decorated_definition wrapping a Python function or
+// class, or the definition itself when it has no decorators
+func (ue *UnifiedExtractor) pythonDecoratedNode(node *tree_sitter.Node) *tree_sitter.Node {
+	if parent := node.Parent(); parent != nil && ue.getNodeType(parent) == "decorated_definition" {
+		return parent
+	}
+	return node
+}
+
+// rustContainerName returns the name methods inside a trait or impl block belong to.
+// For impl blocks this is the implementing type without generic arguments
+// (impl<D> Display for UserService<D> -> UserService).
+func (ue *UnifiedExtractor) rustContainerName(node *tree_sitter.Node, nodeType string) string {
+	field := "name"
+	if nodeType == "impl_item" {
+		field = "type"
+	}
+	target := node.ChildByFieldName(field)
+	if target == nil {
+		return ""
+	}
+	if ue.getNodeType(target) == "generic_type" {
+		if inner := target.ChildByFieldName("type"); inner != nil {
+			target = inner
+		}
+	}
+	return string(ue.content[target.StartByte():target.EndByte()])
+}
+
+// extractRustMethod extracts a Rust function_item or function_signature_item that's
+// inside a trait/impl as a method. The span covers the whole item, so trait method
+// signatures split across several lines keep their full extent.
 func (ue *UnifiedExtractor) extractRustMethod(node *tree_sitter.Node) {
 	startPoint := node.StartPosition()
 	endPoint := node.EndPosition()
@@ -1042,6 +1303,7 @@ func (ue *UnifiedExtractor) extractRustMethod(node *tree_sitter.Node) {
 		Column:    int(startPoint.Column) + 1,
 		EndLine:   int(endPoint.Row) + 1,
 		EndColumn: int(endPoint.Column) + 1,
+		Parent:    ue.traitOrImplName,
 	}
 	ue.symbols = append(ue.symbols, symbol)
 }
@@ -1933,7 +2195,8 @@ func (ue *UnifiedExtractor) extractZigVariableDeclaration(node *tree_sitter.Node
 	ue.symbols = append(ue.symbols, symbol)
 }
 
-// extractPythonClass extracts a Python class definition
+// extractPythonClass extracts a Python class definition, with its decorators in the
+// byte span and the enclosing class or function of a nested class as parent
 func (ue *UnifiedExtractor) extractPythonClass(node *tree_sitter.Node) {
 	startPoint := node.StartPosition()
 	endPoint := node.EndPosition()
@@ -1947,8 +2210,10 @@ func (ue *UnifiedExtractor) extractPythonClass(node *tree_sitter.Node) {
 		return
 	}
 
+	spanNode := ue.pythonDecoratedNode(node)
+
 	block := types.BlockBoundary{
-		Start: int(startPoint.Row),
+		Start: int(spanNode.StartPosition().Row),
 		End:   int(endPoint.Row),
 		Type:  types.BlockTypeClass,
 		Name:  name,
@@ -1962,10 +2227,54 @@ func (ue *UnifiedExtractor) extractPythonClass(node *tree_sitter.Node) {
 		Column:    int(startPoint.Column) + 1,
 		EndLine:   int(endPoint.Row) + 1,
 		EndColumn: int(endPoint.Column) + 1,
+		StartByte: int(spanNode.StartByte()),
+		EndByte:   int(node.EndByte()),
+		Parent:    ue.pyParent,
 	}
 	ue.symbols = append(ue.symbols, symbol)
 }
 
+// extractPythonAssignment extracts the names bound by a module-level Python assignment
+// (including tuple targets such as a, b = 1, 2) as variables. Assignments inside
+// functions and class bodies are locals and attributes, not symbols.
+func (ue *UnifiedExtractor) extractPythonAssignment(node *tree_sitter.Node) {
+	stmt := node.Parent()
+	if stmt == nil || ue.getNodeType(stmt) != "expression_statement" {
+		return
+	}
+	if module := stmt.Parent(); module == nil || ue.getNodeType(module) != "module" {
+		return
+	}
+
+	left := node.ChildByFieldName("left")
+	if left == nil {
+		return
+	}
+	targets := []*tree_sitter.Node{left}
+	if kind := ue.getNodeType(left); kind == "pattern_list" || kind == "tuple_pattern" {
+		targets = targets[:0]
+		for i := uint(0); i < left.NamedChildCount(); i++ {
+			targets = append(targets, left.NamedChild(i))
+		}
+	}
+
+	endPoint := stmt.EndPosition()
+	for _, target := range targets {
+		if target == nil || ue.getNodeType(target) != "identifier" {
+			continue
+		}
+		startPoint := target.StartPosition()
+		ue.symbols = append(ue.symbols, types.Symbol{
+			Name:      string(ue.content[target.StartByte():target.EndByte()]),
+			Type:      types.SymbolTypeVariable,
+			Line:      int(startPoint.Row) + 1,
+			Column:    int(startPoint.Column) + 1,
+			EndLine:   int(endPoint.Row) + 1,
+			EndColumn: int(endPoint.Column) + 1,
+		})
+	}
+}
+
 // processDeclarationNode extracts declaration metadata
 func (ue *UnifiedExtractor) processDeclarationNode(node *tree_sitter.Node, nodeType string) {
 	if !isDeclarationNode(nodeType) {
@@ -1,6 +1,8 @@
 package parser
 
 import (
+	"strings"
+
 	tree_sitter "github.com/tree-sitter/go-tree-sitter"
 )
 
@@ -148,6 +150,16 @@ func (ue *UnifiedExtractor) extractGoReceiver(receiverNode *tree_sitter.Node) (n
 	return
 }
 
+// baseReceiverType strips pointer and type-argument syntax from a Go receiver type
+// (*UserService -> UserService, *Cache[K, V] -> Cache)
+func baseReceiverType(receiverType string) string {
+	receiverType = strings.TrimSpace(strings.TrimPrefix(strings.TrimSpace(receiverType), "*"))
+	if idx := strings.IndexByte(receiverType, '['); idx >= 0 {
+		receiverType = receiverType[:idx]
+	}
+	return receiverType
+}
+
 // processSideEffectNode processes a node for side effect tracking
 func (ue *UnifiedExtractor) processSideEffectNode(node *tree_sitter.Node, nodeType string) {
 	if ue.sideEffectTracker == nil || !ue.sideEffectTracker.IsInFunction() {
@@ -0,0 +1,104 @@
+package query
+
+import (
+	"sort"
+	"strconv"
+)
+
+// Near matches symbols whose declaration holds both words within Distance tokens of
+// each other: `token NEAR/5 expires`. Words are whole tokens, matched ignoring case,
+// and positions count every token, so adjacent words are 1 apart. NEAR accepts either
+// order; NEARW, Ordered, needs Left before Right. When both words are the same, as in
+// `err NEAR/3 err`, two separate occurrences must be that close.
+type Near struct {
+	Left, Right string // Lowercased words
+	Distance    int
+	Ordered     bool
+}
+
+// Match reports whether sym holds the words within Distance tokens
+func (q *Near) Match(sym *Symbol) bool {
+	d, ok := q.closest(sym)
+	return ok && d <= q.Distance
+}
+
+func (q *Near) String() string {
+	op := "NEAR/"
+	if q.Ordered {
+		op = "NEARW/"
+	}
+	return "(" + q.Left + " " + op + strconv.Itoa(q.Distance) + " " + q.Right + ")"
+}
+
+// closest returns the smallest distance between an occurrence of Left and a different
+// occurrence of Right, after it when Ordered, or false when sym has no such pair
+func (q *Near) closest(sym *Symbol) (int, bool) {
+	if sym.Positions == nil {
+		return 0, false
+	}
+	left, right := sym.Positions(q.Left), sym.Positions(q.Right)
+	best, found := 0, false
+	consider := func(d int) {
+		if d > 0 && (!found || d < best) {
+			best, found = d, true
+		}
+	}
+	for _, l := range left {
+		// The first occurrence of Right after l, then the last one before it
+		i := sort.SearchInts(right, l+1)
+		if i < len(right) {
+			consider(right[i] - l)
+		}
+		if q.Ordered {
+			continue
+		}
+		if i--; i >= 0 && right[i] == l {
+			i-- // The same occurrence as l, when Left and Right are the same word
+		}
+		if i >= 0 {
+			consider(l - right[i])
+		}
+	}
+	return best, found
+}
+
+// Score rates how closely sym matches q, from 0 to 1. NEAR scores its closest pair,
+// from 1 for adjacent words down to 1/Distance at the full distance; AND multiplies its
+// operands' scores, OR takes the better operand that matches, and every other term
+// scores 1, so queries without NEAR score every match alike.
+func Score(q Query, sym *Symbol) float64 {
+	switch n := q.(type) {
+	case *Near:
+		d, ok := n.closest(sym)
+		if !ok || d > n.Distance {
+			return 0
+		}
+		return float64(n.Distance-d+1) / float64(n.Distance)
+	case *And:
+		return Score(n.Left, sym) * Score(n.Right, sym)
+	case *Or:
+		var best float64
+		for _, operand := range []Query{n.Left, n.Right} {
+			if operand.Match(sym) {
+				best = max(best, Score(operand, sym))
+			}
+		}
+		return best
+	}
+	return 1
+}
+
+// HasNear reports whether q uses NEAR or NEARW, and so reads Symbol.Positions
+func HasNear(q Query) bool {
+	switch n := q.(type) {
+	case *Near:
+		return true
+	case *And:
+		return HasNear(n.Left) || HasNear(n.Right)
+	case *Or:
+		return HasNear(n.Left) || HasNear(n.Right)
+	case *Not:
+		return HasNear(n.Operand)
+	}
+	return false
+}
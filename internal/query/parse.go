@@ -0,0 +1,437 @@
+package query
+
+import (
+	"fmt"
+	"path"
+	"regexp"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/lang"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// QueryError describes a malformed query. Pos is the byte offset in Query of the
+// offending token: the unclosed parenthesis, the unknown field, the bad value.
+type QueryError struct {
+	Query string
+	Pos   int
+	Msg   string
+}
+
+func (e *QueryError) Error() string {
+	return fmt.Sprintf("invalid query at column %d: %s", e.Pos+1, e.Msg)
+}
+
+type tokenKind int
+
+const (
+	tokEOF tokenKind = iota
+	tokLParen
+	tokRParen
+	tokAnd
+	tokOr
+	tokNot
+	tokNear
+	tokTerm
+)
+
+type token struct {
+	kind     tokenKind
+	pos      int
+	text     string // Source text of an operator or parenthesis
+	field    string // Field of a term, "" for a bare value
+	value    string
+	valuePos int
+	phrase   bool
+	distance int  // Distance of a NEAR/n operator, 0 when missing or malformed
+	ordered  bool // The operator was NEARW
+}
+
+// Parse parses a query expression. Errors are *QueryError.
+func Parse(input string) (Query, error) {
+	tokens, err := lex(input)
+	if err != nil {
+		return nil, err
+	}
+	p := &parser{input: input, tokens: tokens}
+	if p.peek().kind == tokEOF {
+		return nil, p.errorf(0, "empty query")
+	}
+
+	q, err := p.parseOr()
+	if err != nil {
+		return nil, err
+	}
+	if tok := p.peek(); tok.kind != tokEOF {
+		// parseAnd consumes every token that can continue an expression
+		return nil, p.errorf(tok.pos, "unexpected ) without a matching (")
+	}
+	return q, nil
+}
+
+// lex splits input into tokens. Words are separated by whitespace and parentheses;
+// a quote starts a phrase, which runs to the next unescaped quote.
+func lex(input string) ([]token, error) {
+	var tokens []token
+	i := 0
+	for i < len(input) {
+		c := input[i]
+		switch {
+		case isSpace(c):
+			i++
+		case c == '(':
+			tokens = append(tokens, token{kind: tokLParen, pos: i, text: "("})
+			i++
+		case c == ')':
+			tokens = append(tokens, token{kind: tokRParen, pos: i, text: ")"})
+			i++
+		case isNegation(input, i):
+			tokens = append(tokens, token{kind: tokNot, pos: i, text: "-"})
+			i++
+		case c == '"':
+			value, end, err := lexPhrase(input, i)
+			if err != nil {
+				return nil, err
+			}
+			tokens = append(tokens, token{kind: tokTerm, pos: i, value: value, valuePos: i, phrase: true})
+			i = end
+		default:
+			start := i
+			for i < len(input) && !isSpace(input[i]) && input[i] != '(' && input[i] != ')' && input[i] != '"' {
+				i++
+			}
+			word := input[start:i]
+			switch word {
+			case "AND":
+				tokens = append(tokens, token{kind: tokAnd, pos: start, text: word})
+				continue
+			case "OR":
+				tokens = append(tokens, token{kind: tokOr, pos: start, text: word})
+				continue
+			case "NOT":
+				tokens = append(tokens, token{kind: tokNot, pos: start, text: word})
+				continue
+			}
+			if ordered, distance, ok := nearOperator(word); ok {
+				tokens = append(tokens, token{kind: tokNear, pos: start, text: word, distance: distance, ordered: ordered})
+				continue
+			}
+
+			tok := token{kind: tokTerm, pos: start, value: word, valuePos: start}
+			if field, value, ok := strings.Cut(word, ":"); ok {
+				tok.field, tok.value, tok.valuePos = field, value, start+len(field)+1
+				if value == "" && i < len(input) && input[i] == '"' {
+					phrase, end, err := lexPhrase(input, i)
+					if err != nil {
+						return nil, err
+					}
+					tok.value, tok.phrase = phrase, true
+					i = end
+				}
+			}
+			tokens = append(tokens, tok)
+		}
+	}
+	return append(tokens, token{kind: tokEOF, pos: len(input)}), nil
+}
+
+// nearOperator parses NEAR/n and NEARW/n. NEAR alone, or with a distance that is not a
+// positive number, is still the operator, with distance 0, so the parser reports the
+// mistake instead of searching for a symbol named NEAR.
+func nearOperator(word string) (ordered bool, distance int, ok bool) {
+	op, n, _ := strings.Cut(word, "/")
+	if op != "NEAR" && op != "NEARW" {
+		return false, 0, false
+	}
+	if distance, err := strconv.Atoi(n); err == nil && distance > 0 {
+		return op == "NEARW", distance, true
+	}
+	return op == "NEARW", 0, true
+}
+
+// lexPhrase reads the quoted phrase starting at input[start], returning its unescaped
+// value and the offset just past the closing quote
+func lexPhrase(input string, start int) (string, int, error) {
+	var b strings.Builder
+	for i := start + 1; i < len(input); i++ {
+		switch c := input[i]; {
+		case c == '\\' && i+1 < len(input):
+			i++
+			b.WriteByte(input[i])
+		case c == '"':
+			return b.String(), i + 1, nil
+		default:
+			b.WriteByte(c)
+		}
+	}
+	return "", 0, &QueryError{Query: input, Pos: start, Msg: "unterminated quote"}
+}
+
+// Phrase is one term of a plain search pattern, as split by SplitPhrases
+type Phrase struct {
+	Text         string
+	Quoted       bool     // Text was quoted, so it is matched as one contiguous phrase
+	Negated      bool     // Text was prefixed with "-", so matches must not contain it
+	Alternatives []string // Other spellings that satisfy the phrase as well as Text, from alias expansion
+}
+
+// Spellings returns Text followed by the Alternatives
+func (p Phrase) Spellings() []string {
+	return append([]string{p.Text}, p.Alternatives...)
+}
+
+// SplitPhrases splits a plain search pattern such as `"handle user request" auth` into
+// whitespace-separated words and quoted phrases, quoted and escaped like query values.
+// A quote only opens a phrase at the start of a word and only closes one at the end of
+// a word, so the quotes in code such as f("x") are literal, as is an unterminated
+// quote.
+//
+// A word or phrase prefixed with "-", as in `user -token -"auth header"`, is negated:
+// sugar for NOT in the query language. Only a "-" before a letter, "_" or quote
+// negates, so code such as `x -1` and `--verbose` is matched as written.
+func SplitPhrases(input string) []Phrase {
+	var phrases []Phrase
+	i := 0
+	for i < len(input) {
+		if isSpace(input[i]) {
+			i++
+			continue
+		}
+		negated := isNegation(input, i)
+		if negated {
+			i++
+		}
+		if input[i] == '"' {
+			value, end, err := lexPhrase(input, i)
+			if err == nil && value != "" && (end == len(input) || isSpace(input[end])) {
+				phrases = append(phrases, Phrase{Text: value, Quoted: true, Negated: negated})
+				i = end
+				continue
+			}
+		}
+		start := i
+		for i < len(input) && !isSpace(input[i]) {
+			i++
+		}
+		phrases = append(phrases, Phrase{Text: input[start:i], Negated: negated})
+	}
+	return phrases
+}
+
+// isNegation reports whether input[i] is a "-" that negates the word or phrase after it
+func isNegation(input string, i int) bool {
+	if input[i] != '-' || i+1 == len(input) {
+		return false
+	}
+	c := input[i+1]
+	return c == '"' || c == '_' || (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z')
+}
+
+func isSpace(c byte) bool {
+	return c == ' ' || c == '\t' || c == '\n' || c == '\r'
+}
+
+// parser is a recursive descent parser over the tokens of one query:
+//
+//	or   = and { "OR" and }
+//	and  = not { ["AND"] not }
+//	not  = ("NOT" | "-") not | "(" or ")" | near
+//	near = term [("NEAR/n" | "NEARW/n") term]
+type parser struct {
+	input  string
+	tokens []token
+	i      int
+}
+
+func (p *parser) peek() token {
+	return p.tokens[p.i]
+}
+
+func (p *parser) next() token {
+	tok := p.tokens[p.i]
+	if tok.kind != tokEOF {
+		p.i++
+	}
+	return tok
+}
+
+func (p *parser) errorf(pos int, format string, args ...any) *QueryError {
+	return &QueryError{Query: p.input, Pos: pos, Msg: fmt.Sprintf(format, args...)}
+}
+
+func (p *parser) parseOr() (Query, error) {
+	left, err := p.parseAnd()
+	if err != nil {
+		return nil, err
+	}
+	for p.peek().kind == tokOr {
+		p.next()
+		right, err := p.parseAnd()
+		if err != nil {
+			return nil, err
+		}
+		left = &Or{Left: left, Right: right}
+	}
+	return left, nil
+}
+
+func (p *parser) parseAnd() (Query, error) {
+	left, err := p.parseNot()
+	if err != nil {
+		return nil, err
+	}
+	for {
+		switch p.peek().kind {
+		case tokAnd:
+			p.next()
+		case tokTerm, tokLParen, tokNot:
+			// Adjacent expressions are implicitly joined with AND
+		default:
+			return left, nil
+		}
+		right, err := p.parseNot()
+		if err != nil {
+			return nil, err
+		}
+		left = &And{Left: left, Right: right}
+	}
+}
+
+func (p *parser) parseNot() (Query, error) {
+	if p.peek().kind != tokNot {
+		return p.parseNear()
+	}
+	p.next()
+	operand, err := p.parseNot()
+	if err != nil {
+		return nil, err
+	}
+	return &Not{Operand: operand}, nil
+}
+
+func (p *parser) parseNear() (Query, error) {
+	left, err := p.parsePrimary()
+	if err != nil || p.peek().kind != tokNear {
+		return left, err
+	}
+	op := p.next()
+	if op.distance == 0 {
+		return nil, p.errorf(op.pos, "%s needs a distance of at least one token, as in NEAR/5", op.text)
+	}
+	right, err := p.parsePrimary()
+	if err != nil {
+		return nil, err
+	}
+	leftWord, err := p.nearWord(left, op)
+	if err != nil {
+		return nil, err
+	}
+	rightWord, err := p.nearWord(right, op)
+	if err != nil {
+		return nil, err
+	}
+	if next := p.peek(); next.kind == tokNear {
+		return nil, p.errorf(next.pos, "%s cannot follow another NEAR; join the pairs with AND", next.text)
+	}
+	return &Near{Left: leftWord, Right: rightWord, Distance: op.distance, Ordered: op.ordered}, nil
+}
+
+// nearWord returns the word an operand of op stands for. Operands are single words,
+// bare or as text: terms.
+func (p *parser) nearWord(operand Query, op token) (string, error) {
+	t, ok := operand.(*Term)
+	if !ok {
+		return "", p.errorf(op.pos, "%s joins two words, not parenthesized expressions", op.text)
+	}
+	if t.Field != FieldName && t.Field != FieldText {
+		return "", p.errorf(t.Pos, "%s joins words, not %s: terms", op.text, t.Field)
+	}
+	for i := 0; i < len(t.Value); i++ {
+		if c := t.Value[i]; !(c == '_' || c >= '0' && c <= '9' || c >= 'a' && c <= 'z' || c >= 'A' && c <= 'Z') {
+			return "", p.errorf(t.Pos, "%s joins single words of letters, digits and _, not %q", op.text, t.Value)
+		}
+	}
+	return t.lower, nil
+}
+
+func (p *parser) parsePrimary() (Query, error) {
+	tok := p.next()
+	switch tok.kind {
+	case tokLParen:
+		q, err := p.parseOr()
+		if err != nil {
+			return nil, err
+		}
+		if p.peek().kind != tokRParen {
+			return nil, p.errorf(tok.pos, "unclosed (")
+		}
+		p.next()
+		return q, nil
+	case tokTerm:
+		return p.newTerm(tok)
+	case tokRParen:
+		if p.i >= 2 && p.tokens[p.i-2].kind == tokLParen {
+			return nil, p.errorf(tok.pos, "empty parentheses")
+		}
+		return nil, p.errorf(tok.pos, "unexpected ) without a matching (")
+	case tokEOF:
+		if p.i > 0 {
+			prev := p.tokens[p.i-1]
+			return nil, p.errorf(tok.pos, "expected a term after %s", prev.text)
+		}
+		return nil, p.errorf(tok.pos, "expected a term")
+	default:
+		return nil, p.errorf(tok.pos, "expected a term before %s", tok.text)
+	}
+}
+
+// newTerm validates a term token's field and value
+func (p *parser) newTerm(tok token) (*Term, error) {
+	field := FieldName
+	if tok.field != "" {
+		field = Field(strings.ToLower(tok.field))
+		if !slices.Contains(Fields, field) {
+			return nil, p.errorf(tok.pos, "unknown field %q, valid fields: %s", tok.field, fieldNames())
+		}
+	}
+	if tok.value == "" {
+		return nil, p.errorf(tok.valuePos, "missing value after %s:", tok.field)
+	}
+
+	t := &Term{Field: field, Value: tok.value, Phrase: tok.phrase, Pos: tok.pos, lower: strings.ToLower(tok.value)}
+	switch field {
+	case FieldKind:
+		kind, ok := types.ParseSymbolType(tok.value)
+		if !ok {
+			return nil, p.errorf(tok.valuePos, "unknown kind %q, valid kinds: %s", tok.value, strings.Join(types.SymbolTypeNames(), ", "))
+		}
+		t.kind = kind
+	case FieldLang:
+		if names := lang.Names(); t.lower != lang.PlainText && !slices.Contains(names, t.lower) {
+			return nil, p.errorf(tok.valuePos, "unknown language %q, known languages: %s, %s", tok.value, strings.Join(names, ", "), lang.PlainText)
+		}
+	case FieldText:
+		t.text = regexp.MustCompile("(?i)" + regexp.QuoteMeta(tok.value))
+	case FieldSig:
+		t.sig = strings.ToLower(types.NormalizeSignature(tok.value, false))
+		t.bareSig = strings.ToLower(types.NormalizeSignature(tok.value, true))
+	default:
+		if strings.ContainsAny(tok.value, "*?[") {
+			if _, err := path.Match(t.lower, ""); err != nil {
+				return nil, p.errorf(tok.valuePos, "malformed glob %q", tok.value)
+			}
+			t.glob = true
+		}
+	}
+	return t, nil
+}
+
+func fieldNames() string {
+	names := make([]string, len(Fields))
+	for i, f := range Fields {
+		names[i] = string(f)
+	}
+	return strings.Join(names, ", ")
+}
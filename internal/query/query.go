@@ -0,0 +1,210 @@
+// Package query implements lci's query language, which combines symbol filters with
+// boolean logic:
+//
+//	kind:fn AND name:user AND NOT path:auth
+//	(kind:struct OR kind:trait) AND lang:rust
+//	name:handler text:"not found"
+//	kind:fn sig:Result<User
+//	kind:struct AND token NEAR/5 expires
+//
+// A term is field:value, or a bare value that matches symbol names. Values may be
+// quoted to include spaces, parentheses or the words AND, OR, NOT and NEAR. Adjacent
+// terms are joined with AND; NOT binds tighter than AND, which binds tighter than OR.
+// Parentheses group. A "-" before a term, as in `name:user -path:auth`, is shorthand
+// for NOT. NEAR/n and NEARW/n join two words that must appear within n tokens of each
+// other in the symbol's declaration, and bind tightest; see Near.
+//
+// Queries are evaluated against symbols, one Symbol at a time. Plain content searches
+// quote phrases the same way; see SplitPhrases.
+package query
+
+import (
+	"path"
+	"regexp"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// Field is the part of a symbol a term matches
+type Field string
+
+const (
+	FieldKind   Field = "kind"   // Symbol kind: fn, method, struct, ... (see types.ParseSymbolType)
+	FieldName   Field = "name"   // Symbol name, case-insensitive substring or glob
+	FieldParent Field = "parent" // Enclosing type or function, case-insensitive substring or glob
+	FieldPath   Field = "path"   // File path, case-insensitive substring, or glob against the path or file name
+	FieldLang   Field = "lang"   // File language as reported by lang.Detect, "text" for plain text
+	FieldText   Field = "text"   // Source of the symbol's declaration, case-insensitive substring
+	FieldSig    Field = "sig"    // Function parameters and return type, case-insensitive substring after types.NormalizeSignature
+)
+
+// Fields lists the fields a term may name, in the order they are documented
+var Fields = []Field{FieldKind, FieldName, FieldParent, FieldPath, FieldLang, FieldText, FieldSig}
+
+// Symbol is what a query is evaluated against: one indexed symbol and its file
+type Symbol struct {
+	Name      string
+	Kind      types.SymbolType
+	Parent    string
+	Path      string
+	Language  string
+	Source    []byte // The symbol's declaration span, matched by text: terms
+	Signature string // types.Symbol.Signature, matched by sig: terms
+
+	// Positions returns the ascending token positions of a lowercased word within the
+	// declaration span, for NEAR; nil when the query has none
+	Positions func(word string) []int
+
+	StripGenerics bool // Compare sig: terms with lifetimes and generic arguments removed from both sides
+}
+
+// Query is a parsed query expression
+type Query interface {
+	// Match reports whether sym satisfies the expression
+	Match(sym *Symbol) bool
+	// String formats the expression with explicit operators and parentheses
+	String() string
+}
+
+// And matches symbols both operands match
+type And struct {
+	Left, Right Query
+}
+
+// Or matches symbols either operand matches
+type Or struct {
+	Left, Right Query
+}
+
+// Not matches symbols its operand does not match
+type Not struct {
+	Operand Query
+}
+
+// Term matches one field of a symbol against a value
+type Term struct {
+	Field  Field
+	Value  string
+	Phrase bool // The value was quoted
+	Pos    int  // Byte offset of the term in the query
+
+	kind    types.SymbolType // Parsed Value of a kind: term
+	lower   string           // Lowercased Value
+	glob    bool             // Value contains glob metacharacters
+	text    *regexp.Regexp   // Case-insensitive matcher of a text: term
+	sig     string           // Normalized, lowercased Value of a sig: term
+	bareSig string           // sig with lifetimes and generic arguments stripped
+}
+
+func (q *And) Match(sym *Symbol) bool { return q.Left.Match(sym) && q.Right.Match(sym) }
+func (q *Or) Match(sym *Symbol) bool  { return q.Left.Match(sym) || q.Right.Match(sym) }
+func (q *Not) Match(sym *Symbol) bool { return !q.Operand.Match(sym) }
+
+func (q *And) String() string { return "(" + q.Left.String() + " AND " + q.Right.String() + ")" }
+func (q *Or) String() string  { return "(" + q.Left.String() + " OR " + q.Right.String() + ")" }
+func (q *Not) String() string { return "NOT " + q.Operand.String() }
+
+func (t *Term) String() string {
+	if t.Phrase {
+		return string(t.Field) + ":" + quote(t.Value)
+	}
+	return string(t.Field) + ":" + t.Value
+}
+
+// Match reports whether the term's field of sym matches its value
+func (t *Term) Match(sym *Symbol) bool {
+	switch t.Field {
+	case FieldKind:
+		return sym.Kind == t.kind
+	case FieldName:
+		return t.matchString(sym.Name)
+	case FieldParent:
+		return sym.Parent != "" && t.matchString(sym.Parent)
+	case FieldPath:
+		if t.glob {
+			p := strings.ToLower(sym.Path)
+			return globMatch(t.lower, p) || globMatch(t.lower, path.Base(p))
+		}
+		return strings.Contains(strings.ToLower(sym.Path), t.lower)
+	case FieldLang:
+		return sym.Language == t.lower
+	case FieldText:
+		return t.text.Match(sym.Source)
+	case FieldSig:
+		return t.matchSignature(sym)
+	}
+	return false
+}
+
+// matchString matches s as a glob when the value has metacharacters, else as a
+// substring, ignoring case either way
+func (t *Term) matchString(s string) bool {
+	s = strings.ToLower(s)
+	if t.glob {
+		return globMatch(t.lower, s)
+	}
+	return strings.Contains(s, t.lower)
+}
+
+// matchSignature matches sym's signature as a substring, normalized the way the term's
+// value was
+func (t *Term) matchSignature(sym *Symbol) bool {
+	if sym.Signature == "" {
+		return false
+	}
+	if sym.StripGenerics {
+		return strings.Contains(strings.ToLower(types.NormalizeSignature(sym.Signature, true)), t.bareSig)
+	}
+	return strings.Contains(strings.ToLower(sym.Signature), t.sig)
+}
+
+func globMatch(pattern, s string) bool {
+	ok, _ := path.Match(pattern, s)
+	return ok
+}
+
+// Terms returns the query's terms in the order they appear
+func Terms(q Query) []*Term {
+	var terms []*Term
+	var walk func(Query)
+	walk = func(q Query) {
+		switch n := q.(type) {
+		case *And:
+			walk(n.Left)
+			walk(n.Right)
+		case *Or:
+			walk(n.Left)
+			walk(n.Right)
+		case *Not:
+			walk(n.Operand)
+		case *Term:
+			terms = append(terms, n)
+		}
+	}
+	walk(q)
+	return terms
+}
+
+// NeedsSource reports whether evaluating q reads Symbol.Source
+func NeedsSource(q Query) bool {
+	for _, t := range Terms(q) {
+		if t.Field == FieldText {
+			return true
+		}
+	}
+	return false
+}
+
+func quote(s string) string {
+	var b strings.Builder
+	b.WriteByte('"')
+	for _, r := range s {
+		if r == '"' || r == '\\' {
+			b.WriteByte('\\')
+		}
+		b.WriteRune(r)
+	}
+	b.WriteByte('"')
+	return b.String()
+}
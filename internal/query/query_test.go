@@ -0,0 +1,202 @@
+package query
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestParse(t *testing.T) {
+	tests := map[string]string{
+		"kind:fn AND name:user AND NOT path:auth": "((kind:fn AND name:user) AND NOT path:auth)",
+		"kind:fn name:user":                       "(kind:fn AND name:user)",
+		"a OR b AND c":                            "(name:a OR (name:b AND name:c))",
+		"(a OR b) AND c":                          "((name:a OR name:b) AND name:c)",
+		"NOT NOT a":                               "NOT NOT name:a",
+		`text:"not found" OR "AND"`:               `(text:"not found" OR name:"AND")`,
+		`text:"say \"hi\""`:                       `text:"say \"hi\""`,
+		"Kind:struct":                             "kind:struct",
+		"name:user -path:auth":                    "(name:user AND NOT path:auth)",
+		`a -"b c"`:                                `(name:a AND NOT name:"b c")`,
+		"token NEAR/5 expires":                    "(token NEAR/5 expires)",
+		"kind:struct Token NEARW/3 text:Expires":  "(kind:struct AND (token NEARW/3 expires))",
+		"NOT a NEAR/2 b OR c":                     "(NOT (a NEAR/2 b) OR name:c)",
+		`"NEAR"`:                                  `name:"NEAR"`,
+	}
+	for input, want := range tests {
+		q, err := Parse(input)
+		require.NoError(t, err, input)
+		assert.Equal(t, want, q.String(), input)
+	}
+}
+
+func TestParseErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		pos   int
+		msg   string
+	}{
+		{"", 0, "empty query"},
+		{"(kind:fn OR name:user", 0, "unclosed ("},
+		{"kind:fn AND (name:a OR (name:b)", 12, "unclosed ("},
+		{"kind:fn)", 7, "unexpected )"},
+		{"kind:fn AND ()", 13, "empty parentheses"},
+		{"kind:fn AND size:10", 12, `unknown field "size"`},
+		{"kind:fn AND kind:widget", 17, `unknown kind "widget"`},
+		{"lang:cobol", 5, `unknown language "cobol"`},
+		{"name:", 5, "missing value after name:"},
+		{`text:"unterminated`, 5, "unterminated quote"},
+		{"kind:fn AND", 11, "expected a term after AND"},
+		{"kind:fn AND OR name:a", 12, "expected a term before OR"},
+		{"name:[a", 5, "malformed glob"},
+		{"a NEAR b", 2, "NEAR needs a distance"},
+		{"a NEAR/0 b", 2, "NEAR/0 needs a distance"},
+		{"(a OR b) NEAR/3 c", 9, "not parenthesized expressions"},
+		{"a NEAR/3 kind:fn", 9, "not kind: terms"},
+		{"a NEAR/3 foo.bar", 9, "single words"},
+		{"a NEAR/3 b NEAR/3 c", 11, "cannot follow another NEAR"},
+		{"a NEAR/3", 8, "expected a term after NEAR/3"},
+	}
+	for _, tt := range tests {
+		_, err := Parse(tt.input)
+		var qe *QueryError
+		require.True(t, errors.As(err, &qe), "%q: want a QueryError, got %v", tt.input, err)
+		assert.Equal(t, tt.pos, qe.Pos, tt.input)
+		assert.Contains(t, qe.Msg, tt.msg, tt.input)
+	}
+}
+
+func TestMatch(t *testing.T) {
+	findUser := &Symbol{Name: "find_user", Kind: types.SymbolTypeFunction, Path: "src/db.rs", Language: "rust", Source: []byte("fn find_user() { panic!(\"Not Found\") }")}
+	login := &Symbol{Name: "login_user", Kind: types.SymbolTypeFunction, Path: "src/auth/login.rs", Language: "rust"}
+	user := &Symbol{Name: "User", Kind: types.SymbolTypeStruct, Path: "src/models.go", Language: "go"}
+	method := &Symbol{Name: "save", Kind: types.SymbolTypeMethod, Parent: "UserStore", Path: "src/store_test.go", Language: "go"}
+	all := []*Symbol{findUser, login, user, method}
+
+	tests := map[string][]*Symbol{
+		"kind:fn AND name:user AND NOT path:auth": {findUser},
+		"name:user":                               {findUser, login, user},
+		"kind:struct OR parent:userstore":         {user, method},
+		"NOT lang:rust":                           {user, method},
+		`text:"not found"`:                        {findUser},
+		"name:*_user":                             {findUser, login},
+		"path:*_test.go":                          {method},
+		"path:src/*.go":                           {user, method},
+	}
+	for input, want := range tests {
+		q, err := Parse(input)
+		require.NoError(t, err, input)
+		var got []*Symbol
+		for _, sym := range all {
+			if q.Match(sym) {
+				got = append(got, sym)
+			}
+		}
+		assert.Equal(t, want, got, input)
+	}
+}
+
+func TestMatchSignature(t *testing.T) {
+	findUser := &Symbol{Name: "find_user", Kind: types.SymbolTypeMethod, Signature: "(&self, id: &str) -> Result<User, Box<dyn Error>>"}
+	getUser := &Symbol{Name: "get_user", Kind: types.SymbolTypeMethod, Signature: "(&'a self, id: &str) -> Result<User, Error>"}
+	save := &Symbol{Name: "save", Kind: types.SymbolTypeMethod, Signature: "(&self, user: &User) -> Result<(), Error>"}
+	user := &Symbol{Name: "User", Kind: types.SymbolTypeStruct}
+	all := []*Symbol{findUser, getUser, save, user}
+
+	matching := func(input string, strip bool) []string {
+		t.Helper()
+		q, err := Parse(input)
+		require.NoError(t, err, input)
+		var got []string
+		for _, sym := range all {
+			candidate := *sym
+			candidate.StripGenerics = strip
+			if q.Match(&candidate) {
+				got = append(got, sym.Name)
+			}
+		}
+		return got
+	}
+
+	assert.Equal(t, []string{"find_user", "get_user"}, matching("sig:Result<User", false))
+	assert.Equal(t, []string{"find_user", "get_user"}, matching(`sig:"result< user ,"`, false), "the value is normalized like signatures")
+	assert.Equal(t, []string{"find_user", "save"}, matching(`sig:"(&self,"`, false))
+	assert.Equal(t, []string{"find_user", "get_user", "save"}, matching(`sig:"(&self,"`, true), "stripping drops lifetimes")
+	assert.Equal(t, []string{"find_user", "get_user", "save"}, matching(`sig:"-> Result<T>"`, true), "stripping drops generic arguments")
+	assert.Empty(t, matching(`sig:"-> Result<T>"`, false))
+}
+
+func TestMatchNear(t *testing.T) {
+	symbol := func(name, source string) *Symbol {
+		return &Symbol{Name: name, Kind: types.SymbolTypeStruct, Positions: func(word string) []int {
+			var positions []int
+			for _, o := range core.FindOccurrences([]byte(source), word) {
+				positions = append(positions, o.Position)
+			}
+			return positions
+		}}
+	}
+	tok := symbol("Token", "pub struct Token { value: String, expires: Instant }")
+	session := symbol("Session", "struct Session { expires: Instant, user: User, token: Token }")
+	errs := symbol("check", "fn check() { if err != nil { return err } }")
+	all := []*Symbol{tok, session, errs}
+
+	matching := func(input string) []string {
+		t.Helper()
+		q, err := Parse(input)
+		require.NoError(t, err, input)
+		var got []string
+		for _, sym := range all {
+			if q.Match(sym) {
+				got = append(got, sym.Name)
+			}
+		}
+		return got
+	}
+
+	assert.Equal(t, []string{"Token"}, matching("token NEAR/3 expires"), "Token's words are 3 apart, Session's 4")
+	assert.Equal(t, []string{"Token", "Session"}, matching("token NEAR/4 expires"))
+	assert.Equal(t, []string{"Token", "Session"}, matching("expires NEAR/4 token"), "NEAR ignores order")
+	assert.Equal(t, []string{"Token"}, matching("token NEARW/4 expires"), "NEARW needs the left word first")
+	assert.Equal(t, []string{"Session"}, matching("expires NEARW/4 token"))
+	assert.Equal(t, []string{"Session"}, matching("token NEAR/1 token"), "the same word needs two occurrences")
+	assert.Equal(t, []string{"check"}, matching("err NEAR/3 err"))
+	assert.Empty(t, matching("err NEAR/2 err"))
+	assert.False(t, (&Near{Left: "a", Right: "b", Distance: 5}).Match(&Symbol{Name: "a"}), "symbols without positions never match")
+
+	q, err := Parse("token NEAR/4 expires")
+	require.NoError(t, err)
+	assert.Equal(t, 1.0/2, Score(q, tok), "3 apart of at most 4 scores (4-3+1)/4")
+	assert.Equal(t, 1.0/4, Score(q, session))
+	assert.Greater(t, Score(q, tok), Score(q, session), "closer words score higher")
+
+	q, err = Parse("kind:struct OR token NEAR/4 expires")
+	require.NoError(t, err)
+	assert.Equal(t, 1.0, Score(q, session), "OR scores its better operand")
+	assert.False(t, HasNear(&Not{Operand: &Term{Field: FieldName, Value: "a"}}))
+	assert.True(t, HasNear(q))
+}
+
+func TestSplitPhrases(t *testing.T) {
+	tests := map[string][]Phrase{
+		`invalid credentials`:           {{Text: "invalid"}, {Text: "credentials"}},
+		`"invalid credentials"`:         {{Text: "invalid credentials", Quoted: true}},
+		`  "handle user request"  auth`: {{Text: "handle user request", Quoted: true}, {Text: "auth"}},
+		`"say \"hi\""`:                  {{Text: `say "hi"`, Quoted: true}},
+		`f("x")`:                        {{Text: `f("x")`}},
+		`"use strict";`:                 {{Text: `"use`}, {Text: `strict";`}},
+		`"unterminated phrase`:          {{Text: `"unterminated`}, {Text: "phrase"}},
+		`""`:                            {{Text: `""`}},
+		`user -token -"auth header"`:    {{Text: "user"}, {Text: "token", Negated: true}, {Text: "auth header", Quoted: true, Negated: true}},
+		`x -1 --verbose -`:              {{Text: "x"}, {Text: "-1"}, {Text: "--verbose"}, {Text: "-"}},
+	}
+	for input, want := range tests {
+		assert.Equal(t, want, SplitPhrases(input), input)
+	}
+	assert.Empty(t, SplitPhrases("   "))
+}
@@ -107,6 +107,44 @@ func (hre *HybridRegexEngine) SearchWithRegex(
 		result.CacheTime += classifyTime
 	}
 
+	return hre.execute(simplePattern, complexRegex, candidateFiles, getFileContent, result, startTime)
+}
+
+// SearchWithCompiled is SearchWithRegex for a pattern the caller has already compiled,
+// with the flags SearchWithRegex would add, so it is not compiled again. pattern is
+// the uncompiled expression, used to classify it and extract literals.
+func (hre *HybridRegexEngine) SearchWithCompiled(
+	pattern string,
+	compiled *regexp.Regexp,
+	getFileContent func(types.FileID) ([]byte, bool),
+	candidateFiles []types.FileID,
+) ([]searchtypes.Match, *RegexExecutionResult) {
+
+	startTime := time.Now()
+	result := &RegexExecutionResult{
+		CandidatesTotal: len(candidateFiles),
+	}
+
+	if hre.classifier.IsSimple(pattern) {
+		simplePattern := &SimpleRegexPattern{
+			Pattern:  pattern,
+			Literals: hre.extractor.ExtractLiterals(pattern),
+			Compiled: compiled,
+		}
+		return hre.execute(simplePattern, nil, candidateFiles, getFileContent, result, startTime)
+	}
+	return hre.execute(nil, compiled, candidateFiles, getFileContent, result, startTime)
+}
+
+// execute runs whichever of simplePattern and complexRegex is set over candidateFiles
+func (hre *HybridRegexEngine) execute(
+	simplePattern *SimpleRegexPattern,
+	complexRegex *regexp.Regexp,
+	candidateFiles []types.FileID,
+	getFileContent func(types.FileID) ([]byte, bool),
+	result *RegexExecutionResult,
+	startTime time.Time,
+) ([]searchtypes.Match, *RegexExecutionResult) {
 	// Execute based on pattern type
 	var matches []searchtypes.Match
 
@@ -83,6 +83,35 @@ func TestHybridRegexEngineCaching(t *testing.T) {
 	}
 }
 
+// TestHybridRegexEngineSearchWithCompiled tests searching with a caller-compiled regex
+func TestHybridRegexEngineSearchWithCompiled(t *testing.T) {
+	engine := NewHybridRegexEngine(5, 5, nil)
+
+	content := []byte("func main() {}\nfunc helper() {}")
+	contentProvider := func(id types.FileID) ([]byte, bool) {
+		if id == 1 {
+			return content, true
+		}
+		return nil, false
+	}
+
+	compiled := regexp.MustCompile(`(?m)^func \w+`)
+	matches, result := engine.SearchWithCompiled(`^func \w+`, compiled, contentProvider, []types.FileID{1})
+
+	if len(matches) != 2 {
+		t.Errorf("Expected 2 matches, got %d", len(matches))
+	}
+	if result.CacheHit {
+		t.Errorf("A compiled pattern should not come from the cache")
+	}
+
+	// The cache is left alone, so SearchWithRegex still compiles the pattern itself
+	_, result = engine.SearchWithRegex(`^func \w+`, false, contentProvider, []types.FileID{1})
+	if result.CacheHit {
+		t.Errorf("SearchWithCompiled should not have cached the pattern")
+	}
+}
+
 // TestHybridRegexEngineLiteralExtraction tests trigram filtering
 func TestHybridRegexEngineLiteralExtraction(t *testing.T) {
 	engine := NewHybridRegexEngine(10, 10, nil)
@@ -0,0 +1,118 @@
+package search
+
+import (
+	"slices"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/query"
+)
+
+// defaultAliases expands common abbreviations in identifiers to the words they stand
+// for, keyed by lower-case abbreviation. Expansion is one way: cfg finds config, but
+// config does not find cfg.
+var defaultAliases = map[string][]string{
+	"arg":   {"argument"},
+	"auth":  {"authenticate", "authorize"},
+	"btn":   {"button"},
+	"cfg":   {"config", "configuration"},
+	"conf":  {"config"},
+	"ctx":   {"context"},
+	"db":    {"database"},
+	"env":   {"environment"},
+	"err":   {"error"},
+	"fn":    {"function", "func"},
+	"idx":   {"index"},
+	"impl":  {"implementation"},
+	"init":  {"initialize"},
+	"mgr":   {"manager"},
+	"msg":   {"message"},
+	"num":   {"number"},
+	"param": {"parameter"},
+	"repo":  {"repository"},
+	"req":   {"request"},
+	"resp":  {"response"},
+	"str":   {"string"},
+	"svc":   {"service"},
+	"usr":   {"user"},
+}
+
+// DefaultAliases returns a copy of the built-in aliases, which callers may change
+func DefaultAliases() map[string][]string {
+	return MergeAliases(defaultAliases, nil)
+}
+
+// Expansion limits, so a pattern of many abbreviations stays a few extra substring
+// checks per line rather than a search per combination of spellings
+const (
+	maxAliasesPerTerm  = 4  // Alternatives kept for one word
+	maxAliasesPerQuery = 12 // Alternatives kept across the pattern; later words are not expanded
+)
+
+// MergeAliases returns base with the alternatives of extra added, such as the defaults
+// with a project's aliases from .lci.kdl. Keys are lower-cased and repeated spellings
+// dropped.
+func MergeAliases(base, extra map[string][]string) map[string][]string {
+	merged := make(map[string][]string, len(base)+len(extra))
+	for _, aliases := range []map[string][]string{base, extra} {
+		for term, spellings := range aliases {
+			term = strings.ToLower(term)
+			for _, s := range spellings {
+				if s != "" && !slices.Contains(merged[term], s) {
+					merged[term] = append(merged[term], s)
+				}
+			}
+		}
+	}
+	return merged
+}
+
+// expandAliases gives each unquoted term listed in aliases its alternatives, so any of
+// them satisfies the term; negated terms then exclude every spelling. Each term is an
+// OR group of its spellings, so n expanded words cost n groups, not a search for every
+// combination, and the limits above bound the group sizes. Without wholeWord, a
+// spelling holding the word itself, such as initialize for init, already matches and
+// is left out.
+func expandAliases(terms []query.Phrase, aliases map[string][]string, caseInsensitive, wholeWord bool) []query.Phrase {
+	if len(aliases) == 0 {
+		return terms
+	}
+	fold := func(s string) string { return s }
+	if caseInsensitive {
+		fold = strings.ToLower
+	}
+	budget := maxAliasesPerQuery
+	for i, t := range terms {
+		if t.Quoted {
+			continue
+		}
+		var alternatives []string
+		for _, s := range aliases[strings.ToLower(t.Text)] {
+			if budget == 0 || len(alternatives) == maxAliasesPerTerm {
+				break
+			}
+			if !wholeWord && strings.Contains(fold(s), fold(t.Text)) {
+				continue
+			}
+			alternatives = append(alternatives, s)
+			budget--
+		}
+		terms[i].Alternatives = alternatives
+	}
+	return terms
+}
+
+// hasAliases reports whether any word of pattern has aliases
+func hasAliases(pattern string, aliases map[string][]string) bool {
+	for _, word := range strings.Fields(pattern) {
+		if len(aliases[strings.ToLower(strings.TrimPrefix(word, "-"))]) > 0 {
+			return true
+		}
+	}
+	return false
+}
+
+// needsTermSearch reports whether terms must be searched by searchAllTerms, because
+// there are several or because one has alternatives
+func needsTermSearch(terms []query.Phrase) bool {
+	return len(terms) > 1 || (len(terms) == 1 && len(terms[0].Alternatives) > 0)
+}
@@ -0,0 +1,46 @@
+package search
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/query"
+)
+
+func TestExpandAliases(t *testing.T) {
+	terms := expandAliases(query.SplitPhrases(`cfg -usr "cfg" init`), defaultAliases, true, false)
+	assert.Equal(t, []string{"config", "configuration"}, terms[0].Alternatives)
+	assert.Equal(t, []string{"user"}, terms[1].Alternatives, "negated words exclude every spelling")
+	assert.Empty(t, terms[2].Alternatives, "quoted phrases are matched as written")
+	assert.Empty(t, terms[3].Alternatives, "initialize already matches init as a substring")
+
+	terms = expandAliases(query.SplitPhrases("init"), defaultAliases, true, true)
+	assert.Equal(t, []string{"initialize"}, terms[0].Alternatives, "whole-word searches need the longer spelling")
+
+	many := map[string][]string{"x": {"a1", "a2", "a3", "a4", "a5", "a6"}}
+	terms = expandAliases(query.SplitPhrases("x"), many, false, false)
+	assert.Len(t, terms[0].Alternatives, maxAliasesPerTerm)
+
+	words := strings.Fields("cfg usr db ctx err msg req resp svc mgr repo env btn")
+	total := 0
+	for _, term := range expandAliases(query.SplitPhrases(strings.Join(words, " ")), defaultAliases, true, false) {
+		total += len(term.Alternatives)
+	}
+	assert.Equal(t, maxAliasesPerQuery, total, "a pattern of many abbreviations stops expanding at the limit")
+}
+
+func TestMergeAliases(t *testing.T) {
+	merged := MergeAliases(map[string][]string{"cfg": {"config"}}, map[string][]string{"CFG": {"settings", "config"}, "tx": {"transaction"}})
+	assert.Equal(t, map[string][]string{"cfg": {"config", "settings"}, "tx": {"transaction"}}, merged)
+}
+
+func TestDefaultAliasesIsACopy(t *testing.T) {
+	aliases := DefaultAliases()
+	aliases["cfg"][0] = "changed"
+	delete(aliases, "ctx")
+
+	assert.Equal(t, []string{"config", "configuration"}, DefaultAliases()["cfg"])
+	assert.Contains(t, DefaultAliases(), "ctx")
+}
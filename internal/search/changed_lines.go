@@ -0,0 +1,57 @@
+package search
+
+import "github.com/standardbeagle/lci/internal/types"
+
+// resolveLineRanges converts SearchOptions.ChangedLines to byte spans through each
+// file's LineIndex. Paths must equal the indexed ones exactly, so a file is never
+// matched by another that merely shares its name.
+func (e *Engine) resolveLineRanges(ranges map[string][]types.LineRange, candidates []types.FileID) map[types.FileID][]byteSpan {
+	spans := make(map[types.FileID][]byteSpan)
+	if len(ranges) == 0 {
+		return spans
+	}
+	if len(candidates) == 0 {
+		candidates = e.getAllFileIDs()
+	}
+
+	for _, fileID := range candidates {
+		lines, ok := ranges[e.indexer.GetFilePath(fileID)]
+		if !ok {
+			continue
+		}
+		content, ok := e.indexer.GetFileContent(fileID)
+		if !ok {
+			continue
+		}
+		index := types.NewLineIndex(content)
+		for _, r := range lines {
+			if r.Start < 1 || r.Start > index.LineCount() || r.End < r.Start {
+				continue
+			}
+			start := index.Offset(types.Position{Line: r.Start, Column: 1}, types.PositionEncodingUTF8)
+			end := len(content)
+			if r.End < index.LineCount() {
+				end = index.Offset(types.Position{Line: r.End + 1, Column: 1}, types.PositionEncodingUTF8)
+			}
+			if start < end {
+				spans[fileID] = append(spans[fileID], byteSpan{start, end})
+			}
+		}
+	}
+	return spans
+}
+
+// intersectSpans returns the overlap of two span sets, by file
+func intersectSpans(a, b map[types.FileID][]byteSpan) map[types.FileID][]byteSpan {
+	out := make(map[types.FileID][]byteSpan)
+	for fileID, as := range a {
+		for _, x := range as {
+			for _, y := range b[fileID] {
+				if s := (byteSpan{max(x.start, y.start), min(x.end, y.end)}); s.start < s.end {
+					out[fileID] = append(out[fileID], s)
+				}
+			}
+		}
+	}
+	return out
+}
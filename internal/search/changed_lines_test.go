@@ -0,0 +1,46 @@
+package search_test
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchChangedLines(t *testing.T) {
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{
+		"auth.go":  "package auth\n\n// token one\n// token two\n// token three\n// token four\n",
+		"other.go": "package other\n\n// token\n",
+	})
+	defer cleanup()
+
+	paths := make(map[string]string)
+	for _, r := range engine.SearchWithOptions("package", fileIDs, types.SearchOptions{}) {
+		paths[filepath.Base(r.Path)] = r.Path
+	}
+
+	hitLines := func(options types.SearchOptions) map[string][]int {
+		got := make(map[string][]int)
+		for _, r := range engine.SearchWithOptions("token", fileIDs, options) {
+			name := filepath.Base(r.Path)
+			got[name] = append(got[name], r.Line)
+		}
+		return got
+	}
+
+	changed := map[string][]types.LineRange{paths["auth.go"]: {{Start: 4, End: 5}}}
+	got := hitLines(types.SearchOptions{ChangedLines: changed})
+	assert.ElementsMatch(t, []int{4, 5}, got["auth.go"], "hits outside changed hunks are suppressed")
+	assert.NotContains(t, got, "other.go", "unchanged files have no hits")
+
+	last := map[string][]types.LineRange{paths["auth.go"]: {{Start: 6, End: 6}}}
+	assert.Equal(t, map[string][]int{"auth.go": {6}}, hitLines(types.SearchOptions{ChangedLines: last}),
+		"a hunk ending on the last line covers it")
+
+	assert.Empty(t, hitLines(types.SearchOptions{ChangedLines: map[string][]types.LineRange{}}),
+		"an empty diff matches nothing")
+	assert.Empty(t, hitLines(types.SearchOptions{ChangedLines: map[string][]types.LineRange{"auth.go": {{Start: 4, End: 5}}}}),
+		"paths must equal the indexed ones, not merely end like them")
+}
@@ -0,0 +1,54 @@
+package search_test
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestSearch_CRLFLineEndings searches a fixture saved with CRLF endings and one LF-only
+// line: "\r\n" is a single line break, snippets carry no '\r' and byte spans still
+// index the original content
+func TestSearch_CRLFLineEndings(t *testing.T) {
+	content, err := os.ReadFile(filepath.Join("testdata", "sample_crlf.go"))
+	require.NoError(t, err)
+	require.Contains(t, string(content), "\r\n")
+	require.Contains(t, string(content), "}\n\r\n", "the fixture mixes in an LF-only line")
+
+	gi, engine := setupRankerIndex(t, map[string]string{"sample_crlf.go": string(content)})
+
+	for _, options := range []types.SearchOptions{
+		{MaxContextLines: 2},
+		{MaxContextLines: 0},
+		{WordBoundary: true, MaxContextLines: 2},
+	} {
+		results := engine.SearchWithOptions("Farewell", nil, options)
+		var hits []string
+		for _, r := range results {
+			hits = append(hits, fmt.Sprintf("%d:%d", r.Line, r.Column))
+			assert.Equal(t, "Farewell", string(content[r.StartByte:r.EndByte]), "byte spans keep their original offsets")
+			require.NotEmpty(t, r.Context.Lines)
+			for _, line := range r.Context.Lines {
+				assert.False(t, strings.HasSuffix(line, "\r"), "snippet line %q keeps a carriage return", line)
+			}
+		}
+		assert.Equal(t, []string{"8:3", "9:5"}, hits, "options %+v", options)
+	}
+
+	results := engine.SearchWithOptions("Greeting()", nil, types.SearchOptions{MaxContextLines: 1})
+	require.Len(t, results, 1)
+	assert.Equal(t, 4, results[0].Line)
+	assert.Equal(t, []string{"// Greeting is saved with CRLF line endings", "func Greeting() string {", "\treturn \"hello\""}, results[0].Context.Lines)
+
+	line, ok := gi.GetFileLine(results[0].FileID, 6)
+	require.True(t, ok)
+	assert.Equal(t, "}", line, "an LF-only line reads the same")
+	assert.Equal(t, 9, gi.GetFileLineCount(results[0].FileID))
+}
@@ -0,0 +1,27 @@
+package search
+
+import (
+	"time"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// DeadlineExceeded reports whether options carries a deadline that has passed. Searches
+// check it between files, so a search that runs past its deadline stops early and
+// returns the hits found in the files it had already searched.
+func DeadlineExceeded(options types.SearchOptions) bool {
+	return !options.Deadline.IsZero() && time.Now().After(options.Deadline)
+}
+
+// stopAtDeadline is DeadlineExceeded for a search loop about to search another file: a
+// search that stops there has skipped files, so it sets options.TimedOut. A deadline
+// passed only while ranking the hits of every file is not a timeout.
+func stopAtDeadline(options types.SearchOptions) bool {
+	if !DeadlineExceeded(options) {
+		return false
+	}
+	if options.TimedOut != nil {
+		options.TimedOut.Store(true)
+	}
+	return true
+}
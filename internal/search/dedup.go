@@ -0,0 +1,71 @@
+package search
+
+import (
+	"slices"
+
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// matcherFor names the matcher a search for pattern runs with options
+func matcherFor(pattern string, options types.SearchOptions) Matcher {
+	switch {
+	case options.Fuzzy != "":
+		return searchtypes.MatcherFuzzy
+	case options.SymbolsOnly:
+		return searchtypes.MatcherSymbol
+	case options.DocsOnly:
+		return searchtypes.MatcherDoc
+	case options.UseRegex && !isLiteralPattern(pattern):
+		return searchtypes.MatcherRegex
+	default:
+		return searchtypes.MatcherSubstring
+	}
+}
+
+// tagMatcher records m on results that do not name a matcher yet
+func tagMatcher(results []GrepResult, m Matcher) {
+	for i := range results {
+		if len(results[i].MatchedBy) == 0 {
+			results[i].MatchedBy = []Matcher{m}
+		}
+	}
+}
+
+// dedupKey identifies a hit by the bytes it covers
+type dedupKey struct {
+	path       string
+	start, end int
+}
+
+// dedupResults merges results covering the same byte span of the same file, keeping
+// the first one's position in the list. Results without a span are never merged.
+func dedupResults(results []GrepResult) []GrepResult {
+	seen := make(map[dedupKey]int, len(results))
+	out := results[:0]
+	for _, r := range results {
+		if r.EndByte <= r.StartByte {
+			out = append(out, r)
+			continue
+		}
+		key := dedupKey{path: r.Path, start: r.StartByte, end: r.EndByte}
+		if i, ok := seen[key]; ok {
+			mergeDuplicate(&out[i], r)
+			continue
+		}
+		seen[key] = len(out)
+		out = append(out, r)
+	}
+	return out
+}
+
+// mergeDuplicate folds src into dst: the higher score wins and the matchers of both
+// are recorded
+func mergeDuplicate(dst *GrepResult, src GrepResult) {
+	dst.Score = max(dst.Score, src.Score)
+	for _, m := range src.MatchedBy {
+		if !slices.Contains(dst.MatchedBy, m) {
+			dst.MatchedBy = append(dst.MatchedBy, m)
+		}
+	}
+}
@@ -0,0 +1,47 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchDedupOverlappingMatchers(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"user.rs": "fn get_user() {}\n",
+	})
+	defer cleanup()
+
+	options := types.SearchOptions{
+		Patterns: []string{"get_user", `get_\w+`},
+		UseRegex: true,
+	}
+	results := engine.SearchWithOptions("get_user", nil, options)
+	require.Len(t, results, 1, "both patterns hit the same span")
+	assert.Equal(t, "get_user", results[0].Match)
+	assert.Equal(t, []searchtypes.Matcher{searchtypes.MatcherSubstring, searchtypes.MatcherRegex}, results[0].MatchedBy)
+
+	options.NoDedup = true
+	results = engine.SearchWithOptions("get_user", nil, options)
+	require.Len(t, results, 2, "--no-dedup keeps each matcher's hit")
+	for _, r := range results {
+		assert.Len(t, r.MatchedBy, 1)
+	}
+}
+
+func TestSearchDedupKeepsDistinctSpans(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"user.rs": "fn get_user() {}\nfn get_user_id() {}\n",
+	})
+	defer cleanup()
+
+	results := engine.SearchWithOptions("get_user", nil, types.SearchOptions{})
+	require.Len(t, results, 2)
+	for _, r := range results {
+		assert.Equal(t, []searchtypes.Matcher{searchtypes.MatcherSubstring}, r.MatchedBy)
+	}
+}
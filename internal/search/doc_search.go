@@ -0,0 +1,42 @@
+package search
+
+import (
+	"regexp"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// searchDocComments matches re against the leading doc comment of every indexed symbol,
+// so "retrieves a user" finds the function documented as doing so. Results point at the
+// documented symbol's name, like symbol-name hits, and are ordered by path and position.
+func (e *Engine) searchDocComments(re *regexp.Regexp, options types.SearchOptions) []GrepResult {
+	candidates := e.getAllFileIDs()
+	candidates = e.filterIncludedFiles(candidates, options.IncludePattern)
+	candidates = e.filterExcludedFiles(candidates, options.ExcludePattern)
+	candidates = e.filterGlobFiles(candidates, options.IncludeGlobs, options.ExcludeGlobs)
+
+	var results []GrepResult
+	for _, fileID := range candidates {
+		if ResultCapReached(options, len(results)) || stopAtDeadline(options) {
+			break
+		}
+		symbols := e.indexer.GetFileEnhancedSymbols(fileID)
+		if len(symbols) == 0 {
+			continue
+		}
+		path := e.indexer.GetFilePath(fileID)
+		offsets, _ := e.indexer.GetFileLineOffsets(fileID)
+
+		for _, sym := range symbols {
+			if sym == nil || sym.DocComment == "" || !re.MatchString(sym.DocComment) {
+				continue
+			}
+			if len(options.SymbolTypes) > 0 && !contains(options.SymbolTypes, sym.Type.String()) {
+				continue
+			}
+			results = append(results, e.symbolNameResult(fileID, path, offsets, sym.Symbol, options))
+		}
+	}
+
+	return sortSymbolResults(results, options)
+}
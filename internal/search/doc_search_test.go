@@ -0,0 +1,47 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+const docSearchCode = `pub struct Store;
+
+impl Store {
+    /// Retrieves a user by id.
+    ///
+    /// Returns None when no user matches.
+    pub fn get_user(&self, id: u32) -> Option<User> {
+        // retrieves a user from the cache first
+        None
+    }
+
+    pub fn delete_user(&self, id: u32) {}
+}
+`
+
+func TestSearchDocComments(t *testing.T) {
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"store.rs": docSearchCode})
+	defer cleanup()
+
+	results := engine.SearchWithOptions("retrieves a user", fileIDs, types.SearchOptions{DocsOnly: true, CaseInsensitive: true})
+	require.Len(t, results, 1, "one hit per documented symbol, not per comment line")
+	assert.Equal(t, "get_user", results[0].Match)
+	assert.Equal(t, 7, results[0].Line, "hit points at the symbol's declaration")
+	assert.Equal(t, []searchtypes.Matcher{searchtypes.MatcherDoc}, results[0].MatchedBy)
+
+	results, err := engine.SearchRegex(`None\s+when`, types.SearchOptions{DocsOnly: true})
+	require.NoError(t, err)
+	require.Len(t, results, 1, "multi-line doc comments match as one text")
+	assert.Equal(t, "get_user", results[0].Match)
+
+	assert.Empty(t, engine.SearchWithOptions("delete", fileIDs, types.SearchOptions{DocsOnly: true}), "symbol names are not docs")
+
+	code := engine.SearchWithOptions("retrieves a user", fileIDs, types.SearchOptions{CodeOnly: true, ExcludeComments: true, CaseInsensitive: true})
+	assert.Empty(t, code, "--in code skips comments")
+}
@@ -11,7 +11,9 @@ import (
 	"sort"
 	"strconv"
 	"strings"
+	"sync/atomic"
 	"time"
+	"unicode/utf8"
 
 	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/interfaces"
@@ -35,6 +37,7 @@ type Engine struct {
 	contextExtractor *ContextExtractor
 	regexEngine      *regex_analyzer.HybridRegexEngine
 	semanticScorer   *semantic.SemanticScorer // Semantic scoring for advanced matching (camelCase, fuzzy, etc.)
+	ranker           atomic.Pointer[Ranker]   // Optional scoring strategy replacing scoreMatch (see SetRanker)
 }
 
 func (e *Engine) LastError() error {
@@ -53,6 +56,10 @@ type GrepResult = searchtypes.GrepResult
 type StandardResult = searchtypes.StandardResult
 type Match = searchtypes.Match
 type ExtractedContext = searchtypes.ExtractedContext
+type Matcher = searchtypes.Matcher
+type ScoreExplanation = searchtypes.ScoreExplanation
+type ScoreFactor = searchtypes.ScoreFactor
+type Blame = searchtypes.Blame
 
 // Note: The following type aliases have been removed (use the new names instead):
 // - Result → GrepResult
@@ -266,6 +273,16 @@ func lineStart(content []byte, offset int) int {
 	return idx + 1
 }
 
+// regionText returns the matched bytes as a string. Files indexed as text regardless of
+// their encoding need not be UTF-8, so each matched region is checked here and invalid
+// bytes become U+FFFD.
+func regionText(region []byte) string {
+	if utf8.Valid(region) {
+		return string(region)
+	}
+	return strings.ToValidUTF8(string(region), "\uFFFD")
+}
+
 // bytesToLine counts the number of lines from the beginning of content up to offset
 func bytesToLine(content []byte, offset int) int {
 	if offset >= len(content) {
@@ -309,22 +326,12 @@ func findAllMatchesWithOptions(content, pattern []byte, options types.SearchOpti
 	// Content filtering will be integrated with AST in future iteration
 	// For now, maintain existing search behavior
 
-	// Word boundary support (grep -w)
-	// Convert to regex with word boundaries if enabled
-	if options.WordBoundary && !options.UseRegex {
-		// Use regex engine with \b word boundaries
-		regexPattern := `\b` + regexp.QuoteMeta(string(pattern)) + `\b`
-		opts := options
-		opts.UseRegex = true
-		m, err := findRegexMatchesLegacy(content, regexPattern, opts)
-		if err != nil {
-			return nil
-		}
-		return m
-	}
-
 	if options.UseRegex {
-		m, err := findRegexMatchesLegacy(content, string(pattern), options)
+		expr := string(pattern)
+		if options.WordBoundary {
+			expr = wordRegex(expr)
+		}
+		m, err := findRegexMatchesLegacy(content, expr, options)
 		if err != nil {
 			return nil
 		}
@@ -332,7 +339,23 @@ func findAllMatchesWithOptions(content, pattern []byte, options types.SearchOpti
 	}
 
 	// Literal string search (original behavior)
-	return findLiteralMatches(content, pattern, options)
+	matches := findLiteralMatches(content, pattern, options)
+	if options.WordBoundary {
+		matches = wholeWordMatches(matches)
+	}
+	return matches
+}
+
+// wholeWordMatches keeps the literal matches bounded by non-word characters or the
+// ends of the file (grep -w). findLiteralMatches has already marked those Exact.
+func wholeWordMatches(matches []Match) []Match {
+	kept := matches[:0]
+	for _, m := range matches {
+		if m.Exact {
+			kept = append(kept, m)
+		}
+	}
+	return kept
 }
 
 func findRegexMatchesLegacy(content []byte, pattern string, options types.SearchOptions) ([]Match, error) {
@@ -363,19 +386,37 @@ func findRegexMatchesLegacy(content []byte, pattern string, options types.Search
 	return matches, nil
 }
 
-// searchWithHybridRegex uses the hybrid regex engine for multi-file regex searches
-func (e *Engine) searchWithHybridRegex(pattern string, candidates []types.FileID, options types.SearchOptions) []GrepResult {
-	regexMatches, _ := e.regexEngine.SearchWithRegex(
-		pattern,
-		options.CaseInsensitive,
-		e.indexer.GetFileContent,
-		candidates,
-	)
+// searchWithHybridRegex uses the hybrid regex engine for multi-file regex searches.
+// re is pattern as compiled by compileContentRegex, or nil to have the engine compile it.
+func (e *Engine) searchWithHybridRegex(pattern string, re *regexp.Regexp, candidates []types.FileID, options types.SearchOptions) []GrepResult {
+	expr := contentRegexExpr(pattern, options)
+	find := func(files []types.FileID) []searchtypes.Match {
+		if re != nil {
+			matches, _ := e.regexEngine.SearchWithCompiled(expr, re, e.indexer.GetFileContent, files)
+			return matches
+		}
+		matches, _ := e.regexEngine.SearchWithRegex(expr, options.CaseInsensitive, e.indexer.GetFileContent, files)
+		return matches
+	}
+	var regexMatches []searchtypes.Match
+	if options.Deadline.IsZero() && options.ResultCap <= 0 {
+		regexMatches = find(candidates)
+	} else {
+		// One file at a time so the deadline and result cap are checked between files
+		for _, fileID := range candidates {
+			if ResultCapReached(options, len(regexMatches)) || stopAtDeadline(options) {
+				break
+			}
+			regexMatches = append(regexMatches, find([]types.FileID{fileID})...)
+		}
+	}
 
 	var allResults []GrepResult
 
 	// Track matches per file for MaxCountPerFile support
 	fileMatchCounts := make(map[types.FileID]int)
+	// Line indexes of files with matches spanning lines, built on the first such match
+	lineIndexes := make(map[types.FileID]*types.LineIndex)
 
 	// Hybrid engine returns matches without file attribution; we must map by scanning each file.
 	for _, match := range regexMatches {
@@ -418,9 +459,21 @@ func (e *Engine) searchWithHybridRegex(pattern string, candidates []types.FileID
 		if match.End > len(content) {
 			continue
 		}
-		matchText := string(content[match.Start:match.End])
-		res := GrepResult{FileID: match.FileID, Path: path, Line: line, Column: col, Match: matchText, Score: 1.0}
-		res.Context = e.extractSimpleContext(content, match.Start, match.End)
+		matchText := regionText(content[match.Start:match.End])
+		res := GrepResult{FileID: match.FileID, Path: path, Line: line, Column: col, StartByte: match.Start, EndByte: match.End, Match: matchText, Score: 1.0}
+		if bytes.IndexByte(content[match.Start:max(match.Start, match.End-1)], '\n') >= 0 {
+			li, ok := lineIndexes[match.FileID]
+			if !ok {
+				li = types.NewLineIndex(content)
+				lineIndexes[match.FileID] = li
+			}
+			res.EndLine = li.LineSpan(match.Start, match.End).End
+		}
+		if options.LocationsOnly {
+			res.Context = locationContext(line)
+		} else if !options.CountPerFile {
+			res.Context = e.extractSimpleContext(content, match.Start, match.End)
+		}
 		allResults = append(allResults, res)
 		fileMatchCounts[match.FileID]++
 	}
@@ -568,6 +621,11 @@ func (e *Engine) passesSemanticFilterForGrepResultZeroAlloc(fileInfo *types.File
 	return true
 }
 
+// locationContext is the context of a LocationsOnly hit: its matched line, without text
+func locationContext(line int) ExtractedContext {
+	return ExtractedContext{StartLine: line, EndLine: line, MatchedLines: []int{line}, MatchCount: 1}
+}
+
 // extractSimpleContext extracts basic context around a match
 // Note: This is a fallback for when FileInfo.LineOffsets is not available
 // Prefer using extractSimpleContextWithOffsets for better performance
@@ -583,8 +641,9 @@ func (e *Engine) extractSimpleContext(content []byte, start, end int) ExtractedC
 	}
 
 	// Estimate end line (we don't have total line count without splitting)
-	// This is inefficient but necessary without LineOffsets
-	endLine := matchLine + contextLines
+	// This is inefficient but necessary without LineOffsets. A match spanning lines
+	// keeps all of them, with the context after its last.
+	endLine := bytesToLine(content, max(start, end-1)) + contextLines
 
 	// Extract context lines by counting newlines
 	var contextLinesSlice []string
@@ -593,10 +652,7 @@ func (e *Engine) extractSimpleContext(content []byte, start, end int) ExtractedC
 	for i := 0; i < len(content); i++ {
 		if content[i] == '\n' || i == len(content)-1 {
 			lineNum++
-			lineEnd := i
-			if i == len(content)-1 && content[i] != '\n' {
-				lineEnd = i + 1
-			}
+			lineEnd := types.TrimLineEnding(content, lineStart, i+1)
 
 			if lineNum >= startLine && lineNum <= endLine {
 				contextLinesSlice = append(contextLinesSlice, string(content[lineStart:lineEnd]))
@@ -628,27 +684,26 @@ func findLiteralMatches(content, pattern []byte, options types.SearchOptions) []
 
 	offset := 0
 
-	searchContent := content
-	searchPattern := pattern
-
-	if options.CaseInsensitive {
-		searchContent = bytes.ToLower(content)
-		searchPattern = bytes.ToLower(pattern)
-	}
-
 	for {
 		// Check bounds before slicing
-		if offset >= len(searchContent) {
+		if offset >= len(content) {
 			break
 		}
 
-		idx := bytes.Index(searchContent[offset:], searchPattern)
-		if idx < 0 {
+		// Case-insensitive matching folds case while comparing instead of lowercasing
+		// a copy of the whole file
+		var start, end int
+		if options.CaseInsensitive {
+			start, end = types.IndexFold(content[offset:], pattern)
+		} else {
+			start = bytes.Index(content[offset:], pattern)
+			end = start + len(pattern)
+		}
+		if start < 0 {
 			break
 		}
-
-		start := offset + idx
-		end := start + len(pattern)
+		start += offset
+		end += offset
 
 		// Optionally skip matches on comment-only lines when requested
 		if options.ExcludeComments {
@@ -713,9 +768,15 @@ func (e *Engine) validateAndPreparePatterns(pattern string, options types.Search
 
 // prepareCandidates prepares the candidate files for searching
 func (e *Engine) prepareCandidates(pattern string, candidates []types.FileID, options types.SearchOptions) []types.FileID {
-	// Prefer indexed candidate pruning when available (non-regex, length>=3)
+	// Prefer indexed candidate pruning when available: token postings for whole-word
+	// searches, else trigrams (non-regex, length>=3)
 	if len(candidates) == 0 {
-		if !options.UseRegex && len(pattern) >= 3 {
+		if files, ok := e.postingsCandidates(pattern, options); ok {
+			if len(files) == 0 {
+				return nil // No file holds every word of the pattern
+			}
+			candidates = files
+		} else if !options.UseRegex && len(pattern) >= 3 {
 			// Try to use indexer's candidate finder if available
 			type candidateProvider interface {
 				FindCandidateFiles(string, bool) []types.FileID
@@ -735,12 +796,16 @@ func (e *Engine) prepareCandidates(pattern string, candidates []types.FileID, op
 	// Apply path-based include/exclude filters early to reduce candidates
 	candidates = e.filterIncludedFiles(candidates, options.IncludePattern)
 	candidates = e.filterExcludedFiles(candidates, options.ExcludePattern)
+	candidates = e.filterGlobFiles(candidates, options.IncludeGlobs, options.ExcludeGlobs)
 
 	return candidates
 }
 
 // getEffectiveResultCap determines the effective result cap for the search
 func (e *Engine) getEffectiveResultCap(candidates []types.FileID, options types.SearchOptions) int {
+	if options.CountPerFile {
+		return 0 // Counts must cover every file; a cap would undercount
+	}
 	effectiveCap := options.MaxResults
 	if effectiveCap <= 0 {
 		if len(candidates) >= 400 {
@@ -830,8 +895,8 @@ func (e *Engine) applyMatchLimits(matches []Match, options types.SearchOptions)
 
 // prepareFileInfo prepares FileInfo based on what's needed for processing
 func (e *Engine) prepareFileInfo(fileID types.FileID, path string, content []byte, options types.SearchOptions) (*types.FileInfo, bool) {
-	needsBlocks := options.FullFunction || options.EnsureCompleteStmt ||
-		options.MaxContextLines == 0 || options.MaxContextLines > 10
+	needsBlocks := !options.LocationsOnly && (options.FullFunction || options.EnsureCompleteStmt ||
+		options.MaxContextLines == 0 || options.MaxContextLines > 10)
 
 	var fileInfo *types.FileInfo
 	if needsBlocks {
@@ -856,7 +921,7 @@ func (e *Engine) prepareFileInfo(fileID types.FileID, path string, content []byt
 
 // shouldMergeResults determines if results should be merged
 func (e *Engine) shouldMergeResults(matches []Match, options types.SearchOptions) bool {
-	return options.MergeFileResults && len(matches) > 1 &&
+	return options.MergeFileResults && len(matches) > 1 && !options.LocationsOnly &&
 		!options.DeclarationOnly && !options.UsageOnly &&
 		len(options.SymbolTypes) == 0 && !options.ExportedOnly &&
 		!options.MutableOnly && !options.GlobalOnly
@@ -881,7 +946,7 @@ func (e *Engine) processFile(fileID types.FileID, patternBytes []byte, pattern s
 	}
 
 	// Find all matches
-	matches := findAllMatchesWithOptions(content, patternBytes, options)
+	matches := e.findMatches(fileID, content, patternBytes, options)
 	if len(matches) == 0 {
 		return
 	}
@@ -901,6 +966,12 @@ func (e *Engine) processFile(fileID types.FileID, patternBytes []byte, pattern s
 		return
 	}
 
+	// Count mode only needs the number of matches, so skip building their snippets
+	if options.CountPerFile {
+		*allResults = append(*allResults, GrepResult{FileID: fileID, Path: path, FileMatchCount: len(matches)})
+		return
+	}
+
 	// Process and append results
 	if e.shouldMergeResults(matches, options) {
 		results := e.mergeFileResults(fileInfo, matches, pattern, options)
@@ -917,9 +988,11 @@ func (e *Engine) processIndividualMatches(fileInfo *types.FileInfo, matches []Ma
 
 	var results []GrepResult
 	seenLines := make(map[int]bool)
+	lineSpans := make(map[int][][2]int) // Every match on each line, for TermSpans
 
 	for _, match := range matches {
 		line := bytesToLine(fileInfo.Content, match.Start)
+		lineSpans[line] = append(lineSpans[line], [2]int{match.Start, match.End})
 
 		// Skip if we've already processed this line
 		if seenLines[line] {
@@ -932,7 +1005,9 @@ func (e *Engine) processIndividualMatches(fileInfo *types.FileInfo, matches []Ma
 			options.MaxContextLines == 0 || options.MaxContextLines > 10
 
 		var context ExtractedContext
-		if needsEnhancedContext {
+		if options.LocationsOnly {
+			context = locationContext(line)
+		} else if needsEnhancedContext {
 			context = e.extractEnhancedContext(fileInfo, line, options)
 		} else {
 			context = e.extractSimpleContextWithOptions(fileInfo.ID, line, options)
@@ -941,23 +1016,32 @@ func (e *Engine) processIndividualMatches(fileInfo *types.FileInfo, matches []Ma
 		// Extract the actual matched text
 		matchText := ""
 		if match.End > match.Start && match.End <= len(fileInfo.Content) {
-			matchText = string(fileInfo.Content[match.Start:match.End])
+			matchText = regionText(fileInfo.Content[match.Start:match.End])
 		}
 
 		// Create result
 		result := GrepResult{
-			FileID:  fileInfo.ID,
-			Path:    fileInfo.Path,
-			Line:    line,
-			Column:  match.Start - lineStart(fileInfo.Content, match.Start),
-			Match:   matchText,
-			Context: context,
-			Score:   e.scoreMatch(fileInfo, match, pattern, line),
+			FileID:    fileInfo.ID,
+			Path:      fileInfo.Path,
+			Line:      line,
+			Column:    match.Start - lineStart(fileInfo.Content, match.Start),
+			StartByte: match.Start,
+			EndByte:   match.End,
+			Match:     matchText,
+			Context:   context,
+			Score:     e.scoreMatch(fileInfo, match, pattern, line),
 		}
 
 		results = append(results, result)
 	}
 
+	// A line holding the pattern several times is one hit; its spans locate the others
+	for i := range results {
+		if spans := lineSpans[results[i].Line]; len(spans) > 1 {
+			results[i].TermSpans = spans
+		}
+	}
+
 	return results
 }
 
@@ -1053,8 +1137,83 @@ func (e *Engine) extractEnhancedContext(fileInfo *types.FileInfo, line int, opti
 // ====== End of helper functions ======
 
 // SearchWithOptions performs a search with configurable options
-// Refactored to reduce cyclomatic complexity from 52 to ~8
 func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, options types.SearchOptions) []GrepResult {
+	options = resolveCase(pattern, options)
+	pattern, options = ignoreWhitespace(pattern, options)
+	if options.Query {
+		results, _ := e.SearchQuery(pattern, options) // SearchQuery reports malformed queries
+		return results
+	}
+	if options.Fuzzy != "" {
+		return e.finishResults(e.searchFuzzySymbols(pattern, options), pattern, options)
+	}
+	if spanRestricted(options) {
+		return e.finishResults(e.searchWithin(pattern, candidates, options), pattern, options)
+	}
+	if options.SymbolsOnly {
+		re, err := compileSearchRegex(pattern, options)
+		if err != nil {
+			return nil // SearchRegex reports invalid patterns
+		}
+		return e.finishResults(e.searchSymbolNames(re, options), pattern, options)
+	}
+	if options.DocsOnly {
+		re, err := compileSearchRegex(pattern, options)
+		if err != nil {
+			return nil // SearchRegex reports invalid patterns
+		}
+		return e.finishResults(e.searchDocComments(re, options), pattern, options)
+	}
+	return e.finishResults(e.searchWithOptions(pattern, candidates, options), pattern, options)
+}
+
+// finishResults applies the post-search steps shared by every search mode. Duplicates
+// are merged before reranking so callers truncating to the top N see distinct hits;
+// nearby hits are merged after it, so their merged score comes from the ranker's.
+func (e *Engine) finishResults(results []GrepResult, pattern string, options types.SearchOptions) []GrepResult {
+	if options.ExportedOnly {
+		results = e.exportedResults(results)
+	}
+	tagMatcher(results, matcherFor(pattern, options))
+	if !options.NoDedup {
+		results = dedupResults(results)
+	}
+	if ranker := e.rankerFor(options); ranker != nil && !options.CountPerFile && !options.FilesOnly {
+		e.rerank(ranker, results, pattern, options)
+	} else if options.Explain && !options.CountPerFile && !options.FilesOnly {
+		explainDefaultScores(results)
+	}
+	if options.MergeGap > 0 && !options.CountPerFile && !options.FilesOnly {
+		results = e.mergeNearbyResults(results, options.MergeGap, options.MergeScore)
+	}
+	results = capResults(results, options)
+	if options.SnippetContext > 0 && !options.CountPerFile && !options.FilesOnly && !options.LocationsOnly {
+		e.attachSnippetContext(results, options.SnippetContext)
+	}
+	e.noteEncodings(results)
+	return results
+}
+
+// encodingProvider is implemented by indexers that decode files from other encodings
+// before indexing them
+type encodingProvider interface {
+	FileEncoding(fileID types.FileID) string
+}
+
+// noteEncodings sets the Encoding of results in files that were decoded to UTF-8
+func (e *Engine) noteEncodings(results []GrepResult) {
+	ep, ok := e.indexer.(encodingProvider)
+	if !ok {
+		return
+	}
+	for i := range results {
+		results[i].Encoding = ep.FileEncoding(results[i].FileID)
+	}
+}
+
+// searchWithOptions runs the search pipeline behind SearchWithOptions
+// Refactored to reduce cyclomatic complexity from 52 to ~8
+func (e *Engine) searchWithOptions(pattern string, candidates []types.FileID, options types.SearchOptions) []GrepResult {
 	// Step 1: Validate and prepare patterns
 	searchPatterns, valid := e.validateAndPreparePatterns(pattern, options)
 	if !valid {
@@ -1066,6 +1225,20 @@ func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, op
 		return e.searchMultiplePatterns(searchPatterns, candidates, options)
 	}
 
+	// Step 2.5: Split literal patterns into words and quoted phrases
+	terms := searchTerms(pattern, options)
+	if needsTermSearch(terms) {
+		return e.searchAllTerms(terms, candidates, options)
+	}
+	if len(terms) == 1 {
+		pattern = terms[0].Text
+	}
+	return e.searchPattern(pattern, candidates, options, nil)
+}
+
+// searchPattern searches for a single pattern as written. re is the pattern as
+// compiled by compileContentRegex for a regex search, or nil to compile it here.
+func (e *Engine) searchPattern(pattern string, candidates []types.FileID, options types.SearchOptions, re *regexp.Regexp) []GrepResult {
 	// Step 3: Single pattern validation
 	if pattern == "" {
 		return nil
@@ -1078,14 +1251,9 @@ func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, op
 	}
 
 	// Step 4.1: Filter out deleted/invalidated files
-	type deletedFileFilter interface {
-		FilterDeletedFiles([]types.FileID) []types.FileID
-	}
-	if dff, ok := any(e.indexer).(deletedFileFilter); ok {
-		candidates = dff.FilterDeletedFiles(candidates)
-		if len(candidates) == 0 {
-			return nil
-		}
+	candidates = e.filterDeletedFiles(candidates)
+	if len(candidates) == 0 {
+		return nil
 	}
 
 	// Step 4.5: Optimize literal patterns (8x faster than regex!)
@@ -1097,7 +1265,11 @@ func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, op
 	// Step 5: Handle regex search (use cached regex engine for all regex searches)
 	// Note: InvertMatch requires per-file processing, so skip hybrid engine for that case
 	if options.UseRegex && !options.InvertMatch && len(candidates) >= 1 {
-		return e.searchWithHybridRegex(pattern, candidates, options)
+		results := e.searchWithHybridRegex(pattern, re, candidates, options)
+		if options.CountPerFile {
+			return e.countPerFileResults(results)
+		}
+		return results
 	}
 
 	// Step 6: Determine effective result cap
@@ -1112,6 +1284,9 @@ func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, op
 		if effectiveCap > 0 && len(allResults) >= effectiveCap {
 			break
 		}
+		if ResultCapReached(options, len(allResults)) || stopAtDeadline(options) {
+			break
+		}
 
 		// Process single file
 		e.processFile(fileID, patternBytes, pattern, options, effectiveCap, &allResults)
@@ -1119,59 +1294,12 @@ func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, op
 
 	// Step 8: Handle special output modes (Count Per File)
 	if options.CountPerFile {
-		fileCounts := make(map[types.FileID]int)
-		for _, result := range allResults {
-			fileCounts[result.FileID]++
-		}
-
-		// Convert to results showing counts
-		var fileResults []GrepResult
-		for fileID, count := range fileCounts {
-			path := e.indexer.GetFilePath(fileID)
-			fileResults = append(fileResults, GrepResult{
-				FileID:         fileID,
-				Path:           path,
-				Line:           0, // No specific line in count mode
-				Column:         0,
-				Match:          strconv.Itoa(count),
-				Score:          float64(count),
-				FileMatchCount: count, // Store count in proper field
-			})
-		}
-
-		// Sort by path for consistent output
-		sort.Slice(fileResults, func(i, j int) bool {
-			return fileResults[i].Path < fileResults[j].Path
-		})
-
-		return fileResults
+		return e.countPerFileResults(allResults)
 	}
 
 	// Step 9: Handle Files Only mode (grep -l)
 	if options.FilesOnly {
-		seenFiles := make(map[types.FileID]bool)
-		var fileResults []GrepResult
-
-		for _, result := range allResults {
-			if !seenFiles[result.FileID] {
-				seenFiles[result.FileID] = true
-				// Return minimal result with just filename
-				fileResults = append(fileResults, GrepResult{
-					FileID:  result.FileID,
-					Path:    result.Path,
-					Line:    0,                  // No line number in files-only mode
-					Score:   1.0,                // Default score
-					Context: ExtractedContext{}, // No context
-				})
-			}
-		}
-
-		// Sort by path for consistent output
-		sort.Slice(fileResults, func(i, j int) bool {
-			return fileResults[i].Path < fileResults[j].Path
-		})
-
-		return fileResults
+		return filesOnlyResults(allResults)
 	}
 
 	// Step 10: Sort results by score (normal mode)
@@ -1182,6 +1310,75 @@ func (e *Engine) SearchWithOptions(pattern string, candidates []types.FileID, op
 	return allResults
 }
 
+// filterDeletedFiles drops deleted or invalidated files when the indexer tracks them
+func (e *Engine) filterDeletedFiles(candidates []types.FileID) []types.FileID {
+	type deletedFileFilter interface {
+		FilterDeletedFiles([]types.FileID) []types.FileID
+	}
+	if dff, ok := any(e.indexer).(deletedFileFilter); ok {
+		return dff.FilterDeletedFiles(candidates)
+	}
+	return candidates
+}
+
+// countPerFileResults collapses results into one per file carrying its match count
+func (e *Engine) countPerFileResults(allResults []GrepResult) []GrepResult {
+	fileCounts := make(map[types.FileID]int)
+	for _, result := range allResults {
+		// processFile pre-counts each file's matches; other paths yield one result per hit
+		fileCounts[result.FileID] += max(result.FileMatchCount, 1)
+	}
+
+	// Convert to results showing counts
+	var fileResults []GrepResult
+	for fileID, count := range fileCounts {
+		path := e.indexer.GetFilePath(fileID)
+		fileResults = append(fileResults, GrepResult{
+			FileID:         fileID,
+			Path:           path,
+			Line:           0, // No specific line in count mode
+			Column:         0,
+			Match:          strconv.Itoa(count),
+			Score:          float64(count),
+			FileMatchCount: count, // Store count in proper field
+		})
+	}
+
+	// Sort by path for consistent output
+	sort.Slice(fileResults, func(i, j int) bool {
+		return fileResults[i].Path < fileResults[j].Path
+	})
+
+	return fileResults
+}
+
+// filesOnlyResults collapses results into one filename-only result per file
+func filesOnlyResults(allResults []GrepResult) []GrepResult {
+	seenFiles := make(map[types.FileID]bool)
+	var fileResults []GrepResult
+
+	for _, result := range allResults {
+		if !seenFiles[result.FileID] {
+			seenFiles[result.FileID] = true
+			// Return minimal result with just filename
+			fileResults = append(fileResults, GrepResult{
+				FileID:  result.FileID,
+				Path:    result.Path,
+				Line:    0,                  // No line number in files-only mode
+				Score:   1.0,                // Default score
+				Context: ExtractedContext{}, // No context
+			})
+		}
+	}
+
+	// Sort by path for consistent output
+	sort.Slice(fileResults, func(i, j int) bool {
+		return fileResults[i].Path < fileResults[j].Path
+	})
+
+	return fileResults
+}
+
 // SearchDetailed performs a detailed search with relational data
 func (e *Engine) SearchDetailed(pattern string, candidates []types.FileID, maxContextLines int) []StandardResult {
 	return e.SearchDetailedWithOptions(pattern, candidates, types.SearchOptions{
@@ -1628,10 +1825,8 @@ func (e *Engine) GetLine(fileInfo *types.FileInfo, lineNum int) string {
 				end := len(content)
 				if lineIdx+1 < len(offsets) {
 					end = int(offsets[lineIdx+1])
-					if end > start && content[end-1] == '\n' {
-						end--
-					}
 				}
+				end = types.TrimLineEnding(content, start, end)
 				if start < len(content) && start <= end {
 					return string(content[start:end])
 				}
@@ -1651,7 +1846,7 @@ func (e *Engine) GetLine(fileInfo *types.FileInfo, lineNum int) string {
 	for i, b := range content {
 		if b == '\n' {
 			if currentLine == lineNum {
-				return string(content[start:i])
+				return string(content[start:types.TrimLineEnding(content, start, i+1)])
 			}
 			currentLine++
 			start = i + 1
@@ -1747,9 +1942,7 @@ func (e *Engine) getLineRef(fileInfo *types.FileInfo, lineNum int) types.ZeroAll
 		end := len(fileInfo.Content)
 		if lineIdx+1 < len(fileInfo.LineOffsets) {
 			end = fileInfo.LineOffsets[lineIdx+1]
-			if end > start && fileInfo.Content[end-1] == '\n' {
-				end--
-			}
+			end = types.TrimLineEnding(fileInfo.Content, start, end)
 		}
 		return makeRef(fileInfo.Content, start, end)
 	}
@@ -1769,10 +1962,8 @@ func (e *Engine) getLineRef(fileInfo *types.FileInfo, lineNum int) types.ZeroAll
 				end := len(content)
 				if lineIdx+1 < len(offsets) {
 					end = int(offsets[lineIdx+1])
-					if end > start && content[end-1] == '\n' {
-						end--
-					}
 				}
+				end = types.TrimLineEnding(content, start, end)
 				return makeRef(content, start, end)
 			}
 		}
@@ -1789,7 +1980,7 @@ func (e *Engine) getLineRef(fileInfo *types.FileInfo, lineNum int) types.ZeroAll
 	for i, b := range content {
 		if b == '\n' {
 			if currentLine == lineNum {
-				return makeRef(content, start, i)
+				return makeRef(content, start, types.TrimLineEnding(content, start, i+1))
 			}
 			currentLine++
 			start = i + 1
@@ -1817,6 +2008,12 @@ func (e *Engine) isExportedEnhancedSymbol(symbol *types.EnhancedSymbol, fileInfo
 		return true
 	}
 
+	// A visibility the parser recorded is authoritative, so a private Rust struct is not
+	// taken as exported for its capitalized name
+	if symbol.Visibility.Access != types.AccessUnknown {
+		return false
+	}
+
 	// Check if symbol name starts with uppercase (Go convention)
 	if len(symbol.Name) > 0 && symbol.Name[0] >= 'A' && symbol.Name[0] <= 'Z' {
 		return true
@@ -2097,6 +2294,16 @@ func (e *Engine) mergeFileResults(fileInfo *types.FileInfo, matches []Match, pat
 			Context: context,
 			Score:   r.Score,
 		}
+		var spans [][2]int
+		for _, match := range r.AllMatches {
+			if bytesToLine(fileInfo.Content, match.Start) == r.MatchLine {
+				spans = append(spans, [2]int{match.Start, match.End})
+			}
+		}
+		if len(spans) > 1 {
+			sort.Slice(spans, func(i, j int) bool { return spans[i][0] < spans[j][0] })
+			result.TermSpans = spans
+		}
 
 		results = append(results, result)
 	}
@@ -2116,9 +2323,10 @@ func (e *Engine) searchMultiplePatterns(patterns []string, candidates []types.Fi
 	// Apply path-based filters
 	candidates = e.filterIncludedFiles(candidates, options.IncludePattern)
 	candidates = e.filterExcludedFiles(candidates, options.ExcludePattern)
+	candidates = e.filterGlobFiles(candidates, options.IncludeGlobs, options.ExcludeGlobs)
 
 	var allResults []GrepResult
-	matchedLines := make(map[types.FileID]map[int]bool) // Track matched lines to avoid duplicates
+	matchedLines := make(map[types.FileID]map[int]int) // Index of the result kept for each matched line
 
 	// Search each pattern across all files
 	for _, pat := range patterns {
@@ -2127,8 +2335,12 @@ func (e *Engine) searchMultiplePatterns(patterns []string, candidates []types.Fi
 		}
 
 		patBytes := []byte(pat)
+		matcher := matcherFor(pat, options)
 
 		for _, fileID := range candidates {
+			if ResultCapReached(options, len(allResults)) || stopAtDeadline(options) {
+				break
+			}
 			fileInfo := e.indexer.GetFileInfo(fileID)
 			if fileInfo == nil {
 				continue
@@ -2136,7 +2348,7 @@ func (e *Engine) searchMultiplePatterns(patterns []string, candidates []types.Fi
 
 			// Initialize line tracking for this file
 			if matchedLines[fileID] == nil {
-				matchedLines[fileID] = make(map[int]bool)
+				matchedLines[fileID] = make(map[int]int)
 			}
 
 			// Find all matches for this pattern
@@ -2148,27 +2360,37 @@ func (e *Engine) searchMultiplePatterns(patterns []string, candidates []types.Fi
 			// Convert matches to results, avoiding duplicate lines
 			for _, match := range matches {
 				line := bytesToLine(fileInfo.Content, match.Start)
+				score := e.scoreMatch(fileInfo, match, pat, line)
 
-				// Skip if we already have a result for this line
-				if matchedLines[fileID][line] {
+				// Keep one result per line; another pattern matching the same span is
+				// recorded on it instead
+				if i, ok := matchedLines[fileID][line]; ok && !options.NoDedup {
+					if kept := &allResults[i]; kept.StartByte == match.Start && kept.EndByte == match.End {
+						mergeDuplicate(kept, GrepResult{Score: score, MatchedBy: []Matcher{matcher}})
+					}
 					continue
 				}
-				matchedLines[fileID][line] = true
+				matchedLines[fileID][line] = len(allResults)
 
 				column := match.Start - lineStart(fileInfo.Content, match.Start)
-				score := e.scoreMatch(fileInfo, match, pat, line)
 
 				// Extract context
-				context := e.extractSimpleContext(fileInfo.Content, match.Start, match.End)
+				context := locationContext(line)
+				if !options.LocationsOnly {
+					context = e.extractSimpleContext(fileInfo.Content, match.Start, match.End)
+				}
 
 				result := GrepResult{
-					FileID:  fileID,
-					Path:    fileInfo.Path,
-					Line:    line,
-					Column:  column,
-					Match:   string(fileInfo.Content[match.Start:match.End]),
-					Context: context,
-					Score:   score,
+					FileID:    fileID,
+					Path:      fileInfo.Path,
+					Line:      line,
+					Column:    column,
+					StartByte: match.Start,
+					EndByte:   match.End,
+					Match:     regionText(fileInfo.Content[match.Start:match.End]),
+					Context:   context,
+					Score:     score,
+					MatchedBy: []Matcher{matcher},
 				}
 
 				allResults = append(allResults, result)
@@ -2236,16 +2458,18 @@ func (e *Engine) createMergedResult(fileInfo *types.FileInfo, block *types.Block
 	bonusScore := avgScore + float64(len(matches)-1)*2.0
 
 	// Extract the matched text from the best match
-	matchText := string(fileInfo.Content[bestMatch.Start:bestMatch.End])
+	matchText := regionText(fileInfo.Content[bestMatch.Start:bestMatch.End])
 
 	return GrepResult{
-		FileID:  fileInfo.ID,
-		Path:    fileInfo.Path,
-		Line:    bestLine,
-		Column:  bestMatch.Start - lineStart(fileInfo.Content, bestMatch.Start),
-		Match:   matchText,
-		Context: context,
-		Score:   bonusScore,
+		FileID:    fileInfo.ID,
+		Path:      fileInfo.Path,
+		Line:      bestLine,
+		Column:    bestMatch.Start - lineStart(fileInfo.Content, bestMatch.Start),
+		StartByte: bestMatch.Start,
+		EndByte:   bestMatch.End,
+		Match:     matchText,
+		Context:   context,
+		Score:     bonusScore,
 	}
 }
 
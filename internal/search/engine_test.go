@@ -209,6 +209,19 @@ func (m *MockIndexer) GetEnhancedSymbolAtLine(fileID types.FileID, line int) *ty
 	}
 }
 
+func (m *MockIndexer) GetSymbolAtOffset(fileID types.FileID, offset int) *types.Symbol {
+	var best *types.Symbol
+	for _, symbol := range m.symbols[fileID] {
+		if offset < symbol.StartByte || offset >= symbol.EndByte {
+			continue
+		}
+		if best == nil || symbol.EndByte-symbol.StartByte < best.EndByte-best.StartByte {
+			best = &symbol.Symbol
+		}
+	}
+	return best
+}
+
 func (m *MockIndexer) GetFileScopeInfo(fileID types.FileID) []types.ScopeInfo {
 	return []types.ScopeInfo{}
 }
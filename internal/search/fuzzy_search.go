@@ -0,0 +1,180 @@
+package search
+
+import (
+	"sort"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// FuzzyResult is a symbol whose name is within the requested edit distance of the query
+type FuzzyResult struct {
+	Symbol   types.Symbol `json:"symbol"`
+	Path     string       `json:"path"`
+	Distance int          `json:"distance"`
+}
+
+// DefaultFuzzyDistance returns the edit distance used when the caller does not pick one:
+// 1 for queries of up to 4 characters, 2 for longer queries.
+func DefaultFuzzyDistance(query string) int {
+	if utf8.RuneCountInString(query) <= 4 {
+		return 1
+	}
+	return 2
+}
+
+// SearchFuzzy finds symbols whose names are within maxDistance Levenshtein edits of query.
+// A negative maxDistance selects DefaultFuzzyDistance(query). Matching is case-insensitive.
+//
+// Results are ordered by distance (closest first), then by name, path and position so
+// that symbols tied at the same distance always come back in the same order.
+func (e *Engine) SearchFuzzy(query string, maxDistance int) []FuzzyResult {
+	return e.SearchFuzzyWithOptions(query, maxDistance, types.SearchOptions{CaseInsensitive: true})
+}
+
+// SearchFuzzyWithOptions is SearchFuzzy with case handling taken from options: names are
+// compared case-sensitively unless CaseInsensitive is set or SmartCase resolves to it.
+func (e *Engine) SearchFuzzyWithOptions(query string, maxDistance int, options types.SearchOptions) []FuzzyResult {
+	if query == "" {
+		return nil
+	}
+	if maxDistance < 0 {
+		maxDistance = DefaultFuzzyDistance(query)
+	}
+
+	// Fuzzy queries are names, not regexes
+	options.UseRegex = false
+	options = resolveCase(query, options)
+	fold := func(s string) string { return s }
+	if options.CaseInsensitive {
+		fold = strings.ToLower
+	}
+
+	queryRunes := []rune(fold(query))
+	queryLen := len(queryRunes)
+
+	var results []FuzzyResult
+	for _, fileID := range e.indexer.GetAllFileIDs() {
+		symbols := e.indexer.GetFileSymbols(fileID)
+		if len(symbols) == 0 {
+			continue
+		}
+		path := e.indexer.GetFilePath(fileID)
+
+		for _, sym := range symbols {
+			nameLen := utf8.RuneCountInString(sym.Name)
+			// Length difference is a lower bound on the edit distance
+			if nameLen-queryLen > maxDistance || queryLen-nameLen > maxDistance {
+				continue
+			}
+			distance, ok := boundedLevenshtein(queryRunes, []rune(fold(sym.Name)), maxDistance)
+			if !ok {
+				continue
+			}
+			results = append(results, FuzzyResult{Symbol: sym, Path: path, Distance: distance})
+		}
+	}
+
+	sort.SliceStable(results, func(i, j int) bool {
+		a, b := results[i], results[j]
+		if a.Distance != b.Distance {
+			return a.Distance < b.Distance
+		}
+		if a.Symbol.Name != b.Symbol.Name {
+			return a.Symbol.Name < b.Symbol.Name
+		}
+		if a.Path != b.Path {
+			return a.Path < b.Path
+		}
+		if a.Symbol.Line != b.Symbol.Line {
+			return a.Symbol.Line < b.Symbol.Line
+		}
+		return a.Symbol.Column < b.Symbol.Column
+	})
+
+	return results
+}
+
+// boundedLevenshtein computes the edit distance between a and b, giving up as soon as
+// every cell in a DP row exceeds maxDist. Only the diagonal band of width 2*maxDist+1 is
+// evaluated, which behaves like running b through a Levenshtein automaton for a.
+// Returns (distance, true) when the distance is <= maxDist.
+func boundedLevenshtein(a, b []rune, maxDist int) (int, bool) {
+	if len(a) < len(b) {
+		a, b = b, a
+	}
+	if len(a)-len(b) > maxDist {
+		return 0, false
+	}
+	if len(b) == 0 {
+		return len(a), len(a) <= maxDist
+	}
+
+	// Cells outside the band are treated as "too far" (maxDist+1)
+	inf := maxDist + 1
+	prev := make([]int, len(b)+1)
+	curr := make([]int, len(b)+1)
+	for j := range prev {
+		if j <= maxDist {
+			prev[j] = j
+		} else {
+			prev[j] = inf
+		}
+	}
+
+	for i := 1; i <= len(a); i++ {
+		lo := i - maxDist
+		if lo < 1 {
+			lo = 1
+		}
+		hi := i + maxDist
+		if hi > len(b) {
+			hi = len(b)
+		}
+
+		if i <= maxDist {
+			curr[0] = i
+		} else {
+			curr[0] = inf
+		}
+		if lo > 1 {
+			curr[lo-1] = inf
+		}
+
+		rowMin := curr[0]
+		for j := lo; j <= hi; j++ {
+			cost := 1
+			if a[i-1] == b[j-1] {
+				cost = 0
+			}
+			best := prev[j-1] + cost
+			if v := prev[j] + 1; v < best {
+				best = v
+			}
+			if v := curr[j-1] + 1; v < best {
+				best = v
+			}
+			if best > inf {
+				best = inf
+			}
+			curr[j] = best
+			if best < rowMin {
+				rowMin = best
+			}
+		}
+		if hi < len(b) {
+			curr[hi+1] = inf
+		}
+
+		if rowMin > maxDist {
+			return 0, false
+		}
+		prev, curr = curr, prev
+	}
+
+	if prev[len(b)] > maxDist {
+		return 0, false
+	}
+	return prev[len(b)], true
+}
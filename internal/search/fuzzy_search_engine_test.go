@@ -0,0 +1,119 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchFuzzy_FindsNearMisses(t *testing.T) {
+	code := `package main
+
+func get_user() {}
+func get_users() {}
+func set_user() {}
+func delete_account() {}
+`
+	engine, _, cleanup := setupTestEngine(t, map[string]string{"users.go": code})
+	defer cleanup()
+
+	results := engine.SearchFuzzy("get_usr", -1)
+	require.NotEmpty(t, results, "get_usr should fuzzy-match get_user")
+
+	assert.Equal(t, "get_user", results[0].Symbol.Name)
+	assert.Equal(t, 1, results[0].Distance)
+
+	for _, r := range results {
+		assert.LessOrEqual(t, r.Distance, 2)
+		assert.NotEqual(t, "delete_account", r.Symbol.Name)
+	}
+	for i := 1; i < len(results); i++ {
+		assert.LessOrEqual(t, results[i-1].Distance, results[i].Distance, "results must be ordered by distance")
+	}
+}
+
+func TestSearchFuzzy_ExplicitDistance(t *testing.T) {
+	code := `package main
+
+func get_user() {}
+`
+	engine, _, cleanup := setupTestEngine(t, map[string]string{"users.go": code})
+	defer cleanup()
+
+	assert.Empty(t, engine.SearchFuzzy("get_usr", 0), "distance 0 requires an exact name")
+	assert.NotEmpty(t, engine.SearchFuzzy("GET_USER", 0), "matching is case-insensitive")
+	assert.Empty(t, engine.SearchFuzzy("", 2))
+}
+
+func TestSearchFuzzy_DeterministicTies(t *testing.T) {
+	files := map[string]string{
+		"b.go": "package main\n\nfunc cat() {}\nfunc bat() {}\n",
+		"a.go": "package main\n\nfunc hat() {}\nfunc cat() {}\n",
+	}
+	engine, _, cleanup := setupTestEngine(t, files)
+	defer cleanup()
+
+	first := engine.SearchFuzzy("rat", 1)
+	require.Len(t, first, 4)
+
+	var names, paths []string
+	for _, r := range first {
+		assert.Equal(t, 1, r.Distance)
+		names = append(names, r.Symbol.Name)
+		paths = append(paths, r.Path)
+	}
+	assert.Equal(t, []string{"bat", "cat", "cat", "hat"}, names, "ties sort alphabetically")
+	assert.Less(t, paths[1], paths[2], "same-name ties sort by path")
+
+	for i := 0; i < 5; i++ {
+		again := engine.SearchFuzzy("rat", 1)
+		assert.Equal(t, first, again)
+	}
+}
+
+func TestSearchWithOptions_FuzzySubsequence(t *testing.T) {
+	code := `package main
+
+func language() {}
+func bug_utils() {}
+func get_user() {}
+func gauge() {}
+func guard() {}
+func process() {}
+`
+	engine, _, cleanup := setupTestEngine(t, map[string]string{"names.go": code})
+	defer cleanup()
+
+	results := engine.SearchWithOptions("gu", nil, types.SearchOptions{Fuzzy: search.FuzzySubsequence, SmartCase: true, Explain: true})
+	var names []string
+	for _, r := range results {
+		names = append(names, r.Match)
+	}
+	assert.Equal(t, []string{"guard", "get_user", "gauge", "bug_utils", "language"}, names,
+		"matches at word starts rank above the same characters mid-word")
+	for i := 1; i < len(results); i++ {
+		assert.Greater(t, results[i-1].Score, results[i].Score)
+	}
+
+	require.NotEmpty(t, results)
+	r := results[1]
+	assert.Equal(t, []searchtypes.Matcher{searchtypes.MatcherFuzzy}, r.MatchedBy)
+	require.NotNil(t, r.Explanation)
+	components := map[string]float64{}
+	sum := 0.0
+	for _, c := range r.Explanation.Components {
+		components[c.Name] = c.Value
+		sum += c.Value
+	}
+	assert.Equal(t, map[string]float64{"match": 32, "boundary": 24, "consecutive": 0, "gap": -5, "start": 0}, components)
+	assert.Equal(t, r.Score, sum, "the components add up to the score")
+
+	edit := engine.SearchWithOptions("get_usr", nil, types.SearchOptions{Fuzzy: search.FuzzyEdit, SmartCase: true})
+	require.NotEmpty(t, edit)
+	assert.Equal(t, "get_user", edit[0].Match)
+}
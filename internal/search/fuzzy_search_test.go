@@ -0,0 +1,34 @@
+package search
+
+import (
+	"testing"
+
+	"github.com/hbollon/go-edlib"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestBoundedLevenshteinMatchesFullDistance(t *testing.T) {
+	words := []string{"", "a", "get_usr", "get_user", "get_users", "set_user", "user", "usr", "GetUser", "save_user", "find_user", "kitten", "sitting"}
+
+	for _, a := range words {
+		for _, b := range words {
+			full := edlib.LevenshteinDistance(a, b)
+			for maxDist := 0; maxDist <= 3; maxDist++ {
+				dist, ok := boundedLevenshtein([]rune(a), []rune(b), maxDist)
+				if full <= maxDist {
+					assert.True(t, ok, "%q vs %q within %d", a, b, maxDist)
+					assert.Equal(t, full, dist, "%q vs %q", a, b)
+				} else {
+					assert.False(t, ok, "%q vs %q should exceed %d (distance %d)", a, b, maxDist, full)
+				}
+			}
+		}
+	}
+}
+
+func TestDefaultFuzzyDistance(t *testing.T) {
+	assert.Equal(t, 1, DefaultFuzzyDistance("usr"))
+	assert.Equal(t, 1, DefaultFuzzyDistance("user"))
+	assert.Equal(t, 2, DefaultFuzzyDistance("users"))
+	assert.Equal(t, 2, DefaultFuzzyDistance("get_usr"))
+}
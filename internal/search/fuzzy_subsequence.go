@@ -0,0 +1,270 @@
+package search
+
+import (
+	"math"
+	"sort"
+	"strings"
+	"unicode"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// Fuzzy scorers selectable with SearchOptions.Fuzzy
+const (
+	FuzzyEdit        = "edit"        // Names within DefaultFuzzyDistance Levenshtein edits of the query
+	FuzzySubsequence = "subsequence" // Names holding the query's characters in order, scored like fzf
+)
+
+// Subsequence scoring, after fzf: each matched character earns fuzzyScoreMatch plus a
+// bonus at the start of a word, and skipped characters cost a gap penalty. The first
+// query character's bonus counts double, as typing the start of a word is what people
+// do most.
+const (
+	fuzzyScoreMatch         = 16
+	fuzzyScoreGapStart      = -3
+	fuzzyScoreGapExtension  = -1
+	fuzzyBonusBoundary      = fuzzyScoreMatch / 2                            // Word start: the name's first character or one after _ - . / or space
+	fuzzyBonusCamel         = fuzzyBonusBoundary + fuzzyScoreGapExtension    // camelCase hump, or a digit after a letter
+	fuzzyBonusConsecutive   = -(fuzzyScoreGapStart + fuzzyScoreGapExtension) // Character right after the previous match
+	fuzzyBonusFirstCharMult = 2
+)
+
+// SubsequenceScore is how well a name holds a query as a subsequence, split into the
+// components that add up to Total
+type SubsequenceScore struct {
+	Positions   []int // Rune index in the name of each query character
+	Match       int   // fuzzyScoreMatch per query character
+	Boundary    int   // Bonuses for characters at word starts and camelCase humps
+	Consecutive int   // Bonuses for characters directly after the previous match
+	Gap         int   // Penalty for the characters skipped between matches
+	Start       int   // Penalty for the characters before the first match, so earlier matches win
+}
+
+// Total is the score the components add up to; higher is better
+func (s SubsequenceScore) Total() int {
+	return s.Match + s.Boundary + s.Consecutive + s.Gap + s.Start
+}
+
+// ScoreSubsequence scores name against query by the best alignment of query's
+// characters, in order, onto name's. ok is false when name does not hold them all.
+// With caseInsensitive characters are compared case-folded; word boundaries always
+// come from name as written. For "gu", get_user scores high because both characters
+// start a word.
+func ScoreSubsequence(query, name string, caseInsensitive bool) (SubsequenceScore, bool) {
+	q, n := []rune(query), []rune(name)
+	if len(q) == 0 || len(q) > len(n) {
+		return SubsequenceScore{}, false
+	}
+	folded := n
+	if caseInsensitive {
+		// Rune by rune, so folded stays aligned with n
+		folded = make([]rune, len(n))
+		for j, r := range n {
+			folded[j] = unicode.ToLower(r)
+		}
+		for i, r := range q {
+			q[i] = unicode.ToLower(r)
+		}
+	}
+	bonus := make([]int, len(n))
+	for j := range n {
+		bonus[j] = boundaryBonus(n, j)
+	}
+
+	// best[i][j] is the best score of q[:i+1] with q[i] matched at n[j], and from[i][j]
+	// where q[i-1] was matched in that alignment
+	const none = math.MinInt32
+	best := make([][]int, len(q))
+	from := make([][]int, len(q))
+	for i := range q {
+		best[i] = make([]int, len(n))
+		from[i] = make([]int, len(n))
+		// A gap of j-k-1 characters after a match at k costs fuzzyScoreGapStart plus
+		// fuzzyScoreGapExtension per further character, so the best match to leave a gap
+		// after is the k <= j-2 maximizing best[i-1][k] - fuzzyScoreGapExtension*k
+		gapBest, gapFrom := none, -1
+		for j := range n {
+			best[i][j] = none
+			if k := j - 2; i > 0 && k >= 0 && best[i-1][k] != none && best[i-1][k]-fuzzyScoreGapExtension*k > gapBest {
+				gapBest, gapFrom = best[i-1][k]-fuzzyScoreGapExtension*k, k
+			}
+			if folded[j] != q[i] {
+				continue
+			}
+			if i == 0 {
+				best[i][j] = fuzzyScoreMatch + fuzzyBonusFirstCharMult*bonus[j] + fuzzyScoreGapExtension*j
+				continue
+			}
+			prev, prevFrom := none, -1
+			if j >= 1 && best[i-1][j-1] != none {
+				prev, prevFrom = best[i-1][j-1]+fuzzyBonusConsecutive, j-1
+			}
+			if gapBest != none {
+				if gapped := gapBest + fuzzyScoreGapStart + fuzzyScoreGapExtension*(j-2); gapped > prev {
+					prev, prevFrom = gapped, gapFrom
+				}
+			}
+			if prev != none {
+				best[i][j] = fuzzyScoreMatch + bonus[j] + prev
+				from[i][j] = prevFrom
+			}
+		}
+	}
+
+	last := len(q) - 1
+	end := -1
+	for j := range n {
+		if best[last][j] != none && (end < 0 || best[last][j] > best[last][end]) {
+			end = j
+		}
+	}
+	if end < 0 {
+		return SubsequenceScore{}, false
+	}
+	positions := make([]int, len(q))
+	for i, j := last, end; i >= 0; i-- {
+		positions[i] = j
+		j = from[i][j]
+	}
+	return subsequenceComponents(bonus, positions), true
+}
+
+// subsequenceComponents scores an alignment given each name character's boundary bonus
+func subsequenceComponents(bonus, positions []int) SubsequenceScore {
+	s := SubsequenceScore{Positions: positions, Match: fuzzyScoreMatch * len(positions)}
+	for i, p := range positions {
+		if i == 0 {
+			s.Boundary += fuzzyBonusFirstCharMult * bonus[p]
+			s.Start = fuzzyScoreGapExtension * p
+			continue
+		}
+		s.Boundary += bonus[p]
+		if gap := p - positions[i-1] - 1; gap == 0 {
+			s.Consecutive += fuzzyBonusConsecutive
+		} else {
+			s.Gap += fuzzyScoreGapStart + (gap-1)*fuzzyScoreGapExtension
+		}
+	}
+	return s
+}
+
+// boundaryBonus is the bonus for matching name[j]: a word start after a delimiter or
+// at the start of the name, or a camelCase hump
+func boundaryBonus(name []rune, j int) int {
+	cur := name[j]
+	if !isWordRune(cur) {
+		return 0
+	}
+	if j == 0 || !isWordRune(name[j-1]) {
+		return fuzzyBonusBoundary
+	}
+	prev := name[j-1]
+	if (unicode.IsLower(prev) && unicode.IsUpper(cur)) || (unicode.IsLetter(prev) && unicode.IsDigit(cur)) {
+		return fuzzyBonusCamel
+	}
+	return 0
+}
+
+func isWordRune(r rune) bool {
+	return unicode.IsLetter(r) || unicode.IsDigit(r)
+}
+
+// searchFuzzySymbols matches pattern against every symbol name with the scorer named by
+// options.Fuzzy and returns the matches best first. Ties go to the shorter name, then
+// by name, path and position. With options.Explain each hit carries its score components.
+func (e *Engine) searchFuzzySymbols(pattern string, options types.SearchOptions) []GrepResult {
+	if pattern == "" || (options.Fuzzy != FuzzyEdit && options.Fuzzy != FuzzySubsequence) {
+		return nil
+	}
+	fold := func(s string) string { return s }
+	if options.CaseInsensitive {
+		fold = strings.ToLower
+	}
+	queryRunes := []rune(fold(pattern))
+	maxDistance := DefaultFuzzyDistance(pattern)
+
+	candidates := e.getAllFileIDs()
+	candidates = e.filterIncludedFiles(candidates, options.IncludePattern)
+	candidates = e.filterExcludedFiles(candidates, options.ExcludePattern)
+	candidates = e.filterGlobFiles(candidates, options.IncludeGlobs, options.ExcludeGlobs)
+
+	var results []GrepResult
+	for _, fileID := range candidates {
+		if ResultCapReached(options, len(results)) || stopAtDeadline(options) {
+			break
+		}
+		symbols := e.indexer.GetFileEnhancedSymbols(fileID)
+		if len(symbols) == 0 {
+			continue
+		}
+		path := e.indexer.GetFilePath(fileID)
+		offsets, _ := e.indexer.GetFileLineOffsets(fileID)
+
+		for _, sym := range symbols {
+			if sym == nil || (len(options.SymbolTypes) > 0 && !contains(options.SymbolTypes, sym.Type.String())) {
+				continue
+			}
+			var r GrepResult
+			if options.Fuzzy == FuzzySubsequence {
+				score, ok := ScoreSubsequence(pattern, sym.Name, options.CaseInsensitive)
+				if !ok {
+					continue
+				}
+				r = e.symbolNameResult(fileID, path, offsets, sym.Symbol, options)
+				r.Score = float64(score.Total())
+				if options.Explain {
+					r.Explanation = explainSubsequence(score)
+				}
+			} else {
+				nameLen := utf8.RuneCountInString(sym.Name)
+				if nameLen-len(queryRunes) > maxDistance || len(queryRunes)-nameLen > maxDistance {
+					continue
+				}
+				distance, ok := boundedLevenshtein(queryRunes, []rune(fold(sym.Name)), maxDistance)
+				if !ok {
+					continue
+				}
+				r = e.symbolNameResult(fileID, path, offsets, sym.Symbol, options)
+				r.Score = 1 / float64(1+distance)
+				if options.Explain {
+					r.Explanation = &ScoreExplanation{Ranker: "fuzzy-" + FuzzyEdit, Score: r.Score}
+					r.Explanation.Add("edit distance", r.Score, ScoreFactor{Name: "distance", Value: float64(distance)})
+				}
+			}
+			results = append(results, r)
+		}
+	}
+
+	sort.SliceStable(results, func(i, j int) bool {
+		a, b := results[i], results[j]
+		if a.Score != b.Score {
+			return a.Score > b.Score
+		}
+		if len(a.Match) != len(b.Match) {
+			return len(a.Match) < len(b.Match)
+		}
+		if a.Match != b.Match {
+			return a.Match < b.Match
+		}
+		if a.Path != b.Path {
+			return a.Path < b.Path
+		}
+		return a.StartByte < b.StartByte
+	})
+	if options.MaxResults > 0 && len(results) > options.MaxResults {
+		results = results[:options.MaxResults]
+	}
+	return results
+}
+
+// explainSubsequence reports a subsequence score as its components
+func explainSubsequence(score SubsequenceScore) *ScoreExplanation {
+	x := &ScoreExplanation{Ranker: "fuzzy-" + FuzzySubsequence, Score: float64(score.Total())}
+	x.Add("match", float64(score.Match))
+	x.Add("boundary", float64(score.Boundary))
+	x.Add("consecutive", float64(score.Consecutive))
+	x.Add("gap", float64(score.Gap))
+	x.Add("start", float64(score.Start))
+	return x
+}
@@ -0,0 +1,76 @@
+package search
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestScoreSubsequence(t *testing.T) {
+	score, ok := ScoreSubsequence("gu", "get_user", true)
+	require.True(t, ok)
+	assert.Equal(t, []int{0, 4}, score.Positions, "both characters start a word")
+	assert.Equal(t, SubsequenceScore{Positions: []int{0, 4}, Match: 32, Boundary: 24, Gap: -5}, score)
+	assert.Equal(t, 51, score.Total())
+
+	score, ok = ScoreSubsequence("user", "get_user", true)
+	require.True(t, ok)
+	assert.Equal(t, []int{4, 5, 6, 7}, score.Positions)
+	assert.Equal(t, 12, score.Consecutive)
+	assert.Equal(t, -4, score.Start, "characters before the first match cost")
+
+	score, ok = ScoreSubsequence("gu", "getUser", true)
+	require.True(t, ok)
+	assert.Equal(t, fuzzyBonusFirstCharMult*fuzzyBonusBoundary+fuzzyBonusCamel, score.Boundary, "a camelCase hump is a word start")
+
+	_, ok = ScoreSubsequence("gu", "getUser", false)
+	assert.False(t, ok, "case-sensitive matching compares characters as written")
+	_, ok = ScoreSubsequence("ug", "get_user", true)
+	assert.False(t, ok, "characters must appear in order")
+	_, ok = ScoreSubsequence("", "get_user", true)
+	assert.False(t, ok)
+}
+
+// bruteForceSubsequence scores every alignment of query onto name and keeps the best
+func bruteForceSubsequence(query, name string) (int, bool) {
+	q, n := []rune(strings.ToLower(query)), []rune(name)
+	bonus := make([]int, len(n))
+	for j := range n {
+		bonus[j] = boundaryBonus(n, j)
+	}
+	best, found := 0, false
+	var walk func(i, from int, positions []int)
+	walk = func(i, from int, positions []int) {
+		if i == len(q) {
+			if total := subsequenceComponents(bonus, positions).Total(); !found || total > best {
+				best, found = total, true
+			}
+			return
+		}
+		for j := from; j < len(n); j++ {
+			if strings.ToLower(string(n[j])) == string(q[i]) {
+				walk(i+1, j+1, append(positions, j))
+			}
+		}
+	}
+	walk(0, 0, nil)
+	return best, found
+}
+
+func TestScoreSubsequence_BestAlignment(t *testing.T) {
+	names := []string{"get_user", "getUser", "get_user_by_user_id", "useUserState", "gauge_utils", "language", "bug", "a_b_a_b", "Base64Decoder"}
+	queries := []string{"gu", "user", "us", "guid", "ab", "aab", "b64", "lg", "e"}
+	for _, name := range names {
+		for _, query := range queries {
+			want, wantOK := bruteForceSubsequence(query, name)
+			score, ok := ScoreSubsequence(query, name, true)
+			require.Equal(t, wantOK, ok, "%q in %q", query, name)
+			if ok {
+				assert.Equal(t, want, score.Total(), "%q in %q", query, name)
+				assert.Len(t, score.Positions, len([]rune(query)))
+			}
+		}
+	}
+}
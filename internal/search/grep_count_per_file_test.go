@@ -323,6 +323,35 @@ func main() {
 	assert.Equal(t, len(results2), len(results3), "Should get consistent counts")
 }
 
+// TestCountPerFile_CountsMatches checks the exact tallies, including several matches on
+// one line, for literal and regex patterns, with no snippets attached
+func TestCountPerFile_CountsMatches(t *testing.T) {
+	files := map[string]string{
+		"file1.go": "package main\n\nfunc main() {\n\ttoken := token + 1\n\ttoken++\n}\n",
+		"file2.go": "package main\n\nvar token = 1\n",
+		"file3.go": "package main\n\nvar other = 1\n",
+	}
+	engine, fileIDs, cleanup := setupCountPerFileTestEngine(t, files)
+	defer cleanup()
+
+	for _, opts := range []types.SearchOptions{
+		{CountPerFile: true},
+		{CountPerFile: true, UseRegex: true, WordBoundary: true},
+		{CountPerFile: true, UseRegex: true},
+	} {
+		pattern := "token"
+		if opts.UseRegex {
+			pattern = "tok[e]n"
+		}
+		counts := make(map[string]int)
+		for _, r := range engine.SearchWithOptions(pattern, fileIDs, opts) {
+			counts[filepath.Base(r.Path)] = r.FileMatchCount
+			assert.Empty(t, r.Context.Lines, "count mode builds no snippets")
+		}
+		assert.Equal(t, map[string]int{"file1.go": 3, "file2.go": 1}, counts, "%+v", opts)
+	}
+}
+
 // setupCountPerFileTestEngine creates a test search engine for count per file tests
 func setupCountPerFileTestEngine(t *testing.T, files map[string]string) (*search.Engine, []types.FileID, func()) {
 	tempDir := t.TempDir()
@@ -469,6 +469,7 @@ func setupTestEngine(t *testing.T, files map[string]string) (*search.Engine, []t
 	// Write test files
 	for filename, code := range files {
 		testFilePath := filepath.Join(tempDir, filename)
+		require.NoError(t, os.MkdirAll(filepath.Dir(testFilePath), 0755))
 		err := os.WriteFile(testFilePath, []byte(code), 0644)
 		require.NoError(t, err, "Failed to write test file %s", filename)
 	}
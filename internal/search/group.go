@@ -0,0 +1,72 @@
+package search
+
+import (
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TopLevelGroup is the symbol name of the group collecting a file's hits that fall
+// outside every symbol
+const TopLevelGroup = "<file top-level>"
+
+// SymbolGroup is the set of hits inside one symbol of one file
+type SymbolGroup struct {
+	Path   string       `json:"path"`
+	Root   string       `json:"root,omitempty"`
+	Symbol string       `json:"symbol"`         // Parent-qualified name, e.g. "AuthService::authenticate"
+	Kind   string       `json:"kind,omitempty"` // Empty for the top-level group
+	Line   int          `json:"line,omitempty"` // Declaration line, 0 for the top-level group
+	Hits   []GrepResult `json:"hits"`           // In line order
+}
+
+// groupKey identifies a symbol within a file; the top-level group has start -1
+type groupKey struct {
+	fileID types.FileID
+	path   string
+	start  int
+	name   string
+}
+
+// GroupBySymbol nests results under the innermost symbol enclosing each hit. Groups are
+// ordered by their best-ranked hit, so the input ranking carries over to the groups.
+func (e *Engine) GroupBySymbol(results []GrepResult) []SymbolGroup {
+	var groups []SymbolGroup
+	index := make(map[groupKey]int)
+
+	for _, r := range results {
+		key := groupKey{fileID: r.FileID, path: r.Path, start: -1}
+		group := SymbolGroup{Path: r.Path, Root: r.Root, Symbol: TopLevelGroup}
+
+		if offset := e.resultOffset(r); offset >= 0 {
+			if sym := e.indexer.GetSymbolAtOffset(r.FileID, offset); sym != nil {
+				key.start, key.name = sym.StartByte, sym.Name
+				group.Symbol = sym.Name
+				if sym.Parent != "" {
+					group.Symbol = sym.Parent + "::" + sym.Name
+				}
+				group.Kind = sym.Type.String()
+				group.Line = sym.Line
+			}
+		}
+
+		i, ok := index[key]
+		if !ok {
+			i = len(groups)
+			index[key] = i
+			groups = append(groups, group)
+		}
+		groups[i].Hits = append(groups[i].Hits, r)
+	}
+
+	for i := range groups {
+		hits := groups[i].Hits
+		sort.SliceStable(hits, func(a, b int) bool {
+			if hits[a].Line != hits[b].Line {
+				return hits[a].Line < hits[b].Line
+			}
+			return hits[a].Column < hits[b].Column
+		})
+	}
+	return groups
+}
@@ -0,0 +1,34 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestGroupBySymbol(t *testing.T) {
+	code := "// token checks\n\n" + withinAuthCode
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.rs": code})
+	defer cleanup()
+
+	results := engine.SearchWithOptions("token", fileIDs, types.SearchOptions{})
+	require.NotEmpty(t, results)
+
+	got := make(map[string][]int)
+	for _, g := range engine.GroupBySymbol(results) {
+		var lines []int
+		for _, h := range g.Hits {
+			lines = append(lines, h.Line)
+		}
+		got[g.Symbol] = lines
+	}
+
+	assert.Equal(t, []int{8, 9}, got["AuthService::authenticate"], "hits nest under the method, in line order")
+	assert.Equal(t, []int{12, 13}, got["AuthService::refresh"])
+	assert.Equal(t, []int{20, 21}, got["ApiClient::authenticate"], "same-named methods of different types stay apart")
+	assert.Equal(t, []int{1}, got[search.TopLevelGroup], "hits outside every symbol")
+}
@@ -0,0 +1,90 @@
+package search
+
+import (
+	"fmt"
+	"math"
+	"strconv"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// BoostDoc is the KindBoosts key weighting hits on comment lines, such as a symbol's doc
+// comment, whatever symbol encloses them
+const BoostDoc = "doc"
+
+// KindBoosts weights SymbolProximityRanker scores by where a hit lies. Keys are the
+// SymbolType name of the innermost symbol enclosing the hit's line, or BoostDoc for a hit
+// in a comment; a hit matching no key keeps its score. {"function": 2, "doc": 0.5}
+// ranks a function name above the same word in a comment.
+type KindBoosts map[string]float64
+
+// ParseKindBoosts parses --boost values such as "fn=2.0", "struct=1.5" or "doc=0.5".
+// Kinds are those --kind accepts, short forms included, plus doc. A later value for the
+// same kind replaces an earlier one.
+func ParseKindBoosts(specs []string) (KindBoosts, error) {
+	if len(specs) == 0 {
+		return nil, nil
+	}
+	boosts := make(KindBoosts, len(specs))
+	for _, spec := range specs {
+		name, value, ok := strings.Cut(spec, "=")
+		if !ok {
+			return nil, fmt.Errorf("%q: expected kind=weight, e.g. fn=2.0", spec)
+		}
+		kind := strings.ToLower(strings.TrimSpace(name))
+		if kind != BoostDoc {
+			st, ok := types.ParseSymbolType(kind)
+			if !ok {
+				return nil, fmt.Errorf("%q: unknown kind %q, valid kinds: %s, %s", spec, strings.TrimSpace(name), BoostDoc, strings.Join(types.SymbolTypeNames(), ", "))
+			}
+			kind = st.String()
+		}
+		weight, err := strconv.ParseFloat(strings.TrimSpace(value), 64)
+		if err != nil || weight <= 0 || math.IsNaN(weight) || math.IsInf(weight, 0) {
+			return nil, fmt.Errorf("%q: weight must be a positive number", spec)
+		}
+		boosts[kind] = weight
+	}
+	return boosts, nil
+}
+
+// weight returns the boost for hit among its file's symbols and the key that chose it,
+// or 1 and "" when no key applies
+func (b KindBoosts) weight(symbols []types.Symbol, hit *RawHit) (float64, string) {
+	if len(b) == 0 {
+		return 1, ""
+	}
+	if w, ok := b[BoostDoc]; ok && isCommentText(hit.LineText) {
+		return w, BoostDoc
+	}
+
+	var enclosing *types.Symbol
+	span := 0
+	for i := range symbols {
+		sym := &symbols[i]
+		endLine := max(sym.EndLine, sym.Line)
+		if hit.Line < sym.Line || hit.Line > endLine {
+			continue
+		}
+		if enclosing == nil || endLine-sym.Line < span {
+			enclosing, span = sym, endLine-sym.Line
+		}
+	}
+	if enclosing == nil {
+		return 1, ""
+	}
+	kind := enclosing.Type.String()
+	if w, ok := b[kind]; ok {
+		return w, kind
+	}
+	return 1, ""
+}
+
+// isCommentText reports whether line is a comment, by the prefixes Engine.isInComment
+// recognizes
+func isCommentText(line string) bool {
+	trimmed := strings.TrimSpace(line)
+	return strings.HasPrefix(trimmed, "//") || strings.HasPrefix(trimmed, "#") ||
+		strings.HasPrefix(trimmed, "/*") || strings.Contains(trimmed, "*/")
+}
@@ -0,0 +1,63 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestParseKindBoosts(t *testing.T) {
+	boosts, err := search.ParseKindBoosts([]string{"fn=2.0", "Struct=1.5", "doc=0.5", "fn=3"})
+	require.NoError(t, err)
+	assert.Equal(t, search.KindBoosts{"function": 3, "struct": 1.5, "doc": 0.5}, boosts, "short forms resolve and a later value wins")
+
+	boosts, err = search.ParseKindBoosts(nil)
+	require.NoError(t, err)
+	assert.Nil(t, boosts)
+
+	for _, spec := range []string{"fn", "widget=2", "fn=0", "fn=-1", "fn=abc", "fn=NaN", "fn=Inf"} {
+		_, err := search.ParseKindBoosts([]string{spec})
+		assert.Error(t, err, spec)
+	}
+}
+
+func TestSymbolProximityRanker_KindBoosts(t *testing.T) {
+	code := `package main
+
+// parseConfig reads the config file
+func parseConfig() {
+	println("parseConfig")
+}
+`
+	gi, engine := setupRankerIndex(t, map[string]string{"main.go": code})
+	scores := func(options types.SearchOptions) map[int]float64 {
+		byLine := make(map[int]float64)
+		for _, r := range engine.SearchWithOptions("parseConfig", nil, options) {
+			byLine[r.Line] = r.Score
+		}
+		return byLine
+	}
+
+	engine.SetRanker(search.NewSymbolProximityRanker(gi))
+	plain := scores(types.SearchOptions{})
+	require.Len(t, plain, 3)
+
+	engine.SetRanker(nil)
+	boosted := scores(types.SearchOptions{KindBoosts: map[string]float64{"function": 2, search.BoostDoc: 0.5}})
+	assert.InDelta(t, 2*plain[4], boosted[4], 1e-9, "the definition is inside its function")
+	assert.InDelta(t, 2*plain[5], boosted[5], 1e-9)
+	assert.InDelta(t, 0.5*plain[3], boosted[3], 1e-9, "the doc comment is weighted as doc")
+
+	results := engine.SearchWithOptions("parseConfig", nil, types.SearchOptions{KindBoosts: map[string]float64{"function": 2}, Explain: true})
+	require.NotEmpty(t, results)
+	top := results[0].Explanation
+	require.NotNil(t, top)
+	assert.Equal(t, "symbol-proximity", top.Ranker)
+	require.Len(t, top.Components, 1)
+	assert.InDelta(t, top.Score, top.Components[0].Value, 1e-9, "the weight scales the single component")
+	assert.Contains(t, top.Components[0].Factors, search.ScoreFactor{Name: "kind_boost", Value: 2})
+}
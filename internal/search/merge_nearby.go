@@ -0,0 +1,175 @@
+package search
+
+import (
+	"slices"
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// How a hit merged by SearchOptions.MergeGap is scored
+const (
+	MergeScoreMax = "max" // The highest score of the merged hits (the default)
+	MergeScoreSum = "sum" // The sum of their scores, so a run of hits outranks a lone one
+)
+
+// mergeNearbyResults combines the hits of each file whose lines are at most gap apart
+// into one hit, for SearchOptions.MergeGap: a hit on line 12 and one on lines 14-15
+// merge with a gap of 2. The merged hit spans the union of the byte spans, its EndLine
+// and context lines are read through the file's LineIndex, and it takes the place of
+// its first hit in the list. Hits without a line are never merged. With MergeScoreSum
+// the results are re-sorted by score, since merging raised some of them.
+func (e *Engine) mergeNearbyResults(results []GrepResult, gap int, scoring string) []GrepResult {
+	if gap <= 0 || len(results) < 2 {
+		return results
+	}
+
+	type fileKey struct{ root, path string }
+	byFile := make(map[fileKey][]int)
+	var files []fileKey
+	for i, r := range results {
+		if r.Line < 1 {
+			continue
+		}
+		key := fileKey{r.Root, r.Path}
+		if _, ok := byFile[key]; !ok {
+			files = append(files, key)
+		}
+		byFile[key] = append(byFile[key], i)
+	}
+
+	dropped := make([]bool, len(results))
+	merged := false
+	for _, key := range files {
+		hits := byFile[key]
+		if len(hits) < 2 {
+			continue
+		}
+		sort.SliceStable(hits, func(a, b int) bool {
+			ra, rb := results[hits[a]], results[hits[b]]
+			if ra.Line != rb.Line {
+				return ra.Line < rb.Line
+			}
+			return ra.StartByte < rb.StartByte
+		})
+
+		var li *types.LineIndex
+		content, ok := e.indexer.GetFileContent(results[hits[0]].FileID)
+		if ok {
+			li = types.NewLineIndex(content)
+		}
+		for start := 0; start < len(hits); {
+			end, last := start+1, lastMatchLine(results[hits[start]])
+			for end < len(hits) && results[hits[end]].Line-last <= gap {
+				last = max(last, lastMatchLine(results[hits[end]]))
+				end++
+			}
+			if end-start > 1 {
+				run := hits[start:end]
+				target := slices.Min(run) // Keep the place of the run's first hit in the list
+				results[target] = mergeRun(results, run, li, content, scoring)
+				for _, i := range run {
+					dropped[i] = i != target
+				}
+				merged = true
+			}
+			start = end
+		}
+	}
+	if !merged {
+		return results
+	}
+
+	out := results[:0]
+	for i, r := range results {
+		if !dropped[i] {
+			out = append(out, r)
+		}
+	}
+	if scoring == MergeScoreSum {
+		sort.SliceStable(out, func(i, j int) bool { return out[i].Score > out[j].Score })
+	}
+	return out
+}
+
+// lastMatchLine returns the last line r's match covers
+func lastMatchLine(r GrepResult) int {
+	return max(r.Line, r.EndLine)
+}
+
+// mergeRun builds the hit that replaces the hits of run, indexes into results ordered
+// by line. li indexes the file's content, or is nil when the content is not available,
+// in which case the span's lines come from the hits alone and the context is the first
+// hit's.
+func mergeRun(results []GrepResult, run []int, li *types.LineIndex, content []byte, scoring string) GrepResult {
+	m := results[run[0]]
+	m.Context.MatchedLines, m.Context.MatchCount = nil, len(run)
+	contextStart, contextEnd := m.Context.StartLine, m.Context.EndLine
+	for k, i := range run {
+		r := results[i]
+		for line := r.Line; line <= lastMatchLine(r); line++ {
+			m.Context.MatchedLines = append(m.Context.MatchedLines, line)
+		}
+		if k == 0 {
+			continue
+		}
+
+		if r.EndByte > r.StartByte {
+			if m.EndByte <= m.StartByte {
+				m.StartByte = r.StartByte // The run's first hit has no span
+			}
+			m.StartByte, m.EndByte = min(m.StartByte, r.StartByte), max(m.EndByte, r.EndByte)
+		}
+		m.EndLine = max(m.EndLine, lastMatchLine(r))
+		m.TermSpans = append(m.TermSpans, r.TermSpans...)
+		for _, matcher := range r.MatchedBy {
+			if !slices.Contains(m.MatchedBy, matcher) {
+				m.MatchedBy = append(m.MatchedBy, matcher)
+			}
+		}
+		if r.Context.StartLine > 0 {
+			if contextStart == 0 || r.Context.StartLine < contextStart {
+				contextStart = r.Context.StartLine
+			}
+			contextEnd = max(contextEnd, r.Context.EndLine)
+		}
+		switch {
+		case scoring == MergeScoreSum:
+			m.Score += r.Score
+			m.Explanation.Add("merged hit", r.Score)
+		case r.Score > m.Score:
+			m.Score, m.Explanation = r.Score, r.Explanation
+		}
+	}
+	if scoring == MergeScoreSum && m.Explanation != nil {
+		m.Explanation.Score = m.Score
+	}
+	slices.Sort(m.Context.MatchedLines)
+	m.Context.MatchedLines = slices.Compact(m.Context.MatchedLines)
+
+	if li != nil {
+		if m.StartByte < m.EndByte && m.EndByte <= len(content) {
+			m.EndLine = li.LineSpan(m.StartByte, m.EndByte).End
+			m.Match = regionText(content[m.StartByte:m.EndByte])
+		}
+		if contextStart > 0 {
+			contextEnd = min(max(contextEnd, m.EndLine), li.LineCount())
+			lines := make([]string, 0, contextEnd-contextStart+1)
+			for line := contextStart; line <= contextEnd; line++ {
+				lines = append(lines, lineText(li, content, line))
+			}
+			m.Context.Lines, m.Context.StartLine, m.Context.EndLine = lines, contextStart, contextEnd
+		}
+	}
+	if m.EndLine <= m.Line {
+		m.EndLine = 0
+	}
+	return m
+}
+
+// lineText returns a 1-based line of content without its line ending
+func lineText(li *types.LineIndex, content []byte, line int) string {
+	start := li.Offset(types.Position{Line: line, Column: 1}, types.PositionEncodingUTF8)
+	end := li.Offset(types.Position{Line: line, Column: len(content) + 1}, types.PositionEncodingUTF8)
+	return regionText(content[start:end])
+}
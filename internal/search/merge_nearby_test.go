@@ -0,0 +1,59 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchWithOptions_MergeGap(t *testing.T) {
+	code := "token = read()\n" +
+		"check(token)\n" +
+		"use(token)\n" +
+		"\n" +
+		"\n" +
+		"\n" +
+		"print(token)\n"
+
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"app.py": code})
+	defer cleanup()
+
+	separate := engine.SearchWithOptions("token", fileIDs, types.SearchOptions{})
+	require.Len(t, separate, 4, "no merging unless requested")
+	scores := make(map[int]float64)
+	for _, r := range separate {
+		scores[r.Line] = r.Score
+	}
+
+	results := engine.SearchWithOptions("token", fileIDs, types.SearchOptions{MergeGap: 1})
+	require.Len(t, results, 2, "lines 1-3 are 1 apart; line 7 is 4 lines past them")
+	var merged, lone search.GrepResult
+	for _, r := range results {
+		if r.Line == 1 {
+			merged = r
+		} else {
+			lone = r
+		}
+	}
+	assert.Equal(t, 3, merged.EndLine)
+	assert.Equal(t, 0, merged.StartByte)
+	assert.Equal(t, "token = read()\ncheck(token)\nuse(token", merged.Match, "the merged span runs from the first hit to the end of the last")
+	assert.Equal(t, []int{1, 2, 3}, merged.Context.MatchedLines)
+	assert.Equal(t, max(scores[1], scores[2], scores[3]), merged.Score, "max scoring by default")
+	assert.Equal(t, 7, lone.Line)
+	assert.Zero(t, lone.EndLine)
+
+	results = engine.SearchWithOptions("token", fileIDs, types.SearchOptions{MergeGap: 1, MergeScore: search.MergeScoreSum})
+	require.Len(t, results, 2)
+	assert.Equal(t, 1, results[0].Line, "the merged hit outranks the lone one by its summed score")
+	assert.InDelta(t, scores[1]+scores[2]+scores[3], results[0].Score, 1e-9)
+
+	results = engine.SearchWithOptions("token", fileIDs, types.SearchOptions{MergeGap: 4})
+	require.Len(t, results, 1)
+	assert.Equal(t, 7, results[0].EndLine)
+	assert.Equal(t, 4, results[0].Context.MatchCount)
+}
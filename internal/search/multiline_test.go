@@ -0,0 +1,50 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchWithOptions_Multiline(t *testing.T) {
+	code := "package main\n" +
+		"\n" +
+		"func open(\n" +
+		"\tpath string,\n" +
+		") error {\n" +
+		"\treturn nil\n" +
+		"}\n" +
+		"\n" +
+		"func ok() {}\n"
+
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"open.go": code})
+	defer cleanup()
+
+	pattern := `func \w+\(.*?\) error`
+	results := engine.SearchWithOptions(pattern, fileIDs, types.SearchOptions{UseRegex: true})
+	assert.Empty(t, results, ". stops at newlines without Multiline")
+
+	results = engine.SearchWithOptions(pattern, fileIDs, types.SearchOptions{UseRegex: true, Multiline: true, SnippetContext: 1})
+	require.Len(t, results, 1)
+	r := results[0]
+	assert.Equal(t, 3, r.Line)
+	assert.Equal(t, 5, r.EndLine)
+	assert.Equal(t, "func open(\n\tpath string,\n) error", r.Match)
+	assert.Equal(t, 1, r.Context.StartLine)
+	assert.Equal(t, 8, r.Context.EndLine, "the context block covers every matched line")
+	assert.Equal(t, []string{""}, r.ContextBefore)
+	assert.Equal(t, []string{"\treturn nil"}, r.ContextAfter, "context after follows the last matched line")
+
+	results = engine.SearchWithOptions(pattern, fileIDs, types.SearchOptions{UseRegex: true, Multiline: true, CountPerFile: true})
+	require.Len(t, results, 1)
+	assert.Equal(t, 1, results[0].FileMatchCount, "a match spanning lines counts once")
+
+	// Matches on one line keep EndLine unset
+	results = engine.SearchWithOptions(`func \w+\(\)`, fileIDs, types.SearchOptions{UseRegex: true, Multiline: true})
+	require.Len(t, results, 1)
+	assert.Equal(t, 9, results[0].Line)
+	assert.Zero(t, results[0].EndLine)
+}
@@ -0,0 +1,74 @@
+package search
+
+import (
+	"path/filepath"
+	"strings"
+
+	"github.com/bmatcuk/doublestar/v4"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// MatchPathGlobs reports whether relPath, a slash-separated path relative to the project
+// root, matches any of globs. Globs use doublestar syntax, so ** crosses directories. A
+// glob that matches one of the path's directories matches every file under it, and a
+// glob without a slash, like *.rs or vendor, is matched against each name in the path.
+func MatchPathGlobs(relPath string, globs []string) bool {
+	for _, glob := range globs {
+		glob = strings.TrimSuffix(strings.TrimPrefix(glob, "./"), "/")
+		if globMatch(glob, relPath) {
+			return true
+		}
+		for i := range len(relPath) {
+			if relPath[i] == '/' && globMatch(glob, relPath[:i]) {
+				return true
+			}
+		}
+		if !strings.Contains(glob, "/") {
+			for _, name := range strings.Split(relPath, "/") {
+				if globMatch(glob, name) {
+					return true
+				}
+			}
+		}
+	}
+	return false
+}
+
+// globMatch is doublestar.Match with an invalid glob matching nothing
+func globMatch(glob, path string) bool {
+	ok, err := doublestar.Match(glob, path)
+	return err == nil && ok
+}
+
+// filterGlobFiles keeps the files matching an include glob (all files when there are
+// none) and then drops those matching an exclude glob, so exclude wins when a path
+// matches both. Files the index walk ignored are never candidates, so these globs only
+// narrow what the ignore files already allow.
+func (e *Engine) filterGlobFiles(candidates []types.FileID, include, exclude []string) []types.FileID {
+	if len(include) == 0 && len(exclude) == 0 {
+		return candidates
+	}
+
+	projectRoot := getProjectRoot(e.indexer)
+	var filtered []types.FileID
+	for _, fileID := range candidates {
+		path := e.indexer.GetFilePath(fileID)
+		if path == "" {
+			continue
+		}
+		if rel, err := filepath.Rel(projectRoot, path); err == nil && !strings.HasPrefix(rel, "..") {
+			path = rel
+		}
+		path = filepath.ToSlash(path)
+
+		if len(include) > 0 && !MatchPathGlobs(path, include) {
+			continue
+		}
+		if MatchPathGlobs(path, exclude) {
+			continue
+		}
+		filtered = append(filtered, fileID)
+	}
+	return filtered
+}
@@ -0,0 +1,63 @@
+package search_test
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestMatchPathGlobs(t *testing.T) {
+	tests := []struct {
+		path string
+		glob string
+		want bool
+	}{
+		{"src/auth.rs", "src/auth.rs", true},
+		{"src/auth.rs", "./src/auth.rs", true},
+		{"lib/src/auth.rs", "src/auth.rs", false},
+		{"src/auth.rs", "*.rs", true},
+		{"src/nested/db.rs", "src/*.rs", false},
+		{"src/nested/db.rs", "src/**/*.rs", true},
+		{"src/nested/db.rs", "src", true},
+		{"src/nested/db.rs", "src/", true},
+		{"a/vendor/lib.go", "vendor", true},
+		{"a/vendor/lib.go", "vend", false},
+		{"src/auth.rs", "[", false},
+	}
+	for _, tt := range tests {
+		assert.Equal(t, tt.want, search.MatchPathGlobs(tt.path, []string{tt.glob}), "%q against %q", tt.path, tt.glob)
+	}
+	assert.False(t, search.MatchPathGlobs("src/auth.rs", nil))
+}
+
+func TestSearchPathGlobs(t *testing.T) {
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{
+		"src/auth.rs":      "fn token() {}\n",
+		"src/auth_test.rs": "fn token_test() {}\n",
+		"src/db.rs":        "fn token_db() {}\n",
+		"tools/gen.go":     "func token() {}\n",
+	})
+	defer cleanup()
+
+	hitFiles := func(include, exclude []string) []string {
+		seen := make(map[string]bool)
+		var files []string
+		for _, r := range engine.SearchWithOptions("token", fileIDs, types.SearchOptions{IncludeGlobs: include, ExcludeGlobs: exclude}) {
+			if name := filepath.ToSlash(r.Path); !seen[name] {
+				seen[name] = true
+				files = append(files, filepath.Base(filepath.Dir(name))+"/"+filepath.Base(name))
+			}
+		}
+		return files
+	}
+
+	assert.Equal(t, []string{"src/auth.rs"}, hitFiles([]string{"src/auth.rs"}, nil), "an exact path selects one file")
+	assert.ElementsMatch(t, []string{"src/auth.rs", "src/auth_test.rs", "src/db.rs", "tools/gen.go"}, hitFiles(nil, nil))
+	assert.ElementsMatch(t, []string{"src/auth.rs", "src/db.rs", "tools/gen.go"}, hitFiles([]string{"*.rs", "tools/**"}, []string{"*_test.rs"}),
+		"include globs are alternatives and exclude removes from them")
+	assert.Empty(t, hitFiles([]string{"src/auth.rs"}, []string{"src"}), "exclude wins when both match")
+}
@@ -0,0 +1,252 @@
+package search
+
+import (
+	"cmp"
+	"slices"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/query"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// searchTerms splits a literal content pattern into its words and quoted phrases for
+// options.Terms, so `invalid credentials` finds lines holding both words and
+// `"invalid credentials"` only the contiguous phrase. Words with options.Aliases get
+// their alternatives. It returns nil when the pattern is searched as written, like
+// grep: without Terms, for regex, query, symbol and doc searches, which keep their own
+// syntax, for inverted and multi-pattern searches, which match whole patterns, and
+// when every term is negated, leaving nothing to find.
+func searchTerms(pattern string, options types.SearchOptions) []query.Phrase {
+	if !options.Terms || options.UseRegex || options.Query || options.SymbolsOnly || options.DocsOnly || options.InvertMatch ||
+		len(options.Patterns) > 1 || (!strings.ContainsAny(pattern, " \t\"") && !hasAliases(pattern, options.Aliases)) {
+		return nil
+	}
+	terms := query.SplitPhrases(pattern)
+	if !slices.ContainsFunc(terms, func(t query.Phrase) bool { return !t.Negated }) {
+		return nil
+	}
+	return expandAliases(terms, options.Aliases, options.CaseInsensitive, options.WordBoundary)
+}
+
+// searchAllTerms finds the lines holding every one of terms, in any of its spellings,
+// and none of the negated ones. The anchor term is searched for and the others are
+// looked for around each line it is found on: on the line itself, or with
+// options.TermsInSymbol anywhere in the innermost symbol enclosing it. A hit spans from
+// the first term on the line to the end of the last. Like searchWithin, lines are
+// filtered before any merging, count or files-only collapsing. Whole-word searches
+// first narrow the files to those whose postings hold every word of every term without
+// alternatives.
+func (e *Engine) searchAllTerms(terms []query.Phrase, candidates []types.FileID, options types.SearchOptions) []GrepResult {
+	var wanted, excluded []query.Phrase
+	for _, t := range terms {
+		if t.Negated {
+			excluded = append(excluded, t)
+		} else {
+			wanted = append(wanted, t)
+		}
+	}
+	anchor := termAnchor(wanted)
+	var texts []string
+	for _, t := range wanted {
+		if len(t.Alternatives) == 0 {
+			texts = append(texts, t.Text)
+		}
+	}
+	if len(candidates) == 0 && len(texts) > 0 {
+		if files, ok := e.postingsCandidates(strings.Join(texts, " "), options); ok {
+			if len(files) == 0 {
+				return nil
+			}
+			candidates = files
+		}
+	}
+
+	inner := options
+	inner.MergeFileResults = false
+	inner.CountPerFile = false
+	inner.FilesOnly = false
+	inner.MaxResults = 0
+	inner.MaxCountPerFile = 0
+	results := e.searchPattern(anchor.Text, candidates, inner, nil)
+	if len(anchor.Alternatives) > 0 {
+		for _, s := range anchor.Alternatives {
+			results = append(results, e.searchPattern(s, candidates, inner, nil)...)
+		}
+		results = uniqueLines(results)
+	}
+
+	lineOptions := types.SearchOptions{CaseInsensitive: options.CaseInsensitive, WordBoundary: options.WordBoundary}
+	perFile := make(map[types.FileID]int)
+	kept := results[:0]
+	for _, r := range results {
+		if options.MaxCountPerFile > 0 && perFile[r.FileID] >= options.MaxCountPerFile {
+			continue
+		}
+		line, ok := e.indexer.GetFileLine(r.FileID, r.Line)
+		if !ok {
+			continue
+		}
+		unit := line
+		if options.TermsInSymbol {
+			if text, ok := e.enclosingSymbolText(r.FileID, r.Line); ok {
+				unit = text
+			}
+		}
+		if !holdsTerms(unit, wanted, excluded, lineOptions) {
+			continue
+		}
+		start, end, spans, ok := termsSpan(line, wanted, lineOptions)
+		if !ok {
+			continue
+		}
+		lineStart := r.StartByte - r.Column
+		r.Column, r.StartByte, r.EndByte, r.Match = start, lineStart+start, lineStart+end, line[start:end]
+		r.TermSpans = make([][2]int, len(spans))
+		for i, span := range spans {
+			r.TermSpans[i] = [2]int{lineStart + span[0], lineStart + span[1]}
+		}
+		perFile[r.FileID]++
+		kept = append(kept, r)
+	}
+
+	switch {
+	case options.CountPerFile:
+		return e.countPerFileResults(kept)
+	case options.FilesOnly:
+		return filesOnlyResults(kept)
+	}
+	if options.MaxResults > 0 && len(kept) > options.MaxResults {
+		kept = kept[:options.MaxResults]
+	}
+	return kept
+}
+
+// termAnchor picks the term searchAllTerms searches for: the longest term without
+// alternatives, as the rarest to find, or failing that the one with fewest spellings
+func termAnchor(wanted []query.Phrase) query.Phrase {
+	anchor := wanted[0]
+	for _, t := range wanted[1:] {
+		switch a, b := len(anchor.Alternatives), len(t.Alternatives); {
+		case b < a, b == a && len(t.Text) > len(anchor.Text):
+			anchor = t
+		}
+	}
+	return anchor
+}
+
+// uniqueLines keeps the first hit on each line, ordered by file and position, for an
+// anchor found under several spellings
+func uniqueLines(results []GrepResult) []GrepResult {
+	slices.SortStableFunc(results, func(a, b GrepResult) int {
+		return cmp.Or(cmp.Compare(a.FileID, b.FileID), cmp.Compare(a.Line, b.Line), cmp.Compare(a.StartByte, b.StartByte))
+	})
+	return slices.CompactFunc(results, func(a, b GrepResult) bool {
+		return a.FileID == b.FileID && a.Line == b.Line
+	})
+}
+
+// enclosingSymbolText returns the lines of the innermost symbol enclosing line, joined
+// by newlines, or false when line lies outside every symbol
+func (e *Engine) enclosingSymbolText(fileID types.FileID, line int) (string, bool) {
+	sym := e.indexer.GetSymbolAtLine(fileID, line)
+	if sym == nil || sym.EndLine < sym.Line {
+		return "", false
+	}
+	return strings.Join(e.indexer.GetFileLines(fileID, sym.Line, sym.EndLine), "\n"), true
+}
+
+// holdsTerms reports whether text holds every one of wanted and none of excluded, each
+// in any of its spellings
+func holdsTerms(text string, wanted, excluded []query.Phrase, options types.SearchOptions) bool {
+	content := []byte(text)
+	holds := func(t query.Phrase) bool {
+		for _, s := range t.Spellings() {
+			if len(findAllMatchesWithOptions(content, []byte(s), options)) > 0 {
+				return true
+			}
+		}
+		return false
+	}
+	for _, t := range wanted {
+		if !holds(t) {
+			return false
+		}
+	}
+	for _, t := range excluded {
+		if holds(t) {
+			return false
+		}
+	}
+	return true
+}
+
+// termsSpan returns the byte range of line from its first term to the end of its last,
+// using each spelling's first occurrence, or false when none of terms is on the line.
+// Terms missing from the line, which a symbol-wide search allows, are skipped. spans
+// lists every occurrence of every spelling, ordered by start.
+func termsSpan(line string, terms []query.Phrase, options types.SearchOptions) (start, end int, spans [][2]int, ok bool) {
+	content := []byte(line)
+	start = len(line)
+	for _, t := range terms {
+		for _, s := range t.Spellings() {
+			matches := findAllMatchesWithOptions(content, []byte(s), options)
+			if len(matches) == 0 {
+				continue
+			}
+			start, end = min(start, matches[0].Start), max(end, matches[0].End)
+			for _, m := range matches {
+				spans = append(spans, [2]int{m.Start, m.End})
+			}
+		}
+	}
+	slices.SortFunc(spans, func(a, b [2]int) int { return cmp.Compare(a[0], b[0]) })
+	return start, end, spans, len(spans) > 0
+}
+
+// phraseProximity scores how closely line holds the phrases of a pattern: 1 for each
+// quoted phrase on it, plus, for two or more unquoted words, their share of the stretch
+// of line from the first of them to the last, which is 1 when they appear together as
+// written
+func phraseProximity(phrases []query.Phrase, line string, caseInsensitive bool) float64 {
+	fold := func(s string) string {
+		if caseInsensitive {
+			return strings.ToLower(s)
+		}
+		return s
+	}
+	line = fold(line)
+
+	score := 0.0
+	var words []string
+	for _, p := range phrases {
+		if p.Negated {
+			continue
+		}
+		if p.Quoted {
+			if strings.Contains(line, fold(p.Text)) {
+				score++
+			}
+			continue
+		}
+		words = append(words, fold(p.Text))
+	}
+	if len(words) < 2 {
+		return score
+	}
+	if strings.Contains(line, strings.Join(words, " ")) {
+		return score + 1
+	}
+
+	start, end, length := len(line), 0, 0
+	for _, w := range words {
+		i := strings.Index(line, w)
+		if i < 0 {
+			return score
+		}
+		start, end, length = min(start, i), max(end, i+len(w)), length+len(w)
+	}
+	if share := float64(length) / float64(end-start); share < 1 {
+		return score + share
+	}
+	return score + 1
+}
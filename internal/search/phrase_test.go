@@ -0,0 +1,128 @@
+package search_test
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchTerms(t *testing.T) {
+	source := "fn authenticate() {\n    return Err(\"invalid credentials\".into());\n}\n\n// credentials that are invalid\n// invalid input\n"
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.rs": source, "f.go": "package f\n\nvar y = f(\"x\")\n"})
+	defer cleanup()
+
+	find := func(pattern string, options types.SearchOptions) []string {
+		var matches []string
+		for _, r := range engine.SearchWithOptions(pattern, fileIDs, options) {
+			if filepath.Base(r.Path) == "auth.rs" {
+				require.Equal(t, r.Match, source[r.StartByte:r.EndByte], "spans point at the original bytes")
+			}
+			matches = append(matches, r.Match)
+		}
+		return matches
+	}
+	terms := types.SearchOptions{Terms: true}
+
+	assert.Equal(t, []string{"invalid credentials"}, find(`"invalid credentials"`, terms),
+		"a quoted phrase only matches as written")
+	assert.ElementsMatch(t, []string{"invalid credentials", "credentials that are invalid"}, find("invalid credentials", terms),
+		"unquoted words match anywhere on the line, and the hit spans from the first to the last")
+	assert.Equal(t, []string{`Err("invalid credentials`}, find(`"invalid credentials" Err`, terms),
+		"phrases and words combine")
+	assert.Empty(t, find(`"credentials invalid"`, terms))
+
+	for _, r := range engine.SearchWithOptions("invalid credentials", fileIDs, terms) {
+		var spanned []string
+		for _, span := range r.TermSpans {
+			spanned = append(spanned, source[span[0]:span[1]])
+		}
+		assert.ElementsMatch(t, []string{"invalid", "credentials"}, spanned, "each term occurrence on the line has a span")
+	}
+
+	assert.Equal(t, []string{"invalid credentials"}, find("invalid credentials", types.SearchOptions{}),
+		"without Terms the pattern is matched as written, like grep")
+	assert.Equal(t, []string{`f("x")`}, find(`f("x")`, terms), "quotes inside a word are literal")
+
+	counts := engine.SearchWithOptions("invalid credentials", fileIDs, types.SearchOptions{Terms: true, CountPerFile: true})
+	require.Len(t, counts, 1)
+	assert.Equal(t, 2, counts[0].FileMatchCount, "only lines holding every term are counted")
+
+	var streamed []string
+	require.NoError(t, engine.SearchStream("invalid credentials", fileIDs, terms, func(r search.GrepResult) bool {
+		streamed = append(streamed, r.Match)
+		return true
+	}))
+	assert.ElementsMatch(t, find("invalid credentials", terms), streamed)
+}
+
+func TestBm25Ranker_PhraseProximity(t *testing.T) {
+	gi, engine := setupRankerIndex(t, map[string]string{
+		"auth.rs": "// credentials were invalid, so the request stops here\nfn check() {\n    return Err(\"invalid credentials\".into());\n}\n",
+	})
+	engine.SetRanker(search.NewBm25Ranker(gi.GetTermStatsIndex()))
+
+	results := engine.SearchWithOptions("invalid credentials", nil, types.SearchOptions{Terms: true, Explain: true})
+	require.Len(t, results, 2)
+	assert.Equal(t, "invalid credentials", results[0].Match, "the words together as written rank first")
+	assert.Greater(t, results[0].Score, results[1].Score)
+	for _, r := range results {
+		require.NotNil(t, r.Explanation)
+		assert.InDelta(t, r.Score, r.Explanation.Score, 1e-9)
+	}
+
+	noBoost := search.NewBm25Ranker(gi.GetTermStatsIndex())
+	noBoost.PhraseBoost = 0
+	engine.SetRanker(noBoost)
+	results = engine.SearchWithOptions("invalid credentials", nil, types.SearchOptions{Terms: true})
+	require.Len(t, results, 2)
+	assert.Equal(t, results[0].Score, results[1].Score, "without the boost hits in one file share their BM25 score")
+}
+
+func TestSearchTerms_Negated(t *testing.T) {
+	_, engine := setupRankerIndex(t, map[string]string{
+		"users.rs": "fn create_user(name: &str, email: &str) {\n    save(name);\n    notify(email);\n}\n\nfn create_team(name: &str) {\n    save(name);\n}\n",
+	})
+	lines := func(pattern string, options types.SearchOptions) []int {
+		var out []int
+		for _, r := range engine.SearchWithOptions(pattern, nil, options) {
+			out = append(out, r.Line)
+		}
+		return out
+	}
+	onLine := types.SearchOptions{Terms: true}
+	inSymbol := types.SearchOptions{Terms: true, TermsInSymbol: true}
+
+	assert.ElementsMatch(t, []int{1, 6}, lines("create", onLine))
+	assert.Equal(t, []int{6}, lines("create -email", onLine), "a line mentioning a -term is dropped")
+	assert.Equal(t, []int{6}, lines("-email create", onLine), "negated terms may come first")
+	assert.ElementsMatch(t, []int{2, 7}, lines("save -email", onLine), "line 2 does not mention email itself")
+	assert.Equal(t, []int{7}, lines("save -email", inSymbol), "create_user's body mentions email")
+	assert.Equal(t, []int{3}, lines("save notify", inSymbol), "positive terms may be anywhere in the symbol")
+	assert.Empty(t, lines("save notify", onLine))
+	assert.Equal(t, []int{6}, lines(`create -"email: &str"`, onLine), "a quoted phrase can be negated")
+
+	assert.Empty(t, lines("-email -name", onLine), "with only negated terms the pattern is matched as written")
+	assert.Empty(t, lines("save -1", onLine), "a dash before a digit is part of a literal term")
+}
+
+func TestSearch_TermSpansOfOneTerm(t *testing.T) {
+	source := "package f\n\n// needle, then needle again\n"
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"f.go": source})
+	defer cleanup()
+
+	for _, options := range []types.SearchOptions{{}, {Terms: true, MergeFileResults: true}} {
+		results := engine.SearchWithOptions("needle", fileIDs, options)
+		require.Len(t, results, 1, "one hit for the line")
+		var spanned []int
+		for _, span := range results[0].TermSpans {
+			assert.Equal(t, "needle", source[span[0]:span[1]])
+			spanned = append(spanned, span[0])
+		}
+		assert.Equal(t, []int{14, 27}, spanned, "both occurrences on the line have a span")
+	}
+}
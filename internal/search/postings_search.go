@@ -0,0 +1,97 @@
+package search
+
+import (
+	"bytes"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// postingsProvider is implemented by indexers that keep a token postings index
+type postingsProvider interface {
+	GetPostingsIndex() *core.PostingsIndex
+}
+
+// postingsIndex returns the indexer's postings index, or nil when it has none
+func (e *Engine) postingsIndex() *core.PostingsIndex {
+	if pp, ok := e.indexer.(postingsProvider); ok {
+		return pp.GetPostingsIndex()
+	}
+	return nil
+}
+
+// postingsTokens returns the lowercased word runs of pattern that the postings index
+// answers, or false when it cannot be used. Only whole-word literal searches qualify:
+// a -w match has a word boundary at both ends, so each word run of the pattern is a
+// whole token wherever it matches. Substring searches keep trigram candidates, since
+// "user" must still find "getUser", and regex and inverted searches scan.
+func postingsTokens(pattern string, options types.SearchOptions) ([]string, bool) {
+	if !options.WordBoundary || options.UseRegex || options.InvertMatch || !isASCII(pattern) {
+		return nil, false
+	}
+	var tokens []string
+	start := -1
+	for i := 0; i <= len(pattern); i++ {
+		if i < len(pattern) && isWordChar(pattern[i]) {
+			if start < 0 {
+				start = i
+			}
+			continue
+		}
+		// Shorter runs are not indexed and only narrow the match further
+		if start >= 0 && i-start >= core.MinPostingsTokenLength {
+			tokens = append(tokens, strings.ToLower(pattern[start:i]))
+		}
+		start = -1
+	}
+	return tokens, len(tokens) > 0
+}
+
+// postingsCandidates returns the files holding every token of a whole-word pattern, or
+// false when the postings index does not apply or does not cover the whole index
+func (e *Engine) postingsCandidates(pattern string, options types.SearchOptions) ([]types.FileID, bool) {
+	tokens, ok := postingsTokens(pattern, options)
+	if !ok {
+		return nil, false
+	}
+	postings := e.postingsIndex()
+	if postings == nil || postings.FileCount() != e.indexer.GetFileCount() {
+		return nil, false
+	}
+	return postings.FilesWithAll(tokens), true
+}
+
+// findMatches finds pattern in a file's content. A whole-word search for a single token
+// reads the token's offsets from the postings index instead of scanning content.
+func (e *Engine) findMatches(fileID types.FileID, content, pattern []byte, options types.SearchOptions) []Match {
+	if tokens, ok := postingsTokens(string(pattern), options); ok && len(tokens) == 1 && len(tokens[0]) == len(pattern) && !options.ExcludeComments {
+		if postings := e.postingsIndex(); postings != nil && postings.Covers(fileID) {
+			return tokenMatches(content, pattern, postings.Offsets(tokens[0], fileID), options.CaseInsensitive)
+		}
+	}
+	return findAllMatchesWithOptions(content, pattern, options)
+}
+
+// tokenMatches turns the offsets of a case-folded token into whole-word matches of
+// pattern, dropping ones whose case differs when the search is case-sensitive
+func tokenMatches(content, pattern []byte, offsets []int, caseInsensitive bool) []Match {
+	var matches []Match
+	for _, start := range offsets {
+		end := start + len(pattern)
+		if end > len(content) || (!caseInsensitive && !bytes.Equal(content[start:end], pattern)) {
+			continue
+		}
+		matches = append(matches, Match{Start: start, End: end, Exact: true})
+	}
+	return matches
+}
+
+func isASCII(s string) bool {
+	for i := 0; i < len(s); i++ {
+		if s[i] > 0x7F {
+			return false
+		}
+	}
+	return true
+}
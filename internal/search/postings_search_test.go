@@ -0,0 +1,108 @@
+package search_test
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/interfaces"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// scanOnly hides the postings index (and every other optional indexer interface), so
+// its engine finds matches by scanning every file
+type scanOnly struct {
+	interfaces.Indexer
+}
+
+func TestWholeWordSearch_Postings(t *testing.T) {
+	gi, engine := setupRankerIndex(t, map[string]string{
+		"user.go":  "package user\n\nfunc getUser() User {\n\tuser := User{}\n\t// the user docs\n\treturn user\n}\n",
+		"name.go":  "package name\n\nvar username = \"user_name\"\n",
+		"other.go": "package other\n\nfunc Other() {}\n",
+	})
+	scan := search.NewEngine(scanOnly{gi})
+
+	hits := func(engine *search.Engine, pattern string, options types.SearchOptions) []string {
+		var out []string
+		for _, r := range engine.SearchWithOptions(pattern, nil, options) {
+			out = append(out, fmt.Sprintf("%s:%d:%d:%s", filepath.Base(r.Path), r.Line, r.Column, r.Match))
+		}
+		return out
+	}
+	wholeWord := types.SearchOptions{WordBoundary: true}
+	folded := types.SearchOptions{WordBoundary: true, CaseInsensitive: true}
+	terms := types.SearchOptions{WordBoundary: true, Terms: true}
+
+	assert.ElementsMatch(t, []string{"user.go:1:8:user", "user.go:4:1:user", "user.go:5:8:user", "user.go:6:8:user"}, hits(engine, "user", wholeWord),
+		"a token lookup finds whole words only, in their case")
+	assert.ElementsMatch(t, []string{"user.go:3:15:User", "user.go:4:9:User"}, hits(engine, "User", wholeWord))
+	assert.Empty(t, hits(engine, "missing", wholeWord))
+
+	for _, tc := range []struct {
+		pattern string
+		options types.SearchOptions
+	}{
+		{"user", wholeWord},
+		{"USER", folded},
+		{"user docs", wholeWord},
+		{"user := User", wholeWord},
+		{"user docs", terms},
+		{`"the user" docs`, terms},
+		{"user", types.SearchOptions{}},
+	} {
+		assert.ElementsMatch(t, hits(scan, tc.pattern, tc.options), hits(engine, tc.pattern, tc.options),
+			"postings and a full scan agree on %q", tc.pattern)
+	}
+
+	// A file indexed after the first run is found through its postings too
+	require.NoError(t, os.WriteFile(filepath.Join(gi.GetConfig().Project.Root, "late.go"), []byte("package late\n\nvar user = 1\n"), 0644))
+	require.NoError(t, gi.IndexFile(filepath.Join(gi.GetConfig().Project.Root, "late.go")))
+	assert.Contains(t, hits(engine, "user", wholeWord), "late.go:3:4:user")
+}
+
+// BenchmarkWholeWordSearch compares a whole-word search answered from the postings
+// index with a scan of every file, over the testdata fixtures copied 300 times
+func BenchmarkWholeWordSearch(b *testing.B) {
+	tempDir := b.TempDir()
+	for _, name := range []string{"sample.go", "sample.js", "sample.py"} {
+		content, err := os.ReadFile(filepath.Join("testdata", name))
+		require.NoError(b, err)
+		for i := 0; i < 300; i++ {
+			require.NoError(b, os.WriteFile(filepath.Join(tempDir, fmt.Sprintf("%d_%s", i, name)), content, 0644))
+		}
+	}
+	cfg := &config.Config{
+		Version: 1,
+		Project: config.Project{Root: tempDir},
+		Index:   config.Index{MaxFileSize: types.DefaultMaxFileSize, MaxFileCount: types.DefaultMaxFileCount},
+	}
+	gi := indexing.NewMasterIndex(cfg)
+	defer gi.Close()
+	require.NoError(b, gi.IndexDirectory(context.Background(), tempDir))
+
+	options := types.SearchOptions{WordBoundary: true, MaxResults: 10000}
+	for _, bc := range []struct {
+		name   string
+		engine *search.Engine
+	}{
+		{"postings", search.NewEngine(gi)},
+		{"scan", search.NewEngine(scanOnly{gi})},
+	} {
+		b.Run(bc.name, func(b *testing.B) {
+			for i := 0; i < b.N; i++ {
+				if len(bc.engine.SearchWithOptions("ExportedFunction", nil, options)) == 0 {
+					b.Fatal("expected results")
+				}
+			}
+		})
+	}
+}
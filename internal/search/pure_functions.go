@@ -3,6 +3,8 @@ package search
 import (
 	"bytes"
 	"strings"
+
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 // Pure functions for search operations
@@ -143,10 +145,17 @@ func FindLiteralOccurrencesCaseInsensitive(content, pattern []byte) []int {
 		return nil
 	}
 
-	lower := bytes.ToLower(content)
-	lowerPattern := bytes.ToLower(pattern)
-
-	return FindLiteralOccurrences(lower, lowerPattern)
+	var positions []int
+	offset := 0
+	for offset < len(content) {
+		start, _ := types.IndexFold(content[offset:], pattern)
+		if start < 0 {
+			break
+		}
+		positions = append(positions, offset+start)
+		offset += start + 1 // Allow overlapping matches
+	}
+	return positions
 }
 
 // FindWholeWordOccurrences finds occurrences where pattern appears as a whole word.
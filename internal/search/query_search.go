@@ -0,0 +1,199 @@
+package search
+
+import (
+	"math"
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/lang"
+	"github.com/standardbeagle/lci/internal/query"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// SearchQuery evaluates a query such as `kind:fn AND name:user AND NOT path:auth`
+// against every indexed symbol. Results point at the names of the matching symbols and
+// are ordered by path and position, like SymbolsOnly results, except that a query with
+// NEAR puts the symbols whose words are closest first, carrying query.Score as their
+// Score. Include, exclude and SymbolTypes filters still apply.
+//
+// A malformed query is reported as a *query.QueryError carrying the offending position.
+func (e *Engine) SearchQuery(input string, options types.SearchOptions) ([]GrepResult, error) {
+	q, err := query.Parse(input)
+	if err != nil {
+		return nil, err
+	}
+	options.Query = true
+	options.SymbolsOnly = true
+	return e.finishResults(e.searchQuery(q, options), input, options), nil
+}
+
+// searchQuery matches q against the symbols of every candidate file. Each file's
+// language is detected once and its content is only read when the query has a text:
+// term or a NEAR word the postings index does not record.
+func (e *Engine) searchQuery(q query.Query, options types.SearchOptions) []GrepResult {
+	candidates := e.getAllFileIDs()
+	candidates = e.filterIncludedFiles(candidates, options.IncludePattern)
+	candidates = e.filterExcludedFiles(candidates, options.ExcludePattern)
+	candidates = e.filterGlobFiles(candidates, options.IncludeGlobs, options.ExcludeGlobs)
+	needsSource := query.NeedsSource(q)
+	near := query.HasNear(q)
+
+	var results []GrepResult
+	for _, fileID := range candidates {
+		if ResultCapReached(options, len(results)) || stopAtDeadline(options) {
+			break
+		}
+		symbols := e.indexer.GetFileSymbols(fileID)
+		if len(symbols) == 0 {
+			continue
+		}
+		path := e.indexer.GetFilePath(fileID)
+		language := e.detectLanguage(path)
+		if language == "" {
+			language = lang.PlainText
+		}
+		var content []byte
+		if needsSource {
+			content, _ = e.indexer.GetFileContent(fileID)
+		}
+		var offsets []uint32
+		var positions *filePositions
+		if near {
+			positions = e.newFilePositions(fileID, content)
+		}
+
+		for _, sym := range symbols {
+			if sym.Name == "" {
+				continue
+			}
+			if len(options.SymbolTypes) > 0 && !contains(options.SymbolTypes, sym.Type.String()) {
+				continue
+			}
+			candidate := query.Symbol{
+				Name:          sym.Name,
+				Kind:          sym.Type,
+				Parent:        sym.Parent,
+				Path:          path,
+				Language:      language,
+				Signature:     sym.Signature,
+				StripGenerics: options.SignatureStripGenerics,
+			}
+			if needsSource {
+				candidate.Source = e.symbolSource(fileID, content, sym)
+			}
+			if near {
+				start, end := positions.symbolSpan(sym)
+				candidate.Positions = func(word string) []int { return positions.within(word, start, end) }
+			}
+			if !q.Match(&candidate) {
+				continue
+			}
+			if offsets == nil {
+				offsets, _ = e.indexer.GetFileLineOffsets(fileID)
+			}
+			result := e.symbolNameResult(fileID, path, offsets, sym, options)
+			if near {
+				result.Score = query.Score(q, &candidate)
+			}
+			results = append(results, result)
+		}
+	}
+	if !near {
+		return sortSymbolResults(results, options)
+	}
+	// Closest first, by path and position among equal scores
+	unlimited := options
+	unlimited.MaxResults = 0
+	results = sortSymbolResults(results, unlimited)
+	sort.SliceStable(results, func(i, j int) bool { return results[i].Score > results[j].Score })
+	if options.MaxResults > 0 && len(results) > options.MaxResults {
+		results = results[:options.MaxResults]
+	}
+	return results
+}
+
+// symbolSource returns the bytes of sym's declaration span, or its declaration line
+// when the extractor recorded no span
+func (e *Engine) symbolSource(fileID types.FileID, content []byte, sym types.Symbol) []byte {
+	if sym.EndByte > sym.StartByte && sym.EndByte <= len(content) {
+		return content[sym.StartByte:sym.EndByte]
+	}
+	line, _ := e.indexer.GetFileLine(fileID, sym.Line)
+	return []byte(line)
+}
+
+// filePositions looks up the token positions of NEAR words in one file. Words the
+// postings index records are read from it; shorter words, and files it does not
+// cover, are found by scanning the content, which numbers positions the same way.
+type filePositions struct {
+	e        *Engine
+	fileID   types.FileID
+	postings *core.PostingsIndex // nil when the file is not covered
+	content  []byte              // Read on the first scan
+	offsets  []uint32            // Line offsets, read for symbols without a byte span
+	words    map[string][]core.Occurrence
+}
+
+func (e *Engine) newFilePositions(fileID types.FileID, content []byte) *filePositions {
+	fp := &filePositions{e: e, fileID: fileID, content: content, words: make(map[string][]core.Occurrence)}
+	if postings := e.postingsIndex(); postings != nil && postings.Covers(fileID) {
+		fp.postings = postings
+	}
+	return fp
+}
+
+// symbolSpan returns the byte span of sym's declaration, or of its declaration line
+// when the extractor recorded no span
+func (fp *filePositions) symbolSpan(sym types.Symbol) (start, end int) {
+	if sym.EndByte > sym.StartByte {
+		return sym.StartByte, sym.EndByte
+	}
+	if fp.offsets == nil {
+		fp.offsets, _ = fp.e.indexer.GetFileLineOffsets(fp.fileID)
+	}
+	if sym.Line < 1 || sym.Line > len(fp.offsets) {
+		return 0, 0
+	}
+	start, end = int(fp.offsets[sym.Line-1]), math.MaxInt
+	if sym.Line < len(fp.offsets) {
+		end = int(fp.offsets[sym.Line])
+	}
+	return start, end
+}
+
+// within returns the ascending positions of word's occurrences starting in [start, end)
+func (fp *filePositions) within(word string, start, end int) []int {
+	occurrences, ok := fp.words[word]
+	if !ok {
+		if fp.postings != nil && len(word) >= core.MinPostingsTokenLength {
+			occurrences = fp.postings.Occurrences(word, fp.fileID)
+		} else {
+			if fp.content == nil {
+				fp.content, _ = fp.e.indexer.GetFileContent(fp.fileID)
+			}
+			occurrences = core.FindOccurrences(fp.content, word)
+		}
+		fp.words[word] = occurrences
+	}
+	var positions []int
+	for _, o := range occurrences {
+		if o.Offset >= start && o.Offset < end {
+			positions = append(positions, o.Position)
+		}
+	}
+	return positions
+}
+
+// languageDetector is implemented by indexers with their own language overrides
+type languageDetector interface {
+	DetectLanguage(path string) string
+}
+
+// detectLanguage returns the language of the file at path as the indexer detects it,
+// or by extension alone for indexers without overrides
+func (e *Engine) detectLanguage(path string) string {
+	if ld, ok := e.indexer.(languageDetector); ok {
+		return ld.DetectLanguage(path)
+	}
+	return lang.Detect(path)
+}
@@ -0,0 +1,100 @@
+package search_test
+
+import (
+	"errors"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/query"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchQuery(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"users.go": `package main
+
+type User struct{}
+
+func find_user() {}
+func save_user() { panic("disk full") }
+`,
+		"auth/login.go": `package auth
+
+func login_user() {}
+`,
+	})
+	defer cleanup()
+
+	located := func(q string) []string {
+		t.Helper()
+		results, err := engine.SearchQuery(q, types.SearchOptions{})
+		require.NoError(t, err, q)
+		var got []string
+		for _, r := range results {
+			got = append(got, filepath.Base(r.Path)+":"+r.Match)
+		}
+		return got
+	}
+
+	assert.Equal(t, []string{"users.go:find_user", "users.go:save_user"}, located("kind:fn AND name:user AND NOT path:auth"))
+	assert.Equal(t, []string{"login.go:login_user", "users.go:User"}, located("kind:struct OR path:auth"))
+	assert.Equal(t, []string{"users.go:save_user"}, located(`text:"DISK FULL"`))
+
+	// The Query option routes SearchWithOptions through the query evaluator
+	results := engine.SearchWithOptions("name:find", nil, types.SearchOptions{Query: true})
+	require.Len(t, results, 1)
+	assert.Equal(t, "find_user", results[0].Match)
+
+	_, err := engine.SearchQuery("kind:fn AND size:3", types.SearchOptions{})
+	var qe *query.QueryError
+	require.True(t, errors.As(err, &qe), "want a QueryError, got %v", err)
+	assert.Equal(t, 12, qe.Pos)
+}
+
+func TestSearchQuery_Near(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"token.go": `package auth
+
+type Session struct {
+	Expires time.Time
+	User    string
+	ID      int
+	Token   Token
+}
+
+type Token struct {
+	Value   string
+	Expires time.Time
+}
+`,
+	})
+	defer cleanup()
+
+	run := func(q string) []search.GrepResult {
+		t.Helper()
+		results, err := engine.SearchQuery(q, types.SearchOptions{})
+		require.NoError(t, err, q)
+		return results
+	}
+	names := func(results []search.GrepResult) []string {
+		var got []string
+		for _, r := range results {
+			got = append(got, r.Match)
+		}
+		return got
+	}
+
+	results := run("kind:struct AND token NEAR/8 expires")
+	assert.Equal(t, []string{"Token", "Session"}, names(results), "the closest words come first")
+	require.Len(t, results, 2)
+	assert.Equal(t, 0.625, results[0].Score, "4 tokens apart of at most 8")
+	assert.Equal(t, 0.25, results[1].Score)
+
+	assert.Equal(t, []string{"Token"}, names(run("kind:struct AND token NEAR/5 expires")))
+	assert.Equal(t, []string{"Session"}, names(run("kind:struct AND expires NEARW/8 token")), "NEARW keeps the order")
+	assert.Equal(t, []string{"Session"}, names(run("kind:struct AND id NEAR/2 int")), "words shorter than the postings index records are scanned")
+}
@@ -0,0 +1,433 @@
+package search
+
+import (
+	"fmt"
+	"math"
+	"sort"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/interfaces"
+	"github.com/standardbeagle/lci/internal/query"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// RankQuery is the query a Ranker scores hits against
+type RankQuery struct {
+	Pattern         string
+	Terms           []string       // Pattern split into index terms (see core.TokenizeTerms)
+	Phrases         []query.Phrase // Words and quoted phrases, set when the pattern is split into terms
+	CaseInsensitive bool
+}
+
+// NewRankQuery builds a RankQuery for pattern, splitting it into words and quoted
+// phrases as a search with SearchOptions.Terms would
+func NewRankQuery(pattern string, caseInsensitive bool) RankQuery {
+	return RankQuery{
+		Pattern:         pattern,
+		Terms:           core.TokenizeTerms(pattern),
+		Phrases:         query.SplitPhrases(pattern),
+		CaseInsensitive: caseInsensitive,
+	}
+}
+
+// RawHit is a match before ranking: where it was found and the text around it
+type RawHit struct {
+	FileID   types.FileID
+	Path     string
+	Line     int
+	Column   int
+	Match    string
+	LineText string // Full source line containing the match
+}
+
+// Ranker scores search hits. Higher scores rank first. Implementations must be safe
+// for concurrent use since one engine serves many searches.
+type Ranker interface {
+	Name() string
+	Score(query *RankQuery, hit *RawHit) float64
+}
+
+// Explainer is implemented by rankers that can break a score into its parts for
+// debugging. Explain must return the score Score would for the same hit.
+type Explainer interface {
+	Explain(query *RankQuery, hit *RawHit) ScoreExplanation
+}
+
+// defaultRankerName labels explanations of the engine's built-in scoring
+const defaultRankerName = "default"
+
+// SetRanker replaces the engine's built-in scoring with r. Pass nil to restore the
+// default scoring. It is safe to call while searches run; each search uses the ranker
+// set when it started.
+func (e *Engine) SetRanker(r Ranker) {
+	if r == nil {
+		e.ranker.Store(nil)
+		return
+	}
+	e.ranker.Store(&r)
+}
+
+// Ranker returns the ranker set with SetRanker, or nil when default scoring is in use
+func (e *Engine) Ranker() Ranker {
+	if r := e.ranker.Load(); r != nil {
+		return *r
+	}
+	return nil
+}
+
+// rankerFor returns the ranker scoring a search with options: none for a fuzzy search,
+// a SymbolProximityRanker weighted by options.KindBoosts when set, otherwise the one
+// set with SetRanker
+func (e *Engine) rankerFor(options types.SearchOptions) Ranker {
+	if options.Fuzzy != "" {
+		return nil // Fuzzy hits are ranked by their fuzzy score
+	}
+	if len(options.KindBoosts) > 0 {
+		return &SymbolProximityRanker{Indexer: e.indexer, Boosts: options.KindBoosts}
+	}
+	return e.Ranker()
+}
+
+// rerank rescores results with ranker and sorts them best first; ties keep their order
+func (e *Engine) rerank(ranker Ranker, results []GrepResult, pattern string, options types.SearchOptions) {
+	e.rescore(ranker, results, pattern, options)
+	sort.SliceStable(results, func(i, j int) bool {
+		return results[i].Score > results[j].Score
+	})
+}
+
+// rescore replaces each result's score with ranker's, leaving the order alone. With
+// options.Explain each result also gets the ranker's explanation, or just its score
+// when the ranker is not an Explainer.
+func (e *Engine) rescore(ranker Ranker, results []GrepResult, pattern string, options types.SearchOptions) {
+	query := NewRankQuery(pattern, options.CaseInsensitive)
+	query.Phrases = searchTerms(pattern, options) // Only a Terms search was split
+	explainer, _ := ranker.(Explainer)
+	for i := range results {
+		r := &results[i]
+		lineText, _ := e.indexer.GetFileLine(r.FileID, r.Line)
+		hit := RawHit{
+			FileID:   r.FileID,
+			Path:     r.Path,
+			Line:     r.Line,
+			Column:   r.Column,
+			Match:    r.Match,
+			LineText: lineText,
+		}
+		switch {
+		case !options.Explain:
+			r.Score = ranker.Score(&query, &hit)
+		case explainer != nil:
+			x := explainer.Explain(&query, &hit)
+			r.Score, r.Explanation = x.Score, &x
+		default:
+			r.Score = ranker.Score(&query, &hit)
+			r.Explanation = &ScoreExplanation{Ranker: ranker.Name(), Score: r.Score}
+		}
+	}
+}
+
+// explainDefaultScores attaches an explanation to results scored by the engine's
+// built-in scoring, which reports its total but no breakdown
+func explainDefaultScores(results []GrepResult) {
+	for i := range results {
+		if r := &results[i]; r.Explanation == nil {
+			r.Explanation = &ScoreExplanation{Ranker: defaultRankerName, Score: r.Score}
+			r.Explanation.Add("built-in score", r.Score)
+		}
+	}
+}
+
+// RankResults sorts results best first. Equal scores are ordered by path, then byte
+// offset, so the order never depends on how the search was scheduled.
+func RankResults(results []GrepResult) {
+	sort.SliceStable(results, func(i, j int) bool {
+		a, b := &results[i], &results[j]
+		if a.Score != b.Score {
+			return a.Score > b.Score
+		}
+		if a.Path != b.Path {
+			return a.Path < b.Path
+		}
+		if a.StartByte != b.StartByte {
+			return a.StartByte < b.StartByte
+		}
+		return a.Line < b.Line // Results without byte spans
+	})
+}
+
+// LimitResults ranks results with RankResults and keeps the best limit of them.
+// A limit of 0 or less keeps every result.
+func LimitResults(results []GrepResult, limit int) []GrepResult {
+	return PageResults(results, 0, limit)
+}
+
+// PageResults ranks results with RankResults and keeps ranks [offset, offset+limit).
+// A limit of 0 or less keeps every result from offset on; an offset past the end
+// keeps none.
+func PageResults(results []GrepResult, offset, limit int) []GrepResult {
+	RankResults(results)
+	if offset >= len(results) {
+		return results[:0]
+	}
+	results = results[max(offset, 0):]
+	if limit > 0 && len(results) > limit {
+		results = results[:limit]
+	}
+	return results
+}
+
+// SubstringRanker scores hits on how well the matched line contains the pattern:
+// exact-case matches beat case-folded ones, whole-word matches beat partial ones, and
+// shorter lines (where the pattern is a larger share of the text) rank higher.
+type SubstringRanker struct{}
+
+// Name implements Ranker
+func (SubstringRanker) Name() string { return "substring" }
+
+// Score implements Ranker
+func (r SubstringRanker) Score(query *RankQuery, hit *RawHit) float64 {
+	return r.score(query, hit, nil)
+}
+
+// Explain implements Explainer
+func (r SubstringRanker) Explain(query *RankQuery, hit *RawHit) ScoreExplanation {
+	x := ScoreExplanation{Ranker: r.Name()}
+	x.Score = r.score(query, hit, &x)
+	return x
+}
+
+// score computes Score, recording its components in x when x is not nil
+func (SubstringRanker) score(query *RankQuery, hit *RawHit, x *ScoreExplanation) float64 {
+	line := strings.TrimSpace(hit.LineText)
+	if line == "" || query.Pattern == "" {
+		return 0
+	}
+
+	score := 0.0
+	idx := strings.Index(line, query.Pattern)
+	if idx >= 0 {
+		score += 2
+		x.Add("exact-case match", 2)
+	} else if idx = strings.Index(strings.ToLower(line), strings.ToLower(query.Pattern)); idx >= 0 {
+		score += 1
+		x.Add("case-folded match", 1)
+	} else {
+		return 0
+	}
+
+	end := idx + len(query.Pattern)
+	if (idx == 0 || !isWordChar(line[idx-1])) && (end == len(line) || !isWordChar(line[end])) {
+		score += 1
+		x.Add("whole word", 1)
+	}
+
+	share := float64(len(query.Pattern)) / float64(len(line))
+	if x != nil {
+		x.Add("share of line", share,
+			ScoreFactor{Name: "pattern_length", Value: float64(len(query.Pattern))},
+			ScoreFactor{Name: "line_length", Value: float64(len(line))})
+	}
+	return score + share
+}
+
+// TermStatsProvider exposes the corpus statistics BM25 needs. *core.TermStatsIndex
+// implements it with frequencies gathered at index time.
+type TermStatsProvider interface {
+	TermFrequency(fileID types.FileID, term string) (freq, length int)
+	DocumentFrequency(term string) int
+	CorpusStats() (docCount int, avgLength float64)
+}
+
+// QueryTokenizer is implemented by term statistics that were not gathered with the
+// default raw tokenization. BM25 splits the query with it so query terms match the
+// indexed ones; *core.TermStatsIndex implements it.
+type QueryTokenizer interface {
+	TokenizeTerms(text string) []string
+}
+
+// Default BM25 parameters
+const (
+	DefaultBM25K1          = 1.2
+	DefaultBM25B           = 0.75
+	DefaultBM25PhraseBoost = 1.0
+)
+
+// Bm25Ranker scores hits with Okapi BM25 over the query terms of the hit's file, so
+// files where rare query terms are concentrated rank first. Hits within the same file
+// share that score; a pattern of several words or quoted phrases adds a boost for how
+// closely the hit's line holds them, so the phrase as written ranks first within a file.
+type Bm25Ranker struct {
+	Stats       TermStatsProvider
+	K1          float64
+	B           float64
+	PhraseBoost float64 // Weight of the phrase proximity boost, 0 to disable it
+}
+
+// NewBm25Ranker creates a BM25 ranker with the default parameters
+func NewBm25Ranker(stats TermStatsProvider) *Bm25Ranker {
+	return &Bm25Ranker{Stats: stats, K1: DefaultBM25K1, B: DefaultBM25B, PhraseBoost: DefaultBM25PhraseBoost}
+}
+
+// Name implements Ranker
+func (r *Bm25Ranker) Name() string { return "bm25" }
+
+// Score implements Ranker
+func (r *Bm25Ranker) Score(query *RankQuery, hit *RawHit) float64 {
+	return r.score(query, hit, nil)
+}
+
+// Explain implements Explainer. Each query term found in the hit's file is one
+// component, with its term frequency, inverse document frequency and length
+// normalization as factors.
+func (r *Bm25Ranker) Explain(query *RankQuery, hit *RawHit) ScoreExplanation {
+	x := ScoreExplanation{Ranker: r.Name()}
+	x.Score = r.score(query, hit, &x)
+	return x
+}
+
+// score computes Score, recording its components in x when x is not nil
+func (r *Bm25Ranker) score(query *RankQuery, hit *RawHit, x *ScoreExplanation) float64 {
+	if r.Stats == nil {
+		return 0
+	}
+	docCount, avgLength := r.Stats.CorpusStats()
+	if docCount == 0 || avgLength == 0 {
+		return 0
+	}
+
+	terms := query.Terms
+	if tokenizer, ok := r.Stats.(QueryTokenizer); ok {
+		terms = tokenizer.TokenizeTerms(query.Pattern)
+	}
+
+	score := 0.0
+	for _, term := range terms {
+		freq, length := r.Stats.TermFrequency(hit.FileID, term)
+		if freq == 0 {
+			continue
+		}
+		df := float64(r.Stats.DocumentFrequency(term))
+		idf := math.Log(1 + (float64(docCount)-df+0.5)/(df+0.5))
+		tf := float64(freq)
+		norm := r.K1 * (1 - r.B + r.B*float64(length)/avgLength)
+		contribution := idf * tf * (r.K1 + 1) / (tf + norm)
+		score += contribution
+		if x != nil {
+			x.Add(fmt.Sprintf("term %q", term), contribution,
+				ScoreFactor{Name: "tf", Value: tf},
+				ScoreFactor{Name: "idf", Value: idf},
+				ScoreFactor{Name: "length_norm", Value: norm},
+				ScoreFactor{Name: "doc_length", Value: float64(length)},
+				ScoreFactor{Name: "avg_doc_length", Value: avgLength})
+		}
+	}
+
+	if r.PhraseBoost > 0 && len(query.Phrases) > 0 {
+		if proximity := phraseProximity(query.Phrases, hit.LineText, query.CaseInsensitive); proximity > 0 {
+			boost := r.PhraseBoost * proximity
+			score += boost
+			if x != nil {
+				x.Add("phrase proximity", boost, ScoreFactor{Name: "proximity", Value: proximity})
+			}
+		}
+	}
+	return score
+}
+
+// SymbolProximityRanker favours hits that are symbol definitions, then hits inside
+// the body of a symbol named after the query, then hits close to any definition. With
+// Boosts the score is multiplied by the weight of where the hit lies.
+type SymbolProximityRanker struct {
+	Indexer interfaces.Indexer
+	Boosts  KindBoosts // Weights by enclosing symbol kind, nil to leave scores unweighted
+}
+
+// NewSymbolProximityRanker creates a ranker that reads symbols from indexer
+func NewSymbolProximityRanker(indexer interfaces.Indexer) *SymbolProximityRanker {
+	return &SymbolProximityRanker{Indexer: indexer}
+}
+
+// Name implements Ranker
+func (r *SymbolProximityRanker) Name() string { return "symbol-proximity" }
+
+// Score implements Ranker
+func (r *SymbolProximityRanker) Score(query *RankQuery, hit *RawHit) float64 {
+	return r.score(query, hit, nil)
+}
+
+// Explain implements Explainer. The score is that of the best-placed symbol, so the
+// explanation has a single component naming it.
+func (r *SymbolProximityRanker) Explain(query *RankQuery, hit *RawHit) ScoreExplanation {
+	x := ScoreExplanation{Ranker: r.Name()}
+	x.Score = r.score(query, hit, &x)
+	return x
+}
+
+// score computes Score, recording the best symbol's component in x when x is not nil
+func (r *SymbolProximityRanker) score(query *RankQuery, hit *RawHit, x *ScoreExplanation) float64 {
+	if r.Indexer == nil {
+		return 0
+	}
+	symbols := r.Indexer.GetFileSymbols(hit.FileID)
+	if len(symbols) == 0 {
+		return 0
+	}
+
+	pattern := strings.ToLower(query.Pattern)
+	best := 0.0
+	var bestSym *types.Symbol
+	for i := range symbols {
+		sym := &symbols[i]
+		named := pattern != "" && strings.Contains(strings.ToLower(sym.Name), pattern)
+		endLine := sym.EndLine
+		if endLine < sym.Line {
+			endLine = sym.Line
+		}
+
+		var score float64
+		switch {
+		case sym.Line == hit.Line && named:
+			score = 4 // The definition of the symbol being searched for
+		case sym.Line == hit.Line:
+			score = 3 // Some other definition on the matched line
+		case named && hit.Line > sym.Line && hit.Line <= endLine:
+			score = 2 // Inside the body of a matching symbol
+		default:
+			distance := abs(hit.Line - sym.Line)
+			score = 1 / float64(1+distance)
+		}
+		if score > best {
+			best = score
+			bestSym = sym
+		}
+	}
+	boost, kind := r.Boosts.weight(symbols, hit)
+	if x != nil && bestSym != nil {
+		factors := []ScoreFactor{
+			{Name: "symbol_line", Value: float64(bestSym.Line)},
+			{Name: "hit_line", Value: float64(hit.Line)},
+		}
+		reason := proximityReason(bestSym, hit.Line, best)
+		if kind != "" {
+			factors = append(factors, ScoreFactor{Name: "kind_boost", Value: boost})
+			reason += ", weighted as " + kind
+		}
+		x.Add(reason, best*boost, factors...)
+	}
+	return best * boost
+}
+
+// proximityReason describes which SymbolProximityRanker case produced score
+func proximityReason(sym *types.Symbol, line int, score float64) string {
+	switch score {
+	case 4:
+		return fmt.Sprintf("definition of matching symbol %s", sym.Name)
+	case 3:
+		return fmt.Sprintf("definition of %s on the matched line", sym.Name)
+	case 2:
+		return fmt.Sprintf("inside the body of matching symbol %s", sym.Name)
+	}
+	return fmt.Sprintf("%d lines from the definition of %s", abs(line-sym.Line), sym.Name)
+}
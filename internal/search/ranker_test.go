@@ -0,0 +1,219 @@
+package search_test
+
+import (
+	"context"
+	"math/rand"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// setupRankerIndex indexes files and returns the master index alongside an engine on it
+func setupRankerIndex(t *testing.T, files map[string]string) (*indexing.MasterIndex, *search.Engine) {
+	t.Helper()
+	tempDir := t.TempDir()
+	for name, code := range files {
+		require.NoError(t, os.WriteFile(filepath.Join(tempDir, name), []byte(code), 0644))
+	}
+
+	cfg := &config.Config{
+		Version: 1,
+		Project: config.Project{Root: tempDir, Name: "ranker-test"},
+		Index: config.Index{
+			MaxFileSize:    types.DefaultMaxFileSize,
+			MaxTotalSizeMB: int64(types.DefaultMaxTotalSizeMB),
+			MaxFileCount:   types.DefaultMaxFileCount,
+		},
+		Performance: config.Performance{MaxMemoryMB: types.DefaultMaxMemoryMB},
+	}
+
+	gi := indexing.NewMasterIndex(cfg)
+	t.Cleanup(func() { gi.Close() })
+	require.NoError(t, gi.IndexDirectory(context.Background(), tempDir))
+	return gi, search.NewEngine(gi)
+}
+
+func TestSubstringRanker(t *testing.T) {
+	ranker := search.SubstringRanker{}
+	query := search.NewRankQuery("token", false)
+
+	exactWord := ranker.Score(&query, &search.RawHit{LineText: "return token"})
+	partial := ranker.Score(&query, &search.RawHit{LineText: "return tokenizer"})
+	folded := ranker.Score(&query, &search.RawHit{LineText: "return Token"})
+	longLine := ranker.Score(&query, &search.RawHit{LineText: "return token + someOtherRatherLongExpression(withArguments)"})
+	missing := ranker.Score(&query, &search.RawHit{LineText: "return nothing"})
+
+	assert.Greater(t, exactWord, partial, "whole-word matches beat partial ones")
+	assert.Greater(t, exactWord, folded, "exact case beats case-folded")
+	assert.Greater(t, exactWord, longLine, "shorter lines rank higher")
+	assert.Zero(t, missing)
+}
+
+func TestBm25Ranker_PrefersConcentratedTerms(t *testing.T) {
+	gi, engine := setupRankerIndex(t, map[string]string{
+		"focused.go": "package auth\n\nfunc refresh(token string) string {\n\treturn token + token\n}\n",
+		"diluted.go": "package misc\n\n// token\nfunc a() {}\nfunc b() {}\nfunc c() {}\nfunc d() {}\nfunc e() {}\nfunc f() {}\nfunc g() {}\n",
+	})
+
+	stats := gi.GetTermStatsIndex()
+	require.NotNil(t, stats)
+	docs, avg := stats.CorpusStats()
+	assert.Equal(t, 2, docs, "term stats are gathered by IndexDirectory")
+	assert.Greater(t, avg, 0.0)
+
+	ranker := search.NewBm25Ranker(stats)
+	engine.SetRanker(ranker)
+	assert.Equal(t, ranker, engine.Ranker())
+
+	results := engine.SearchWithOptions("token", nil, types.SearchOptions{})
+	require.NotEmpty(t, results)
+	assert.Equal(t, "focused.go", filepath.Base(results[0].Path))
+	for i := 1; i < len(results); i++ {
+		assert.GreaterOrEqual(t, results[i-1].Score, results[i].Score, "results must be sorted by ranker score")
+	}
+}
+
+func TestBm25Ranker_IdentifierTokenizer(t *testing.T) {
+	files := map[types.FileID]string{
+		1: "type UserService struct{}\nfunc get_user() {}\n",
+		2: "var userService = newService()\n",
+		3: "func render() {}\n",
+	}
+	raw := core.NewTermStatsIndex()
+	identifier := core.NewTermStatsIndexWithTokenizer(core.TermTokenizerIdentifier)
+	for fileID, content := range files {
+		raw.IndexFile(fileID, []byte(content))
+		identifier.IndexFile(fileID, []byte(content))
+	}
+
+	user := search.NewRankQuery("user", true)
+	assert.Zero(t, search.NewBm25Ranker(raw).Score(&user, &search.RawHit{FileID: 1}), "raw terms keep identifiers whole")
+	assert.Greater(t, search.NewBm25Ranker(identifier).Score(&user, &search.RawHit{FileID: 1}), 0.0)
+
+	exact := search.NewRankQuery("UserService", false)
+	ranker := search.NewBm25Ranker(identifier)
+	assert.Greater(t, ranker.Score(&exact, &search.RawHit{FileID: 1}), ranker.Score(&exact, &search.RawHit{FileID: 2}),
+		"the identifier in its original casing outranks one sharing only its parts")
+}
+
+func TestSymbolProximityRanker_DefinitionFirst(t *testing.T) {
+	code := `package main
+
+// callers mention parseConfig before it is defined
+func main() {
+	parseConfig()
+}
+
+func parseConfig() {
+	println("parseConfig")
+}
+`
+	gi, engine := setupRankerIndex(t, map[string]string{"main.go": code})
+	engine.SetRanker(search.NewSymbolProximityRanker(gi))
+
+	results := engine.SearchWithOptions("parseConfig", nil, types.SearchOptions{})
+	require.GreaterOrEqual(t, len(results), 3)
+	assert.Equal(t, 8, results[0].Line, "the definition outranks the call and the comment")
+	assert.Equal(t, 9, results[1].Line, "a use inside the matching symbol comes next")
+}
+
+func TestLimitResults_StableTieBreak(t *testing.T) {
+	results := []search.GrepResult{
+		{Path: "b.go", StartByte: 10, Score: 5},
+		{Path: "a.go", StartByte: 40, Score: 5},
+		{Path: "c.go", StartByte: 0, Score: 9},
+		{Path: "a.go", StartByte: 12, Score: 5},
+	}
+	for range 10 {
+		shuffled := slices.Clone(results)
+		rand.Shuffle(len(shuffled), func(i, j int) { shuffled[i], shuffled[j] = shuffled[j], shuffled[i] })
+
+		limited := search.LimitResults(shuffled, 3)
+		require.Len(t, limited, 3)
+		assert.Equal(t, "c.go", limited[0].Path, "the best score comes first")
+		assert.Equal(t, [2]string{"a.go", "a.go"}, [2]string{limited[1].Path, limited[2].Path}, "ties break by path")
+		assert.Equal(t, 12, limited[1].StartByte, "then by byte offset")
+	}
+
+	assert.Len(t, search.LimitResults(slices.Clone(results), 0), len(results), "0 keeps every result")
+}
+
+func TestPageResults(t *testing.T) {
+	results := []search.GrepResult{
+		{Path: "a.go", Score: 4},
+		{Path: "b.go", Score: 9},
+		{Path: "c.go", Score: 7},
+		{Path: "d.go", Score: 1},
+		{Path: "e.go", Score: 5},
+	}
+	paths := func(page []search.GrepResult) []string {
+		var got []string
+		for _, r := range page {
+			got = append(got, r.Path)
+		}
+		return got
+	}
+
+	assert.Equal(t, []string{"b.go", "c.go"}, paths(search.PageResults(slices.Clone(results), 0, 2)))
+	assert.Equal(t, []string{"e.go", "a.go"}, paths(search.PageResults(slices.Clone(results), 2, 2)), "pages follow the ranking")
+	assert.Equal(t, []string{"d.go"}, paths(search.PageResults(slices.Clone(results), 4, 2)), "the last page may be short")
+	assert.Equal(t, []string{"a.go", "d.go"}, paths(search.PageResults(slices.Clone(results), 3, 0)), "0 keeps the rest")
+	assert.Empty(t, search.PageResults(slices.Clone(results), 10, 2), "an offset past the end returns nothing")
+}
+
+func TestExplain_MatchesScore(t *testing.T) {
+	gi, engine := setupRankerIndex(t, map[string]string{
+		"focused.go": "package auth\n\nfunc refresh(token string) string {\n\treturn token + token\n}\n",
+		"other.go":   "package misc\n\n// Token is unused\nfunc a() {}\n",
+	})
+
+	substring := search.SubstringRanker{}
+	query := search.NewRankQuery("token", false)
+	hit := search.RawHit{LineText: "return token + token"}
+	x := substring.Explain(&query, &hit)
+	assert.Equal(t, "substring", x.Ranker)
+	assert.InDelta(t, substring.Score(&query, &hit), x.Score, 1e-9)
+	var sum float64
+	for _, c := range x.Components {
+		sum += c.Value
+	}
+	assert.InDelta(t, x.Score, sum, 1e-9, "substring components add up to the score")
+
+	bm25 := search.NewBm25Ranker(gi.GetTermStatsIndex())
+	engine.SetRanker(bm25)
+	results := engine.SearchWithOptions("token", nil, types.SearchOptions{Explain: true})
+	require.NotEmpty(t, results)
+	for _, r := range results {
+		require.NotNil(t, r.Explanation, "%s:%d", r.Path, r.Line)
+		assert.Equal(t, "bm25", r.Explanation.Ranker)
+		assert.InDelta(t, r.Score, r.Explanation.Score, 1e-9)
+	}
+	top := results[0].Explanation
+	require.NotEmpty(t, top.Components)
+	var factors []string
+	for _, f := range top.Components[0].Factors {
+		factors = append(factors, f.Name)
+	}
+	assert.Subset(t, factors, []string{"tf", "idf", "length_norm"})
+
+	engine.SetRanker(search.NewSymbolProximityRanker(gi))
+	results = engine.SearchWithOptions("token", nil, types.SearchOptions{Explain: true})
+	require.NotEmpty(t, results)
+	assert.Equal(t, "symbol-proximity", results[0].Explanation.Ranker)
+
+	results = engine.SearchWithOptions("token", nil, types.SearchOptions{})
+	require.NotEmpty(t, results)
+	for _, r := range results {
+		assert.Nil(t, r.Explanation, "explanations are only built on request")
+	}
+}
@@ -0,0 +1,76 @@
+package search
+
+import (
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// Reference is a whole-word occurrence of a symbol name. Definition marks the name at a
+// declaration the extractor recorded for that symbol.
+type Reference struct {
+	GrepResult
+	Definition bool `json:"definition,omitempty"`
+}
+
+// FindReferences returns the usage sites of the symbol called name: every case-sensitive
+// whole-word occurrence except the names at its declarations, ordered by path and
+// offset. With includeDef the declarations are kept and marked as such. Path filters
+// and MaxResults are taken from options; the second applies after definitions are
+// removed.
+func (e *Engine) FindReferences(name string, options types.SearchOptions, includeDef bool) []Reference {
+	if name == "" {
+		return nil
+	}
+	opts := types.SearchOptions{
+		WordBoundary:   true,
+		IncludePattern: options.IncludePattern,
+		ExcludePattern: options.ExcludePattern,
+		NoDedup:        options.NoDedup,
+	}
+	results := e.SearchWithOptions(name, nil, opts)
+
+	defs := e.definitionSpans(name, results)
+	refs := make([]Reference, 0, len(results))
+	for _, r := range results {
+		isDef := defs[dedupKey{r.Path, r.StartByte, r.EndByte}]
+		if isDef && !includeDef {
+			continue
+		}
+		refs = append(refs, Reference{GrepResult: r, Definition: isDef})
+	}
+
+	sort.SliceStable(refs, func(i, j int) bool {
+		if refs[i].Path != refs[j].Path {
+			return refs[i].Path < refs[j].Path
+		}
+		return refs[i].StartByte < refs[j].StartByte
+	})
+	if options.MaxResults > 0 && len(refs) > options.MaxResults {
+		refs = refs[:options.MaxResults]
+	}
+	return refs
+}
+
+// definitionSpans returns the spans of name at its declarations in the files results
+// came from
+func (e *Engine) definitionSpans(name string, results []GrepResult) map[dedupKey]bool {
+	spans := make(map[dedupKey]bool)
+	seen := make(map[types.FileID]bool)
+	for _, r := range results {
+		if seen[r.FileID] {
+			continue
+		}
+		seen[r.FileID] = true
+
+		offsets, _ := e.indexer.GetFileLineOffsets(r.FileID)
+		for _, sym := range e.indexer.GetFileSymbols(r.FileID) {
+			if sym.Name != name {
+				continue
+			}
+			_, start := e.symbolNameSpan(r.FileID, offsets, sym)
+			spans[dedupKey{r.Path, start, start + len(name)}] = true
+		}
+	}
+	return spans
+}
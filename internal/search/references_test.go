@@ -0,0 +1,28 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestFindReferences(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"service.go": "package service\n\nfunc findUser(id string) string {\n\treturn id\n}\n\nfunc getUser(id string) string {\n\treturn findUser(id)\n}\n\nfunc findUserByName() {}\n",
+	})
+	defer cleanup()
+
+	refs := engine.FindReferences("findUser", types.SearchOptions{}, false)
+	require.Len(t, refs, 1, "only the call; the definition and findUserByName are left out")
+	assert.Equal(t, 8, refs[0].Line)
+	assert.False(t, refs[0].Definition)
+
+	withDef := engine.FindReferences("findUser", types.SearchOptions{}, true)
+	require.Len(t, withDef, 2)
+	assert.Equal(t, 3, withDef[0].Line, "ordered by offset, definition first")
+	assert.True(t, withDef[0].Definition)
+	assert.False(t, withDef[1].Definition)
+}
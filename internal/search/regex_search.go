@@ -0,0 +1,232 @@
+package search
+
+import (
+	"fmt"
+	"regexp"
+	"regexp/syntax"
+	"sort"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// SearchRegex runs pattern as a regular expression. With options.SymbolsOnly the regex
+// is matched against extracted symbol names, so `^(get|set)_\w+` finds symbols whose
+// names start with get_ or set_; with options.DocsOnly it is matched against their doc
+// comments; otherwise it searches file content like SearchWithOptions with UseRegex set.
+//
+// An invalid pattern is reported as an error instead of producing an empty result.
+func (e *Engine) SearchRegex(pattern string, options types.SearchOptions) ([]GrepResult, error) {
+	options.UseRegex = true
+	options = resolveCase(pattern, options)
+
+	if (options.SymbolsOnly || options.DocsOnly) && !spanRestricted(options) {
+		re, err := compileSearchRegex(pattern, options)
+		if err != nil {
+			return nil, err
+		}
+		if options.SymbolsOnly {
+			return e.finishResults(e.searchSymbolNames(re, options), pattern, options), nil
+		}
+		return e.finishResults(e.searchDocComments(re, options), pattern, options), nil
+	}
+	if !plainContentSearch(options) {
+		// These modes compile the regexes they search with themselves
+		if _, err := compileSearchRegex(pattern, options); err != nil {
+			return nil, err
+		}
+		return e.SearchWithOptions(pattern, nil, options), nil
+	}
+	re, err := compileContentRegex(pattern, options)
+	if err != nil {
+		return nil, err
+	}
+	return e.finishResults(e.searchPattern(pattern, nil, options, re), pattern, options), nil
+}
+
+// plainContentSearch reports whether options search file content for the pattern as
+// written, the searches the regex from compileContentRegex is run for unchanged
+func plainContentSearch(options types.SearchOptions) bool {
+	return !options.Query && options.Fuzzy == "" && !spanRestricted(options) && !options.SymbolsOnly &&
+		!options.DocsOnly && len(options.Patterns) == 0
+}
+
+// maxRegexInsts caps the compiled size of a search regex. Go's regexp runs in time
+// linear in the input, so nested quantifiers like (a+)+ cannot backtrack, but counted
+// repeats such as (abcdef){1000} expand into programs that make every byte expensive.
+const maxRegexInsts = 10000
+
+// compileSearchRegex builds the single regex used for a search. Literal patterns are
+// quoted so symbol-name matching can share the regex path. Patterns that compile to
+// more than maxRegexInsts instructions are rejected.
+func compileSearchRegex(pattern string, options types.SearchOptions) (*regexp.Regexp, error) {
+	expr := searchRegexExpr(pattern, options)
+	if options.CaseInsensitive {
+		expr = "(?i)" + expr
+	}
+	return compileLimited(pattern, expr)
+}
+
+// compileContentRegex builds the regex a search of file content runs, with the flags
+// the hybrid regex engine searches files with: ^ and $ match at line boundaries, and
+// with options.Multiline . matches newlines. contentRegexExpr gives its uncompiled form.
+func compileContentRegex(pattern string, options types.SearchOptions) (*regexp.Regexp, error) {
+	expr := contentRegexExpr(pattern, options)
+	flags := "(?m)"
+	if options.CaseInsensitive {
+		flags = "(?mi)"
+	}
+	return compileLimited(pattern, flags+expr)
+}
+
+// searchRegexExpr returns pattern as a regex expression, quoted unless options.UseRegex
+// and wrapped in word boundaries with options.WordBoundary
+func searchRegexExpr(pattern string, options types.SearchOptions) string {
+	expr := pattern
+	if !options.UseRegex {
+		expr = regexp.QuoteMeta(pattern)
+	}
+	if options.WordBoundary {
+		expr = wordRegex(expr)
+	}
+	return expr
+}
+
+// contentRegexExpr returns the expression a content search hands the hybrid regex
+// engine for pattern, which adds its line and case flags itself
+func contentRegexExpr(pattern string, options types.SearchOptions) string {
+	expr := searchRegexExpr(pattern, options)
+	if options.Multiline {
+		expr = "(?s)" + expr
+	}
+	return expr
+}
+
+// compileLimited compiles expr, the regex built for pattern, rejecting it when it
+// compiles to more than maxRegexInsts instructions
+func compileLimited(pattern, expr string) (*regexp.Regexp, error) {
+	re, err := regexp.Compile(expr)
+	if err != nil {
+		return nil, fmt.Errorf("invalid regex %q: %w", pattern, err)
+	}
+	if n := regexSize(expr); n > maxRegexInsts {
+		return nil, fmt.Errorf("regex %q is too large: %d instructions, limit %d", pattern, n, maxRegexInsts)
+	}
+	return re, nil
+}
+
+// regexSize returns the number of instructions expr compiles to. expr must already
+// have compiled.
+func regexSize(expr string) int {
+	parsed, err := syntax.Parse(expr, syntax.Perl)
+	if err != nil {
+		return 0
+	}
+	prog, err := syntax.Compile(parsed.Simplify())
+	if err != nil {
+		return 0
+	}
+	return len(prog.Inst)
+}
+
+// symbolNameSpan locates sym's name on its declaration line, returning its 0-based byte
+// column within the line and its byte offset in the file
+func (e *Engine) symbolNameSpan(fileID types.FileID, offsets []uint32, sym types.Symbol) (column, start int) {
+	if sym.Column > 0 {
+		column = sym.Column - 1
+	}
+	if lineText, ok := e.indexer.GetFileLine(fileID, sym.Line); ok {
+		from := min(column, len(lineText))
+		if idx := strings.Index(lineText[from:], sym.Name); idx >= 0 {
+			column = from + idx
+		} else if idx := strings.Index(lineText, sym.Name); idx >= 0 {
+			column = idx
+		}
+	}
+
+	start = column
+	if sym.Line >= 1 && sym.Line <= len(offsets) {
+		start += int(offsets[sym.Line-1])
+	}
+	return column, start
+}
+
+// wordRegex wraps a regex so it only matches whole words (grep -w)
+func wordRegex(expr string) string {
+	return `\b(?:` + expr + `)\b`
+}
+
+// searchSymbolNames matches re against every indexed symbol name. The regex is compiled
+// once by the caller and reused for all files. Results point at the name on the
+// declaration line and are ordered by path and position.
+func (e *Engine) searchSymbolNames(re *regexp.Regexp, options types.SearchOptions) []GrepResult {
+	candidates := e.getAllFileIDs()
+	candidates = e.filterIncludedFiles(candidates, options.IncludePattern)
+	candidates = e.filterExcludedFiles(candidates, options.ExcludePattern)
+	candidates = e.filterGlobFiles(candidates, options.IncludeGlobs, options.ExcludeGlobs)
+
+	var results []GrepResult
+	for _, fileID := range candidates {
+		if ResultCapReached(options, len(results)) || stopAtDeadline(options) {
+			break
+		}
+		symbols := e.indexer.GetFileSymbols(fileID)
+		if len(symbols) == 0 {
+			continue
+		}
+		path := e.indexer.GetFilePath(fileID)
+		offsets, _ := e.indexer.GetFileLineOffsets(fileID)
+
+		for _, sym := range symbols {
+			if sym.Name == "" || !re.MatchString(sym.Name) {
+				continue
+			}
+			if len(options.SymbolTypes) > 0 && !contains(options.SymbolTypes, sym.Type.String()) {
+				continue
+			}
+			results = append(results, e.symbolNameResult(fileID, path, offsets, sym, options))
+		}
+	}
+
+	return sortSymbolResults(results, options)
+}
+
+// sortSymbolResults orders symbol hits by path and position and applies MaxResults
+func sortSymbolResults(results []GrepResult, options types.SearchOptions) []GrepResult {
+	sort.SliceStable(results, func(i, j int) bool {
+		a, b := results[i], results[j]
+		if a.Path != b.Path {
+			return a.Path < b.Path
+		}
+		if a.Line != b.Line {
+			return a.Line < b.Line
+		}
+		return a.Column < b.Column
+	})
+
+	if options.MaxResults > 0 && len(results) > options.MaxResults {
+		results = results[:options.MaxResults]
+	}
+	return results
+}
+
+// symbolNameResult converts a matched symbol to a result spanning its name. Column is a
+// 0-based byte offset within the line, matching content search results.
+func (e *Engine) symbolNameResult(fileID types.FileID, path string, offsets []uint32, sym types.Symbol, options types.SearchOptions) GrepResult {
+	column, start := e.symbolNameSpan(fileID, offsets, sym)
+	context := locationContext(sym.Line)
+	if !options.LocationsOnly {
+		context = e.extractSimpleContextWithOptions(fileID, sym.Line, options)
+	}
+	return GrepResult{
+		FileID:    fileID,
+		Path:      path,
+		Line:      sym.Line,
+		Column:    column,
+		StartByte: start,
+		EndByte:   start + len(sym.Name),
+		Match:     sym.Name,
+		Score:     1.0,
+		Context:   context,
+	}
+}
@@ -0,0 +1,142 @@
+package search_test
+
+import (
+	"strings"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchRegex_SymbolsOnly(t *testing.T) {
+	code := `package main
+
+func get_user() {}
+func set_user() {}
+func reset_user() {}
+func delete_user() {}
+
+// get_user and set_user are only mentioned here
+var forget_value = 1
+`
+	engine, _, cleanup := setupTestEngine(t, map[string]string{"users.go": code})
+	defer cleanup()
+
+	results, err := engine.SearchRegex(`^(get|set)_\w+`, types.SearchOptions{SymbolsOnly: true})
+	require.NoError(t, err)
+
+	var names []string
+	for _, r := range results {
+		names = append(names, r.Match)
+	}
+	assert.Equal(t, []string{"get_user", "set_user"}, names, "only symbol names anchored at ^ should match")
+
+	// Results point at the name on the declaration line
+	require.Len(t, results, 2)
+	assert.Equal(t, 3, results[0].Line)
+	assert.Equal(t, len("func "), results[0].Column)
+	assert.Equal(t, len("get_user"), results[0].EndByte-results[0].StartByte)
+	assert.Equal(t, "get_user", code[results[0].StartByte:results[0].EndByte])
+}
+
+func TestSearchRegex_ContentAndErrors(t *testing.T) {
+	code := `package main
+
+func get_user() {}
+func reset_user() {}
+`
+	engine, _, cleanup := setupTestEngine(t, map[string]string{"users.go": code})
+	defer cleanup()
+
+	// Without SymbolsOnly the regex runs over file content
+	results, err := engine.SearchRegex(`_user\(`, types.SearchOptions{})
+	require.NoError(t, err)
+	assert.Len(t, results, 2)
+
+	_, err = engine.SearchRegex(`(get|set`, types.SearchOptions{SymbolsOnly: true})
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), "invalid regex")
+
+	_, err = engine.SearchRegex(`[unclosed`, types.SearchOptions{})
+	assert.Error(t, err, "content mode must reject invalid patterns too")
+
+	// SmartCase applies to symbol names
+	results, err = engine.SearchRegex(`^GET_`, types.SearchOptions{SymbolsOnly: true, SmartCase: true})
+	require.NoError(t, err)
+	assert.Empty(t, results)
+	results, err = engine.SearchRegex(`^get_`, types.SearchOptions{SymbolsOnly: true, SmartCase: true})
+	require.NoError(t, err)
+	assert.Len(t, results, 1)
+}
+
+func TestSearchRegex_Limits(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"a.go": "package a\n\nvar s = \"" + strings.Repeat("a", 100000) + "b\"\n",
+		"b.go": "package b\n\nfunc aaa() {}\n",
+	})
+	defer cleanup()
+
+	// Nested quantifiers run in linear time rather than backtracking
+	start := time.Now()
+	_, err := engine.SearchRegex(`(a+)+$`, types.SearchOptions{})
+	require.NoError(t, err)
+	assert.Less(t, time.Since(start), 5*time.Second)
+
+	_, err = engine.SearchRegex(`(abcdefghijklmnop){1000}`, types.SearchOptions{})
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), "too large")
+
+	// A passed deadline stops before the first file, keeping what was found: nothing
+	expired := types.SearchOptions{Deadline: time.Now().Add(-time.Second)}
+	var timedOut atomic.Bool
+	expired.TimedOut = &timedOut
+	results, err := engine.SearchRegex(`a+a`, expired)
+	require.NoError(t, err)
+	assert.Empty(t, results)
+	assert.True(t, search.DeadlineExceeded(expired))
+	assert.True(t, timedOut.Load(), "the search reports that it stopped early")
+	assert.Empty(t, engine.SearchWithOptions("aaa", nil, expired))
+
+	timedOut.Store(false)
+	results, err = engine.SearchRegex(`a+a`, types.SearchOptions{Deadline: time.Now().Add(time.Minute), TimedOut: &timedOut})
+	require.NoError(t, err)
+	assert.NotEmpty(t, results, "a deadline that has not passed changes nothing")
+	assert.False(t, timedOut.Load())
+}
+
+// sleepyRanker takes long enough over each hit to run a search past its deadline
+type sleepyRanker struct{}
+
+func (sleepyRanker) Name() string { return "sleepy" }
+
+func (sleepyRanker) Score(*search.RankQuery, *search.RawHit) float64 {
+	time.Sleep(150 * time.Millisecond)
+	return 1
+}
+
+func TestSearch_DeadlinePassedWhileRanking(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"a.go": "package a
+
+func aaa() {}
+",
+		"b.go": "package b
+
+func aaa() {}
+",
+	})
+	defer cleanup()
+	engine.SetRanker(sleepyRanker{})
+
+	var timedOut atomic.Bool
+	options := types.SearchOptions{Deadline: time.Now().Add(100 * time.Millisecond), TimedOut: &timedOut}
+	results := engine.SearchWithOptions("aaa", nil, options)
+	assert.Len(t, results, 2)
+	assert.True(t, search.DeadlineExceeded(options), "ranking ran past the deadline")
+	assert.False(t, timedOut.Load(), "every file was searched, so the search did not time out")
+}
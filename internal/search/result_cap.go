@@ -0,0 +1,50 @@
+package search
+
+import (
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// ResultCapReached reports whether n hits are more than options.ResultCap. Searches
+// check it between files like DeadlineExceeded, so a broad pattern stops soon after the
+// cap is passed instead of collecting every hit; stopping one hit past it, not at it,
+// tells a search with exactly ResultCap hits from one that had more. Count searches are
+// never capped, since a partial count would be wrong rather than incomplete.
+func ResultCapReached(options types.SearchOptions, n int) bool {
+	return options.ResultCap > 0 && !options.CountPerFile && n > options.ResultCap
+}
+
+// capResults drops the hits past options.ResultCap, setting options.CapReached when
+// there were any; the search loops stop between files, so the last file searched may
+// have added more
+func capResults(results []GrepResult, options types.SearchOptions) []GrepResult {
+	if ResultCapReached(options, len(results)) {
+		noteCapReached(options)
+		return results[:options.ResultCap]
+	}
+	return results
+}
+
+// capEmit wraps a SearchStream callback so the stream ends once options.ResultCap hits
+// have been emitted. The stream goes on to the next hit to tell whether there was one,
+// setting options.CapReached instead of emitting it.
+func capEmit(emit func(GrepResult) bool, options types.SearchOptions) func(GrepResult) bool {
+	if options.ResultCap <= 0 || options.CountPerFile {
+		return emit
+	}
+	sent := 0
+	return func(r GrepResult) bool {
+		if sent == options.ResultCap {
+			noteCapReached(options)
+			return false
+		}
+		sent++
+		return emit(r)
+	}
+}
+
+// noteCapReached sets options.CapReached, when the caller asked for it
+func noteCapReached(options types.SearchOptions) {
+	if options.CapReached != nil {
+		options.CapReached.Store(true)
+	}
+}
@@ -0,0 +1,60 @@
+package search_test
+
+import (
+	"fmt"
+	"sync/atomic"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestResultCap(t *testing.T) {
+	files := make(map[string]string)
+	for i := range 5 {
+		files[fmt.Sprintf("f%d.go", i)] = "package f\n\n// needle\n// needle\n"
+	}
+	engine, fileIDs, cleanup := setupTestEngine(t, files)
+	defer cleanup()
+
+	assert.Len(t, engine.SearchWithOptions("needle", fileIDs, types.SearchOptions{}), 10)
+
+	var capReached atomic.Bool
+	capped := types.SearchOptions{ResultCap: 3, CapReached: &capReached}
+	results := engine.SearchWithOptions("needle", fileIDs, capped)
+	assert.Len(t, results, 3, "hits past the cap are dropped")
+	assert.True(t, capReached.Load())
+	assert.True(t, search.ResultCapReached(capped, 4))
+	assert.False(t, search.ResultCapReached(capped, 3), "exactly ResultCap hits are not truncated")
+
+	var exactReached atomic.Bool
+	exact := types.SearchOptions{ResultCap: 10, CapReached: &exactReached}
+	assert.Len(t, engine.SearchWithOptions("needle", fileIDs, exact), 10)
+	assert.False(t, exactReached.Load(), "a search finding exactly ResultCap hits dropped none")
+
+	regex := types.SearchOptions{ResultCap: 3, UseRegex: true}
+	assert.Len(t, engine.SearchWithOptions("nee+dle", fileIDs, regex), 3)
+
+	counts := engine.SearchWithOptions("needle", fileIDs, types.SearchOptions{ResultCap: 3, CountPerFile: true})
+	assert.Len(t, counts, 5, "counts are never capped, since a partial count would be wrong")
+
+	capReached.Store(false)
+	streamed := 0
+	require.NoError(t, engine.SearchStream("needle", fileIDs, capped, func(search.GrepResult) bool {
+		streamed++
+		return true
+	}))
+	assert.Equal(t, 3, streamed, "the stream ends at the cap")
+	assert.True(t, capReached.Load())
+
+	streamed = 0
+	require.NoError(t, engine.SearchStream("needle", fileIDs, exact, func(search.GrepResult) bool {
+		streamed++
+		return true
+	}))
+	assert.Equal(t, 10, streamed)
+	assert.False(t, exactReached.Load())
+}
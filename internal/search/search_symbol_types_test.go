@@ -1,8 +1,11 @@
 package search_test
 
 import (
+	"os"
 	"testing"
 
+	"github.com/stretchr/testify/assert"
+
 	"github.com/standardbeagle/lci/internal/types"
 )
 
@@ -222,3 +225,29 @@ type MyType struct{}
 	t.Logf("Case-sensitive: %d results, Case-insensitive: %d results",
 		len(caseSensitive), len(caseInsensitive))
 }
+
+// TestSymbolTypes_RustKinds tests --kind style filtering on the rust-sample fixture
+func TestSymbolTypes_RustKinds(t *testing.T) {
+	code, err := os.ReadFile("../../tests/search-comparison/fixtures/rust-sample/src/main.rs")
+	if err != nil {
+		t.Skip("Test fixture not found")
+	}
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"main.rs": string(code)})
+	defer cleanup()
+
+	lines := func(pattern string, kinds ...string) []int {
+		var got []int
+		for _, r := range engine.SearchWithOptions(pattern, fileIDs, types.SearchOptions{SymbolTypes: kinds}) {
+			got = append(got, r.Line)
+		}
+		return got
+	}
+
+	// Only the trait declaration, not the D: Database bounds on the struct and impl
+	assert.Equal(t, []int{11}, lines("Database", "trait"))
+
+	methods := lines("user", "method")
+	assert.Subset(t, methods, []int{12, 13, 28, 33}, "trait and impl methods")
+	assert.NotContains(t, methods, 44, "handle_user_request is a free function")
+	assert.Contains(t, lines("user", "function"), 44)
+}
@@ -142,6 +142,10 @@ func (zasf *ZeroAllocSemanticFilter) isExportedSymbolZeroAlloc(symbol *types.Enh
 	if symbol.IsExported {
 		return true
 	}
+	// A visibility the parser recorded is authoritative
+	if symbol.Visibility.Access != types.AccessUnknown {
+		return false
+	}
 	// For most languages, a symbol is exported if it starts with uppercase
 	if len(symbol.Name) == 0 {
 		return false
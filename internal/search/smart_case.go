@@ -0,0 +1,71 @@
+package search
+
+import (
+	"unicode"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// SmartCaseInsensitive reports whether a smart-case search should ignore case: it does
+// unless one of the patterns contains an uppercase letter. In regex patterns escapes
+// such as \W, \S, \p{Lu} or \x{1F} are syntax rather than text and don't count.
+func SmartCaseInsensitive(regex bool, patterns ...string) bool {
+	for _, p := range patterns {
+		if hasUpper(p, regex) {
+			return false
+		}
+	}
+	return true
+}
+
+// resolveCase applies SmartCase to options, setting CaseInsensitive from the patterns
+func resolveCase(pattern string, options types.SearchOptions) types.SearchOptions {
+	if !options.SmartCase {
+		return options
+	}
+	patterns := options.Patterns
+	if len(patterns) == 0 {
+		patterns = []string{pattern}
+	}
+	options.CaseInsensitive = SmartCaseInsensitive(options.UseRegex, patterns...)
+	return options
+}
+
+func hasUpper(pattern string, regex bool) bool {
+	for i := 0; i < len(pattern); {
+		r, size := utf8.DecodeRuneInString(pattern[i:])
+		i += size
+		if regex && r == '\\' && i < len(pattern) {
+			i = skipRegexEscape(pattern, i)
+			continue
+		}
+		if unicode.IsUpper(r) {
+			return true
+		}
+	}
+	return false
+}
+
+// skipRegexEscape returns the index just past the escape whose backslash ends at i
+func skipRegexEscape(pattern string, i int) int {
+	c := pattern[i]
+	i++
+	switch c {
+	case 'p', 'P', 'x':
+		if i < len(pattern) && pattern[i] == '{' {
+			for i < len(pattern) && pattern[i] != '}' {
+				i++
+			}
+			return i + 1
+		}
+		if c == 'x' {
+			return min(i+2, len(pattern)) // \xFF
+		}
+		return min(i+1, len(pattern)) // \pL
+	}
+	if c >= utf8.RuneSelf {
+		return i - 1 // An escaped non-ASCII rune is literal text
+	}
+	return i
+}
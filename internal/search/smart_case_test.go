@@ -0,0 +1,82 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSmartCaseInsensitive(t *testing.T) {
+	tests := []struct {
+		patterns []string
+		regex    bool
+		want     bool
+	}{
+		{[]string{"user"}, false, true},
+		{[]string{"User"}, false, false},
+		{[]string{"get_user", "find"}, false, true},
+		{[]string{"get_user", "Find"}, false, false},
+		{[]string{"états"}, false, true},
+		{[]string{"États"}, false, false},
+		{[]string{`user\W+id`}, true, true},
+		{[]string{`\p{Lu}ser`}, true, true},
+		{[]string{`\x{1F}user\S`}, true, true},
+		{[]string{`\bUser`}, true, false},
+		{[]string{`user\W+id`}, false, false}, // literal text: the W is a real uppercase letter
+	}
+
+	for _, tt := range tests {
+		assert.Equal(t, tt.want, search.SmartCaseInsensitive(tt.regex, tt.patterns...), "%q regex=%v", tt.patterns, tt.regex)
+	}
+}
+
+func TestSearchWithOptions_SmartCase(t *testing.T) {
+	code := `package main
+
+type User struct{}
+
+func findUser() *User { return nil }
+
+var label = "the user record"
+var user = findUser()
+`
+	engine, _, cleanup := setupTestEngine(t, map[string]string{"user.go": code})
+	defer cleanup()
+
+	lines := func(results []search.GrepResult) []int {
+		seen := make(map[int]bool)
+		var out []int
+		for _, r := range results {
+			if !seen[r.Line] {
+				seen[r.Line] = true
+				out = append(out, r.Line)
+			}
+		}
+		return out
+	}
+
+	smart := types.SearchOptions{SmartCase: true}
+
+	// All-lowercase pattern ignores case
+	assert.ElementsMatch(t, []int{3, 5, 7, 8}, lines(engine.SearchWithOptions("user", nil, smart)))
+	// Any uppercase letter makes it case-sensitive
+	assert.ElementsMatch(t, []int{3, 5, 8}, lines(engine.SearchWithOptions("User", nil, smart)))
+
+	// Regex mode follows the same rule
+	regex := smart
+	regex.UseRegex = true
+	assert.ElementsMatch(t, []int{3, 5, 7, 8}, lines(engine.SearchWithOptions(`user\b`, nil, regex)))
+	assert.ElementsMatch(t, []int{3, 5, 8}, lines(engine.SearchWithOptions(`User\b`, nil, regex)))
+
+	// Without SmartCase the default stays case-sensitive, and CaseInsensitive forces folding
+	assert.ElementsMatch(t, []int{7, 8}, lines(engine.SearchWithOptions("user", nil, types.SearchOptions{})))
+	assert.ElementsMatch(t, []int{3, 5, 7, 8}, lines(engine.SearchWithOptions("USER", nil, types.SearchOptions{CaseInsensitive: true})))
+
+	// Fuzzy mode too
+	assert.NotEmpty(t, engine.SearchFuzzyWithOptions("finduser", 0, smart))
+	assert.Empty(t, engine.SearchFuzzyWithOptions("FindUser", 0, smart))
+	assert.NotEmpty(t, engine.SearchFuzzyWithOptions("findUser", 0, smart))
+}
@@ -0,0 +1,69 @@
+package search
+
+import "strings"
+
+// attachSnippetContext fills ContextBefore/ContextAfter with up to n lines on either side
+// of each result's matched lines. The window is clamped to the file, so matches on the
+// first or last lines simply get fewer context lines.
+func (e *Engine) attachSnippetContext(results []GrepResult, n int) {
+	for i := range results {
+		r := &results[i]
+		if r.Line < 1 {
+			continue
+		}
+
+		content, ok := e.indexer.GetFileContent(r.FileID)
+		if !ok {
+			continue
+		}
+		offsets, ok := e.indexer.GetFileLineOffsets(r.FileID)
+		if !ok || len(offsets) == 0 {
+			continue
+		}
+
+		r.ContextBefore, r.ContextAfter = snippetContextLines(content, offsets, r.Line, max(r.Line, r.EndLine), n)
+	}
+}
+
+// snippetContextLines returns the lines surrounding the 1-based lines line..endLine, at
+// most n on each side
+func snippetContextLines(content []byte, offsets []uint32, line, endLine, n int) (before, after []string) {
+	lineCount := len(offsets)
+	// A trailing newline produces an empty final "line" that isn't part of the file
+	if int(offsets[lineCount-1]) >= len(content) {
+		lineCount--
+	}
+	if line > lineCount {
+		return nil, nil
+	}
+
+	first := line - n
+	if first < 1 {
+		first = 1
+	}
+	last := endLine + n
+	if last > lineCount {
+		last = lineCount
+	}
+
+	for l := first; l < line; l++ {
+		before = append(before, snippetLine(content, offsets, l))
+	}
+	for l := endLine + 1; l <= last; l++ {
+		after = append(after, snippetLine(content, offsets, l))
+	}
+	return before, after
+}
+
+// snippetLine returns a 1-based line without its line terminator
+func snippetLine(content []byte, offsets []uint32, line int) string {
+	start := int(offsets[line-1])
+	end := len(content)
+	if line < len(offsets) {
+		end = int(offsets[line])
+	}
+	if start > end || end > len(content) {
+		return ""
+	}
+	return strings.TrimRight(string(content[start:end]), "\r\n")
+}
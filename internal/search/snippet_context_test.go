@@ -0,0 +1,93 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchWithOptions_SnippetContext(t *testing.T) {
+	code := "fn authenticate() {\n" +
+		"    let token = read_token();\n" +
+		"    verify(token)\n" +
+		"}\n" +
+		"\n" +
+		"fn trailer() { authenticate() }\n"
+
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.rs": code})
+	defer cleanup()
+
+	results := engine.SearchWithOptions("verify", fileIDs, types.SearchOptions{SnippetContext: 1})
+	require.Len(t, results, 1)
+	assert.Equal(t, 3, results[0].Line)
+	assert.Equal(t, []string{"    let token = read_token();"}, results[0].ContextBefore)
+	assert.Equal(t, []string{"}"}, results[0].ContextAfter)
+
+	// Windows larger than the file are clamped at both ends
+	results = engine.SearchWithOptions("authenticate", fileIDs, types.SearchOptions{SnippetContext: 10})
+	require.Len(t, results, 2)
+	for _, r := range results {
+		switch r.Line {
+		case 1:
+			assert.Empty(t, r.ContextBefore, "nothing precedes the first line")
+			assert.Len(t, r.ContextAfter, 5)
+		case 6:
+			assert.Len(t, r.ContextBefore, 5)
+			assert.Empty(t, r.ContextAfter, "the trailing newline is not a line")
+		default:
+			t.Fatalf("unexpected match line %d", r.Line)
+		}
+	}
+
+	// No context unless requested
+	results = engine.SearchWithOptions("verify", fileIDs, types.SearchOptions{})
+	require.Len(t, results, 1)
+	assert.Nil(t, results[0].ContextBefore)
+	assert.Nil(t, results[0].ContextAfter)
+}
+
+func TestSearchWithOptions_LocationsOnly(t *testing.T) {
+	code := "fn authenticate() {\n" +
+		"    verify(token)\n" +
+		"}\n" +
+		"\n" +
+		"fn trailer() { authenticate() }\n"
+
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.rs": code})
+	defer cleanup()
+
+	options := types.SearchOptions{MergeFileResults: true, EnsureCompleteStmt: true, SnippetContext: 2, LocationsOnly: true}
+	results := engine.SearchWithOptions("authenticate", fileIDs, options)
+	require.Len(t, results, 2, "hits in one file are not merged")
+	lines := map[int]int{}
+	for _, r := range results {
+		lines[r.Line] = r.Column
+		assert.Empty(t, r.Context.Lines, "no snippet is extracted")
+		assert.Equal(t, []int{r.Line}, r.Context.MatchedLines)
+		assert.Nil(t, r.ContextBefore)
+		assert.Nil(t, r.ContextAfter)
+	}
+	assert.Equal(t, map[int]int{1: 3, 5: 15}, lines)
+}
+
+func TestSearchWithOptions_LocationsOnlyListsMergedHits(t *testing.T) {
+	code := "fn authenticate() {\n" +
+		"    verify(token);\n" +
+		"    refresh(token);\n" +
+		"}\n"
+
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.rs": code})
+	defer cleanup()
+
+	options := types.SearchOptions{MergeFileResults: true, EnsureCompleteStmt: true}
+	merged := engine.SearchWithOptions("token", fileIDs, options)
+	require.Len(t, merged, 1, "the default output merges hits in one function")
+
+	options.LocationsOnly = true
+	locations := engine.SearchWithOptions("token", fileIDs, options)
+	require.Len(t, locations, 2, "--locations lists every hit")
+	assert.ElementsMatch(t, []int{2, 3}, []int{locations[0].Line, locations[1].Line})
+}
@@ -0,0 +1,119 @@
+package search
+
+import (
+	"regexp"
+	"slices"
+	"sort"
+
+	"github.com/standardbeagle/lci/internal/query"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// SearchStream searches like SearchWithOptions but hands each hit to emit as soon as
+// the file it is in has been searched, instead of collecting every hit first. Files
+// are visited in discovery (file ID) order and hits within a file in offset order.
+// There is no global ranking: each hit keeps its own score, rescored by the engine's
+// ranker when one is set, but a better hit in a later file is never moved ahead.
+//
+// Search stops as soon as emit returns false, so a caller that wants N hits can stop
+// after the Nth without searching the remaining files; ResultCap ends the stream at
+// the first hit past the cap, which is not emitted. Multi-pattern, multi-term, symbol, query, --within, --diff, count-per-file
+// and files-only searches need every file before producing output; they run to
+// completion and are emitted afterwards.
+//
+// An invalid regex or query is reported as an error before anything is emitted.
+func (e *Engine) SearchStream(pattern string, candidates []types.FileID, options types.SearchOptions, emit func(GrepResult) bool) error {
+	options = resolveCase(pattern, options)
+	pattern, options = ignoreWhitespace(pattern, options)
+	emit = capEmit(emit, options)
+	if options.Query {
+		if _, err := query.Parse(pattern); err != nil {
+			return err
+		}
+	}
+	var re *regexp.Regexp
+	if options.UseRegex && len(options.Patterns) == 0 {
+		var err error
+		if re, err = compileContentRegex(pattern, options); err != nil {
+			return err
+		}
+	}
+
+	terms := searchTerms(pattern, options)
+	if !streamable(options) || needsTermSearch(terms) {
+		for _, r := range e.SearchWithOptions(pattern, candidates, options) {
+			if !emit(r) {
+				break
+			}
+		}
+		return nil
+	}
+
+	searched := pattern // Ranking still sees the quotes
+	if len(terms) == 1 {
+		searched = terms[0].Text
+	}
+	if searched == "" {
+		return nil
+	}
+	candidates = e.filterDeletedFiles(e.prepareCandidates(searched, candidates, options))
+	candidates = slices.Clone(candidates)
+	slices.Sort(candidates)
+
+	if options.UseRegex && isLiteralPattern(searched) {
+		options.UseRegex = false
+	}
+	patternBytes := []byte(searched)
+	for _, fileID := range candidates {
+		if stopAtDeadline(options) {
+			return nil
+		}
+		var results []GrepResult
+		if options.UseRegex && !options.InvertMatch {
+			results = e.searchWithHybridRegex(searched, re, []types.FileID{fileID}, options)
+		} else {
+			e.processFile(fileID, patternBytes, searched, options, 0, &results)
+		}
+		for _, r := range e.finishFileResults(results, pattern, options) {
+			if !emit(r) {
+				return nil
+			}
+		}
+	}
+	return nil
+}
+
+// streamable reports whether a search can produce its hits one file at a time
+func streamable(options types.SearchOptions) bool {
+	return len(options.Patterns) <= 1 && !options.SymbolsOnly && !options.DocsOnly && !options.Query && !spanRestricted(options) &&
+		!options.CountPerFile && !options.FilesOnly
+}
+
+// finishFileResults is finishResults for the hits of one file, ordered by position
+// rather than score
+func (e *Engine) finishFileResults(results []GrepResult, pattern string, options types.SearchOptions) []GrepResult {
+	if options.ExportedOnly {
+		results = e.exportedResults(results)
+	}
+	tagMatcher(results, matcherFor(pattern, options))
+	if !options.NoDedup {
+		results = dedupResults(results)
+	}
+	if ranker := e.rankerFor(options); ranker != nil {
+		e.rescore(ranker, results, pattern, options)
+	} else if options.Explain {
+		explainDefaultScores(results)
+	}
+	results = e.mergeNearbyResults(results, options.MergeGap, options.MergeScore)
+	sort.SliceStable(results, func(i, j int) bool {
+		if results[i].StartByte != results[j].StartByte {
+			return results[i].StartByte < results[j].StartByte
+		}
+		return results[i].Line < results[j].Line
+	})
+	if options.SnippetContext > 0 {
+		e.attachSnippetContext(results, options.SnippetContext)
+	}
+	e.noteEncodings(results)
+	return results
+}
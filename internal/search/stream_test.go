@@ -0,0 +1,61 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchStream(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"a.go": "package a\n\nfunc tokenA() {}\n\n// token again\n",
+		"b.go": "package b\n\nfunc tokenB() {}\n",
+		"c.go": "package c\n\nvar token = 1\n",
+	})
+	defer cleanup()
+
+	var streamed []search.GrepResult
+	err := engine.SearchStream("token", nil, types.SearchOptions{}, func(r search.GrepResult) bool {
+		streamed = append(streamed, r)
+		return true
+	})
+	require.NoError(t, err)
+	require.Len(t, streamed, len(engine.SearchWithOptions("token", nil, types.SearchOptions{})),
+		"streaming finds the same hits as a collected search")
+
+	for i := 1; i < len(streamed); i++ {
+		prev, cur := streamed[i-1], streamed[i]
+		assert.LessOrEqual(t, prev.FileID, cur.FileID, "files are emitted in discovery order")
+		if prev.FileID == cur.FileID {
+			assert.Less(t, prev.StartByte, cur.StartByte, "hits within a file are in offset order")
+		}
+	}
+
+	var first []search.GrepResult
+	err = engine.SearchStream("token", nil, types.SearchOptions{}, func(r search.GrepResult) bool {
+		first = append(first, r)
+		return len(first) < 2
+	})
+	require.NoError(t, err)
+	assert.Len(t, first, 2, "returning false stops the search")
+	assert.Equal(t, streamed[:2], first)
+}
+
+func TestSearchStreamInvalidRegex(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"a.go": "package a\n",
+	})
+	defer cleanup()
+
+	called := false
+	err := engine.SearchStream("(unclosed", nil, types.SearchOptions{UseRegex: true}, func(search.GrepResult) bool {
+		called = true
+		return true
+	})
+	assert.Error(t, err)
+	assert.False(t, called)
+}
@@ -0,0 +1,40 @@
+package search
+
+import (
+	"strings"
+	"unicode/utf8"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// maxSuggestions caps the names SuggestSymbols returns, so a novel pattern that happens
+// to sit near many short names does not bury the "no results" message
+const maxSuggestions = 3
+
+// SuggestSymbols returns up to maxSuggestions distinct symbol names within
+// DefaultFuzzyDistance of pattern, closest first, for a search that found nothing: a
+// typo such as get_usr suggests get_user. Regex, query and several-word patterns are not
+// names, and patterns under 3 characters are near too many names, so they get none.
+// Neither does a pattern that names a symbol exactly, since then the search's filters
+// rather than a typo excluded it.
+func (e *Engine) SuggestSymbols(pattern string, options types.SearchOptions) []string {
+	if options.UseRegex || options.Query || len(options.Patterns) > 1 ||
+		utf8.RuneCountInString(pattern) < 3 || strings.ContainsAny(pattern, " \t\"") {
+		return nil
+	}
+
+	var names []string
+	seen := make(map[string]bool)
+	for _, r := range e.SearchFuzzy(pattern, -1) {
+		name := r.Symbol.Name
+		if name == pattern {
+			return nil
+		}
+		if seen[name] || len(names) == maxSuggestions {
+			continue
+		}
+		seen[name] = true
+		names = append(names, name)
+	}
+	return names
+}
@@ -0,0 +1,26 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSuggestSymbols(t *testing.T) {
+	engine, _, cleanup := setupTestEngine(t, map[string]string{
+		"user.go": "package user\n\nfunc get_user() {}\n\nfunc save_user() {}\n",
+		"foo.go":  "package foo\n\nfunc fooa() {}\nfunc foob() {}\nfunc fooc() {}\nfunc food() {}\n",
+	})
+	defer cleanup()
+
+	assert.Equal(t, []string{"get_user"}, engine.SuggestSymbols("get_usr", types.SearchOptions{}))
+	assert.Equal(t, []string{"fooa", "foob", "fooc"}, engine.SuggestSymbols("foo", types.SearchOptions{}),
+		"only the closest few are suggested")
+
+	assert.Empty(t, engine.SuggestSymbols("completely_novel", types.SearchOptions{}))
+	assert.Empty(t, engine.SuggestSymbols("get_user", types.SearchOptions{}), "an exact name was excluded by filters, not mistyped")
+	assert.Empty(t, engine.SuggestSymbols("get_usr", types.SearchOptions{UseRegex: true}))
+	assert.Empty(t, engine.SuggestSymbols("get usr", types.SearchOptions{}))
+}
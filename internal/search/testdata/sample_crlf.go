@@ -0,0 +1,9 @@
+package sample
+
+// Greeting is saved with CRLF line endings
+func Greeting() string {
+	return "hello"
+}
+
+// Farewell follows an LF-only line
+func Farewell() string { return "bye" }
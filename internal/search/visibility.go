@@ -0,0 +1,49 @@
+package search
+
+import (
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// exportedResults keeps the hits inside an exported symbol, for SearchOptions.ExportedOnly.
+// A hit is judged by the innermost symbol enclosing it, so a line in a private helper's
+// body is dropped even if the helper sits in a pub impl; hits outside every symbol are
+// dropped too.
+func (e *Engine) exportedResults(results []GrepResult) []GrepResult {
+	files := make(map[types.FileID]*types.FileInfo)
+	kept := results[:0]
+	for _, r := range results {
+		offset := e.resultOffset(r)
+		if offset < 0 {
+			continue
+		}
+		sym := e.indexer.GetSymbolAtOffset(r.FileID, offset)
+		if sym == nil {
+			continue
+		}
+		fileInfo, ok := files[r.FileID]
+		if !ok {
+			content, _ := e.indexer.GetFileContent(r.FileID)
+			fileInfo = &types.FileInfo{
+				ID:              r.FileID,
+				Path:            r.Path,
+				Content:         content,
+				EnhancedSymbols: e.indexer.GetFileEnhancedSymbols(r.FileID),
+			}
+			files[r.FileID] = fileInfo
+		}
+		if enhanced := enhancedSymbolFor(fileInfo, sym); enhanced != nil && e.isExportedEnhancedSymbol(enhanced, fileInfo) {
+			kept = append(kept, r)
+		}
+	}
+	return kept
+}
+
+// enhancedSymbolFor finds the enhanced form of sym among the symbols of fileInfo
+func enhancedSymbolFor(fileInfo *types.FileInfo, sym *types.Symbol) *types.EnhancedSymbol {
+	for _, enhanced := range fileInfo.EnhancedSymbols {
+		if enhanced.Line == sym.Line && enhanced.Column == sym.Column && enhanced.Name == sym.Name {
+			return enhanced
+		}
+	}
+	return nil
+}
@@ -0,0 +1,67 @@
+package search
+
+import (
+	"regexp"
+	"strings"
+	"unicode"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// horizontalSpace is what a whitespace run in an IgnoreWhitespace pattern matches. Line
+// breaks are left out so every hit stays on one line.
+const horizontalSpace = `[ \t]+`
+
+// ignoreWhitespace rewrites literal patterns for options.IgnoreWhitespace as regexes in
+// which each run of whitespace matches any run of spaces and tabs, so "invalid
+// credentials" finds "invalid\t\tcredentials". The content is not normalized: the regex
+// runs on the original bytes, so reported spans and matched text are those of the file.
+// Regex and query patterns are left as written. With options.Terms only a lone quoted
+// phrase is rewritten, since separate words already match whatever lies between them.
+func ignoreWhitespace(pattern string, options types.SearchOptions) (string, types.SearchOptions) {
+	if !options.IgnoreWhitespace || options.UseRegex || options.Query {
+		return pattern, options
+	}
+	terms := searchTerms(pattern, options)
+	if needsTermSearch(terms) {
+		return pattern, options
+	}
+	if len(terms) == 1 {
+		pattern = terms[0].Text
+	}
+	options.UseRegex = true
+	if len(options.Patterns) > 0 {
+		patterns := make([]string, len(options.Patterns))
+		for i, p := range options.Patterns {
+			patterns[i] = whitespaceRegex(p)
+		}
+		options.Patterns = patterns
+	}
+	return whitespaceRegex(pattern), options
+}
+
+// whitespaceRegex quotes literal and replaces each of its whitespace runs with
+// horizontalSpace
+func whitespaceRegex(literal string) string {
+	var b strings.Builder
+	inSpace := false
+	start := 0
+	for i, r := range literal {
+		if unicode.IsSpace(r) {
+			if !inSpace {
+				b.WriteString(regexp.QuoteMeta(literal[start:i]))
+				b.WriteString(horizontalSpace)
+				inSpace = true
+			}
+			continue
+		}
+		if inSpace {
+			start = i
+			inSpace = false
+		}
+	}
+	if !inSpace {
+		b.WriteString(regexp.QuoteMeta(literal[start:]))
+	}
+	return b.String()
+}
@@ -0,0 +1,37 @@
+package search_test
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchIgnoreWhitespace(t *testing.T) {
+	source := "package auth\n\nfunc check() {\n\treturn errors.New(\"invalid\t\tcredentials\")\n}\n\n// invalid    credentials\n// invalidcredentials\n"
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.go": source})
+	defer cleanup()
+
+	search := func(pattern string, options types.SearchOptions) []string {
+		var matches []string
+		for _, r := range engine.SearchWithOptions(pattern, fileIDs, options) {
+			require.Equal(t, r.Match, source[r.StartByte:r.EndByte], "spans point at the original bytes")
+			matches = append(matches, r.Match)
+		}
+		return matches
+	}
+
+	assert.Empty(t, search("invalid credentials", types.SearchOptions{}), "spacing must match exactly by default")
+
+	matches := search("invalid credentials", types.SearchOptions{IgnoreWhitespace: true})
+	assert.ElementsMatch(t, []string{"invalid\t\tcredentials", "invalid    credentials"}, matches,
+		"tabs and runs of spaces both match a single space, but missing whitespace does not")
+
+	matches = search("invalid \t credentials", types.SearchOptions{IgnoreWhitespace: true, CaseInsensitive: true})
+	assert.Len(t, matches, 2, "whitespace runs in the pattern collapse too")
+
+	assert.Equal(t, []string{"return errors.New("}, search("return errors.New(", types.SearchOptions{IgnoreWhitespace: true}),
+		"regex metacharacters in the literal are quoted")
+}
@@ -0,0 +1,198 @@
+package search
+
+import (
+	"path/filepath"
+	"slices"
+	"strings"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// byteSpan is the [start, end) byte range of a symbol named by SearchOptions.Within or
+// of lines listed in SearchOptions.ChangedLines
+type byteSpan struct {
+	start, end int
+}
+
+func (s byteSpan) contains(pos int) bool {
+	return pos >= s.start && pos < s.end
+}
+
+// withinTarget is a parsed Within value such as "auth.rs:AuthService::authenticate"
+type withinTarget struct {
+	file   string // Path suffix, empty for any file
+	parent string // Enclosing type, empty for any
+	name   string
+}
+
+// parseWithin splits a Within value into an optional "file:" qualifier, an optional
+// "Parent::" or "Parent." qualifier and the symbol name
+func parseWithin(within string) withinTarget {
+	var target withinTarget
+	rest := strings.TrimSpace(within)
+	for i := 0; i < len(rest); i++ {
+		if rest[i] != ':' {
+			continue
+		}
+		if i+1 < len(rest) && rest[i+1] == ':' {
+			i++ // Skip the "::" path separator
+			continue
+		}
+		target.file, rest = rest[:i], rest[i+1:]
+		break
+	}
+
+	if i := strings.LastIndex(rest, "::"); i >= 0 {
+		target.parent, target.name = rest[:i], rest[i+2:]
+	} else if i := strings.LastIndex(rest, "."); i >= 0 {
+		target.parent, target.name = rest[:i], rest[i+1:]
+	} else {
+		target.name = rest
+	}
+	return target
+}
+
+// matchesPath reports whether path is the qualifier's file, compared by path suffix so
+// "auth.rs" and "src/auth.rs" both select /repo/src/auth.rs
+func (w withinTarget) matchesPath(path string) bool {
+	return w.file == "" || pathHasSuffix(path, w.file)
+}
+
+// pathHasSuffix reports whether file names path, in full or as its trailing path
+// components
+func pathHasSuffix(path, file string) bool {
+	path, file = filepath.ToSlash(path), filepath.ToSlash(file)
+	return path == file || strings.HasSuffix(path, "/"+strings.TrimPrefix(file, "/"))
+}
+
+// resolveWithin returns the spans of every symbol the Within value names, by file.
+// A name shared by symbols in several files selects all of them unless the value
+// carries a file qualifier.
+func (e *Engine) resolveWithin(within string, candidates []types.FileID) map[types.FileID][]byteSpan {
+	target := parseWithin(within)
+	if target.name == "" {
+		return nil
+	}
+	if len(candidates) == 0 {
+		candidates = e.getAllFileIDs()
+	}
+
+	spans := make(map[types.FileID][]byteSpan)
+	for _, fileID := range candidates {
+		if !target.matchesPath(e.indexer.GetFilePath(fileID)) {
+			continue
+		}
+		for _, sym := range e.indexer.GetFileSymbols(fileID) {
+			if sym.Name != target.name || sym.EndByte <= sym.StartByte {
+				continue
+			}
+			if target.parent != "" && sym.Parent != target.parent {
+				continue
+			}
+			spans[fileID] = append(spans[fileID], byteSpan{sym.StartByte, sym.EndByte})
+		}
+	}
+	return spans
+}
+
+// spanRestricted reports whether Within or ChangedLines limits where options match
+func spanRestricted(options types.SearchOptions) bool {
+	return options.Within != "" || options.ChangedLines != nil
+}
+
+// restrictionSpans returns the spans Within and ChangedLines allow matches in, by file.
+// With both set only their overlap is searched.
+func (e *Engine) restrictionSpans(candidates []types.FileID, options types.SearchOptions) map[types.FileID][]byteSpan {
+	var spans map[types.FileID][]byteSpan
+	if options.Within != "" {
+		spans = e.resolveWithin(options.Within, candidates)
+	}
+	if options.ChangedLines != nil {
+		changed := e.resolveLineRanges(options.ChangedLines, candidates)
+		if spans == nil {
+			spans = changed
+		} else {
+			spans = intersectSpans(spans, changed)
+		}
+	}
+	return spans
+}
+
+// searchWithin runs the search on the files holding the Within symbols or changed lines
+// and keeps only matches inside their spans. Matches are filtered one per line before
+// any merging, count or files-only collapsing, so those modes only see matches inside
+// the spans.
+func (e *Engine) searchWithin(pattern string, candidates []types.FileID, options types.SearchOptions) []GrepResult {
+	spans := e.restrictionSpans(candidates, options)
+	if len(spans) == 0 {
+		return nil
+	}
+	files := make([]types.FileID, 0, len(spans))
+	for fileID := range spans {
+		files = append(files, fileID)
+	}
+	slices.Sort(files)
+
+	inner := options
+	inner.Within = ""
+	inner.ChangedLines = nil
+	inner.MergeFileResults = false
+	inner.CountPerFile = false
+	inner.FilesOnly = false
+	inner.MaxResults = 0
+
+	var results []GrepResult
+	if inner.SymbolsOnly || inner.DocsOnly {
+		re, err := compileSearchRegex(pattern, inner)
+		if err != nil {
+			return nil
+		}
+		if inner.SymbolsOnly {
+			results = e.searchSymbolNames(re, inner)
+		} else {
+			results = e.searchDocComments(re, inner)
+		}
+	} else {
+		results = e.searchWithOptions(pattern, files, inner)
+	}
+
+	kept := results[:0]
+	for _, r := range results {
+		if inSpans(spans[r.FileID], e.resultOffset(r)) {
+			kept = append(kept, r)
+		}
+	}
+
+	switch {
+	case options.CountPerFile:
+		return e.countPerFileResults(kept)
+	case options.FilesOnly:
+		return filesOnlyResults(kept)
+	}
+	if options.MaxResults > 0 && len(kept) > options.MaxResults {
+		kept = kept[:options.MaxResults]
+	}
+	return kept
+}
+
+// resultOffset is the byte position a result is tested at: its match start, or the
+// start of its line for results without a match span (e.g. inverted matches)
+func (e *Engine) resultOffset(r GrepResult) int {
+	if r.EndByte > r.StartByte {
+		return r.StartByte
+	}
+	offsets, ok := e.indexer.GetFileLineOffsets(r.FileID)
+	if !ok || r.Line < 1 || r.Line > len(offsets) {
+		return -1
+	}
+	return int(offsets[r.Line-1])
+}
+
+func inSpans(spans []byteSpan, pos int) bool {
+	for _, s := range spans {
+		if s.contains(pos) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,78 @@
+package search_test
+
+import (
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+const withinAuthCode = `pub struct AuthService {
+    token: String,
+}
+
+impl AuthService {
+    pub fn authenticate(&self, token: &str) -> bool {
+        token == self.token
+    }
+
+    pub fn refresh(&self, token: &str) -> String {
+        token.to_string()
+    }
+}
+
+pub struct ApiClient;
+
+impl ApiClient {
+    pub fn authenticate(&self, token: &str) -> bool {
+        !token.is_empty()
+    }
+}
+`
+
+func TestSearchWithin(t *testing.T) {
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{
+		"auth.rs":  withinAuthCode,
+		"other.rs": "pub fn authenticate(token: &str) -> bool {\n    token.len() > 3\n}\n",
+	})
+	defer cleanup()
+
+	lines := func(within string) map[string][]int {
+		got := make(map[string][]int)
+		for _, r := range engine.SearchWithOptions("token", fileIDs, types.SearchOptions{Within: within}) {
+			name := filepath.Base(r.Path)
+			got[name] = append(got[name], r.Line)
+		}
+		for _, l := range got {
+			slices.Sort(l)
+		}
+		return got
+	}
+
+	// Parent-qualified: only AuthService::authenticate, not refresh or ApiClient
+	assert.Equal(t, map[string][]int{"auth.rs": {6, 7}}, lines("AuthService::authenticate"))
+
+	// A bare name searches every symbol with that name, across files
+	assert.Equal(t, map[string][]int{"auth.rs": {6, 7, 18, 19}, "other.rs": {1, 2}}, lines("authenticate"))
+
+	// A file qualifier narrows to one file
+	assert.Equal(t, map[string][]int{"other.rs": {1, 2}}, lines("other.rs:authenticate"))
+
+	assert.Empty(t, lines("AuthService::missing"))
+}
+
+func TestSearchWithin_CountPerFile(t *testing.T) {
+	engine, fileIDs, cleanup := setupTestEngine(t, map[string]string{"auth.rs": withinAuthCode})
+	defer cleanup()
+
+	results := engine.SearchWithOptions("token", fileIDs, types.SearchOptions{
+		Within:       "AuthService::authenticate",
+		CountPerFile: true,
+	})
+	if assert.Len(t, results, 1) {
+		assert.Equal(t, 2, results[0].FileMatchCount, "only matches inside the span are counted")
+	}
+}
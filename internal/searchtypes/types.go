@@ -1,22 +1,85 @@
 package searchtypes
 
 import (
+	"time"
+
 	"github.com/standardbeagle/lci/internal/idcodec"
 	"github.com/standardbeagle/lci/internal/types"
 )
 
-// GrepResult represents a basic search result (grep-like, fast)
+// GrepResult represents a basic search result (grep-like, fast).
+// StartByte/EndByte are the canonical match location; Line is 1-based and Column is
+// the 0-based byte column of the match start. Use types.LineIndex to derive other
+// positions from the byte span.
 type GrepResult struct {
-	FileID         types.FileID     `json:"file_id"`
-	Path           string           `json:"path"`
-	Line           int              `json:"line"`
-	Column         int              `json:"column"`
-	Match          string           `json:"match"`
-	Context        ExtractedContext `json:"context"`
-	Score          float64          `json:"score"`
-	FileMatchCount int              `json:"file_match_count,omitempty"` // Total matches in this file (for CountPerFile mode)
+	FileID         types.FileID      `json:"file_id"`
+	Path           string            `json:"path"`
+	Root           string            `json:"root,omitempty"` // Root the result came from when several roots are searched at once
+	Line           int               `json:"line"`
+	EndLine        int               `json:"end_line,omitempty"` // Last line of a match spanning several lines (0 when it ends on Line)
+	Column         int               `json:"column"`
+	StartByte      int               `json:"start_byte,omitempty"` // Byte offset of the match within the file
+	EndByte        int               `json:"end_byte,omitempty"`   // Byte offset one past the end of the match
+	Match          string            `json:"match"`
+	Context        ExtractedContext  `json:"context"`
+	ContextBefore  []string          `json:"context_before,omitempty"` // Lines preceding the match (SnippetContext option)
+	ContextAfter   []string          `json:"context_after,omitempty"`  // Lines following the match (SnippetContext option)
+	Score          float64           `json:"score"`
+	FileMatchCount int               `json:"file_match_count,omitempty"` // Total matches in this file (for CountPerFile mode)
+	MatchedBy      []Matcher         `json:"matched_by,omitempty"`       // Matchers that found this span, several when duplicates were merged
+	Explanation    *ScoreExplanation `json:"explanation,omitempty"`      // How Score was computed (Explain option)
+	TermSpans      [][2]int          `json:"term_spans,omitempty"`       // File byte spans of every term occurrence on the line, when it holds several
+	Blame          *Blame            `json:"blame,omitempty"`            // Who last touched the matched line, for displayed hits in git repositories (--blame)
+	Encoding       string            `json:"encoding,omitempty"`         // Encoding the file was decoded from before indexing, when not UTF-8; spans are in the decoded content
+}
+
+// Blame is the commit that last touched a result's line
+type Blame struct {
+	Commit string    `json:"commit"` // Full hash; all zeros for a line not committed yet
+	Author string    `json:"author"`
+	Date   time.Time `json:"date"` // When the change was authored
+}
+
+// ScoreExplanation breaks a result's score into the parts that produced it
+type ScoreExplanation struct {
+	Ranker     string           `json:"ranker"`
+	Score      float64          `json:"score"`
+	Components []ScoreComponent `json:"components,omitempty"` // Parts that add up to Score
+}
+
+// ScoreComponent is one additive part of a score and the factors it was computed from
+type ScoreComponent struct {
+	Name    string        `json:"name"`
+	Value   float64       `json:"value"`
+	Factors []ScoreFactor `json:"factors,omitempty"`
 }
 
+// ScoreFactor is a named input of a ScoreComponent, such as BM25's term frequency
+type ScoreFactor struct {
+	Name  string  `json:"name"`
+	Value float64 `json:"value"`
+}
+
+// Add records a component. It does nothing on a nil explanation, so scorers can report
+// components unconditionally; guard calls that pass factors, which allocate.
+func (x *ScoreExplanation) Add(name string, value float64, factors ...ScoreFactor) {
+	if x == nil {
+		return
+	}
+	x.Components = append(x.Components, ScoreComponent{Name: name, Value: value, Factors: factors})
+}
+
+// Matcher names the matching strategy that produced a result
+type Matcher string
+
+const (
+	MatcherSubstring Matcher = "substring" // Literal match against file content
+	MatcherRegex     Matcher = "regex"     // Regular expression match against file content
+	MatcherSymbol    Matcher = "symbol"    // Match against extracted symbol names
+	MatcherDoc       Matcher = "doc"       // Match against symbols' leading doc comments
+	MatcherFuzzy     Matcher = "fuzzy"     // Fuzzy match against symbol names
+)
+
 // ExtractedContext represents the context around a search match
 type ExtractedContext struct {
 	Lines      []string `json:"lines"`
@@ -244,6 +244,13 @@ func (ns *NameSplitter) splitCamelCase(name string) string {
 	return strings.Join(words, " ")
 }
 
+// Normalize returns a language-neutral form of a symbol name so that the same
+// concept compares equal across naming conventions and export rules:
+// GetUser, getUser, get_user and GET_USER all normalize to "get_user".
+func (ns *NameSplitter) Normalize(name string) string {
+	return strings.Join(ns.Split(name), "_")
+}
+
 // SplitToSet splits a name and returns unique words as a set
 func (ns *NameSplitter) SplitToSet(name string) map[string]bool {
 	words := ns.Split(name)
@@ -106,6 +106,33 @@ func TestNameSplitter(t *testing.T) {
 	}
 }
 
+func TestNameSplitterNormalize(t *testing.T) {
+	splitter := NewNameSplitter()
+
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{"GetUser", "get_user"},
+		{"getUser", "get_user"},
+		{"get_user", "get_user"},
+		{"GET_USER", "get_user"},
+		{"HandleUserRequest", "handle_user_request"},
+		{"handle_user_request", "handle_user_request"},
+		{"NewUserService", "new_user_service"},
+		{"UserService", "user_service"},
+		{"", ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			if result := splitter.Normalize(tt.input); result != tt.expected {
+				t.Errorf("Normalize(%q) = %q, want %q", tt.input, result, tt.expected)
+			}
+		})
+	}
+}
+
 func BenchmarkNameSplitter(b *testing.B) {
 	inputs := []string{
 		"getUserName",
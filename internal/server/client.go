@@ -94,12 +94,27 @@ func (c *Client) GetStatus() (*IndexStatus, error) {
 
 // Search performs a search query on the remote index
 func (c *Client) Search(pattern string, options types.SearchOptions, maxResults int) ([]searchtypes.Result, error) {
-	req := SearchRequest{
+	results, _, err := c.SearchWithTotal(pattern, options, maxResults)
+	return results, err
+}
+
+// SearchWithTotal is Search that also returns the number of hits found before the
+// server kept the best maxResults
+func (c *Client) SearchWithTotal(pattern string, options types.SearchOptions, maxResults int) ([]searchtypes.Result, int, error) {
+	searchResp, err := c.SearchWithRequest(SearchRequest{
 		Pattern:    pattern,
 		Options:    options,
 		MaxResults: maxResults,
+	})
+	if err != nil {
+		return nil, 0, err
 	}
+	return searchResp.Results, searchResp.Total, nil
+}
 
+// SearchWithRequest runs req on the server and returns the whole response, for paging,
+// grouping and timeouts. A search error reported by the server is returned as err.
+func (c *Client) SearchWithRequest(req SearchRequest) (*SearchResponse, error) {
 	body, err := json.Marshal(req)
 	if err != nil {
 		return nil, fmt.Errorf("failed to marshal request: %w", err)
@@ -125,7 +140,92 @@ func (c *Client) Search(pattern string, options types.SearchOptions, maxResults
 		return nil, fmt.Errorf("search error: %s", searchResp.Error)
 	}
 
-	return searchResp.Results, nil
+	return &searchResp, nil
+}
+
+// SearchStream runs a search on the server and calls emit for each hit as it arrives,
+// in the order the server finds them. The server stops after maxResults hits (0 = all);
+// an error from emit stops reading and is returned. capReached reports that the search
+// found more than options.ResultCap hits and sent only that many.
+func (c *Client) SearchStream(pattern string, options types.SearchOptions, maxResults int, emit func(searchtypes.Result) error) (capReached bool, err error) {
+	req := SearchRequest{
+		Pattern:    pattern,
+		Options:    options,
+		MaxResults: maxResults,
+	}
+
+	body, err := json.Marshal(req)
+	if err != nil {
+		return false, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	resp, err := c.httpClient.Post("http://unix/search/stream", "application/json", bytes.NewReader(body))
+	if err != nil {
+		return false, fmt.Errorf("failed to search: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return false, fmt.Errorf("server error: %s", string(body))
+	}
+
+	dec := json.NewDecoder(resp.Body)
+	for {
+		var event SearchStreamEvent
+		if err := dec.Decode(&event); err == io.EOF {
+			return capReached, nil
+		} else if err != nil {
+			return capReached, fmt.Errorf("failed to decode response: %w", err)
+		}
+		if event.Error != "" {
+			return capReached, fmt.Errorf("search error: %s", event.Error)
+		}
+		capReached = capReached || event.CapReached
+		if event.Result != nil {
+			if err := emit(*event.Result); err != nil {
+				return capReached, err
+			}
+		}
+	}
+}
+
+// ExportSymbols streams every symbol in the index from the server, calling emit for each
+// as it arrives; an error from emit stops reading and is returned
+func (c *Client) ExportSymbols(req ExportRequest, emit func(ExportSymbol) error) error {
+	body, err := json.Marshal(req)
+	if err != nil {
+		return fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	resp, err := c.httpClient.Post("http://unix/export", "application/json", bytes.NewReader(body))
+	if err != nil {
+		return fmt.Errorf("failed to export: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return fmt.Errorf("server error: %s", string(body))
+	}
+
+	dec := json.NewDecoder(resp.Body)
+	for {
+		var event ExportStreamEvent
+		if err := dec.Decode(&event); err == io.EOF {
+			return nil
+		} else if err != nil {
+			return fmt.Errorf("failed to decode response: %w", err)
+		}
+		if event.Error != "" {
+			return fmt.Errorf("export error: %s", event.Error)
+		}
+		if event.Symbol != nil {
+			if err := emit(*event.Symbol); err != nil {
+				return err
+			}
+		}
+	}
 }
 
 // GetSymbol retrieves symbol information
@@ -299,11 +399,13 @@ func (c *Client) GetDefinition(pattern string, maxResults int) ([]DefinitionLoca
 	return defResp.Definitions, nil
 }
 
-// GetReferences searches for symbol references (usages) by name pattern
-func (c *Client) GetReferences(pattern string, maxResults int) ([]ReferenceLocation, error) {
+// GetReferences finds the usages of a symbol by name, plus its declarations when
+// includeDef is set
+func (c *Client) GetReferences(pattern string, maxResults int, includeDef bool) ([]ReferenceLocation, error) {
 	req := ReferencesRequest{
-		Pattern:    pattern,
-		MaxResults: maxResults,
+		Pattern:           pattern,
+		MaxResults:        maxResults,
+		IncludeDefinition: includeDef,
 	}
 
 	body, err := json.Marshal(req)
@@ -400,6 +502,13 @@ func (c *Client) GetStats() (*StatsResponse, error) {
 
 // WaitForReady waits until the index is ready or timeout
 func (c *Client) WaitForReady(timeout time.Duration) error {
+	_, err := c.WaitForIndex(timeout, nil)
+	return err
+}
+
+// WaitForIndex waits until the index is ready or timeout and returns its final status.
+// onStatus, when not nil, sees each status polled while the index is still building.
+func (c *Client) WaitForIndex(timeout time.Duration, onStatus func(*IndexStatus)) (*IndexStatus, error) {
 	ctx, cancel := context.WithTimeout(context.Background(), timeout)
 	defer cancel()
 
@@ -409,14 +518,17 @@ func (c *Client) WaitForReady(timeout time.Duration) error {
 	for {
 		select {
 		case <-ctx.Done():
-			return fmt.Errorf("timeout waiting for index to be ready")
+			return nil, fmt.Errorf("timeout waiting for index to be ready")
 		case <-ticker.C:
 			status, err := c.GetStatus()
 			if err != nil {
 				continue
 			}
 			if status.Ready {
-				return nil
+				return status, nil
+			}
+			if onStatus != nil {
+				onStatus(status)
 			}
 		}
 	}
@@ -541,3 +653,63 @@ func (c *Client) BrowseFile(req BrowseFileRequest) (*BrowseFileHTTPResponse, err
 
 	return &result, nil
 }
+
+// SymbolAt returns the innermost symbol defined at a byte offset of a file
+func (c *Client) SymbolAt(req SymbolAtRequest) (*SymbolAtResponse, error) {
+	body, err := json.Marshal(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	resp, err := c.httpClient.Post("http://unix/symbol-at", "application/json", bytes.NewReader(body))
+	if err != nil {
+		return nil, fmt.Errorf("failed to look up symbol: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, fmt.Errorf("server error: %s", string(body))
+	}
+
+	var result SymbolAtResponse
+	if err := json.NewDecoder(resp.Body).Decode(&result); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+
+	if result.Error != "" {
+		return nil, fmt.Errorf("symbol lookup error: %s", result.Error)
+	}
+
+	return &result, nil
+}
+
+// SymbolPrefix returns the symbols whose name starts with a prefix
+func (c *Client) SymbolPrefix(req SymbolPrefixRequest) (*SymbolPrefixResponse, error) {
+	body, err := json.Marshal(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	resp, err := c.httpClient.Post("http://unix/symbol-prefix", "application/json", bytes.NewReader(body))
+	if err != nil {
+		return nil, fmt.Errorf("failed to search symbol prefix: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, fmt.Errorf("server error: %s", string(body))
+	}
+
+	var result SymbolPrefixResponse
+	if err := json.NewDecoder(resp.Body).Decode(&result); err != nil {
+		return nil, fmt.Errorf("failed to decode response: %w", err)
+	}
+
+	if result.Error != "" {
+		return nil, fmt.Errorf("prefix search error: %s", result.Error)
+	}
+
+	return &result, nil
+}
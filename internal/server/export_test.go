@@ -0,0 +1,75 @@
+package server
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+)
+
+func TestIndexServer_ExportSymbols(t *testing.T) {
+	testDir := t.TempDir()
+	store := "struct Store;\n\nimpl Store {\n    /// Retrieves a user by id.\n    pub fn get_user(&self) {}\n}\n"
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "b.rs"), []byte(store), 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "a.rs"), []byte("fn helper() {}\n"), 0644))
+	cfg := &config.Config{
+		Project: config.Project{Root: testDir},
+		Include: []string{"*.rs"},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+
+	emit := func(ExportSymbol) error { return nil }
+	assert.ErrorIs(t, srv.ExportSymbols(ExportRequest{}, emit), ErrIndexNotReady)
+	_, err = srv.Reindex("")
+	require.NoError(t, err)
+
+	var symbols []ExportSymbol
+	require.NoError(t, srv.ExportSymbols(ExportRequest{}, func(sym ExportSymbol) error {
+		symbols = append(symbols, sym)
+		return nil
+	}))
+	require.NotEmpty(t, symbols)
+	assert.Equal(t, "helper", symbols[0].Name, "files are exported in path order")
+	assert.Equal(t, "a.rs", filepath.Base(symbols[0].Path))
+
+	var getUser *ExportSymbol
+	for i := range symbols {
+		assert.Empty(t, symbols[i].Content, "content is only inlined on request")
+		if symbols[i].Name == "get_user" {
+			getUser = &symbols[i]
+		}
+	}
+	require.NotNil(t, getUser)
+	assert.Equal(t, "Store", getUser.Parent)
+	assert.Equal(t, "Retrieves a user by id.", getUser.Doc)
+	assert.Equal(t, 5, getUser.Line)
+
+	symbols = nil
+	require.NoError(t, srv.ExportSymbols(ExportRequest{Content: true}, func(sym ExportSymbol) error {
+		symbols = append(symbols, sym)
+		return nil
+	}))
+	for _, sym := range symbols {
+		if sym.Name == "get_user" {
+			assert.Equal(t, store[sym.ByteSpan[0]:sym.ByteSpan[1]], sym.Content)
+			assert.Contains(t, sym.Content, "get_user")
+		}
+	}
+
+	// An error from emit stops the export
+	stop := errors.New("stop")
+	calls := 0
+	err = srv.ExportSymbols(ExportRequest{}, func(ExportSymbol) error {
+		calls++
+		return stop
+	})
+	assert.ErrorIs(t, err, stop)
+	assert.Equal(t, 1, calls)
+}
@@ -0,0 +1,247 @@
+package server
+
+import (
+	"encoding/binary"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"os"
+	"sync"
+
+	"github.com/standardbeagle/lci/internal/debug"
+)
+
+// Framed request types
+const (
+	FramedSearch  = "search"
+	FramedReindex = "reindex"
+	FramedStats   = "stats"
+)
+
+// maxFrameSize caps a single frame so a corrupt length prefix cannot make either side
+// allocate without bound
+const maxFrameSize = 64 << 20
+
+// writeFrame writes v as JSON prefixed with its length as a 4-byte big-endian integer
+func writeFrame(w io.Writer, v any) error {
+	body, err := json.Marshal(v)
+	if err != nil {
+		return err
+	}
+	if len(body) > maxFrameSize {
+		return fmt.Errorf("frame of %d bytes exceeds the %d byte limit", len(body), maxFrameSize)
+	}
+	var header [4]byte
+	binary.BigEndian.PutUint32(header[:], uint32(len(body)))
+	if _, err := w.Write(header[:]); err != nil {
+		return err
+	}
+	_, err = w.Write(body)
+	return err
+}
+
+// readFrame reads one length-prefixed JSON frame into v. It returns io.EOF when the
+// peer closed the connection between frames.
+func readFrame(r io.Reader, v any) error {
+	var header [4]byte
+	if _, err := io.ReadFull(r, header[:]); err != nil {
+		return err
+	}
+	size := binary.BigEndian.Uint32(header[:])
+	if size > maxFrameSize {
+		return fmt.Errorf("frame of %d bytes exceeds the %d byte limit", size, maxFrameSize)
+	}
+	body := make([]byte, size)
+	if _, err := io.ReadFull(r, body); err != nil {
+		return fmt.Errorf("truncated frame: %w", err)
+	}
+	return json.Unmarshal(body, v)
+}
+
+// FramedServer answers length-prefixed JSON requests on a Unix socket from an
+// IndexServer's in-memory index. Each connection is served by its own goroutine and
+// may send any number of requests, answered in order.
+type FramedServer struct {
+	index      *IndexServer
+	socketPath string
+	listener   net.Listener
+	conns      map[net.Conn]struct{}
+	wg         sync.WaitGroup
+	mu         sync.Mutex
+	closed     bool
+}
+
+// NewFramedServer creates a framed server for index on socketPath
+func NewFramedServer(index *IndexServer, socketPath string) *FramedServer {
+	return &FramedServer{
+		index:      index,
+		socketPath: socketPath,
+		conns:      make(map[net.Conn]struct{}),
+	}
+}
+
+// Listen binds the socket, replacing a stale socket file left by a server that did not
+// shut down cleanly
+func (s *FramedServer) Listen() error {
+	os.Remove(s.socketPath)
+
+	listener, err := net.Listen("unix", s.socketPath)
+	if err != nil {
+		return fmt.Errorf("failed to listen on %s: %w", s.socketPath, err)
+	}
+	if err := os.Chmod(s.socketPath, 0600); err != nil {
+		listener.Close()
+		return fmt.Errorf("failed to set socket permissions: %w", err)
+	}
+
+	s.mu.Lock()
+	s.listener = listener
+	s.mu.Unlock()
+	return nil
+}
+
+// Serve accepts connections until Close is called
+func (s *FramedServer) Serve() error {
+	for {
+		conn, err := s.listener.Accept()
+		if err != nil {
+			s.mu.Lock()
+			closed := s.closed
+			s.mu.Unlock()
+			if closed {
+				return nil
+			}
+			return err
+		}
+
+		s.mu.Lock()
+		if s.closed {
+			s.mu.Unlock()
+			conn.Close()
+			return nil
+		}
+		s.conns[conn] = struct{}{}
+		s.wg.Add(1)
+		s.mu.Unlock()
+
+		go s.serveConn(conn)
+	}
+}
+
+// serveConn answers requests on conn until the client disconnects
+func (s *FramedServer) serveConn(conn net.Conn) {
+	defer func() {
+		conn.Close()
+		s.mu.Lock()
+		delete(s.conns, conn)
+		s.mu.Unlock()
+		s.wg.Done()
+	}()
+
+	for {
+		var req FramedRequest
+		if err := readFrame(conn, &req); err != nil {
+			if !errors.Is(err, io.EOF) && !errors.Is(err, net.ErrClosed) {
+				debug.LogMCP("Framed connection error: %v", err)
+			}
+			return
+		}
+		if err := writeFrame(conn, s.handle(req)); err != nil {
+			debug.LogMCP("Framed write error: %v", err)
+			return
+		}
+	}
+}
+
+// handle dispatches one request
+func (s *FramedServer) handle(req FramedRequest) FramedResponse {
+	switch req.Type {
+	case "", FramedSearch:
+		if req.Search == nil {
+			return FramedResponse{Error: "search request has no search field"}
+		}
+		resp, err := s.index.Search(*req.Search)
+		if err != nil {
+			return FramedResponse{Error: err.Error()}
+		}
+		return FramedResponse{Search: &resp}
+
+	case FramedReindex:
+		var path string
+		if req.Reindex != nil {
+			path = req.Reindex.Path
+		}
+		resp, err := s.index.Reindex(path)
+		if err != nil {
+			return FramedResponse{Error: fmt.Sprintf("reindex failed: %v", err)}
+		}
+		return FramedResponse{Reindex: &resp}
+
+	case FramedStats:
+		stats, err := s.index.Stats()
+		if err != nil {
+			return FramedResponse{Error: err.Error()}
+		}
+		return FramedResponse{Stats: &stats}
+
+	default:
+		return FramedResponse{Error: fmt.Sprintf("unknown request type %q", req.Type)}
+	}
+}
+
+// Close stops accepting connections, closes open ones, waits for their handlers and
+// removes the socket file
+func (s *FramedServer) Close() error {
+	s.mu.Lock()
+	if s.closed {
+		s.mu.Unlock()
+		return nil
+	}
+	s.closed = true
+	var err error
+	if s.listener != nil {
+		err = s.listener.Close()
+	}
+	for conn := range s.conns {
+		conn.Close()
+	}
+	s.mu.Unlock()
+
+	s.wg.Wait()
+	os.Remove(s.socketPath)
+	return err
+}
+
+// FramedClient sends requests to a FramedServer over a single connection. It is not
+// safe for concurrent use; open one client per goroutine.
+type FramedClient struct {
+	conn net.Conn
+}
+
+// DialFramed connects to the framed server on socketPath
+func DialFramed(socketPath string) (*FramedClient, error) {
+	conn, err := net.Dial("unix", socketPath)
+	if err != nil {
+		return nil, fmt.Errorf("failed to connect to %s: %w", socketPath, err)
+	}
+	return &FramedClient{conn: conn}, nil
+}
+
+// Do sends req and waits for its response
+func (c *FramedClient) Do(req FramedRequest) (*FramedResponse, error) {
+	if err := writeFrame(c.conn, req); err != nil {
+		return nil, fmt.Errorf("failed to send request: %w", err)
+	}
+	var resp FramedResponse
+	if err := readFrame(c.conn, &resp); err != nil {
+		return nil, fmt.Errorf("failed to read response: %w", err)
+	}
+	return &resp, nil
+}
+
+// Close closes the connection
+func (c *FramedClient) Close() error {
+	return c.conn.Close()
+}
@@ -0,0 +1,98 @@
+package server
+
+import (
+	"bytes"
+	"encoding/binary"
+	"os"
+	"path/filepath"
+	"sync"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+)
+
+func TestFrame_RoundTrip(t *testing.T) {
+	var buf bytes.Buffer
+	sent := FramedRequest{Type: FramedSearch, Search: &SearchRequest{Pattern: "needle"}}
+	require.NoError(t, writeFrame(&buf, sent))
+	assert.Equal(t, uint32(buf.Len()-4), binary.BigEndian.Uint32(buf.Bytes()[:4]), "big-endian length of the body")
+
+	var got FramedRequest
+	require.NoError(t, readFrame(&buf, &got))
+	assert.Equal(t, sent, got)
+
+	oversized := make([]byte, 4)
+	binary.BigEndian.PutUint32(oversized, maxFrameSize+1)
+	assert.Error(t, readFrame(bytes.NewReader(oversized), &got))
+}
+
+func TestFramedServer_ConcurrentQueriesAndReindex(t *testing.T) {
+	testDir := t.TempDir()
+	socketPath := filepath.Join(t.TempDir(), "lci.sock")
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "a.go"), []byte("package a\n\nfunc Needle() {}\n"), 0644))
+
+	cfg := &config.Config{
+		Project: config.Project{Root: testDir},
+		Include: []string{"*.go"},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+	_, err = srv.Reindex("")
+	require.NoError(t, err)
+
+	framed := NewFramedServer(srv, socketPath)
+	require.NoError(t, framed.Listen())
+	go framed.Serve()
+
+	search := func(client *FramedClient) *SearchResponse {
+		resp, err := client.Do(FramedRequest{Search: &SearchRequest{Pattern: "Needle"}})
+		require.NoError(t, err)
+		require.Empty(t, resp.Error)
+		require.NotNil(t, resp.Search)
+		return resp.Search
+	}
+
+	var wg sync.WaitGroup
+	for i := 0; i < 8; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			client, err := DialFramed(socketPath)
+			if !assert.NoError(t, err) {
+				return
+			}
+			defer client.Close()
+			for j := 0; j < 5; j++ {
+				resp, err := client.Do(FramedRequest{Search: &SearchRequest{Pattern: "Needle"}})
+				if assert.NoError(t, err) && assert.NotNil(t, resp.Search) {
+					assert.Equal(t, 1, resp.Search.Total, "each connection answers requests in turn")
+				}
+			}
+		}()
+	}
+	wg.Wait()
+
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "b.go"), []byte("package b\n\nfunc Needle() {}\n"), 0644))
+	client, err := DialFramed(socketPath)
+	require.NoError(t, err)
+	defer client.Close()
+
+	resp, err := client.Do(FramedRequest{Type: FramedReindex})
+	require.NoError(t, err)
+	require.NotNil(t, resp.Reindex)
+	assert.True(t, resp.Reindex.Success)
+	assert.Greater(t, resp.Reindex.SymbolCount, resp.Reindex.OldSymbolCount, "b.go adds a symbol")
+	assert.Equal(t, 2, search(client).Total, "reindex replies once the new index is live")
+
+	resp, err = client.Do(FramedRequest{Type: "bogus"})
+	require.NoError(t, err)
+	assert.Contains(t, resp.Error, "unknown request type")
+
+	require.NoError(t, framed.Close())
+	_, err = os.Stat(socketPath)
+	assert.True(t, os.IsNotExist(err), "socket file is removed on close")
+}
@@ -0,0 +1,95 @@
+package server
+
+import (
+	"net/http"
+	"sync"
+
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+)
+
+// indexSnapshot is an index together with the engine searching it. The server serves
+// one snapshot at a time and replaces it whole, so a request that acquired a snapshot
+// searches the same, complete index to its end however many reindexes happen meanwhile.
+type indexSnapshot struct {
+	id      uint64 // Tells snapshots apart in search cache keys, as each index counts its generations from zero
+	indexer *indexing.MasterIndex
+	engine  *search.Engine // nil while indexer is being built in place
+
+	mu      sync.Mutex
+	users   int           // Requests using the snapshot
+	retired bool          // Replaced: acquire moves on to the new snapshot
+	drained chan struct{} // Closed once retired without users
+}
+
+// acquire returns the served snapshot, which stays usable until release. Its engine is
+// nil while the index is being built in place.
+func (s *IndexServer) acquire() *indexSnapshot {
+	for {
+		snap := s.index.Load()
+		snap.mu.Lock()
+		if !snap.retired {
+			snap.users++
+			snap.mu.Unlock()
+			return snap
+		}
+		snap.mu.Unlock() // Replaced since the load: its successor is already served
+	}
+}
+
+// acquireReady returns the served snapshot like acquire when its index is built, and
+// otherwise answers 503 and returns nil
+func (s *IndexServer) acquireReady(w http.ResponseWriter) *indexSnapshot {
+	snap := s.acquire()
+	if snap.engine == nil {
+		snap.release()
+		http.Error(w, ErrIndexNotReady.Error(), http.StatusServiceUnavailable)
+		return nil
+	}
+	return snap
+}
+
+// release ends a request's use of the snapshot
+func (snap *indexSnapshot) release() {
+	snap.mu.Lock()
+	snap.users--
+	if snap.retired && snap.users == 0 {
+		close(snap.drained)
+	}
+	snap.mu.Unlock()
+}
+
+// publish serves indexer and engine, then retires the snapshot they replace: it waits
+// for the requests still using it and, when it held a different indexer, closes that
+// indexer in the background so the caller does not wait for slow requests such as an
+// export.
+func (s *IndexServer) publish(indexer *indexing.MasterIndex, engine *search.Engine) {
+	snap := &indexSnapshot{id: s.snapshots.Add(1), indexer: indexer, engine: engine, drained: make(chan struct{})}
+	old := s.index.Swap(snap)
+	if old == nil {
+		return
+	}
+	if old.indexer == indexer {
+		old.retire()
+		return
+	}
+	s.wg.Add(1)
+	go func() {
+		defer s.wg.Done()
+		old.retire()
+		old.indexer.Close()
+	}()
+}
+
+// retire turns later acquirers away and waits for the requests using the snapshot.
+// Acquirers never wait on it: one that loaded the snapshot just before it was replaced
+// still gets it, complete as it is, and retire waits for that request too.
+func (snap *indexSnapshot) retire() {
+	snap.mu.Lock()
+	snap.retired = true
+	if snap.users == 0 {
+		close(snap.drained)
+	}
+	snap.mu.Unlock()
+	<-snap.drained
+}
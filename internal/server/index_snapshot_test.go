@@ -0,0 +1,94 @@
+package server
+
+import (
+	"os"
+	"path/filepath"
+	"sync"
+	"sync/atomic"
+	"testing"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestIndexServer_ReindexSwapsIndex(t *testing.T) {
+	testDir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "a.go"), []byte("package a\n\nfunc Needle() {}\n"), 0644))
+	cfg := &config.Config{
+		Project: config.Project{Root: testDir},
+		Include: []string{"*.go"},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+	first, err := srv.Reindex("")
+	require.NoError(t, err)
+	assert.Zero(t, first.OldSymbolCount, "the first build replaces no index")
+
+	// A request that started before the reindex
+	inFlight := srv.acquire()
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "b.go"), []byte("package b\n\nfunc Needle() {}\n\nfunc Other() {}\n"), 0644))
+
+	var notReady atomic.Int32
+	stop := make(chan struct{})
+	var wg sync.WaitGroup
+	wg.Add(1)
+	go func() {
+		defer wg.Done()
+		for {
+			select {
+			case <-stop:
+				return
+			default:
+			}
+			if _, err := srv.Search(SearchRequest{Pattern: "Needle"}); err != nil {
+				notReady.Add(1)
+			}
+		}
+	}()
+
+	resp, err := srv.Reindex("")
+	close(stop)
+	wg.Wait()
+	require.NoError(t, err, "the reindex does not wait for requests on the old index")
+	assert.Zero(t, notReady.Load(), "searches are served throughout the reindex")
+	assert.True(t, resp.Success)
+	assert.Equal(t, 2, resp.FileCount)
+	assert.Equal(t, first.SymbolCount, resp.OldSymbolCount)
+	assert.Greater(t, resp.SymbolCount, resp.OldSymbolCount)
+
+	assert.Len(t, inFlight.engine.SearchWithOptions("Needle", nil, types.SearchOptions{}), 1, "the in-flight request keeps the old index")
+	inFlight.release()
+
+	after, err := srv.Search(SearchRequest{Pattern: "Needle"})
+	require.NoError(t, err)
+	assert.Equal(t, 2, after.Total, "new requests search the new index")
+}
+
+func TestIndexServer_ReindexInPlace(t *testing.T) {
+	testDir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "a.go"), []byte("package a\n\nfunc Needle() {}\n"), 0644))
+	cfg := &config.Config{
+		Project:     config.Project{Root: testDir},
+		Include:     []string{"*.go"},
+		Index:       config.Index{MaxFileSize: 10 * 1024 * 1024},
+		Performance: config.Performance{ReindexInPlace: true},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+	_, err = srv.Reindex("")
+	require.NoError(t, err)
+	before := srv.index.Load().indexer
+
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "b.go"), []byte("package b\n\nfunc Needle() {}\n"), 0644))
+	resp, err := srv.Reindex("")
+	require.NoError(t, err)
+	assert.Same(t, before, srv.index.Load().indexer, "the index is rebuilt rather than replaced")
+	assert.Equal(t, 2, resp.FileCount)
+
+	after, err := srv.Search(SearchRequest{Pattern: "Needle"})
+	require.NoError(t, err)
+	assert.Equal(t, 2, after.Total)
+}
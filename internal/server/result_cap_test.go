@@ -0,0 +1,53 @@
+package server
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestServerResultCap(t *testing.T) {
+	assert.Equal(t, maxSearchResults, serverResultCap(0), "every search is capped")
+	assert.Equal(t, 10, serverResultCap(10))
+	assert.Equal(t, maxSearchResults, serverResultCap(maxSearchResults+1), "a request cannot raise the cap")
+}
+
+func TestIndexServer_SearchCapReached(t *testing.T) {
+	testDir := t.TempDir()
+	for i := range 4 {
+		path := filepath.Join(testDir, fmt.Sprintf("f%d.go", i))
+		require.NoError(t, os.WriteFile(path, []byte("package f\n\nfunc Needle() {}\n"), 0644))
+	}
+	cfg := &config.Config{
+		Project: config.Project{Root: testDir},
+		Include: []string{"*.go"},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+	_, err = srv.Reindex("")
+	require.NoError(t, err)
+
+	resp, err := srv.Search(SearchRequest{Pattern: "Needle"})
+	require.NoError(t, err)
+	assert.Equal(t, 4, resp.Total)
+	assert.False(t, resp.CapReached)
+
+	resp, err = srv.Search(SearchRequest{Pattern: "Needle", Options: types.SearchOptions{ResultCap: 2}})
+	require.NoError(t, err)
+	assert.Equal(t, 2, resp.Total)
+	assert.Len(t, resp.Results, 2)
+	assert.True(t, resp.CapReached)
+
+	resp, err = srv.Search(SearchRequest{Pattern: "Needle", Options: types.SearchOptions{ResultCap: 4}})
+	require.NoError(t, err)
+	assert.Equal(t, 4, resp.Total)
+	assert.False(t, resp.CapReached, "exactly ResultCap hits are not truncated")
+}
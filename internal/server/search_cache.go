@@ -0,0 +1,129 @@
+package server
+
+import (
+	"container/list"
+	"encoding/json"
+	"sync"
+	"time"
+)
+
+// maxCachedResults bounds the search cache by the hits it holds rather than by entry
+// count, since one broad query can return more hits than thousands of narrow ones
+const maxCachedResults = 50000
+
+// SearchCacheStats reports the search cache's effectiveness in StatsResponse
+type SearchCacheStats struct {
+	Hits      int64 `json:"hits"`
+	Misses    int64 `json:"misses"`
+	Evictions int64 `json:"evictions"`
+	Entries   int   `json:"entries"`
+	Results   int   `json:"results"` // Hits held across all entries
+}
+
+// searchCacheKey identifies a response: the normalized request and the index snapshot
+// and generation it was computed at. An index mutation changes the generation and a
+// reindex that swaps the index changes the snapshot, so entries from before either
+// are never looked up again and age out of the LRU.
+type searchCacheKey struct {
+	query      string
+	index      uint64 // indexSnapshot.id
+	generation uint64
+}
+
+type searchCacheEntry struct {
+	key      searchCacheKey
+	response SearchResponse
+	size     int
+}
+
+// searchCache is an LRU of search responses holding at most maxResults hits in total
+type searchCache struct {
+	mu         sync.Mutex
+	maxResults int
+	results    int
+	order      *list.List // Front is most recently used
+	entries    map[searchCacheKey]*list.Element
+	hits       int64
+	misses     int64
+	evictions  int64
+}
+
+func newSearchCache(maxResults int) *searchCache {
+	return &searchCache{
+		maxResults: maxResults,
+		order:      list.New(),
+		entries:    make(map[searchCacheKey]*list.Element),
+	}
+}
+
+// normalizeSearchRequest returns the cache key text for req. The timeout is left out:
+// only responses that finished in time are cached, and those hold every hit whatever
+// the timeout was.
+func normalizeSearchRequest(req SearchRequest) (string, bool) {
+	req.TimeoutMs = 0
+	req.Options.Deadline = time.Time{}
+	body, err := json.Marshal(req)
+	if err != nil {
+		return "", false
+	}
+	return string(body), true
+}
+
+// get returns the cached response for key, marking it most recently used
+func (c *searchCache) get(key searchCacheKey) (SearchResponse, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	elem, ok := c.entries[key]
+	if !ok {
+		c.misses++
+		return SearchResponse{}, false
+	}
+	c.hits++
+	c.order.MoveToFront(elem)
+	return elem.Value.(*searchCacheEntry).response, true
+}
+
+// put caches response under key, evicting least recently used entries until the total
+// hit count fits. A response larger than the whole budget is not cached.
+func (c *searchCache) put(key searchCacheKey, response SearchResponse) {
+	size := len(response.Results)
+	for _, g := range response.Groups {
+		size += len(g.Hits)
+	}
+	size = max(size, 1) // An empty response still costs an entry
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	if size > c.maxResults {
+		return
+	}
+	if elem, ok := c.entries[key]; ok {
+		c.removeElement(elem)
+	}
+	for c.results+size > c.maxResults && c.order.Len() > 0 {
+		c.removeElement(c.order.Back())
+		c.evictions++
+	}
+	c.entries[key] = c.order.PushFront(&searchCacheEntry{key: key, response: response, size: size})
+	c.results += size
+}
+
+func (c *searchCache) removeElement(elem *list.Element) {
+	entry := c.order.Remove(elem).(*searchCacheEntry)
+	delete(c.entries, entry.key)
+	c.results -= entry.size
+}
+
+func (c *searchCache) stats() SearchCacheStats {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return SearchCacheStats{
+		Hits:      c.hits,
+		Misses:    c.misses,
+		Evictions: c.evictions,
+		Entries:   c.order.Len(),
+		Results:   c.results,
+	}
+}
@@ -0,0 +1,86 @@
+package server
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+func TestSearchCache_BoundedByResults(t *testing.T) {
+	cache := newSearchCache(5)
+	response := func(n int) SearchResponse {
+		return SearchResponse{Results: make([]searchtypes.Result, n), Total: n}
+	}
+	key := func(q string) searchCacheKey { return searchCacheKey{query: q, generation: 1} }
+
+	cache.put(key("a"), response(2))
+	cache.put(key("b"), response(2))
+	_, ok := cache.get(key("a"))
+	require.True(t, ok)
+
+	cache.put(key("c"), response(3))
+	_, ok = cache.get(key("b"))
+	assert.False(t, ok, "the least recently used entry is evicted to fit")
+	_, ok = cache.get(key("a"))
+	assert.True(t, ok)
+
+	cache.put(key("huge"), response(6))
+	_, ok = cache.get(key("huge"))
+	assert.False(t, ok, "a response larger than the budget is not cached")
+
+	_, ok = cache.get(searchCacheKey{query: "a", generation: 2})
+	assert.False(t, ok, "entries only match their own generation")
+
+	assert.Equal(t, SearchCacheStats{Hits: 2, Misses: 3, Evictions: 1, Entries: 2, Results: 5}, cache.stats())
+}
+
+func TestIndexServer_SearchCache(t *testing.T) {
+	testDir := t.TempDir()
+	path := filepath.Join(testDir, "a.go")
+	require.NoError(t, os.WriteFile(path, []byte("package a\n\nfunc Needle() {}\n"), 0644))
+
+	cfg := &config.Config{
+		Project: config.Project{Root: testDir},
+		Include: []string{"*.go"},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+	_, err = srv.Reindex("")
+	require.NoError(t, err)
+
+	search := func(req SearchRequest) SearchResponse {
+		resp, err := srv.Search(req)
+		require.NoError(t, err)
+		return resp
+	}
+	cacheStats := func() SearchCacheStats {
+		stats, err := srv.Stats()
+		require.NoError(t, err)
+		require.NotNil(t, stats.Cache)
+		return *stats.Cache
+	}
+
+	assert.Equal(t, 1, search(SearchRequest{Pattern: "Needle"}).Total)
+	assert.Equal(t, 1, search(SearchRequest{Pattern: "Needle", TimeoutMs: 5000}).Total)
+	assert.Equal(t, int64(1), cacheStats().Hits, "the timeout is not part of the key")
+
+	search(SearchRequest{Pattern: "Needle", MaxResults: 1})
+	assert.Equal(t, int64(2), cacheStats().Misses, "other options are")
+
+	require.NoError(t, srv.index.Load().indexer.UpdateFile(path, []byte("package a\n\nfunc Needle() {}\nfunc Needle2() {}\n")))
+	assert.Equal(t, 2, search(SearchRequest{Pattern: "Needle"}).Total, "an index update invalidates cached responses")
+	assert.Equal(t, int64(3), cacheStats().Misses)
+
+	resp := search(SearchRequest{Pattern: "(", Options: types.SearchOptions{UseRegex: true}})
+	require.NotEmpty(t, resp.Error)
+	search(SearchRequest{Pattern: "(", Options: types.SearchOptions{UseRegex: true}})
+	assert.Equal(t, int64(1), cacheStats().Hits, "error responses are not cached")
+}
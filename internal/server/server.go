@@ -3,14 +3,17 @@ package server
 import (
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"net"
 	"net/http"
 	"os"
 	"path/filepath"
 	"runtime"
+	"sort"
 	"strings"
 	"sync"
+	"sync/atomic"
 	"time"
 
 	"github.com/standardbeagle/lci/internal/config"
@@ -25,10 +28,28 @@ import (
 	"github.com/standardbeagle/lci/internal/version"
 )
 
+// ErrIndexNotReady is returned by searches made while the index is being built
+var ErrIndexNotReady = errors.New("index not ready - still indexing")
+
+// maxSearchResults caps the hits one search may collect, so a pattern as broad as "e"
+// cannot monopolize the server's CPU and memory. A request may set a lower
+// Options.ResultCap, never a higher one.
+const maxSearchResults = 100000
+
+// serverResultCap is the ResultCap a request is searched with under maxSearchResults
+func serverResultCap(requested int) int {
+	if requested <= 0 || requested > maxSearchResults {
+		return maxSearchResults
+	}
+	return requested
+}
+
 // IndexServer manages a persistent index that can be shared between CLI and MCP
 type IndexServer struct {
-	indexer          *indexing.MasterIndex
-	searchEngine     *search.Engine
+	index            atomic.Pointer[indexSnapshot]        // Served index (see acquire)
+	snapshots        atomic.Uint64                        // Snapshots published, numbering the next one
+	building         atomic.Pointer[indexing.MasterIndex] // Index Reindex is building, for status progress
+	sharedIndex      bool                                 // The index belongs to NewIndexServerWithIndex's caller
 	cfg              *config.Config
 	listener         net.Listener
 	server           *http.Server
@@ -36,6 +57,8 @@ type IndexServer struct {
 	shutdownChan     chan struct{}
 	wg               sync.WaitGroup
 	mu               sync.RWMutex
+	reindexMu        sync.Mutex // Serializes Reindex calls
+	searchCache      *searchCache
 	running          bool
 	indexingActive   bool
 	socketPath       string // Custom socket path (empty uses default)
@@ -44,33 +67,32 @@ type IndexServer struct {
 
 // NewIndexServer creates a new persistent index server
 func NewIndexServer(cfg *config.Config) (*IndexServer, error) {
-	// Create the master index
-	indexer := indexing.NewMasterIndex(cfg)
-
-	// Create search engine - will be initialized after indexing
-	var searchEngine *search.Engine
-
-	return &IndexServer{
-		indexer:        indexer,
-		searchEngine:   searchEngine,
+	s := &IndexServer{
 		cfg:            cfg,
 		startTime:      time.Now(),
 		shutdownChan:   make(chan struct{}),
+		searchCache:    newSearchCache(maxCachedResults),
 		indexingActive: false,
-	}, nil
+	}
+	// The search engine is created once the master index is built
+	s.publish(indexing.NewMasterIndex(cfg), nil)
+	return s, nil
 }
 
 // NewIndexServerWithIndex creates a new persistent index server with an existing MasterIndex
-// This is used when the index is managed externally (e.g., by MCP)
+// This is used when the index is managed externally (e.g., by MCP). Reindex rebuilds
+// such an index in place, since its owner keeps using it.
 func NewIndexServerWithIndex(cfg *config.Config, indexer *indexing.MasterIndex, searchEngine *search.Engine) (*IndexServer, error) {
-	return &IndexServer{
-		indexer:        indexer,
-		searchEngine:   searchEngine,
+	s := &IndexServer{
+		sharedIndex:    true,
 		cfg:            cfg,
 		startTime:      time.Now(),
 		shutdownChan:   make(chan struct{}),
+		searchCache:    newSearchCache(maxCachedResults),
 		indexingActive: false, // External caller manages indexing
-	}, nil
+	}
+	s.publish(indexer, searchEngine)
+	return s, nil
 }
 
 // GetSocketPath returns the default path to the Unix socket (for backwards compatibility)
@@ -147,32 +169,8 @@ func (s *IndexServer) Start() error {
 
 	// Start indexing in background only if search engine not already set
 	// (When using NewIndexServerWithIndex, the index is managed externally)
-	s.mu.Lock()
-	hasSearchEngine := s.searchEngine != nil
-	s.mu.Unlock()
-
-	if !hasSearchEngine {
-		go func() {
-			s.mu.Lock()
-			s.indexingActive = true
-			s.mu.Unlock()
-
-			debug.LogMCP("Starting indexing of %s...", s.cfg.Project.Root)
-			if err := s.indexer.IndexDirectory(context.Background(), s.cfg.Project.Root); err != nil {
-				debug.LogMCP("Indexing error: %v", err)
-			} else {
-				debug.LogMCP("Indexing completed successfully")
-			}
-
-			// Create search engine after indexing completes
-			s.mu.Lock()
-			s.searchEngine = search.NewEngine(s.indexer)
-			s.indexer.SetSearchEngine(s.searchEngine)
-			s.indexingActive = false
-			s.mu.Unlock()
-
-			debug.LogMCP("Index ready for queries")
-		}()
+	if s.index.Load().engine == nil {
+		go s.Reindex("")
 	} else {
 		debug.LogMCP("Using externally managed index (ready immediately)")
 	}
@@ -196,6 +194,7 @@ func (s *IndexServer) Start() error {
 func (s *IndexServer) registerHandlers(mux *http.ServeMux) {
 	mux.HandleFunc("/status", s.handleStatus)
 	mux.HandleFunc("/search", s.handleSearch)
+	mux.HandleFunc("/search/stream", s.handleSearchStream)
 	mux.HandleFunc("/symbol", s.handleGetSymbol)
 	mux.HandleFunc("/fileinfo", s.handleGetFileInfo)
 	mux.HandleFunc("/shutdown", s.handleShutdown)
@@ -209,29 +208,43 @@ func (s *IndexServer) registerHandlers(mux *http.ServeMux) {
 	mux.HandleFunc("/list-symbols", s.handleListSymbols)
 	mux.HandleFunc("/inspect-symbol", s.handleInspectSymbol)
 	mux.HandleFunc("/browse-file", s.handleBrowseFile)
+	mux.HandleFunc("/symbol-at", s.handleSymbolAt)
+	mux.HandleFunc("/symbol-prefix", s.handleSymbolPrefix)
+	mux.HandleFunc("/export", s.handleExport)
 }
 
 // handleStatus returns the current index status
 func (s *IndexServer) handleStatus(w http.ResponseWriter, r *http.Request) {
 	s.mu.RLock()
 	indexingActive := s.indexingActive
-	ready := s.searchEngine != nil
 	s.mu.RUnlock()
 
 	// Get index statistics
+	snap := s.acquire()
+	ready := snap.engine != nil
 	fileCount := 0
 	symbolCount := 0
 	if ready {
-		fileCount = s.indexer.GetFileCount()
-		symbolCount = s.indexer.GetSymbolCount()
+		fileCount = snap.indexer.GetFileCount()
+		symbolCount = snap.indexer.GetSymbolCount()
 	}
+	snap.release()
 
 	status := IndexStatus{
 		Ready:          ready,
 		FileCount:      fileCount,
 		SymbolCount:    symbolCount,
 		IndexingActive: indexingActive,
-		Progress:       1.0, // TODO: Add real progress tracking
+		Progress:       1.0,
+	}
+	if building := s.building.Load(); indexingActive && building != nil {
+		progress := building.GetProgress()
+		status.TotalFiles = progress.TotalFiles
+		status.FilesProcessed = min(progress.FilesProcessed, progress.TotalFiles)
+		status.Progress = 0
+		if status.TotalFiles > 0 {
+			status.Progress = float64(status.FilesProcessed) / float64(status.TotalFiles)
+		}
 	}
 
 	w.Header().Set("Content-Type", "application/json")
@@ -246,29 +259,216 @@ func (s *IndexServer) handleSearch(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	engine := s.searchEngine
-	s.mu.RUnlock()
+	response, err := s.Search(req)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusServiceUnavailable)
+		return
+	}
 
-	if engine == nil {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(response)
+}
+
+// Search runs a search request against the current index. It fails with
+// ErrIndexNotReady while the index is being built; an invalid pattern is reported in
+// the response's Error instead.
+//
+// Responses are cached by request and index generation, so repeating a query against
+// an unchanged index is answered without searching. Timed out responses are not cached.
+// Every search stops at maxSearchResults hits, flagging the response CapReached.
+func (s *IndexServer) Search(req SearchRequest) (SearchResponse, error) {
+	snap := s.acquire()
+	defer snap.release()
+
+	if snap.engine == nil {
+		return SearchResponse{}, ErrIndexNotReady
+	}
+	req.Options.ResultCap = serverResultCap(req.Options.ResultCap)
+
+	// Read the generation before searching: a mutation that overlaps the search moves
+	// it on, so the response is stored under a key no later lookup uses
+	query, cacheable := normalizeSearchRequest(req)
+	key := searchCacheKey{query: query, index: snap.id, generation: snap.indexer.Generation()}
+	if cacheable {
+		if response, ok := s.searchCache.get(key); ok {
+			return response, nil
+		}
+	}
+
+	response := s.search(snap.engine, req)
+	if cacheable && response.Error == "" && !response.TimedOut {
+		s.searchCache.put(key, response)
+	}
+	return response, nil
+}
+
+// search runs req on engine, uncached
+func (s *IndexServer) search(engine *search.Engine, req SearchRequest) SearchResponse {
+	var timedOut, capReached atomic.Bool
+	if req.TimeoutMs > 0 {
+		req.Options.Deadline = time.Now().Add(time.Duration(req.TimeoutMs) * time.Millisecond)
+		req.Options.TimedOut = &timedOut
+	}
+	req.Options.CapReached = &capReached
+
+	// Perform search using engine; regex and query searches go through SearchRegex and
+	// SearchQuery so an invalid pattern comes back as an error rather than an empty result
+	var results []searchtypes.Result
+	var err error
+	switch {
+	case req.Options.Query:
+		results, err = engine.SearchQuery(req.Pattern, req.Options)
+	case req.Options.UseRegex:
+		results, err = engine.SearchRegex(req.Pattern, req.Options)
+	default:
+		results = engine.SearchWithOptions(req.Pattern, nil, req.Options)
+	}
+	if err != nil {
+		return SearchResponse{Error: err.Error()}
+	}
+
+	// Rank and keep MaxResults from Offset on; the engine has already merged
+	// duplicates, so Total counts distinct hits
+	resp := SearchResponse{
+		Total:      len(results),
+		TimedOut:   timedOut.Load(),
+		CapReached: capReached.Load(),
+	}
+	results = search.PageResults(results, req.Offset, req.MaxResults)
+	if req.GroupBy == GroupBySymbol {
+		resp.Groups = engine.GroupBySymbol(results)
+	} else {
+		resp.Results = results
+	}
+	if req.Suggest && resp.Total == 0 && !resp.TimedOut {
+		resp.Suggestions = engine.SuggestSymbols(req.Pattern, req.Options)
+	}
+	return resp
+}
+
+// handleSearchStream streams hits as newline-delimited SearchStreamEvents in the order
+// the engine finds them, flushing after each one. Hits are not ranked across files.
+func (s *IndexServer) handleSearchStream(w http.ResponseWriter, r *http.Request) {
+	var req SearchRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
 		return
 	}
 
-	// Perform search using engine
-	results := engine.SearchWithOptions(req.Pattern, nil, req.Options)
+	snap := s.acquireReady(w)
+	if snap == nil {
+		return
+	}
+	defer snap.release()
+	engine := snap.engine
 
-	// Limit results if requested
-	if req.MaxResults > 0 && len(results) > req.MaxResults {
-		results = results[:req.MaxResults]
+	req.Options.ResultCap = serverResultCap(req.Options.ResultCap)
+	var capReached atomic.Bool
+	req.Options.CapReached = &capReached
+	w.Header().Set("Content-Type", "application/x-ndjson")
+	enc := json.NewEncoder(w)
+	flusher, _ := w.(http.Flusher)
+
+	sent := 0
+	err := engine.SearchStream(req.Pattern, nil, req.Options, func(result searchtypes.Result) bool {
+		if enc.Encode(SearchStreamEvent{Result: &result}) != nil {
+			return false // Client went away
+		}
+		if flusher != nil {
+			flusher.Flush()
+		}
+		sent++
+		return req.MaxResults <= 0 || sent < req.MaxResults
+	})
+	if err != nil {
+		enc.Encode(SearchStreamEvent{Error: err.Error()})
+	} else if capReached.Load() {
+		enc.Encode(SearchStreamEvent{CapReached: true})
 	}
+}
 
-	response := SearchResponse{
-		Results: results,
+// handleExport streams every symbol in the index as ExportStreamEvent lines
+func (s *IndexServer) handleExport(w http.ResponseWriter, r *http.Request) {
+	var req ExportRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
 	}
 
-	w.Header().Set("Content-Type", "application/json")
-	json.NewEncoder(w).Encode(response)
+	// ExportSymbols acquires the index itself; a reindex in place that starts before it
+	// does is reported as a stream error
+	if s.index.Load().engine == nil {
+		http.Error(w, ErrIndexNotReady.Error(), http.StatusServiceUnavailable)
+		return
+	}
+
+	w.Header().Set("Content-Type", "application/x-ndjson")
+	enc := json.NewEncoder(w)
+	flusher, _ := w.(http.Flusher)
+
+	err := s.ExportSymbols(req, func(sym ExportSymbol) error {
+		if err := enc.Encode(ExportStreamEvent{Symbol: &sym}); err != nil {
+			return err // Client went away
+		}
+		if flusher != nil {
+			flusher.Flush()
+		}
+		return nil
+	})
+	if err != nil {
+		enc.Encode(ExportStreamEvent{Error: err.Error()})
+	}
+}
+
+// ExportSymbols calls emit for every symbol in the index, files in path order and each
+// file's symbols in declaration order, so nothing is buffered beyond one file. An error
+// from emit stops the export and is returned.
+func (s *IndexServer) ExportSymbols(req ExportRequest, emit func(ExportSymbol) error) error {
+	snap := s.acquire()
+	defer snap.release()
+	if snap.engine == nil {
+		return ErrIndexNotReady
+	}
+	indexer := snap.indexer
+
+	fileIDs := indexer.GetAllFileIDsFiltered()
+	paths := make(map[types.FileID]string, len(fileIDs))
+	for _, fileID := range fileIDs {
+		paths[fileID] = indexer.GetFilePath(fileID)
+	}
+	sort.Slice(fileIDs, func(i, j int) bool { return paths[fileIDs[i]] < paths[fileIDs[j]] })
+
+	for _, fileID := range fileIDs {
+		path := paths[fileID]
+		if path == "" {
+			continue
+		}
+		var content []byte
+		if req.Content {
+			content, _ = indexer.GetFileContent(fileID)
+		}
+		for _, sym := range indexer.GetFileEnhancedSymbols(fileID) {
+			out := ExportSymbol{
+				Path:      path,
+				Kind:      sym.Symbol.Type.String(),
+				Name:      sym.Symbol.Name,
+				Parent:    sym.Symbol.Parent,
+				Line:      sym.Symbol.Line,
+				Column:    sym.Symbol.Column,
+				EndLine:   sym.Symbol.EndLine,
+				EndColumn: sym.Symbol.EndColumn,
+				ByteSpan:  [2]int{sym.Symbol.StartByte, sym.Symbol.EndByte},
+				Doc:       sym.DocComment,
+			}
+			if start, end := sym.Symbol.StartByte, sym.Symbol.EndByte; start >= 0 && start < end && end <= len(content) {
+				out.Content = string(content[start:end])
+			}
+			if err := emit(out); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
 }
 
 // handleGetSymbol retrieves symbol information
@@ -279,16 +479,14 @@ func (s *IndexServer) handleGetSymbol(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
-	symbol := s.indexer.GetEnhancedSymbol(req.SymbolID)
+	symbol := indexer.GetEnhancedSymbol(req.SymbolID)
 	if symbol == nil {
 		http.Error(w, fmt.Sprintf("symbol %d not found", req.SymbolID), http.StatusNotFound)
 		return
@@ -310,16 +508,14 @@ func (s *IndexServer) handleGetFileInfo(w http.ResponseWriter, r *http.Request)
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
-	fileInfo := s.indexer.GetFileInfo(req.FileID)
+	fileInfo := indexer.GetFileInfo(req.FileID)
 	if fileInfo == nil {
 		http.Error(w, fmt.Sprintf("file %d not found", req.FileID), http.StatusNotFound)
 		return
@@ -375,17 +571,29 @@ func (s *IndexServer) handlePing(w http.ResponseWriter, r *http.Request) {
 
 // handleStats returns index statistics including file count, symbol count, and memory usage
 func (s *IndexServer) handleStats(w http.ResponseWriter, r *http.Request) {
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	response, err := s.Stats()
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusServiceUnavailable)
 		return
 	}
 
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(response)
+}
+
+// Stats reports index, memory and search cache statistics. It fails with
+// ErrIndexNotReady while the index is being built.
+func (s *IndexServer) Stats() (StatsResponse, error) {
+	snap := s.acquire()
+	defer snap.release()
+
+	if snap.engine == nil {
+		return StatsResponse{}, ErrIndexNotReady
+	}
+	indexer := snap.indexer
+
 	// Get index stats from the indexer
-	indexStats := s.indexer.Stats()
+	indexStats := indexer.Stats()
 
 	// Get memory stats
 	var memStats runtime.MemStats
@@ -415,10 +623,14 @@ func (s *IndexServer) handleStats(w http.ResponseWriter, r *http.Request) {
 		UptimeSeconds:   time.Since(s.startTime).Seconds(),
 		SearchCount:     searchCount,
 		AvgSearchTimeMs: avgSearchTimeMs,
-	}
-
-	w.Header().Set("Content-Type", "application/json")
-	json.NewEncoder(w).Encode(response)
+		Languages:       indexer.LanguageCounts(),
+		ParseWarnings:   indexer.ParseWarnings(),
+		SkippedBinary:   indexer.SkippedBinaryFiles(),
+		SampledOut:      indexer.SampledOutFiles(),
+	}
+	cache := s.searchCache.stats()
+	response.Cache = &cache
+	return response, nil
 }
 
 // handleDefinition searches for symbol definitions by name pattern
@@ -429,14 +641,12 @@ func (s *IndexServer) handleDefinition(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	engine := s.searchEngine
-	s.mu.RUnlock()
-
-	if engine == nil {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	engine := snap.engine
 
 	// Build search options for definition search
 	searchOpts := types.SearchOptions{
@@ -489,14 +699,12 @@ func (s *IndexServer) handleTree(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
 	// Build tree options from request
 	treeOpts := types.TreeOptions{
@@ -508,7 +716,7 @@ func (s *IndexServer) handleTree(w http.ResponseWriter, r *http.Request) {
 	}
 
 	// Generate the function tree using the indexer
-	tree, err := s.indexer.GenerateFunctionTree(req.FunctionName, treeOpts)
+	tree, err := indexer.GenerateFunctionTree(req.FunctionName, treeOpts)
 	if err != nil {
 		response := TreeResponse{
 			Error: fmt.Sprintf("failed to generate tree: %v", err),
@@ -534,29 +742,16 @@ func (s *IndexServer) handleReferences(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	engine := s.searchEngine
-	s.mu.RUnlock()
-
-	if engine == nil {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	engine := snap.engine
 
-	// Build search options for reference search
-	// Unlike definition search, we don't set DeclarationOnly=true
-	// This will find all usages of the symbol, not just declarations
-	searchOpts := types.SearchOptions{
-		DeclarationOnly: false, // Get all usages, not just declarations
-	}
-
-	// Perform search using engine
-	results := engine.SearchWithOptions(req.Pattern, nil, searchOpts)
-
-	// Limit results if requested
-	if req.MaxResults > 0 && len(results) > req.MaxResults {
-		results = results[:req.MaxResults]
-	}
+	// Whole-word usages of the name, without the declarations the extractor recorded
+	// unless they were asked for
+	results := engine.FindReferences(req.Pattern, types.SearchOptions{MaxResults: req.MaxResults}, req.IncludeDefinition)
 
 	// Convert results to ReferenceLocation structs
 	references := make([]ReferenceLocation, 0, len(results))
@@ -575,11 +770,12 @@ func (s *IndexServer) handleReferences(w http.ResponseWriter, r *http.Request) {
 		}
 
 		ref := ReferenceLocation{
-			FilePath: result.Path,
-			Line:     result.Line,
-			Column:   result.Column,
-			Context:  contextStr,
-			Match:    result.Match,
+			FilePath:   result.Path,
+			Line:       result.Line,
+			Column:     result.Column,
+			Context:    contextStr,
+			Match:      result.Match,
+			Definition: result.Definition,
 		}
 		references = append(references, ref)
 	}
@@ -606,36 +802,90 @@ func (s *IndexServer) handleReindex(w http.ResponseWriter, r *http.Request) {
 	}
 
 	// Start re-indexing in background
-	go func() {
-		s.mu.Lock()
-		s.indexingActive = true
-		s.searchEngine = nil // Invalidate during reindex
-		s.mu.Unlock()
+	go s.Reindex(rootPath)
 
-		debug.LogMCP("Re-indexing %s...", rootPath)
-		if err := s.indexer.IndexDirectory(context.Background(), rootPath); err != nil {
-			debug.LogMCP("Re-indexing error: %v", err)
-		} else {
-			debug.LogMCP("Re-indexing completed successfully")
-		}
+	response := ReindexResponse{
+		Success: true,
+		Message: fmt.Sprintf("Re-indexing started for %s", rootPath),
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(response)
+}
 
-		// Recreate search engine
+// Reindex indexes rootPath, or the configured root when it is empty, and returns once
+// the new index serves queries, reporting the symbol counts of the old and new index.
+//
+// The new index is built aside while the old one keeps serving, then swapped in whole:
+// requests already searching the old index finish on it, later ones search the new
+// one, and none sees a partly built index. If the build fails the old index stays. The
+// first build, a shared index (see NewIndexServerWithIndex) and performance
+// { reindex_in_place true } instead rebuild the index in place, holding one index in
+// memory, and searches fail with ErrIndexNotReady meanwhile.
+func (s *IndexServer) Reindex(rootPath string) (ReindexResponse, error) {
+	if rootPath == "" {
+		rootPath = s.cfg.Project.Root
+	}
+
+	s.reindexMu.Lock()
+	defer s.reindexMu.Unlock()
+
+	s.mu.Lock()
+	s.indexingActive = true
+	s.mu.Unlock()
+	defer func() {
 		s.mu.Lock()
-		s.searchEngine = search.NewEngine(s.indexer)
-		s.indexer.SetSearchEngine(s.searchEngine)
 		s.indexingActive = false
 		s.mu.Unlock()
-
-		debug.LogMCP("Index ready after reindex")
 	}()
 
-	response := ReindexResponse{
-		Success: true,
-		Message: fmt.Sprintf("Re-indexing started for %s", rootPath),
+	// Only Reindex publishes, and reindexMu is held, so current stays served until then
+	current := s.index.Load()
+	var resp ReindexResponse
+	if current.engine != nil {
+		resp.OldSymbolCount = current.indexer.GetSymbolCount()
 	}
 
-	w.Header().Set("Content-Type", "application/json")
-	json.NewEncoder(w).Encode(response)
+	indexer := current.indexer
+	inPlace := current.engine == nil || s.sharedIndex || s.cfg.Performance.ReindexInPlace
+	if inPlace {
+		s.publish(indexer, nil) // Waits for the requests still searching the index
+	} else {
+		indexer = indexing.NewMasterIndex(s.cfg)
+	}
+	s.building.Store(indexer)
+	defer s.building.Store(nil)
+
+	debug.LogMCP("Indexing %s...", rootPath)
+	err := indexer.IndexDirectory(context.Background(), rootPath)
+	if err != nil {
+		debug.LogMCP("Indexing error: %v", err)
+		if !inPlace {
+			indexer.Close()
+			return resp, err
+		}
+	} else {
+		debug.LogMCP("Indexing completed successfully")
+	}
+
+	// Create the search engine and swap the index in with it
+	engine := search.NewEngine(indexer)
+	indexer.SetSearchEngine(engine)
+	s.publish(indexer, engine)
+
+	resp.Success = err == nil
+	resp.FileCount = indexer.GetFileCount()
+	resp.SymbolCount = indexer.GetSymbolCount()
+	resp.Message = fmt.Sprintf("indexed %d files, %d symbols (was %d)", resp.FileCount, resp.SymbolCount, resp.OldSymbolCount)
+	debug.LogMCP("Index ready for queries")
+	return resp, err
+}
+
+// FileCount returns the number of indexed files
+func (s *IndexServer) FileCount() int {
+	snap := s.acquire()
+	defer snap.release()
+	return snap.indexer.GetFileCount()
 }
 
 // Wait blocks until the server is shut down
@@ -692,14 +942,12 @@ func (s *IndexServer) handleGitAnalyze(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
 	// Convert scope string to AnalysisScope
 	var scope git.AnalysisScope
@@ -729,7 +977,7 @@ func (s *IndexServer) handleGitAnalyze(w http.ResponseWriter, r *http.Request) {
 	}
 
 	// Create analyzer
-	analyzer := git.NewAnalyzer(gitProvider, s.indexer)
+	analyzer := git.NewAnalyzer(gitProvider, indexer)
 
 	// Build params
 	params := git.DefaultAnalysisParams()
@@ -772,17 +1020,15 @@ func (s *IndexServer) handleListSymbols(w http.ResponseWriter, r *http.Request)
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
 	// Delegate to the indexer's symbol enumeration
-	allFileIDs := s.indexer.GetAllFileIDsFiltered()
+	allFileIDs := indexer.GetAllFileIDsFiltered()
 	kinds := parseHTTPSymbolKinds(req.Kind)
 	maxResults := req.Max
 	if maxResults <= 0 {
@@ -792,11 +1038,11 @@ func (s *IndexServer) handleListSymbols(w http.ResponseWriter, r *http.Request)
 		maxResults = 500
 	}
 
-	tracker := s.indexer.GetRefTracker()
+	tracker := indexer.GetRefTracker()
 
 	var allEntries []ListSymbolsEntry
 	for _, fileID := range allFileIDs {
-		filePath := s.indexer.GetFilePath(fileID)
+		filePath := indexer.GetFilePath(fileID)
 		if filePath == "" {
 			continue
 		}
@@ -809,7 +1055,7 @@ func (s *IndexServer) handleListSymbols(w http.ResponseWriter, r *http.Request)
 				continue
 			}
 		}
-		symbols := s.indexer.GetFileEnhancedSymbols(fileID)
+		symbols := indexer.GetFileEnhancedSymbols(fileID)
 		for _, sym := range symbols {
 			if !matchesHTTPListFilters(sym, kinds, req) {
 				continue
@@ -850,21 +1096,19 @@ func (s *IndexServer) handleInspectSymbol(w http.ResponseWriter, r *http.Request
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
 	var matched []*types.EnhancedSymbol
 
 	if req.ID != "" {
 		symbolID, err := idcodec.DecodeSymbolID(req.ID)
 		if err == nil {
-			tracker := s.indexer.GetRefTracker()
+			tracker := indexer.GetRefTracker()
 			if tracker != nil {
 				if sym := tracker.GetEnhancedSymbol(symbolID); sym != nil {
 					matched = append(matched, sym)
@@ -874,14 +1118,14 @@ func (s *IndexServer) handleInspectSymbol(w http.ResponseWriter, r *http.Request
 	}
 
 	if req.Name != "" && len(matched) == 0 {
-		matched = s.indexer.FindSymbolsByName(req.Name)
+		matched = indexer.FindSymbolsByName(req.Name)
 	}
 
 	// Apply disambiguators
 	if req.File != "" || req.Type != "" {
 		filtered := matched[:0]
 		for _, sym := range matched {
-			filePath := s.indexer.GetFilePath(sym.FileID)
+			filePath := indexer.GetFilePath(sym.FileID)
 			if req.File != "" {
 				m, _ := filepath.Match(req.File, filePath)
 				if !m {
@@ -902,10 +1146,10 @@ func (s *IndexServer) handleInspectSymbol(w http.ResponseWriter, r *http.Request
 		matched = filtered
 	}
 
-	tracker := s.indexer.GetRefTracker()
+	tracker := indexer.GetRefTracker()
 	results := make([]InspectSymbolEntry, len(matched))
 	for i, sym := range matched {
-		results[i] = buildHTTPInspectEntry(sym, s.indexer.GetFilePath(sym.FileID), tracker, s.indexer)
+		results[i] = buildHTTPInspectEntry(sym, indexer.GetFilePath(sym.FileID), tracker, indexer)
 	}
 
 	resp := InspectSymbolHTTPResponse{
@@ -925,14 +1169,12 @@ func (s *IndexServer) handleBrowseFile(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	s.mu.RLock()
-	ready := s.searchEngine != nil
-	s.mu.RUnlock()
-
-	if !ready {
-		http.Error(w, "index not ready - still indexing", http.StatusServiceUnavailable)
+	snap := s.acquireReady(w)
+	if snap == nil {
 		return
 	}
+	defer snap.release()
+	indexer := snap.indexer
 
 	// Find the target file
 	var targetFileID types.FileID
@@ -941,32 +1183,14 @@ func (s *IndexServer) handleBrowseFile(w http.ResponseWriter, r *http.Request) {
 
 	if req.FileID != nil {
 		targetFileID = types.FileID(*req.FileID)
-		targetFilePath = s.indexer.GetFilePath(targetFileID)
+		targetFilePath = indexer.GetFilePath(targetFileID)
 		if targetFilePath != "" {
 			found = true
 		}
 	}
 
 	if !found && req.File != "" {
-		allFileIDs := s.indexer.GetAllFileIDsFiltered()
-		for _, fid := range allFileIDs {
-			fp := s.indexer.GetFilePath(fid)
-			if fp == "" {
-				continue
-			}
-			if fp == req.File || strings.HasSuffix(fp, "/"+req.File) || strings.HasSuffix(fp, "\\"+req.File) {
-				targetFileID = fid
-				targetFilePath = fp
-				found = true
-				break
-			}
-			if m, _ := filepath.Match(req.File, fp); m {
-				targetFileID = fid
-				targetFilePath = fp
-				found = true
-				break
-			}
-		}
+		targetFileID, targetFilePath, found = findFile(indexer, req.File)
 	}
 
 	if !found {
@@ -977,13 +1201,13 @@ func (s *IndexServer) handleBrowseFile(w http.ResponseWriter, r *http.Request) {
 	}
 
 	kinds := parseHTTPSymbolKinds(req.Kind)
-	tracker := s.indexer.GetRefTracker()
+	tracker := indexer.GetRefTracker()
 	maxResults := req.Max
 	if maxResults <= 0 {
 		maxResults = 100
 	}
 
-	symbols := s.indexer.GetFileEnhancedSymbols(targetFileID)
+	symbols := indexer.GetFileEnhancedSymbols(targetFileID)
 	var entries []ListSymbolsEntry
 	for _, sym := range symbols {
 		if kinds != nil && !kinds[sym.Symbol.Type] {
@@ -1019,7 +1243,7 @@ func (s *IndexServer) handleBrowseFile(w http.ResponseWriter, r *http.Request) {
 	}
 
 	if req.ShowImports {
-		fileInfo := s.indexer.GetFile(targetFileID)
+		fileInfo := indexer.GetFile(targetFileID)
 		if fileInfo != nil {
 			imports := make([]string, len(fileInfo.Imports))
 			for i, imp := range fileInfo.Imports {
@@ -1038,6 +1262,87 @@ func (s *IndexServer) handleBrowseFile(w http.ResponseWriter, r *http.Request) {
 	json.NewEncoder(w).Encode(resp)
 }
 
+// handleSymbolAt returns the innermost symbol defined at a byte offset of a file
+func (s *IndexServer) handleSymbolAt(w http.ResponseWriter, r *http.Request) {
+	var req SymbolAtRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	snap := s.acquireReady(w)
+	if snap == nil {
+		return
+	}
+	defer snap.release()
+	indexer := snap.indexer
+
+	var resp SymbolAtResponse
+	if fileID, filePath, found := findFile(indexer, req.File); !found {
+		resp.Error = fmt.Sprintf("file not found: %s", req.File)
+	} else {
+		resp.File = filePath
+		if sym := indexer.GetEnhancedSymbolAtOffset(fileID, req.Offset); sym != nil {
+			entry := buildHTTPSymbolEntry(sym, filePath, indexer.GetRefTracker())
+			resp.Symbol = &entry
+		}
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(resp)
+}
+
+// handleSymbolPrefix returns the symbols whose name starts with a prefix, looked up in
+// the symbol name trie
+func (s *IndexServer) handleSymbolPrefix(w http.ResponseWriter, r *http.Request) {
+	var req SymbolPrefixRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+
+	snap := s.acquireReady(w)
+	if snap == nil {
+		return
+	}
+	defer snap.release()
+	indexer := snap.indexer
+
+	resp := SymbolPrefixResponse{Symbols: []ListSymbolsEntry{}}
+	if req.Max < 0 {
+		resp.Error = fmt.Sprintf("max must not be negative, got %d", req.Max)
+	} else {
+		refTracker := indexer.GetRefTracker()
+		for _, sym := range indexer.FindSymbolsByPrefix(req.Prefix, req.IgnoreCase, req.Max) {
+			resp.Symbols = append(resp.Symbols, buildHTTPSymbolEntry(sym, indexer.GetFilePath(sym.FileID), refTracker))
+		}
+	}
+
+	w.Header().Set("Content-Type", "application/json")
+	json.NewEncoder(w).Encode(resp)
+}
+
+// findFile resolves a file argument to an indexed file: an exact path, a path suffix
+// such as "server.go" or "internal/server.go", or a glob over the full path
+func findFile(indexer *indexing.MasterIndex, file string) (types.FileID, string, bool) {
+	if file == "" {
+		return 0, "", false
+	}
+	for _, fid := range indexer.GetAllFileIDsFiltered() {
+		fp := indexer.GetFilePath(fid)
+		if fp == "" {
+			continue
+		}
+		if fp == file || strings.HasSuffix(fp, "/"+file) || strings.HasSuffix(fp, "\\"+file) {
+			return fid, fp, true
+		}
+		if m, _ := filepath.Match(file, fp); m {
+			return fid, fp, true
+		}
+	}
+	return 0, "", false
+}
+
 // ========== HTTP helpers ==========
 
 func parseHTTPSymbolKinds(kindStr string) map[types.SymbolType]bool {
@@ -1109,12 +1414,23 @@ func matchesHTTPListFilters(sym *types.EnhancedSymbol, kinds map[types.SymbolTyp
 	return true
 }
 
+// NewListSymbolsEntry describes sym of filePath the way the browse and symbols
+// endpoints do, for callers that parse files without a server
+func NewListSymbolsEntry(sym *types.EnhancedSymbol, filePath string) ListSymbolsEntry {
+	return buildHTTPSymbolEntry(sym, filePath, nil)
+}
+
 func buildHTTPSymbolEntry(sym *types.EnhancedSymbol, filePath string, tracker *core.ReferenceTracker) ListSymbolsEntry {
 	entry := ListSymbolsEntry{
 		Name:           sym.Symbol.Name,
 		Type:           sym.Symbol.Type.String(),
+		Parent:         sym.Symbol.Parent,
 		File:           filePath,
 		Line:           sym.Symbol.Line,
+		Column:         sym.Symbol.Column,
+		EndLine:        sym.Symbol.EndLine,
+		EndColumn:      sym.Symbol.EndColumn,
+		ByteSpan:       [2]int{sym.Symbol.StartByte, sym.Symbol.EndByte},
 		ObjectID:       searchtypes.EncodeSymbolID(sym.ID),
 		IsExported:     sym.IsExported,
 		Signature:      sym.Signature,
@@ -18,6 +18,7 @@ import (
 	"github.com/standardbeagle/lci/internal/config"
 	"github.com/standardbeagle/lci/internal/indexing"
 	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
 	"github.com/standardbeagle/lci/internal/types"
 )
 
@@ -90,6 +91,21 @@ func TestFunction() string {
 	}
 	assert.GreaterOrEqual(t, len(results), 1, "Should find TestFunction (found %d results)", len(results))
 
+	// Limiting keeps the best hits but still reports how many were found
+	limited, total, err := client.SearchWithTotal("TestFunction", searchOpts, 1)
+	require.NoError(t, err)
+	assert.Len(t, limited, 1)
+	assert.Equal(t, len(results), total)
+
+	// Streaming stops once maxResults hits have been sent
+	var streamed []searchtypes.Result
+	_, err = client.SearchStream("TestFunction", searchOpts, 1, func(r searchtypes.Result) error {
+		streamed = append(streamed, r)
+		return nil
+	})
+	require.NoError(t, err)
+	assert.Len(t, streamed, 1)
+
 	// Verify result contains our file
 	foundTestFile := false
 	for _, r := range results {
@@ -170,7 +186,7 @@ func UpdatedFunction() string {
 	require.NoError(t, err)
 
 	// Manually trigger re-index via server's indexer
-	err = srv.indexer.IndexFile(testFile)
+	err = srv.index.Load().indexer.IndexFile(testFile)
 	require.NoError(t, err)
 
 	// Small delay for index to propagate
@@ -522,7 +538,7 @@ func DeleteFunction() {}
 	err = os.Remove(deleteFile)
 	require.NoError(t, err)
 
-	err = srv.indexer.RemoveFile(deleteFile)
+	err = srv.index.Load().indexer.RemoveFile(deleteFile)
 	require.NoError(t, err)
 
 	time.Sleep(100 * time.Millisecond)
@@ -880,14 +896,19 @@ func AnotherCaller() string {
 	require.NoError(t, err)
 
 	// Test finding references to HelperFunction
-	references, err := client.GetReferences("HelperFunction", 100)
+	references, err := client.GetReferences("HelperFunction", 100, false)
 	require.NoError(t, err)
-	// Should find: definition + 2 call sites = at least 3 references
+	// Should find the 2 call sites; the definition is left out
 	assert.GreaterOrEqual(t, len(references), 2, "Should find multiple references to HelperFunction")
 	t.Logf("Found %d references to 'HelperFunction'", len(references))
 	for i, ref := range references {
 		t.Logf("  Reference %d: %s:%d - %s", i+1, ref.FilePath, ref.Line, ref.Match)
+		assert.False(t, ref.Definition)
 	}
+
+	withDef, err := client.GetReferences("HelperFunction", 100, true)
+	require.NoError(t, err)
+	assert.Len(t, withDef, len(references)+1, "--include-def adds the definition")
 }
 
 // TestServerIntegration_TreeEndpoint tests the /tree endpoint
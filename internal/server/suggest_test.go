@@ -0,0 +1,40 @@
+package server
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/config"
+)
+
+func TestIndexServer_SearchSuggestions(t *testing.T) {
+	testDir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(testDir, "a.go"), []byte("package a\n\nfunc get_user() {}\n"), 0644))
+	cfg := &config.Config{
+		Project: config.Project{Root: testDir},
+		Include: []string{"*.go"},
+		Index:   config.Index{MaxFileSize: 10 * 1024 * 1024},
+	}
+	srv, err := NewIndexServer(cfg)
+	require.NoError(t, err)
+	_, err = srv.Reindex("")
+	require.NoError(t, err)
+
+	resp, err := srv.Search(SearchRequest{Pattern: "get_usr", Suggest: true})
+	require.NoError(t, err)
+	assert.Zero(t, resp.Total)
+	assert.Equal(t, []string{"get_user"}, resp.Suggestions)
+
+	resp, err = srv.Search(SearchRequest{Pattern: "get_usr"})
+	require.NoError(t, err)
+	assert.Empty(t, resp.Suggestions, "suggestions are only made on request")
+
+	resp, err = srv.Search(SearchRequest{Pattern: "get_use", Suggest: true})
+	require.NoError(t, err)
+	assert.NotZero(t, resp.Total)
+	assert.Empty(t, resp.Suggestions, "a search with hits needs none")
+}
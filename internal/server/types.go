@@ -1,6 +1,8 @@
 package server
 
 import (
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
 	"github.com/standardbeagle/lci/internal/searchtypes"
 	"github.com/standardbeagle/lci/internal/types"
 )
@@ -13,7 +15,9 @@ type IndexStatus struct {
 	FileCount      int     `json:"file_count"`
 	SymbolCount    int     `json:"symbol_count"`
 	IndexingActive bool    `json:"indexing_active"`
-	Progress       float64 `json:"progress"`
+	Progress       float64 `json:"progress"`                  // Fraction of TotalFiles processed, 1 when not indexing
+	FilesProcessed int     `json:"files_processed,omitempty"` // Files indexed so far while IndexingActive
+	TotalFiles     int     `json:"total_files,omitempty"`     // Files found to index, 0 while they are still being discovered
 	Error          string  `json:"error,omitempty"`
 }
 
@@ -21,13 +25,35 @@ type IndexStatus struct {
 type SearchRequest struct {
 	Pattern    string              `json:"pattern"`
 	Options    types.SearchOptions `json:"options"`
-	MaxResults int                 `json:"max_results,omitempty"`
+	Offset     int                 `json:"offset,omitempty"`      // Skip the best N after ranking, before MaxResults
+	MaxResults int                 `json:"max_results,omitempty"` // Keep the best N after ranking, 0 = all
+	GroupBy    string              `json:"group_by,omitempty"`    // GroupBySymbol nests the kept hits under their enclosing symbol
+	TimeoutMs  int                 `json:"timeout_ms,omitempty"`  // Stop searching further files after this long, 0 = no limit
+	Suggest    bool                `json:"suggest,omitempty"`     // Fill SearchResponse.Suggestions when nothing is found
 }
 
+// GroupBySymbol is the SearchRequest.GroupBy value that fills SearchResponse.Groups
+const GroupBySymbol = "symbol"
+
 // SearchResponse contains search results
 type SearchResponse struct {
-	Results []searchtypes.Result `json:"results"`
-	Error   string               `json:"error,omitempty"`
+	Results     []searchtypes.Result `json:"results"`
+	Groups      []search.SymbolGroup `json:"groups,omitempty"`      // Results nested by symbol, with GroupBy
+	Total       int                  `json:"total"`                 // Hits found before Offset and MaxResults were applied
+	TimedOut    bool                 `json:"timed_out,omitempty"`   // TimeoutMs passed; files left unsearched may hold more hits
+	CapReached  bool                 `json:"cap_reached,omitempty"` // The search found more than Options.ResultCap hits and kept that many
+	Suggestions []string             `json:"suggestions,omitempty"` // With Suggest and no hits, symbol names close to the pattern
+	Error       string               `json:"error,omitempty"`
+}
+
+// SearchStreamEvent is one line of a /search/stream response: a hit, or an error that
+// ends the stream. The request's MaxResults stops the search once that many hits have
+// been sent. A stream that ended at Options.ResultCap with hits left unsent ends with
+// a CapReached event.
+type SearchStreamEvent struct {
+	Result     *searchtypes.Result `json:"result,omitempty"`
+	Error      string              `json:"error,omitempty"`
+	CapReached bool                `json:"cap_reached,omitempty"`
 }
 
 // GetSymbolRequest requests symbol information
@@ -78,10 +104,32 @@ type ReindexRequest struct {
 	Path string `json:"path,omitempty"` // Empty means use configured root
 }
 
-// ReindexResponse confirms re-indexing started
+// ReindexResponse confirms re-indexing started, or over the framed protocol that the
+// new index is serving, with the symbol counts of the index it replaced and its own
 type ReindexResponse struct {
-	Success bool   `json:"success"`
-	Message string `json:"message,omitempty"`
+	Success        bool   `json:"success"`
+	Message        string `json:"message,omitempty"`
+	FileCount      int    `json:"file_count,omitempty"`
+	SymbolCount    int    `json:"symbol_count,omitempty"`
+	OldSymbolCount int    `json:"old_symbol_count,omitempty"`
+}
+
+// FramedRequest is one request to a `lci serve` socket. Type is "search" (the
+// default), "reindex" or "stats", and selects which of the other fields is read; a
+// stats request has none.
+type FramedRequest struct {
+	Type    string          `json:"type,omitempty"`
+	Search  *SearchRequest  `json:"search,omitempty"`
+	Reindex *ReindexRequest `json:"reindex,omitempty"`
+}
+
+// FramedResponse answers a FramedRequest. Error is set when the request could not be
+// served at all; search errors such as an invalid pattern are reported in Search.
+type FramedResponse struct {
+	Search  *SearchResponse  `json:"search,omitempty"`
+	Reindex *ReindexResponse `json:"reindex,omitempty"`
+	Stats   *StatsResponse   `json:"stats,omitempty"`
+	Error   string           `json:"error,omitempty"`
 }
 
 // StatsRequest requests index statistics
@@ -89,18 +137,23 @@ type StatsRequest struct{}
 
 // StatsResponse contains index statistics
 type StatsResponse struct {
-	FileCount       int     `json:"file_count"`
-	SymbolCount     int     `json:"symbol_count"`
-	IndexSizeBytes  int64   `json:"index_size_bytes"`
-	BuildDurationMs int64   `json:"build_duration_ms"`
-	MemoryAllocMB   float64 `json:"memory_alloc_mb"`
-	MemoryTotalMB   float64 `json:"memory_total_mb"`
-	MemoryHeapMB    float64 `json:"memory_heap_mb"`
-	NumGoroutines   int     `json:"num_goroutines"`
-	UptimeSeconds   float64 `json:"uptime_seconds"`
-	SearchCount     int64   `json:"search_count,omitempty"`
-	AvgSearchTimeMs float64 `json:"avg_search_time_ms,omitempty"`
-	Error           string  `json:"error,omitempty"`
+	FileCount       int                     `json:"file_count"`
+	SymbolCount     int                     `json:"symbol_count"`
+	IndexSizeBytes  int64                   `json:"index_size_bytes"`
+	BuildDurationMs int64                   `json:"build_duration_ms"`
+	MemoryAllocMB   float64                 `json:"memory_alloc_mb"`
+	MemoryTotalMB   float64                 `json:"memory_total_mb"`
+	MemoryHeapMB    float64                 `json:"memory_heap_mb"`
+	NumGoroutines   int                     `json:"num_goroutines"`
+	UptimeSeconds   float64                 `json:"uptime_seconds"`
+	SearchCount     int64                   `json:"search_count,omitempty"`
+	AvgSearchTimeMs float64                 `json:"avg_search_time_ms,omitempty"`
+	Languages       map[string]int          `json:"languages,omitempty"`      // Indexed files per detected language, "text" for the rest
+	ParseWarnings   []indexing.ParseWarning `json:"parse_warnings,omitempty"` // Files that parsed with syntax errors
+	SkippedBinary   int                     `json:"skipped_binary,omitempty"` // Files skipped as binary
+	SampledOut      int                     `json:"sampled_out,omitempty"`    // Files left out by --sample
+	Cache           *SearchCacheStats       `json:"cache,omitempty"`          // Search result cache counters
+	Error           string                  `json:"error,omitempty"`
 }
 
 // DefinitionRequest requests symbol definition locations
@@ -128,17 +181,19 @@ type DefinitionResponse struct {
 
 // ReferencesRequest requests symbol reference locations (usages)
 type ReferencesRequest struct {
-	Pattern    string `json:"pattern"`               // Symbol name pattern to search for
-	MaxResults int    `json:"max_results,omitempty"` // Maximum number of results to return
+	Pattern           string `json:"pattern"`                      // Symbol name to search for
+	MaxResults        int    `json:"max_results,omitempty"`        // Maximum number of results to return
+	IncludeDefinition bool   `json:"include_definition,omitempty"` // Also return the symbol's declarations
 }
 
 // ReferenceLocation represents a single reference location
 type ReferenceLocation struct {
-	FilePath string `json:"file_path"` // Full file path
-	Line     int    `json:"line"`      // Line number (1-based)
-	Column   int    `json:"column"`    // Column number (0-based)
-	Context  string `json:"context"`   // Line content or surrounding context
-	Match    string `json:"match"`     // The matched text
+	FilePath   string `json:"file_path"`            // Full file path
+	Line       int    `json:"line"`                 // Line number (1-based)
+	Column     int    `json:"column"`               // Column number (0-based)
+	Context    string `json:"context"`              // Line content or surrounding context
+	Match      string `json:"match"`                // The matched text
+	Definition bool   `json:"definition,omitempty"` // The symbol's declaration rather than a usage
 }
 
 // ReferencesResponse contains reference search results
@@ -201,8 +256,13 @@ type ListSymbolsRequest struct {
 type ListSymbolsEntry struct {
 	Name           string   `json:"name"`
 	Type           string   `json:"type"`
+	Parent         string   `json:"parent,omitempty"` // Enclosing type, e.g. a method's receiver or impl
 	File           string   `json:"file"`
 	Line           int      `json:"line"`
+	Column         int      `json:"column,omitempty"`     // 1-based byte column of the declaration start
+	EndLine        int      `json:"end_line,omitempty"`   // Line of the declaration end
+	EndColumn      int      `json:"end_column,omitempty"` // 1-based byte column one past the declaration end
+	ByteSpan       [2]int   `json:"byte_span"`            // [start, end) byte offsets of the declaration
 	ObjectID       string   `json:"object_id,omitempty"`
 	IsExported     bool     `json:"is_exported"`
 	Signature      string   `json:"signature,omitempty"`
@@ -303,6 +363,62 @@ type BrowseFileInfoEntry struct {
 	Language string `json:"language,omitempty"`
 }
 
+// SymbolAtRequest asks for the symbol defined at a byte offset of a file
+type SymbolAtRequest struct {
+	File   string `json:"file"`   // Path, matched like BrowseFileRequest.File
+	Offset int    `json:"offset"` // Byte offset into the file
+}
+
+// SymbolAtResponse holds the innermost symbol containing the offset, nil when the
+// offset is outside every symbol
+type SymbolAtResponse struct {
+	File   string            `json:"file"`
+	Symbol *ListSymbolsEntry `json:"symbol"`
+	Error  string            `json:"error,omitempty"`
+}
+
+// SymbolPrefixRequest asks for the symbols whose name starts with Prefix
+type SymbolPrefixRequest struct {
+	Prefix     string `json:"prefix"`
+	IgnoreCase bool   `json:"ignore_case,omitempty"`
+	Max        int    `json:"max,omitempty"` // Most symbols returned, 0 = all
+}
+
+// SymbolPrefixResponse holds the matching symbols in the order of their lowercase names
+type SymbolPrefixResponse struct {
+	Symbols []ListSymbolsEntry `json:"symbols"`
+	Error   string             `json:"error,omitempty"`
+}
+
+// ExportRequest asks for every symbol in the index, streamed from /export as one
+// ExportStreamEvent per line
+type ExportRequest struct {
+	Content bool `json:"content,omitempty"` // Fill ExportSymbol.Content with each symbol's source
+}
+
+// ExportSymbol is one symbol of the index as `lci export` writes it. Line/Column and
+// EndLine/EndColumn are 1-based; columns count bytes.
+type ExportSymbol struct {
+	Path      string `json:"path"`
+	Kind      string `json:"kind"`
+	Name      string `json:"name"`
+	Parent    string `json:"parent,omitempty"` // Enclosing type, e.g. a method's receiver or impl
+	Line      int    `json:"line"`
+	Column    int    `json:"column,omitempty"`
+	EndLine   int    `json:"end_line,omitempty"`
+	EndColumn int    `json:"end_column,omitempty"`
+	ByteSpan  [2]int `json:"byte_span"`         // [start, end) byte offsets of the declaration
+	Doc       string `json:"doc,omitempty"`     // Doc comment preceding the declaration
+	Content   string `json:"content,omitempty"` // Source bytes of ByteSpan, with ExportRequest.Content
+}
+
+// ExportStreamEvent is one line of an /export response: a symbol, or an error that
+// ends the stream
+type ExportStreamEvent struct {
+	Symbol *ExportSymbol `json:"symbol,omitempty"`
+	Error  string        `json:"error,omitempty"`
+}
+
 // FileStatsEntry for file-level statistics
 type FileStatsEntry struct {
 	SymbolCount   int     `json:"symbol_count"`
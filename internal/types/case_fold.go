@@ -0,0 +1,92 @@
+package types
+
+import (
+	"unicode"
+	"unicode/utf8"
+)
+
+// IndexFold returns the byte span [start, end) of the first case-insensitive occurrence
+// of pattern in s, or (-1, -1) if there is none.
+//
+// Case is folded while comparing, so no lowercased copy of s is made. The span is
+// measured in s, which matters for the few runes whose folded forms differ in UTF-8
+// length (e.g. the Kelvin sign and 'k').
+func IndexFold(s, pattern []byte) (start, end int) {
+	if len(pattern) == 0 {
+		return -1, -1
+	}
+	if isASCIIBytes(pattern) {
+		return indexFoldASCII(s, pattern)
+	}
+	return indexFoldUnicode(s, pattern)
+}
+
+// indexFoldASCII handles the common case of an ASCII pattern byte by byte
+func indexFoldASCII(s, pattern []byte) (int, int) {
+	n := len(pattern)
+	first := lowerASCII(pattern[0])
+	for i := 0; i+n <= len(s); i++ {
+		if lowerASCII(s[i]) != first {
+			continue
+		}
+		j := 1
+		for j < n && lowerASCII(s[i+j]) == lowerASCII(pattern[j]) {
+			j++
+		}
+		if j == n {
+			return i, i + n
+		}
+	}
+	return -1, -1
+}
+
+// indexFoldUnicode tries a rune-wise fold comparison at every rune boundary of s
+func indexFoldUnicode(s, pattern []byte) (int, int) {
+	for i := 0; i < len(s); {
+		if n, ok := prefixFold(s[i:], pattern); ok {
+			return i, i + n
+		}
+		_, size := utf8.DecodeRune(s[i:])
+		i += size
+	}
+	return -1, -1
+}
+
+// prefixFold reports whether s starts with pattern under simple case folding and, if
+// so, how many bytes of s the match covers
+func prefixFold(s, pattern []byte) (int, bool) {
+	si := 0
+	for pi := 0; pi < len(pattern); {
+		if si >= len(s) {
+			return 0, false
+		}
+		pr, psize := utf8.DecodeRune(pattern[pi:])
+		sr, ssize := utf8.DecodeRune(s[si:])
+		if !equalRuneFold(pr, sr) {
+			return 0, false
+		}
+		pi += psize
+		si += ssize
+	}
+	return si, true
+}
+
+// equalRuneFold walks the simple-fold orbit of a looking for b, as strings.EqualFold does
+func equalRuneFold(a, b rune) bool {
+	if a == b {
+		return true
+	}
+	for r := unicode.SimpleFold(a); r != a; r = unicode.SimpleFold(r) {
+		if r == b {
+			return true
+		}
+	}
+	return false
+}
+
+func lowerASCII(b byte) byte {
+	if b >= 'A' && b <= 'Z' {
+		return b + 32
+	}
+	return b
+}
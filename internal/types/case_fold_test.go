@@ -0,0 +1,46 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestIndexFold(t *testing.T) {
+	tests := []struct {
+		name      string
+		s         string
+		pattern   string
+		wantStart int
+		wantEnd   int
+	}{
+		{"exact", "find user here", "user", 5, 9},
+		{"upper content", "type User struct", "user", 5, 9},
+		{"upper pattern", "get_user()", "USER", 4, 8},
+		{"first of several", "USER user User", "user", 0, 4},
+		{"at end", "abcXYZ", "xyz", 3, 6},
+		{"no match", "username", "users", -1, -1},
+		{"pattern longer than text", "us", "user", -1, -1},
+		{"empty pattern", "user", "", -1, -1},
+		{"non-ascii pattern", "Straße STRASSE", "straße", 0, 7},
+		{"greek fold", "ΣΊΣΥΦΟΣ", "σίσυφος", 0, 14},
+		{"span measured in text", "temp 5k", "5\u212a", 5, 7}, // Kelvin sign pattern, ASCII text
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			start, end := IndexFold([]byte(tt.s), []byte(tt.pattern))
+			assert.Equal(t, tt.wantStart, start)
+			assert.Equal(t, tt.wantEnd, end)
+		})
+	}
+}
+
+func TestIndexFold_NoAllocations(t *testing.T) {
+	content := []byte("package main\n\nfunc FindUser(id string) (*User, error) {}\n")
+	pattern := []byte("finduser")
+	allocs := testing.AllocsPerRun(100, func() {
+		IndexFold(content, pattern)
+	})
+	assert.Zero(t, allocs)
+}
@@ -0,0 +1,138 @@
+package types
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+	"sync"
+	"unicode/utf16"
+	"unicode/utf8"
+)
+
+// PositionEncoding is the unit a Position column counts in
+type PositionEncoding uint8
+
+const (
+	// PositionEncodingUTF8 counts columns in bytes
+	PositionEncodingUTF8 PositionEncoding = iota
+	// PositionEncodingUTF16 counts columns in UTF-16 code units, as VS Code and LSP
+	// clients do by default
+	PositionEncodingUTF16
+)
+
+func (e PositionEncoding) String() string {
+	if e == PositionEncodingUTF16 {
+		return "utf-16"
+	}
+	return "utf-8"
+}
+
+// ParsePositionEncoding accepts "utf-8" and "utf-16", with or without the dash
+func ParsePositionEncoding(s string) (PositionEncoding, error) {
+	switch strings.ToLower(strings.TrimSpace(s)) {
+	case "", "utf-8", "utf8":
+		return PositionEncodingUTF8, nil
+	case "utf-16", "utf16":
+		return PositionEncodingUTF16, nil
+	}
+	return PositionEncodingUTF8, fmt.Errorf("unknown position encoding %q (expected utf-8 or utf-16)", s)
+}
+
+// Position is a 1-based line and column. Spans are stored as byte offsets; positions
+// are derived from them through a LineIndex when displayed.
+type Position struct {
+	Line   int `json:"line"`
+	Column int `json:"column"`
+}
+
+// LineRange is an inclusive range of 1-based lines
+type LineRange struct {
+	Start int `json:"start"`
+	End   int `json:"end"`
+}
+
+// LineIndex converts between byte offsets and positions in one file's content. Line
+// starts are computed on first use, so building an index for a file that is never
+// queried costs nothing.
+type LineIndex struct {
+	content []byte
+	once    sync.Once
+	offsets []int
+}
+
+// NewLineIndex creates an index over content, which must not change afterwards
+func NewLineIndex(content []byte) *LineIndex {
+	return &LineIndex{content: content}
+}
+
+func (li *LineIndex) lineOffsets() []int {
+	li.once.Do(func() {
+		li.offsets = ComputeLineOffsets(li.content)
+	})
+	return li.offsets
+}
+
+// LineCount returns the number of lines, counting a trailing empty line after a final
+// newline
+func (li *LineIndex) LineCount() int {
+	return len(li.lineOffsets())
+}
+
+// Position returns the position of a byte offset. Offsets outside the content are
+// clamped to its start or end.
+func (li *LineIndex) Position(offset int, enc PositionEncoding) Position {
+	offset = max(0, min(offset, len(li.content)))
+	offsets := li.lineOffsets()
+	line := sort.Search(len(offsets), func(i int) bool { return offsets[i] > offset }) - 1
+
+	column := offset - offsets[line]
+	if enc == PositionEncodingUTF16 {
+		column = utf16Len(li.content[offsets[line]:offset])
+	}
+	return Position{Line: line + 1, Column: column + 1}
+}
+
+// LineSpan returns the lines the byte span [start, end) covers. A span ending just after
+// a newline ends on the line holding that newline, and an empty span covers the line of
+// its start.
+func (li *LineIndex) LineSpan(start, end int) LineRange {
+	first := li.Position(start, PositionEncodingUTF8).Line
+	if end <= start+1 {
+		return LineRange{Start: first, End: first}
+	}
+	return LineRange{Start: first, End: li.Position(end-1, PositionEncodingUTF8).Line}
+}
+
+// Offset returns the byte offset of a position. Lines outside the content and columns
+// past the end of their line are clamped.
+func (li *LineIndex) Offset(pos Position, enc PositionEncoding) int {
+	offsets := li.lineOffsets()
+	line := max(0, min(pos.Line-1, len(offsets)-1))
+	start, end := offsets[line], len(li.content)
+	if line+1 < len(offsets) {
+		end = TrimLineEnding(li.content, start, offsets[line+1]) // Stop before the newline
+	}
+
+	column := max(pos.Column-1, 0)
+	if enc == PositionEncodingUTF8 {
+		return min(start+column, end)
+	}
+	offset := start
+	for units := 0; offset < end && units < column; {
+		r, size := utf8.DecodeRune(li.content[offset:end])
+		units += utf16.RuneLen(r)
+		offset += size
+	}
+	return offset
+}
+
+// utf16Len counts the UTF-16 code units needed to encode b
+func utf16Len(b []byte) int {
+	n := 0
+	for len(b) > 0 {
+		r, size := utf8.DecodeRune(b)
+		n += utf16.RuneLen(r)
+		b = b[size:]
+	}
+	return n
+}
@@ -0,0 +1,77 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLineIndex(t *testing.T) {
+	// "é" is 2 bytes and 1 UTF-16 unit, "😀" is 4 bytes and 2 UTF-16 units
+	content := []byte("fn a() {}\nlet é = \"😀x\";\n")
+	li := NewLineIndex(content)
+	require.Equal(t, 3, li.LineCount())
+
+	tests := []struct {
+		name   string
+		offset int
+		utf8   Position
+		utf16  Position
+	}{
+		{"file start", 0, Position{1, 1}, Position{1, 1}},
+		{"newline", 9, Position{1, 10}, Position{1, 10}},
+		{"second line start", 10, Position{2, 1}, Position{2, 1}},
+		{"after two-byte rune", 16, Position{2, 7}, Position{2, 6}},
+		{"after four-byte rune", 24, Position{2, 15}, Position{2, 12}},
+		{"end of content", len(content), Position{3, 1}, Position{3, 1}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			assert.Equal(t, tt.utf8, li.Position(tt.offset, PositionEncodingUTF8))
+			assert.Equal(t, tt.utf16, li.Position(tt.offset, PositionEncodingUTF16))
+			assert.Equal(t, tt.offset, li.Offset(tt.utf8, PositionEncodingUTF8))
+			assert.Equal(t, tt.offset, li.Offset(tt.utf16, PositionEncodingUTF16))
+		})
+	}
+
+	assert.Equal(t, Position{3, 1}, li.Position(1000, PositionEncodingUTF8), "offsets are clamped")
+	assert.Equal(t, 9, li.Offset(Position{1, 80}, PositionEncodingUTF8), "columns clamp to the line end")
+	assert.Equal(t, len(content), li.Offset(Position{99, 1}, PositionEncodingUTF16), "lines clamp to the content")
+}
+
+func TestLineIndex_LineSpan(t *testing.T) {
+	li := NewLineIndex([]byte("one\ntwo\nthree\n"))
+	assert.Equal(t, LineRange{1, 1}, li.LineSpan(0, 3), "within a line")
+	assert.Equal(t, LineRange{1, 1}, li.LineSpan(0, 4), "up to and including the newline")
+	assert.Equal(t, LineRange{1, 2}, li.LineSpan(2, 5), "across one newline")
+	assert.Equal(t, LineRange{1, 3}, li.LineSpan(0, 13), "across two newlines")
+	assert.Equal(t, LineRange{2, 2}, li.LineSpan(4, 4), "empty span")
+}
+
+func TestLineIndex_CRLF(t *testing.T) {
+	content := []byte("one\r\ntwo\nthree\r\n")
+	li := NewLineIndex(content)
+	assert.Equal(t, 4, li.LineCount(), "\"\\r\\n\" is one line break")
+	assert.Equal(t, Position{2, 1}, li.Position(5, PositionEncodingUTF8))
+	assert.Equal(t, Position{3, 1}, li.Position(9, PositionEncodingUTF8))
+	assert.Equal(t, 3, li.Offset(Position{1, 80}, PositionEncodingUTF8), "columns clamp before the \"\\r\\n\"")
+	assert.Equal(t, 8, li.Offset(Position{2, 80}, PositionEncodingUTF16), "and before a lone \"\\n\"")
+	assert.Equal(t, []byte("three"), GetLineFromOffsets(content, ComputeLineOffsets(content), 3))
+}
+
+func TestParsePositionEncoding(t *testing.T) {
+	for input, want := range map[string]PositionEncoding{
+		"":       PositionEncodingUTF8,
+		"utf-8":  PositionEncodingUTF8,
+		"UTF16":  PositionEncodingUTF16,
+		"utf-16": PositionEncodingUTF16,
+	} {
+		got, err := ParsePositionEncoding(input)
+		require.NoError(t, err, input)
+		assert.Equal(t, want, got, input)
+	}
+	_, err := ParsePositionEncoding("latin1")
+	assert.Error(t, err)
+	assert.Equal(t, "utf-16", PositionEncodingUTF16.String())
+}
@@ -0,0 +1,88 @@
+package types
+
+import "strings"
+
+// NormalizeSignature puts a signature fragment, such as a symbol's parameter list and
+// return type or the value of a sig: query term, into the form they are compared in:
+// whitespace runs collapse to one space, spaces just inside brackets and before commas
+// are dropped, and so are trailing commas, so a signature split over lines reads like
+// one written on a line. With stripGenerics, Rust lifetimes and everything between
+// angle brackets are removed as well, so (&'a str) -> Vec<T> becomes (&str) -> Vec; an
+// unclosed < drops the rest of the fragment.
+func NormalizeSignature(sig string, stripGenerics bool) string {
+	if stripGenerics {
+		sig = stripSignatureGenerics(sig)
+	}
+	out := make([]byte, 0, len(sig))
+	space := false
+	for i := 0; i < len(sig); i++ {
+		c := sig[i]
+		if c == ' ' || c == '\t' || c == '\n' || c == '\r' {
+			space = true
+			continue
+		}
+		if isClosingBracket(c) && len(out) > 0 && out[len(out)-1] == ',' {
+			out = out[:len(out)-1] // Trailing comma of a list split over lines
+		}
+		if space && len(out) > 0 && !isOpeningBracket(out[len(out)-1]) && !isClosingBracket(c) && c != ',' {
+			out = append(out, ' ')
+		}
+		space = false
+		out = append(out, c)
+	}
+	return string(out)
+}
+
+// stripSignatureGenerics removes lifetimes and angle-bracketed arguments from sig. The
+// > of -> and => is an arrow, not a bracket.
+func stripSignatureGenerics(sig string) string {
+	var b strings.Builder
+	depth := 0
+	for i := 0; i < len(sig); i++ {
+		c := sig[i]
+		switch {
+		case c == '<':
+			depth++
+		case c == '>' && i > 0 && (sig[i-1] == '-' || sig[i-1] == '='):
+			if depth == 0 {
+				b.WriteByte(c)
+			}
+		case c == '>' && depth > 0:
+			depth--
+		case depth > 0:
+			// Inside generic arguments
+		case c == '\'' && isLifetime(sig, i):
+			for i+1 < len(sig) && isIdentByte(sig[i+1]) {
+				i++
+			}
+			for i+1 < len(sig) && sig[i+1] == ' ' {
+				i++ // &'a str becomes &str
+			}
+		default:
+			b.WriteByte(c)
+		}
+	}
+	return b.String()
+}
+
+// isLifetime reports whether the quote at sig[i] starts a Rust lifetime such as 'a or
+// 'static rather than a quoted string or character
+func isLifetime(sig string, i int) bool {
+	j := i + 1
+	for j < len(sig) && isIdentByte(sig[j]) {
+		j++
+	}
+	return j > i+1 && (j == len(sig) || sig[j] != '\'')
+}
+
+func isIdentByte(c byte) bool {
+	return c == '_' || c >= '0' && c <= '9' || c >= 'a' && c <= 'z' || c >= 'A' && c <= 'Z'
+}
+
+func isOpeningBracket(c byte) bool {
+	return c == '(' || c == '[' || c == '<'
+}
+
+func isClosingBracket(c byte) bool {
+	return c == ')' || c == ']' || c == '>'
+}
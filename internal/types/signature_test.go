@@ -0,0 +1,25 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestNormalizeSignature(t *testing.T) {
+	tests := []struct {
+		sig, want, bare string
+	}{
+		{"(&self, id: &str) -> Result<User, Box<dyn Error>>", "(&self, id: &str) -> Result<User, Box<dyn Error>>", "(&self, id: &str) -> Result"},
+		{"(\n    &self,\n    id:  &str,\n) -> Option< User >", "(&self, id: &str) -> Option<User>", "(&self, id: &str) -> Option"},
+		{"(s: &'a str, t: &'static mut T) -> Vec<&'a str>", "(s: &'a str, t: &'static mut T) -> Vec<&'a str>", "(s: &str, t: &mut T) -> Vec"},
+		{"(f: Box<dyn Fn(u8) -> bool>) -> u8", "(f: Box<dyn Fn(u8) -> bool>) -> u8", "(f: Box) -> u8"},
+		{"(ctx context.Context, id string) (*User, error)", "(ctx context.Context, id string) (*User, error)", "(ctx context.Context, id string) (*User, error)"},
+		{"(sep='x')", "(sep='x')", "(sep='x')"},
+		{"Result<User", "Result<User", "Result"},
+	}
+	for _, tt := range tests {
+		assert.Equal(t, tt.want, NormalizeSignature(tt.sig, false), tt.sig)
+		assert.Equal(t, tt.bare, NormalizeSignature(tt.sig, true), tt.sig)
+	}
+}
@@ -2,6 +2,7 @@ package types
 
 import (
 	"encoding/json"
+	"strings"
 	"testing"
 )
 
@@ -571,3 +572,30 @@ func BenchmarkCompositeSymbolID_JSONRoundTrip(b *testing.B) {
 		_ = json.Unmarshal(data, &decoded)
 	}
 }
+
+// TestParseSymbolTypeList tests comma-separated kind parsing for --kind.
+func TestParseSymbolTypeList(t *testing.T) {
+	kinds, err := ParseSymbolTypeList("struct, trait,fn,method,field,fn")
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	want := []SymbolType{SymbolTypeStruct, SymbolTypeTrait, SymbolTypeFunction, SymbolTypeMethod, SymbolTypeField}
+	if len(kinds) != len(want) {
+		t.Fatalf("got %v, want %v", kinds, want)
+	}
+	for i := range want {
+		if kinds[i] != want[i] {
+			t.Errorf("kind %d: got %s, want %s", i, kinds[i], want[i])
+		}
+	}
+
+	_, err = ParseSymbolTypeList("trait,strcut")
+	if err == nil {
+		t.Fatal("expected an error for an unknown kind")
+	}
+	for _, part := range []string{`"strcut"`, "struct", "trait", "fn"} {
+		if !strings.Contains(err.Error(), part) {
+			t.Errorf("error %q should mention %s", err, part)
+		}
+	}
+}
@@ -2,7 +2,9 @@ package types
 
 import (
 	"fmt"
+	"slices"
 	"strings"
+	"sync/atomic"
 	"time"
 )
 
@@ -14,6 +16,11 @@ const (
 	// generated files while covering 99.9% of source files.
 	// Large files are typically binaries or generated code.
 
+	DefaultMmapThreshold = 1024 * 1024 // 1MB - files at least this large are memory-mapped while loading
+	// Rationale: A large file is checked in place and copied to the
+	// heap only once accepted, so a skipped file never takes a heap
+	// copy; below this size a plain read is cheaper than a mapping.
+
 	// Memory limits
 	DefaultMaxMemoryMB = 100 // 100MB - typical memory limit for lightweight indexing
 	// Rationale: Allows indexing to run on resource-constrained
@@ -36,9 +43,15 @@ const (
 	// Rationale: Reading first 512 bytes to detect binary files
 	// is cheaper than loading the entire file into memory.
 	// This prevents wasting memory on large binary files.
-	BinaryPreCheckBytes = 512 // Number of bytes to read for binary magic number detection
+	BinaryPreCheckBytes = 8000 // Number of bytes checked for magic numbers and NUL bytes
+	// Rationale: The same window git uses to tell binary from text;
+	// a NUL anywhere in it marks the file as binary.
 )
 
+// ArchivePathSeparator joins the path of an indexed archive to the path of an entry
+// inside it, as in corpus.tar.gz!/src/main.rs
+const ArchivePathSeparator = "!/"
+
 type FileID uint32
 type SymbolID uint64
 
@@ -137,6 +150,9 @@ type TypeParameter struct {
 	Constraint string `json:"constraint"` // e.g., "any", "comparable", "io.Reader"
 }
 
+// Symbol is an extracted declaration. StartByte/EndByte are the canonical span;
+// Line/EndLine and Column/EndColumn are 1-based with byte columns. Use LineIndex to
+// convert between the two, e.g. for UTF-16 columns.
 type Symbol struct {
 	Name           string
 	Type           SymbolType
@@ -145,6 +161,10 @@ type Symbol struct {
 	Column         int
 	EndLine        int
 	EndColumn      int
+	StartByte      int                `json:"start_byte,omitempty"` // Byte offset of the declaration start
+	EndByte        int                `json:"end_byte,omitempty"`   // Byte offset one past the declaration end
+	Parent         string             `json:"parent,omitempty"`     // Enclosing type (Rust impl/trait), empty for top-level symbols
+	Signature      string             `json:"signature,omitempty"`  // Function parameters and return type, e.g. (id: &str) -> Option<User>; see NormalizeSignature
 	Attributes     []ContextAttribute // Context-altering attributes
 	TypeParameters []TypeParameter    `json:"type_parameters,omitempty"` // Generic type parameters
 	Visibility     SymbolVisibility   `json:"visibility,omitempty"`      // Visibility/export status
@@ -248,6 +268,64 @@ func (st SymbolType) String() string {
 	}
 }
 
+// symbolTypeAliases are the short kind names accepted alongside SymbolType.String()
+var symbolTypeAliases = map[string]SymbolType{
+	"fn":    SymbolTypeFunction,
+	"func":  SymbolTypeFunction,
+	"var":   SymbolTypeVariable,
+	"const": SymbolTypeConstant,
+	"iface": SymbolTypeInterface,
+	"cls":   SymbolTypeClass,
+}
+
+// ParseSymbolType returns the SymbolType named by kind, which is either a
+// SymbolType.String() value or one of the short forms fn, func, var, const, iface, cls
+func ParseSymbolType(kind string) (SymbolType, bool) {
+	kind = strings.ToLower(strings.TrimSpace(kind))
+	if st, ok := symbolTypeAliases[kind]; ok {
+		return st, true
+	}
+	for st := SymbolTypeFunction; st <= SymbolTypeConstructor; st++ {
+		if st.String() == kind {
+			return st, true
+		}
+	}
+	return 0, false
+}
+
+// ParseSymbolTypeList parses a comma-separated kind list such as "struct,trait,fn".
+// An unknown name is an error that lists the valid ones.
+func ParseSymbolTypeList(kinds string) ([]SymbolType, error) {
+	var result []SymbolType
+	for _, kind := range strings.Split(kinds, ",") {
+		if strings.TrimSpace(kind) == "" {
+			continue
+		}
+		st, ok := ParseSymbolType(kind)
+		if !ok {
+			return nil, fmt.Errorf("unknown kind %q, valid kinds: %s", strings.TrimSpace(kind), strings.Join(SymbolTypeNames(), ", "))
+		}
+		if !slices.Contains(result, st) {
+			result = append(result, st)
+		}
+	}
+	return result, nil
+}
+
+// SymbolTypeNames returns every accepted kind name, canonical names first
+func SymbolTypeNames() []string {
+	var names []string
+	for st := SymbolTypeFunction; st <= SymbolTypeConstructor; st++ {
+		names = append(names, st.String())
+	}
+	aliases := make([]string, 0, len(symbolTypeAliases))
+	for alias := range symbolTypeAliases {
+		aliases = append(aliases, alias)
+	}
+	slices.Sort(aliases)
+	return append(names, aliases...)
+}
+
 type Import struct {
 	Path   string
 	FileID FileID
@@ -1334,27 +1412,60 @@ func (tree *FunctionTree) formatHumanModeMetadata(node *TreeNode) string {
 type SearchOptions struct {
 	// Basic search options
 	CaseInsensitive    bool
+	SmartCase          bool     // Decide CaseInsensitive from the pattern: insensitive unless it has an uppercase letter
 	MaxContextLines    int
-	MergeFileResults   bool   // Merge multiple results from same file
-	EnsureCompleteStmt bool   // Ensure complete statements with comments
-	ExcludePattern     string // Regex pattern to exclude files
-	IncludePattern     string // Regex pattern to include files (whitelist)
+	MergeFileResults   bool     // Merge multiple results from same file
+	EnsureCompleteStmt bool     // Ensure complete statements with comments
+	ExcludePattern     string   // Regex pattern to exclude files
+	IncludePattern     string   // Regex pattern to include files (whitelist)
+	IncludeGlobs       []string // Only search files matching one of these globs; see search.MatchPathGlobs
+	ExcludeGlobs       []string // Skip files matching any of these globs, even when an include glob matches
 
 	// Result control
-	MaxResults int // Optional cap for number of results to return (0 = no cap)
+	MaxResults int          // Optional cap for number of results to return (0 = no cap)
+	NoDedup    bool         // Keep hits of the same span found by several matchers separate (for debugging matchers)
+	Explain    bool         // Attach the ranker's score breakdown to each result (for debugging ranking)
+	Deadline   time.Time    // Stop before searching further files once passed, keeping the hits found so far (zero = none)
+	TimedOut   *atomic.Bool // Set when Deadline stopped the search before every file was searched, so hits may be missing (nil = not reported)
+	ResultCap  int          // Stop searching once more than this many hits are found and keep this many, bounding the work a broad pattern does (0 = no cap)
+	CapReached *atomic.Bool // Set when ResultCap dropped hits, so there are more than were returned (nil = not reported)
+	MergeGap   int          // Merge the hits of a file whose lines are at most this many apart into one hit spanning them (0 = off)
+	MergeScore string       // Score of a merged hit: search.MergeScoreMax, the best of its hits (""), or search.MergeScoreSum
 
 	// Regex support
-	UseRegex bool // Enable regex pattern matching
+	UseRegex  bool // Enable regex pattern matching
+	Multiline bool // Let . match newlines so a regex can span lines; a hit covering several lines reports its last in EndLine
+
+	// Phrase support
+	Terms         bool // Split a literal pattern into words and quoted phrases that must all appear on the matched line, and none prefixed with "-"
+	TermsInSymbol bool // Look for the other terms of a Terms search in the innermost symbol enclosing the matched line instead of on the line
+
+	// Alias expansion, e.g. cfg to config; see search.DefaultAliases
+	Aliases map[string][]string // Let each unquoted word of a Terms search also match these spellings, keyed by lower-case word (nil = off)
+
+	// Ranking
+	KindBoosts map[string]float64 // Rank with the symbol proximity ranker, weighting hits by enclosing symbol kind or "doc" for comments; see search.KindBoosts
 
 	// Semantic search filters for AI agents
 	SymbolTypes     []string // Filter by symbol types: "function", "variable", "class", "type", "constant"
 	DeclarationOnly bool     // Only show symbol definitions, not usages
+	SymbolsOnly     bool     // Match the pattern against symbol names instead of file content
+	DocsOnly        bool     // Match the pattern against symbols' leading doc comments instead of file content
+	Query           bool     // Interpret the pattern as a query such as `kind:fn AND NOT path:auth`, matched against symbols
 	UsageOnly       bool     // Only show symbol usages, not definitions
-	ExportedOnly    bool     // Only show public/exported symbols
+	ExportedOnly    bool     // Only match inside exported symbols: pub or pub(crate) in Rust, exported names in Go and Python
 	ExcludeTests    bool     // Exclude test files and test functions
 	ExcludeComments bool     // Exclude matches in comments (deprecated: use CodeOnly instead)
 	MutableOnly     bool     // Only mutable variables (var, not const)
 	GlobalOnly      bool     // Only global/package-level symbols
+	Within          string   // Only match inside this symbol's span: "name", "Parent::name" or "file:name"
+	Fuzzy           string   // Match the pattern fuzzily against symbol names with this scorer, search.FuzzyEdit or search.FuzzySubsequence ("" = off)
+
+	// Signature matching of sig: query terms
+	SignatureStripGenerics bool // Compare signatures with lifetimes and generic arguments removed, so sig:"(&str) -> Vec" matches (&'a str) -> Vec<T>
+
+	// Line restriction, e.g. the lines a diff added or modified
+	ChangedLines map[string][]LineRange // Only match on these lines, keyed by file path exactly as indexed (nil = no restriction)
 
 	// Content-specific filters (powered by AST)
 	CommentsOnly    bool // Search only in comments
@@ -1366,14 +1477,17 @@ type SearchOptions struct {
 	FullFunction     bool // Show complete function body for matches inside functions
 	MaxFunctionLines int  // Max lines for function context (0 = unlimited)
 	ContextPadding   int  // Extra lines around match when not showing full function
+	SnippetContext   int  // Lines before/after the matched line returned in ContextBefore/ContextAfter (grep -C)
+	LocationsOnly    bool // Only hit positions are wanted: results carry no context lines, and hits are never merged
 
 	// Grep-like features (P0 - Critical for LLM use cases)
-	InvertMatch     bool     // Inverted match (grep -v): show lines that DON'T match pattern
-	Patterns        []string // Multiple patterns with OR logic (grep -e pattern1 -e pattern2)
-	CountPerFile    bool     // Return match count per file (grep -c)
-	FilesOnly       bool     // Return only filenames with matches (grep -l)
-	WordBoundary    bool     // Match whole words only (grep -w)
-	MaxCountPerFile int      // Max matches per file (grep -m), 0 = unlimited
+	InvertMatch      bool     // Inverted match (grep -v): show lines that DON'T match pattern
+	Patterns         []string // Multiple patterns with OR logic (grep -e pattern1 -e pattern2)
+	CountPerFile     bool     // Return match count per file (grep -c)
+	FilesOnly        bool     // Return only filenames with matches (grep -l)
+	WordBoundary     bool     // Match whole words only (grep -w)
+	IgnoreWhitespace bool     // Let each whitespace run in a literal pattern match any run of spaces and tabs
+	MaxCountPerFile  int      // Max matches per file (grep -m), 0 = unlimited
 
 	// Additional indexing-specific option
 	Verbose bool // Show verbose output
@@ -1767,6 +1881,19 @@ func ComputeLineOffsets(content []byte) []int {
 	return offsets
 }
 
+// TrimLineEnding returns end moved back over a "\n" or "\r\n" line ending of the line
+// content[start:end], so CRLF files yield lines without a trailing carriage return.
+// A lone '\r' is not a line ending and is kept.
+func TrimLineEnding(content []byte, start, end int) int {
+	if end > start && end <= len(content) && content[end-1] == '\n' {
+		end--
+		if end > start && content[end-1] == '\r' {
+			end--
+		}
+	}
+	return end
+}
+
 // GetLineFromOffsets extracts a single line using precomputed offsets.
 // Returns the line content (without trailing newline) or empty slice if line is out of range.
 func GetLineFromOffsets(content []byte, lineOffsets []int, lineNum int) []byte {
@@ -1784,11 +1911,7 @@ func GetLineFromOffsets(content []byte, lineOffsets []int, lineNum int) []byte {
 
 	// Find end of line (next line's start, minus newline char)
 	if lineIdx+1 < len(lineOffsets) {
-		end = lineOffsets[lineIdx+1]
-		// Exclude the newline character
-		if end > start && content[end-1] == '\n' {
-			end--
-		}
+		end = TrimLineEnding(content, start, lineOffsets[lineIdx+1])
 	}
 
 	if start >= len(content) {
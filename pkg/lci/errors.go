@@ -0,0 +1,95 @@
+package lci
+
+import (
+	"errors"
+	"io/fs"
+)
+
+// ErrorKind says what kind of failure an *Error is
+type ErrorKind int
+
+const (
+	ErrorIO                   ErrorKind = iota + 1 // The root, a config file or the index file could not be read or written
+	ErrorParse                                     // A config file or an Options value is malformed
+	ErrorInvalidQuery                              // The pattern is not a valid regex, or Include or Exclude not a valid glob
+	ErrorIndexVersionMismatch                      // Options.IndexFile was written by another lci version or format
+	ErrorTimeout                                   // Query.Timeout stopped the search before every file was searched
+)
+
+func (k ErrorKind) String() string {
+	switch k {
+	case ErrorIO:
+		return "i/o error"
+	case ErrorParse:
+		return "parse error"
+	case ErrorInvalidQuery:
+		return "invalid query"
+	case ErrorIndexVersionMismatch:
+		return "index version mismatch"
+	case ErrorTimeout:
+		return "timeout"
+	}
+	return "unknown error"
+}
+
+// Error is the error Build and Index methods return. Kind lets a caller tell a bad regex
+// from a missing directory without matching on messages, either directly or through the
+// sentinels:
+//
+//	hits, err := idx.Search(lci.Query{Pattern: "(", Regex: true})
+//	if errors.Is(err, lci.ErrInvalidQuery) {
+//		...
+//	}
+//
+// Err is the underlying error, which errors.Is and errors.As see through, so
+// errors.Is(err, fs.ErrNotExist) holds for a missing root too.
+type Error struct {
+	Kind ErrorKind
+	Op   string // What failed, e.g. "failed to index /src"
+	Err  error
+}
+
+// Sentinel errors matching any *Error of their kind with errors.Is
+var (
+	ErrIO                   = &Error{Kind: ErrorIO}
+	ErrParse                = &Error{Kind: ErrorParse}
+	ErrInvalidQuery         = &Error{Kind: ErrorInvalidQuery}
+	ErrIndexVersionMismatch = &Error{Kind: ErrorIndexVersionMismatch}
+	ErrTimeout              = &Error{Kind: ErrorTimeout}
+)
+
+func (e *Error) Error() string {
+	switch {
+	case e.Err == nil:
+		return e.Kind.String()
+	case e.Op == "":
+		return e.Err.Error()
+	}
+	return e.Op + ": " + e.Err.Error()
+}
+
+// Unwrap returns the underlying error
+func (e *Error) Unwrap() error {
+	return e.Err
+}
+
+// Is implements errors.Is: an *Error matches the sentinel of its kind
+func (e *Error) Is(target error) bool {
+	t, ok := target.(*Error)
+	return ok && t.Err == nil && t.Kind == e.Kind
+}
+
+// newError wraps err as an *Error of kind
+func newError(kind ErrorKind, op string, err error) *Error {
+	return &Error{Kind: kind, Op: op, Err: err}
+}
+
+// readError wraps err from reading a file as ErrorIO, or as ErrorParse when the file was
+// read but its content is malformed
+func readError(op string, err error) *Error {
+	var pathErr *fs.PathError
+	if errors.As(err, &pathErr) {
+		return newError(ErrorIO, op, err)
+	}
+	return newError(ErrorParse, op, err)
+}
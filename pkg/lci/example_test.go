@@ -0,0 +1,45 @@
+package lci_test
+
+import (
+	"fmt"
+	"log"
+
+	"github.com/standardbeagle/lci/pkg/lci"
+)
+
+func Example() {
+	idx, err := lci.Build("../../tests/search-comparison/fixtures/rust-sample", lci.Options{})
+	if err != nil {
+		log.Fatal(err)
+	}
+	defer idx.Close()
+
+	hits, err := idx.Search(lci.Query{Pattern: "UserService", SymbolsOnly: true, Limit: 1})
+	if err != nil {
+		log.Fatal(err)
+	}
+	for _, hit := range hits {
+		fmt.Printf("%s:%d %s\n", hit.Path, hit.Line, hit.Match)
+	}
+	// Output:
+	// src/main.rs:17 UserService
+}
+
+func ExampleIndex_AllSymbols() {
+	idx, err := lci.Build("../../tests/search-comparison/fixtures/rust-sample", lci.Options{})
+	if err != nil {
+		log.Fatal(err)
+	}
+	defer idx.Close()
+
+	for sym := range idx.AllSymbols() {
+		if sym.Kind == "struct" {
+			fmt.Printf("%s:%d %s\n", sym.Path, sym.Line, sym.Name)
+		}
+	}
+	// Output:
+	// src/auth.rs:5 Token
+	// src/auth.rs:11 AuthService
+	// src/main.rs:4 User
+	// src/main.rs:17 UserService
+}
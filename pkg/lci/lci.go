@@ -0,0 +1,326 @@
+// Package lci embeds Lightning Code Index search in another program without running
+// the lci binary or its index server.
+//
+// Build indexes a directory once; the returned Index answers any number of searches
+// from memory:
+//
+//	idx, err := lci.Build("path/to/project", lci.Options{})
+//	if err != nil {
+//		return err
+//	}
+//	defer idx.Close()
+//	hits, err := idx.Search(lci.Query{Pattern: "UserService", Limit: 10})
+//
+// Build and Search run the indexer and search engine behind `lci search`, ranking hits
+// the same default way, but the CLI does not go through this package and Query covers
+// only some of its flags. SetRanker plugs in custom scoring.
+// Failures are *Error values whose Kind, or errors.Is against the Err sentinels, tells
+// them apart.
+package lci
+
+import (
+	"cmp"
+	"context"
+	"errors"
+	"fmt"
+	"iter"
+	"os"
+	"path/filepath"
+	"slices"
+	"sync/atomic"
+	"time"
+
+	"github.com/bmatcuk/doublestar/v4"
+
+	"github.com/standardbeagle/lci/internal/config"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/lang"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+)
+
+// Options overrides the index settings Build reads from the project's .lci.kdl
+type Options struct {
+	Include     []string          // Glob patterns of files to index, replacing the configured ones
+	Exclude     []string          // Glob patterns of files to skip, added to the configured ones
+	MaxFileSize int64             // Skip files larger than this many bytes, 0 = configured default
+	NoIgnore    bool              // Index files matched by .gitignore
+	Text        bool              // Index files that look binary as text instead of skipping them
+	Workers     int               // Parallel indexing workers, 0 = one per CPU
+	Languages   map[string]string // Parse files with these extensions as the given language, e.g. ".tpl": "go"
+	IndexFile   string            // Reuse the parses saved here by an earlier Build and save this one's; see ErrIndexVersionMismatch
+}
+
+// Index is an in-memory index of one directory tree. It is safe for concurrent
+// searches; Close releases it.
+type Index struct {
+	root    string
+	indexer *indexing.MasterIndex
+	engine  *search.Engine
+}
+
+// Build indexes root. Settings come from root's .lci.kdl (merged over ~/.lci.kdl) when
+// present, otherwise the lci defaults, and options override them. An Options.IndexFile
+// written by another lci version is an ErrIndexVersionMismatch error rather than
+// silently rebuilt, so the caller can decide whether to delete it.
+func Build(root string, options Options) (*Index, error) {
+	absRoot, err := filepath.Abs(root)
+	if err != nil {
+		return nil, newError(ErrorIO, fmt.Sprintf("failed to resolve root path %q", root), err)
+	}
+	if _, err := os.Stat(absRoot); err != nil {
+		return nil, newError(ErrorIO, "failed to read root", err)
+	}
+	cfg, err := config.LoadWithRoot("", absRoot)
+	if err != nil {
+		return nil, readError("failed to load config for "+absRoot, err)
+	}
+	cfg.Project.Root = absRoot
+	if len(options.Include) > 0 {
+		cfg.Include = options.Include
+	}
+	cfg.Exclude = append(cfg.Exclude, options.Exclude...)
+	if options.MaxFileSize > 0 {
+		cfg.Index.MaxFileSize = options.MaxFileSize
+	}
+	if options.NoIgnore {
+		cfg.Index.RespectGitignore = false
+	}
+	if options.Text {
+		cfg.Index.TreatAsText = true
+	}
+	if options.Workers > 0 {
+		cfg.Performance.ParallelFileWorkers = options.Workers
+	}
+	for ext, language := range options.Languages {
+		if err := lang.ValidateLanguage(language); err != nil {
+			return nil, newError(ErrorParse, "", err)
+		}
+		if cfg.Index.Languages == nil {
+			cfg.Index.Languages = make(map[string]string)
+		}
+		cfg.Index.Languages[ext] = language
+	}
+
+	if options.IndexFile != "" {
+		switch err := indexing.CheckIndexFile(options.IndexFile); {
+		case errors.Is(err, indexing.ErrIncompatibleIndexFile):
+			return nil, newError(ErrorIndexVersionMismatch, "", err)
+		case err != nil && !errors.Is(err, os.ErrNotExist):
+			return nil, newError(ErrorIO, "failed to read index file", err)
+		}
+		cfg.Index.IndexFile = options.IndexFile
+	}
+
+	indexer := indexing.NewMasterIndex(cfg)
+	if err := indexer.IndexDirectory(context.Background(), absRoot); err != nil {
+		indexer.Close()
+		return nil, newError(ErrorIO, "failed to index "+absRoot, err)
+	}
+	return &Index{root: absRoot, indexer: indexer, engine: search.NewEngine(indexer)}, nil
+}
+
+// Close releases the index. It must not be searched afterwards.
+func (idx *Index) Close() error {
+	return idx.indexer.Close()
+}
+
+// Root returns the absolute path of the indexed directory
+func (idx *Index) Root() string {
+	return idx.root
+}
+
+// FileCount returns the number of indexed files
+func (idx *Index) FileCount() int {
+	return idx.indexer.GetFileCount()
+}
+
+// SetRanker replaces the built-in scoring with r for every later search. Pass nil to
+// restore the default scoring. It may be called while searches run, which keep the
+// ranker they started with.
+func (idx *Index) SetRanker(r Ranker) {
+	idx.engine.SetRanker(r)
+}
+
+// Search runs query and returns its hits best first, skipping the best query.Offset
+// and then keeping query.Limit of them when it is set. Include and Exclude are matched
+// like `lci search --include` and --exclude: doublestar globs against the path relative
+// to the root, where a glob without a slash such as *.rs matches any name in the path.
+// An invalid regex or glob is an ErrInvalidQuery error. A search that reaches query.Timeout before searching every file
+// returns the hits found so far together with an ErrTimeout error; one that only runs
+// past it while ranking returns all its hits without error.
+func (idx *Index) Search(query Query) ([]Hit, error) {
+	for _, glob := range []string{query.Include, query.Exclude} {
+		if glob != "" && !doublestar.ValidatePattern(glob) {
+			return nil, newError(ErrorInvalidQuery, "", fmt.Errorf("invalid path glob %q", glob))
+		}
+	}
+	options := query.searchOptions()
+	var timedOut atomic.Bool
+	if query.Timeout > 0 {
+		options.Deadline = time.Now().Add(query.Timeout)
+		options.TimedOut = &timedOut
+	}
+	var results []search.GrepResult
+	if query.Regex {
+		var err error
+		if results, err = idx.engine.SearchRegex(query.Pattern, options); err != nil {
+			return nil, newError(ErrorInvalidQuery, "", err)
+		}
+	} else {
+		results = idx.engine.SearchWithOptions(query.Pattern, nil, options)
+	}
+
+	results = search.PageResults(results, query.Offset, query.Limit)
+	hits := make([]Hit, len(results))
+	for i, r := range results {
+		hits[i] = newHit(r, idx.root)
+	}
+	if timedOut.Load() {
+		return hits, newError(ErrorTimeout, "", fmt.Errorf("search stopped after %v", query.Timeout))
+	}
+	return hits, nil
+}
+
+// ParseWarnings returns the files whose parse hit syntax errors, ordered by path
+func (idx *Index) ParseWarnings() []ParseWarning {
+	var warnings []ParseWarning
+	for _, w := range idx.indexer.ParseWarnings() {
+		warnings = append(warnings, ParseWarning{Path: pathutil.ToRelative(w.Path, idx.root), Message: w.Message})
+	}
+	return warnings
+}
+
+// SkippedBinaryFiles returns how many files were skipped as binary; always 0 with
+// Options.Text
+func (idx *Index) SkippedBinaryFiles() int {
+	return idx.indexer.SkippedBinaryFiles()
+}
+
+// Symbols returns every indexed symbol, ordered by path and then by span start
+func (idx *Index) Symbols() []Symbol {
+	return slices.Collect(idx.AllSymbols())
+}
+
+// AllSymbols yields every indexed symbol, ordered by path and then by span start. Files
+// are read from the index as the sequence advances, so no list of all symbols is built
+// and breaking out of the loop skips the remaining files:
+//
+//	for sym := range idx.AllSymbols() {
+//		fmt.Printf("%s:%d %s %s\n", sym.Path, sym.Line, sym.Kind, sym.Name)
+//	}
+func (idx *Index) AllSymbols() iter.Seq[Symbol] {
+	return func(yield func(Symbol) bool) {
+		for _, file := range idx.files() {
+			if !idx.yieldSymbols(file, yield) {
+				return
+			}
+		}
+	}
+}
+
+// SymbolsIn yields the symbols of the file at path, absolute or relative to the root,
+// ordered by span start. It yields nothing for a file that is not indexed.
+func (idx *Index) SymbolsIn(path string) iter.Seq[Symbol] {
+	return func(yield func(Symbol) bool) {
+		if file, ok := idx.file(path); ok {
+			idx.yieldSymbols(file, yield)
+		}
+	}
+}
+
+// SymbolAt returns the innermost symbol whose span contains the byte offset in the
+// file at path, absolute or relative to the root, preferring a method over the type it
+// belongs to
+func (idx *Index) SymbolAt(path string, offset int) (Symbol, bool) {
+	file, ok := idx.file(path)
+	if !ok {
+		return Symbol{}, false
+	}
+	if sym := idx.indexer.GetSymbolAtOffset(file.id, offset); sym != nil {
+		return newSymbol(*sym, file.path), true
+	}
+	return Symbol{}, false
+}
+
+// PrefixSearch returns the symbols whose name starts with prefix, case-sensitively, so
+// "get" finds get_user but not save_user. Unlike fuzzy matching it looks the prefix up
+// in a trie of the symbol names instead of scoring every symbol. Symbols are ordered by
+// lowercase name and then by path and line.
+func (idx *Index) PrefixSearch(prefix string) []Symbol {
+	var symbols []Symbol
+	for _, sym := range idx.indexer.FindSymbolsByPrefix(prefix, false, 0) {
+		path := pathutil.ToRelative(idx.indexer.GetFilePath(sym.FileID), idx.root)
+		symbols = append(symbols, newSymbol(sym.Symbol, path))
+	}
+	// Symbols of one name are adjacent but in indexing order
+	for start := 0; start < len(symbols); {
+		end := start + 1
+		for end < len(symbols) && symbols[end].Name == symbols[start].Name {
+			end++
+		}
+		slices.SortFunc(symbols[start:end], func(a, b Symbol) int {
+			return cmp.Or(cmp.Compare(a.Path, b.Path), cmp.Compare(a.Line, b.Line), cmp.Compare(a.Column, b.Column))
+		})
+		start = end
+	}
+	return symbols
+}
+
+// indexedFile is an indexed file and its path relative to the root
+type indexedFile struct {
+	id   types.FileID
+	path string
+}
+
+// files returns the indexed files ordered by relative path
+func (idx *Index) files() []indexedFile {
+	var files []indexedFile
+	for _, fileID := range idx.indexer.GetAllFileIDs() {
+		files = append(files, indexedFile{fileID, pathutil.ToRelative(idx.indexer.GetFilePath(fileID), idx.root)})
+	}
+	slices.SortFunc(files, func(a, b indexedFile) int { return cmp.Compare(a.path, b.path) })
+	return files
+}
+
+// file finds the indexed file at path, absolute or relative to the root
+func (idx *Index) file(path string) (indexedFile, bool) {
+	path = pathutil.ToRelative(filepath.Clean(path), idx.root)
+	for _, fileID := range idx.indexer.GetAllFileIDs() {
+		if rel := pathutil.ToRelative(idx.indexer.GetFilePath(fileID), idx.root); rel == path {
+			return indexedFile{fileID, rel}, true
+		}
+	}
+	return indexedFile{}, false
+}
+
+// yieldSymbols yields file's symbols by span start, reporting whether the caller wants
+// more. The index's symbols are ordered through a slice of pointers to them rather than
+// copied.
+func (idx *Index) yieldSymbols(file indexedFile, yield func(Symbol) bool) bool {
+	symbols := slices.Clone(idx.indexer.GetFileEnhancedSymbols(file.id))
+	symbols = slices.DeleteFunc(symbols, func(sym *types.EnhancedSymbol) bool { return sym == nil })
+	slices.SortStableFunc(symbols, func(a, b *types.EnhancedSymbol) int {
+		return cmp.Or(cmp.Compare(a.StartByte, b.StartByte), cmp.Compare(a.Line, b.Line), cmp.Compare(a.Column, b.Column))
+	})
+	for _, sym := range symbols {
+		if !yield(newSymbol(sym.Symbol, file.path)) {
+			return false
+		}
+	}
+	return true
+}
+
+func newSymbol(sym types.Symbol, path string) Symbol {
+	return Symbol{
+		Name:      sym.Name,
+		Kind:      sym.Type.String(),
+		Parent:    sym.Parent,
+		Path:      path,
+		Line:      sym.Line,
+		Column:    sym.Column,
+		StartByte: sym.StartByte,
+		EndByte:   sym.EndByte,
+	}
+}
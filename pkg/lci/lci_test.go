@@ -0,0 +1,289 @@
+package lci_test
+
+import (
+	"encoding/json"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/pkg/lci"
+)
+
+type constantRanker struct{}
+
+func (constantRanker) Name() string { return "constant" }
+
+func (constantRanker) Score(*lci.RankQuery, *lci.RawHit) float64 { return 42 }
+
+func TestIndexSearch(t *testing.T) {
+	root := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(root, "main.go"),
+		[]byte("package main\n\nfunc getUser() {}\n\nfunc getUserID() {}\n"), 0644))
+
+	idx, err := lci.Build(root, lci.Options{Include: []string{"*.go"}})
+	require.NoError(t, err)
+	defer idx.Close()
+	assert.Equal(t, 1, idx.FileCount())
+
+	hits, err := idx.Search(lci.Query{Pattern: "getUser"})
+	require.NoError(t, err)
+	require.Len(t, hits, 2)
+	for _, hit := range hits {
+		assert.Equal(t, "main.go", hit.Path, "paths are relative to the root")
+	}
+
+	limited, err := idx.Search(lci.Query{Pattern: "getUser", Limit: 1})
+	require.NoError(t, err)
+	require.Len(t, limited, 1)
+	assert.Equal(t, hits[0], limited[0], "the limit keeps the best hit")
+
+	_, err = idx.Search(lci.Query{Pattern: "(unclosed", Regex: true})
+	assert.Error(t, err)
+
+	idx.SetRanker(constantRanker{})
+	ranked, err := idx.Search(lci.Query{Pattern: "getUser"})
+	require.NoError(t, err)
+	for _, hit := range ranked {
+		assert.Equal(t, 42.0, hit.Score)
+	}
+
+	var names []string
+	for _, sym := range idx.Symbols() {
+		names = append(names, sym.Name)
+	}
+	assert.Contains(t, names, "getUser")
+	assert.Contains(t, names, "getUserID")
+
+	data, err := json.Marshal(hits[0])
+	require.NoError(t, err)
+	var decoded lci.Hit
+	require.NoError(t, json.Unmarshal(data, &decoded))
+	assert.Equal(t, hits[0], decoded)
+}
+
+func TestQueryPathGlobs(t *testing.T) {
+	root := t.TempDir()
+	require.NoError(t, os.MkdirAll(filepath.Join(root, "src", "nested"), 0755))
+	for _, path := range []string{"main.go", "src/user.go", "src/nested/user_test.go"} {
+		require.NoError(t, os.WriteFile(filepath.Join(root, path), []byte("package main\n\nfunc getUser() {}\n"), 0644))
+	}
+	idx, err := lci.Build(root, lci.Options{})
+	require.NoError(t, err)
+	defer idx.Close()
+
+	paths := func(query lci.Query) []string {
+		t.Helper()
+		hits, err := idx.Search(query)
+		require.NoError(t, err)
+		var out []string
+		for _, hit := range hits {
+			out = append(out, hit.Path)
+		}
+		return out
+	}
+	// Globs, not regexes: the dot of *.go is literal and ** crosses directories
+	assert.ElementsMatch(t, []string{"src/user.go", "src/nested/user_test.go"}, paths(lci.Query{Pattern: "getUser", Include: "src/**"}))
+	assert.ElementsMatch(t, []string{"main.go", "src/user.go"}, paths(lci.Query{Pattern: "getUser", Exclude: "*_test.go"}))
+
+	_, err = idx.Search(lci.Query{Pattern: "getUser", Include: "src/[unclosed"})
+	assert.ErrorIs(t, err, lci.ErrInvalidQuery)
+}
+
+func TestIndexParseWarnings(t *testing.T) {
+	root := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(root, "good.go"),
+		[]byte("package main\n\nfunc healthy() {}\n"), 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(root, "broken.go"),
+		[]byte("package main\n\nfunc before() {}\n\nfunc broken( {\n\treturn brokenMarker\n}\n\nfunc after() {}\n"), 0644))
+
+	idx, err := lci.Build(root, lci.Options{})
+	require.NoError(t, err)
+	defer idx.Close()
+	assert.Equal(t, 2, idx.FileCount(), "a file with a syntax error is still indexed")
+
+	warnings := idx.ParseWarnings()
+	require.Len(t, warnings, 1)
+	assert.Equal(t, "broken.go", warnings[0].Path)
+	assert.Contains(t, warnings[0].Message, "line")
+
+	var names []string
+	for _, sym := range idx.Symbols() {
+		names = append(names, sym.Name)
+	}
+	assert.Contains(t, names, "before", "symbols that parsed cleanly are kept")
+	assert.Contains(t, names, "healthy")
+
+	hits, err := idx.Search(lci.Query{Pattern: "brokenMarker"})
+	require.NoError(t, err)
+	require.NotEmpty(t, hits, "the broken region is still searchable as text")
+	assert.Equal(t, "broken.go", hits[0].Path)
+}
+
+func TestIndexBinaryFiles(t *testing.T) {
+	root := t.TempDir()
+	files := map[string][]byte{
+		"main.go":   []byte("package main\n\nconst marker = 1\n"),
+		"build.txt": []byte("\x1b[32mok\x1b[0m\f\a marker\n"),
+		"cache.dat": append([]byte("marker"), 0x00, 0x01, 0x02),
+		"logo.png":  []byte("marker"),
+	}
+	for name, content := range files {
+		require.NoError(t, os.WriteFile(filepath.Join(root, name), content, 0644))
+	}
+
+	idx, err := lci.Build(root, lci.Options{})
+	require.NoError(t, err)
+	defer idx.Close()
+	assert.Equal(t, 2, idx.FileCount(), "control characters alone do not make a file binary")
+	assert.Equal(t, 2, idx.SkippedBinaryFiles(), "one file has a NUL byte, one a binary extension")
+
+	text, err := lci.Build(root, lci.Options{Text: true})
+	require.NoError(t, err)
+	defer text.Close()
+	assert.Equal(t, 4, text.FileCount())
+	assert.Zero(t, text.SkippedBinaryFiles())
+
+	hits, err := text.Search(lci.Query{Pattern: "marker"})
+	require.NoError(t, err)
+	var paths []string
+	for _, hit := range hits {
+		paths = append(paths, hit.Path)
+	}
+	assert.ElementsMatch(t, []string{"main.go", "build.txt", "cache.dat", "logo.png"}, paths)
+}
+
+func TestIndexSymbolAt(t *testing.T) {
+	root := t.TempDir()
+	code := "pub struct Counter {\n    n: u32,\n}\n\nimpl Counter {\n    pub fn bump(&mut self) {\n        self.n += 1;\n    }\n}\n"
+	require.NoError(t, os.MkdirAll(filepath.Join(root, "src"), 0755))
+	require.NoError(t, os.WriteFile(filepath.Join(root, "src", "lib.rs"), []byte(code), 0644))
+
+	idx, err := lci.Build(root, lci.Options{})
+	require.NoError(t, err)
+	defer idx.Close()
+
+	sym, ok := idx.SymbolAt("src/lib.rs", indexOf(t, code, "self.n += 1"))
+	require.True(t, ok)
+	assert.Equal(t, "bump", sym.Name)
+	assert.Equal(t, "Counter", sym.Parent, "the method wins over the impl around it")
+	assert.Equal(t, filepath.Join("src", "lib.rs"), sym.Path)
+	assert.Equal(t, "pub fn bump", code[sym.StartByte:sym.StartByte+len("pub fn bump")])
+
+	sym, ok = idx.SymbolAt(filepath.Join(root, "src", "lib.rs"), indexOf(t, code, "struct Counter"))
+	require.True(t, ok, "absolute paths resolve too")
+	assert.Equal(t, "Counter", sym.Name)
+	assert.Empty(t, sym.Parent)
+
+	_, ok = idx.SymbolAt("src/lib.rs", len(code)+10)
+	assert.False(t, ok)
+	_, ok = idx.SymbolAt("src/missing.rs", 0)
+	assert.False(t, ok)
+}
+
+func TestIndexSymbolIterators(t *testing.T) {
+	root := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(root, "b.go"), []byte("package b\n\nfunc Zeta() {}\n\nfunc Alpha() {}\n"), 0644))
+	require.NoError(t, os.WriteFile(filepath.Join(root, "a.go"), []byte("package a\n\nfunc Omega() {}\n"), 0644))
+
+	idx, err := lci.Build(root, lci.Options{Include: []string{"*.go"}})
+	require.NoError(t, err)
+	defer idx.Close()
+
+	var funcs []string
+	count := 0
+	for sym := range idx.AllSymbols() {
+		count++
+		if sym.Kind == "function" {
+			funcs = append(funcs, sym.Path+":"+sym.Name)
+		}
+	}
+	assert.Equal(t, []string{"a.go:Omega", "b.go:Zeta", "b.go:Alpha"}, funcs, "ordered by path, then span start")
+	assert.Equal(t, count, len(idx.Symbols()))
+
+	seen := 0
+	for range idx.AllSymbols() {
+		seen++
+		break
+	}
+	assert.Equal(t, 1, seen, "breaking out stops the iteration")
+
+	var inB []string
+	for sym := range idx.SymbolsIn(filepath.Join(root, "b.go")) {
+		if sym.Kind == "function" {
+			inB = append(inB, sym.Name)
+		}
+	}
+	assert.Equal(t, []string{"Zeta", "Alpha"}, inB)
+
+	for range idx.SymbolsIn("missing.go") {
+		t.Fatal("a file that is not indexed has no symbols")
+	}
+}
+
+func TestIndexPrefixSearch(t *testing.T) {
+	root := t.TempDir()
+	code := "fn get_user() {}\n\nfn save_user() {}\n\nfn get_account() {}\n\nfn GetAll() {}\n"
+	require.NoError(t, os.WriteFile(filepath.Join(root, "lib.rs"), []byte(code), 0644))
+
+	idx, err := lci.Build(root, lci.Options{})
+	require.NoError(t, err)
+	defer idx.Close()
+
+	var names []string
+	for _, sym := range idx.PrefixSearch("get") {
+		names = append(names, sym.Name)
+		assert.Equal(t, "lib.rs", sym.Path)
+	}
+	assert.Equal(t, []string{"get_account", "get_user"}, names, "case-sensitive, in name order")
+	assert.Empty(t, idx.PrefixSearch("user"), "only a prefix matches")
+	assert.Len(t, idx.PrefixSearch("Get"), 1)
+}
+
+func indexOf(t *testing.T, s, substr string) int {
+	t.Helper()
+	i := strings.Index(s, substr)
+	require.GreaterOrEqual(t, i, 0, "%q not found", substr)
+	return i
+}
+
+func TestErrors(t *testing.T) {
+	_, err := lci.Build(filepath.Join(t.TempDir(), "missing"), lci.Options{})
+	assert.ErrorIs(t, err, lci.ErrIO)
+	assert.ErrorIs(t, err, fs.ErrNotExist, "the underlying error is still visible")
+	assert.NotErrorIs(t, err, lci.ErrInvalidQuery)
+	var lciErr *lci.Error
+	require.ErrorAs(t, err, &lciErr)
+	assert.Equal(t, lci.ErrorIO, lciErr.Kind)
+
+	root := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(root, "main.go"), []byte("package main\n\nfunc getUser() {}\n"), 0644))
+
+	_, err = lci.Build(root, lci.Options{Languages: map[string]string{".tpl": "klingon"}})
+	assert.ErrorIs(t, err, lci.ErrParse)
+
+	garbage := filepath.Join(t.TempDir(), "lci.index")
+	require.NoError(t, os.WriteFile(garbage, []byte("not an index"), 0644))
+	_, err = lci.Build(root, lci.Options{IndexFile: garbage})
+	assert.ErrorIs(t, err, lci.ErrIndexVersionMismatch)
+
+	indexFile := filepath.Join(t.TempDir(), "lci.index")
+	idx, err := lci.Build(root, lci.Options{IndexFile: indexFile})
+	require.NoError(t, err, "a missing index file is created")
+	defer idx.Close()
+	assert.FileExists(t, indexFile)
+
+	_, err = idx.Search(lci.Query{Pattern: "(unclosed", Regex: true})
+	assert.ErrorIs(t, err, lci.ErrInvalidQuery)
+	assert.NotErrorIs(t, err, lci.ErrIO)
+
+	_, err = idx.Search(lci.Query{Pattern: "getUser", Timeout: time.Nanosecond})
+	assert.ErrorIs(t, err, lci.ErrTimeout)
+	_, err = idx.Search(lci.Query{Pattern: "getUser", Timeout: time.Minute})
+	assert.NoError(t, err)
+}
@@ -0,0 +1,126 @@
+package lci
+
+import (
+	"time"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
+	"github.com/standardbeagle/lci/pkg/pathutil"
+)
+
+// Ranker scores hits for Index.SetRanker; higher scores rank first. Implementations
+// must be safe for concurrent use.
+type Ranker = search.Ranker
+
+// RankQuery is the query a Ranker scores hits against
+type RankQuery = search.RankQuery
+
+// RawHit is a hit as a Ranker sees it, before scoring
+type RawHit = search.RawHit
+
+// Explainer is implemented by rankers that can break their score into parts, which
+// Query.Explain attaches to each Hit
+type Explainer = search.Explainer
+
+// ScoreExplanation is a hit's score broken into components
+type ScoreExplanation = searchtypes.ScoreExplanation
+
+// ScoreComponent is one additive part of a ScoreExplanation
+type ScoreComponent = searchtypes.ScoreComponent
+
+// ScoreFactor is a named input of a ScoreComponent
+type ScoreFactor = searchtypes.ScoreFactor
+
+// Query is one search. Pattern is a literal string unless Regex is set.
+type Query struct {
+	Pattern         string        `json:"pattern"`
+	Regex           bool          `json:"regex,omitempty"`
+	CaseInsensitive bool          `json:"case_insensitive,omitempty"`
+	SmartCase       bool          `json:"smart_case,omitempty"` // Ignore case unless Pattern has an uppercase letter
+	WordBoundary    bool          `json:"word_boundary,omitempty"`
+	SymbolsOnly     bool          `json:"symbols_only,omitempty"` // Match symbol names instead of file content
+	DocsOnly        bool          `json:"docs_only,omitempty"`    // Match symbols' doc comments, returning the documented symbols
+	SymbolKinds     []string      `json:"symbol_kinds,omitempty"` // e.g. "function", "struct"; empty = all kinds
+	Include         string        `json:"include,omitempty"`      // Only search paths matching this glob, e.g. "src/**/*.rs"; see Index.Search
+	Exclude         string        `json:"exclude,omitempty"`      // Skip paths matching this glob, even when Include matches
+	Offset          int           `json:"offset,omitempty"`       // Skip the best N hits, for paging
+	Limit           int           `json:"limit,omitempty"`        // Keep N hits after Offset, 0 = all
+	Explain         bool          `json:"explain,omitempty"`      // Attach the ranker's score breakdown to each Hit
+	Timeout         time.Duration `json:"timeout_ns,omitempty"`   // Stop searching further files after this long, 0 = no limit
+}
+
+func (q Query) searchOptions() types.SearchOptions {
+	options := types.SearchOptions{
+		UseRegex:        q.Regex,
+		CaseInsensitive: q.CaseInsensitive,
+		SmartCase:       q.SmartCase,
+		WordBoundary:    q.WordBoundary,
+		SymbolsOnly:     q.SymbolsOnly,
+		DocsOnly:        q.DocsOnly,
+		SymbolTypes:     q.SymbolKinds,
+		Explain:         q.Explain,
+	}
+	if q.Include != "" {
+		options.IncludeGlobs = []string{q.Include}
+	}
+	if q.Exclude != "" {
+		options.ExcludeGlobs = []string{q.Exclude}
+	}
+	return options
+}
+
+// Hit is one search match. Path is relative to the index root; Line is 1-based and
+// Column is the 0-based byte column of the match start. StartByte and EndByte are the
+// [start, end) byte span of the match in the file.
+type Hit struct {
+	Path        string            `json:"path"`
+	Line        int               `json:"line"`
+	Column      int               `json:"column"`
+	StartByte   int               `json:"start_byte"`
+	EndByte     int               `json:"end_byte"`
+	Match       string            `json:"match"`
+	Score       float64           `json:"score"`
+	Block       string            `json:"block,omitempty"`       // Name of the enclosing function or type
+	MatchedBy   []string          `json:"matched_by,omitempty"`  // Matchers that found this span
+	Explanation *ScoreExplanation `json:"explanation,omitempty"` // Score breakdown when Query.Explain is set
+}
+
+func newHit(r search.GrepResult, root string) Hit {
+	hit := Hit{
+		Path:        pathutil.ToRelative(r.Path, root),
+		Line:        r.Line,
+		Column:      r.Column,
+		StartByte:   r.StartByte,
+		EndByte:     r.EndByte,
+		Match:       r.Match,
+		Score:       r.Score,
+		Block:       r.Context.BlockName,
+		Explanation: r.Explanation,
+	}
+	for _, m := range r.MatchedBy {
+		hit.MatchedBy = append(hit.MatchedBy, string(m))
+	}
+	return hit
+}
+
+// Symbol is a declaration found while indexing. Path is relative to the index root and
+// Line and Column are 1-based.
+type Symbol struct {
+	Name      string `json:"name"`
+	Kind      string `json:"kind"`
+	Parent    string `json:"parent,omitempty"` // Enclosing type, e.g. the impl a Rust method belongs to
+	Path      string `json:"path"`
+	Line      int    `json:"line"`
+	Column    int    `json:"column"`
+	StartByte int    `json:"start_byte"` // Byte offset of the declaration start
+	EndByte   int    `json:"end_byte"`   // Byte offset one past the declaration end
+}
+
+// ParseWarning is a file whose parse recovered from a syntax error. Path is relative to
+// the index root. The file keeps the symbols that parsed cleanly and its content is
+// still searched.
+type ParseWarning struct {
+	Path    string `json:"path"`
+	Message string `json:"message"`
+}
@@ -11,6 +11,7 @@ import (
 	"strings"
 
 	"github.com/standardbeagle/lci/internal/searchtypes"
+	"github.com/standardbeagle/lci/internal/types"
 )
 
 // ToRelative converts an absolute path to relative based on a root directory.
@@ -20,6 +21,7 @@ import (
 //   - ToRelative("/home/user/project/src/main.go", "/home/user/project") → "src/main.go"
 //   - ToRelative("/other/location/file.go", "/home/user/project") → "/other/location/file.go" (outside root)
 //   - ToRelative("src/main.go", "/home/user/project") → "src/main.go" (already relative)
+//   - ToRelative("/data/corpus.tar.gz!/src/main.rs", "/data/corpus.tar.gz") → "corpus.tar.gz!/src/main.rs" (archive root)
 func ToRelative(absPath, rootDir string) string {
 	// Handle empty inputs
 	if absPath == "" || rootDir == "" {
@@ -35,6 +37,11 @@ func ToRelative(absPath, rootDir string) string {
 	absPath = filepath.Clean(absPath)
 	rootDir = filepath.Clean(rootDir)
 
+	// Entries of an archive root are shown under the archive's name
+	if entry, ok := strings.CutPrefix(absPath, rootDir+types.ArchivePathSeparator); ok {
+		return filepath.Base(rootDir) + types.ArchivePathSeparator + entry
+	}
+
 	// Try to make relative
 	relPath, err := filepath.Rel(rootDir, absPath)
 	if err != nil {
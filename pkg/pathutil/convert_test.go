@@ -52,6 +52,12 @@ func TestToRelative(t *testing.T) {
 			rootDir:  "/home/user/project",
 			expected: "/other/location/file.go", // Should return absolute if outside root
 		},
+		{
+			name:     "entry of an archive root",
+			absPath:  "/data/corpus.tar.gz!/src/main.rs",
+			rootDir:  "/data/corpus.tar.gz",
+			expected: "corpus.tar.gz!/src/main.rs",
+		},
 		{
 			name:     "empty root directory",
 			absPath:  "/home/user/project/file.go",
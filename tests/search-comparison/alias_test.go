@@ -0,0 +1,38 @@
+package searchcomparison
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestAliasExpansion measures alias expansion as its own mode: abbreviations find
+// nothing or little as written, and with expansion find the lines spelling the word
+// out. Hit counts are logged for both modes so a change to the alias table can be
+// judged.
+func TestAliasExpansion(t *testing.T) {
+	absFixtureDir, err := filepath.Abs(getFixturePath("all"))
+	require.NoError(t, err)
+	idx, _ := setupPersistentIndex(t, absFixtureDir)
+	engine := search.NewEngine(idx)
+
+	for _, tc := range []struct{ query, word string }{{"usr", "user"}, {"db", "database"}, {"usr db", "user"}} {
+		plain := engine.SearchWithOptions(tc.query, nil, types.SearchOptions{Terms: true, SmartCase: true, MaxResults: 10000})
+		expanded := engine.SearchWithOptions(tc.query, nil, types.SearchOptions{Terms: true, SmartCase: true, Aliases: search.DefaultAliases(), MaxResults: 10000})
+		t.Logf("%-8s plain %d hits, expanded %d hits", tc.query, len(plain), len(expanded))
+
+		assert.Greater(t, len(expanded), len(plain), "%q: expansion finds more", tc.query)
+		spelled := false
+		for _, r := range expanded {
+			line, _ := idx.GetFileLine(r.FileID, r.Line)
+			spelled = spelled || strings.Contains(strings.ToLower(line), tc.word)
+		}
+		assert.True(t, spelled, "%q: some hits spell out %q", tc.query, tc.word)
+	}
+}
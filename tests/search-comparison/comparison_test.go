@@ -132,7 +132,8 @@ func runLCIGrep(t *testing.T, fixtureDir, pattern string) SearchResults {
 	// Use lci grep with --root flag to specify the exact directory to index
 	// This ensures lci only indexes the fixture directory, not the entire project
 	// Binary file exclusion is handled automatically through .lci.kdl configuration loading
-	cmd := exec.Command(lciBinary, "--root", absFixtureDir, "grep", pattern)
+	// --case-sensitive opts out of smart case so lowercase patterns match grep's default
+	cmd := exec.Command(lciBinary, "--root", absFixtureDir, "grep", "--case-sensitive", pattern)
 
 	output, err := cmd.CombinedOutput()
 
@@ -0,0 +1,64 @@
+package searchcomparison
+
+import (
+	"fmt"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// fuzzyCutoff is how many of the best fuzzy hits must hold the intended symbol
+const fuzzyCutoff = 3
+
+// TestFuzzyScorers compares the fuzzy scorers on abbreviations of fixture symbols, the
+// queries people type into a fuzzy finder. A hit is relevant when its name, ignoring
+// case and underscores, is the intended one. Each scorer's best hit is logged with its
+// score components so changes to the weights can be judged.
+func TestFuzzyScorers(t *testing.T) {
+	absFixtureDir, err := filepath.Abs(getFixturePath("all"))
+	require.NoError(t, err)
+	idx, _ := setupPersistentIndex(t, absFixtureDir)
+	engine := search.NewEngine(idx)
+
+	intended := map[string]string{
+		"hur": "handleuserrequest",
+		"vt":  "validatetoken",
+		"nus": "newuserservice",
+		"cu":  "createuser",
+	}
+	found := map[string]int{}
+	for _, query := range []string{"cu", "hur", "nus", "vt"} {
+		for _, scorer := range []string{search.FuzzySubsequence, search.FuzzyEdit} {
+			results := engine.SearchWithOptions(query, nil, types.SearchOptions{Fuzzy: scorer, CaseInsensitive: true, Explain: true})
+			hit := false
+			for i, r := range results {
+				if i == fuzzyCutoff {
+					break
+				}
+				hit = hit || strings.ReplaceAll(strings.ToLower(r.Match), "_", "") == intended[query]
+			}
+			if hit {
+				found[scorer]++
+			}
+			if len(results) == 0 {
+				t.Logf("%-4s %-12s no hits", query, scorer)
+				continue
+			}
+			var components []string
+			for _, c := range results[0].Explanation.Components {
+				components = append(components, fmt.Sprintf("%s=%g", c.Name, c.Value))
+			}
+			t.Logf("%-4s %-12s top=%s score=%g (%s), intended in top %d: %v",
+				query, scorer, results[0].Match, results[0].Score, strings.Join(components, " "), fuzzyCutoff, hit)
+		}
+	}
+
+	assert.Equal(t, len(intended), found[search.FuzzySubsequence], "subsequence scoring finds every abbreviation")
+	assert.GreaterOrEqual(t, found[search.FuzzySubsequence], found[search.FuzzyEdit])
+}
@@ -0,0 +1,189 @@
+package searchcomparison
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// expectedHitsFile is the per-fixture file holding judged results, keyed by query
+const expectedHitsFile = "expected.json"
+
+// ExpectedHit is a result judged relevant for a query. File is relative to the fixture
+// directory and uses forward slashes.
+type ExpectedHit struct {
+	File string `json:"file"`
+	Line int    `json:"line"`
+}
+
+// Hit is one ranked result produced by a search mode, best first
+type Hit struct {
+	File  string
+	Line  int
+	Score float64
+}
+
+// EvalResult holds the quality metrics for one query
+type EvalResult struct {
+	Precision      float64
+	Recall         float64
+	F1             float64
+	ReciprocalRank float64 // Averaged over queries this gives mean reciprocal rank (MRR)
+	Retrieved      int     // Distinct file:line results returned
+	Matched        int     // Retrieved results that were expected
+}
+
+// loadExpectedHits reads fixtureDir/expected.json. A fixture without one has no
+// judgements and yields a nil map.
+func loadExpectedHits(fixtureDir string) (map[string][]ExpectedHit, error) {
+	data, err := os.ReadFile(filepath.Join(fixtureDir, expectedHitsFile))
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	var expected map[string][]ExpectedHit
+	if err := json.Unmarshal(data, &expected); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", filepath.Join(fixtureDir, expectedHitsFile), err)
+	}
+	return expected, nil
+}
+
+// evaluate scores ranked hits against the expected set. Duplicate file:line hits count
+// once, at their best position.
+//
+// Hits sharing a score form a tie group whose internal order is arbitrary, so the
+// reciprocal rank uses the expected rank of the first relevant hit within its group:
+// a group of n hits starting after s better ones and holding r relevant hits ranks it at
+// s + (n+1)/(r+1). Reordering tied hits therefore never changes the result.
+func evaluate(expected []ExpectedHit, actual []Hit) EvalResult {
+	relevant := make(map[string]bool, len(expected))
+	for _, e := range expected {
+		relevant[hitKey(e.File, e.Line)] = true
+	}
+
+	seen := make(map[string]bool, len(actual))
+	ranked := make([]Hit, 0, len(actual))
+	for _, h := range actual {
+		key := hitKey(h.File, h.Line)
+		if seen[key] {
+			continue
+		}
+		seen[key] = true
+		ranked = append(ranked, h)
+	}
+
+	var result EvalResult
+	result.Retrieved = len(ranked)
+	for _, h := range ranked {
+		if relevant[hitKey(h.File, h.Line)] {
+			result.Matched++
+		}
+	}
+
+	if result.Retrieved > 0 {
+		result.Precision = float64(result.Matched) / float64(result.Retrieved)
+	}
+	if len(relevant) > 0 {
+		result.Recall = float64(result.Matched) / float64(len(relevant))
+	}
+	if result.Precision+result.Recall > 0 {
+		result.F1 = 2 * result.Precision * result.Recall / (result.Precision + result.Recall)
+	}
+
+	for start := 0; start < len(ranked); {
+		end := start + 1
+		for end < len(ranked) && ranked[end].Score == ranked[start].Score {
+			end++
+		}
+
+		relevantInGroup := 0
+		for _, h := range ranked[start:end] {
+			if relevant[hitKey(h.File, h.Line)] {
+				relevantInGroup++
+			}
+		}
+		if relevantInGroup > 0 {
+			rank := float64(start) + float64(end-start+1)/float64(relevantInGroup+1)
+			result.ReciprocalRank = 1 / rank
+			break
+		}
+		start = end
+	}
+
+	return result
+}
+
+func hitKey(file string, line int) string {
+	return fmt.Sprintf("%s:%d", filepath.ToSlash(file), line)
+}
+
+func TestEvaluate(t *testing.T) {
+	expected := []ExpectedHit{{File: "auth.go", Line: 25}, {File: "main.go", Line: 10}}
+
+	t.Run("perfect ranking", func(t *testing.T) {
+		result := evaluate(expected, []Hit{
+			{File: "auth.go", Line: 25, Score: 2},
+			{File: "main.go", Line: 10, Score: 1},
+		})
+		assert.Equal(t, 1.0, result.Precision)
+		assert.Equal(t, 1.0, result.Recall)
+		assert.Equal(t, 1.0, result.F1)
+		assert.Equal(t, 1.0, result.ReciprocalRank)
+	})
+
+	t.Run("partial results", func(t *testing.T) {
+		result := evaluate(expected, []Hit{
+			{File: "auth.go", Line: 1, Score: 3},
+			{File: "auth.go", Line: 1, Score: 3}, // duplicates count once
+			{File: "main.go", Line: 10, Score: 2},
+			{File: "main.go", Line: 40, Score: 1},
+		})
+		assert.Equal(t, 3, result.Retrieved)
+		assert.Equal(t, 1, result.Matched)
+		assert.InDelta(t, 1.0/3, result.Precision, 1e-9)
+		assert.InDelta(t, 0.5, result.Recall, 1e-9)
+		assert.InDelta(t, 0.4, result.F1, 1e-9)
+		assert.InDelta(t, 0.5, result.ReciprocalRank, 1e-9)
+	})
+
+	t.Run("ties do not depend on order", func(t *testing.T) {
+		first := evaluate(expected, []Hit{
+			{File: "main.go", Line: 10, Score: 1},
+			{File: "other.go", Line: 3, Score: 1},
+			{File: "other.go", Line: 4, Score: 1},
+		})
+		last := evaluate(expected, []Hit{
+			{File: "other.go", Line: 3, Score: 1},
+			{File: "other.go", Line: 4, Score: 1},
+			{File: "main.go", Line: 10, Score: 1},
+		})
+		// One relevant hit among three tied ones is expected at rank (3+1)/(1+1) = 2
+		assert.InDelta(t, 0.5, first.ReciprocalRank, 1e-9)
+		assert.Equal(t, first, last)
+	})
+
+	t.Run("no results", func(t *testing.T) {
+		assert.Equal(t, EvalResult{}, evaluate(expected, nil))
+	})
+}
+
+func TestLoadExpectedHits(t *testing.T) {
+	expected, err := loadExpectedHits(getFixturePath("go"))
+	require.NoError(t, err)
+	require.NotEmpty(t, expected)
+	for query, hits := range expected {
+		assert.NotEmpty(t, hits, "query %q has no expected hits", query)
+	}
+
+	missing, err := loadExpectedHits(t.TempDir())
+	require.NoError(t, err)
+	assert.Nil(t, missing)
+}
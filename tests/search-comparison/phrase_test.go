@@ -0,0 +1,67 @@
+package searchcomparison
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestPhraseQuoting checks Terms searches against the Rust fixture: a quoted phrase
+// finds the error string in auth.rs as written, and the same words unquoted find it
+// too because both appear on the line, along with any other line holding both.
+func TestPhraseQuoting(t *testing.T) {
+	fixtureDir, err := filepath.Abs(getFixturePath("rust"))
+	require.NoError(t, err)
+	ipi := getOrCreateIndex(t, fixtureDir)
+
+	search := func(pattern string) []string {
+		t.Helper()
+		results, err := ipi.idx.SearchWithOptions(pattern, types.SearchOptions{Terms: true, MaxResults: 1000})
+		require.NoError(t, err, pattern)
+		var hits []string
+		for _, r := range convertGrepResults(results, ipi.projectRoot) {
+			hits = append(hits, hitKey(r.FilePath, r.Line))
+		}
+		return hits
+	}
+	literal := hitKey("src/auth.rs", 24) // return Err("invalid credentials".into());
+
+	phrase := search(`"invalid credentials"`)
+	assert.Equal(t, []string{literal}, phrase)
+
+	words := search("invalid credentials")
+	assert.Contains(t, words, literal)
+	assert.GreaterOrEqual(t, len(words), len(phrase), "every phrase hit holds both words")
+
+	assert.Empty(t, search(`"credentials invalid"`), "a phrase must match in order")
+}
+
+// TestNegatedTerms checks `create -email` against the Rust fixture: create_user's
+// signature mentions email, so it is dropped while the doc comment above it is kept,
+// and with the symbol as the unit the method's body rules it out as well.
+func TestNegatedTerms(t *testing.T) {
+	fixtureDir, err := filepath.Abs(getFixturePath("rust"))
+	require.NoError(t, err)
+	ipi := getOrCreateIndex(t, fixtureDir)
+
+	search := func(pattern string, inSymbol bool) []string {
+		t.Helper()
+		results, err := ipi.idx.SearchWithOptions(pattern, types.SearchOptions{Terms: true, TermsInSymbol: inSymbol, MaxResults: 1000})
+		require.NoError(t, err, pattern)
+		var hits []string
+		for _, r := range convertGrepResults(results, ipi.projectRoot) {
+			hits = append(hits, hitKey(r.FilePath, r.Line))
+		}
+		return hits
+	}
+	createUser := hitKey("src/main.rs", 33) // pub fn create_user(&self, username: String, email: String) ...
+
+	assert.Contains(t, search("create fn", false), createUser)
+	assert.NotContains(t, search("create fn -email", false), createUser)
+	assert.Contains(t, search("create -email", false), hitKey("src/main.rs", 32), "the doc comment does not mention email")
+	assert.NotContains(t, search("create -email", true), createUser)
+}
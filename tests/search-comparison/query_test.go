@@ -0,0 +1,70 @@
+package searchcomparison
+
+import (
+	"errors"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/query"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestCompoundQueries checks boolean queries against the fixtures by comparing each
+// compound query with the set algebra of its parts' results.
+func TestCompoundQueries(t *testing.T) {
+	fixtureDir, err := filepath.Abs(getFixturePath("all"))
+	require.NoError(t, err)
+	ipi := getOrCreateIndex(t, fixtureDir)
+
+	hits := func(q string) map[SearchResult]bool {
+		t.Helper()
+		results, err := ipi.idx.SearchWithOptions(q, types.SearchOptions{Query: true, MaxResults: 10000})
+		require.NoError(t, err, q)
+		set := make(map[SearchResult]bool)
+		for _, r := range convertGrepResults(results, ipi.projectRoot) {
+			set[r] = true
+		}
+		return set
+	}
+
+	fns, users, rust := hits("kind:fn"), hits("name:user"), hits("path:rust")
+	require.NotEmpty(t, fns)
+	require.NotEmpty(t, users)
+	require.NotEmpty(t, rust)
+
+	want := make(map[SearchResult]bool)
+	for r := range fns {
+		if users[r] && !rust[r] {
+			want[r] = true
+		}
+	}
+	got := hits("kind:fn AND name:user AND NOT path:rust")
+	require.NotEmpty(t, got, "every fixture language has user functions")
+	assert.Equal(t, want, got)
+	for r := range got {
+		assert.NotContains(t, r.FilePath, "rust")
+	}
+
+	either := make(map[SearchResult]bool)
+	for r := range fns {
+		either[r] = true
+	}
+	for r := range users {
+		either[r] = true
+	}
+	assert.Equal(t, either, hits("kind:fn OR name:user"))
+
+	assert.Equal(t, hits("kind:fn name:user"), hits("name:user AND kind:fn"), "adjacent terms are ANDed")
+	for r := range hits("(kind:class OR kind:struct) AND name:user AND lang:python") {
+		assert.True(t, strings.HasSuffix(r.FilePath, ".py"), r.FilePath)
+	}
+
+	_, err = ipi.idx.SearchWithOptions("kind:fn AND (name:user", types.SearchOptions{Query: true})
+	var qe *query.QueryError
+	require.True(t, errors.As(err, &qe), "want a QueryError, got %v", err)
+	assert.Equal(t, 12, qe.Pos)
+}
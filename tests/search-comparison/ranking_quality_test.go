@@ -0,0 +1,269 @@
+package searchcomparison
+
+import (
+	"fmt"
+	"path/filepath"
+	"sort"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/core"
+	"github.com/standardbeagle/lci/internal/indexing"
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// rankingCutoff is the k used for precision@k and recall@k
+const rankingCutoff = 10
+
+// rankingStrategy pairs a name with the ranker under test; a nil ranker is the engine's
+// built-in scoring and serves as the baseline
+type rankingStrategy struct {
+	Name   string
+	Ranker search.Ranker
+}
+
+// fixtureKindBoosts weights hits in functions and structs over hits in comments, the
+// weighting `lci search --boost fn=2.0 --boost struct=1.5 --boost doc=0.5` applies
+var fixtureKindBoosts = search.KindBoosts{"function": 2.0, "struct": 1.5, search.BoostDoc: 0.5}
+
+// rankingStrategies returns every ranker the harness compares, baseline first
+func rankingStrategies(idx *indexing.MasterIndex) []rankingStrategy {
+	return []rankingStrategy{
+		{Name: "default"},
+		{Name: "substring", Ranker: search.SubstringRanker{}},
+		{Name: "bm25", Ranker: search.NewBm25Ranker(idx.GetTermStatsIndex())},
+		{Name: "bm25-identifier", Ranker: search.NewBm25Ranker(identifierTermStats(idx))},
+		{Name: "symbol-proximity", Ranker: search.NewSymbolProximityRanker(idx)},
+		{Name: "kind-boosted", Ranker: &search.SymbolProximityRanker{Indexer: idx, Boosts: fixtureKindBoosts}},
+	}
+}
+
+// identifierTermStats gathers BM25 statistics over idx's files with identifier
+// tokenization, so bm25 and bm25-identifier compare the two tokenizers on one index
+func identifierTermStats(idx *indexing.MasterIndex) *core.TermStatsIndex {
+	stats := core.NewTermStatsIndexWithTokenizer(core.TermTokenizerIdentifier)
+	for _, fileID := range idx.GetAllFileIDs() {
+		if content, ok := idx.GetFileContent(fileID); ok {
+			stats.IndexFile(fileID, content)
+		}
+	}
+	return stats
+}
+
+// TestRankingQuality compares ranking strategies on the multi-language fixtures.
+// A hit is relevant when its line defines a symbol whose name contains the query, which
+// is what someone searching for a name usually wants to land on first.
+func TestRankingQuality(t *testing.T) {
+	absFixtureDir, err := filepath.Abs(getFixturePath("all"))
+	require.NoError(t, err)
+	idx, _ := setupPersistentIndex(t, absFixtureDir)
+
+	strategies := rankingStrategies(idx)
+	queries := []string{"user", "find_user", "database", "authenticate", "token"}
+
+	precision := make(map[string]float64)
+	recall := make(map[string]float64)
+
+	for _, query := range queries {
+		relevant := relevantDefinitions(idx, query)
+		if len(relevant) == 0 {
+			t.Logf("%-14s no relevant definitions in fixtures, skipped", query)
+			continue
+		}
+
+		for _, strategy := range strategies {
+			engine := search.NewEngine(idx)
+			engine.SetRanker(strategy.Ranker)
+			results := engine.SearchWithOptions(query, nil, types.SearchOptions{
+				CaseInsensitive: true,
+				MaxResults:      1000,
+			})
+
+			p, r := precisionRecallAtK(results, relevant, rankingCutoff)
+			assert.True(t, p >= 0 && p <= 1, "%s/%s precision out of range: %f", strategy.Name, query, p)
+			assert.True(t, r >= 0 && r <= 1, "%s/%s recall out of range: %f", strategy.Name, query, r)
+			precision[strategy.Name] += p
+			recall[strategy.Name] += r
+
+			t.Logf("%-14s %-16s P@%d=%.2f R@%d=%.2f (%d results, %d relevant)",
+				query, strategy.Name, rankingCutoff, p, rankingCutoff, r, len(results), len(relevant))
+		}
+	}
+
+	for _, strategy := range strategies {
+		t.Logf("mean over %d queries: %-16s P@%d=%.2f R@%d=%.2f", len(queries), strategy.Name,
+			rankingCutoff, precision[strategy.Name]/float64(len(queries)),
+			rankingCutoff, recall[strategy.Name]/float64(len(queries)))
+	}
+
+	// Symbol proximity ranks definitions first, so it can't do worse than plain substring
+	// scoring under a definition-based relevance judgement
+	assert.GreaterOrEqual(t, precision["symbol-proximity"], precision["substring"])
+}
+
+// relevantDefinitions returns "path:line" keys of symbol definitions whose name contains query
+func relevantDefinitions(idx *indexing.MasterIndex, query string) map[string]bool {
+	query = strings.ToLower(query)
+	relevant := make(map[string]bool)
+	for _, fileID := range idx.GetAllFileIDs() {
+		path := idx.GetFilePath(fileID)
+		for _, sym := range idx.GetFileSymbols(fileID) {
+			if strings.Contains(strings.ToLower(sym.Name), query) {
+				relevant[fmt.Sprintf("%s:%d", path, sym.Line)] = true
+			}
+		}
+	}
+	return relevant
+}
+
+// precisionRecallAtK scores the first k results against the relevant set
+func precisionRecallAtK(results []search.GrepResult, relevant map[string]bool, k int) (precision, recall float64) {
+	if len(results) > k {
+		results = results[:k]
+	}
+	if len(results) == 0 {
+		return 0, 0
+	}
+
+	hits := 0
+	seen := make(map[string]bool)
+	for _, r := range results {
+		key := fmt.Sprintf("%s:%d", r.Path, r.Line)
+		if relevant[key] && !seen[key] {
+			hits++
+		}
+		seen[key] = true
+	}
+	return float64(hits) / float64(len(results)), float64(hits) / float64(len(relevant))
+}
+
+// TestSearchQualityByFixture scores each ranker against the judged results in every
+// fixture's expected.json and prints one table row per fixture and ranker, plus an
+// overall row per ranker, so the strategies can be compared side by side.
+func TestSearchQualityByFixture(t *testing.T) {
+	languages := []string{"go", "js", "python", "rust", "cpp", "java"}
+
+	type totals struct {
+		eval    EvalResult
+		queries int
+	}
+	overall := make(map[string]*totals)
+	var order []string
+
+	var table strings.Builder
+	fmt.Fprintf(&table, "%-14s %-16s %7s %9s %7s %6s %6s\n", "fixture", "ranker", "queries", "precision", "recall", "f1", "mrr")
+
+	for _, language := range languages {
+		fixtureDir := getFixturePath(language)
+		expected, err := loadExpectedHits(fixtureDir)
+		require.NoError(t, err)
+		if len(expected) == 0 {
+			continue
+		}
+
+		absFixtureDir, err := filepath.Abs(fixtureDir)
+		require.NoError(t, err)
+		ipi := getOrCreateIndex(t, absFixtureDir)
+
+		queries := make([]string, 0, len(expected))
+		for query := range expected {
+			queries = append(queries, query)
+		}
+		sort.Strings(queries)
+
+		for _, strategy := range rankingStrategies(ipi.idx) {
+			engine := search.NewEngine(ipi.idx)
+			engine.SetRanker(strategy.Ranker)
+
+			var sum EvalResult
+			for _, query := range queries {
+				results := engine.SearchWithOptions(query, nil, types.SearchOptions{
+					CaseInsensitive: true,
+					MaxResults:      1000,
+				})
+				eval := evaluate(expected[query], toHits(results, absFixtureDir))
+				sum.Precision += eval.Precision
+				sum.Recall += eval.Recall
+				sum.F1 += eval.F1
+				sum.ReciprocalRank += eval.ReciprocalRank
+			}
+
+			mean := meanEval(sum, len(queries))
+			assertEvalInRange(t, language+"/"+strategy.Name, mean)
+			fmt.Fprintf(&table, "%-14s %-16s %7d %9.3f %7.3f %6.3f %6.3f\n", filepath.Base(fixtureDir), strategy.Name,
+				len(queries), mean.Precision, mean.Recall, mean.F1, mean.ReciprocalRank)
+
+			tot, ok := overall[strategy.Name]
+			if !ok {
+				tot = &totals{}
+				overall[strategy.Name] = tot
+				order = append(order, strategy.Name)
+			}
+			tot.eval.Precision += sum.Precision
+			tot.eval.Recall += sum.Recall
+			tot.eval.F1 += sum.F1
+			tot.eval.ReciprocalRank += sum.ReciprocalRank
+			tot.queries += len(queries)
+		}
+	}
+	require.NotEmpty(t, order, "no fixture has an %s", expectedHitsFile)
+
+	for _, name := range order {
+		tot := overall[name]
+		mean := meanEval(tot.eval, tot.queries)
+		fmt.Fprintf(&table, "%-14s %-16s %7d %9.3f %7.3f %6.3f %6.3f\n", "overall", name,
+			tot.queries, mean.Precision, mean.Recall, mean.F1, mean.ReciprocalRank)
+	}
+	t.Logf("Search quality by fixture:\n%s", table.String())
+
+	// Reranking only reorders results, so every ranker finds the same judged hits
+	baseline := meanEval(overall["default"].eval, overall["default"].queries)
+	assert.Greater(t, baseline.Recall, 0.0, "text search should find the judged definitions")
+	for _, name := range order {
+		mean := meanEval(overall[name].eval, overall[name].queries)
+		assert.InDelta(t, baseline.Recall, mean.Recall, 1e-9, "%s changed recall", name)
+	}
+}
+
+// toHits converts engine results to ranked hits with fixture-relative paths
+func toHits(results []search.GrepResult, absFixtureDir string) []Hit {
+	hits := make([]Hit, 0, len(results))
+	for _, r := range results {
+		file := r.Path
+		if rel, err := filepath.Rel(absFixtureDir, r.Path); err == nil && filepath.IsAbs(r.Path) {
+			file = rel
+		}
+		hits = append(hits, Hit{File: filepath.ToSlash(file), Line: r.Line, Score: r.Score})
+	}
+	return hits
+}
+
+// meanEval divides summed per-query metrics by the query count
+func meanEval(sum EvalResult, queries int) EvalResult {
+	if queries == 0 {
+		return EvalResult{}
+	}
+	n := float64(queries)
+	return EvalResult{
+		Precision:      sum.Precision / n,
+		Recall:         sum.Recall / n,
+		F1:             sum.F1 / n,
+		ReciprocalRank: sum.ReciprocalRank / n,
+	}
+}
+
+func assertEvalInRange(t *testing.T, label string, eval EvalResult) {
+	t.Helper()
+	for name, v := range map[string]float64{
+		"precision": eval.Precision,
+		"recall":    eval.Recall,
+		"f1":        eval.F1,
+		"mrr":       eval.ReciprocalRank,
+	} {
+		assert.True(t, v >= 0 && v <= 1, "%s %s out of range: %f", label, name, v)
+	}
+}
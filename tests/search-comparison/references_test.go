@@ -0,0 +1,32 @@
+package searchcomparison
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestFindReferencesFixture checks that refs for the Database trait method find_user
+// surfaces its call inside UserService::get_user and not the trait declaration.
+func TestFindReferencesFixture(t *testing.T) {
+	fixtureDir, err := filepath.Abs(getFixturePath("rust"))
+	require.NoError(t, err)
+	engine := search.NewEngine(getOrCreateIndex(t, fixtureDir).idx)
+
+	refs := engine.FindReferences("find_user", types.SearchOptions{}, false)
+	require.Len(t, refs, 1)
+	rel, err := filepath.Rel(fixtureDir, refs[0].Path)
+	require.NoError(t, err)
+	assert.Equal(t, filepath.Join("src", "main.rs"), rel)
+	assert.Equal(t, 29, refs[0].Line, "the call in UserService::get_user")
+
+	withDef := engine.FindReferences("find_user", types.SearchOptions{}, true)
+	require.Len(t, withDef, 2)
+	assert.True(t, withDef[0].Definition)
+	assert.Equal(t, 12, withDef[0].Line, "the Database trait declaration")
+}
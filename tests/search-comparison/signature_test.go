@@ -0,0 +1,37 @@
+package searchcomparison
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestSignatureQuery checks sig: terms on the Rust fixture find functions by return
+// type, and that stripping generics matches every Result whatever its arguments.
+func TestSignatureQuery(t *testing.T) {
+	absFixtureDir, err := filepath.Abs(getFixturePath("rust"))
+	require.NoError(t, err)
+	idx, _ := setupPersistentIndex(t, absFixtureDir)
+	engine := search.NewEngine(idx)
+
+	names := func(q string, stripGenerics bool) []string {
+		t.Helper()
+		results, err := engine.SearchQuery(q, types.SearchOptions{SignatureStripGenerics: stripGenerics})
+		require.NoError(t, err, q)
+		var out []string
+		for _, r := range results {
+			out = append(out, r.Match)
+		}
+		return out
+	}
+
+	assert.Equal(t, []string{"find_user", "get_user"}, names("sig:Result<User", false))
+	assert.Equal(t, []string{"find_user", "get_user"}, names(`sig:"(&self, id: &str)"`, false))
+	assert.Equal(t, []string{"find_user", "save_user", "get_user", "create_user"}, names(`sig:"-> Result<T>"`, true))
+	assert.Empty(t, names(`sig:"-> Result<T>"`, false))
+}
@@ -0,0 +1,69 @@
+package searchcomparison
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/parser"
+	"github.com/standardbeagle/lci/internal/semantic"
+)
+
+// TestCrossLanguageSymbolParity checks that the parallel go-sample, rust-sample and
+// python-sample fixtures yield comparable symbols once names are normalized, so
+// symbol-aware recall is not skewed toward whichever language happens to be indexed.
+func TestCrossLanguageSymbolParity(t *testing.T) {
+	fixtures := map[string]string{
+		"go-sample":     "fixtures/go-sample/main.go",
+		"rust-sample":   "fixtures/rust-sample/src/main.rs",
+		"python-sample": "fixtures/python-sample/main.py",
+	}
+
+	// Concepts all fixtures implement: name -> expected parent (normalized)
+	shared := map[string]string{
+		"user":                "",
+		"database":            "",
+		"find_user":           "database",
+		"save_user":           "database",
+		"user_service":        "",
+		"get_user":            "user_service",
+		"create_user":         "user_service",
+		"handle_user_request": "",
+	}
+
+	for fixture, file := range fixtures {
+		path, _ := filepath.Abs(file)
+		symbols := normalizedSymbols(t, path)
+		for name, parent := range shared {
+			got, ok := symbols[name]
+			assert.True(t, ok, "%s missing %s", fixture, name)
+			assert.Equal(t, parent, got, "%s parent for %s", fixture, name)
+		}
+	}
+}
+
+// normalizedSymbols parses a fixture file and returns normalized symbol name -> normalized parent.
+func normalizedSymbols(t *testing.T, path string) map[string]string {
+	t.Helper()
+
+	content, err := os.ReadFile(path)
+	if os.IsNotExist(err) {
+		t.Skipf("Fixture not found: %s", path)
+	}
+	require.NoError(t, err)
+
+	splitter := semantic.NewNameSplitter()
+	_, symbols, _ := parser.NewTreeSitterParser().ParseFile(path, content)
+
+	result := make(map[string]string, len(symbols))
+	for _, sym := range symbols {
+		name := splitter.Normalize(sym.Name)
+		if _, seen := result[name]; !seen {
+			result[name] = splitter.Normalize(sym.Parent)
+		}
+	}
+	return result
+}
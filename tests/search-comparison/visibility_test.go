@@ -0,0 +1,62 @@
+package searchcomparison
+
+import (
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/search"
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestVisibilityPub checks --visibility pub on the Rust fixture keeps the public API,
+// including methods of the pub trait and pub fn methods of the impl, and drops main.
+func TestVisibilityPub(t *testing.T) {
+	absFixtureDir, err := filepath.Abs(getFixturePath("rust"))
+	require.NoError(t, err)
+	idx, _ := setupPersistentIndex(t, absFixtureDir)
+	engine := search.NewEngine(idx)
+
+	names := func(exportedOnly bool) []string {
+		t.Helper()
+		results, err := engine.SearchRegex(".", types.SearchOptions{SymbolsOnly: true, ExportedOnly: exportedOnly, MaxResults: 1000})
+		require.NoError(t, err)
+		var out []string
+		for _, r := range results {
+			out = append(out, r.Match)
+		}
+		return out
+	}
+
+	all := names(false)
+	assert.Contains(t, all, "main", "--visibility all is unfiltered")
+
+	public := names(true)
+	for _, name := range []string{"User", "Database", "UserService", "find_user", "new", "get_user", "create_user", "handle_user_request"} {
+		assert.Contains(t, public, name)
+	}
+	assert.NotContains(t, public, "main")
+	assert.Less(t, len(public), len(all))
+}
+
+// TestExportedOnlyPython checks ExportedOnly on the Python fixture keeps public names and
+// drops those with a leading underscore, dunders included, as computeIsExported does.
+func TestExportedOnlyPython(t *testing.T) {
+	absFixtureDir, err := filepath.Abs(getFixturePath("python"))
+	require.NoError(t, err)
+	idx, _ := setupPersistentIndex(t, absFixtureDir)
+	engine := search.NewEngine(idx)
+
+	results, err := engine.SearchRegex(".", types.SearchOptions{SymbolsOnly: true, ExportedOnly: true, MaxResults: 1000})
+	require.NoError(t, err)
+	var names []string
+	for _, r := range results {
+		names = append(names, r.Match)
+	}
+	for _, name := range []string{"User", "Database", "UserService", "find_user", "authenticate", "handle_user_request"} {
+		assert.Contains(t, names, name)
+	}
+	assert.NotContains(t, names, "__init__")
+}
@@ -0,0 +1,50 @@
+package searchcomparison
+
+import (
+	"path/filepath"
+	"regexp"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+
+	"github.com/standardbeagle/lci/internal/types"
+)
+
+// TestWholeWordMatching checks -w against the fixtures: `user` must stop matching
+// identifiers such as username and user_service in both substring and regex mode,
+// with and without case folding, while still finding the standalone word.
+func TestWholeWordMatching(t *testing.T) {
+	fixtureDir, err := filepath.Abs(getFixturePath("all"))
+	require.NoError(t, err)
+	ipi := getOrCreateIndex(t, fixtureDir)
+
+	word := regexp.MustCompile(`(?i)(^|[^A-Za-z0-9_])user([^A-Za-z0-9_]|$)`)
+	tests := []struct {
+		name    string
+		pattern string
+		opts    types.SearchOptions
+	}{
+		{"substring", "user", types.SearchOptions{}},
+		{"substring ignore case", "user", types.SearchOptions{CaseInsensitive: true}},
+		{"regex", "us[e]r", types.SearchOptions{UseRegex: true}},
+		{"regex ignore case", "us[e]r", types.SearchOptions{UseRegex: true, CaseInsensitive: true}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			tt.opts.MaxResults = 1000
+			loose, err := ipi.idx.SearchWithOptions(tt.pattern, tt.opts)
+			require.NoError(t, err)
+
+			tt.opts.WordBoundary = true
+			whole, err := ipi.idx.SearchWithOptions(tt.pattern, tt.opts)
+			require.NoError(t, err)
+
+			require.NotEmpty(t, whole, "the fixtures use user as a standalone word")
+			assert.Less(t, len(whole), len(loose), "-w drops username, user_service and friends")
+			for _, r := range convertGrepResults(whole, ipi.projectRoot) {
+				assert.Regexp(t, word, r.Content, "%s:%d is not a whole-word hit", r.FilePath, r.Line)
+			}
+		})
+	}
+}